@@ -0,0 +1,171 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::{collections::HashMap, path::Path};
+
+use server_admin_proto as admin_proto;
+
+use crate::{
+    AdminClient,
+    command::{CommandDefinition, CommandRegistry, CommandResult, Result},
+    error::AdminError,
+};
+
+const DATABASE_BACKUP_USAGE: &str = "database backup <name> <directory>";
+const DATABASE_BACKUP_INCREMENTAL_USAGE: &str = "database backup-incremental <name> <directory>";
+const DATABASE_RESTORE_USAGE: &str = "database restore <name> <directory>";
+const DATABASE_STATISTICS_USAGE: &str = "database statistics <name>";
+
+pub fn register(registry: CommandRegistry) -> CommandRegistry {
+    registry
+        .register(CommandDefinition {
+            tokens: &["database", "backup"],
+            description: "Back up a database into a new directory on the server, without blocking transactions",
+            args: &["name", "directory"],
+            executor: |ctx| Box::pin(database_backup(ctx.client, ctx.args)),
+        })
+        .register(CommandDefinition {
+            tokens: &["database", "backup-incremental"],
+            description: "Add the transactions committed since the last backup to an existing backup directory",
+            args: &["name", "directory"],
+            executor: |ctx| Box::pin(database_backup_incremental(ctx.client, ctx.args)),
+        })
+        .register(CommandDefinition {
+            tokens: &["database", "restore"],
+            description: "Create a new database from a backup directory on the server",
+            args: &["name", "directory"],
+            executor: |ctx| Box::pin(database_restore(ctx.client, ctx.args)),
+        })
+        .register(CommandDefinition {
+            tokens: &["database", "statistics"],
+            description: "Show the number of instances of each type, as used by the query planner",
+            args: &["name"],
+            executor: |ctx| Box::pin(database_statistics(ctx.client, ctx.args)),
+        })
+}
+
+pub async fn execute_database_backup(
+    client: &mut AdminClient,
+    name: &str,
+    directory: &str,
+    incremental: bool,
+) -> Result<admin_proto::database_backup::Res> {
+    let response = client
+        .database_backup(admin_proto::database_backup::Req {
+            name: name.to_string(),
+            directory: directory.to_string(),
+            incremental,
+        })
+        .await?;
+    Ok(response.into_inner())
+}
+
+pub async fn execute_database_restore(client: &mut AdminClient, name: &str, directory: &str) -> Result<()> {
+    client
+        .database_restore(admin_proto::database_restore::Req {
+            name: name.to_string(),
+            directory: directory.to_string(),
+        })
+        .await?;
+    Ok(())
+}
+
+pub async fn execute_database_statistics(
+    client: &mut AdminClient,
+    name: &str,
+) -> Result<admin_proto::database_statistics::Res> {
+    let response = client.database_statistics(admin_proto::database_statistics::Req { name: name.to_string() }).await?;
+    Ok(response.into_inner())
+}
+
+// The admin endpoint only serves localhost, so the server resolves paths on this machine; relative
+// paths are made absolute here so they do not depend on the server's working directory
+fn absolute_directory(directory: &str) -> Result<String> {
+    std::path::absolute(Path::new(directory))
+        .map(|path| path.to_string_lossy().to_string())
+        .map_err(|err| AdminError::InvalidArgument { name: "directory".to_string(), reason: err.to_string() })
+}
+
+async fn database_backup(client: &mut AdminClient, args: &[String]) -> CommandResult {
+    let [name, directory] = args else {
+        return Err(AdminError::InvalidArgCount { usage: DATABASE_BACKUP_USAGE.to_string() });
+    };
+    let directory = absolute_directory(directory)?;
+    let res = execute_database_backup(client, name, &directory, false).await?;
+    println!("Backed up '{name}' at sequence number {} into '{directory}'.", res.sequence_number);
+    Ok(())
+}
+
+async fn database_backup_incremental(client: &mut AdminClient, args: &[String]) -> CommandResult {
+    let [name, directory] = args else {
+        return Err(AdminError::InvalidArgCount { usage: DATABASE_BACKUP_INCREMENTAL_USAGE.to_string() });
+    };
+    let directory = absolute_directory(directory)?;
+    let res = execute_database_backup(client, name, &directory, true).await?;
+    println!("Backed up '{name}' up to sequence number {} into '{directory}'.", res.sequence_number);
+    Ok(())
+}
+
+async fn database_restore(client: &mut AdminClient, args: &[String]) -> CommandResult {
+    let [name, directory] = args else {
+        return Err(AdminError::InvalidArgCount { usage: DATABASE_RESTORE_USAGE.to_string() });
+    };
+    let directory = absolute_directory(directory)?;
+    execute_database_restore(client, name, &directory).await?;
+    println!("Restored '{name}' from '{directory}'.");
+    Ok(())
+}
+
+async fn database_statistics(client: &mut AdminClient, args: &[String]) -> CommandResult {
+    let [name] = args else {
+        return Err(AdminError::InvalidArgCount { usage: DATABASE_STATISTICS_USAGE.to_string() });
+    };
+    let res = execute_database_statistics(client, name).await?;
+    println!("Statistics of '{name}' at sequence number {}:", res.sequence_number);
+    println!(
+        "  {} entities, {} relations, {} attributes, {} role players, {} ownerships",
+        res.total_entity_count,
+        res.total_relation_count,
+        res.total_attribute_count,
+        res.total_role_count,
+        res.total_has_count
+    );
+    print_counts("Entity types", &res.entity_counts);
+    print_counts("Relation types", &res.relation_counts);
+    print_counts("Attribute types", &res.attribute_counts);
+    print_counts("Role types", &res.role_counts);
+    print_nested_counts("Ownerships", "has", &res.has_counts);
+    print_nested_counts("Role players", "plays", &res.role_player_counts);
+    print_nested_counts("Relation roles", "relates", &res.relation_role_counts);
+    print_nested_counts("Links index", "with", &res.links_index_counts);
+    Ok(())
+}
+
+fn print_counts(title: &str, counts: &HashMap<String, u64>) {
+    if counts.is_empty() {
+        return;
+    }
+    println!("{title}:");
+    let mut counts = Vec::from_iter(counts);
+    counts.sort();
+    for (label, count) in counts {
+        println!("  {label}: {count}");
+    }
+}
+
+fn print_nested_counts(title: &str, relationship: &str, counts: &HashMap<String, admin_proto::LabelledCounts>) {
+    if counts.is_empty() {
+        return;
+    }
+    println!("{title}:");
+    let mut counts = Vec::from_iter(
+        counts.iter().flat_map(|(outer, inner)| inner.counts.iter().map(move |(label, count)| (outer, label, count))),
+    );
+    counts.sort();
+    for (outer, label, count) in counts {
+        println!("  {outer} {relationship} {label}: {count}");
+    }
+}
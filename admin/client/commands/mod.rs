@@ -4,11 +4,15 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+pub mod database;
 pub mod server;
+pub mod user;
 
 use crate::command::CommandRegistry;
 
 pub fn base_commands() -> CommandRegistry {
     let registry = CommandRegistry::new();
-    server::register(registry)
+    let registry = server::register(registry);
+    let registry = user::register(registry);
+    database::register(registry)
 }
@@ -0,0 +1,275 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use server_admin_proto as admin_proto;
+
+use crate::{
+    AdminClient,
+    command::{CommandDefinition, CommandRegistry, CommandResult, Result},
+    error::AdminError,
+};
+
+const USER_GRANTS_USAGE: &str = "user grants <username>";
+const USER_GRANT_USAGE: &str = "user grant <username> <role> [database]";
+const USER_REVOKE_USAGE: &str = "user revoke <username> <role> [database]";
+const USER_SESSIONS_USAGE: &str = "user sessions <username>";
+const USER_LOGOUT_USAGE: &str = "user logout <username> [session-id]";
+const USER_API_KEYS_USAGE: &str = "user api-keys <username>";
+const USER_API_KEY_CREATE_USAGE: &str = "user api-key-create <username> <name> [expires-in-seconds]";
+const USER_API_KEY_DELETE_USAGE: &str = "user api-key-delete <username> <name>";
+const USER_PASSWORD_EXPIRE_USAGE: &str = "user password-expire <username>";
+
+pub fn register(registry: CommandRegistry) -> CommandRegistry {
+    registry
+        .register(CommandDefinition {
+            tokens: &["user", "grants"],
+            description: "List the roles granted to a user",
+            args: &["username"],
+            executor: |ctx| Box::pin(user_grants(ctx.client, ctx.args)),
+        })
+        .register(CommandDefinition {
+            tokens: &["user", "grant"],
+            description: "Grant a role (reader, writer, schema-admin) on a database, or on all databases if omitted",
+            args: &["username", "role", "database"],
+            executor: |ctx| Box::pin(user_grant(ctx.client, ctx.args)),
+        })
+        .register(CommandDefinition {
+            tokens: &["user", "revoke"],
+            description: "Revoke a role previously granted to a user",
+            args: &["username", "role", "database"],
+            executor: |ctx| Box::pin(user_revoke(ctx.client, ctx.args)),
+        })
+        .register(CommandDefinition {
+            tokens: &["user", "sessions"],
+            description: "List the active sessions of a user",
+            args: &["username"],
+            executor: |ctx| Box::pin(user_sessions(ctx.client, ctx.args)),
+        })
+        .register(CommandDefinition {
+            tokens: &["user", "logout"],
+            description: "Revoke a session of a user, or all of their sessions if omitted",
+            args: &["username", "session-id"],
+            executor: |ctx| Box::pin(user_logout(ctx.client, ctx.args)),
+        })
+        .register(CommandDefinition {
+            tokens: &["user", "api-keys"],
+            description: "List the API keys of a user",
+            args: &["username"],
+            executor: |ctx| Box::pin(user_api_keys(ctx.client, ctx.args)),
+        })
+        .register(CommandDefinition {
+            tokens: &["user", "api-key-create"],
+            description: "Create an API key that can be used in place of the user's password, optionally expiring",
+            args: &["username", "name", "expires-in-seconds"],
+            executor: |ctx| Box::pin(user_api_key_create(ctx.client, ctx.args)),
+        })
+        .register(CommandDefinition {
+            tokens: &["user", "api-key-delete"],
+            description: "Delete an API key of a user",
+            args: &["username", "name"],
+            executor: |ctx| Box::pin(user_api_key_delete(ctx.client, ctx.args)),
+        })
+        .register(CommandDefinition {
+            tokens: &["user", "password-expire"],
+            description: "Require a user to change their password before signing in with it again",
+            args: &["username"],
+            executor: |ctx| Box::pin(user_password_expire(ctx.client, ctx.args)),
+        })
+}
+
+pub async fn execute_user_grants(client: &mut AdminClient, username: &str) -> Result<admin_proto::user_grants::Res> {
+    let response = client.user_grants(admin_proto::user_grants::Req { username: username.to_string() }).await?;
+    Ok(response.into_inner())
+}
+
+pub async fn execute_user_grant(client: &mut AdminClient, username: &str, grant: admin_proto::Grant) -> Result<()> {
+    client.user_grant(admin_proto::user_grant::Req { username: username.to_string(), grant: Some(grant) }).await?;
+    Ok(())
+}
+
+pub async fn execute_user_revoke(client: &mut AdminClient, username: &str, grant: admin_proto::Grant) -> Result<()> {
+    client.user_revoke(admin_proto::user_revoke::Req { username: username.to_string(), grant: Some(grant) }).await?;
+    Ok(())
+}
+
+pub async fn execute_user_sessions(
+    client: &mut AdminClient,
+    username: &str,
+) -> Result<admin_proto::user_sessions::Res> {
+    let response = client.user_sessions(admin_proto::user_sessions::Req { username: username.to_string() }).await?;
+    Ok(response.into_inner())
+}
+
+pub async fn execute_user_sessions_revoke(
+    client: &mut AdminClient,
+    username: &str,
+    session_id: Option<&str>,
+) -> Result<()> {
+    client
+        .user_sessions_revoke(admin_proto::user_sessions_revoke::Req {
+            username: username.to_string(),
+            session_id: session_id.map(str::to_string),
+        })
+        .await?;
+    Ok(())
+}
+
+pub async fn execute_user_api_keys(
+    client: &mut AdminClient,
+    username: &str,
+) -> Result<admin_proto::user_api_keys::Res> {
+    let response = client.user_api_keys(admin_proto::user_api_keys::Req { username: username.to_string() }).await?;
+    Ok(response.into_inner())
+}
+
+pub async fn execute_user_api_key_create(
+    client: &mut AdminClient,
+    username: &str,
+    name: &str,
+    expires_in_seconds: Option<u64>,
+) -> Result<admin_proto::user_api_key_create::Res> {
+    let response = client
+        .user_api_key_create(admin_proto::user_api_key_create::Req {
+            username: username.to_string(),
+            name: name.to_string(),
+            expires_in_seconds,
+        })
+        .await?;
+    Ok(response.into_inner())
+}
+
+pub async fn execute_user_api_key_delete(client: &mut AdminClient, username: &str, name: &str) -> Result<()> {
+    client
+        .user_api_key_delete(admin_proto::user_api_key_delete::Req {
+            username: username.to_string(),
+            name: name.to_string(),
+        })
+        .await?;
+    Ok(())
+}
+
+pub async fn execute_user_password_expire(client: &mut AdminClient, username: &str) -> Result<()> {
+    client.user_password_expire(admin_proto::user_password_expire::Req { username: username.to_string() }).await?;
+    Ok(())
+}
+
+fn parse_grant_args<'a>(args: &'a [String], usage: &str) -> Result<(&'a str, admin_proto::Grant)> {
+    match args {
+        [username, role] => Ok((username, admin_proto::Grant { role: role.clone(), database: None })),
+        [username, role, database] => {
+            Ok((username, admin_proto::Grant { role: role.clone(), database: Some(database.clone()) }))
+        }
+        _ => Err(AdminError::InvalidArgCount { usage: usage.to_string() }),
+    }
+}
+
+async fn user_grants(client: &mut AdminClient, args: &[String]) -> CommandResult {
+    let [username] = args else {
+        return Err(AdminError::InvalidArgCount { usage: USER_GRANTS_USAGE.to_string() });
+    };
+    let res = execute_user_grants(client, username).await?;
+    if res.grants.is_empty() {
+        println!("No roles granted to '{username}'.");
+    }
+    for grant in &res.grants {
+        match &grant.database {
+            Some(database) => println!("  {} on '{database}'", grant.role),
+            None => println!("  {} on all databases", grant.role),
+        }
+    }
+    Ok(())
+}
+
+async fn user_grant(client: &mut AdminClient, args: &[String]) -> CommandResult {
+    let (username, grant) = parse_grant_args(args, USER_GRANT_USAGE)?;
+    execute_user_grant(client, username, grant).await?;
+    println!("Granted.");
+    Ok(())
+}
+
+async fn user_revoke(client: &mut AdminClient, args: &[String]) -> CommandResult {
+    let (username, grant) = parse_grant_args(args, USER_REVOKE_USAGE)?;
+    execute_user_revoke(client, username, grant).await?;
+    println!("Revoked.");
+    Ok(())
+}
+
+async fn user_sessions(client: &mut AdminClient, args: &[String]) -> CommandResult {
+    let [username] = args else {
+        return Err(AdminError::InvalidArgCount { usage: USER_SESSIONS_USAGE.to_string() });
+    };
+    let res = execute_user_sessions(client, username).await?;
+    if res.sessions.is_empty() {
+        println!("No active sessions for '{username}'.");
+    }
+    for session in &res.sessions {
+        println!("  {} (issued at {}, expires at {})", session.id, session.issued_at, session.expires_at);
+    }
+    Ok(())
+}
+
+async fn user_logout(client: &mut AdminClient, args: &[String]) -> CommandResult {
+    let (username, session_id) = match args {
+        [username] => (username, None),
+        [username, session_id] => (username, Some(session_id.as_str())),
+        _ => return Err(AdminError::InvalidArgCount { usage: USER_LOGOUT_USAGE.to_string() }),
+    };
+    execute_user_sessions_revoke(client, username, session_id).await?;
+    println!("Revoked.");
+    Ok(())
+}
+
+async fn user_api_keys(client: &mut AdminClient, args: &[String]) -> CommandResult {
+    let [username] = args else {
+        return Err(AdminError::InvalidArgCount { usage: USER_API_KEYS_USAGE.to_string() });
+    };
+    let res = execute_user_api_keys(client, username).await?;
+    if res.api_keys.is_empty() {
+        println!("No API keys for '{username}'.");
+    }
+    for api_key in &res.api_keys {
+        let expires = api_key.expires_at.map_or("never".to_string(), |expires_at| expires_at.to_string());
+        let last_used = api_key.last_used_at.map_or("never".to_string(), |last_used_at| last_used_at.to_string());
+        println!("  {} (expires at {expires}, last used at {last_used})", api_key.name);
+    }
+    Ok(())
+}
+
+async fn user_api_key_create(client: &mut AdminClient, args: &[String]) -> CommandResult {
+    let (username, name, expires_in_seconds) = match args {
+        [username, name] => (username, name, None),
+        [username, name, expires_in_seconds] => {
+            let expires_in_seconds = expires_in_seconds.parse::<u64>().map_err(|_| AdminError::InvalidArgument {
+                name: "expires-in-seconds".to_string(),
+                reason: "expected a number of seconds".to_string(),
+            })?;
+            (username, name, Some(expires_in_seconds))
+        }
+        _ => return Err(AdminError::InvalidArgCount { usage: USER_API_KEY_CREATE_USAGE.to_string() }),
+    };
+    let res = execute_user_api_key_create(client, username, name, expires_in_seconds).await?;
+    println!("Created API key '{name}'. Store it now, it cannot be shown again:");
+    println!("  {}", res.key);
+    Ok(())
+}
+
+async fn user_api_key_delete(client: &mut AdminClient, args: &[String]) -> CommandResult {
+    let [username, name] = args else {
+        return Err(AdminError::InvalidArgCount { usage: USER_API_KEY_DELETE_USAGE.to_string() });
+    };
+    execute_user_api_key_delete(client, username, name).await?;
+    println!("Deleted.");
+    Ok(())
+}
+
+async fn user_password_expire(client: &mut AdminClient, args: &[String]) -> CommandResult {
+    let [username] = args else {
+        return Err(AdminError::InvalidArgCount { usage: USER_PASSWORD_EXPIRE_USAGE.to_string() });
+    };
+    execute_user_password_expire(client, username).await?;
+    println!("Password expired.");
+    Ok(())
+}
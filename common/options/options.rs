@@ -4,10 +4,14 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+use std::{collections::BTreeMap, time::SystemTime};
+
 use resource::constants::server::{
-    DEFAULT_ANSWER_COUNT_LIMIT_GRPC, DEFAULT_ANSWER_COUNT_LIMIT_HTTP, DEFAULT_INCLUDE_INSTANCE_TYPES,
-    DEFAULT_INCLUDE_STRUCTURE_GRPC, DEFAULT_INCLUDE_STRUCTURE_HTTP, DEFAULT_PREFETCH_SIZE,
-    DEFAULT_SCHEMA_LOCK_ACQUIRE_TIMEOUT_MILLIS, DEFAULT_TRANSACTION_PARALLEL, DEFAULT_TRANSACTION_TIMEOUT_MILLIS,
+    DEFAULT_ANSWER_COUNT_LIMIT_GRPC, DEFAULT_ANSWER_COUNT_LIMIT_HTTP, DEFAULT_COLLECTED_ROWS_LIMIT,
+    DEFAULT_INCLUDE_INSTANCE_TYPES, DEFAULT_INCLUDE_STRUCTURE_GRPC, DEFAULT_INCLUDE_STRUCTURE_HTTP,
+    DEFAULT_PREFETCH_SIZE, DEFAULT_QUERY_EXPLAIN, DEFAULT_QUERY_PROFILE, DEFAULT_QUERY_TIMEOUT_MILLIS,
+    DEFAULT_SCHEMA_LOCK_ACQUIRE_TIMEOUT_MILLIS, DEFAULT_TABLED_ROWS_LIMIT, DEFAULT_TRANSACTION_PARALLEL,
+    DEFAULT_TRANSACTION_TIMEOUT_MILLIS,
 };
 
 #[derive(Debug)]
@@ -15,6 +19,14 @@ pub struct TransactionOptions {
     pub parallel: bool,
     pub schema_lock_acquire_timeout_millis: u64,
     pub transaction_timeout_millis: u64,
+    pub read_point: Option<ReadPoint>,
+}
+
+/// A point in the database's history at which a read transaction is opened, instead of the latest commit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadPoint {
+    SequenceNumber(u64),
+    Timestamp(SystemTime),
 }
 
 impl Default for TransactionOptions {
@@ -23,6 +35,7 @@ impl Default for TransactionOptions {
             parallel: DEFAULT_TRANSACTION_PARALLEL,
             schema_lock_acquire_timeout_millis: DEFAULT_SCHEMA_LOCK_ACQUIRE_TIMEOUT_MILLIS,
             transaction_timeout_millis: DEFAULT_TRANSACTION_TIMEOUT_MILLIS,
+            read_point: None,
         }
     }
 }
@@ -33,6 +46,25 @@ pub struct QueryOptions {
     pub answer_count_limit: Option<usize>,
     pub prefetch_size: usize,
     pub include_query_structure: bool,
+    /// Compile the query and return the chosen plan instead of executing it. Takes precedence over `profile`.
+    pub explain: bool,
+    /// Execute the query with profiling enabled and return the collected profile alongside the answers.
+    pub profile: bool,
+    /// Fail the query once it has been executing for longer than this.
+    pub query_timeout_millis: Option<u64>,
+    /// Fail the query once a sort stage has collected more rows, or a reduce stage more groups, than this.
+    pub collected_rows_limit: Option<usize>,
+    /// Fail the query once the answer tables of its recursive function calls hold more rows than this.
+    pub tabled_rows_limit: Option<usize>,
+    /// Values bound to named variables of the query, by the variable name without its `$`.
+    pub parameters: BTreeMap<String, QueryParameter>,
+}
+
+/// A value bound to a named variable of a query, given as the name of its value type and the text of the value.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct QueryParameter {
+    pub value_type: String,
+    pub value: String,
 }
 
 impl QueryOptions {
@@ -42,6 +74,12 @@ impl QueryOptions {
             answer_count_limit: DEFAULT_ANSWER_COUNT_LIMIT_GRPC,
             prefetch_size: DEFAULT_PREFETCH_SIZE,
             include_query_structure: DEFAULT_INCLUDE_STRUCTURE_GRPC,
+            explain: DEFAULT_QUERY_EXPLAIN,
+            profile: DEFAULT_QUERY_PROFILE,
+            query_timeout_millis: DEFAULT_QUERY_TIMEOUT_MILLIS,
+            collected_rows_limit: DEFAULT_COLLECTED_ROWS_LIMIT,
+            tabled_rows_limit: DEFAULT_TABLED_ROWS_LIMIT,
+            parameters: BTreeMap::new(),
         }
     }
 
@@ -51,6 +89,12 @@ impl QueryOptions {
             answer_count_limit: DEFAULT_ANSWER_COUNT_LIMIT_HTTP,
             prefetch_size: DEFAULT_PREFETCH_SIZE,
             include_query_structure: DEFAULT_INCLUDE_STRUCTURE_HTTP,
+            explain: DEFAULT_QUERY_EXPLAIN,
+            profile: DEFAULT_QUERY_PROFILE,
+            query_timeout_millis: DEFAULT_QUERY_TIMEOUT_MILLIS,
+            collected_rows_limit: DEFAULT_COLLECTED_ROWS_LIMIT,
+            tabled_rows_limit: DEFAULT_TABLED_ROWS_LIMIT,
+            parameters: BTreeMap::new(),
         }
     }
 }
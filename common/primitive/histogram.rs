@@ -0,0 +1,123 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::{
+    array,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+// Upper bounds of the latency buckets, from 50 microseconds to 10 seconds
+pub const LATENCY_BUCKET_BOUNDS_MICROS: [u64; 16] = [
+    50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000, 25_000, 50_000, 100_000, 250_000, 500_000, 1_000_000, 2_500_000,
+    10_000_000,
+];
+
+const BUCKET_COUNT: usize = LATENCY_BUCKET_BOUNDS_MICROS.len() + 1;
+
+// Lock-free histogram of latencies that can be recorded from any thread.
+// The last bucket counts the latencies above the largest bound.
+#[derive(Debug)]
+pub struct LatencyHistogram {
+    buckets: [AtomicU64; BUCKET_COUNT],
+    sum_micros: AtomicU64,
+}
+
+impl LatencyHistogram {
+    pub fn new() -> Self {
+        Self { buckets: array::from_fn(|_| AtomicU64::new(0)), sum_micros: AtomicU64::new(0) }
+    }
+
+    pub fn record(&self, latency: Duration) {
+        let micros = latency.as_micros().min(u64::MAX as u128) as u64;
+        let bucket = LATENCY_BUCKET_BOUNDS_MICROS.partition_point(|bound| *bound < micros);
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.sum_micros.fetch_add(micros, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> LatencySnapshot {
+        LatencySnapshot {
+            bucket_counts: self.buckets.iter().map(|count| count.load(Ordering::Relaxed)).collect(),
+            sum_micros: self.sum_micros.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LatencySnapshot {
+    bucket_counts: Vec<u64>,
+    sum_micros: u64,
+}
+
+impl LatencySnapshot {
+    pub fn count(&self) -> u64 {
+        self.bucket_counts.iter().sum()
+    }
+
+    pub fn sum(&self) -> Duration {
+        Duration::from_micros(self.sum_micros)
+    }
+
+    // Cumulative counts of the latencies up to each bound, where `None` stands for an unbounded bucket
+    pub fn cumulative_buckets(&self) -> impl Iterator<Item = (Option<Duration>, u64)> + '_ {
+        let bounds = LATENCY_BUCKET_BOUNDS_MICROS.iter().map(|bound| Some(Duration::from_micros(*bound)));
+        bounds.chain([None]).zip(self.bucket_counts.iter().scan(0, |total, count| {
+            *total += count;
+            Some(*total)
+        }))
+    }
+}
+
+impl Default for LatencySnapshot {
+    fn default() -> Self {
+        Self { bucket_counts: vec![0; BUCKET_COUNT], sum_micros: 0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{LATENCY_BUCKET_BOUNDS_MICROS, LatencyHistogram};
+
+    #[test]
+    fn latencies_are_counted_in_the_first_bucket_bounding_them() {
+        let histogram = LatencyHistogram::new();
+        for micros in [0, 50, 51, 100, 10_000_000, 10_000_001] {
+            histogram.record(Duration::from_micros(micros));
+        }
+        let snapshot = histogram.snapshot();
+        let mut expected = vec![0; LATENCY_BUCKET_BOUNDS_MICROS.len() + 1];
+        expected[0] = 2;
+        expected[1] = 2;
+        expected[LATENCY_BUCKET_BOUNDS_MICROS.len() - 1] = 1;
+        expected[LATENCY_BUCKET_BOUNDS_MICROS.len()] = 1;
+        assert_eq!(snapshot.bucket_counts, expected);
+        assert_eq!(snapshot.count(), 6);
+        assert_eq!(snapshot.sum(), Duration::from_micros(20_000_202));
+    }
+
+    #[test]
+    fn cumulative_buckets_end_with_the_unbounded_bucket() {
+        let histogram = LatencyHistogram::new();
+        histogram.record(Duration::from_micros(75));
+        histogram.record(Duration::from_micros(300));
+        histogram.record(Duration::from_secs(60));
+        let buckets = histogram.snapshot().cumulative_buckets().collect::<Vec<_>>();
+        assert_eq!(buckets.len(), LATENCY_BUCKET_BOUNDS_MICROS.len() + 1);
+        assert_eq!(buckets[0], (Some(Duration::from_micros(50)), 0));
+        assert_eq!(buckets[1], (Some(Duration::from_micros(100)), 1));
+        assert_eq!(buckets[3], (Some(Duration::from_micros(500)), 2));
+        assert_eq!(buckets[LATENCY_BUCKET_BOUNDS_MICROS.len() - 1], (Some(Duration::from_secs(10)), 2));
+        assert_eq!(buckets[LATENCY_BUCKET_BOUNDS_MICROS.len()], (None, 3));
+    }
+}
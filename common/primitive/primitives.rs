@@ -7,6 +7,7 @@
 use std::ops::Bound;
 
 pub mod either;
+pub mod histogram;
 pub mod maybe_owns;
 pub mod prefix;
 
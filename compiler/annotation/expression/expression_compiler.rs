@@ -29,8 +29,14 @@ use crate::annotation::expression::{
     instructions::{
         CompilableExpression, ExpressionInstruction,
         binary::{
-            MathMaxDecimalDecimal, MathMaxDoubleDouble, MathMaxIntegerInteger, MathMinDecimalDecimal,
-            MathMinDoubleDouble, MathMinIntegerInteger,
+            ConcatString, EndsWithString, MathMaxDecimalDecimal, MathMaxDoubleDouble, MathMaxIntegerInteger,
+            MathMinDecimalDecimal, MathMinDoubleDouble, MathMinIntegerInteger, StartsWithString,
+        },
+        datetime::{
+            DayDate, DayDateTime, DayDateTimeTZ, DurationBetweenDateTime, DurationBetweenDateTimeTZ, HourDateTime,
+            HourDateTimeTZ, InTimezoneDateTimeTZ, MinuteDateTime, MinuteDateTimeTZ, MonthDate, MonthDateTime,
+            MonthDateTimeTZ, NowDateTimeTZ, SecondDateTime, SecondDateTimeTZ, TruncateDate, TruncateDateTime,
+            TruncateDateTimeTZ, YearDate, YearDateTime, YearDateTimeTZ,
         },
         list_operations,
         load_cast::{
@@ -39,13 +45,16 @@ use crate::annotation::expression::{
         },
         op_codes::ExpressionOpCode,
         operators,
+        ternary::{RegexReplaceString, SubstringString},
         unary::{
-            LenString, MathAbsDecimal, MathAbsDouble, MathAbsInteger, MathCeilDecimal, MathCeilDouble,
-            MathFloorDecimal, MathFloorDouble, MathRoundDecimal, MathRoundDouble,
+            LenString, LowerString, MathAbsDecimal, MathAbsDouble, MathAbsInteger, MathCeilDecimal, MathCeilDouble,
+            MathFloorDecimal, MathFloorDouble, MathRoundDecimal, MathRoundDouble, TrimString, UpperString,
         },
     },
 };
 
+type AppendInstruction = fn(&mut ExpressionCompilationContext<'_>) -> Result<(), Box<ExpressionCompileError>>;
+
 pub struct ExpressionCompilationContext<'this> {
     expression_tree: &'this ExpressionTree<Variable>,
     variable_value_categories: &'this HashMap<Variable, ExpressionValueType>,
@@ -644,6 +653,193 @@ impl<'this> ExpressionCompilationContext<'this> {
                     })?,
                 }
             }
+            BuiltinValueFunctionID::Concat => {
+                self.compile_builtin_arguments(builtin, &[ValueTypeCategory::String, ValueTypeCategory::String])?;
+                ConcatString::validate_and_append(self)?
+            }
+            BuiltinValueFunctionID::Substring => {
+                self.compile_builtin_arguments(
+                    builtin,
+                    &[ValueTypeCategory::String, ValueTypeCategory::Integer, ValueTypeCategory::Integer],
+                )?;
+                SubstringString::validate_and_append(self)?
+            }
+            BuiltinValueFunctionID::Upper => {
+                self.compile_builtin_arguments(builtin, &[ValueTypeCategory::String])?;
+                UpperString::validate_and_append(self)?
+            }
+            BuiltinValueFunctionID::Lower => {
+                self.compile_builtin_arguments(builtin, &[ValueTypeCategory::String])?;
+                LowerString::validate_and_append(self)?
+            }
+            BuiltinValueFunctionID::Trim => {
+                self.compile_builtin_arguments(builtin, &[ValueTypeCategory::String])?;
+                TrimString::validate_and_append(self)?
+            }
+            BuiltinValueFunctionID::StartsWith => {
+                self.compile_builtin_arguments(builtin, &[ValueTypeCategory::String, ValueTypeCategory::String])?;
+                StartsWithString::validate_and_append(self)?
+            }
+            BuiltinValueFunctionID::EndsWith => {
+                self.compile_builtin_arguments(builtin, &[ValueTypeCategory::String, ValueTypeCategory::String])?;
+                EndsWithString::validate_and_append(self)?
+            }
+            BuiltinValueFunctionID::RegexReplace => {
+                self.compile_builtin_arguments(
+                    builtin,
+                    &[ValueTypeCategory::String, ValueTypeCategory::String, ValueTypeCategory::String],
+                )?;
+                RegexReplaceString::validate_and_append(self)?
+            }
+            BuiltinValueFunctionID::Split => {
+                self.compile_builtin_arguments(builtin, &[ValueTypeCategory::String, ValueTypeCategory::String])?;
+                self.pop_type_single()?;
+                self.pop_type_single()?;
+                self.append_instruction(list_operations::SplitString::OP_CODE);
+                self.push_type_list(ValueType::String);
+            }
+            BuiltinValueFunctionID::Year => self.compile_temporal_builtin(
+                builtin,
+                &[],
+                Some(YearDate::validate_and_append),
+                Some(YearDateTime::validate_and_append),
+                Some(YearDateTimeTZ::validate_and_append),
+            )?,
+            BuiltinValueFunctionID::Month => self.compile_temporal_builtin(
+                builtin,
+                &[],
+                Some(MonthDate::validate_and_append),
+                Some(MonthDateTime::validate_and_append),
+                Some(MonthDateTimeTZ::validate_and_append),
+            )?,
+            BuiltinValueFunctionID::Day => self.compile_temporal_builtin(
+                builtin,
+                &[],
+                Some(DayDate::validate_and_append),
+                Some(DayDateTime::validate_and_append),
+                Some(DayDateTimeTZ::validate_and_append),
+            )?,
+            BuiltinValueFunctionID::Hour => self.compile_temporal_builtin(
+                builtin,
+                &[],
+                None,
+                Some(HourDateTime::validate_and_append),
+                Some(HourDateTimeTZ::validate_and_append),
+            )?,
+            BuiltinValueFunctionID::Minute => self.compile_temporal_builtin(
+                builtin,
+                &[],
+                None,
+                Some(MinuteDateTime::validate_and_append),
+                Some(MinuteDateTimeTZ::validate_and_append),
+            )?,
+            BuiltinValueFunctionID::Second => self.compile_temporal_builtin(
+                builtin,
+                &[],
+                None,
+                Some(SecondDateTime::validate_and_append),
+                Some(SecondDateTimeTZ::validate_and_append),
+            )?,
+            BuiltinValueFunctionID::Truncate => self.compile_temporal_builtin(
+                builtin,
+                &[ValueTypeCategory::String],
+                Some(TruncateDate::validate_and_append),
+                Some(TruncateDateTime::validate_and_append),
+                Some(TruncateDateTimeTZ::validate_and_append),
+            )?,
+            BuiltinValueFunctionID::Now => {
+                self.append_instruction(NowDateTimeTZ::OP_CODE);
+                self.push_type_single(ValueType::DateTimeTZ);
+            }
+            BuiltinValueFunctionID::DurationBetween => {
+                self.compile_recursive(self.expression_tree.get(builtin.argument_expression_ids()[0]))?;
+                let arg_1_category = self.peek_type_single()?.category();
+                self.compile_recursive(self.expression_tree.get(builtin.argument_expression_ids()[1]))?;
+                let arg_2_category = self.peek_type_single()?.category();
+                // Both arguments must have the same type category
+                if arg_1_category != arg_2_category {
+                    return Err(Box::new(ExpressionCompileError::UnsupportedDifferentArgumentForBuiltin {
+                        function: builtin.function_id(),
+                        arg_1_category,
+                        arg_2_category,
+                        source_span: builtin.source_span(),
+                    }));
+                }
+                match arg_1_category {
+                    ValueTypeCategory::DateTime => DurationBetweenDateTime::validate_and_append(self)?,
+                    ValueTypeCategory::DateTimeTZ => DurationBetweenDateTimeTZ::validate_and_append(self)?,
+                    _ => Err(ExpressionCompileError::UnsupportedArgumentsForBuiltin {
+                        function: builtin.function_id(),
+                        category: arg_1_category,
+                        source_span: builtin.source_span(),
+                    })?,
+                }
+            }
+            BuiltinValueFunctionID::InTimezone => self.compile_temporal_builtin(
+                builtin,
+                &[ValueTypeCategory::String],
+                None,
+                None,
+                Some(InTimezoneDateTimeTZ::validate_and_append),
+            )?,
+        }
+        Ok(())
+    }
+
+    /// Compiles a built-in function whose first argument is a date, datetime or datetime-tz, choosing the instruction
+    /// by the category of that argument
+    fn compile_temporal_builtin(
+        &mut self,
+        builtin: &BuiltinValueFunctionCall,
+        other_argument_categories: &[ValueTypeCategory],
+        date: Option<AppendInstruction>,
+        datetime: Option<AppendInstruction>,
+        datetime_tz: Option<AppendInstruction>,
+    ) -> Result<(), Box<ExpressionCompileError>> {
+        self.compile_recursive(self.expression_tree.get(builtin.argument_expression_ids()[0]))?;
+        let temporal_category = self.peek_type_single()?.category();
+        let append_instruction = match temporal_category {
+            ValueTypeCategory::Date => date,
+            ValueTypeCategory::DateTime => datetime,
+            ValueTypeCategory::DateTimeTZ => datetime_tz,
+            _ => None,
+        };
+        let Some(append_instruction) = append_instruction else {
+            return Err(Box::new(ExpressionCompileError::UnsupportedArgumentsForBuiltin {
+                function: builtin.function_id(),
+                category: temporal_category,
+                source_span: builtin.source_span(),
+            }));
+        };
+        for (argument_id, expected) in builtin.argument_expression_ids()[1..].iter().zip(other_argument_categories) {
+            self.compile_recursive(self.expression_tree.get(*argument_id))?;
+            let category = self.peek_type_single()?.category();
+            if category != *expected {
+                return Err(Box::new(ExpressionCompileError::UnsupportedArgumentsForBuiltin {
+                    function: builtin.function_id(),
+                    category,
+                    source_span: builtin.source_span(),
+                }));
+            }
+        }
+        append_instruction(self)
+    }
+
+    fn compile_builtin_arguments(
+        &mut self,
+        builtin: &BuiltinValueFunctionCall,
+        categories: &[ValueTypeCategory],
+    ) -> Result<(), Box<ExpressionCompileError>> {
+        for (argument_id, expected) in builtin.argument_expression_ids().iter().zip(categories) {
+            self.compile_recursive(self.expression_tree.get(*argument_id))?;
+            let category = self.peek_type_single()?.category();
+            if category != *expected {
+                return Err(Box::new(ExpressionCompileError::UnsupportedArgumentsForBuiltin {
+                    function: builtin.function_id(),
+                    category,
+                    source_span: builtin.source_span(),
+                }));
+            }
         }
         Ok(())
     }
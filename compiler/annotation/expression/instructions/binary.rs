@@ -3,7 +3,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
-use std::{cmp, marker::PhantomData, ops::Rem};
+use std::{borrow::Cow, cmp, marker::PhantomData, ops::Rem};
 
 use encoding::value::{decimal_value::Decimal, value::NativeValueConvertible, value_type::ValueTypeCategory};
 
@@ -105,4 +105,8 @@ binary_instruction! { 'a
     MathMaxIntegerInteger = MathMaxIntegerIntegerImpl(a1: i64, a2: i64) -> i64 { Ok(cmp::max(a1, a2)) }
     MathMaxDoubleDouble = MathMaxDoubleDoubleImpl(a1: f64, a2: f64) -> f64 { Ok(f64::max(a1, a2)) }
     MathMaxDecimalDecimal = MathMaxDecimalDecimalImpl(a1: Decimal, a2: Decimal) -> Decimal { Ok(cmp::max(a1, a2)) }
+
+    ConcatString = ConcatStringImpl(a1: Cow<'a, str>, a2: Cow<'a, str>) -> Cow<'a, str> { Ok(Cow::Owned(format!("{a1}{a2}"))) }
+    StartsWithString = StartsWithStringImpl(a1: Cow<'a, str>, a2: Cow<'a, str>) -> bool { Ok(a1.starts_with(a2.as_ref())) }
+    EndsWithString = EndsWithStringImpl(a1: Cow<'a, str>, a2: Cow<'a, str>) -> bool { Ok(a1.ends_with(a2.as_ref())) }
 }
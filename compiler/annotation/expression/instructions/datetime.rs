@@ -0,0 +1,141 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::{borrow::Cow, fmt, str::FromStr};
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeZone as _, Timelike};
+use encoding::value::{duration_value::Duration, timezone::TimeZone};
+
+use crate::annotation::expression::instructions::{
+    ExpressionEvaluationError, ExpressionInstruction,
+    binary::{Binary, BinaryExpression, binary_instruction},
+    check_operation,
+    op_codes::ExpressionOpCode,
+    unary::{Unary, UnaryExpression, unary_instruction},
+};
+
+/// Loads the current time in UTC, read each time the expression is evaluated
+pub struct NowDateTimeTZ {}
+
+impl ExpressionInstruction for NowDateTimeTZ {
+    const OP_CODE: ExpressionOpCode = ExpressionOpCode::NowDateTimeTZ;
+}
+
+unary_instruction! { 'a
+    YearDate = YearDateImpl(a1: NaiveDate) -> i64 { Ok(a1.year() as i64) }
+    YearDateTime = YearDateTimeImpl(a1: NaiveDateTime) -> i64 { Ok(a1.year() as i64) }
+    YearDateTimeTZ = YearDateTimeTZImpl(a1: DateTime<TimeZone>) -> i64 { Ok(a1.year() as i64) }
+
+    MonthDate = MonthDateImpl(a1: NaiveDate) -> i64 { Ok(a1.month() as i64) }
+    MonthDateTime = MonthDateTimeImpl(a1: NaiveDateTime) -> i64 { Ok(a1.month() as i64) }
+    MonthDateTimeTZ = MonthDateTimeTZImpl(a1: DateTime<TimeZone>) -> i64 { Ok(a1.month() as i64) }
+
+    DayDate = DayDateImpl(a1: NaiveDate) -> i64 { Ok(a1.day() as i64) }
+    DayDateTime = DayDateTimeImpl(a1: NaiveDateTime) -> i64 { Ok(a1.day() as i64) }
+    DayDateTimeTZ = DayDateTimeTZImpl(a1: DateTime<TimeZone>) -> i64 { Ok(a1.day() as i64) }
+
+    HourDateTime = HourDateTimeImpl(a1: NaiveDateTime) -> i64 { Ok(a1.hour() as i64) }
+    HourDateTimeTZ = HourDateTimeTZImpl(a1: DateTime<TimeZone>) -> i64 { Ok(a1.hour() as i64) }
+
+    MinuteDateTime = MinuteDateTimeImpl(a1: NaiveDateTime) -> i64 { Ok(a1.minute() as i64) }
+    MinuteDateTimeTZ = MinuteDateTimeTZImpl(a1: DateTime<TimeZone>) -> i64 { Ok(a1.minute() as i64) }
+
+    SecondDateTime = SecondDateTimeImpl(a1: NaiveDateTime) -> i64 { Ok(a1.second() as i64) }
+    SecondDateTimeTZ = SecondDateTimeTZImpl(a1: DateTime<TimeZone>) -> i64 { Ok(a1.second() as i64) }
+}
+
+binary_instruction! { 'a
+    TruncateDate = TruncateDateImpl(a1: NaiveDate, a2: Cow<'a, str>) -> NaiveDate {
+        // a date has no time of day, so truncating it to hours, minutes or seconds leaves it unchanged
+        Ok(truncate_date(a1, a2.parse()?))
+    }
+    TruncateDateTime = TruncateDateTimeImpl(a1: NaiveDateTime, a2: Cow<'a, str>) -> NaiveDateTime {
+        Ok(truncate_datetime(a1, a2.parse()?))
+    }
+    TruncateDateTimeTZ = TruncateDateTimeTZImpl(a1: DateTime<TimeZone>, a2: Cow<'a, str>) -> DateTime<TimeZone> {
+        // truncation applies to the local time, which may fall into a gap or a repeat around a change of offset
+        let truncated = truncate_datetime(a1.naive_local(), a2.parse()?);
+        check_operation(a1.timezone().from_local_datetime(&truncated).earliest(), "truncate")
+    }
+
+    DurationBetweenDateTime = DurationBetweenDateTimeImpl(a1: NaiveDateTime, a2: NaiveDateTime) -> Duration {
+        elapsed_duration(a1.and_utc(), a2.and_utc())
+    }
+    DurationBetweenDateTimeTZ = DurationBetweenDateTimeTZImpl(a1: DateTime<TimeZone>, a2: DateTime<TimeZone>) -> Duration {
+        elapsed_duration(a1, a2)
+    }
+
+    InTimezoneDateTimeTZ = InTimezoneDateTimeTZImpl(a1: DateTime<TimeZone>, a2: Cow<'a, str>) -> DateTime<TimeZone> {
+        let timezone = TimeZone::from_str(&a2)
+            .map_err(|_| ExpressionEvaluationError::InvalidTimezone { timezone: a2.clone().into_owned() })?;
+        Ok(a1.with_timezone(&timezone))
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+enum TimeUnit {
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+}
+
+impl FromStr for TimeUnit {
+    type Err = ExpressionEvaluationError;
+
+    fn from_str(unit: &str) -> Result<Self, Self::Err> {
+        match unit.to_ascii_lowercase().as_str() {
+            "year" => Ok(Self::Year),
+            "month" => Ok(Self::Month),
+            "day" => Ok(Self::Day),
+            "hour" => Ok(Self::Hour),
+            "minute" => Ok(Self::Minute),
+            "second" => Ok(Self::Second),
+            _ => Err(ExpressionEvaluationError::InvalidTimeUnit { unit: unit.to_owned() }),
+        }
+    }
+}
+
+fn truncate_date(date: NaiveDate, unit: TimeUnit) -> NaiveDate {
+    match unit {
+        TimeUnit::Year => date.with_day(1).and_then(|date| date.with_month(1)),
+        TimeUnit::Month => date.with_day(1),
+        TimeUnit::Day | TimeUnit::Hour | TimeUnit::Minute | TimeUnit::Second => Some(date),
+    }
+    .expect("the first day of a month or year is always a valid date")
+}
+
+fn truncate_datetime(datetime: NaiveDateTime, unit: TimeUnit) -> NaiveDateTime {
+    let time = datetime.time();
+    let truncated_time = match unit {
+        TimeUnit::Year | TimeUnit::Month | TimeUnit::Day => Some(NaiveTime::MIN),
+        TimeUnit::Hour => NaiveTime::from_hms_opt(time.hour(), 0, 0),
+        TimeUnit::Minute => NaiveTime::from_hms_opt(time.hour(), time.minute(), 0),
+        TimeUnit::Second => NaiveTime::from_hms_opt(time.hour(), time.minute(), time.second()),
+    }
+    .expect("truncating a valid time is always a valid time");
+    truncate_date(datetime.date(), unit).and_time(truncated_time)
+}
+
+/// Unlike subtraction, which counts calendar months and days, measures the exact time elapsed
+fn elapsed_duration<Tz: chrono::TimeZone>(
+    earlier: DateTime<Tz>,
+    later: DateTime<Tz>,
+) -> Result<Duration, ExpressionEvaluationError>
+where
+    Tz::Offset: fmt::Display,
+{
+    if later < earlier {
+        return Err(ExpressionEvaluationError::NegativeDatetimeSub {
+            lhs: later.to_string(),
+            rhs: earlier.to_string(),
+        });
+    }
+    let nanos = check_operation((later - earlier).num_nanoseconds(), "duration_between")?;
+    Ok(Duration::nanos(nanos as u64))
+}
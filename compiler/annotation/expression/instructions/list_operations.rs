@@ -8,6 +8,7 @@ use crate::annotation::expression::instructions::{ExpressionInstruction, op_code
 pub struct ListConstructor {}
 pub struct ListIndex {}
 pub struct ListIndexRange {}
+pub struct SplitString {}
 
 impl ExpressionInstruction for ListConstructor {
     const OP_CODE: ExpressionOpCode = ExpressionOpCode::ListConstructor;
@@ -20,3 +21,7 @@ impl ExpressionInstruction for ListIndex {
 impl ExpressionInstruction for ListIndexRange {
     const OP_CODE: ExpressionOpCode = ExpressionOpCode::ListIndexRange;
 }
+
+impl ExpressionInstruction for SplitString {
+    const OP_CODE: ExpressionOpCode = ExpressionOpCode::SplitString;
+}
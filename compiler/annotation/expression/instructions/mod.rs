@@ -13,10 +13,12 @@ use crate::annotation::expression::{
 };
 
 pub mod binary;
+pub mod datetime;
 pub mod list_operations;
 pub mod load_cast;
 pub mod op_codes;
 pub mod operators;
+pub mod ternary;
 pub mod unary;
 
 pub trait ExpressionInstruction: Sized {
@@ -50,5 +52,9 @@ typedb_error! {
         ListRangeOutOfRange(7, "List range out of range {from_index}..{to_index}, list length: {length}", from_index: i64, to_index: i64, length: usize),
         OverlongString(8, "Found string with length {len} which is too long to fit in a 64-bit signed integer", len: usize),
         NegativeDatetimeSub(9, "Attempting to subtract later datetime from earlier: {lhs} - {rhs}", lhs: String, rhs: String),
+        SubstringNegative(10, "Substring start {start} and length {length} must not be negative.", start: i64, length: i64),
+        InvalidRegex(11, "Invalid regular expression '{regex}'.", regex: String, source: regex::Error),
+        InvalidTimeUnit(12, "Unrecognised time unit '{unit}', expected one of 'year', 'month', 'day', 'hour', 'minute' or 'second'.", unit: String),
+        InvalidTimezone(13, "Unrecognised timezone '{timezone}', expected an IANA timezone name or an offset such as '+05:30'.", timezone: String),
     }
 }
@@ -89,6 +89,48 @@ pub enum ExpressionOpCode {
     MathMaxDecimalDecimal,
 
     LenString,
+
+    ConcatString,
+    SubstringString,
+    UpperString,
+    LowerString,
+    TrimString,
+    StartsWithString,
+    EndsWithString,
+    RegexReplaceString,
+    SplitString,
+
+    YearDate,
+    YearDateTime,
+    YearDateTimeTZ,
+
+    MonthDate,
+    MonthDateTime,
+    MonthDateTimeTZ,
+
+    DayDate,
+    DayDateTime,
+    DayDateTimeTZ,
+
+    HourDateTime,
+    HourDateTimeTZ,
+
+    MinuteDateTime,
+    MinuteDateTimeTZ,
+
+    SecondDateTime,
+    SecondDateTimeTZ,
+
+    TruncateDate,
+    TruncateDateTime,
+    TruncateDateTimeTZ,
+
+    NowDateTimeTZ,
+
+    DurationBetweenDateTime,
+    DurationBetweenDateTimeTZ,
+
+    InTimezoneDateTimeTZ,
 }
 
 impl fmt::Display for ExpressionOpCode {
@@ -151,6 +193,37 @@ impl fmt::Display for ExpressionOpCode {
             ExpressionOpCode::MathMaxDoubleDouble => write!(f, "max-double-double"),
             ExpressionOpCode::MathMaxDecimalDecimal => write!(f, "max-decimal-decimal"),
             ExpressionOpCode::LenString => write!(f, "len-string"),
+            ExpressionOpCode::ConcatString => write!(f, "concat-string"),
+            ExpressionOpCode::SubstringString => write!(f, "substring-string"),
+            ExpressionOpCode::UpperString => write!(f, "upper-string"),
+            ExpressionOpCode::LowerString => write!(f, "lower-string"),
+            ExpressionOpCode::TrimString => write!(f, "trim-string"),
+            ExpressionOpCode::StartsWithString => write!(f, "starts-with-string"),
+            ExpressionOpCode::EndsWithString => write!(f, "ends-with-string"),
+            ExpressionOpCode::RegexReplaceString => write!(f, "regex-replace-string"),
+            ExpressionOpCode::SplitString => write!(f, "split-string"),
+            ExpressionOpCode::YearDate => write!(f, "year-date"),
+            ExpressionOpCode::YearDateTime => write!(f, "year-datetime"),
+            ExpressionOpCode::YearDateTimeTZ => write!(f, "year-datetime-tz"),
+            ExpressionOpCode::MonthDate => write!(f, "month-date"),
+            ExpressionOpCode::MonthDateTime => write!(f, "month-datetime"),
+            ExpressionOpCode::MonthDateTimeTZ => write!(f, "month-datetime-tz"),
+            ExpressionOpCode::DayDate => write!(f, "day-date"),
+            ExpressionOpCode::DayDateTime => write!(f, "day-datetime"),
+            ExpressionOpCode::DayDateTimeTZ => write!(f, "day-datetime-tz"),
+            ExpressionOpCode::HourDateTime => write!(f, "hour-datetime"),
+            ExpressionOpCode::HourDateTimeTZ => write!(f, "hour-datetime-tz"),
+            ExpressionOpCode::MinuteDateTime => write!(f, "minute-datetime"),
+            ExpressionOpCode::MinuteDateTimeTZ => write!(f, "minute-datetime-tz"),
+            ExpressionOpCode::SecondDateTime => write!(f, "second-datetime"),
+            ExpressionOpCode::SecondDateTimeTZ => write!(f, "second-datetime-tz"),
+            ExpressionOpCode::TruncateDate => write!(f, "truncate-date"),
+            ExpressionOpCode::TruncateDateTime => write!(f, "truncate-datetime"),
+            ExpressionOpCode::TruncateDateTimeTZ => write!(f, "truncate-datetime-tz"),
+            ExpressionOpCode::NowDateTimeTZ => write!(f, "now-datetime-tz"),
+            ExpressionOpCode::DurationBetweenDateTime => write!(f, "duration-between-datetime"),
+            ExpressionOpCode::DurationBetweenDateTimeTZ => write!(f, "duration-between-datetime-tz"),
+            ExpressionOpCode::InTimezoneDateTimeTZ => write!(f, "in-timezone-datetime-tz"),
         }
     }
 }
@@ -0,0 +1,129 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+use std::{borrow::Cow, cell::RefCell, marker::PhantomData};
+
+use encoding::value::{value::NativeValueConvertible, value_type::ValueTypeCategory};
+use regex::Regex;
+
+use crate::annotation::expression::{
+    ExpressionCompileError,
+    expression_compiler::ExpressionCompilationContext,
+    instructions::{
+        CompilableExpression, ExpressionEvaluationError, ExpressionInstruction, op_codes::ExpressionOpCode,
+    },
+};
+
+pub trait TernaryExpression<
+    'a,
+    T1: NativeValueConvertible<'a>,
+    T2: NativeValueConvertible<'a>,
+    T3: NativeValueConvertible<'a>,
+    R: NativeValueConvertible<'a>,
+>
+{
+    const OP_CODE: ExpressionOpCode;
+    fn evaluate(a1: T1, a2: T2, a3: T3) -> Result<R, ExpressionEvaluationError>;
+}
+
+pub struct Ternary<'a, T1, T2, T3, R, F>
+where
+    T1: NativeValueConvertible<'a>,
+    T2: NativeValueConvertible<'a>,
+    T3: NativeValueConvertible<'a>,
+    R: NativeValueConvertible<'a>,
+    F: TernaryExpression<'a, T1, T2, T3, R>,
+{
+    pub phantom: PhantomData<&'a (T1, T2, T3, R, F)>,
+}
+
+impl<'a, T1, T2, T3, R, F> ExpressionInstruction for Ternary<'a, T1, T2, T3, R, F>
+where
+    T1: NativeValueConvertible<'a>,
+    T2: NativeValueConvertible<'a>,
+    T3: NativeValueConvertible<'a>,
+    R: NativeValueConvertible<'a>,
+    F: TernaryExpression<'a, T1, T2, T3, R>,
+{
+    const OP_CODE: ExpressionOpCode = F::OP_CODE;
+}
+
+impl<'a, T1, T2, T3, R, F> CompilableExpression for Ternary<'a, T1, T2, T3, R, F>
+where
+    T1: NativeValueConvertible<'a>,
+    T2: NativeValueConvertible<'a>,
+    T3: NativeValueConvertible<'a>,
+    R: NativeValueConvertible<'a>,
+    F: TernaryExpression<'a, T1, T2, T3, R>,
+{
+    fn return_value_category(&self) -> Option<ValueTypeCategory> {
+        Some(R::VALUE_TYPE_CATEGORY)
+    }
+
+    fn validate_and_append(builder: &mut ExpressionCompilationContext<'_>) -> Result<(), Box<ExpressionCompileError>> {
+        let a3 = builder.pop_type_single()?.category();
+        let a2 = builder.pop_type_single()?.category();
+        let a1 = builder.pop_type_single()?.category();
+        for (actual, expected) in
+            [(a1, T1::VALUE_TYPE_CATEGORY), (a2, T2::VALUE_TYPE_CATEGORY), (a3, T3::VALUE_TYPE_CATEGORY)]
+        {
+            if actual != expected {
+                return Err(Box::new(ExpressionCompileError::ExpressionMismatchedValueType {
+                    op_code: F::OP_CODE,
+                    expected,
+                    actual,
+                }));
+            }
+        }
+        builder.push_type_single(R::VALUE_TYPE_CATEGORY.try_into_value_type().unwrap());
+        builder.append_instruction(Self::OP_CODE);
+        Ok(())
+    }
+}
+
+macro_rules! ternary_instruction {
+    ( $lt:lifetime $( $name:ident = $impl_name:ident($a1:ident: $t1:ty, $a2:ident: $t2:ty, $a3:ident: $t3:ty) -> $r:ty $impl_code:block )* ) => { $(
+        pub type $name<$lt> = Ternary<$lt, $t1, $t2, $t3, $r, $impl_name>;
+        pub struct $impl_name {}
+        impl<$lt> TernaryExpression<$lt, $t1, $t2, $t3, $r> for $impl_name {
+            const OP_CODE: ExpressionOpCode = ExpressionOpCode::$name;
+            fn evaluate($a1: $t1, $a2: $t2, $a3: $t3) -> Result<$r, ExpressionEvaluationError> {
+                $impl_code
+            }
+        })*
+    };
+}
+
+pub(crate) use ternary_instruction;
+
+thread_local! {
+    // The pattern is usually the same for every row, so the last one compiled on each thread is reused
+    static LAST_COMPILED_REGEX: RefCell<Option<Regex>> = const { RefCell::new(None) };
+}
+
+fn with_compiled_regex<T>(pattern: &str, f: impl FnOnce(&Regex) -> T) -> Result<T, ExpressionEvaluationError> {
+    LAST_COMPILED_REGEX.with_borrow_mut(|last_compiled| {
+        if last_compiled.as_ref().is_none_or(|regex| regex.as_str() != pattern) {
+            let regex = Regex::new(pattern)
+                .map_err(|source| ExpressionEvaluationError::InvalidRegex { regex: pattern.to_owned(), source })?;
+            *last_compiled = Some(regex);
+        }
+        Ok(f(last_compiled.as_ref().unwrap()))
+    })
+}
+
+ternary_instruction! { 'a
+    SubstringString = SubstringStringImpl(a1: Cow<'a, str>, a2: i64, a3: i64) -> Cow<'a, str> {
+        // positions count characters rather than bytes, and a substring may run past the end of the string
+        let (Ok(start), Ok(length)) = (usize::try_from(a2), usize::try_from(a3)) else {
+            return Err(ExpressionEvaluationError::SubstringNegative { start: a2, length: a3 });
+        };
+        Ok(Cow::Owned(a1.chars().skip(start).take(length).collect()))
+    }
+
+    RegexReplaceString = RegexReplaceStringImpl(a1: Cow<'a, str>, a2: Cow<'a, str>, a3: Cow<'a, str>) -> Cow<'a, str> {
+        with_compiled_regex(&a2, |regex| Cow::Owned(regex.replace_all(&a1, a3.as_ref()).into_owned()))
+    }
+}
@@ -96,4 +96,8 @@ unary_instruction! { 'a
         let len = a1.chars().count();
         len.try_into().map_err(|_| ExpressionEvaluationError::OverlongString { len })
     }
+
+    UpperString = UpperStringImpl(a1: Cow<'a, str>) -> Cow<'a, str> { Ok(Cow::Owned(a1.to_uppercase())) }
+    LowerString = LowerStringImpl(a1: Cow<'a, str>) -> Cow<'a, str> { Ok(Cow::Owned(a1.to_lowercase())) }
+    TrimString = TrimStringImpl(a1: Cow<'a, str>) -> Cow<'a, str> { Ok(Cow::Owned(a1.trim().to_owned())) }
 }
@@ -428,7 +428,7 @@ fn annotate_return(
             .collect(),
         AnnotatedFunctionReturn::ReduceReducer { instructions } => instructions
             .iter()
-            .map(|instruction| FunctionParameterAnnotation::Value(instruction.output_type()))
+            .map(|instruction| FunctionParameterAnnotation::Value(instruction.output_type().value_type().clone()))
             .collect(),
         AnnotatedFunctionReturn::ReduceCheck {} => {
             vec![FunctionParameterAnnotation::Value(ValueType::Boolean)]
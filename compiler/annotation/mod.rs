@@ -119,6 +119,12 @@ typedb_error!(
             variable: String,
             source_span: Option<Span>,
         ),
+        SortVariableIsList(
+            18,
+            "The sort variable '{variable}' was a list, which cannot be sorted on.",
+            variable: String,
+            source_span: Option<Span>,
+        ),
     }
 );
 
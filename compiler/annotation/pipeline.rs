@@ -309,9 +309,7 @@ fn annotate_stage(
             for &AssignedReduction { assigned, reduction } in &reduce.assigned_reductions {
                 let typed_reduce =
                     resolve_reducer_by_value_type(ctx, reduction, running_annotations, reduce.source_span())?;
-                running_annotations
-                    .values
-                    .insert(assigned, ExpressionValueType::Single(typed_reduce.output_type().clone()));
+                running_annotations.values.insert(assigned, typed_reduce.output_type());
                 reduce_instructions.push(typed_reduce);
             }
             Ok(AnnotatedStage::Reduce(reduce, reduce_instructions))
@@ -360,8 +358,15 @@ pub fn validate_sort_variables_comparable(
     input_annotations: &RunningVariableAnnotations,
 ) -> Result<(), AnnotationError> {
     for sort_var in &sort.variables {
-        if input_annotations.values.contains_key(&sort_var.variable()) {
-            continue; // Expressions always return the same type.
+        if let Some(value_type) = input_annotations.values.get(&sort_var.variable()) {
+            // Expressions always return the same type.
+            if let ExpressionValueType::List(_) = value_type {
+                let variable_name = ctx.name_for_error(sort_var.variable());
+                return Err(AnnotationError::SortVariableIsList {
+                    variable: variable_name,
+                    source_span: sort.source_span(),
+                });
+            }
         } else if let Some(types) = input_annotations.concepts.get(&sort_var.variable()) {
             let value_types = resolve_value_types(&(**types), ctx.snapshot, ctx.type_manager)
                 .map_err(|typedb_source| AnnotationError::TypeInference { typedb_source })?;
@@ -477,7 +482,8 @@ pub fn resolve_reducer_by_value_type(
         | Reducer::Mean(variable)
         | Reducer::Median(variable)
         | Reducer::Min(variable)
-        | Reducer::Std(variable) => {
+        | Reducer::Std(variable)
+        | Reducer::List(variable) => {
             let value_type =
                 determine_value_type_for_reducer(ctx, reducer, variable, variable_annotations, reduce_source_span)?;
             resolve_reduce_instruction_by_value_type(ctx, reducer, value_type, reduce_source_span)
@@ -556,6 +562,7 @@ fn resolve_reduce_instruction_by_value_type(
             Reducer::Mean(var) => Ok(ReduceInstruction::MeanInteger(var)),
             Reducer::Median(var) => Ok(ReduceInstruction::MedianInteger(var)),
             Reducer::Std(var) => Ok(ReduceInstruction::StdInteger(var)),
+            Reducer::List(var) => Ok(ReduceInstruction::ListInteger(var)),
         },
 
         ValueTypeCategory::Double => match reducer {
@@ -567,6 +574,7 @@ fn resolve_reduce_instruction_by_value_type(
             Reducer::Mean(var) => Ok(ReduceInstruction::MeanDouble(var)),
             Reducer::Median(var) => Ok(ReduceInstruction::MedianDouble(var)),
             Reducer::Std(var) => Ok(ReduceInstruction::StdDouble(var)),
+            Reducer::List(var) => Ok(ReduceInstruction::ListDouble(var)),
         },
 
         ValueTypeCategory::Decimal => match reducer {
@@ -578,6 +586,7 @@ fn resolve_reduce_instruction_by_value_type(
             Reducer::Mean(var) => Ok(ReduceInstruction::MeanDecimal(var)),
             Reducer::Median(var) => Ok(ReduceInstruction::MedianDecimal(var)),
             Reducer::Std(var) => Ok(ReduceInstruction::StdDecimal(var)),
+            Reducer::List(var) => Ok(ReduceInstruction::ListDecimal(var)),
         },
 
         ValueTypeCategory::String => match reducer {
@@ -585,6 +594,7 @@ fn resolve_reduce_instruction_by_value_type(
             Reducer::CountVar(var) => Ok(ReduceInstruction::CountVar(var)),
             Reducer::Max(var) => Ok(ReduceInstruction::MaxString(var)),
             Reducer::Min(var) => Ok(ReduceInstruction::MinString(var)),
+            Reducer::List(var) => Ok(ReduceInstruction::ListString(var)),
             _ => err(),
         },
 
@@ -593,6 +603,7 @@ fn resolve_reduce_instruction_by_value_type(
             Reducer::CountVar(var) => Ok(ReduceInstruction::CountVar(var)),
             Reducer::Max(var) => Ok(ReduceInstruction::MaxDate(var)),
             Reducer::Min(var) => Ok(ReduceInstruction::MinDate(var)),
+            Reducer::List(var) => Ok(ReduceInstruction::ListDate(var)),
             _ => err(),
         },
 
@@ -601,6 +612,7 @@ fn resolve_reduce_instruction_by_value_type(
             Reducer::CountVar(var) => Ok(ReduceInstruction::CountVar(var)),
             Reducer::Max(var) => Ok(ReduceInstruction::MaxDateTime(var)),
             Reducer::Min(var) => Ok(ReduceInstruction::MinDateTime(var)),
+            Reducer::List(var) => Ok(ReduceInstruction::ListDateTime(var)),
             _ => err(),
         },
 
@@ -609,10 +621,21 @@ fn resolve_reduce_instruction_by_value_type(
             Reducer::CountVar(var) => Ok(ReduceInstruction::CountVar(var)),
             Reducer::Max(var) => Ok(ReduceInstruction::MaxDateTimeTZ(var)),
             Reducer::Min(var) => Ok(ReduceInstruction::MinDateTimeTZ(var)),
+            Reducer::List(var) => Ok(ReduceInstruction::ListDateTimeTZ(var)),
+            _ => err(),
+        },
+
+        ValueTypeCategory::Boolean => match reducer {
+            Reducer::List(var) => Ok(ReduceInstruction::ListBoolean(var)),
+            _ => err(),
+        },
+
+        ValueTypeCategory::Duration => match reducer {
+            Reducer::List(var) => Ok(ReduceInstruction::ListDuration(var)),
             _ => err(),
         },
 
-        ValueTypeCategory::Boolean | ValueTypeCategory::Duration | ValueTypeCategory::Struct => err(),
+        ValueTypeCategory::Struct => err(),
     }
 }
 
@@ -9,7 +9,10 @@ use std::{collections::HashMap, sync::Arc};
 use answer::variable::Variable;
 use concept::{thing::statistics::Statistics, type_::attribute_type::AttributeType};
 use error::typedb_error;
-use ir::{pattern::ParameterID, pipeline::VariableRegistry};
+use ir::{
+    pattern::ParameterID,
+    pipeline::{ParameterRegistry, VariableRegistry},
+};
 
 use crate::{
     VariablePosition,
@@ -67,17 +70,19 @@ pub struct ExecutableFetchListSubFetch {
 
 pub fn compile_fetch(
     statistics: &Statistics,
+    value_parameters: &ParameterRegistry,
     available_functions: &ExecutableFunctionRegistry,
     fetch: AnnotatedFetch,
     variable_positions: &HashMap<Variable, VariablePosition>,
 ) -> Result<(ExecutableFetch, TypePopulations), FetchCompilationError> {
     let (compiled, type_populations) =
-        compile_object(statistics, available_functions, fetch.object, variable_positions)?;
+        compile_object(statistics, value_parameters, available_functions, fetch.object, variable_positions)?;
     Ok((ExecutableFetch::new(compiled), type_populations))
 }
 
 fn compile_object(
     statistics: &Statistics,
+    value_parameters: &ParameterRegistry,
     available_functions: &ExecutableFunctionRegistry,
     fetch_object: AnnotatedFetchObject,
     variable_positions: &HashMap<Variable, VariablePosition>,
@@ -87,7 +92,8 @@ fn compile_object(
             let mut compiled_entries = HashMap::with_capacity(entries.len());
             let mut type_populations = TypePopulations::default();
             for (key, value) in entries {
-                let (compiled, pop) = compile_some(statistics, available_functions, value, variable_positions)?;
+                let (compiled, pop) =
+                    compile_some(statistics, value_parameters, available_functions, value, variable_positions)?;
                 compiled_entries.insert(key, compiled);
                 type_populations.extend(pop);
             }
@@ -104,6 +110,7 @@ fn compile_object(
 
 fn compile_some(
     statistics: &Statistics,
+    value_parameters: &ParameterRegistry,
     available_functions: &ExecutableFunctionRegistry,
     some: AnnotatedFetchSome,
     variable_positions: &HashMap<Variable, VariablePosition>,
@@ -128,7 +135,7 @@ fn compile_some(
         }
         AnnotatedFetchSome::Object(object) => {
             let (compiled, type_populations) =
-                compile_object(statistics, available_functions, *object, variable_positions)?;
+                compile_object(statistics, value_parameters, available_functions, *object, variable_positions)?;
             Ok((FetchSomeInstruction::Object(Box::new(compiled)), type_populations))
         }
         AnnotatedFetchSome::ListFunction(function) => {
@@ -140,6 +147,7 @@ fn compile_some(
             let AnnotatedFetchListSubFetch { variable_registry, input_variables, stages, fetch } = sub_fetch;
             let (input_positions, compiled_stages, compiled_fetch, type_populations) = compile_stages_and_fetch(
                 statistics,
+                value_parameters,
                 &variable_registry,
                 available_functions,
                 &stages,
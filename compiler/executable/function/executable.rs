@@ -92,6 +92,7 @@ fn compile_function(
     let AnnotatedFunction { variable_registry, parameter_registry, arguments, stages, return_, .. } = function;
     let (argument_positions, executable_stages, _) = compile_pipeline_stages(
         statistics,
+        &parameter_registry,
         &variable_registry,
         call_cost_provider,
         &stages,
@@ -17,7 +17,7 @@ use crate::{
     annotation::expression::compiled_expression::ExecutableExpression,
     executable::match_::{
         instructions::{CheckInstruction, ConstraintInstruction, VariableModes},
-        planner::plan::PlannerStatistics,
+        planner::{plan::PlannerStatistics, vertex::Cost},
     },
 };
 
@@ -28,6 +28,7 @@ pub struct ConjunctionExecutable {
     variable_positions: HashMap<Variable, VariablePosition>,
     variable_reverse_map: HashMap<ExecutorVariable, Variable>,
     planner_statistics: PlannerStatistics,
+    step_costs: Vec<Option<Cost>>,
 }
 
 impl ConjunctionExecutable {
@@ -38,7 +39,18 @@ impl ConjunctionExecutable {
         variable_reverse_map: HashMap<ExecutorVariable, Variable>,
         planner_statistics: PlannerStatistics,
     ) -> Self {
-        Self { executable_id, steps, variable_positions, variable_reverse_map, planner_statistics }
+        Self {
+            executable_id,
+            steps,
+            variable_positions,
+            variable_reverse_map,
+            planner_statistics,
+            step_costs: Vec::new(),
+        }
+    }
+
+    pub(crate) fn with_step_costs(self, step_costs: Vec<Option<Cost>>) -> Self {
+        Self { step_costs, ..self }
     }
 
     pub fn executable_id(&self) -> u64 {
@@ -65,6 +77,17 @@ impl ConjunctionExecutable {
         &self.planner_statistics
     }
 
+    /// The planner's estimated cost of the step at `step_index`, per row entering the step.
+    /// Not every step has its own estimate: patterns the planner grouped together may be lowered into several steps.
+    pub fn step_estimated_cost(&self, step_index: usize) -> Option<f64> {
+        self.step_costs.get(step_index)?.map(|cost| cost.cost)
+    }
+
+    /// The planner's estimated number of rows the step at `step_index` produces per row entering it.
+    pub fn step_estimated_size(&self, step_index: usize) -> Option<f64> {
+        self.step_costs.get(step_index)?.map(|cost| cost.io_ratio)
+    }
+
     pub fn selected_variables(&self) -> &[VariablePosition] {
         let Some(last) = self.steps().last() else { return &[] };
         last.selected_variables()
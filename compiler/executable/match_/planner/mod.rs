@@ -14,7 +14,7 @@ use concept::thing::statistics::Statistics;
 use error::typedb_error;
 use ir::{
     pattern::{BranchID, Vertex, constraint::ExpressionBinding},
-    pipeline::{VariableRegistry, block::Block, function_signature::FunctionID},
+    pipeline::{ParameterRegistry, VariableRegistry, block::Block, function_signature::FunctionID},
 };
 use itertools::Itertools;
 use tracing::{debug, trace};
@@ -32,6 +32,7 @@ use crate::{
                     IntersectionStep, NegationStep, OptionalStep,
                 },
                 plan::{PlannerStatistics, QueryPlanningError, plan_conjunction},
+                vertex::Cost,
             },
         },
         next_executable_id,
@@ -57,6 +58,7 @@ pub fn compile(
     variable_registry: &VariableRegistry,
     expressions: &HashMap<ExpressionBinding<Variable>, ExecutableExpression<Variable>>,
     statistics: &Statistics,
+    value_parameters: &ParameterRegistry,
     call_cost_provider: &impl FunctionCallCostProvider,
 ) -> Result<ConjunctionExecutable, ConjunctionCompilationError> {
     let conjunction = block.conjunction();
@@ -75,6 +77,7 @@ pub fn compile(
         variable_registry,
         expressions,
         statistics,
+        value_parameters,
         call_cost_provider,
     )
     .map_err(|source| ConjunctionCompilationError::PlanningError { typedb_source: source })?
@@ -317,6 +320,8 @@ struct ConjunctionExecutableBuilder {
     next_output: VariablePosition,
 
     planner_statistics: PlannerStatistics,
+    step_costs: HashMap<usize, Cost>,
+    attributed_planned_steps: HashSet<usize>,
     branch_id: Option<BranchID>,
 }
 
@@ -353,6 +358,8 @@ impl ConjunctionExecutableBuilder {
             index,
             next_output,
             planner_statistics,
+            step_costs: HashMap::new(),
+            attributed_planned_steps: HashSet::new(),
         }
     }
 
@@ -503,6 +510,20 @@ impl ConjunctionExecutableBuilder {
         }
     }
 
+    /// Attributes the estimated cost of a step chosen by the planner to the step being built, or the last built one.
+    /// Each planned step is attributed once, to the first step that lowers one of its patterns.
+    fn attribute_planned_step_cost(&mut self, planned_step: usize, cost: Cost) {
+        if !self.attributed_planned_steps.insert(planned_step) {
+            return;
+        }
+        let Some(step) =
+            (if self.current.is_some() { Some(self.steps.len()) } else { self.steps.len().checked_sub(1) })
+        else {
+            return;
+        };
+        self.step_costs.entry(step).and_modify(|step_cost| *step_cost = step_cost.chain(cost)).or_insert(cost);
+    }
+
     fn finish_one(&mut self) {
         if let Some(mut current) = self.current.take() {
             current.selected_variables = Vec::from_iter(self.current_outputs.iter().copied());
@@ -517,6 +538,7 @@ impl ConjunctionExecutableBuilder {
             .iter()
             .filter_map(|(var, &pos)| variable_registry.variable_names().get(var).and(Some(pos)))
             .collect();
+        let step_costs = (0..self.steps.len()).map(|step| self.step_costs.get(&step).copied()).collect();
         let steps = self
             .steps
             .into_iter()
@@ -529,5 +551,6 @@ impl ConjunctionExecutableBuilder {
             self.reverse_index,
             self.planner_statistics,
         )
+        .with_step_costs(step_costs)
     }
 }
@@ -14,7 +14,7 @@ use std::{
 };
 
 use answer::variable::Variable;
-use concept::thing::statistics::Statistics;
+use concept::thing::{statistics::Statistics, value_histogram::ValueHistogram};
 use error::{typedb_error, unimplemented_feature};
 use ir::{
     pattern::{
@@ -27,7 +27,7 @@ use ir::{
         nested_pattern::NestedPattern,
         variable_category::{VariableCategory, VariableOptionality},
     },
-    pipeline::{VariableRegistry, block::BlockContext},
+    pipeline::{ParameterRegistry, VariableRegistry, block::BlockContext},
 };
 use itertools::{Itertools, chain};
 use tracing::{Level, event};
@@ -90,6 +90,7 @@ pub(crate) fn plan_conjunction<'a>(
     variable_registry: &VariableRegistry,
     expressions: &'a HashMap<ExpressionBinding<Variable>, ExecutableExpression<Variable>>,
     statistics: &'a Statistics,
+    value_parameters: &'a ParameterRegistry,
     call_cost_provider: &'a impl FunctionCallCostProvider,
 ) -> Result<ConjunctionPlan<'a>, QueryPlanningError> {
     make_builder(
@@ -100,6 +101,7 @@ pub(crate) fn plan_conjunction<'a>(
         variable_registry,
         expressions,
         statistics,
+        value_parameters,
         call_cost_provider,
     )?
     .plan()
@@ -113,6 +115,7 @@ fn make_builder<'a>(
     variable_registry: &VariableRegistry,
     expressions: &'a HashMap<ExpressionBinding<Variable>, ExecutableExpression<Variable>>,
     statistics: &'a Statistics,
+    value_parameters: &'a ParameterRegistry,
     call_cost_provider: &impl FunctionCallCostProvider,
 ) -> Result<ConjunctionPlanBuilder<'a>, QueryPlanningError> {
     let mut negation_subplans = Vec::new();
@@ -135,6 +138,7 @@ fn make_builder<'a>(
                                 variable_registry,
                                 expressions,
                                 statistics,
+                                value_parameters,
                                 call_cost_provider,
                             )
                         })
@@ -152,6 +156,7 @@ fn make_builder<'a>(
                     variable_registry,
                     expressions,
                     statistics,
+                    value_parameters,
                     call_cost_provider,
                 )?
                 .set_to_input(negation.required_inputs())
@@ -174,6 +179,7 @@ fn make_builder<'a>(
                         variable_registry,
                         expressions,
                         statistics,
+                        value_parameters,
                         call_cost_provider,
                     )?
                     .set_to_input(required_vars.into_iter())
@@ -184,8 +190,12 @@ fn make_builder<'a>(
     }
 
     let conjunction_annotations = block_annotations.type_annotations_of(conjunction).unwrap();
-    let mut plan_builder =
-        ConjunctionPlanBuilder::new(conjunction.required_inputs().collect(), conjunction_annotations, statistics);
+    let mut plan_builder = ConjunctionPlanBuilder::new(
+        conjunction.required_inputs().collect(),
+        conjunction_annotations,
+        statistics,
+        value_parameters,
+    );
 
     let optional_variables = optional_subplans.iter().flat_map(|optional| optional.optional_variables.iter()).copied();
     plan_builder.register_variables(
@@ -256,6 +266,7 @@ pub(super) struct ConjunctionPlanBuilder<'a> {
     graph: Graph<'a>,
     local_annotations: &'a TypeAnnotations,
     statistics: &'a Statistics,
+    value_parameters: &'a ParameterRegistry,
     planner_statistics: PlannerStatistics,
 }
 
@@ -266,11 +277,17 @@ impl fmt::Debug for ConjunctionPlanBuilder<'_> {
 }
 
 impl<'a> ConjunctionPlanBuilder<'a> {
-    fn new(required_inputs: Vec<Variable>, local_annotations: &'a TypeAnnotations, statistics: &'a Statistics) -> Self {
+    fn new(
+        required_inputs: Vec<Variable>,
+        local_annotations: &'a TypeAnnotations,
+        statistics: &'a Statistics,
+        value_parameters: &'a ParameterRegistry,
+    ) -> Self {
         Self {
             graph: Graph::default(),
             local_annotations,
             statistics,
+            value_parameters,
             planner_statistics: PlannerStatistics::new(),
             required_inputs,
         }
@@ -544,24 +561,52 @@ impl<'a> ConjunctionPlanBuilder<'a> {
     fn register_comparison(&mut self, comparison: &'a Comparison<Variable>) {
         let lhs = Input::from_vertex(comparison.lhs(), &self.graph.variable_index);
         let rhs = Input::from_vertex(comparison.rhs(), &self.graph.variable_index);
-        if let Input::Variable(lhs) = lhs {
-            let lhs = self.graph.elements.get_mut(&VertexId::Variable(lhs)).unwrap().as_variable_mut().unwrap();
+        if let Input::Variable(lhs_id) = lhs {
+            let (lhs_vertex, rhs_vertex) = (comparison.lhs(), comparison.rhs());
+            let selectivity = match comparison.comparator() {
+                Comparator::Less | Comparator::LessOrEqual => {
+                    self.estimate_value_selectivity(lhs_vertex, rhs_vertex, ValueHistogram::fraction_below)
+                }
+                Comparator::Greater | Comparator::GreaterOrEqual => {
+                    self.estimate_value_selectivity(lhs_vertex, rhs_vertex, ValueHistogram::fraction_above)
+                }
+                Comparator::Like => {
+                    self.estimate_value_selectivity(lhs_vertex, rhs_vertex, ValueHistogram::fraction_like)
+                }
+                Comparator::Equal | Comparator::NotEqual | Comparator::Contains => None,
+            };
+            let lhs = self.graph.elements.get_mut(&VertexId::Variable(lhs_id)).unwrap().as_variable_mut().unwrap();
             match comparison.comparator() {
                 Comparator::Equal => lhs.add_equal(rhs),
                 Comparator::NotEqual => (), // no tangible impact on traversal costs
-                Comparator::Less | Comparator::LessOrEqual => lhs.add_upper_bound(rhs),
-                Comparator::Greater | Comparator::GreaterOrEqual => lhs.add_lower_bound(rhs),
-                Comparator::Like => (),
+                Comparator::Less | Comparator::LessOrEqual => lhs.add_upper_bound(rhs, selectivity),
+                Comparator::Greater | Comparator::GreaterOrEqual => lhs.add_lower_bound(rhs, selectivity),
+                Comparator::Like => {
+                    if let Some(selectivity) = selectivity {
+                        lhs.add_pattern_match(rhs, selectivity)
+                    }
+                }
                 Comparator::Contains => (),
             }
         }
-        if let Input::Variable(rhs) = rhs {
-            let rhs = self.graph.elements.get_mut(&VertexId::Variable(rhs)).unwrap().as_variable_mut().unwrap();
+        if let Input::Variable(rhs_id) = rhs {
+            let (rhs_vertex, lhs_vertex) = (comparison.rhs(), comparison.lhs());
+            // the right hand side is bounded in the opposite direction to the left hand side
+            let selectivity = match comparison.comparator() {
+                Comparator::Less | Comparator::LessOrEqual => {
+                    self.estimate_value_selectivity(rhs_vertex, lhs_vertex, ValueHistogram::fraction_above)
+                }
+                Comparator::Greater | Comparator::GreaterOrEqual => {
+                    self.estimate_value_selectivity(rhs_vertex, lhs_vertex, ValueHistogram::fraction_below)
+                }
+                Comparator::Equal | Comparator::NotEqual | Comparator::Like | Comparator::Contains => None,
+            };
+            let rhs = self.graph.elements.get_mut(&VertexId::Variable(rhs_id)).unwrap().as_variable_mut().unwrap();
             match comparison.comparator() {
                 Comparator::Equal => rhs.add_equal(lhs),
                 Comparator::NotEqual => (), // no tangible impact on traversal costs
-                Comparator::Less | Comparator::LessOrEqual => rhs.add_upper_bound(lhs),
-                Comparator::Greater | Comparator::GreaterOrEqual => rhs.add_lower_bound(lhs),
+                Comparator::Less | Comparator::LessOrEqual => rhs.add_lower_bound(lhs, selectivity),
+                Comparator::Greater | Comparator::GreaterOrEqual => rhs.add_upper_bound(lhs, selectivity),
                 Comparator::Like => (),
                 Comparator::Contains => (),
             }
@@ -574,6 +619,33 @@ impl<'a> ConjunctionPlanBuilder<'a> {
         ));
     }
 
+    /// Estimates the fraction of the attributes of `variable` that pass a comparison with the value of the parameter
+    /// `other`, from the value histograms of the attribute types. Cached plans are reused for other values of the
+    /// parameter, so the estimate reflects the values the query was first planned with.
+    fn estimate_value_selectivity(
+        &self,
+        variable: &Vertex<Variable>,
+        other: &Vertex<Variable>,
+        fraction: impl Fn(&ValueHistogram, &encoding::value::value::Value<'static>) -> Option<f64>,
+    ) -> Option<f64> {
+        let value = self.value_parameters.value(other.as_parameter()?)?;
+        let mut matching = 0.0;
+        let mut total = 0;
+        for type_ in self.local_annotations.vertex_annotations_of(variable)?.iter() {
+            let answer::Type::Attribute(attribute_type) = type_ else {
+                return None;
+            };
+            let count = self.statistics.attribute_counts.get(attribute_type).copied().unwrap_or(0);
+            if count == 0 {
+                continue;
+            }
+            let histogram = self.statistics.complete_value_histogram(*attribute_type)?;
+            matching += fraction(histogram, value)? * count as f64;
+            total += count;
+        }
+        (total > 0).then(|| matching / total as f64)
+    }
+
     fn register_optimised_to_unsatisfiable(&mut self, optimised_unsatisfiable: &'a Unsatisfiable) {
         let planner = UnsatisfiableVertex::from_constraint(
             optimised_unsatisfiable,
@@ -611,9 +683,7 @@ impl<'a> ConjunctionPlanBuilder<'a> {
     // (When a step has multiple pattern, the first such produced variable is always the join variable)
     // We record directionality information for each pattern in the plan, indicating which prefix index to use for pattern retrieval
 
-    fn beam_search_plan(
-        &self,
-    ) -> Result<(Vec<VertexId>, HashMap<PatternVertexId, CostMetaData>, Cost), QueryPlanningError> {
+    fn beam_search_plan(&self) -> Result<CompleteCostPlan, QueryPlanningError> {
         const INDENT: &str = "";
 
         let search_patterns: HashSet<_> = self.graph.pattern_to_variable.keys().copied().collect();
@@ -697,13 +767,19 @@ impl<'a> ConjunctionPlanBuilder<'a> {
             complete_plan.vertex_ordering,
             complete_plan.pattern_metadata
         );
-        Ok((complete_plan.vertex_ordering, complete_plan.pattern_metadata, complete_plan.cumulative_cost))
+        Ok(complete_plan)
     }
 
     // Execute plans
     pub(super) fn plan(self) -> Result<ConjunctionPlan<'a>, QueryPlanningError> {
         // Beam plan
-        let (ordering, metadata, cost) = self.beam_search_plan()?;
+        let CompleteCostPlan { vertex_ordering: ordering, pattern_metadata: metadata, cumulative_cost: cost, steps } =
+            self.beam_search_plan()?;
+        let step_costs = steps
+            .into_iter()
+            .enumerate()
+            .flat_map(|(step, (patterns, cost))| patterns.into_iter().map(move |pattern| (pattern, (step, cost))))
+            .collect();
 
         let element_to_order = ordering.iter().copied().enumerate().map(|(order, index)| (index, order)).collect();
 
@@ -716,6 +792,7 @@ impl<'a> ConjunctionPlanBuilder<'a> {
             ordering,
             metadata,
             element_to_order,
+            step_costs,
             planner_statistics,
         })
     }
@@ -762,6 +839,16 @@ impl PlannerStatistics {
         }
     }
 
+    /// The planner's estimated cost of producing this conjunction's answers, per input row.
+    pub fn estimated_cost(&self) -> f64 {
+        self.query_cost.cost
+    }
+
+    /// The planner's estimated number of answers produced per input row.
+    pub fn estimated_size(&self) -> f64 {
+        self.query_cost.io_ratio
+    }
+
     pub(crate) fn increment_var(&mut self, count: f64) {
         self.var_count.0 += 1.0;
         self.var_count.1 += count;
@@ -810,12 +897,14 @@ pub(super) struct CompleteCostPlan {
     vertex_ordering: Vec<VertexId>,
     pattern_metadata: HashMap<PatternVertexId, CostMetaData>,
     cumulative_cost: Cost,
+    steps: Vec<(Vec<PatternVertexId>, Cost)>,
 }
 
 #[derive(Clone, PartialEq, Debug)]
 pub(super) struct PartialCostPlan {
     vertex_ordering: Vec<VertexId>, // the part of the plan that has been decided upon
     cumulative_cost: Cost,          // the cost of the part of the plan that has been decided upon
+    decided_steps: Vec<(Vec<PatternVertexId>, Cost)>, // the patterns and cost of each decided step
 
     ongoing_step: HashSet<PatternVertexId>, // the set of non-trivial patterns in the ongoing step
     ongoing_step_stash: Vec<PatternVertexId>, // the set of trivial patterns in the ongoing step
@@ -847,6 +936,7 @@ impl PartialCostPlan {
             pattern_metadata: HashMap::new(),
             all_produced_vars: produced_vars,
             cumulative_cost: Cost::NOOP,
+            decided_steps: Vec::new(),
             remaining_patterns,
             ongoing_step: HashSet::new(),
             ongoing_step_stash: Vec::new(),
@@ -1103,6 +1193,7 @@ impl PartialCostPlan {
             pattern_metadata: new_pattern_metadata,
             remaining_patterns: new_remaining_patterns,
             cumulative_cost: self.cumulative_cost,
+            decided_steps: self.decided_steps.clone(),
             ongoing_step: new_ongoing_step,
             ongoing_step_stash: self.ongoing_step_stash.clone(),
             ongoing_step_cost: extension.step_cost,
@@ -1124,6 +1215,8 @@ impl PartialCostPlan {
             .cumulative_cost
             .chain(self.ongoing_step_cost)
             .chain(Cost { cost: (self.ongoing_step_stash.len() as f64) * Cost::TRIVIAL_COST, io_ratio: 1.0 });
+        let mut new_decided_steps = self.decided_steps.clone();
+        new_decided_steps.push(self.current_step_with_cost());
 
         // Then start a new step with the given plan extension
         let mut new_ongoing_step = HashSet::new();
@@ -1149,6 +1242,7 @@ impl PartialCostPlan {
         PartialCostPlan {
             vertex_ordering: new_vertex_ordering,
             cumulative_cost: new_cumulative_cost,
+            decided_steps: new_decided_steps,
             ongoing_step: new_ongoing_step,
             ongoing_step_stash: Vec::new(),
             ongoing_step_cost: extension.step_cost,
@@ -1171,14 +1265,25 @@ impl PartialCostPlan {
             .cumulative_cost
             .chain(self.ongoing_step_cost)
             .chain(Cost { cost: (self.ongoing_step_stash.len() as f64) * Cost::TRIVIAL_COST, io_ratio: 1.0 });
+        let mut final_steps = self.decided_steps.clone();
+        final_steps.push(self.current_step_with_cost());
 
         CompleteCostPlan {
             vertex_ordering: final_vertex_ordering,
             pattern_metadata: self.pattern_metadata.clone(),
             cumulative_cost: final_cumulative_cost,
+            steps: final_steps,
         }
     }
 
+    fn current_step_with_cost(&self) -> (Vec<PatternVertexId>, Cost) {
+        let patterns = self.ongoing_step.iter().chain(self.ongoing_step_stash.iter()).copied().collect();
+        let cost = self
+            .ongoing_step_cost
+            .chain(Cost { cost: (self.ongoing_step_stash.len() as f64) * Cost::TRIVIAL_COST, io_ratio: 1.0 });
+        (patterns, cost)
+    }
+
     fn hash(&self) -> PartialPlanHash {
         PartialPlanHash {
             n_remaining_patterns: self.remaining_patterns.len() as u32,
@@ -1252,6 +1357,7 @@ pub(crate) struct ConjunctionPlan<'a> {
     ordering: Vec<VertexId>,
     metadata: HashMap<PatternVertexId, CostMetaData>,
     element_to_order: HashMap<VertexId, usize>,
+    step_costs: HashMap<PatternVertexId, (usize, Cost)>, // the planned step each pattern belongs to, and its cost
     pub(crate) planner_statistics: PlannerStatistics,
 }
 
@@ -1473,6 +1579,7 @@ impl ConjunctionPlan<'_> {
                     conjunction_builder.push_step(&HashMap::new(), step_builder.into())
                 }
             }
+            self.attribute_step_cost(conjunction_builder, producer);
         }
         conjunction_builder.finish_one();
         Ok(())
@@ -1620,9 +1727,16 @@ impl ConjunctionPlan<'_> {
                 )
             }
         }
+        self.attribute_step_cost(conjunction_builder, pattern);
         Ok(())
     }
 
+    fn attribute_step_cost(&self, conjunction_builder: &mut ConjunctionExecutableBuilder, pattern: PatternVertexId) {
+        if let Some(&(planned_step, cost)) = self.step_costs.get(&pattern) {
+            conjunction_builder.attribute_planned_step_cost(planned_step, cost);
+        }
+    }
+
     fn lower_constraint(
         &self,
         conjunction_builder: &mut ConjunctionExecutableBuilder,
@@ -2293,3 +2407,82 @@ impl<'a> Graph<'a> {
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{BTreeMap, BTreeSet, HashMap},
+        sync::Arc,
+    };
+
+    use answer::{Type, variable::Variable};
+    use concept::{thing::statistics::Statistics, type_::attribute_type::AttributeType};
+    use encoding::{
+        graph::type_::vertex::{TypeID, TypeVertexEncoding},
+        value::value::Value,
+    };
+    use ir::{
+        pattern::{
+            Vertex,
+            constraint::{Comparator, Comparison},
+        },
+        pipeline::ParameterRegistry,
+    };
+    use storage::sequence_number::SequenceNumber;
+    use typeql::common::Span;
+
+    use super::{ConjunctionPlanBuilder, TypeAnnotations, VertexId};
+
+    /// The fraction of the attributes of `$age` expected to pass the comparisons, each given as whether `$age` is
+    /// their left hand side, their comparator, and the value `$age` is compared with
+    fn age_selectivity(comparisons: &[(bool, Comparator, i64)]) -> f64 {
+        let age = Variable::new(0);
+        let age_type = AttributeType::build_from_type_id(TypeID::new(0));
+        let annotations = TypeAnnotations::new(
+            BTreeMap::from([(Vertex::Variable(age), Arc::new(BTreeSet::from([Type::Attribute(age_type)])))]),
+            HashMap::new(),
+        );
+        let mut statistics = Statistics::new(SequenceNumber::MIN);
+        statistics.attribute_counts.insert(age_type, 100);
+
+        let mut parameters = ParameterRegistry::new();
+        let comparisons = comparisons
+            .iter()
+            .map(|&(is_age_lhs, comparator, value)| {
+                let span = Span { begin_offset: 0, end_offset: 0 };
+                let value = Vertex::Parameter(parameters.register_value(Value::Integer(value), span));
+                match is_age_lhs {
+                    true => Comparison::new(Vertex::Variable(age), value, comparator, None),
+                    false => Comparison::new(value, Vertex::Variable(age), comparator, None),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut builder = ConjunctionPlanBuilder::new(Vec::new(), &annotations, &statistics, &parameters);
+        builder.register_thing_var(age);
+        for comparison in &comparisons {
+            builder.register_comparison(comparison);
+        }
+        let age_id = builder.graph.variable_index[&age];
+        builder.graph.elements[&VertexId::Variable(age_id)].as_variable().unwrap().restriction_based_selectivity(&[])
+    }
+
+    #[test]
+    fn comparisons_bound_the_right_hand_side_opposite_to_the_left_hand_side() {
+        use Comparator::{Greater, Less};
+
+        let lower_bound = age_selectivity(&[(true, Greater, 30)]);
+        let upper_bound = age_selectivity(&[(true, Less, 50)]);
+        assert_eq!(age_selectivity(&[(false, Less, 30)]), lower_bound);
+        assert_eq!(age_selectivity(&[(false, Greater, 50)]), upper_bound);
+
+        // `$age > 30; 40 < $age;` bounds $age from below twice, so only the stricter bound counts
+        assert_eq!(age_selectivity(&[(true, Greater, 30), (false, Less, 40)]), lower_bound);
+        assert_eq!(age_selectivity(&[(true, Less, 50), (false, Greater, 60)]), upper_bound);
+
+        // `$age > 30; 50 > $age;` bounds $age from both sides, which restricts it further than either bound
+        let range = age_selectivity(&[(true, Greater, 30), (false, Greater, 50)]);
+        assert!(range < lower_bound && range < upper_bound, "{range} should be below {lower_bound} and {upper_bound}");
+        assert_eq!(age_selectivity(&[(false, Less, 30), (true, Less, 50)]), range);
+    }
+}
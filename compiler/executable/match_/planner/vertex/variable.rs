@@ -4,7 +4,10 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use std::{collections::HashSet, fmt};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
 
 use answer::variable::Variable;
 use concept::thing::statistics::Statistics;
@@ -98,24 +101,33 @@ impl VariableVertex {
         }
     }
 
-    pub(crate) fn add_lower_bound(&mut self, other: Input) {
+    pub(crate) fn add_lower_bound(&mut self, other: Input, selectivity: Option<f64>) {
         match self {
             Self::Input(_) => (),
             Self::Type(_) => unreachable!(),
-            Self::Thing(inner) => inner.add_lower_bound(other),
+            Self::Thing(inner) => inner.add_lower_bound(other, selectivity),
             Self::Value(inner) => inner.add_lower_bound(other),
         }
     }
 
-    pub(crate) fn add_upper_bound(&mut self, other: Input) {
+    pub(crate) fn add_upper_bound(&mut self, other: Input, selectivity: Option<f64>) {
         match self {
             Self::Input(_) => (),
             Self::Type(_) => unreachable!(),
-            Self::Thing(inner) => inner.add_upper_bound(other),
+            Self::Thing(inner) => inner.add_upper_bound(other, selectivity),
             Self::Value(inner) => inner.add_upper_bound(other),
         }
     }
 
+    pub(crate) fn add_pattern_match(&mut self, other: Input, selectivity: f64) {
+        match self {
+            Self::Input(_) => (),
+            Self::Type(_) => unreachable!(),
+            Self::Thing(inner) => inner.add_pattern_match(other, selectivity),
+            Self::Value(_) => (),
+        }
+    }
+
     /// Returns `true` if the variable vertex is [`Input`].
     ///
     /// [`Input`]: VariableVertex::Input
@@ -215,8 +227,10 @@ pub(crate) struct ThingPlanner {
     restriction_exact: HashSet<VariableVertexId>, // IID or exact Type + Value
 
     restriction_equal: HashSet<Input>,
-    restriction_from_below: HashSet<Input>,
-    restriction_from_above: HashSet<Input>,
+    // each restriction maps to the fraction of the unrestricted size expected to pass it
+    restriction_from_below: HashMap<Input, f64>,
+    restriction_from_above: HashMap<Input, f64>,
+    restriction_pattern_match: HashMap<Input, f64>,
 }
 
 impl fmt::Debug for ThingPlanner {
@@ -271,8 +285,9 @@ impl ThingPlanner {
             unrestricted_expected_attribute_types,
             restriction_exact: HashSet::new(),
             restriction_equal: HashSet::new(),
-            restriction_from_below: HashSet::new(),
-            restriction_from_above: HashSet::new(),
+            restriction_from_below: HashMap::new(),
+            restriction_from_above: HashMap::new(),
+            restriction_pattern_match: HashMap::new(),
         }
     }
 
@@ -284,12 +299,32 @@ impl ThingPlanner {
         self.restriction_equal.insert(other);
     }
 
-    pub(crate) fn add_lower_bound(&mut self, other: Input) {
-        self.restriction_from_below.insert(other);
+    pub(crate) fn add_lower_bound(&mut self, other: Input, selectivity: Option<f64>) {
+        let selectivity = selectivity.unwrap_or(Self::RESTRICTION_BELOW_SELECTIVITY);
+        Self::add_restriction(&mut self.restriction_from_below, other, selectivity);
     }
 
-    pub(crate) fn add_upper_bound(&mut self, other: Input) {
-        self.restriction_from_above.insert(other);
+    pub(crate) fn add_upper_bound(&mut self, other: Input, selectivity: Option<f64>) {
+        let selectivity = selectivity.unwrap_or(Self::RESTRICTION_ABOVE_SELECTIVITY);
+        Self::add_restriction(&mut self.restriction_from_above, other, selectivity);
+    }
+
+    pub(crate) fn add_pattern_match(&mut self, other: Input, selectivity: f64) {
+        Self::add_restriction(&mut self.restriction_pattern_match, other, selectivity);
+    }
+
+    fn add_restriction(restrictions: &mut HashMap<Input, f64>, other: Input, selectivity: f64) {
+        // all fixed inputs share one entry, which keeps the strictest of them
+        let entry = restrictions.entry(other).or_insert(selectivity);
+        *entry = f64::min(*entry, selectivity);
+    }
+
+    fn strictest_available_restriction(restrictions: &HashMap<Input, f64>, inputs: &[VertexId]) -> Option<f64> {
+        restrictions
+            .iter()
+            .filter(|(restriction, _)| is_input_available(restriction, inputs))
+            .map(|(_, selectivity)| *selectivity)
+            .reduce(f64::min)
     }
 
     fn set_binding(&mut self, binding_pattern: PatternVertexId) {
@@ -315,14 +350,19 @@ impl ThingPlanner {
                 selected = self.unrestricted_expected_attribute_types as f64;
                 any_restrictions = true;
             }
-            if self.restriction_from_below.iter().any(|restriction| is_input_available(restriction, inputs)) {
+            if let Some(selectivity) = Self::strictest_available_restriction(&self.restriction_from_below, inputs) {
                 // some fraction of the selected will pass the strictest below filter
-                selected *= Self::RESTRICTION_BELOW_SELECTIVITY;
+                selected *= selectivity;
                 any_restrictions = true;
             }
-            if self.restriction_from_above.iter().any(|restriction| is_input_available(restriction, inputs)) {
+            if let Some(selectivity) = Self::strictest_available_restriction(&self.restriction_from_above, inputs) {
                 // some fraction of the selected will pass the strictest above filter
-                selected *= Self::RESTRICTION_ABOVE_SELECTIVITY;
+                selected *= selectivity;
+                any_restrictions = true;
+            }
+            if let Some(selectivity) = Self::strictest_available_restriction(&self.restriction_pattern_match, inputs) {
+                // some fraction of the selected will match the strictest pattern
+                selected *= selectivity;
                 any_restrictions = true;
             }
             // normalise again by all possible (with no restrictions, we get selectivity of 1.0)
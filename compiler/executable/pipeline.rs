@@ -14,7 +14,7 @@ use answer::{Type, variable::Variable};
 use concept::thing::statistics::Statistics;
 use ir::{
     pattern::{Pattern, Vertex, conjunction::Conjunction, nested_pattern::NestedPattern},
-    pipeline::{VariableRegistry, function_signature::FunctionID, reduce::AssignedReduction},
+    pipeline::{ParameterRegistry, VariableRegistry, function_signature::FunctionID, reduce::AssignedReduction},
 };
 
 use crate::{
@@ -141,6 +141,7 @@ fn insert_row_schema_to_mapping(
 
 pub fn compile_pipeline_and_functions(
     statistics: &Statistics,
+    value_parameters: &ParameterRegistry,
     variable_registry: &VariableRegistry,
     annotated_schema_functions: &AnnotatedSchemaFunctions,
     annotated_preamble: AnnotatedPreambleFunctions,
@@ -178,6 +179,7 @@ pub fn compile_pipeline_and_functions(
         ExecutableFunctionRegistry::new(arced_executable_schema_functions, executable_preamble_functions);
     let (_input_positions, executable_stages, executable_fetch, type_populations) = compile_stages_and_fetch(
         statistics,
+        value_parameters,
         variable_registry,
         &schema_and_preamble_functions,
         &annotated_stages,
@@ -196,6 +198,7 @@ pub fn compile_pipeline_and_functions(
 
 pub fn compile_stages_and_fetch(
     statistics: &Statistics,
+    value_parameters: &ParameterRegistry,
     variable_registry: &VariableRegistry,
     available_functions: &ExecutableFunctionRegistry,
     annotated_stages: &[AnnotatedStage],
@@ -207,6 +210,7 @@ pub fn compile_stages_and_fetch(
 > {
     let (input_positions, executable_stages, mut type_populations) = compile_pipeline_stages(
         statistics,
+        value_parameters,
         variable_registry,
         available_functions,
         annotated_stages,
@@ -218,7 +222,7 @@ pub fn compile_stages_and_fetch(
 
     if let Some(fetch) = annotated_fetch {
         let (executable_fetch, fetch_type_populations) =
-            compile_fetch(statistics, available_functions, fetch, &stages_variable_positions)
+            compile_fetch(statistics, value_parameters, available_functions, fetch, &stages_variable_positions)
                 .map_err(|err| ExecutableCompilationError::FetchCompilation { typedb_source: err })?;
         type_populations.extend(fetch_type_populations);
         Ok((input_positions, executable_stages, Some(Arc::new(executable_fetch)), type_populations))
@@ -229,6 +233,7 @@ pub fn compile_stages_and_fetch(
 
 pub(crate) fn compile_pipeline_stages(
     statistics: &Statistics,
+    value_parameters: &ParameterRegistry,
     variable_registry: &VariableRegistry,
     call_cost_provider: &impl FunctionCallCostProvider,
     annotated_stages: &[AnnotatedStage],
@@ -246,6 +251,7 @@ pub(crate) fn compile_pipeline_stages(
             match executable_stages.last().map(|stage| stage.output_row_mapping()) {
                 Some(row_mapping) => compile_stage(
                     statistics,
+                    value_parameters,
                     variable_registry,
                     call_cost_provider,
                     &row_mapping,
@@ -255,6 +261,7 @@ pub(crate) fn compile_pipeline_stages(
                 )?,
                 None => compile_stage(
                     statistics,
+                    value_parameters,
                     variable_registry,
                     call_cost_provider,
                     &input_variable_positions,
@@ -275,6 +282,7 @@ pub(crate) fn compile_pipeline_stages(
 
 fn compile_stage(
     statistics: &Statistics,
+    value_parameters: &ParameterRegistry,
     variable_registry: &VariableRegistry,
     call_cost_provider: &impl FunctionCallCostProvider,
     stage_input_positions: &HashMap<Variable, VariablePosition>,
@@ -297,6 +305,7 @@ fn compile_stage(
                 variable_registry,
                 executable_expressions,
                 statistics,
+                value_parameters,
                 call_cost_provider,
             )
             .map_err(|source| ExecutableCompilationError::MatchCompilation { typedb_source: source })?;
@@ -338,6 +347,7 @@ fn compile_stage(
                 variable_registry,
                 &HashMap::new(),
                 statistics,
+                value_parameters,
                 call_cost_provider,
             )
             .map_err(|source| ExecutableCompilationError::PutMatchCompilation { typedb_source: source })?;
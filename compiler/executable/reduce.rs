@@ -10,7 +10,9 @@ use answer::variable::Variable;
 use encoding::value::value_type::ValueType;
 use ir::{pattern::IrID, pipeline::reduce::Reducer};
 
-use crate::{VariablePosition, executable::next_executable_id};
+use crate::{
+    VariablePosition, annotation::expression::compiled_expression::ExpressionValueType, executable::next_executable_id,
+};
 
 #[derive(Debug, Clone)]
 pub struct ReduceExecutable {
@@ -49,6 +51,8 @@ pub enum ReduceInstruction<ID: IrID> {
     MaxDate(ID), MinDate(ID),
     MaxDateTime(ID), MinDateTime(ID),
     MaxDateTimeTZ(ID), MinDateTimeTZ(ID),
+    ListInteger(ID), ListDouble(ID), ListDecimal(ID), ListBoolean(ID), ListString(ID),
+    ListDate(ID), ListDateTime(ID), ListDateTimeTZ(ID), ListDuration(ID),
 }
 
 impl<ID: IrID> ReduceInstruction<ID> {
@@ -82,11 +86,35 @@ impl<ID: IrID> ReduceInstruction<ID> {
             | ReduceInstruction::MaxDateTime(id)
             | ReduceInstruction::MinDateTime(id)
             | ReduceInstruction::MaxDateTimeTZ(id)
-            | ReduceInstruction::MinDateTimeTZ(id) => Some(id),
+            | ReduceInstruction::MinDateTimeTZ(id)
+            | ReduceInstruction::ListInteger(id)
+            | ReduceInstruction::ListDouble(id)
+            | ReduceInstruction::ListDecimal(id)
+            | ReduceInstruction::ListBoolean(id)
+            | ReduceInstruction::ListString(id)
+            | ReduceInstruction::ListDate(id)
+            | ReduceInstruction::ListDateTime(id)
+            | ReduceInstruction::ListDateTimeTZ(id)
+            | ReduceInstruction::ListDuration(id) => Some(id),
         }
     }
 
-    pub fn output_type(&self) -> ValueType {
+    pub fn output_type(&self) -> ExpressionValueType {
+        match self {
+            Self::ListInteger(_) => ExpressionValueType::List(ValueType::Integer),
+            Self::ListDouble(_) => ExpressionValueType::List(ValueType::Double),
+            Self::ListDecimal(_) => ExpressionValueType::List(ValueType::Decimal),
+            Self::ListBoolean(_) => ExpressionValueType::List(ValueType::Boolean),
+            Self::ListString(_) => ExpressionValueType::List(ValueType::String),
+            Self::ListDate(_) => ExpressionValueType::List(ValueType::Date),
+            Self::ListDateTime(_) => ExpressionValueType::List(ValueType::DateTime),
+            Self::ListDateTimeTZ(_) => ExpressionValueType::List(ValueType::DateTimeTZ),
+            Self::ListDuration(_) => ExpressionValueType::List(ValueType::Duration),
+            _ => ExpressionValueType::Single(self.output_single_type()),
+        }
+    }
+
+    fn output_single_type(&self) -> ValueType {
         match self {
             Self::Count => ValueType::Integer,
             Self::CountVar(_) => ValueType::Integer,
@@ -123,6 +151,16 @@ impl<ID: IrID> ReduceInstruction<ID> {
 
             Self::MaxDateTimeTZ(_) => ValueType::DateTimeTZ,
             Self::MinDateTimeTZ(_) => ValueType::DateTimeTZ,
+
+            Self::ListInteger(_)
+            | Self::ListDouble(_)
+            | Self::ListDecimal(_)
+            | Self::ListBoolean(_)
+            | Self::ListString(_)
+            | Self::ListDate(_)
+            | Self::ListDateTime(_)
+            | Self::ListDateTimeTZ(_)
+            | Self::ListDuration(_) => unreachable!("List reducers output a list"),
         }
     }
 
@@ -156,6 +194,15 @@ impl<ID: IrID> ReduceInstruction<ID> {
             ReduceInstruction::MinDateTime(id) => ReduceInstruction::MinDateTime(mapping[&id]),
             ReduceInstruction::MaxDateTimeTZ(id) => ReduceInstruction::MaxDateTimeTZ(mapping[&id]),
             ReduceInstruction::MinDateTimeTZ(id) => ReduceInstruction::MinDateTimeTZ(mapping[&id]),
+            ReduceInstruction::ListInteger(id) => ReduceInstruction::ListInteger(mapping[&id]),
+            ReduceInstruction::ListDouble(id) => ReduceInstruction::ListDouble(mapping[&id]),
+            ReduceInstruction::ListDecimal(id) => ReduceInstruction::ListDecimal(mapping[&id]),
+            ReduceInstruction::ListBoolean(id) => ReduceInstruction::ListBoolean(mapping[&id]),
+            ReduceInstruction::ListString(id) => ReduceInstruction::ListString(mapping[&id]),
+            ReduceInstruction::ListDate(id) => ReduceInstruction::ListDate(mapping[&id]),
+            ReduceInstruction::ListDateTime(id) => ReduceInstruction::ListDateTime(mapping[&id]),
+            ReduceInstruction::ListDateTimeTZ(id) => ReduceInstruction::ListDateTimeTZ(mapping[&id]),
+            ReduceInstruction::ListDuration(id) => ReduceInstruction::ListDuration(mapping[&id]),
         }
     }
 }
@@ -183,6 +230,15 @@ impl ReduceInstruction<Variable> {
             Self::MeanInteger(id) | Self::MeanDouble(id) | Self::MeanDecimal(id) => Reducer::Mean(id),
             Self::MedianInteger(id) | Self::MedianDouble(id) | Self::MedianDecimal(id) => Reducer::Median(id),
             Self::StdInteger(id) | Self::StdDouble(id) | Self::StdDecimal(id) => Reducer::Std(id),
+            Self::ListInteger(id)
+            | Self::ListDouble(id)
+            | Self::ListDecimal(id)
+            | Self::ListBoolean(id)
+            | Self::ListString(id)
+            | Self::ListDate(id)
+            | Self::ListDateTime(id)
+            | Self::ListDateTimeTZ(id)
+            | Self::ListDuration(id) => Reducer::List(id),
         }
     }
 }
@@ -535,7 +535,8 @@ impl From<&AssignedReduction> for StructureReduceAssign {
             | Reducer::Mean(var)
             | Reducer::Median(var)
             | Reducer::Min(var)
-            | Reducer::Std(var) => vec![var.into()],
+            | Reducer::Std(var)
+            | Reducer::List(var) => vec![var.into()],
         };
         let reducer = StructureReducer { reducer: value.reduction.name().to_owned(), arguments };
         StructureReduceAssign { assigned: value.assigned.into(), reducer }
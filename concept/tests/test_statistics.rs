@@ -25,7 +25,7 @@ use encoding::value::{label::Label, value::Value, value_type::ValueType};
 use resource::profile::{CommitProfile, StorageCounters};
 use storage::{
     MVCCStorage,
-    durability_client::WALClient,
+    durability_client::{DurabilityRecord, WALClient},
     sequence_number::SequenceNumber,
     snapshot::{CommittableSnapshot, ReadableSnapshot},
 };
@@ -648,3 +648,53 @@ fn relation_index_counts() {
     assert!(!read.links_index_counts.is_empty(), "expected links_index_counts to be populated by data-snapshot writes");
     assert_statistics_eq!(synchronised, read);
 }
+
+#[test]
+fn attribute_value_histograms() {
+    let (_tmp_dir, mut storage) = create_core_storage();
+    setup_concept_storage(&mut storage);
+    let (type_manager, thing_manager) = load_managers(storage.clone(), None);
+
+    let age_label = Label::build("age", None);
+
+    let mut snapshot = storage.clone().open_snapshot_schema();
+    let age_type = type_manager.create_attribute_type(&mut snapshot, &age_label).unwrap();
+    age_type.set_value_type(&mut snapshot, &type_manager, &thing_manager, ValueType::Integer).unwrap();
+    age_type
+        .set_annotation(
+            &mut snapshot,
+            &type_manager,
+            &thing_manager,
+            AttributeTypeAnnotation::Independent(AnnotationIndependent),
+            StorageCounters::DISABLED,
+        )
+        .unwrap();
+    let ages = (1..=100)
+        .map(|age| thing_manager.create_attribute(&mut snapshot, age_type, Value::Integer(age)).unwrap())
+        .collect::<Vec<_>>();
+    thing_manager.finalise(&mut snapshot, StorageCounters::DISABLED).unwrap();
+    let create_commit_seq = snapshot.commit(&mut CommitProfile::DISABLED).unwrap().unwrap();
+
+    let mut snapshot = storage.clone().open_snapshot_write_at(create_commit_seq);
+    for age in ages.into_iter().take(20) {
+        age.delete(&mut snapshot, &thing_manager, StorageCounters::DISABLED).unwrap();
+    }
+    thing_manager.finalise(&mut snapshot, StorageCounters::DISABLED).unwrap();
+    snapshot.commit(&mut CommitProfile::DISABLED).unwrap().unwrap();
+
+    let mut synchronised = Statistics::new(SequenceNumber::MIN);
+    synchronised.may_synchronise(&storage).unwrap();
+
+    let histogram = synchronised.complete_value_histogram(age_type).expect("expected a complete histogram of ages");
+    assert_eq!(histogram.count(), 80);
+    let above = histogram.fraction_above(&Value::Integer(60)).unwrap();
+    assert!((above - 0.5).abs() < 0.1, "expected about half of the ages above 60, estimated {above}");
+    let below = histogram.fraction_below(&Value::Integer(0)).unwrap();
+    assert_eq!(below, 0.0);
+    assert_eq!(histogram.fraction_like(&Value::String("^a".into())), None);
+
+    let mut buffer = Vec::new();
+    synchronised.serialise_into(&mut buffer).unwrap();
+    let deserialised = Statistics::deserialise_from(&mut buffer.as_slice()).unwrap();
+    assert_eq!(deserialised.attribute_value_histograms, synchronised.attribute_value_histograms);
+}
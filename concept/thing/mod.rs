@@ -32,6 +32,7 @@ pub mod relation;
 pub mod statistics;
 mod r#struct;
 pub mod thing_manager;
+pub mod value_histogram;
 
 pub trait ThingAPI: Sized + Clone {
     type TypeAPI: TypeAPI;
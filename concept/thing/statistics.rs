@@ -48,7 +48,10 @@ use storage::{
 use tracing::{Level, event};
 
 use crate::{
-    thing::{ThingAPI, attribute::Attribute, entity::Entity, object::Object, relation::Relation},
+    thing::{
+        ThingAPI, attribute::Attribute, entity::Entity, object::Object, relation::Relation,
+        value_histogram::ValueHistogram,
+    },
     type_::{
         TypeAPI, attribute_type::AttributeType, entity_type::EntityType, object_type::ObjectType,
         relation_type::RelationType, role_type::RoleType,
@@ -91,12 +94,17 @@ pub struct Statistics {
 
     // TODO: adding role types is possible, but won't help with filtering before reading storage since roles are not in the prefix
     pub links_index_counts: HashMap<ObjectType, HashMap<ObjectType, u64>>,
-    // future: attribute value distributions, attribute value ownership distributions, etc.
+
+    // Only describes all attributes of a type when its count matches the attribute count: statistics recorded before
+    // histograms were maintained start out with empty histograms
+    pub attribute_value_histograms: HashMap<AttributeType, ValueHistogram>,
+    // future: attribute value ownership distributions, etc.
 }
 
 impl Statistics {
-    const ENCODING_VERSION: StatisticsEncodingVersion = 0;
-    const COMMIT_CONTEXT_SIZE: u64 = 8;
+    const ENCODING_VERSION: StatisticsEncodingVersion = 1;
+    const ENCODING_VERSION_VALUE_HISTOGRAMS: StatisticsEncodingVersion = 1;
+    pub const COMMIT_CONTEXT_SIZE: u64 = 8;
     const COMMIT_CONTEXT_MEMORY_LIMIT: usize = 1 << 30; // 1 GiB
 
     pub fn new(sequence_number: SequenceNumber) -> Self {
@@ -123,6 +131,7 @@ impl Statistics {
             relation_role_player_counts: HashMap::new(),
             player_role_relation_counts: HashMap::new(),
             links_index_counts: HashMap::new(),
+            attribute_value_histograms: HashMap::new(),
         }
     }
 
@@ -257,8 +266,9 @@ impl Statistics {
                 self.update_relations(type_, delta);
                 total_delta += delta;
             } else if AttributeVertex::is_attribute_vertex(StorageKeyReference::from(&key)) {
-                let type_ = Attribute::new(AttributeVertex::decode(key.bytes())).type_();
-                self.update_attributes(type_, delta);
+                let attribute = Attribute::new(AttributeVertex::decode(key.bytes()));
+                self.update_attributes(attribute.type_(), delta);
+                self.update_attribute_values(attribute, delta);
             } else if ThingEdgeHas::is_has(&key) {
                 let edge = ThingEdgeHas::decode(Bytes::Reference(key.bytes()));
                 self.update_has(Object::new(edge.from()).type_(), Attribute::new(edge.to()).type_(), delta);
@@ -302,6 +312,7 @@ impl Statistics {
                     deferred_type_cleanups.push(Box::new(move |this: &mut Self| {
                         this.attribute_counts.remove(&type_);
                         this.attribute_owner_counts.remove(&type_);
+                        this.attribute_value_histograms.remove(&type_);
                         for map in this.has_attribute_counts.values_mut() {
                             map.remove(&type_);
                         }
@@ -387,6 +398,20 @@ impl Statistics {
         Self::saturating_add(&mut self.total_thing_count, delta, "total_thing");
     }
 
+    fn update_attribute_values(&mut self, attribute: Attribute, delta: i64) {
+        let histogram = self.attribute_value_histograms.entry(attribute.type_()).or_default();
+        histogram.update(attribute.vertex().attribute_id(), delta);
+        if histogram.is_empty() {
+            self.attribute_value_histograms.remove(&attribute.type_());
+        }
+    }
+
+    /// The value histogram of an attribute type, if it accounts for every attribute of the type
+    pub fn complete_value_histogram(&self, attribute_type: AttributeType) -> Option<&ValueHistogram> {
+        let attribute_count = self.attribute_counts.get(&attribute_type).copied().unwrap_or(0);
+        self.attribute_value_histograms.get(&attribute_type).filter(|histogram| histogram.count() == attribute_count)
+    }
+
     fn update_has(&mut self, owner_type: ObjectType, attribute_type: AttributeType, delta: i64) {
         let attribute_count =
             self.has_attribute_counts.entry(owner_type).or_default().entry(attribute_type).or_default();
@@ -533,6 +558,7 @@ impl Statistics {
         self.role_player_counts.clear();
         self.relation_role_counts.clear();
         self.links_index_counts.clear();
+        self.attribute_value_histograms.clear();
     }
 }
 
@@ -670,6 +696,7 @@ impl fmt::Debug for Statistics {
         write_hashmap!("relation_role_player_counts", self.relation_role_player_counts);
         write_hashmap!("player_role_relation_counts", self.player_role_relation_counts);
         write_hashmap!("links_index_counts", self.links_index_counts);
+        write_hashmap!("attribute_value_histograms", self.attribute_value_histograms);
 
         if pretty {
             write!(f, "}}")?;
@@ -793,7 +820,10 @@ mod serialise {
     };
 
     use crate::{
-        thing::statistics::{SerialisableType, Statistics},
+        thing::{
+            statistics::{SerialisableType, Statistics, StatisticsEncodingVersion},
+            value_histogram::ValueHistogram,
+        },
         type_::{
             attribute_type::AttributeType, entity_type::EntityType, object_type::ObjectType,
             relation_type::RelationType, role_type::RoleType,
@@ -822,10 +852,11 @@ mod serialise {
         RelationRolePlayerCounts,
         PlayerRoleRelationCounts,
         LinksIndexCounts,
+        AttributeValueHistograms,
     }
 
     impl Field {
-        const NAMES: [&'static str; 21] = [
+        const NAMES: [&'static str; 22] = [
             Self::StatisticsVersion.name(),
             Self::OpenSequenceNumber.name(),
             Self::LastDurableWriteTotalCount.name(),
@@ -847,6 +878,7 @@ mod serialise {
             Self::RelationRolePlayerCounts.name(),
             Self::PlayerRoleRelationCounts.name(),
             Self::LinksIndexCounts.name(),
+            Self::AttributeValueHistograms.name(),
         ];
 
         const fn name(&self) -> &str {
@@ -872,6 +904,7 @@ mod serialise {
                 Field::RelationRolePlayerCounts => "RelationRolePlayerCounts",
                 Field::PlayerRoleRelationCounts => "RolePlayerRelationCounts",
                 Field::LinksIndexCounts => "PlayerIndexCounts",
+                Field::AttributeValueHistograms => "AttributeValueHistograms",
             }
         }
 
@@ -898,6 +931,7 @@ mod serialise {
                 "RelationRolePlayerCounts" => Some(Field::RelationRolePlayerCounts),
                 "RolePlayerRelationCounts" => Some(Field::PlayerRoleRelationCounts),
                 "PlayerIndexCounts" => Some(Field::LinksIndexCounts),
+                "AttributeValueHistograms" => Some(Field::AttributeValueHistograms),
                 _ => None,
             }
         }
@@ -958,6 +992,11 @@ mod serialise {
             state
                 .serialize_field(Field::LinksIndexCounts.name(), &to_serialisable_map_map(&self.links_index_counts))?;
 
+            state.serialize_field(
+                Field::AttributeValueHistograms.name(),
+                &to_serialisable_histogram_map(&self.attribute_value_histograms),
+            )?;
+
             state.end()
         }
     }
@@ -984,6 +1023,18 @@ mod serialise {
         map.iter().map(|(type_, value)| (type_.clone().into(), *value)).collect()
     }
 
+    fn to_serialisable_histogram_map(
+        map: &HashMap<AttributeType, ValueHistogram>,
+    ) -> HashMap<SerialisableType, &ValueHistogram> {
+        map.iter().map(|(type_, histogram)| ((*type_).into(), histogram)).collect()
+    }
+
+    fn into_attribute_histogram_map(
+        map: HashMap<SerialisableType, ValueHistogram>,
+    ) -> HashMap<AttributeType, ValueHistogram> {
+        map.into_iter().map(|(type_, histogram)| (type_.into_attribute_type(), histogram)).collect()
+    }
+
     fn into_entity_map(map: HashMap<SerialisableType, u64>) -> HashMap<EntityType, u64> {
         map.into_iter().map(|(type_, value)| (type_.into_entity_type(), value)).collect()
     }
@@ -1048,7 +1099,8 @@ mod serialise {
                 where
                     V: SeqAccess<'de>,
                 {
-                    let statistics_version = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                    let statistics_version: StatisticsEncodingVersion =
+                        seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
                     let sequence_number = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
                     let last_durable_write_total_count =
                         seq.next_element()?.ok_or_else(|| de::Error::invalid_length(2, &self))?;
@@ -1133,8 +1185,18 @@ mod serialise {
                         .into_iter()
                         .map(|(type_1, map)| (type_1.into_object_type(), into_object_map(map)))
                         .collect();
+                    // records written before histograms were maintained end here
+                    let attribute_value_histograms =
+                        if statistics_version >= Statistics::ENCODING_VERSION_VALUE_HISTOGRAMS {
+                            into_attribute_histogram_map(
+                                seq.next_element()?.ok_or_else(|| de::Error::invalid_length(21, &self))?,
+                            )
+                        } else {
+                            HashMap::new()
+                        };
                     Ok(Statistics {
-                        encoding_version: statistics_version,
+                        // the statistics are upgraded to the current encoding once read
+                        encoding_version: Statistics::ENCODING_VERSION,
                         sequence_number,
                         last_durable_write_sequence_number: sequence_number,
                         last_durable_write_total_count,
@@ -1156,6 +1218,7 @@ mod serialise {
                         relation_role_player_counts,
                         player_role_relation_counts,
                         links_index_counts,
+                        attribute_value_histograms,
                     })
                 }
 
@@ -1184,6 +1247,7 @@ mod serialise {
                     let mut relation_role_player_counts = None;
                     let mut player_role_relation_counts = None;
                     let mut links_indexs_counts = None;
+                    let mut attribute_value_histograms = None;
                     while let Some(key) = map.next_key()? {
                         match key {
                             Field::StatisticsVersion => {
@@ -1383,12 +1447,20 @@ mod serialise {
                                         .collect(),
                                 );
                             }
+                            Field::AttributeValueHistograms => {
+                                if attribute_value_histograms.is_some() {
+                                    return Err(de::Error::duplicate_field(Field::AttributeValueHistograms.name()));
+                                }
+                                attribute_value_histograms = Some(into_attribute_histogram_map(map.next_value()?));
+                            }
                         }
                     }
 
+                    let _: StatisticsEncodingVersion =
+                        statistics_version.ok_or_else(|| de::Error::missing_field(Field::StatisticsVersion.name()))?;
                     Ok(Statistics {
-                        encoding_version: statistics_version
-                            .ok_or_else(|| de::Error::missing_field(Field::StatisticsVersion.name()))?,
+                        // the statistics are upgraded to the current encoding once read
+                        encoding_version: Statistics::ENCODING_VERSION,
                         sequence_number: open_sequence_number
                             .ok_or_else(|| de::Error::missing_field(Field::OpenSequenceNumber.name()))?,
                         last_durable_write_total_count: last_durable_write_total_count
@@ -1429,6 +1501,8 @@ mod serialise {
                             .ok_or_else(|| de::Error::missing_field(Field::PlayerRoleRelationCounts.name()))?,
                         links_index_counts: links_indexs_counts
                             .ok_or_else(|| de::Error::missing_field(Field::LinksIndexCounts.name()))?,
+                        // absent from statistics written before histograms were maintained
+                        attribute_value_histograms: attribute_value_histograms.unwrap_or_default(),
                     })
                 }
             }
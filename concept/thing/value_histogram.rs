@@ -0,0 +1,135 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::BTreeMap;
+
+use chrono::Datelike;
+use encoding::{graph::thing::vertex_attribute::AttributeID, value::value::Value};
+use serde::{Deserialize, Serialize};
+
+/// The distribution of the values of one attribute type, counted in buckets that preserve the order of the values.
+///
+/// Bucket boundaries are fixed functions of the value, rather than derived from the data, so the histogram can be
+/// maintained one insertion or deletion at a time:
+///  - numbers fall into buckets of bounded relative width, so small and large magnitudes are both resolved
+///  - dates and datetimes fall into one bucket per day
+///  - strings fall into one bucket per first character
+///
+/// Durations and structs have no total order, and are not counted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValueHistogram {
+    count: u64,
+    buckets: BTreeMap<ValueBucket, u64>,
+}
+
+type ValueBucket = i64;
+
+impl ValueHistogram {
+    const NUMERIC_BUCKETS_PER_DOUBLING: f64 = 8.0;
+    const EMPTY_STRING_BUCKET: ValueBucket = -1;
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub(crate) fn update(&mut self, attribute_id: AttributeID, delta: i64) {
+        let Some(bucket) = Self::bucket_of_attribute(attribute_id) else {
+            return;
+        };
+        let bucket_count = self.buckets.entry(bucket).or_default();
+        *bucket_count = bucket_count.saturating_add_signed(delta);
+        if *bucket_count == 0 {
+            self.buckets.remove(&bucket);
+        }
+        self.count = self.count.saturating_add_signed(delta);
+    }
+
+    /// The estimated fraction of values ordered before `value`, or `None` if `value` cannot be placed in this histogram
+    pub fn fraction_below(&self, value: &Value<'_>) -> Option<f64> {
+        let bucket = Self::bucket_of_value(value)?;
+        if self.is_empty() {
+            return None;
+        }
+        let below: u64 = self.buckets.range(..bucket).map(|(_, count)| count).sum();
+        // values are assumed to be spread evenly within a bucket
+        let within = self.buckets.get(&bucket).copied().unwrap_or(0) as f64 / 2.0;
+        Some((below as f64 + within) / self.count as f64)
+    }
+
+    /// The estimated fraction of values ordered after `value`, or `None` if `value` cannot be placed in this histogram
+    pub fn fraction_above(&self, value: &Value<'_>) -> Option<f64> {
+        self.fraction_below(value).map(|below| 1.0 - below)
+    }
+
+    /// The estimated fraction of string values matching a `like` regex, or `None` unless the regex is anchored to a
+    /// literal first character
+    pub fn fraction_like(&self, pattern: &Value<'_>) -> Option<f64> {
+        let Value::String(pattern) = pattern else {
+            return None;
+        };
+        let mut pattern = pattern.strip_prefix('^')?.chars();
+        let first = pattern.next().filter(|first| first.is_alphanumeric())?;
+        // a quantifier after the first character may skip it
+        if matches!(pattern.next(), Some('?' | '*' | '{')) || self.is_empty() {
+            return None;
+        }
+        let matching = self.buckets.get(&Self::string_bucket(Some(first))).copied().unwrap_or(0);
+        Some(matching as f64 / self.count as f64)
+    }
+
+    fn bucket_of_attribute(attribute_id: AttributeID) -> Option<ValueBucket> {
+        match attribute_id {
+            AttributeID::Boolean(id) => Some(id.read().as_bool() as ValueBucket),
+            AttributeID::Integer(id) => Some(Self::numeric_bucket(id.read().as_i64() as f64)),
+            AttributeID::Double(id) => Some(Self::numeric_bucket(id.read().as_f64())),
+            AttributeID::Decimal(id) => Some(Self::numeric_bucket(id.read().as_decimal().to_f64())),
+            AttributeID::Date(id) => Some(id.read().as_naive_date().num_days_from_ce() as ValueBucket),
+            AttributeID::DateTime(id) => Some(id.read().as_naive_date_time().num_days_from_ce() as ValueBucket),
+            AttributeID::DateTimeTZ(id) => Some(id.read().as_date_time().naive_utc().num_days_from_ce() as ValueBucket),
+            AttributeID::String(id) => {
+                // long strings are identified by a hash, but keep enough of their prefix for the first character
+                let first = if id.is_inline() {
+                    id.get_inline_id_value().as_str().chars().next()
+                } else {
+                    String::from_utf8_lossy(&id.get_hash_prefix()).chars().next()
+                };
+                Some(Self::string_bucket(first))
+            }
+            AttributeID::Duration(_) | AttributeID::Struct(_) => None,
+        }
+    }
+
+    fn bucket_of_value(value: &Value<'_>) -> Option<ValueBucket> {
+        match value {
+            Value::Boolean(boolean) => Some(*boolean as ValueBucket),
+            Value::Integer(integer) => Some(Self::numeric_bucket(*integer as f64)),
+            Value::Double(double) => Some(Self::numeric_bucket(*double)),
+            Value::Decimal(decimal) => Some(Self::numeric_bucket(decimal.to_f64())),
+            Value::Date(date) => Some(date.num_days_from_ce() as ValueBucket),
+            Value::DateTime(date_time) => Some(date_time.num_days_from_ce() as ValueBucket),
+            Value::DateTimeTZ(date_time_tz) => Some(date_time_tz.naive_utc().num_days_from_ce() as ValueBucket),
+            Value::String(string) => Some(Self::string_bucket(string.chars().next())),
+            Value::Duration(_) | Value::Struct(_) => None,
+        }
+    }
+
+    fn numeric_bucket(number: f64) -> ValueBucket {
+        if number.is_nan() {
+            return 0;
+        }
+        let magnitude = ((1.0 + number.abs()).log2() * Self::NUMERIC_BUCKETS_PER_DOUBLING).floor() as ValueBucket;
+        if number < 0.0 { -magnitude - 1 } else { magnitude }
+    }
+
+    fn string_bucket(first: Option<char>) -> ValueBucket {
+        // UTF-8 orders strings by the code points of their characters
+        first.map_or(Self::EMPTY_STRING_BUCKET, |first| first as ValueBucket)
+    }
+}
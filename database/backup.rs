@@ -0,0 +1,362 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use durability::{DurabilityService, DurabilityServiceError, wal::WAL};
+use error::typedb_error;
+use storage::{
+    durability_client::{DurabilityClient, DurabilityClientError},
+    sequence_number::SequenceNumber,
+};
+use tracing::debug;
+
+const BACKUP_MANIFEST_FILE_NAME: &str = "BACKUP_MANIFEST";
+const INCREMENTS_DIR_NAME: &str = "increments";
+const TEMP_FILE_EXTENSION: &str = "tmp";
+
+/// Describes a backup directory: the storage checkpoint it was taken from, followed by contiguous ranges of WAL
+/// records. The first segment is the WAL copied by the full backup, and each incremental backup adds a segment
+/// with only the records committed since the previous one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupManifest {
+    pub database_name: String,
+    pub checkpoint_sequence_number: SequenceNumber,
+    pub segments: Vec<BackupSegment>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackupSegment {
+    pub start: SequenceNumber,
+    pub end: SequenceNumber,
+}
+
+impl BackupManifest {
+    pub fn new(database_name: String, checkpoint_sequence_number: SequenceNumber, base: BackupSegment) -> Self {
+        Self { database_name, checkpoint_sequence_number, segments: vec![base] }
+    }
+
+    pub fn exists(backup_directory: &Path) -> bool {
+        backup_directory.join(BACKUP_MANIFEST_FILE_NAME).is_file()
+    }
+
+    pub fn read(backup_directory: &Path) -> Result<Self, BackupError> {
+        use BackupError::{ManifestMalformed, ManifestRead};
+
+        let path = backup_directory.join(BACKUP_MANIFEST_FILE_NAME);
+        let contents = fs::read_to_string(&path).map_err(|source| ManifestRead { path, source: Arc::new(source) })?;
+        let malformed = |line: &str| ManifestMalformed { dir: backup_directory.to_owned(), line: line.to_owned() };
+
+        let mut database_name = None;
+        let mut checkpoint_sequence_number = None;
+        let mut segments = Vec::new();
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            match parts.as_slice() {
+                ["database", name] => database_name = Some(name.to_string()),
+                ["checkpoint", number] => {
+                    checkpoint_sequence_number = Some(parse_sequence_number(number).ok_or_else(|| malformed(line))?)
+                }
+                ["segment", start, end] => {
+                    let start = parse_sequence_number(start).ok_or_else(|| malformed(line))?;
+                    let end = parse_sequence_number(end).ok_or_else(|| malformed(line))?;
+                    if segments.last().is_some_and(|last: &BackupSegment| last.end.next() != start) {
+                        return Err(malformed(line));
+                    }
+                    segments.push(BackupSegment { start, end });
+                }
+                _ => return Err(malformed(line)),
+            }
+        }
+
+        match (database_name, checkpoint_sequence_number, segments.is_empty()) {
+            (Some(database_name), Some(checkpoint_sequence_number), false) => {
+                Ok(Self { database_name, checkpoint_sequence_number, segments })
+            }
+            _ => Err(malformed("<missing entries>")),
+        }
+    }
+
+    pub fn write(&self, backup_directory: &Path) -> Result<(), BackupError> {
+        use BackupError::ManifestWrite;
+
+        let mut contents =
+            format!("database {}\ncheckpoint {}\n", self.database_name, self.checkpoint_sequence_number.number());
+        for segment in &self.segments {
+            contents.push_str(&format!("segment {} {}\n", segment.start.number(), segment.end.number()));
+        }
+
+        let path = backup_directory.join(BACKUP_MANIFEST_FILE_NAME);
+        let tmp = path.with_extension(TEMP_FILE_EXTENSION);
+        fs::write(&tmp, contents)
+            .and_then(|_| fs::rename(&tmp, &path))
+            .map_err(|source| ManifestWrite { path, source: Arc::new(source) })
+    }
+
+    pub fn last_sequence_number(&self) -> SequenceNumber {
+        self.segments.last().expect("A backup manifest always has the base segment").end
+    }
+
+    fn segment_directory(backup_directory: &Path, segment: &BackupSegment) -> PathBuf {
+        backup_directory.join(INCREMENTS_DIR_NAME).join(format!("{}-{}", segment.start.number(), segment.end.number()))
+    }
+
+    // The base segment is the WAL of the full backup itself
+    fn last_segment_directory(&self, backup_directory: &Path) -> PathBuf {
+        match self.segments.as_slice() {
+            [_base] => backup_directory.to_owned(),
+            [.., last] => Self::segment_directory(backup_directory, last),
+            [] => unreachable!("A backup manifest always has the base segment"),
+        }
+    }
+}
+
+fn parse_sequence_number(number: &str) -> Option<SequenceNumber> {
+    number.parse().ok().map(SequenceNumber::new)
+}
+
+/// Writes the WAL records following the last segment of `manifest` as a new increment of the backup in
+/// `backup_directory`. The live WAL is copied file by file first, so commits are not blocked while the records are
+/// filtered.
+///
+/// Unsequenced records, such as commit statuses, take the sequence number of the latest record when they are
+/// written. Those written at the end of the last segment after it was copied are carried into the new increment,
+/// ahead of its start.
+pub(crate) fn write_increment(
+    durability_client: &impl DurabilityClient,
+    backup_directory: &Path,
+    manifest: &BackupManifest,
+) -> Result<BackupSegment, BackupError> {
+    use BackupError::{DurabilityCopy, StagingCleanup};
+
+    let previous_end = manifest.last_sequence_number();
+    let start = previous_end.next();
+    let backed_up_at_previous_end = count_records_at(&manifest.last_segment_directory(backup_directory), previous_end)?;
+    let increments_directory = backup_directory.join(INCREMENTS_DIR_NAME);
+    let staging_directory = increments_directory.join(format!("{}.{TEMP_FILE_EXTENSION}", start.number()));
+    let result = durability_client
+        .copy_from(previous_end, &staging_directory)
+        .map_err(|typedb_source| DurabilityCopy { typedb_source })
+        .and_then(|end| {
+            let segment = BackupSegment { start, end };
+            let segment_directory = BackupManifest::segment_directory(backup_directory, &segment);
+            filter_increment(&staging_directory, &segment_directory, segment, backed_up_at_previous_end)
+                .inspect_err(|_| {
+                    let _ = fs::remove_dir_all(&segment_directory);
+                })
+                .map(|_| segment)
+        });
+    let cleanup = fs::remove_dir_all(&staging_directory);
+    let segment = result?;
+    cleanup.map_err(|source| StagingCleanup { dir: staging_directory, source: Arc::new(source) })?;
+    Ok(segment)
+}
+
+fn count_records_at(records_directory: &Path, sequence_number: SequenceNumber) -> Result<usize, BackupError> {
+    use BackupError::SegmentRead;
+
+    let wal =
+        WAL::load(records_directory).map_err(|source| SegmentRead { dir: records_directory.to_owned(), source })?;
+    let mut count = 0;
+    for record in wal
+        .iter_any_from(sequence_number)
+        .map_err(|source| SegmentRead { dir: records_directory.to_owned(), source })?
+    {
+        let record = record.map_err(|source| SegmentRead { dir: records_directory.to_owned(), source })?;
+        if record.sequence_number > sequence_number {
+            break;
+        } else if record.sequence_number == sequence_number {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+// Copies the records of the segment, and the records at the end of the previous segment after the first
+// `backed_up_at_previous_end`, which were written after the previous segment was copied
+fn filter_increment(
+    staging_directory: &Path,
+    segment_directory: &Path,
+    segment: BackupSegment,
+    backed_up_at_previous_end: usize,
+) -> Result<(), BackupError> {
+    use BackupError::{SegmentRead, SegmentWrite};
+
+    debug!("Writing backup increment of records {} to {} into {segment_directory:?}", segment.start, segment.end);
+    let staged =
+        WAL::load(staging_directory).map_err(|source| SegmentRead { dir: staging_directory.to_owned(), source })?;
+    let increment =
+        WAL::create(segment_directory).map_err(|source| SegmentWrite { dir: segment_directory.to_owned(), source })?;
+    let previous_end = segment.start.previous();
+    let records = staged
+        .iter_any_from(previous_end)
+        .map_err(|source| SegmentRead { dir: staging_directory.to_owned(), source })?;
+    let mut skipped_at_previous_end = 0;
+    for record in records {
+        let record = record.map_err(|source| SegmentRead { dir: staging_directory.to_owned(), source })?;
+        if record.sequence_number > segment.end {
+            break;
+        } else if record.sequence_number < previous_end {
+            continue;
+        } else if record.sequence_number == previous_end && skipped_at_previous_end < backed_up_at_previous_end {
+            skipped_at_previous_end += 1;
+            continue;
+        }
+        increment.append_copied(record).map_err(|source| SegmentWrite { dir: segment_directory.to_owned(), source })?;
+    }
+    let _ = increment.request_sync(true).recv();
+    Ok(())
+}
+
+/// Copies the checkpoint and base WAL of a backup into a new database directory.
+pub(crate) fn copy_base(backup_directory: &Path, database_directory: &Path) -> Result<(), BackupError> {
+    copy_directory(backup_directory, database_directory, &[BACKUP_MANIFEST_FILE_NAME, INCREMENTS_DIR_NAME])
+        .map_err(|source| BackupError::BaseCopy { dir: backup_directory.to_owned(), source: Arc::new(source) })
+}
+
+/// Appends the records of every increment to the WAL of a database restored with `copy_base`. Opening the database
+/// afterwards replays them on top of the checkpoint through the usual commit recovery.
+pub(crate) fn append_increments(
+    manifest: &BackupManifest,
+    backup_directory: &Path,
+    database_directory: &Path,
+) -> Result<(), BackupError> {
+    use BackupError::{SegmentMissingRecords, SegmentRead, SegmentWrite};
+
+    if manifest.segments.len() == 1 {
+        return Ok(());
+    }
+    let wal =
+        WAL::load(database_directory).map_err(|source| SegmentWrite { dir: database_directory.to_owned(), source })?;
+    for segment in &manifest.segments[1..] {
+        let segment_directory = BackupManifest::segment_directory(backup_directory, segment);
+        let increment =
+            WAL::load(&segment_directory).map_err(|source| SegmentRead { dir: segment_directory.clone(), source })?;
+        let resume_after = wal.previous();
+        let records = increment
+            .iter_any_from(segment.start.previous())
+            .map_err(|source| SegmentRead { dir: segment_directory.clone(), source })?;
+        for record in records {
+            let record = record.map_err(|source| SegmentRead { dir: segment_directory.clone(), source })?;
+            // Records before the segment's start were carried over from the end of the previous segment
+            let is_carried = record.sequence_number < segment.start;
+            if record.sequence_number < resume_after || (!is_carried && record.sequence_number <= resume_after) {
+                continue;
+            }
+            if record.sequence_number > wal.previous().next() {
+                return Err(SegmentMissingRecords {
+                    dir: segment_directory,
+                    expected: wal.previous().next().number(),
+                    found: record.sequence_number.number(),
+                });
+            }
+            wal.append_copied(record).map_err(|source| SegmentWrite { dir: database_directory.to_owned(), source })?;
+        }
+    }
+    let _ = wal.request_sync(true).recv();
+    Ok(())
+}
+
+fn copy_directory(source: &Path, target: &Path, excluded: &[&str]) -> io::Result<()> {
+    fs::create_dir_all(target)?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        if excluded.iter().any(|name| entry.file_name() == *name) {
+            continue;
+        }
+        let target_path = target.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_directory(&entry.path(), &target_path, &[])?;
+        } else {
+            fs::copy(entry.path(), &target_path)?;
+        }
+    }
+    Ok(())
+}
+
+typedb_error! {
+    pub BackupError(component = "Backup", prefix = "BKP") {
+        ManifestRead(1, "Error reading backup manifest '{path:?}'.", path: PathBuf, source: Arc<io::Error>),
+        ManifestWrite(2, "Error writing backup manifest '{path:?}'.", path: PathBuf, source: Arc<io::Error>),
+        ManifestMalformed(3, "Backup manifest in '{dir:?}' is malformed at '{line}'.", dir: PathBuf, line: String),
+        DurabilityCopy(4, "Error copying write-ahead log records for the backup.", typedb_source: DurabilityClientError),
+        SegmentRead(5, "Error reading write-ahead log records from '{dir:?}'.", dir: PathBuf, source: DurabilityServiceError),
+        SegmentWrite(6, "Error writing write-ahead log records into '{dir:?}'.", dir: PathBuf, source: DurabilityServiceError),
+        SegmentMissingRecords(7, "Backup increment '{dir:?}' is missing records: expected sequence number {expected} but found {found}.", dir: PathBuf, expected: u64, found: u64),
+        StagingCleanup(8, "Error removing temporary backup directory '{dir:?}'.", dir: PathBuf, source: Arc<io::Error>),
+        BaseCopy(9, "Error copying the full backup in '{dir:?}'.", dir: PathBuf, source: Arc<io::Error>),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Cow, path::Path};
+
+    use durability::{DurabilityRecordType, DurabilityService, RawRecord, wal::WAL};
+    use storage::{
+        durability_client::{DurabilityClient, WALClient},
+        sequence_number::SequenceNumber,
+    };
+    use test_utils::create_tmp_dir;
+
+    use super::{BackupManifest, BackupSegment, append_increments, copy_base, write_increment};
+
+    const COMMIT: DurabilityRecordType = 0;
+    const STATUS: DurabilityRecordType = 1;
+
+    fn append(client: &WALClient, sequence_number: u64, record_type: DurabilityRecordType) {
+        let sequence_number = SequenceNumber::new(sequence_number);
+        let bytes = Cow::Owned(vec![record_type]);
+        client.append_replicated(RawRecord { sequence_number, record_type, bytes }).unwrap();
+    }
+
+    fn records(directory: &Path) -> Vec<(u64, DurabilityRecordType)> {
+        let wal = WAL::load(directory).unwrap();
+        let records = wal.iter_any_from(SequenceNumber::MIN).unwrap();
+        records
+            .map(|record| record.unwrap())
+            .map(|record| (record.sequence_number.number(), record.record_type))
+            .collect()
+    }
+
+    #[test]
+    fn records_written_at_the_end_of_a_segment_are_carried_into_the_next_increment() {
+        let database_directory = create_tmp_dir("backup_source");
+        let backup_directory = create_tmp_dir("backup");
+        let restore_directory = create_tmp_dir("backup_restore");
+        let client = WALClient::new(WAL::create(&database_directory).unwrap());
+
+        append(&client, 1, COMMIT);
+        let base_end = client.copy_from(SequenceNumber::new(1), &backup_directory).unwrap();
+        let base = BackupSegment { start: SequenceNumber::new(1), end: base_end };
+        let mut manifest = BackupManifest::new("database".to_owned(), SequenceNumber::new(1), base);
+
+        // The status of the last backed up commit is written after the base was copied
+        append(&client, 1, STATUS);
+        append(&client, 2, COMMIT);
+        let segment = write_increment(&client, &backup_directory, &manifest).unwrap();
+        assert_eq!(segment, BackupSegment { start: SequenceNumber::new(2), end: SequenceNumber::new(2) });
+        let segment_directory = BackupManifest::segment_directory(&backup_directory, &segment);
+        assert_eq!(records(&segment_directory), vec![(1, STATUS), (2, COMMIT)]);
+        manifest.segments.push(segment);
+
+        // Nothing was written at the end of the previous increment, so nothing is carried again
+        append(&client, 3, COMMIT);
+        let segment = write_increment(&client, &backup_directory, &manifest).unwrap();
+        let segment_directory = BackupManifest::segment_directory(&backup_directory, &segment);
+        assert_eq!(records(&segment_directory), vec![(3, COMMIT)]);
+        manifest.segments.push(segment);
+
+        copy_base(&backup_directory, &restore_directory).unwrap();
+        append_increments(&manifest, &backup_directory, &restore_directory).unwrap();
+        assert_eq!(records(&restore_directory), records(&database_directory));
+        assert_eq!(records(&restore_directory), vec![(1, COMMIT), (1, STATUS), (2, COMMIT), (3, COMMIT)]);
+    }
+}
@@ -0,0 +1,355 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+use concept::{
+    error::ConceptReadError,
+    thing::{
+        ThingAPI, attribute::Attribute, entity::Entity, object::Object, relation::Relation, thing_manager::ThingManager,
+    },
+    type_::{
+        TypeAPI,
+        role_type::RoleType,
+        type_manager::{
+            TypeManager,
+            type_cache::{TypeCache, TypeCacheCreateError},
+        },
+    },
+};
+use encoding::{
+    graph::{
+        thing::{
+            ThingVertex,
+            edge::{ThingEdgeHas, ThingEdgeLinks},
+            vertex_attribute::AttributeVertex,
+            vertex_object::ObjectVertex,
+        },
+        type_::vertex::PrefixedTypeVertexEncoding,
+    },
+    value::{label::Label, value::Value},
+};
+use error::typedb_error;
+use resource::{
+    constants::{database::CHANGE_STREAM_BATCH_COMMITS, snapshot::BUFFER_KEY_INLINE},
+    profile::StorageCounters,
+};
+use storage::{
+    durability_client::DurabilityClient,
+    history::HistoricalReadError,
+    key_value::{StorageKeyArray, StorageKeyReference},
+    record::{CommitRecord, CommitType},
+    recovery::commit_recovery::{RecoveryCommitStatus, StorageRecoveryError, load_resolved_commit_data_from},
+    sequence_number::SequenceNumber,
+    snapshot::{ReadSnapshot, ReadableSnapshot, SnapshotGetError, write::Write},
+};
+
+use crate::Database;
+
+/// Tails the commit records in the write-ahead log and decodes them into logical data changes, in commit order.
+/// Each poll resumes after the last commit it reported, so a consumer that records the sequence number of the
+/// last commit it processed can reconnect with a new stream starting from the next one.
+///
+/// Deleted concepts are described as of just before their commit, which can only be read within the history
+/// retention window of the database.
+pub struct ChangeStream<D> {
+    database: Arc<Database<D>>,
+    next_sequence_number: SequenceNumber,
+    batch_commits: usize,
+    // The schema as of the last decoded commit, which stays valid until the next schema commit
+    schema: Option<SchemaView>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CommittedChanges {
+    pub sequence_number: SequenceNumber,
+    pub events: Vec<ChangeEvent>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChangeEvent {
+    pub operation: ChangeOperation,
+    pub change: Change,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeOperation {
+    Insert,
+    Delete,
+}
+
+#[derive(Debug, Clone)]
+pub enum Change {
+    Entity {
+        entity: Entity,
+        type_label: Label,
+    },
+    Relation {
+        relation: Relation,
+        type_label: Label,
+    },
+    Attribute {
+        attribute: Attribute,
+        type_label: Label,
+        value: Value<'static>,
+    },
+    Has {
+        owner: Object,
+        owner_type_label: Label,
+        attribute: Attribute,
+        attribute_type_label: Label,
+        value: Value<'static>,
+    },
+    RolePlayer {
+        relation: Relation,
+        relation_type_label: Label,
+        player: Object,
+        player_type_label: Label,
+        role_label: Label,
+    },
+}
+
+impl<D: DurabilityClient> ChangeStream<D> {
+    pub fn new(database: Arc<Database<D>>, start: SequenceNumber) -> Self {
+        Self {
+            database,
+            next_sequence_number: start.max(SequenceNumber::MIN.next()),
+            batch_commits: CHANGE_STREAM_BATCH_COMMITS,
+            schema: None,
+        }
+    }
+
+    /// Starts after the latest applied commit, so only commits made from now on are reported.
+    pub fn new_from_latest(database: Arc<Database<D>>) -> Self {
+        let start = database.storage.snapshot_watermark().next();
+        Self::new(database, start)
+    }
+
+    /// Limits how many commits a single poll reads from the write-ahead log.
+    pub fn with_batch_commits(mut self, batch_commits: usize) -> Self {
+        self.batch_commits = batch_commits.max(1);
+        self
+    }
+
+    pub fn next_sequence_number(&self) -> SequenceNumber {
+        self.next_sequence_number
+    }
+
+    /// Whether every commit applied to storage so far has been polled.
+    pub fn is_caught_up(&self) -> bool {
+        self.next_sequence_number > self.database.storage.snapshot_watermark()
+    }
+
+    /// Decodes the next batch of commits applied to storage since the last poll. Rejected commits and commits without
+    /// data changes advance the stream without being reported.
+    pub fn poll(&mut self) -> Result<Vec<CommittedChanges>, ChangeStreamError> {
+        use ChangeStreamError::CommitDataRead;
+
+        let watermark = self.database.storage.snapshot_watermark();
+        if self.next_sequence_number > watermark {
+            return Ok(Vec::new());
+        }
+
+        let commits = load_resolved_commit_data_from(
+            self.next_sequence_number,
+            self.batch_commits,
+            self.database.storage.durability(),
+        )
+        .map_err(|typedb_source| CommitDataRead { typedb_source })?;
+        let mut changes = Vec::new();
+        for (sequence_number, status) in
+            commits.into_iter().take_while(|(seq, _)| *seq <= watermark).take(self.batch_commits)
+        {
+            match status {
+                // the status of every applied commit is durable, so this only occurs at the tail of the log
+                RecoveryCommitStatus::Pending(_) => break,
+                RecoveryCommitStatus::Validated(record) => {
+                    let events = self.decode_commit(sequence_number, &record, watermark)?;
+                    if !events.is_empty() {
+                        changes.push(CommittedChanges { sequence_number, events });
+                    }
+                }
+                RecoveryCommitStatus::Rejected => (),
+            }
+            self.next_sequence_number = sequence_number.next();
+        }
+        Ok(changes)
+    }
+
+    fn decode_commit(
+        &mut self,
+        sequence_number: SequenceNumber,
+        record: &CommitRecord,
+        watermark: SequenceNumber,
+    ) -> Result<Vec<ChangeEvent>, ChangeStreamError> {
+        use ChangeStreamError::{HistoryNotRetained, SnapshotRead};
+
+        let operations = record.operations();
+        if !operations.iterate_writes().any(|(key, _)| is_data_change(&key)) {
+            if matches!(record.commit_type(), CommitType::Schema) {
+                self.schema = None;
+            }
+            return Ok(Vec::new());
+        }
+
+        // Inserted concepts are described as of the commit, and deleted concepts as of just before it. Only a schema
+        // commit changes the schema between the two.
+        let before_schema = match self.schema.take() {
+            Some(schema) => schema,
+            None => SchemaView::open(&self.database, sequence_number.previous())?,
+        };
+        let after_schema = match record.commit_type() {
+            CommitType::Data => None,
+            CommitType::Schema => Some(SchemaView::open(&self.database, sequence_number)?),
+        };
+        let before_snapshot = self.database.storage.clone().open_snapshot_read_at(sequence_number.previous());
+        let before = CommitView { snapshot: &before_snapshot, schema: &before_schema };
+        let after_snapshot = self.database.storage.clone().open_snapshot_read_at(sequence_number);
+        let after = CommitView { snapshot: &after_snapshot, schema: after_schema.as_ref().unwrap_or(&before_schema) };
+
+        let mut is_before_retained = false;
+        let mut events = Vec::new();
+        for (key, write) in operations.iterate_writes() {
+            if !is_data_change(&key) {
+                continue;
+            }
+            // Puts and deletes may be no-ops, depending on what concurrent commits did to the same key
+            let mut existed_before = || {
+                if !is_before_retained {
+                    self.database
+                        .storage
+                        .commit_history()
+                        .validate_sequence_number(sequence_number.previous(), watermark)
+                        .map_err(|typedb_source| HistoryNotRetained {
+                            sequence_number: sequence_number.number(),
+                            typedb_source,
+                        })?;
+                    is_before_retained = true;
+                }
+                before
+                    .snapshot
+                    .contains(StorageKeyReference::from(&key), StorageCounters::DISABLED)
+                    .map_err(|source| SnapshotRead { sequence_number: sequence_number.number(), source })
+            };
+            let (operation, view) = match write {
+                Write::Insert { .. } => (ChangeOperation::Insert, &after),
+                Write::Put { .. } if !existed_before()? => (ChangeOperation::Insert, &after),
+                Write::Delete if existed_before()? => (ChangeOperation::Delete, &before),
+                Write::Put { .. } | Write::Delete => continue,
+            };
+            let change = view.decode(&key).map_err(|typedb_source| ChangeStreamError::ConceptRead {
+                sequence_number: sequence_number.number(),
+                typedb_source,
+            })?;
+            events.push(ChangeEvent { operation, change });
+        }
+        self.schema = Some(after_schema.unwrap_or(before_schema));
+        Ok(events)
+    }
+}
+
+fn is_data_change(key: &StorageKeyArray<BUFFER_KEY_INLINE>) -> bool {
+    let key_reference = StorageKeyReference::from(key);
+    ObjectVertex::is_entity_vertex(key_reference)
+        || ObjectVertex::is_relation_vertex(key_reference)
+        || AttributeVertex::is_attribute_vertex(key_reference)
+        || ThingEdgeHas::is_has(key)
+        || ThingEdgeLinks::is_links(key)
+}
+
+/// The type and thing managers for the schema as of a commit. The database's own caches reflect the latest schema,
+/// which may postdate the commit, so the types are cached from the commit instead.
+struct SchemaView {
+    type_manager: Arc<TypeManager>,
+    thing_manager: ThingManager,
+}
+
+impl SchemaView {
+    fn open<D: DurabilityClient>(
+        database: &Database<D>,
+        sequence_number: SequenceNumber,
+    ) -> Result<Self, ChangeStreamError> {
+        let type_cache = TypeCache::new(database.storage.clone(), sequence_number).map_err(|typedb_source| {
+            ChangeStreamError::TypeCacheCreate { sequence_number: sequence_number.number(), typedb_source }
+        })?;
+        let type_manager = Arc::new(TypeManager::new(
+            database.definition_key_generator.clone(),
+            database.type_vertex_generator.clone(),
+            Some(Arc::new(type_cache)),
+        ));
+        let statistics = database.schema.read().unwrap().thing_statistics.clone();
+        let thing_manager =
+            ThingManager::new(database.thing_vertex_generator.clone(), type_manager.clone(), statistics);
+        Ok(Self { type_manager, thing_manager })
+    }
+}
+
+struct CommitView<'a, D> {
+    snapshot: &'a ReadSnapshot<D>,
+    schema: &'a SchemaView,
+}
+
+impl<D: DurabilityClient> CommitView<'_, D> {
+    fn decode(&self, key: &StorageKeyArray<BUFFER_KEY_INLINE>) -> Result<Change, Box<ConceptReadError>> {
+        let key_reference = StorageKeyReference::from(key);
+        if ObjectVertex::is_entity_vertex(key_reference) {
+            let entity = Entity::new(ObjectVertex::decode(key.bytes()));
+            Ok(Change::Entity { type_label: self.label(entity.type_())?, entity })
+        } else if ObjectVertex::is_relation_vertex(key_reference) {
+            let relation = Relation::new(ObjectVertex::decode(key.bytes()));
+            Ok(Change::Relation { type_label: self.label(relation.type_())?, relation })
+        } else if AttributeVertex::is_attribute_vertex(key_reference) {
+            let attribute = Attribute::new(AttributeVertex::decode(key.bytes()));
+            Ok(Change::Attribute {
+                type_label: self.label(attribute.type_())?,
+                value: self.value(&attribute)?,
+                attribute,
+            })
+        } else if ThingEdgeHas::is_has(key) {
+            let edge = ThingEdgeHas::decode(Bytes::Reference(key.bytes()));
+            let owner = Object::new(edge.from());
+            let attribute = Attribute::new(edge.to());
+            Ok(Change::Has {
+                owner_type_label: self.label(owner.type_())?,
+                attribute_type_label: self.label(attribute.type_())?,
+                value: self.value(&attribute)?,
+                owner,
+                attribute,
+            })
+        } else {
+            debug_assert!(ThingEdgeLinks::is_links(key));
+            let edge = ThingEdgeLinks::decode(Bytes::Reference(key.bytes()));
+            let relation = Relation::new(edge.from());
+            let player = Object::new(edge.to());
+            Ok(Change::RolePlayer {
+                relation_type_label: self.label(relation.type_())?,
+                player_type_label: self.label(player.type_())?,
+                role_label: self.label(RoleType::build_from_type_id(edge.role_id()))?,
+                relation,
+                player,
+            })
+        }
+    }
+
+    fn label(&self, type_: impl TypeAPI) -> Result<Label, Box<ConceptReadError>> {
+        Ok(type_.get_label(self.snapshot, &self.schema.type_manager)?.clone())
+    }
+
+    fn value(&self, attribute: &Attribute) -> Result<Value<'static>, Box<ConceptReadError>> {
+        Ok(attribute.get_value(self.snapshot, &self.schema.thing_manager, StorageCounters::DISABLED)?.into_owned())
+    }
+}
+
+typedb_error! {
+    pub ChangeStreamError(component = "Change stream", prefix = "CHS") {
+        CommitDataRead(1, "Error reading commit records from the write-ahead log.", typedb_source: StorageRecoveryError),
+        SnapshotRead(2, "Error reading the data preceding commit {sequence_number}.", sequence_number: u64, source: SnapshotGetError),
+        ConceptRead(3, "Error reading the concepts changed by commit {sequence_number}.", sequence_number: u64, typedb_source: Box<ConceptReadError>),
+        HistoryNotRetained(4, "Cannot decode the data deleted by commit {sequence_number}, which is older than the history retention window. Resume the stream from a later commit.", sequence_number: u64, typedb_source: HistoricalReadError),
+        TypeCacheCreate(5, "Error reading the schema as of commit {sequence_number}.", sequence_number: u64, typedb_source: TypeCacheCreateError),
+    }
+}
@@ -11,6 +11,7 @@ use std::{
     path::{Path, PathBuf},
     sync::{
         Arc, Mutex, MutexGuard, RwLock, TryLockError,
+        atomic::{AtomicBool, Ordering},
         mpsc::{SyncSender, sync_channel},
     },
     time::{Duration, Instant},
@@ -24,7 +25,7 @@ use concept::{
     },
 };
 use concurrency::IntervalRunner;
-use diagnostics::metrics::{DataLoadMetrics, DatabaseMetrics, SchemaLoadMetrics};
+use diagnostics::metrics::{DataLoadMetrics, DatabaseMetrics, KeyspaceSizeMetrics, SchemaLoadMetrics, StorageMetrics};
 use durability::{
     DurabilitySequenceNumber, DurabilityServiceError,
     wal::{WAL, WALError},
@@ -40,11 +41,13 @@ use encoding::{
 use error::typedb_error;
 use fail_point::{UNFINISHED_CHECKPOINT, fail_point};
 use function::{FunctionError, function_cache::FunctionCache};
+use options::ReadPoint;
 use query::query_cache::QueryCache;
 use resource::constants::database::{CHECKPOINT_INTERVAL, STATISTICS_UPDATE_INTERVAL};
 use storage::{
     MVCCStorage, StorageDeleteError, StorageOpenError, StorageResetError,
     durability_client::{DurabilityClient, DurabilityClientError, WALClient},
+    history::HistoricalReadError,
     recovery::checkpoint::{CheckpointCreateError, CheckpointLoadError, CheckpointReader, CheckpointWriter},
     sequence_number::SequenceNumber,
     snapshot::snapshot_id::SnapshotId,
@@ -57,6 +60,7 @@ use crate::{
         CorruptionPartialResetKeyGeneratorInUse, CorruptionPartialResetThingVertexGeneratorInUse,
         CorruptionPartialResetTypeVertexGeneratorInUse,
     },
+    backup::{self, BackupError, BackupManifest, BackupSegment},
     transaction::TransactionError,
 };
 
@@ -85,6 +89,8 @@ pub struct Database<D> {
     pub(super) schema: Arc<RwLock<Schema>>,
     pub(super) query_cache: Arc<QueryCache>,
     schema_write_transaction_exclusivity: Mutex<SchemaWriteTransactionState>,
+    read_only: AtomicBool,
+    pub(super) replication_lock: Mutex<()>,
     _statistics_updater: IntervalRunner,
     _checkpointer: IntervalRunner,
 }
@@ -102,7 +108,36 @@ impl<D> Database<D> {
         &self.name
     }
 
+    pub fn set_history_retention(&self, retention: Duration) {
+        self.storage.commit_history().set_retention(retention);
+    }
+
+    /// Marks the database as a read-only replica, which only opens read transactions and is updated by applying
+    /// the records replicated from its primary.
+    pub fn set_read_only(&self, read_only: bool) {
+        self.read_only.store(read_only, Ordering::SeqCst);
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only.load(Ordering::SeqCst)
+    }
+
+    pub(super) fn resolve_read_point(&self, read_point: ReadPoint) -> Result<SequenceNumber, HistoricalReadError> {
+        let history = self.storage.commit_history();
+        match read_point {
+            ReadPoint::SequenceNumber(number) => {
+                let sequence_number = SequenceNumber::new(number);
+                history.validate_sequence_number(sequence_number, self.storage.snapshot_watermark())?;
+                Ok(sequence_number)
+            }
+            ReadPoint::Timestamp(time) => history.sequence_number_at(time),
+        }
+    }
+
     pub(super) fn reserve_write_transaction(&self, timeout_millis: u64) -> Result<(), TransactionError> {
+        if self.is_read_only() {
+            return Err(TransactionError::ReadOnlyReplica { name: self.name.clone() });
+        }
         let (mut guard, timeout_left) =
             self.try_acquire_schema_write_transaction_lock(Duration::from_millis(timeout_millis))?;
         let (has_schema_transaction, running_write_transactions, ref mut notify_queue) = *guard;
@@ -120,6 +155,9 @@ impl<D> Database<D> {
     }
 
     pub(super) fn reserve_schema_transaction(&self, timeout_millis: u64) -> Result<(), TransactionError> {
+        if self.is_read_only() {
+            return Err(TransactionError::ReadOnlyReplica { name: self.name.clone() });
+        }
         let (mut guard, timeout_left) =
             self.try_acquire_schema_write_transaction_lock(Duration::from_millis(timeout_millis))?;
         let (has_schema_transaction, running_write_transactions, ref mut notify_queue) = *guard;
@@ -309,6 +347,8 @@ impl Database<WALClient> {
             schema,
             query_cache,
             schema_write_transaction_exclusivity: Mutex::new((false, 0, VecDeque::with_capacity(100))),
+            read_only: AtomicBool::new(false),
+            replication_lock: Mutex::new(()),
             _statistics_updater: IntervalRunner::new(update_statistics, STATISTICS_UPDATE_INTERVAL),
             _checkpointer: IntervalRunner::new(checkpoint_fn, CHECKPOINT_INTERVAL),
         })
@@ -420,6 +460,8 @@ impl Database<WALClient> {
             schema,
             query_cache,
             schema_write_transaction_exclusivity: Mutex::new((false, 0, VecDeque::with_capacity(100))),
+            read_only: AtomicBool::new(false),
+            replication_lock: Mutex::new(()),
             _statistics_updater: IntervalRunner::new(update_statistics, STATISTICS_UPDATE_INTERVAL),
             _checkpointer: IntervalRunner::new_with_initial_delay(
                 checkpoint_fn,
@@ -439,6 +481,98 @@ impl Database<WALClient> {
         checkpoint_storage(&self.name, &self.path, &self.storage)
     }
 
+    /// Writes a physical backup into `directory` without blocking transactions. The backup is laid out like a
+    /// database directory: a storage checkpoint plus the WAL records needed to recover on top of it.
+    /// Returns the sequence number of the checkpoint.
+    pub fn backup(&self, directory: &Path) -> Result<SequenceNumber, DatabaseBackupError> {
+        use DatabaseBackupError::{DirectoryCreate, DirectoryExists};
+
+        if directory.exists() {
+            return Err(DirectoryExists { name: self.name.clone(), path: directory.to_owned() });
+        }
+        fs::create_dir_all(directory)
+            .map_err(|source| DirectoryCreate { path: directory.to_owned(), source: Arc::new(source) })?;
+
+        let result = self.write_backup(directory);
+        if result.is_err() {
+            let _ = fs::remove_dir_all(directory);
+        }
+        result
+    }
+
+    fn write_backup(&self, directory: &Path) -> Result<SequenceNumber, DatabaseBackupError> {
+        use DatabaseBackupError::{CheckpointCreate, CheckpointRead, DurabilityCopy, Manifest};
+
+        debug!("Starting backup of database {} into {directory:?}", self.name);
+        let statistics_sequence_number =
+            self.schema.read().unwrap().thing_statistics.last_durable_write_sequence_number;
+
+        let checkpoint = CheckpointWriter::new(directory)
+            .and_then(|checkpoint| {
+                self.storage.checkpoint(&checkpoint)?;
+                checkpoint.finish()
+            })
+            .map_err(|source| CheckpointCreate { name: self.name.clone(), source })?;
+        let checkpoint_sequence_number = checkpoint
+            .read_sequence_number()
+            .map_err(|typedb_source| CheckpointRead { name: self.name.clone(), typedb_source })?;
+
+        // On load, commits after the checkpoint are replayed and statistics are synchronised from their last
+        // durable write, each reading some preceding records for context
+        let durability_start = checkpoint_sequence_number
+            .next()
+            .min(statistics_sequence_number)
+            .saturating_sub(Statistics::COMMIT_CONTEXT_SIZE);
+        let durability_end = self
+            .storage
+            .durability()
+            .copy_from(durability_start, directory)
+            .map_err(|typedb_source| DurabilityCopy { name: self.name.clone(), typedb_source })?;
+
+        let base = BackupSegment { start: durability_start, end: durability_end };
+        BackupManifest::new(self.name.clone(), checkpoint_sequence_number, base)
+            .write(directory)
+            .map_err(|typedb_source| Manifest { name: self.name.clone(), typedb_source })?;
+        debug!("Finished backup of database {} at sequence number {checkpoint_sequence_number}", self.name);
+        Ok(checkpoint_sequence_number)
+    }
+
+    /// Adds the WAL records committed since the last backup in `directory` to it as a new increment, without
+    /// blocking transactions. Returns the last sequence number covered by the backup.
+    pub fn backup_incremental(&self, directory: &Path) -> Result<SequenceNumber, DatabaseBackupError> {
+        use DatabaseBackupError::{BackupAheadOfDatabase, BackupOfOtherDatabase, Increment, Manifest};
+
+        let mut manifest = BackupManifest::read(directory)
+            .map_err(|typedb_source| Manifest { name: self.name.clone(), typedb_source })?;
+        if manifest.database_name != self.name {
+            return Err(BackupOfOtherDatabase {
+                name: self.name.clone(),
+                path: directory.to_owned(),
+                backup_name: manifest.database_name,
+            });
+        }
+        let last_backed_up = manifest.last_sequence_number();
+        let last_written = self.storage.durability().previous();
+        if last_backed_up > last_written {
+            return Err(BackupAheadOfDatabase {
+                name: self.name.clone(),
+                path: directory.to_owned(),
+                backup_sequence_number: last_backed_up.number(),
+                database_sequence_number: last_written.number(),
+            });
+        } else if last_backed_up == last_written {
+            debug!("Backup of database {} in {directory:?} is already up to date", self.name);
+            return Ok(last_backed_up);
+        }
+
+        let segment = backup::write_increment(self.storage.durability(), directory, &manifest)
+            .map_err(|typedb_source| Increment { name: self.name.clone(), typedb_source })?;
+        manifest.segments.push(segment);
+        manifest.write(directory).map_err(|typedb_source| Manifest { name: self.name.clone(), typedb_source })?;
+        debug!("Finished incremental backup of database {} up to sequence number {}", self.name, segment.end);
+        Ok(segment.end)
+    }
+
     #[allow(clippy::drop_non_drop)]
     pub fn delete(self) -> Result<(), DatabaseDeleteError> {
         trace!("Deleting database '{}'.", self.name);
@@ -516,6 +650,29 @@ impl Database<WALClient> {
                 storage_in_bytes: self.storage.estimate_size_in_bytes().expect("Expected storage size in bytes"),
                 storage_key_count: self.storage.estimate_key_count().expect("Expected storage key count"),
             },
+            storage: self.get_storage_metrics(),
+        }
+    }
+
+    fn get_storage_metrics(&self) -> StorageMetrics {
+        let keyspaces = self.storage.estimate_keyspace_sizes().expect("Expected storage keyspace sizes");
+        StorageMetrics {
+            keyspaces: keyspaces
+                .into_iter()
+                .map(|estimate| KeyspaceSizeMetrics {
+                    keyspace: estimate.name,
+                    live_data_in_bytes: estimate.live_data_in_bytes,
+                    sst_files_in_bytes: estimate.sst_files_in_bytes,
+                    memtables_in_bytes: estimate.memtables_in_bytes,
+                })
+                .collect(),
+            isolation_conflicts: self
+                .storage
+                .isolation_conflict_counts()
+                .into_iter()
+                .map(|(conflict, count)| (conflict.name(), count))
+                .collect(),
+            fsync_latency: self.storage.durability().fsync_latency(),
         }
     }
 }
@@ -591,6 +748,20 @@ typedb_error! {
     }
 }
 
+typedb_error! {
+    pub DatabaseBackupError(component = "Database backup", prefix = "DBB") {
+        DirectoryExists(1, "Cannot back up database '{name}' into '{path:?}' since it already exists.", name: String, path: PathBuf),
+        DirectoryCreate(2, "Error creating backup directory '{path:?}'.", path: PathBuf, source: Arc<io::Error>),
+        CheckpointCreate(3, "Error creating the storage checkpoint for the backup of database '{name}'.", name: String, source: CheckpointCreateError),
+        CheckpointRead(4, "Error reading the storage checkpoint for the backup of database '{name}'.", name: String, typedb_source: CheckpointLoadError),
+        DurabilityCopy(5, "Error copying the write-ahead log for the backup of database '{name}'.", name: String, typedb_source: DurabilityClientError),
+        Manifest(6, "Error accessing the backup manifest for database '{name}'.", name: String, typedb_source: BackupError),
+        Increment(7, "Error writing an incremental backup of database '{name}'.", name: String, typedb_source: BackupError),
+        BackupOfOtherDatabase(8, "Cannot add to the backup in '{path:?}' of database '{backup_name}' from database '{name}'.", name: String, path: PathBuf, backup_name: String),
+        BackupAheadOfDatabase(9, "Cannot add to the backup in '{path:?}', which covers sequence number {backup_sequence_number}, from database '{name}', which is at sequence number {database_sequence_number}.", name: String, path: PathBuf, backup_sequence_number: u64, database_sequence_number: u64),
+    }
+}
+
 typedb_error! {
     pub DatabaseCreateError(component = "Database create", prefix = "DBC") {
         InvalidName(1, "Cannot create database since '{name}' is not a valid database name.", name: String),
@@ -604,6 +775,8 @@ typedb_error! {
         IsNotBeingImported(9, "Internal error: database '{name}' is not being imported.", name: String),
         DirectoryWrite(10, "Error while writing to data directory for '{name}'.", name: String, source: Arc<io::Error>),
         DatabaseMove(11, "Error while moving database {name} while finalization.", name: String),
+        NotABackup(12, "Directory '{path:?}' does not contain a database backup.", path: PathBuf),
+        BackupRestore(13, "Error restoring database '{name}' from backup '{path:?}'.", name: String, path: PathBuf, typedb_source: BackupError),
     }
 }
 
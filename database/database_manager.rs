@@ -8,15 +8,26 @@ use std::{
     collections::HashMap,
     fs,
     path::{Path, PathBuf},
-    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    sync::{
+        Arc, RwLock, RwLockReadGuard, RwLockWriteGuard,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
 };
 
 use cache::CACHE_DB_NAME_PREFIX;
-use resource::{constants::database::INTERNAL_DATABASE_PREFIX, internal_database_prefix};
+use resource::{
+    constants::{database::INTERNAL_DATABASE_PREFIX, storage::DEFAULT_HISTORY_RETENTION},
+    internal_database_prefix,
+};
 use storage::durability_client::WALClient;
 use tracing::{Level, debug, event, warn};
 
-use crate::{Database, DatabaseDeleteError, DatabaseOpenError, DatabaseResetError, database::DatabaseCreateError};
+use crate::{
+    Database, DatabaseDeleteError, DatabaseOpenError, DatabaseResetError,
+    backup::{self, BackupManifest},
+    database::DatabaseCreateError,
+};
 
 type DatabasesMap = HashMap<String, Arc<Database<WALClient>>>;
 type Databases = RwLock<DatabasesMap>;
@@ -27,6 +38,8 @@ pub struct DatabaseManager {
     data_directory: PathBuf,
     import_directory: PathBuf,
     databases: Databases,
+    history_retention: RwLock<Duration>,
+    user_databases_read_only: AtomicBool,
 }
 
 impl DatabaseManager {
@@ -39,7 +52,13 @@ impl DatabaseManager {
         let databases = RwLock::new(Self::initialise_databases(&data_directory, &import_directory)?);
         Self::cleanup_import_directory(&import_directory)?;
 
-        Ok(Arc::new(Self { data_directory, import_directory, databases }))
+        Ok(Arc::new(Self {
+            data_directory,
+            import_directory,
+            databases,
+            history_retention: RwLock::new(DEFAULT_HISTORY_RETENTION),
+            user_databases_read_only: AtomicBool::new(false),
+        }))
     }
 
     fn initialise_databases(
@@ -141,6 +160,23 @@ impl DatabaseManager {
         Ok(())
     }
 
+    /// Sets how long old versions remain readable by point-in-time read transactions, for all databases.
+    pub fn set_history_retention(&self, retention: Duration) {
+        *self.history_retention.write().unwrap() = retention;
+        self.databases.read().unwrap().values().for_each(|database| database.set_history_retention(retention));
+    }
+
+    /// Marks all user databases, including those created later, as read-only replicas of another server's databases.
+    pub fn set_user_databases_read_only(&self, read_only: bool) {
+        self.user_databases_read_only.store(read_only, Ordering::SeqCst);
+        self.databases
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(name, _)| Self::is_user_database(name))
+            .for_each(|(_, database)| database.set_read_only(read_only));
+    }
+
     pub fn put_database(&self, name: impl AsRef<str>) -> Result<(), DatabaseCreateError> {
         Self::validate_user_database_name(name.as_ref())?;
         self.put_database_unrestricted(name)
@@ -207,6 +243,50 @@ impl DatabaseManager {
         self.new_imported_database(&name)
     }
 
+    /// Creates database `name` from a backup written by `Database::backup` and `Database::backup_incremental`.
+    /// The backup is copied and recovered in the import directory, so the name is reserved and a failed restore
+    /// leaves nothing behind.
+    pub fn restore_database(&self, name: &str, backup_directory: &Path) -> Result<(), DatabaseCreateError> {
+        use DatabaseCreateError::{BackupRestore, NotABackup};
+
+        if !BackupManifest::exists(backup_directory) {
+            return Err(NotABackup { path: backup_directory.to_owned() });
+        }
+        let restore_error =
+            |typedb_source| BackupRestore { name: name.to_owned(), path: backup_directory.to_owned(), typedb_source };
+        let manifest = BackupManifest::read(backup_directory).map_err(restore_error)?;
+
+        let database_directory = self.reserve_imported_database(name)?;
+        let restored = backup::copy_base(backup_directory, &database_directory)
+            .and_then(|_| backup::append_increments(&manifest, backup_directory, &database_directory))
+            .map_err(restore_error)
+            .and_then(|_| self.new_imported_database(name));
+        match restored {
+            Ok(database) => self.finalise_imported_database(database),
+            Err(err) => {
+                let _ = fs::remove_dir_all(&database_directory);
+                Err(err)
+            }
+        }
+    }
+
+    fn reserve_imported_database(&self, name: &str) -> Result<PathBuf, DatabaseCreateError> {
+        use DatabaseCreateError::{AlreadyExists, DirectoryWrite, IsBeingImported};
+
+        Self::validate_user_database_name(name)?;
+        let databases = self.databases.write().map_err(|_| DatabaseCreateError::WriteAccessDenied {})?;
+        if self.exists_public(&databases, name) {
+            return Err(AlreadyExists { name: name.to_owned() });
+        }
+        if self.exists_import(&databases, name) {
+            return Err(IsBeingImported { name: name.to_owned() });
+        }
+        let database_directory = self.import_directory.join(name);
+        fs::create_dir_all(&database_directory)
+            .map_err(|source| DirectoryWrite { name: name.to_owned(), source: Arc::new(source) })?;
+        Ok(database_directory)
+    }
+
     pub(crate) fn finalise_imported_database(&self, database: Database<WALClient>) -> Result<(), DatabaseCreateError> {
         let mut databases = self.databases.write().map_err(|_| DatabaseCreateError::WriteAccessDenied {})?;
         let name = database.name().to_string();
@@ -305,13 +385,18 @@ impl DatabaseManager {
     }
 
     fn new_public_database(&self, name: &str) -> Result<Database<WALClient>, DatabaseCreateError> {
-        Database::<WALClient>::open(&self.data_directory.join(name))
-            .map_err(|typedb_source| DatabaseCreateError::DatabaseOpen { typedb_source })
+        let database = Database::<WALClient>::open(&self.data_directory.join(name))
+            .map_err(|typedb_source| DatabaseCreateError::DatabaseOpen { typedb_source })?;
+        database.set_history_retention(*self.history_retention.read().unwrap());
+        database.set_read_only(Self::is_user_database(name) && self.user_databases_read_only.load(Ordering::SeqCst));
+        Ok(database)
     }
 
     fn new_imported_database(&self, name: &str) -> Result<Database<WALClient>, DatabaseCreateError> {
-        Database::<WALClient>::open(&self.import_directory.join(name))
-            .map_err(|typedb_source| DatabaseCreateError::DatabaseOpen { typedb_source })
+        let database = Database::<WALClient>::open(&self.import_directory.join(name))
+            .map_err(|typedb_source| DatabaseCreateError::DatabaseOpen { typedb_source })?;
+        database.set_history_retention(*self.history_retention.read().unwrap());
+        Ok(database)
     }
 
     fn exists_public<'a>(&'a self, databases: &'a DatabasesWriteLock<'a>, name: &str) -> bool {
@@ -7,10 +7,14 @@
 #![deny(unused_must_use)]
 #![deny(elided_lifetimes_in_paths)]
 
-pub use self::database::{Database, DatabaseDeleteError, DatabaseOpenError, DatabaseResetError};
+pub use self::database::{Database, DatabaseBackupError, DatabaseDeleteError, DatabaseOpenError, DatabaseResetError};
 
+pub mod backup;
+pub mod change_stream;
 pub mod database;
 pub mod database_manager;
 pub mod migration;
 pub mod query;
+pub mod replication;
+pub mod statistics;
 pub mod transaction;
@@ -11,13 +11,14 @@ use executor::{
     ExecutionInterrupt,
     batch::Batch,
     document::ConceptDocument,
-    pipeline::stage::{ExecutionContext, StageIterator},
+    pipeline::stage::{ExecutionContext, ExecutionLimits, StageIterator},
 };
 use function::function_manager::FunctionManager;
-use ir::pipeline::ParameterRegistry;
+use ir::pipeline::{ParameterRegistry, QueryParameters};
 use itertools::{Either, Itertools};
 use options::QueryOptions;
 use query::{error::QueryError, query_manager::QueryManager};
+use resource::profile::QueryProfile;
 use storage::{durability_client::WALClient, snapshot::WritableSnapshot};
 use tracing::{Level, event};
 use typeql::query::SchemaQuery;
@@ -36,15 +37,20 @@ pub type WriteQueryResult = Result<WriteQueryAnswer, Box<QueryError>>;
 pub struct WriteQueryAnswer {
     pub query_options: QueryOptions,
     pub answer: Either<WriteQueryBatchAnswer, WriteQueryDocumentsAnswer>,
+    pub query_profile: Arc<QueryProfile>,
 }
 
 impl WriteQueryAnswer {
-    fn new_batch(query_options: QueryOptions, answer: WriteQueryBatchAnswer) -> Self {
-        Self { query_options, answer: Either::Left(answer) }
+    fn new_batch(query_options: QueryOptions, answer: WriteQueryBatchAnswer, query_profile: Arc<QueryProfile>) -> Self {
+        Self { query_options, answer: Either::Left(answer), query_profile }
     }
 
-    fn new_documents(query_options: QueryOptions, answer: WriteQueryDocumentsAnswer) -> Self {
-        Self { query_options, answer: Either::Right(answer) }
+    fn new_documents(
+        query_options: QueryOptions,
+        answer: WriteQueryDocumentsAnswer,
+        query_profile: Arc<QueryProfile>,
+    ) -> Self {
+        Self { query_options, answer: Either::Right(answer), query_profile }
     }
 }
 
@@ -69,6 +75,43 @@ pub fn execute_schema_query(
     )
 }
 
+pub fn execute_schema_query_profiled(
+    transaction: TransactionSchema<WALClient>,
+    query: SchemaQuery,
+    source_query: String,
+) -> (TransactionSchema<WALClient>, QueryProfile, Result<(), Box<QueryError>>) {
+    let (transaction, (query_profile, result)) = with_transaction_parts!(
+        TransactionSchema,
+        transaction,
+        |inner_snapshot, type_manager, thing_manager, function_manager, query_manager| {
+            query_manager.execute_schema_profiled(
+                &mut inner_snapshot,
+                &type_manager,
+                &thing_manager,
+                &function_manager,
+                query,
+                &source_query,
+            )
+        }
+    );
+    (transaction, query_profile, result)
+}
+
+pub fn execution_limits(query_options: &QueryOptions) -> ExecutionLimits {
+    ExecutionLimits {
+        collected_rows_limit: query_options.collected_rows_limit,
+        tabled_rows_limit: query_options.tabled_rows_limit,
+    }
+}
+
+pub fn query_parameters(query_options: &QueryOptions) -> QueryParameters {
+    let mut parameters = QueryParameters::new();
+    for (variable, parameter) in &query_options.parameters {
+        parameters.bind(variable.clone(), parameter.value_type.clone(), parameter.value.clone());
+    }
+    parameters
+}
+
 pub fn execute_write_query_in_schema(
     transaction: TransactionSchema<WALClient>,
     query_options: QueryOptions,
@@ -169,6 +212,11 @@ pub(crate) fn execute_write_query_in<Snapshot: WritableSnapshot + 'static>(
     interrupt: ExecutionInterrupt,
 ) -> (Snapshot, WriteQueryResult) {
     let start_time = Instant::now();
+    let query_manager = match query_options.profile {
+        true => query_manager.profiled(),
+        false => query_manager.clone(),
+    };
+    let query_manager = query_manager.limited(execution_limits(&query_options)).bound(query_parameters(&query_options));
     let result = query_manager.prepare_write_pipeline(
         snapshot,
         type_manager,
@@ -213,7 +261,7 @@ pub(crate) fn execute_write_query_in<Snapshot: WritableSnapshot + 'static>(
                 }
             }
         }
-        if query_profile.is_enabled() {
+        if tracing::enabled!(Level::TRACE) {
             let micros = Instant::now().duration_since(start_time).as_micros();
             event!(
                 Level::INFO,
@@ -224,7 +272,7 @@ pub(crate) fn execute_write_query_in<Snapshot: WritableSnapshot + 'static>(
         }
         (
             Arc::into_inner(snapshot).unwrap(),
-            Ok(WriteQueryAnswer::new_documents(query_options, (parameters, documents))),
+            Ok(WriteQueryAnswer::new_documents(query_options, (parameters, documents), query_profile)),
         )
     } else {
         let named_outputs = pipeline.rows_positions().unwrap();
@@ -246,7 +294,11 @@ pub(crate) fn execute_write_query_in<Snapshot: WritableSnapshot + 'static>(
         let result = match iterator.collect_owned() {
             Ok(batch) => (
                 Arc::into_inner(snapshot).unwrap(),
-                Ok(WriteQueryAnswer::new_batch(query_options, (query_output_descriptor, batch, pipeline_structure))),
+                Ok(WriteQueryAnswer::new_batch(
+                    query_options,
+                    (query_output_descriptor, batch, pipeline_structure),
+                    query_profile.clone(),
+                )),
             ),
             Err(err) => (
                 Arc::into_inner(snapshot).unwrap(),
@@ -257,7 +309,7 @@ pub(crate) fn execute_write_query_in<Snapshot: WritableSnapshot + 'static>(
             ),
         };
 
-        if query_profile.is_enabled() {
+        if tracing::enabled!(Level::TRACE) {
             let micros = Instant::now().duration_since(start_time).as_micros();
             event!(
                 Level::INFO,
@@ -0,0 +1,167 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::sync::Arc;
+
+use concept::{
+    thing::statistics::StatisticsError,
+    type_::type_manager::{
+        TypeManager,
+        type_cache::{TypeCache, TypeCacheCreateError},
+    },
+};
+use durability::RawRecord;
+use error::typedb_error;
+use function::{FunctionError, function_cache::FunctionCache};
+use storage::{
+    StorageCommitError,
+    durability_client::{DurabilityClient, DurabilityClientError},
+    record::{CommitRecord, CommitType},
+    recovery::commit_recovery::{
+        RecoveryCommitStatus, StorageRecoveryError, load_commit_data_from, resolved_records_end,
+    },
+    sequence_number::SequenceNumber,
+};
+use tracing::trace;
+
+use crate::Database;
+
+impl<D: DurabilityClient> Database<D> {
+    /// Reads up to `limit` records of the write-ahead log from `start` onwards, to be applied by a replica.
+    pub fn replication_records_from(
+        &self,
+        start: SequenceNumber,
+        limit: usize,
+    ) -> Result<Vec<RawRecord<'static>>, DatabaseReplicationError> {
+        let record_read_error =
+            |typedb_source| DatabaseReplicationError::RecordRead { name: self.name().to_owned(), typedb_source };
+        self.storage
+            .durability()
+            .iter_from(start)
+            .map_err(record_read_error)?
+            .take(limit)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(record_read_error)
+    }
+
+    /// The sequence number from which a replica requests the next records from its primary.
+    pub fn replication_position(&self) -> SequenceNumber {
+        self.storage.durability().current()
+    }
+
+    /// Appends records read from the primary's write-ahead log and applies the commits among them. Records are only
+    /// appended up to the first commit whose status is not yet known, so a replica never has to validate commits
+    /// itself, and the remaining records are requested again from the returned position.
+    pub fn replicate(&self, records: Vec<RawRecord<'static>>) -> Result<SequenceNumber, DatabaseReplicationError> {
+        use DatabaseReplicationError::{CommitDataRead, NotAReplica, RecordAppend, RecordsMissing};
+
+        if !self.is_read_only() {
+            return Err(NotAReplica { name: self.name().to_owned() });
+        }
+        let _replication_guard = self.replication_lock.lock().unwrap();
+
+        let position = self.replication_position();
+        let records = records.into_iter().skip_while(|record| record.sequence_number < position).collect::<Vec<_>>();
+        if let Some(first) = records.first().filter(|first| first.sequence_number > position) {
+            return Err(RecordsMissing {
+                name: self.name().to_owned(),
+                expected_sequence_number: position.number(),
+                first_sequence_number: first.sequence_number.number(),
+            });
+        }
+
+        let end = resolved_records_end(&records)
+            .map_err(|typedb_source| CommitDataRead { name: self.name().to_owned(), typedb_source })?;
+        for record in records.into_iter().take_while(|record| record.sequence_number < end) {
+            self.storage
+                .durability()
+                .append_replicated(record)
+                .map_err(|typedb_source| RecordAppend { name: self.name().to_owned(), typedb_source })?;
+        }
+        self.apply_replicated_commits()?;
+        Ok(self.replication_position())
+    }
+
+    fn apply_replicated_commits(&self) -> Result<(), DatabaseReplicationError> {
+        use DatabaseReplicationError::{CommitApply, CommitDataRead};
+
+        let start = self.storage.snapshot_watermark().next();
+        let commits = load_commit_data_from(start, self.storage.durability())
+            .map_err(|typedb_source| CommitDataRead { name: self.name().to_owned(), typedb_source })?;
+        for (sequence_number, status) in commits {
+            match status {
+                RecoveryCommitStatus::Validated(commit_record) => match commit_record.commit_type() {
+                    CommitType::Data => self
+                        .storage
+                        .apply_replicated_commit(sequence_number, commit_record)
+                        .map_err(|typedb_source| CommitApply { name: self.name().to_owned(), typedb_source })?,
+                    CommitType::Schema => self.apply_replicated_schema_commit(sequence_number, commit_record)?,
+                },
+                RecoveryCommitStatus::Rejected => self.storage.skip_replicated_commit(sequence_number),
+                // only commits with a known status are appended
+                RecoveryCommitStatus::Pending(_) => break,
+            }
+            trace!("Applied replicated commit @ {sequence_number} to database '{}'", self.name());
+        }
+        Ok(())
+    }
+
+    fn apply_replicated_schema_commit(
+        &self,
+        sequence_number: SequenceNumber,
+        commit_record: CommitRecord,
+    ) -> Result<(), DatabaseReplicationError> {
+        use DatabaseReplicationError::{CommitApply, FunctionCacheUpdate, StatisticsUpdate, TypeCacheUpdate};
+
+        // As in a schema commit, no transaction may open until the caches reflect the new schema
+        let mut schema_guard = self.schema.write().unwrap();
+        let mut schema = (*schema_guard).clone();
+
+        self.storage
+            .apply_replicated_commit(sequence_number, commit_record)
+            .map_err(|typedb_source| CommitApply { name: self.name().to_owned(), typedb_source })?;
+
+        let type_cache = TypeCache::new(self.storage.clone(), sequence_number)
+            .map_err(|typedb_source| TypeCacheUpdate { name: self.name().to_owned(), typedb_source })?;
+        schema.type_cache = Arc::new(type_cache);
+        let type_manager = TypeManager::new(
+            self.definition_key_generator.clone(),
+            self.type_vertex_generator.clone(),
+            Some(schema.type_cache.clone()),
+        );
+        let function_cache = FunctionCache::new(self.storage.clone(), &type_manager, sequence_number)
+            .map_err(|typedb_source| FunctionCacheUpdate { name: self.name().to_owned(), typedb_source })?;
+        schema.function_cache = Arc::new(function_cache);
+
+        let mut thing_statistics = (*schema.thing_statistics).clone();
+        thing_statistics
+            .may_synchronise(&self.storage)
+            .map_err(|typedb_source| StatisticsUpdate { name: self.name().to_owned(), typedb_source })?;
+        schema.thing_statistics = Arc::new(thing_statistics);
+        self.query_cache.force_reset(&schema.thing_statistics);
+
+        *schema_guard = schema;
+        Ok(())
+    }
+}
+
+typedb_error! {
+    pub DatabaseReplicationError(component = "Database replication", prefix = "RPL") {
+        NotAReplica(1, "Cannot apply replicated records to database '{name}', which is not a read-only replica.", name: String),
+        RecordRead(2, "Error reading the write-ahead log of database '{name}' for replication.", name: String, typedb_source: DurabilityClientError),
+        RecordsMissing(
+            3,
+            "Replicated records for database '{name}' start at sequence number {first_sequence_number}, but the replica requires sequence number {expected_sequence_number} first.",
+            name: String, expected_sequence_number: u64, first_sequence_number: u64
+        ),
+        RecordAppend(4, "Error appending replicated records to the write-ahead log of database '{name}'.", name: String, typedb_source: DurabilityClientError),
+        CommitDataRead(5, "Error reading replicated commits of database '{name}'.", name: String, typedb_source: StorageRecoveryError),
+        CommitApply(6, "Error applying a replicated commit to database '{name}'.", name: String, typedb_source: StorageCommitError),
+        TypeCacheUpdate(7, "Error updating the type cache of database '{name}' after a replicated schema commit.", name: String, typedb_source: TypeCacheCreateError),
+        FunctionCacheUpdate(8, "Error updating the function cache of database '{name}' after a replicated schema commit.", name: String, typedb_source: FunctionError),
+        StatisticsUpdate(9, "Error updating the statistics of database '{name}' after a replicated schema commit.", name: String, typedb_source: StatisticsError),
+    }
+}
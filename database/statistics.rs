@@ -0,0 +1,116 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::{BTreeMap, HashMap};
+
+use concept::{
+    error::ConceptReadError,
+    thing::statistics::StatisticsError,
+    type_::{TypeAPI, type_manager::TypeManager},
+};
+use error::typedb_error;
+use storage::{durability_client::DurabilityClient, sequence_number::SequenceNumber, snapshot::ReadSnapshot};
+
+use crate::Database;
+
+pub type LabelledCounts = BTreeMap<String, u64>;
+
+/// The data statistics maintained for query planning, with every type identified by its label.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatabaseStatistics {
+    pub sequence_number: SequenceNumber,
+
+    pub total_thing_count: u64,
+    pub total_entity_count: u64,
+    pub total_relation_count: u64,
+    pub total_attribute_count: u64,
+    pub total_role_count: u64,
+    pub total_has_count: u64,
+
+    pub entity_counts: LabelledCounts,
+    pub relation_counts: LabelledCounts,
+    pub attribute_counts: LabelledCounts,
+    pub role_counts: LabelledCounts,
+
+    /// Owner type, then attribute type, to the number of ownerships
+    pub has_counts: BTreeMap<String, LabelledCounts>,
+    /// Player type, then role type, to the number of roles played
+    pub role_player_counts: BTreeMap<String, LabelledCounts>,
+    /// Relation type, then role type, to the number of role players
+    pub relation_role_counts: BTreeMap<String, LabelledCounts>,
+    /// Player type, then co-player type, to the number of entries in the links index
+    pub links_index_counts: BTreeMap<String, LabelledCounts>,
+}
+
+impl<D: DurabilityClient> Database<D> {
+    /// Brings a copy of the planner statistics up to date with the latest commit, and labels the types they count.
+    pub fn statistics(&self) -> Result<DatabaseStatistics, DatabaseStatisticsError> {
+        let mut statistics = (*self.schema.read().unwrap().thing_statistics).clone();
+        statistics.may_synchronise(&self.storage).map_err(|typedb_source| DatabaseStatisticsError::Synchronise {
+            name: self.name().to_owned(),
+            typedb_source,
+        })?;
+
+        // Types are labelled as of the synchronised statistics, which only count types that exist at that point
+        let labeller = TypeLabeller {
+            snapshot: self.storage.clone().open_snapshot_read_at(statistics.sequence_number),
+            type_manager: TypeManager::new(
+                self.definition_key_generator.clone(),
+                self.type_vertex_generator.clone(),
+                None,
+            ),
+        };
+        let label_error =
+            |typedb_source| DatabaseStatisticsError::TypeLabelRead { name: self.name().to_owned(), typedb_source };
+
+        Ok(DatabaseStatistics {
+            sequence_number: statistics.sequence_number,
+            total_thing_count: statistics.total_thing_count,
+            total_entity_count: statistics.total_entity_count,
+            total_relation_count: statistics.total_relation_count,
+            total_attribute_count: statistics.total_attribute_count,
+            total_role_count: statistics.total_role_count,
+            total_has_count: statistics.total_has_count,
+            entity_counts: labeller.counts(&statistics.entity_counts).map_err(label_error)?,
+            relation_counts: labeller.counts(&statistics.relation_counts).map_err(label_error)?,
+            attribute_counts: labeller.counts(&statistics.attribute_counts).map_err(label_error)?,
+            role_counts: labeller.counts(&statistics.role_counts).map_err(label_error)?,
+            has_counts: labeller.nested_counts(&statistics.has_attribute_counts).map_err(label_error)?,
+            role_player_counts: labeller.nested_counts(&statistics.role_player_counts).map_err(label_error)?,
+            relation_role_counts: labeller.nested_counts(&statistics.relation_role_counts).map_err(label_error)?,
+            links_index_counts: labeller.nested_counts(&statistics.links_index_counts).map_err(label_error)?,
+        })
+    }
+}
+
+struct TypeLabeller<D> {
+    snapshot: ReadSnapshot<D>,
+    type_manager: TypeManager,
+}
+
+impl<D: DurabilityClient> TypeLabeller<D> {
+    fn counts(&self, counts: &HashMap<impl TypeAPI, u64>) -> Result<LabelledCounts, Box<ConceptReadError>> {
+        counts.iter().map(|(type_, count)| Ok((self.label(type_)?, *count))).collect()
+    }
+
+    fn nested_counts<T: TypeAPI>(
+        &self,
+        counts: &HashMap<T, HashMap<impl TypeAPI, u64>>,
+    ) -> Result<BTreeMap<String, LabelledCounts>, Box<ConceptReadError>> {
+        counts.iter().map(|(type_, inner)| Ok((self.label(type_)?, self.counts(inner)?))).collect()
+    }
+
+    fn label(&self, type_: &impl TypeAPI) -> Result<String, Box<ConceptReadError>> {
+        Ok(type_.get_label(&self.snapshot, &self.type_manager)?.scoped_name().as_str().to_owned())
+    }
+}
+
+typedb_error! {
+    pub DatabaseStatisticsError(component = "Database statistics", prefix = "DST") {
+        Synchronise(1, "Error bringing the statistics of database '{name}' up to date.", name: String, typedb_source: StatisticsError),
+        TypeLabelRead(2, "Error reading the labels of the types counted by the statistics of database '{name}'.", name: String, typedb_source: Box<ConceptReadError>),
+    }
+}
@@ -0,0 +1,176 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::{sync::Arc, time::Duration};
+
+use database::{
+    Database,
+    change_stream::{Change, ChangeOperation, ChangeStream, ChangeStreamError, CommittedChanges},
+    database_manager::DatabaseManager,
+    query::{execute_schema_query, execute_write_query_in_write},
+    transaction::{CommitIntent, TransactionSchema, TransactionWrite},
+};
+use encoding::value::value::Value;
+use executor::ExecutionInterrupt;
+use options::{QueryOptions, TransactionOptions};
+use storage::durability_client::WALClient;
+use test_utils::{create_tmp_storage_dir, init_logging};
+
+const SCHEMA: &str = r#"define
+    attribute name value string;
+    relation friendship relates friend @card(0..);
+    entity person owns name, plays friendship:friend;
+"#;
+
+#[test]
+fn change_stream_decodes_and_resumes_committed_writes() {
+    init_logging();
+    let databases_path = create_tmp_storage_dir();
+    let database_manager = DatabaseManager::new(&databases_path).unwrap();
+    database_manager.put_database("changes").unwrap();
+    let database = database_manager.database("changes").unwrap();
+    define_schema(&database);
+
+    let mut stream = ChangeStream::new_from_latest(database.clone());
+    assert!(stream.poll().unwrap().is_empty());
+
+    write(&database, r#"insert $p isa person, has name "alice"; $f isa friendship, links (friend: $p);"#);
+    let inserted = stream.poll().unwrap();
+    assert_eq!(inserted.len(), 1);
+    let operations = summarise(&inserted[0]);
+    assert!(operations.contains(&(ChangeOperation::Insert, "entity person".to_owned())), "{operations:?}");
+    assert!(operations.contains(&(ChangeOperation::Insert, "relation friendship".to_owned())), "{operations:?}");
+    assert!(operations.contains(&(ChangeOperation::Insert, "attribute name alice".to_owned())), "{operations:?}");
+    assert!(operations.contains(&(ChangeOperation::Insert, "has person name alice".to_owned())), "{operations:?}");
+    assert!(
+        operations.contains(&(ChangeOperation::Insert, "role player friendship friendship:friend person".to_owned())),
+        "{operations:?}"
+    );
+
+    write(&database, r#"match $p isa person, has name $n; delete has $n of $p;"#);
+    let deleted = stream.poll().unwrap();
+    assert_eq!(deleted.len(), 1);
+    let operations = summarise(&deleted[0]);
+    assert!(operations.contains(&(ChangeOperation::Delete, "has person name alice".to_owned())), "{operations:?}");
+    assert!(!operations.iter().any(|(operation, _)| *operation == ChangeOperation::Insert), "{operations:?}");
+    assert!(deleted[0].sequence_number > inserted[0].sequence_number);
+
+    let mut resumed = ChangeStream::new(database.clone(), inserted[0].sequence_number.next());
+    let replayed = resumed.poll().unwrap();
+    assert_eq!(replayed.len(), 1);
+    assert_eq!(replayed[0].sequence_number, deleted[0].sequence_number);
+    assert_eq!(resumed.next_sequence_number(), stream.next_sequence_number());
+}
+
+#[test]
+fn change_stream_polls_bounded_batches() {
+    init_logging();
+    let databases_path = create_tmp_storage_dir();
+    let database_manager = DatabaseManager::new(&databases_path).unwrap();
+    database_manager.put_database("batches").unwrap();
+    let database = database_manager.database("batches").unwrap();
+    define_schema(&database);
+
+    let mut stream = ChangeStream::new_from_latest(database.clone()).with_batch_commits(2);
+    for _ in 0..5 {
+        write(&database, r#"insert $p isa person;"#);
+    }
+    let batch_sizes: Vec<_> = (0..4).map(|_| stream.poll().unwrap().len()).collect();
+    assert_eq!(batch_sizes, vec![2, 2, 1, 0]);
+    assert!(stream.is_caught_up());
+}
+
+#[test]
+fn change_stream_decodes_types_defined_after_it_started() {
+    init_logging();
+    let databases_path = create_tmp_storage_dir();
+    let database_manager = DatabaseManager::new(&databases_path).unwrap();
+    database_manager.put_database("schema-changes").unwrap();
+    let database = database_manager.database("schema-changes").unwrap();
+    define_schema(&database);
+
+    let mut stream = ChangeStream::new_from_latest(database.clone());
+    write(&database, r#"insert $p isa person;"#);
+    define(&database, "define entity car;");
+    write(&database, r#"insert $c isa car;"#);
+    let changes = stream.poll().unwrap();
+    assert_eq!(changes.len(), 2);
+    assert_eq!(summarise(&changes[0]), vec![(ChangeOperation::Insert, "entity person".to_owned())]);
+    assert_eq!(summarise(&changes[1]), vec![(ChangeOperation::Insert, "entity car".to_owned())]);
+}
+
+#[test]
+fn change_stream_refuses_deletes_outside_history_retention() {
+    init_logging();
+    let databases_path = create_tmp_storage_dir();
+    let database_manager = DatabaseManager::new(&databases_path).unwrap();
+    database_manager.put_database("retention").unwrap();
+    let database = database_manager.database("retention").unwrap();
+    define_schema(&database);
+    write(&database, r#"insert $p isa person;"#);
+
+    let mut stream = ChangeStream::new_from_latest(database.clone());
+    write(&database, r#"match $p isa person; delete $p;"#);
+    database.set_history_retention(Duration::ZERO);
+    write(&database, r#"insert $p isa person;"#);
+    assert!(matches!(stream.poll(), Err(ChangeStreamError::HistoryNotRetained { .. })));
+}
+
+fn summarise(changes: &CommittedChanges) -> Vec<(ChangeOperation, String)> {
+    changes
+        .events
+        .iter()
+        .map(|event| {
+            let description = match &event.change {
+                Change::Entity { type_label, .. } => format!("entity {type_label}"),
+                Change::Relation { type_label, .. } => format!("relation {type_label}"),
+                Change::Attribute { type_label, value, .. } => format!("attribute {type_label} {}", string(value)),
+                Change::Has { owner_type_label, attribute_type_label, value, .. } => {
+                    format!("has {owner_type_label} {attribute_type_label} {}", string(value))
+                }
+                Change::RolePlayer { relation_type_label, role_label, player_type_label, .. } => {
+                    format!("role player {relation_type_label} {role_label} {player_type_label}")
+                }
+            };
+            (event.operation, description)
+        })
+        .collect()
+}
+
+fn string(value: &Value<'static>) -> String {
+    match value {
+        Value::String(string) => string.to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn define_schema(database: &Arc<Database<WALClient>>) {
+    define(database, SCHEMA);
+}
+
+fn define(database: &Arc<Database<WALClient>>, query: &str) {
+    let schema_query = typeql::parse_query(query).unwrap().into_structure().into_schema();
+    let tx = TransactionSchema::open(database.clone(), TransactionOptions::default()).unwrap();
+    let (tx, result) = execute_schema_query(tx, schema_query, query.to_string());
+    result.unwrap();
+    let (mut profile, intent) = tx.finalise();
+    intent.unwrap().commit(profile.commit_profile()).unwrap();
+}
+
+fn write(database: &Arc<Database<WALClient>>, query: &str) {
+    let tx = TransactionWrite::open(database.clone(), TransactionOptions::default()).unwrap();
+    let pipeline = typeql::parse_query(query).unwrap().into_structure().into_pipeline();
+    let (tx, result) = execute_write_query_in_write(
+        tx,
+        QueryOptions::default_grpc(),
+        pipeline,
+        query.to_string(),
+        ExecutionInterrupt::new_uninterruptible(),
+    );
+    result.unwrap();
+    let (mut profile, intent) = tx.finalise();
+    intent.unwrap().commit(profile.commit_profile()).unwrap();
+}
@@ -4,8 +4,17 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use database::Database;
-use storage::durability_client::WALClient;
+use std::sync::Arc;
+
+use database::{
+    Database,
+    database_manager::DatabaseManager,
+    query::{execute_schema_query, execute_write_query_in_write},
+    transaction::{CommitIntent, TransactionRead, TransactionSchema, TransactionWrite},
+};
+use executor::ExecutionInterrupt;
+use options::{QueryOptions, TransactionOptions};
+use storage::{durability_client::WALClient, snapshot::ReadableSnapshot};
 use test_utils::{create_tmp_storage_dir, init_logging};
 
 #[test]
@@ -18,3 +27,96 @@ fn create_delete_database() {
     let delete_result = db.delete();
     assert!(delete_result.is_ok());
 }
+
+#[test]
+fn backup_restore_database() {
+    init_logging();
+    let databases_path = create_tmp_storage_dir();
+    let database_manager = DatabaseManager::new(&databases_path).expect("Expected database manager");
+    database_manager.put_database("original").expect("Expected database creation");
+    let database = database_manager.database("original").unwrap();
+
+    let tx_write = TransactionWrite::open(database.clone(), TransactionOptions::default()).unwrap();
+    let (mut profile, finalise_result) = tx_write.finalise();
+    let commit_result = finalise_result.and_then(|intent| intent.commit(profile.commit_profile()));
+    assert!(commit_result.is_ok(), "{:?}", commit_result.unwrap_err());
+
+    let backup_path = create_tmp_storage_dir();
+    let backup_directory = backup_path.join("backup");
+    let backup_result = database.backup(&backup_directory);
+    assert!(backup_result.is_ok(), "{:?}", backup_result.unwrap_err());
+    let backup_sequence_number = backup_result.unwrap();
+    assert!(database.backup(&backup_directory).is_err(), "Expected an existing backup directory to be rejected");
+
+    let restore_result = database_manager.restore_database("restored", &backup_directory);
+    assert!(restore_result.is_ok(), "{:?}", restore_result.unwrap_err());
+    let restored = database_manager.database("restored").expect("Expected restored database");
+    let tx_read = TransactionRead::open(restored, TransactionOptions::default()).unwrap();
+    assert!(tx_read.snapshot.open_sequence_number() >= backup_sequence_number);
+
+    let restore_again_result = database_manager.restore_database("restored", &backup_directory);
+    assert!(restore_again_result.is_err(), "Expected restoring over an existing database to fail");
+}
+
+#[test]
+fn incremental_backup_restore_database() {
+    init_logging();
+    let databases_path = create_tmp_storage_dir();
+    let database_manager = DatabaseManager::new(&databases_path).expect("Expected database manager");
+    database_manager.put_database("original").expect("Expected database creation");
+    let database = database_manager.database("original").unwrap();
+    define_person(&database);
+    insert_person(&database, "alice");
+
+    let backup_path = create_tmp_storage_dir();
+    let backup_directory = backup_path.join("backup");
+    let backup_result = database.backup(&backup_directory);
+    assert!(backup_result.is_ok(), "{:?}", backup_result.unwrap_err());
+
+    insert_person(&database, "bob");
+    let increment_result = database.backup_incremental(&backup_directory);
+    assert!(increment_result.is_ok(), "{:?}", increment_result.unwrap_err());
+    let increment_sequence_number = increment_result.unwrap();
+    let repeated_result = database.backup_incremental(&backup_directory);
+    assert_eq!(repeated_result.unwrap(), increment_sequence_number, "Expected an up to date backup to be unchanged");
+    insert_person(&database, "carol");
+    assert!(database.backup_incremental(&backup_directory).unwrap() > increment_sequence_number);
+
+    database_manager.put_database("other").expect("Expected database creation");
+    let other = database_manager.database("other").unwrap();
+    assert!(
+        other.backup_incremental(&backup_directory).is_err(),
+        "Expected a backup of another database to be rejected"
+    );
+
+    let restore_result = database_manager.restore_database("restored", &backup_directory);
+    assert!(restore_result.is_ok(), "{:?}", restore_result.unwrap_err());
+    let restored = database_manager.database("restored").expect("Expected restored database");
+    assert_eq!(restored.get_metrics().data.entity_count, 3);
+}
+
+fn define_person(database: &Arc<Database<WALClient>>) {
+    let schema = "define entity person, owns name; attribute name value string;";
+    let schema_query = typeql::parse_query(schema).unwrap().into_structure().into_schema();
+    let tx = TransactionSchema::open(database.clone(), TransactionOptions::default()).unwrap();
+    let (tx, result) = execute_schema_query(tx, schema_query, schema.to_string());
+    result.unwrap();
+    let (mut profile, intent) = tx.finalise();
+    intent.unwrap().commit(profile.commit_profile()).unwrap();
+}
+
+fn insert_person(database: &Arc<Database<WALClient>>, name: &str) {
+    let tx = TransactionWrite::open(database.clone(), TransactionOptions::default()).unwrap();
+    let query = format!(r#"insert $p isa person, has name "{name}";"#);
+    let pipeline = typeql::parse_query(&query).unwrap().into_structure().into_pipeline();
+    let (tx, result) = execute_write_query_in_write(
+        tx,
+        QueryOptions::default_grpc(),
+        pipeline,
+        query,
+        ExecutionInterrupt::new_uninterruptible(),
+    );
+    result.unwrap();
+    let (mut profile, intent) = tx.finalise();
+    intent.unwrap().commit(profile.commit_profile()).unwrap();
+}
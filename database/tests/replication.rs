@@ -0,0 +1,116 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::sync::Arc;
+
+use database::{
+    Database,
+    database_manager::DatabaseManager,
+    query::{execute_schema_query, execute_write_query_in_write},
+    replication::DatabaseReplicationError,
+    transaction::{CommitIntent, TransactionError, TransactionRead, TransactionSchema, TransactionWrite},
+};
+use encoding::value::label::Label;
+use executor::ExecutionInterrupt;
+use options::{QueryOptions, TransactionOptions};
+use resource::profile::StorageCounters;
+use storage::{durability_client::WALClient, sequence_number::SequenceNumber};
+use test_utils::{create_tmp_storage_dir, init_logging};
+
+const SCHEMA: &str = r#"define
+    attribute name value string;
+    entity person owns name;
+"#;
+
+const BATCH_RECORDS: usize = 1_000;
+
+#[test]
+fn replica_follows_primary_and_only_opens_read_transactions() {
+    init_logging();
+    let primary_path = create_tmp_storage_dir();
+    let primary_manager = DatabaseManager::new(&primary_path).unwrap();
+    primary_manager.put_database("people").unwrap();
+    let primary = primary_manager.database("people").unwrap();
+
+    let replica_path = create_tmp_storage_dir();
+    let replica_manager = DatabaseManager::new(&replica_path).unwrap();
+    replica_manager.put_database("people").unwrap();
+    let replica = replica_manager.database("people").unwrap();
+
+    let records = primary.replication_records_from(replica.replication_position(), BATCH_RECORDS).unwrap();
+    assert!(matches!(replica.replicate(records), Err(DatabaseReplicationError::NotAReplica { .. })));
+    replica.set_read_only(true);
+
+    define_schema(&primary);
+    write(&primary, r#"insert $p isa person, has name "alice";"#);
+    follow(&primary, &replica);
+    assert_eq!(count_people(&replica), 1);
+
+    write(&primary, r#"insert $p isa person, has name "bob";"#);
+    assert_eq!(count_people(&replica), 1);
+    let position = follow(&primary, &replica);
+    assert_eq!(count_people(&replica), 2);
+    assert_eq!(position, primary.replication_position());
+
+    let write_result = TransactionWrite::open(replica.clone(), TransactionOptions::default());
+    assert!(matches!(write_result, Err(TransactionError::ReadOnlyReplica { .. })));
+    let schema_result = TransactionSchema::open(replica.clone(), TransactionOptions::default());
+    assert!(matches!(schema_result, Err(TransactionError::ReadOnlyReplica { .. })));
+
+    // A restarted replica recovers the replicated commits and resumes where it stopped
+    drop(replica);
+    drop(replica_manager);
+    let replica_manager = DatabaseManager::new(&replica_path).unwrap();
+    let replica = replica_manager.database("people").unwrap();
+    replica.set_read_only(true);
+    assert_eq!(count_people(&replica), 2);
+    write(&primary, r#"insert $p isa person, has name "carol";"#);
+    follow(&primary, &replica);
+    assert_eq!(count_people(&replica), 3);
+}
+
+fn follow(primary: &Arc<Database<WALClient>>, replica: &Arc<Database<WALClient>>) -> SequenceNumber {
+    let records = primary.replication_records_from(replica.replication_position(), BATCH_RECORDS).unwrap();
+    replica.replicate(records).unwrap()
+}
+
+fn count_people(database: &Arc<Database<WALClient>>) -> usize {
+    let transaction = TransactionRead::open(database.clone(), TransactionOptions::default()).unwrap();
+    let person_type = transaction
+        .type_manager
+        .get_entity_type(transaction.snapshot(), &Label::build("person", None))
+        .unwrap()
+        .expect("Expected the replicated schema to define person");
+    Iterator::count(transaction.thing_manager.get_entities_in(
+        transaction.snapshot(),
+        person_type,
+        StorageCounters::DISABLED,
+    ))
+}
+
+fn define_schema(database: &Arc<Database<WALClient>>) {
+    let schema_query = typeql::parse_query(SCHEMA).unwrap().into_structure().into_schema();
+    let tx = TransactionSchema::open(database.clone(), TransactionOptions::default()).unwrap();
+    let (tx, result) = execute_schema_query(tx, schema_query, SCHEMA.to_string());
+    result.unwrap();
+    let (mut profile, intent) = tx.finalise();
+    intent.unwrap().commit(profile.commit_profile()).unwrap();
+}
+
+fn write(database: &Arc<Database<WALClient>>, query: &str) {
+    let tx = TransactionWrite::open(database.clone(), TransactionOptions::default()).unwrap();
+    let pipeline = typeql::parse_query(query).unwrap().into_structure().into_pipeline();
+    let (tx, result) = execute_write_query_in_write(
+        tx,
+        QueryOptions::default_grpc(),
+        pipeline,
+        query.to_string(),
+        ExecutionInterrupt::new_uninterruptible(),
+    );
+    result.unwrap();
+    let (mut profile, intent) = tx.finalise();
+    intent.unwrap().commit(profile.commit_profile()).unwrap();
+}
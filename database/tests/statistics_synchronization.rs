@@ -49,13 +49,7 @@ fn statistics_synchronization_under_concurrent_load() {
         let dbm = DatabaseManager::new(&tmp_dir).unwrap();
         dbm.put_database(DB_NAME).unwrap();
         let database = dbm.database(DB_NAME).unwrap();
-
-        let schema_query = typeql::parse_query(SCHEMA).unwrap().into_structure().into_schema();
-        let tx = TransactionSchema::open(database.clone(), TransactionOptions::default()).unwrap();
-        let (tx, result) = execute_schema_query(tx, schema_query, SCHEMA.to_string());
-        result.unwrap();
-        let (mut profile, intent) = tx.finalise();
-        intent.unwrap().commit(profile.commit_profile()).unwrap();
+        define_schema(&database);
 
         let mut handles = Vec::with_capacity(NUM_THREADS);
         for thread_id in 0..NUM_THREADS {
@@ -85,6 +79,37 @@ fn statistics_synchronization_under_concurrent_load() {
     assert_eq!(metrics.data.relation_count, 0, "relation_count after reboot");
 }
 
+#[test]
+fn statistics_are_reported_by_type_label() {
+    init_logging();
+    let tmp_dir = create_tmp_storage_dir();
+    let dbm = DatabaseManager::new(&tmp_dir).unwrap();
+    dbm.put_database(DB_NAME).unwrap();
+    let database = dbm.database(DB_NAME).unwrap();
+    define_schema(&database);
+    run_insert_batch(&database, 0);
+    run_insert_batch(&database, 1);
+
+    // Reported statistics are brought up to date with the latest commit, without waiting for the periodic update
+    let statistics = database.statistics().unwrap();
+    let total_persons = 2 * OPS_PER_BATCH as u64;
+    assert_eq!(statistics.total_entity_count, total_persons);
+    assert_eq!(statistics.entity_counts.get("person"), Some(&total_persons));
+    assert_eq!(statistics.attribute_counts.get("name"), Some(&total_persons));
+    assert_eq!(statistics.attribute_counts.get("age"), Some(&total_persons));
+    assert_eq!(statistics.has_counts["person"].get("name"), Some(&total_persons));
+    assert_eq!(statistics.total_relation_count, 0);
+}
+
+fn define_schema(database: &Arc<Database<WALClient>>) {
+    let schema_query = typeql::parse_query(SCHEMA).unwrap().into_structure().into_schema();
+    let tx = TransactionSchema::open(database.clone(), TransactionOptions::default()).unwrap();
+    let (tx, result) = execute_schema_query(tx, schema_query, SCHEMA.to_string());
+    result.unwrap();
+    let (mut profile, intent) = tx.finalise();
+    intent.unwrap().commit(profile.commit_profile()).unwrap();
+}
+
 fn run_insert_batch(database: &Arc<Database<WALClient>>, batch_id: usize) {
     let mut tx = TransactionWrite::open(database.clone(), TransactionOptions::default()).unwrap();
     for i in 0..OPS_PER_BATCH {
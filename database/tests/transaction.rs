@@ -5,16 +5,16 @@
  */
 use std::{
     sync::Arc,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime},
 };
 
 use database::{
     Database,
     database_manager::DatabaseManager,
-    transaction::{CommitIntent, TransactionRead, TransactionSchema, TransactionWrite},
+    transaction::{CommitIntent, TransactionError, TransactionRead, TransactionSchema, TransactionWrite},
 };
-use options::TransactionOptions;
-use storage::durability_client::WALClient;
+use options::{ReadPoint, TransactionOptions};
+use storage::{durability_client::WALClient, snapshot::ReadableSnapshot};
 use test_utils::{TempDir, create_tmp_storage_dir, init_logging};
 use tokio::{
     runtime::Runtime,
@@ -142,6 +142,53 @@ fn open_close_read_transaction() {
     tx_read.close()
 }
 
+#[test]
+fn read_transaction_opens_at_committed_sequence_number() {
+    init_logging();
+    let databases_path = create_tmp_storage_dir();
+    let database = create_database(&databases_path);
+    let before_commit = open_read(database.clone()).snapshot.open_sequence_number();
+    let tx_write = open_write(database.clone());
+    let (mut profile, finalise_result) = tx_write.finalise();
+    let commit_result = finalise_result.and_then(|intent| intent.commit(profile.commit_profile()));
+    assert_ok!(commit_result);
+
+    let options = TransactionOptions {
+        read_point: Some(ReadPoint::SequenceNumber(before_commit.number())),
+        ..TransactionOptions::default()
+    };
+    let open_result = TransactionRead::open(database.clone(), options);
+    assert_ok!(open_result);
+    assert_eq!(open_result.unwrap().snapshot.open_sequence_number(), before_commit);
+}
+
+#[test]
+fn read_transaction_cannot_open_ahead_of_watermark() {
+    init_logging();
+    let databases_path = create_tmp_storage_dir();
+    let database = create_database(&databases_path);
+    let watermark = open_read(database.clone()).snapshot.open_sequence_number();
+    let options = TransactionOptions {
+        read_point: Some(ReadPoint::SequenceNumber(watermark.number() + 1)),
+        ..TransactionOptions::default()
+    };
+    let open_result = TransactionRead::open(database, options);
+    assert!(matches!(open_result, Err(TransactionError::HistoricalRead { .. })));
+}
+
+#[test]
+fn write_transaction_cannot_open_at_read_point() {
+    init_logging();
+    let databases_path = create_tmp_storage_dir();
+    let database = create_database(&databases_path);
+    let options = TransactionOptions {
+        read_point: Some(ReadPoint::Timestamp(SystemTime::now())),
+        ..TransactionOptions::default()
+    };
+    let open_result = TransactionWrite::open(database, options);
+    assert!(matches!(open_result, Err(TransactionError::ReadPointNotSupported { .. })));
+}
+
 /////////////////////////////
 // SCHEMA TRANSACTION LOCK //
 /////////////////////////////
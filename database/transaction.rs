@@ -25,6 +25,7 @@ use query::query_manager::QueryManager;
 use resource::profile::{CommitProfile, TransactionProfile};
 use storage::{
     durability_client::DurabilityClient,
+    history::HistoricalReadError,
     record::CommitRecord,
     snapshot::{
         CommittableSnapshot, ReadSnapshot, ReadableSnapshot, SchemaSnapshot, SnapshotError, WritableSnapshot,
@@ -59,18 +60,26 @@ pub struct TransactionRead<D> {
 
 impl<D: DurabilityClient> TransactionRead<D> {
     pub fn open(database: Arc<Database<D>>, transaction_options: TransactionOptions) -> Result<Self, TransactionError> {
-        // TODO: when we implement constructor `open_at`, to open a transaction in the past by
-        //      time/sequence number, we need to check whether
+        // TODO: when opening a transaction in the past by time/sequence number, we need to check whether
         //       the statistics that is available is "too far" ahead of the version we're opening (100-1000?)
         //          note: this can also be the approximate frequency at which we persist statistics snapshots to the WAL!
         //       this should be a constant defined in constants.rs
         //       If it's too far in the future, we should find a more appropriate statistics snapshot from the WAL
         let schema = database.schema.read().unwrap();
-        let snapshot: ReadSnapshot<D> = database.storage.clone().open_snapshot_read();
+        let (snapshot, is_historical): (ReadSnapshot<D>, bool) = match transaction_options.read_point {
+            None => (database.storage.clone().open_snapshot_read(), false),
+            Some(read_point) => {
+                let sequence_number = database
+                    .resolve_read_point(read_point)
+                    .map_err(|typedb_source| TransactionError::HistoricalRead { typedb_source })?;
+                (database.storage.clone().open_snapshot_read_at(sequence_number), true)
+            }
+        };
+        // A snapshot in the past may predate the latest schema, which the caches reflect
         let type_manager = Arc::new(TypeManager::new(
             database.definition_key_generator.clone(),
             database.type_vertex_generator.clone(),
-            Some(schema.type_cache.clone()),
+            (!is_historical).then(|| schema.type_cache.clone()),
         ));
         let thing_manager = Arc::new(ThingManager::new(
             database.thing_vertex_generator.clone(),
@@ -79,9 +88,10 @@ impl<D: DurabilityClient> TransactionRead<D> {
         ));
         let function_manager = Arc::new(FunctionManager::new(
             database.definition_key_generator.clone(),
-            Some(schema.function_cache.clone()),
+            (!is_historical).then(|| schema.function_cache.clone()),
         ));
-        let query_manager = Arc::new(QueryManager::new(Some(database.query_cache.clone())));
+        let query_manager = QueryManager::new((!is_historical).then(|| database.query_cache.clone()));
+        let query_manager = Arc::new(with_transaction_parallelism(query_manager, &transaction_options));
 
         drop(schema);
 
@@ -124,6 +134,9 @@ pub struct TransactionWrite<D> {
 
 impl<D: DurabilityClient> TransactionWrite<D> {
     pub fn open(database: Arc<Database<D>>, transaction_options: TransactionOptions) -> Result<Self, TransactionError> {
+        if transaction_options.read_point.is_some() {
+            return Err(TransactionError::ReadPointNotSupported {});
+        }
         database.reserve_write_transaction(transaction_options.schema_lock_acquire_timeout_millis)?;
 
         let schema = database.schema.read().unwrap();
@@ -142,7 +155,8 @@ impl<D: DurabilityClient> TransactionWrite<D> {
             database.definition_key_generator.clone(),
             Some(schema.function_cache.clone()),
         ));
-        let query_manager = Arc::new(QueryManager::new(Some(database.query_cache.clone())));
+        let query_manager = QueryManager::new(Some(database.query_cache.clone()));
+        let query_manager = Arc::new(with_transaction_parallelism(query_manager, &transaction_options));
         drop(schema);
 
         Ok(Self {
@@ -225,6 +239,9 @@ pub struct TransactionSchema<D> {
 
 impl<D: DurabilityClient> TransactionSchema<D> {
     pub fn open(database: Arc<Database<D>>, transaction_options: TransactionOptions) -> Result<Self, TransactionError> {
+        if transaction_options.read_point.is_some() {
+            return Err(TransactionError::ReadPointNotSupported {});
+        }
         database.reserve_schema_transaction(transaction_options.schema_lock_acquire_timeout_millis)?;
 
         let snapshot: SchemaSnapshot<D> = database.storage.clone().open_snapshot_schema();
@@ -242,7 +259,7 @@ impl<D: DurabilityClient> TransactionSchema<D> {
             )
         };
         let function_manager = Arc::new(FunctionManager::new(database.definition_key_generator.clone(), None));
-        let query_manager = Arc::new(QueryManager::new(None));
+        let query_manager = Arc::new(with_transaction_parallelism(QueryManager::new(None), &transaction_options));
 
         Ok(Self {
             snapshot: Arc::new(snapshot),
@@ -330,6 +347,13 @@ impl<D: DurabilityClient> TransactionSchema<D> {
     }
 }
 
+fn with_transaction_parallelism(query_manager: QueryManager, transaction_options: &TransactionOptions) -> QueryManager {
+    match transaction_options.parallel {
+        true => query_manager.parallel(),
+        false => query_manager,
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct TransactionId {
     sequence_number: DurabilitySequenceNumber,
@@ -592,5 +616,8 @@ typedb_error! {
     pub TransactionError(component = "Transaction", prefix = "TXN") {
         Timeout(1, "Transaction timeout.", source: RecvTimeoutError),
         WriteExclusivityTimeout(2, "Transaction timeout due to an exclusive write access requested by this or a concurrent transaction."),
+        HistoricalRead(3, "Cannot open the read transaction at the requested point in history.", typedb_source: HistoricalReadError),
+        ReadPointNotSupported(4, "Only read transactions can be opened at a point in history."),
+        ReadOnlyReplica(5, "Only read transactions can be opened on database '{name}', which is a read-only replica.", name: String),
     }
 }
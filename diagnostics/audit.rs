@@ -0,0 +1,226 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::{fmt, future::Future, io, io::Write, path::Path, sync::Mutex};
+
+use chrono::{SecondsFormat, Utc};
+use logger::error;
+use serde::Serialize;
+use tracing_appender::rolling::RollingFileAppender;
+
+use crate::{
+    LogRotation,
+    metrics::{ActionKind, ClientEndpoint},
+};
+
+pub const AUDIT_LOG_FILE_PREFIX: &str = "audit";
+pub const AUDIT_LOG_FILE_SUFFIX: &str = "jsonl";
+
+tokio::task_local! {
+    static AUDIT_ACCESSOR: String;
+    static AUDIT_TRANSACTION_TYPE: String;
+    static AUDIT_QUERY: String;
+    static AUDIT_TARGET_USER: String;
+}
+
+/// Attributes every action submitted while `future` runs to `accessor`.
+/// Set by the authenticators once a request's user is known.
+pub async fn with_accessor<F: Future>(accessor: String, future: F) -> F::Output {
+    AUDIT_ACCESSOR.scope(accessor, future).await
+}
+
+/// Attaches the type of the transaction being opened to the actions submitted while `future` runs.
+pub async fn with_transaction_type<F: Future>(transaction_type: String, future: F) -> F::Output {
+    AUDIT_TRANSACTION_TYPE.scope(transaction_type, future).await
+}
+
+/// Attaches the source of a query to the actions submitted while `future` runs.
+pub async fn with_query<F: Future>(query: String, future: F) -> F::Output {
+    AUDIT_QUERY.scope(query, future).await
+}
+
+/// Attaches the user managed by a user-management action to the actions submitted while `future` runs.
+pub async fn with_target_user<F: Future>(username: String, future: F) -> F::Output {
+    AUDIT_TARGET_USER.scope(username, future).await
+}
+
+fn current_accessor() -> Option<String> {
+    AUDIT_ACCESSOR.try_with(|accessor| accessor.clone()).ok()
+}
+
+fn current_transaction_type() -> Option<String> {
+    AUDIT_TRANSACTION_TYPE.try_with(|transaction_type| transaction_type.clone()).ok()
+}
+
+fn current_query() -> Option<String> {
+    AUDIT_QUERY.try_with(|query| query.clone()).ok()
+}
+
+fn current_target_user() -> Option<String> {
+    AUDIT_TARGET_USER.try_with(|username| username.clone()).ok()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditOutcome {
+    Success,
+    Fail,
+}
+
+#[derive(Debug, Serialize)]
+struct AuditEvent<'a> {
+    timestamp: String,
+    client: String,
+    user: Option<String>,
+    action: String,
+    database: Option<&'a str>,
+    outcome: AuditOutcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transaction_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    query: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target_user: Option<String>,
+}
+
+pub struct AuditLog {
+    appender: Mutex<RollingFileAppender>,
+}
+
+impl AuditLog {
+    pub fn new(directory: &Path, rotation: LogRotation, max_files: usize) -> io::Result<Self> {
+        std::fs::create_dir_all(directory)?;
+        let appender = RollingFileAppender::builder()
+            .rotation(rotation.into())
+            .filename_prefix(AUDIT_LOG_FILE_PREFIX)
+            .filename_suffix(AUDIT_LOG_FILE_SUFFIX)
+            .max_log_files(max_files)
+            .build(directory)
+            .map_err(io::Error::other)?;
+        Ok(Self { appender: Mutex::new(appender) })
+    }
+
+    pub(crate) fn record(
+        &self,
+        client: ClientEndpoint,
+        database_name: Option<&str>,
+        action_kind: ActionKind,
+        outcome: AuditOutcome,
+    ) {
+        // Every authenticated request is attributed to its user, so only failed authentications
+        // are worth recording on their own
+        if action_kind == ActionKind::Authenticate && outcome == AuditOutcome::Success {
+            return;
+        }
+        let event = AuditEvent {
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            client: client.to_string(),
+            user: current_accessor(),
+            action: action_kind.to_string(),
+            database: database_name,
+            outcome,
+            transaction_type: current_transaction_type(),
+            query: if action_kind.is_query() { current_query() } else { None },
+            target_user: current_target_user(),
+        };
+        if let Err(err) = self.write(&event) {
+            error!("Failed to write audit log event: {err}");
+        }
+    }
+
+    fn write(&self, event: &AuditEvent<'_>) -> io::Result<()> {
+        let mut line = serde_json::to_vec(event).map_err(io::Error::other)?;
+        line.push(b'\n');
+        let mut appender = self.appender.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        appender.write_all(&line)?;
+        appender.flush()
+    }
+}
+
+impl fmt::Debug for AuditLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuditLog").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use serde_json::Value;
+    use test_utils::{TempDir, create_tmp_dir};
+
+    use super::{AUDIT_ACCESSOR, AUDIT_QUERY, AUDIT_TARGET_USER, AuditLog, AuditOutcome};
+    use crate::{
+        LogRotation,
+        metrics::{ActionKind, ClientEndpoint},
+    };
+
+    fn audit_log() -> (TempDir, AuditLog) {
+        let directory = create_tmp_dir("audit_log");
+        let audit_log = AuditLog::new(&directory, LogRotation::Never, 1).unwrap();
+        (directory, audit_log)
+    }
+
+    fn read_events(directory: &TempDir) -> Vec<Value> {
+        fs::read_dir(directory)
+            .unwrap()
+            .flat_map(|entry| {
+                fs::read_to_string(entry.unwrap().path()).unwrap().lines().map(str::to_owned).collect::<Vec<_>>()
+            })
+            .map(|line| serde_json::from_str(&line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn user_management_records_name_accessor_and_target_user() {
+        let (directory, audit_log) = audit_log();
+        AUDIT_ACCESSOR.sync_scope("admin".to_owned(), || {
+            AUDIT_TARGET_USER.sync_scope("alice".to_owned(), || {
+                audit_log.record(ClientEndpoint::Http, None, ActionKind::UsersDelete, AuditOutcome::Fail)
+            })
+        });
+        let events = read_events(&directory);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["client"], "http");
+        assert_eq!(events[0]["user"], "admin");
+        assert_eq!(events[0]["target_user"], "alice");
+        assert_eq!(events[0]["action"], ActionKind::UsersDelete.to_string());
+        assert_eq!(events[0]["outcome"], "fail");
+        assert_eq!(events[0]["database"], Value::Null);
+    }
+
+    #[test]
+    fn only_failed_authentications_are_recorded() {
+        let (directory, audit_log) = audit_log();
+        audit_log.record(ClientEndpoint::Grpc, None, ActionKind::Authenticate, AuditOutcome::Success);
+        audit_log.record(ClientEndpoint::Grpc, None, ActionKind::Authenticate, AuditOutcome::Fail);
+        let events = read_events(&directory);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["outcome"], "fail");
+        assert_eq!(events[0]["user"], Value::Null);
+    }
+
+    #[test]
+    fn query_is_only_recorded_for_query_actions() {
+        let (directory, audit_log) = audit_log();
+        AUDIT_QUERY.sync_scope("match $x isa person;".to_owned(), || {
+            audit_log.record(ClientEndpoint::Http, Some("people"), ActionKind::TransactionQuery, AuditOutcome::Success);
+            audit_log.record(
+                ClientEndpoint::Http,
+                Some("people"),
+                ActionKind::TransactionCommit,
+                AuditOutcome::Success,
+            );
+        });
+        let events = read_events(&directory);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0]["database"], "people");
+        assert_eq!(events[0]["query"], "match $x isa person;");
+        assert!(events[1].get("query").is_none());
+        assert!(events[1].get("target_user").is_none());
+    }
+}
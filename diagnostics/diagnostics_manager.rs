@@ -4,16 +4,18 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use std::{collections::HashSet, hash::Hash, sync::Arc};
+use std::{collections::HashSet, hash::Hash, sync::Arc, time::Duration};
 
 use concurrency::TokioTaskSpawner;
 use resource::constants::database::INTERNAL_DATABASE_PREFIX;
 
 use crate::{
     Diagnostics,
-    metrics::{ActionKind, ClientEndpoint, DatabaseMetrics, LoadKind},
+    audit::{AuditLog, AuditOutcome},
+    metrics::{ActionKind, ClientEndpoint, DatabaseMetrics, LoadKind, QueryKind},
     monitoring_server::MonitoringServer,
     reporter::Reporter,
+    slow_query::{SlowQuery, SlowQueryLog},
 };
 
 macro_rules! diagnostics_method {
@@ -33,6 +35,8 @@ pub struct DiagnosticsManager {
     diagnostics: Arc<Diagnostics>,
     reporter: Option<Reporter>,
     monitoring_server: Option<MonitoringServer>,
+    audit_log: Option<AuditLog>,
+    slow_query_log: Option<SlowQueryLog>,
 }
 
 impl DiagnosticsManager {
@@ -41,6 +45,8 @@ impl DiagnosticsManager {
         monitoring_port: u16,
         is_monitoring_enabled: bool,
         is_development_mode: bool,
+        audit_log: Option<AuditLog>,
+        slow_query_log: Option<SlowQueryLog>,
         background_tasks: TokioTaskSpawner,
     ) -> Self {
         let deployment_id = diagnostics.server_properties.deployment_id().to_owned();
@@ -66,16 +72,54 @@ impl DiagnosticsManager {
             None
         };
 
-        Self { diagnostics, reporter, monitoring_server }
+        Self { diagnostics, reporter, monitoring_server, audit_log, slow_query_log }
     }
 
     diagnostics_method! {
         pub fn submit_database_metrics(&self, database_metrics: HashSet<DatabaseMetrics>);
         pub fn submit_error(&self, client: ClientEndpoint, database_name: Option<impl AsRef<str> + Hash>, error_code: String);
-        pub fn submit_action_success(&self, client: ClientEndpoint, database_name: Option<impl AsRef<str> + Hash>, action_kind: ActionKind);
-        pub fn submit_action_fail(&self, client: ClientEndpoint, database_name: Option<impl AsRef<str> + Hash>, action_kind: ActionKind);
         pub fn increment_load_count(&self, client: ClientEndpoint, database_name: impl AsRef<str> + Hash, connection_: LoadKind);
         pub fn decrement_load_count(&self, client: ClientEndpoint, database_name: impl AsRef<str> + Hash, connection_: LoadKind);
+        pub fn submit_query_latency(&self, database_name: impl AsRef<str> + Hash, query_kind: QueryKind, latency: Duration);
+        pub fn submit_commit_latency(&self, database_name: impl AsRef<str> + Hash, latency: Duration);
+    }
+
+    pub fn submit_action_success(
+        &self,
+        client: ClientEndpoint,
+        database_name: Option<impl AsRef<str> + Hash>,
+        action_kind: ActionKind,
+    ) {
+        self.may_audit(client, database_name.as_ref().map(|name| name.as_ref()), action_kind, AuditOutcome::Success);
+        self.diagnostics.submit_action_success(client, database_name, action_kind)
+    }
+
+    pub fn submit_action_fail(
+        &self,
+        client: ClientEndpoint,
+        database_name: Option<impl AsRef<str> + Hash>,
+        action_kind: ActionKind,
+    ) {
+        self.may_audit(client, database_name.as_ref().map(|name| name.as_ref()), action_kind, AuditOutcome::Fail);
+        self.diagnostics.submit_action_fail(client, database_name, action_kind)
+    }
+
+    fn may_audit(
+        &self,
+        client: ClientEndpoint,
+        database_name: Option<&str>,
+        action_kind: ActionKind,
+        outcome: AuditOutcome,
+    ) {
+        if let Some(audit_log) = &self.audit_log {
+            audit_log.record(client, database_name, action_kind, outcome);
+        }
+    }
+
+    pub fn may_log_slow_query(&self, query: SlowQuery<'_>) {
+        if let Some(slow_query_log) = &self.slow_query_log {
+            slow_query_log.record(query);
+        }
     }
 
     pub async fn may_start_reporting(&self) {
@@ -12,15 +12,18 @@ use std::{
     hash::Hash,
     path::PathBuf,
     sync::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+    time::Duration,
 };
 
+use serde::Deserialize;
 use serde_json::Value as JSONValue;
+use tracing_appender::rolling::Rotation;
 use xxhash_rust::xxh3::Xxh3;
 
 use crate::{
     metrics::{
         ALL_CLIENT_ENDPOINTS, ActionKind, ActionMetrics, ClientEndpoint, DatabaseMetrics, ErrorMetrics, LoadKind,
-        LoadMetrics, ServerMetrics, ServerProperties, client_endpoints_map,
+        LoadMetrics, PerformanceMetrics, QueryKind, ServerMetrics, ServerProperties, client_endpoints_map,
     },
     reports::{
         json_monitoring::to_monitoring_json,
@@ -29,11 +32,13 @@ use crate::{
     },
 };
 
+pub mod audit;
 pub mod diagnostics_manager;
 pub mod metrics;
 mod monitoring_server;
 mod reporter;
 mod reports;
+pub mod slow_query;
 
 #[macro_export]
 macro_rules! error_with_report {
@@ -43,6 +48,24 @@ macro_rules! error_with_report {
     }};
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LogRotation {
+    Hourly,
+    Daily,
+    Never,
+}
+
+impl From<LogRotation> for Rotation {
+    fn from(rotation: LogRotation) -> Self {
+        match rotation {
+            LogRotation::Hourly => Rotation::HOURLY,
+            LogRotation::Daily => Rotation::DAILY,
+            LogRotation::Never => Rotation::NEVER,
+        }
+    }
+}
+
 type DatabaseHash = u64;
 type DatabaseHashOpt = Option<u64>;
 
@@ -53,6 +76,7 @@ pub struct Diagnostics {
     load_metrics: RwLock<HashMap<DatabaseHash, LoadMetrics>>,
     action_metrics: HashMap<ClientEndpoint, RwLock<HashMap<DatabaseHashOpt, ActionMetrics>>>,
     error_metrics: HashMap<ClientEndpoint, RwLock<HashMap<DatabaseHashOpt, ErrorMetrics>>>,
+    performance_metrics: RwLock<HashMap<DatabaseHash, PerformanceMetrics>>,
 
     is_full_reporting: bool,
 }
@@ -72,6 +96,7 @@ impl Diagnostics {
             load_metrics: RwLock::new(HashMap::new()),
             action_metrics: client_endpoints_map!(RwLock::new(HashMap::new())),
             error_metrics: client_endpoints_map!(RwLock::new(HashMap::new())),
+            performance_metrics: RwLock::new(HashMap::new()),
 
             is_full_reporting: is_reporting_enabled,
         }
@@ -79,6 +104,7 @@ impl Diagnostics {
 
     pub fn submit_database_metrics(&self, database_metrics: HashSet<DatabaseMetrics>) {
         let mut loads = self.lock_load_metrics_write();
+        let mut performances = self.lock_performance_metrics_write();
         let mut deleted_databases: HashSet<DatabaseHash> = loads.keys().cloned().collect();
 
         for metrics in database_metrics {
@@ -88,13 +114,36 @@ impl Diagnostics {
             let database_load = loads.entry(database_hash).or_insert(LoadMetrics::new());
             database_load.set_schema(metrics.schema);
             database_load.set_data(metrics.data);
+            performances.entry(database_hash).or_insert(PerformanceMetrics::new()).set_storage(metrics.storage);
         }
 
         for database_hash in deleted_databases {
             loads.get_mut(&database_hash).expect("Expected database in load metrics").mark_deleted();
+            // Latencies are only monitored, never reported, so they need not outlive the database
+            performances.remove(&database_hash);
         }
     }
 
+    pub fn submit_query_latency(
+        &self,
+        database_name: impl AsRef<str> + Hash,
+        query_kind: QueryKind,
+        latency: Duration,
+    ) {
+        let database_hash = Self::hash_database(database_name);
+        let performances = self.lock_performance_metrics_read_for_database(database_hash);
+        performances
+            .get(&database_hash)
+            .expect("Expected database in performances")
+            .submit_query_latency(query_kind, latency);
+    }
+
+    pub fn submit_commit_latency(&self, database_name: impl AsRef<str> + Hash, latency: Duration) {
+        let database_hash = Self::hash_database(database_name);
+        let performances = self.lock_performance_metrics_read_for_database(database_hash);
+        performances.get(&database_hash).expect("Expected database in performances").submit_commit_latency(latency);
+    }
+
     pub fn increment_load_count(
         &self,
         client: ClientEndpoint,
@@ -318,6 +367,18 @@ generate_metric_functions!(
     add_database_to_load_metrics
 );
 
+generate_metric_functions!(
+    performance_metrics,
+    PerformanceMetrics,
+    DatabaseHash,
+    PerformanceMetrics::new,
+    lock_performance_metrics_read,
+    lock_performance_metrics_write,
+    lock_performance_metrics_read_for_database,
+    try_lock_performance_metrics_read_for_database,
+    add_database_to_performance_metrics
+);
+
 generate_metric_functions!(
     action_metrics,
     ActionMetrics,
@@ -12,9 +12,10 @@ use std::{
         RwLock, RwLockReadGuard, RwLockWriteGuard,
         atomic::{AtomicU64, Ordering},
     },
-    time::Instant,
+    time::{Duration, Instant},
 };
 
+use primitive::histogram::{LatencyHistogram, LatencySnapshot};
 use resource::constants::diagnostics::UNKNOWN_STR;
 use serde::{Deserialize, Serialize};
 use sysinfo::{Disks, MemoryRefreshKind, RefreshKind, System};
@@ -22,8 +23,8 @@ use sysinfo::{Disks, MemoryRefreshKind, RefreshKind, System};
 use crate::{
     DatabaseHash, DatabaseHashOpt,
     reports::{
-        ActionReport, ConnectionLoadReport, DataLoadReport, ErrorReport, LoadReport, OsReport, SchemaLoadReport,
-        ServerPropertiesReport, ServerReport, ServerReportSensitivePart,
+        ActionReport, ConnectionLoadReport, DataLoadReport, ErrorReport, LoadReport, OsReport, PerformanceReport,
+        SchemaLoadReport, ServerPropertiesReport, ServerReport, ServerReportSensitivePart,
     },
 };
 
@@ -170,6 +171,7 @@ pub struct DatabaseMetrics {
     pub database_name: String,
     pub schema: SchemaLoadMetrics,
     pub data: DataLoadMetrics,
+    pub storage: StorageMetrics,
 }
 
 #[derive(Debug)]
@@ -250,6 +252,10 @@ impl LoadMetrics {
             None
         }
     }
+
+    pub fn to_current_connection_report(&self) -> ConnectionLoadReport {
+        self.connection.to_current_report()
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Hash)]
@@ -288,6 +294,65 @@ impl DataLoadMetrics {
     }
 }
 
+// Storage engine figures collected from the database, rather than counted by the diagnostics
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct StorageMetrics {
+    pub keyspaces: Vec<KeyspaceSizeMetrics>,
+    pub isolation_conflicts: Vec<(&'static str, u64)>,
+    pub fsync_latency: LatencySnapshot,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyspaceSizeMetrics {
+    pub keyspace: &'static str,
+    pub live_data_in_bytes: u64,
+    pub sst_files_in_bytes: u64,
+    pub memtables_in_bytes: u64,
+}
+
+#[derive(Debug)]
+pub(crate) struct PerformanceMetrics {
+    query_latency: HashMap<QueryKind, LatencyHistogram>,
+    commit_latency: LatencyHistogram,
+    storage: StorageMetrics,
+}
+
+impl PerformanceMetrics {
+    pub fn new() -> Self {
+        Self {
+            query_latency: QueryKind::all_empty_latencies_map(),
+            commit_latency: LatencyHistogram::new(),
+            storage: StorageMetrics::default(),
+        }
+    }
+
+    pub fn submit_query_latency(&self, query_kind: QueryKind, latency: Duration) {
+        self.query_latency.get(&query_kind).expect("Query keys should be preinserted").record(latency);
+    }
+
+    pub fn submit_commit_latency(&self, latency: Duration) {
+        self.commit_latency.record(latency);
+    }
+
+    pub fn set_storage(&mut self, storage: StorageMetrics) {
+        self.storage = storage;
+    }
+
+    pub fn to_state_report(
+        &self,
+        database_hash: &DatabaseHash,
+        open_transactions: ConnectionLoadReport,
+    ) -> PerformanceReport {
+        PerformanceReport {
+            database: DatabaseReport(*database_hash),
+            open_transactions,
+            query_latency: self.query_latency.iter().map(|(kind, latency)| (*kind, latency.snapshot())).collect(),
+            commit_latency: self.commit_latency.snapshot(),
+            storage: self.storage.clone(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct ConnectionLoadMetrics {
     counts: HashMap<ClientEndpoint, HashMap<LoadKind, AtomicU64>>,
@@ -351,6 +416,18 @@ impl ConnectionLoadMetrics {
         }
     }
 
+    pub fn to_current_report(&self) -> ConnectionLoadReport {
+        let mut currents = ConnectionLoadReport::new();
+        for (client, counts) in &self.counts {
+            let mut client_currents = HashMap::new();
+            for (kind, count) in counts {
+                client_currents.insert(*kind, count.load(Ordering::Relaxed));
+            }
+            currents.insert(*client, client_currents);
+        }
+        currents
+    }
+
     pub fn to_peak_report(&self) -> ConnectionLoadReport {
         let mut peaks = ConnectionLoadReport::new();
         for (client, peak_counts) in &self.peak_counts {
@@ -703,6 +780,34 @@ impl fmt::Display for LoadKind {
     }
 }
 
+#[derive(Debug, Hash, Copy, Clone, PartialEq, Eq)]
+pub enum QueryKind {
+    Read,
+    Write,
+    Schema,
+    // ATTENTION: When adding new Kinds, update all_empty_latencies_map()!
+}
+
+impl QueryKind {
+    fn all_empty_latencies_map() -> HashMap<QueryKind, LatencyHistogram> {
+        HashMap::from([
+            (QueryKind::Read, LatencyHistogram::new()),
+            (QueryKind::Write, LatencyHistogram::new()),
+            (QueryKind::Schema, LatencyHistogram::new()),
+        ])
+    }
+}
+
+impl fmt::Display for QueryKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryKind::Read => write!(f, "read"),
+            QueryKind::Write => write!(f, "write"),
+            QueryKind::Schema => write!(f, "schema"),
+        }
+    }
+}
+
 #[derive(Serialize, Debug, Hash, Copy, Clone, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ActionKind {
@@ -718,6 +823,16 @@ pub enum ActionKind {
     UsersDelete,
     UsersAll,
     UsersGet,
+    UsersGrants,
+    UsersGrant,
+    UsersRevoke,
+    UsersSessions,
+    UsersSessionsRevoke,
+    UsersApiKeys,
+    UsersApiKeyCreate,
+    UsersApiKeyDelete,
+    UsersPasswordChange,
+    UsersPasswordExpire,
     Authenticate,
     DatabasesContains,
     DatabasesCreate,
@@ -727,6 +842,9 @@ pub enum ActionKind {
     DatabaseSchema,
     DatabaseTypeSchema,
     DatabaseExport,
+    DatabaseChanges,
+    DatabaseReplication,
+    DatabaseStatistics,
     DatabaseDelete,
     TransactionOpen,
     TransactionClose,
@@ -734,7 +852,11 @@ pub enum ActionKind {
     TransactionRollback,
     TransactionAnalyse,
     TransactionQuery,
+    TransactionKill,
     OneshotQuery,
+    QueriesAll,
+    QueryKill,
+    TransactionsAll,
 }
 
 impl ActionKind {
@@ -751,6 +873,16 @@ impl ActionKind {
             (Self::UsersDelete, ActionInfo::default()),
             (Self::UsersAll, ActionInfo::default()),
             (Self::UsersGet, ActionInfo::default()),
+            (Self::UsersGrants, ActionInfo::default()),
+            (Self::UsersGrant, ActionInfo::default()),
+            (Self::UsersRevoke, ActionInfo::default()),
+            (Self::UsersSessions, ActionInfo::default()),
+            (Self::UsersSessionsRevoke, ActionInfo::default()),
+            (Self::UsersApiKeys, ActionInfo::default()),
+            (Self::UsersApiKeyCreate, ActionInfo::default()),
+            (Self::UsersApiKeyDelete, ActionInfo::default()),
+            (Self::UsersPasswordChange, ActionInfo::default()),
+            (Self::UsersPasswordExpire, ActionInfo::default()),
             (Self::Authenticate, ActionInfo::default()),
             (Self::DatabasesContains, ActionInfo::default()),
             (Self::DatabasesCreate, ActionInfo::default()),
@@ -760,6 +892,9 @@ impl ActionKind {
             (Self::DatabaseSchema, ActionInfo::default()),
             (Self::DatabaseTypeSchema, ActionInfo::default()),
             (Self::DatabaseExport, ActionInfo::default()),
+            (Self::DatabaseChanges, ActionInfo::default()),
+            (Self::DatabaseReplication, ActionInfo::default()),
+            (Self::DatabaseStatistics, ActionInfo::default()),
             (Self::DatabaseDelete, ActionInfo::default()),
             (Self::TransactionOpen, ActionInfo::default()),
             (Self::TransactionClose, ActionInfo::default()),
@@ -767,7 +902,11 @@ impl ActionKind {
             (Self::TransactionRollback, ActionInfo::default()),
             (Self::TransactionQuery, ActionInfo::default()),
             (Self::TransactionAnalyse, ActionInfo::default()),
+            (Self::TransactionKill, ActionInfo::default()),
             (Self::OneshotQuery, ActionInfo::default()),
+            (Self::QueriesAll, ActionInfo::default()),
+            (Self::QueryKill, ActionInfo::default()),
+            (Self::TransactionsAll, ActionInfo::default()),
         ])
     }
 
@@ -784,6 +923,16 @@ impl ActionKind {
             ActionKind::UsersDelete => "user_deletes",
             ActionKind::UsersAll => "user_alls",
             ActionKind::UsersGet => "user_gets",
+            ActionKind::UsersGrants => "user_grants_gets",
+            ActionKind::UsersGrant => "user_grants",
+            ActionKind::UsersRevoke => "user_revokes",
+            ActionKind::UsersSessions => "user_sessions_gets",
+            ActionKind::UsersSessionsRevoke => "user_sessions_revokes",
+            ActionKind::UsersApiKeys => "user_api_keys_gets",
+            ActionKind::UsersApiKeyCreate => "user_api_key_creates",
+            ActionKind::UsersApiKeyDelete => "user_api_key_deletes",
+            ActionKind::UsersPasswordChange => "user_password_changes",
+            ActionKind::UsersPasswordExpire => "user_password_expires",
             ActionKind::Authenticate => "authenticates",
             ActionKind::DatabasesContains => "database_containses",
             ActionKind::DatabasesCreate => "database_creates",
@@ -793,14 +942,21 @@ impl ActionKind {
             ActionKind::DatabaseSchema => "database_schemas",
             ActionKind::DatabaseTypeSchema => "database_type_schemas",
             ActionKind::DatabaseExport => "database_exports",
+            ActionKind::DatabaseChanges => "database_changes",
+            ActionKind::DatabaseReplication => "database_replication",
+            ActionKind::DatabaseStatistics => "database_statistics",
             ActionKind::DatabaseDelete => "databases_deletes",
             ActionKind::TransactionOpen => "transaction_opens",
             ActionKind::TransactionClose => "transaction_closes",
             ActionKind::TransactionCommit => "transaction_commits",
             ActionKind::TransactionRollback => "transaction_rollbacks",
             ActionKind::TransactionQuery => "transaction_queries",
+            ActionKind::TransactionKill => "transaction_kills",
             ActionKind::TransactionAnalyse => "transaction_analyses",
             ActionKind::OneshotQuery => "oneshot_queries",
+            ActionKind::QueriesAll => "query_alls",
+            ActionKind::QueryKill => "query_kills",
+            ActionKind::TransactionsAll => "transaction_alls",
         }
     }
 
@@ -826,6 +982,16 @@ impl fmt::Display for ActionKind {
             ActionKind::UsersDelete => write!(f, "USERS_DELETE"),
             ActionKind::UsersAll => write!(f, "USERS_ALL"),
             ActionKind::UsersGet => write!(f, "USERS_GET"),
+            ActionKind::UsersGrants => write!(f, "USERS_GRANTS"),
+            ActionKind::UsersGrant => write!(f, "USERS_GRANT"),
+            ActionKind::UsersRevoke => write!(f, "USERS_REVOKE"),
+            ActionKind::UsersSessions => write!(f, "USERS_SESSIONS"),
+            ActionKind::UsersSessionsRevoke => write!(f, "USERS_SESSIONS_REVOKE"),
+            ActionKind::UsersApiKeys => write!(f, "USERS_API_KEYS"),
+            ActionKind::UsersApiKeyCreate => write!(f, "USERS_API_KEY_CREATE"),
+            ActionKind::UsersApiKeyDelete => write!(f, "USERS_API_KEY_DELETE"),
+            ActionKind::UsersPasswordChange => write!(f, "USERS_PASSWORD_CHANGE"),
+            ActionKind::UsersPasswordExpire => write!(f, "USERS_PASSWORD_EXPIRE"),
             ActionKind::Authenticate => write!(f, "AUTHENTICATE"), // Analogue of 2.x's USER_TOKEN
             ActionKind::DatabasesContains => write!(f, "DATABASES_CONTAINS"),
             ActionKind::DatabasesCreate => write!(f, "DATABASES_CREATE"),
@@ -835,14 +1001,21 @@ impl fmt::Display for ActionKind {
             ActionKind::DatabaseSchema => write!(f, "DATABASES_SCHEMA"),
             ActionKind::DatabaseTypeSchema => write!(f, "DATABASES_TYPE_SCHEMA"),
             ActionKind::DatabaseExport => write!(f, "DATABASES_EXPORT"),
+            ActionKind::DatabaseChanges => write!(f, "DATABASES_CHANGES"),
+            ActionKind::DatabaseReplication => write!(f, "DATABASES_REPLICATION"),
+            ActionKind::DatabaseStatistics => write!(f, "DATABASES_STATISTICS"),
             ActionKind::DatabaseDelete => write!(f, "DATABASES_DELETE"),
             ActionKind::TransactionOpen => write!(f, "TRANSACTION_OPEN"),
             ActionKind::TransactionClose => write!(f, "TRANSACTION_CLOSE"),
             ActionKind::TransactionCommit => write!(f, "TRANSACTION_COMMIT"),
             ActionKind::TransactionRollback => write!(f, "TRANSACTION_ROLLBACK"),
             ActionKind::TransactionQuery => write!(f, "TRANSACTION_QUERY"),
+            ActionKind::TransactionKill => write!(f, "TRANSACTION_KILL"),
             ActionKind::TransactionAnalyse => write!(f, "TRANSACTION_ANALYSE"),
             ActionKind::OneshotQuery => write!(f, "ONESHOT_QUERY"),
+            ActionKind::QueriesAll => write!(f, "QUERIES_ALL"),
+            ActionKind::QueryKill => write!(f, "QUERY_KILL"),
+            ActionKind::TransactionsAll => write!(f, "TRANSACTIONS_ALL"),
         }
     }
 }
@@ -10,9 +10,11 @@ use chrono::{DateTime, Utc};
 use serde::{Serialize, Serializer, ser::SerializeStruct};
 use serde_json::{Map, Value, to_value};
 
+use primitive::histogram::LatencySnapshot;
+
 use crate::{
     DatabaseHash,
-    metrics::{ActionKind, ClientEndpoint, LoadKind},
+    metrics::{ActionKind, ClientEndpoint, LoadKind, QueryKind, StorageMetrics},
 };
 
 pub(crate) mod json_monitoring;
@@ -129,6 +131,15 @@ pub(crate) struct DataLoadReport {
 
 pub type ConnectionLoadReport = HashMap<ClientEndpoint, HashMap<LoadKind, u64>>;
 
+#[derive(Debug)]
+pub(crate) struct PerformanceReport {
+    pub database: DatabaseReport,
+    pub open_transactions: ConnectionLoadReport,
+    pub query_latency: HashMap<QueryKind, LatencySnapshot>,
+    pub commit_latency: LatencySnapshot,
+    pub storage: StorageMetrics,
+}
+
 #[derive(Debug)]
 pub(crate) struct ActionReport {
     pub database: Option<DatabaseReport>,
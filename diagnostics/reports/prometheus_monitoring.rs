@@ -3,18 +3,41 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
+use std::{collections::HashMap, fmt::Write};
+
+use primitive::histogram::LatencySnapshot;
+
 use crate::{
     Diagnostics,
-    reports::json_monitoring::{JsonMonitoringReport, to_monitoring_report},
+    metrics::LoadKind,
+    reports::{
+        PerformanceReport,
+        json_monitoring::{JsonMonitoringReport, to_monitoring_report},
+    },
 };
 
 pub fn to_monitoring_prometheus(diagnostics: &Diagnostics) -> String {
-    to_prometheus(to_monitoring_report(diagnostics))
+    let mut out = to_prometheus(to_monitoring_report(diagnostics));
+    write_performance_prometheus(&mut out, &to_performance_reports(diagnostics));
+    out
 }
 
-pub fn to_prometheus(report: JsonMonitoringReport) -> String {
-    use std::fmt::Write;
+fn to_performance_reports(diagnostics: &Diagnostics) -> Vec<PerformanceReport> {
+    let mut open_transactions: HashMap<_, _> = diagnostics
+        .lock_load_metrics_read()
+        .iter()
+        .map(|(database_hash, metrics)| (*database_hash, metrics.to_current_connection_report()))
+        .collect();
+    diagnostics
+        .lock_performance_metrics_read()
+        .iter()
+        .map(|(database_hash, metrics)| {
+            metrics.to_state_report(database_hash, open_transactions.remove(database_hash).unwrap_or_default())
+        })
+        .collect()
+}
 
+pub fn to_prometheus(report: JsonMonitoringReport) -> String {
     let mut out = String::new();
 
     writeln!(out, "# distribution: {}", report.server_properties.distribution).unwrap();
@@ -130,3 +153,152 @@ pub fn to_prometheus(report: JsonMonitoringReport) -> String {
 
     out
 }
+
+fn write_performance_prometheus(out: &mut String, reports: &[PerformanceReport]) {
+    writeln!(out, "\n# TYPE typedb_open_transactions gauge").unwrap();
+    for report in reports {
+        let mut counts: HashMap<LoadKind, u64> = HashMap::new();
+        for client_counts in report.open_transactions.values() {
+            for (kind, count) in client_counts {
+                *counts.entry(*kind).or_default() += count;
+            }
+        }
+        for (kind, count) in counts {
+            let kind = match kind {
+                LoadKind::SchemaTransactions => "schema",
+                LoadKind::ReadTransactions => "read",
+                LoadKind::WriteTransactions => "write",
+            };
+            writeln!(out, "typedb_open_transactions{{database=\"{}\", kind=\"{}\"}} {}", report.database, kind, count)
+                .unwrap();
+        }
+    }
+
+    writeln!(out, "\n# TYPE typedb_query_latency_seconds histogram").unwrap();
+    for report in reports {
+        for (kind, latency) in &report.query_latency {
+            let labels = format!("database=\"{}\", kind=\"{}\"", report.database, kind);
+            write_histogram(out, "typedb_query_latency_seconds", &labels, latency);
+        }
+    }
+
+    writeln!(out, "\n# TYPE typedb_commit_latency_seconds histogram").unwrap();
+    for report in reports {
+        let labels = format!("database=\"{}\"", report.database);
+        write_histogram(out, "typedb_commit_latency_seconds", &labels, &report.commit_latency);
+    }
+
+    writeln!(out, "\n# TYPE typedb_isolation_conflicts_total counter").unwrap();
+    for report in reports {
+        for (kind, count) in &report.storage.isolation_conflicts {
+            writeln!(
+                out,
+                "typedb_isolation_conflicts_total{{database=\"{}\", kind=\"{}\"}} {}",
+                report.database, kind, count
+            )
+            .unwrap();
+        }
+    }
+
+    writeln!(out, "\n# TYPE typedb_wal_fsync_latency_seconds histogram").unwrap();
+    for report in reports {
+        let labels = format!("database=\"{}\"", report.database);
+        write_histogram(out, "typedb_wal_fsync_latency_seconds", &labels, &report.storage.fsync_latency);
+    }
+
+    writeln!(out, "\n# TYPE typedb_storage_size_bytes gauge").unwrap();
+    for report in reports {
+        for keyspace in &report.storage.keyspaces {
+            let sizes = [
+                ("liveData", keyspace.live_data_in_bytes),
+                ("sstFiles", keyspace.sst_files_in_bytes),
+                ("memtables", keyspace.memtables_in_bytes),
+            ];
+            for (kind, size) in sizes {
+                writeln!(
+                    out,
+                    "typedb_storage_size_bytes{{database=\"{}\", keyspace=\"{}\", kind=\"{}\"}} {}",
+                    report.database, keyspace.keyspace, kind, size
+                )
+                .unwrap();
+            }
+        }
+    }
+}
+
+fn write_histogram(out: &mut String, name: &str, labels: &str, latency: &LatencySnapshot) {
+    for (bound, count) in latency.cumulative_buckets() {
+        let bound = match bound {
+            Some(bound) => bound.as_secs_f64().to_string(),
+            None => "+Inf".to_string(),
+        };
+        writeln!(out, "{name}_bucket{{{labels}, le=\"{bound}\"}} {count}").unwrap();
+    }
+    writeln!(out, "{name}_sum{{{labels}}} {}", latency.sum().as_secs_f64()).unwrap();
+    writeln!(out, "{name}_count{{{labels}}} {}", latency.count()).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, time::Duration};
+
+    use primitive::histogram::{LatencyHistogram, LatencySnapshot};
+
+    use super::write_performance_prometheus;
+    use crate::{
+        metrics::{ClientEndpoint, KeyspaceSizeMetrics, LoadKind, QueryKind, StorageMetrics},
+        reports::{DatabaseReport, PerformanceReport},
+    };
+
+    fn latencies(micros: &[u64]) -> LatencySnapshot {
+        let histogram = LatencyHistogram::new();
+        for micros in micros {
+            histogram.record(Duration::from_micros(*micros));
+        }
+        histogram.snapshot()
+    }
+
+    #[test]
+    fn performance_metrics_are_written_as_labelled_series() {
+        let report = PerformanceReport {
+            database: DatabaseReport(42),
+            open_transactions: HashMap::from([
+                (ClientEndpoint::Grpc, HashMap::from([(LoadKind::ReadTransactions, 2)])),
+                (ClientEndpoint::Http, HashMap::from([(LoadKind::ReadTransactions, 1)])),
+            ]),
+            query_latency: HashMap::from([(QueryKind::Read, latencies(&[500_000, 20_000_000]))]),
+            commit_latency: latencies(&[]),
+            storage: StorageMetrics {
+                keyspaces: vec![KeyspaceSizeMetrics {
+                    keyspace: "DefaultOptimisedPrefix11",
+                    live_data_in_bytes: 1024,
+                    sst_files_in_bytes: 2048,
+                    memtables_in_bytes: 512,
+                }],
+                isolation_conflicts: vec![("write-write", 3)],
+                fsync_latency: latencies(&[400]),
+            },
+        };
+        let mut out = String::new();
+        write_performance_prometheus(&mut out, &[report]);
+        let lines = out.lines().collect::<Vec<_>>();
+
+        for expected in [
+            "# TYPE typedb_query_latency_seconds histogram",
+            "typedb_open_transactions{database=\"42\", kind=\"read\"} 3",
+            "typedb_query_latency_seconds_bucket{database=\"42\", kind=\"read\", le=\"0.00005\"} 0",
+            "typedb_query_latency_seconds_bucket{database=\"42\", kind=\"read\", le=\"0.25\"} 0",
+            "typedb_query_latency_seconds_bucket{database=\"42\", kind=\"read\", le=\"0.5\"} 1",
+            "typedb_query_latency_seconds_bucket{database=\"42\", kind=\"read\", le=\"10\"} 1",
+            "typedb_query_latency_seconds_bucket{database=\"42\", kind=\"read\", le=\"+Inf\"} 2",
+            "typedb_query_latency_seconds_sum{database=\"42\", kind=\"read\"} 20.5",
+            "typedb_query_latency_seconds_count{database=\"42\", kind=\"read\"} 2",
+            "typedb_commit_latency_seconds_count{database=\"42\"} 0",
+            "typedb_isolation_conflicts_total{database=\"42\", kind=\"write-write\"} 3",
+            "typedb_wal_fsync_latency_seconds_bucket{database=\"42\", le=\"0.0005\"} 1",
+            "typedb_storage_size_bytes{database=\"42\", keyspace=\"DefaultOptimisedPrefix11\", kind=\"sstFiles\"} 2048",
+        ] {
+            assert!(lines.contains(&expected), "Missing '{expected}' in:\n{out}");
+        }
+    }
+}
@@ -0,0 +1,193 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::{borrow::Cow, fmt, io, io::Write, path::Path, sync::Mutex, time::Duration};
+
+use chrono::{SecondsFormat, Utc};
+use logger::error;
+use resource::profile::QueryProfile;
+use serde::Serialize;
+use tracing_appender::rolling::RollingFileAppender;
+
+use crate::{LogRotation, metrics::QueryKind};
+
+pub const SLOW_QUERY_LOG_FILE_PREFIX: &str = "slow-queries";
+pub const SLOW_QUERY_LOG_FILE_SUFFIX: &str = "jsonl";
+
+const REDACTED_LITERAL: char = '?';
+
+#[derive(Debug)]
+pub struct SlowQuery<'a> {
+    pub database_name: &'a str,
+    pub username: &'a str,
+    pub query_kind: QueryKind,
+    pub source_query: &'a str,
+    pub latency: Duration,
+    pub profile: Option<&'a QueryProfile>,
+}
+
+#[derive(Debug, Serialize)]
+struct SlowQueryEvent<'a> {
+    timestamp: String,
+    database: &'a str,
+    user: &'a str,
+    kind: String,
+    duration_micros: u128,
+    query: Cow<'a, str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    profile: Option<String>,
+}
+
+pub struct SlowQueryLog {
+    appender: Mutex<RollingFileAppender>,
+    threshold: Duration,
+    redact_literals: bool,
+}
+
+impl SlowQueryLog {
+    pub fn new(
+        directory: &Path,
+        threshold: Duration,
+        redact_literals: bool,
+        rotation: LogRotation,
+        max_files: usize,
+    ) -> io::Result<Self> {
+        std::fs::create_dir_all(directory)?;
+        let appender = RollingFileAppender::builder()
+            .rotation(rotation.into())
+            .filename_prefix(SLOW_QUERY_LOG_FILE_PREFIX)
+            .filename_suffix(SLOW_QUERY_LOG_FILE_SUFFIX)
+            .max_log_files(max_files)
+            .build(directory)
+            .map_err(io::Error::other)?;
+        Ok(Self { appender: Mutex::new(appender), threshold, redact_literals })
+    }
+
+    pub(crate) fn record(&self, query: SlowQuery<'_>) {
+        if query.latency < self.threshold {
+            return;
+        }
+        let event = SlowQueryEvent {
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            database: query.database_name,
+            user: query.username,
+            kind: query.query_kind.to_string(),
+            duration_micros: query.latency.as_micros(),
+            query: match self.redact_literals {
+                true => Cow::Owned(redact_literals(query.source_query)),
+                false => Cow::Borrowed(query.source_query),
+            },
+            profile: query.profile.filter(|profile| profile.is_enabled()).map(|profile| profile.to_string()),
+        };
+        if let Err(err) = self.write(&event) {
+            error!("Failed to write slow query log event: {err}");
+        }
+    }
+
+    fn write(&self, event: &SlowQueryEvent<'_>) -> io::Result<()> {
+        let mut line = serde_json::to_vec(event).map_err(io::Error::other)?;
+        line.push(b'\n');
+        let mut appender = self.appender.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        appender.write_all(&line)?;
+        appender.flush()
+    }
+}
+
+impl fmt::Debug for SlowQueryLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SlowQueryLog")
+            .field("threshold", &self.threshold)
+            .field("redact_literals", &self.redact_literals)
+            .finish_non_exhaustive()
+    }
+}
+
+// Replaces the string, numeric, date-time, duration and boolean literals of a query with a placeholder,
+// leaving its keywords, labels and variables in place. Comments are kept as they are written.
+fn redact_literals(query: &str) -> String {
+    let mut redacted = String::with_capacity(query.len());
+    let mut chars = query.char_indices().peekable();
+    while let Some((start, current)) = chars.next() {
+        match current {
+            '"' | '\'' => {
+                let mut is_escaped = false;
+                for (_, next) in chars.by_ref() {
+                    match next {
+                        '\\' if !is_escaped => is_escaped = true,
+                        next if next == current && !is_escaped => break,
+                        _ => is_escaped = false,
+                    }
+                }
+                redacted.push(REDACTED_LITERAL);
+            }
+            '#' => {
+                redacted.push(current);
+                while let Some((_, next)) = chars.next_if(|(_, next)| *next != '\n') {
+                    redacted.push(next);
+                }
+            }
+            digit if digit.is_ascii_digit() => {
+                while chars
+                    .next_if(|(_, next)| next.is_alphanumeric() || matches!(*next, '.' | ':' | '-' | '_'))
+                    .is_some()
+                {}
+                redacted.push(REDACTED_LITERAL);
+            }
+            first if first.is_alphabetic() || matches!(first, '_' | '$' | '?') => {
+                let mut end = start + first.len_utf8();
+                while let Some((index, next)) =
+                    chars.next_if(|(_, next)| next.is_alphanumeric() || matches!(*next, '_' | '-'))
+                {
+                    end = index + next.len_utf8();
+                }
+                let word = &query[start..end];
+                if is_literal_word(word) {
+                    redacted.push(REDACTED_LITERAL);
+                } else {
+                    redacted.push_str(word);
+                }
+            }
+            other => redacted.push(other),
+        }
+    }
+    redacted
+}
+
+fn is_literal_word(word: &str) -> bool {
+    let bytes = word.as_bytes();
+    let is_duration = bytes.first() == Some(&b'P')
+        && match bytes.get(1) {
+            Some(b'T') => bytes.get(2).is_some_and(u8::is_ascii_digit),
+            Some(next) => next.is_ascii_digit(),
+            None => false,
+        };
+    is_duration || word == "true" || word == "false"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::redact_literals;
+
+    #[test]
+    fn literals_are_redacted() {
+        assert_eq!(
+            redact_literals(r#"match $p isa person, has name "Alice \"A\" Smith", has age 42, has score 1.5dec;"#),
+            "match $p isa person, has name ?, has age ?, has score ?;"
+        );
+        assert_eq!(
+            redact_literals("match $e isa event, has start 2024-01-01T10:00:00, has length P1DT2H, has open true;"),
+            "match $e isa event, has start ?, has length ?, has open ?;"
+        );
+    }
+
+    #[test]
+    fn labels_variables_and_comments_are_kept() {
+        assert_eq!(
+            redact_literals("match $x1 isa sensor-2; # a 'comment' with 3 words\nlimit 10;"),
+            "match $x1 isa sensor-2; # a 'comment' with 3 words\nlimit ?;"
+        );
+    }
+}
@@ -12,6 +12,7 @@ use std::{
     error::Error,
     fmt, io,
     ops::{Add, AddAssign, Sub},
+    path::Path,
     sync::Arc,
 };
 
@@ -50,6 +51,14 @@ pub trait DurabilityService {
 
     fn truncate_from(&self, sequence_number: DurabilitySequenceNumber) -> Result<(), DurabilityServiceError>;
 
+    /// Copies the records from `sequence_number` onwards into a new durability service rooted at `directory`.
+    /// The copy may also contain some earlier records. Returns the last sequence number included in the copy.
+    fn copy_from(
+        &self,
+        sequence_number: DurabilitySequenceNumber,
+        directory: &Path,
+    ) -> Result<DurabilitySequenceNumber, DurabilityServiceError>;
+
     fn delete_durability(self) -> Result<(), DurabilityServiceError>;
 
     fn reset(&mut self) -> Result<(), DurabilityServiceError>;
@@ -57,7 +66,7 @@ pub trait DurabilityService {
 
 pub type DurabilityRecordType = u8;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RawRecord<'a> {
     pub sequence_number: DurabilitySequenceNumber,
     pub record_type: DurabilityRecordType,
@@ -30,6 +30,7 @@ use fail_point::{
 };
 use itertools::Itertools;
 use logger::result::ResultExt;
+use primitive::histogram::{LatencyHistogram, LatencySnapshot};
 use resource::constants::storage::WAL_SYNC_INTERVAL_MICROSECONDS;
 use tracing::{debug, warn};
 
@@ -118,6 +119,24 @@ impl WAL {
     pub fn request_sync(&self, ack_waits_for_sync: bool) -> mpsc::Receiver<()> {
         self.fsync_thread.schedule_next_sync_may_subscribe(ack_waits_for_sync)
     }
+
+    pub fn fsync_latency(&self) -> LatencySnapshot {
+        self.fsync_thread.context.fsync_latency.snapshot()
+    }
+
+    /// Appends a record read from another WAL, keeping its sequence number. This is used to assemble a WAL from
+    /// copied segments, so the record may not be older than the last record already written.
+    pub fn append_copied(&self, record: RawRecord<'_>) -> Result<(), DurabilityServiceError> {
+        let mut files = self.files.write().unwrap();
+        let sequence_number = record.sequence_number;
+        let previous = self.previous();
+        if sequence_number < previous {
+            Err(WALError::AppendOutOfOrder { sequence_number, previous })?
+        }
+        files.write_record(record)?;
+        self.next_sequence_number.fetch_max(sequence_number.next().number(), Ordering::SeqCst);
+        Ok(())
+    }
 }
 
 impl DurabilityService for WAL {
@@ -206,6 +225,26 @@ impl DurabilityService for WAL {
         Ok(())
     }
 
+    fn copy_from(
+        &self,
+        sequence_number: DurabilitySequenceNumber,
+        directory: &Path,
+    ) -> Result<DurabilitySequenceNumber, DurabilityServiceError> {
+        // Files are append-only and every record is flushed, so each file can be copied up to the length it had
+        // under the lock without blocking further writes
+        let (files, last) = {
+            let files = self.files.read().unwrap();
+            let first = files.file_index_containing(sequence_number).unwrap_or(0);
+            (files.files[first..].to_vec(), self.previous())
+        };
+        let wal_dir = directory.join(Self::WAL_DIR_NAME);
+        fs::create_dir_all(&wal_dir).map_err(|err| WALError::Create { source: Arc::new(err) })?;
+        for file in files {
+            file.copy_into(&wal_dir)?;
+        }
+        Ok(last)
+    }
+
     fn delete_durability(self) -> Result<(), DurabilityServiceError> {
         drop(self.fsync_thread);
         let files = Arc::into_inner(self.files)
@@ -230,6 +269,7 @@ pub enum WALError {
     Compression { source: Arc<io::Error> },
     Decompression { source: Arc<io::Error> },
     Sync { source: Arc<io::Error> },
+    AppendOutOfOrder { sequence_number: DurabilitySequenceNumber, previous: DurabilitySequenceNumber },
 }
 
 impl fmt::Display for WALError {
@@ -248,6 +288,7 @@ impl Error for WALError {
             Self::Compression { source, .. } => Some(source),
             Self::Decompression { source, .. } => Some(source),
             Self::Sync { source, .. } => Some(source),
+            Self::AppendOutOfOrder { .. } => None,
         }
     }
 }
@@ -483,6 +524,13 @@ impl File {
         Ok(())
     }
 
+    fn copy_into(&self, directory: &Path) -> io::Result<()> {
+        let mut source = StdFile::open(&self.path)?.take(self.len);
+        let mut target = StdFile::create(directory.join(self.path.file_name().unwrap()))?;
+        io::copy(&mut source, &mut target)?;
+        target.sync_all()
+    }
+
     fn writer(&self) -> io::Result<BufWriter<StdFile>> {
         Ok(BufWriter::new(OpenOptions::new().read(true).append(true).create(true).open(&self.path)?))
     }
@@ -662,6 +710,7 @@ pub struct FsyncThreadContext {
     shutting_down: AtomicBool,
     signalling: [Mutex<Vec<Option<mpsc::Sender<()>>>>; 2],
     current_signal: AtomicU8,
+    fsync_latency: LatencyHistogram,
 }
 
 #[derive(Debug)]
@@ -677,6 +726,7 @@ impl FsyncThread {
             shutting_down: AtomicBool::new(false),
             signalling: [Mutex::new(Vec::new()), Mutex::new(Vec::new())],
             current_signal: AtomicU8::new(0),
+            fsync_latency: LatencyHistogram::new(),
         };
         Self { handle: None, context: Arc::new(context) }
     }
@@ -723,7 +773,9 @@ impl FsyncThread {
         let vec_lock = context.signalling.get(current_signal as usize).unwrap().lock();
         let mut vec = vec_lock.unwrap();
         if !vec.is_empty() {
+            let sync_start = Instant::now();
             context.files.write().unwrap().sync_all().expect("Expected sync all");
+            context.fsync_latency.record(sync_start.elapsed());
             while let Some(sender_opt) = vec.pop() {
                 if let Some(sender) = sender_opt {
                     sender.send(()).unwrap();
@@ -1066,6 +1118,31 @@ mod test {
         assert_eq!(wal.current(), cut);
     }
 
+    #[test]
+    fn test_wal_copy_from_keeps_tail_across_multiple_files() {
+        let directory = TempDir::new("wal-test").unwrap();
+        let wal = create_wal(&directory);
+
+        let mut seqs = Vec::new();
+        // Should be enough for 3 files
+        let records_num = MAX_WAL_FILE_SIZE.div_ceil(16) as usize;
+        for i in 0..records_num {
+            let payload = format!("r{:04}", i);
+            seqs.push(wal.sequenced_write(TestRecord::RECORD_TYPE, payload.as_bytes()).unwrap());
+        }
+
+        let copy_start = seqs[records_num - 5];
+        let copy_directory = TempDir::new("wal-copy-test").unwrap();
+        wal.copy_from(copy_start, copy_directory.path()).unwrap();
+
+        let copy = load_wal(&copy_directory);
+        let copied = read_all_records(&copy).map(|record| record.sequence_number).collect_vec();
+        assert!(copied.len() < records_num, "Expected only the newest files to be copied");
+        assert!(copied.first().unwrap().number() <= copy_start.number());
+        assert_eq!(copied.last(), seqs.last());
+        assert_eq!(copy.current(), wal.current());
+    }
+
     #[test]
     fn test_wal_truncate_from_beginning_clears_everything() {
         let directory = TempDir::new("wal-test").unwrap();
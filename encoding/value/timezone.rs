@@ -4,9 +4,9 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use std::fmt;
+use std::{fmt, str::FromStr};
 
-use chrono::{FixedOffset, MappedLocalTime, NaiveDate, NaiveDateTime};
+use chrono::{FixedOffset, MappedLocalTime, NaiveDate, NaiveDateTime, Offset as _, Utc};
 use chrono_tz::Tz;
 
 use super::primitive_encoding::{decode_i32, decode_u32, encode_i32, encode_u32};
@@ -102,6 +102,24 @@ impl chrono::TimeZone for TimeZone {
     }
 }
 
+#[derive(Debug)]
+pub struct TimeZoneParseError;
+
+impl FromStr for TimeZone {
+    type Err = TimeZoneParseError;
+
+    /// Parses an IANA timezone name, or an offset from UTC such as `+05:30` or `Z`
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        if str == "Z" {
+            Ok(Self::Fixed(Utc.fix()))
+        } else if str.starts_with(['+', '-']) {
+            FixedOffset::from_str(str).map(Self::Fixed).map_err(|_| TimeZoneParseError)
+        } else {
+            Tz::from_str_insensitive(str).map(Self::IANA).map_err(|_| TimeZoneParseError)
+        }
+    }
+}
+
 impl fmt::Display for TimeZone {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
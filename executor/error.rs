@@ -17,5 +17,7 @@ typedb_error! {
         CreatingIterator(3, "Error creating iterator from {instruction_name} instruction.", instruction_name: String, typedb_source: Box<ConceptReadError>),
         AdvancingIteratorTo(4, "Error moving iterator (by steps or seek) to target value.", typedb_source: Box<ConceptReadError>),
         ExpressionEvaluate(5, "Error evaluating expression.", typedb_source: ExpressionEvaluationError),
+        CollectedRowsLimitExceeded(6, "Query exceeded the limit of {limit} rows sorted, or groups reduced, by a single stage.", limit: usize),
+        TabledRowsLimitExceeded(7, "Query exceeded the limit of {limit} rows held in the answer tables of recursive function calls.", limit: usize),
     }
 }
@@ -7,7 +7,11 @@
 #![deny(unused_must_use)]
 #![deny(elided_lifetimes_in_paths)]
 
-use std::{fmt, slice};
+use std::{
+    fmt, slice,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
 
 use compiler::VariablePosition;
 use ir::pattern::BranchID;
@@ -18,6 +22,7 @@ pub mod document;
 pub mod error;
 pub(crate) mod instruction;
 pub mod match_executor;
+pub mod partitioned_match_executor;
 pub mod pipeline;
 pub mod read;
 pub(crate) mod reduce_executor;
@@ -54,6 +59,8 @@ pub enum InterruptType {
     TransactionRolledback,
     WriteQueryExecution,
     SchemaQueryExecution,
+    QueryKilled,
+    QueryTimeout,
 }
 
 impl fmt::Display for InterruptType {
@@ -64,22 +71,78 @@ impl fmt::Display for InterruptType {
             InterruptType::TransactionRolledback => write!(f, "transaction rollback"),
             InterruptType::WriteQueryExecution => write!(f, "write query"),
             InterruptType::SchemaQueryExecution => write!(f, "schema query"),
+            InterruptType::QueryKilled => write!(f, "query kill"),
+            InterruptType::QueryTimeout => write!(f, "query timeout"),
         }
     }
 }
 
 #[derive(Debug)]
 pub struct ExecutionInterrupt {
-    signal: Option<tokio::sync::broadcast::Receiver<InterruptType>>,
+    signals: Vec<tokio::sync::broadcast::Receiver<InterruptType>>,
+    // shared by all clones, so that pausing it pauses the whole execution of a query
+    deadline: Option<Arc<Mutex<Deadline>>>,
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Deadline {
+    Running(Instant),
+    Paused(Duration),
+}
+
+impl Deadline {
+    fn instant(&self) -> Instant {
+        match *self {
+            Deadline::Running(instant) => instant,
+            Deadline::Paused(remaining) => Instant::now() + remaining,
+        }
+    }
 }
 
 impl ExecutionInterrupt {
     pub fn new(signal: tokio::sync::broadcast::Receiver<InterruptType>) -> Self {
-        Self { signal: Some(signal) }
+        Self { signals: vec![signal], deadline: None }
     }
 
     pub fn new_uninterruptible() -> Self {
-        Self { signal: None }
+        Self { signals: Vec::new(), deadline: None }
+    }
+
+    // Also interrupts on a signal of narrower scope, such as one targeting a single query of a transaction
+    pub fn with_signal(mut self, signal: tokio::sync::broadcast::Receiver<InterruptType>) -> Self {
+        self.signals.push(signal);
+        self
+    }
+
+    // Also interrupts once the deadline has passed, keeping the earliest of any deadlines given
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        let deadline = match self.lock_deadline().map(|existing| existing.instant()) {
+            Some(existing) => existing.min(deadline),
+            None => deadline,
+        };
+        self.deadline = Some(Arc::new(Mutex::new(Deadline::Running(deadline))));
+        self
+    }
+
+    // Stops the time towards the deadline, for every clone of this interrupt, until it is resumed
+    pub fn pause_deadline(&self) {
+        if let Some(mut deadline) = self.lock_deadline() {
+            if let Deadline::Running(instant) = *deadline {
+                *deadline = Deadline::Paused(instant.saturating_duration_since(Instant::now()));
+            }
+        }
+    }
+
+    pub fn resume_deadline(&self) {
+        if let Some(mut deadline) = self.lock_deadline() {
+            if let Deadline::Paused(remaining) = *deadline {
+                *deadline = Deadline::Running(Instant::now() + remaining);
+            }
+        }
+    }
+
+    fn lock_deadline(&self) -> Option<MutexGuard<'_, Deadline>> {
+        self.deadline.as_ref().map(|deadline| deadline.lock().unwrap_or_else(|poisoned| poisoned.into_inner()))
     }
 
     pub fn check(&mut self) -> Option<InterruptType> {
@@ -87,25 +150,37 @@ impl ExecutionInterrupt {
         //       optimise it by caching the last time it was checked, and only actually check
         //       the signal once T micros/millis are elapsed... if this is really really cheap we can
         //       check the optimised interrupt in really hot loops as well.
-        match &mut self.signal {
-            None => None,
-            Some(signal) => match signal.try_recv() {
-                Ok(type_) => Some(type_),
-                Err(TryRecvError::Empty) => None,
-                Err(TryRecvError::Closed) | Err(TryRecvError::Lagged(_)) => {
-                    unreachable!(
-                        "Unexpected interrupt signal state. They should never be lagged or closed before cleaning up the receivers."
-                    )
+        let signalled = self.signals.iter_mut().find_map(|signal| {
+            loop {
+                match signal.try_recv() {
+                    Ok(type_) => return Some(type_),
+                    Err(TryRecvError::Empty) => return None,
+                    // a signal sent more often than the channel holds: the most recent ones are still received
+                    Err(TryRecvError::Lagged(_)) => continue,
+                    Err(TryRecvError::Closed) => {
+                        unreachable!(
+                            "Unexpected interrupt signal state. They should never be closed before cleaning up the receivers."
+                        )
+                    }
                 }
-            },
-        }
+            }
+        });
+        signalled.or_else(|| {
+            let passed = self
+                .lock_deadline()
+                .is_some_and(|deadline| matches!(*deadline, Deadline::Running(instant) if Instant::now() >= instant));
+            passed.then_some(InterruptType::QueryTimeout)
+        })
     }
 }
 
 impl Clone for ExecutionInterrupt {
     // Note: going against tokio's broadcast signal convention, which explicitly isn't `clone()`
     fn clone(&self) -> Self {
-        Self { signal: self.signal.as_ref().map(|signal| signal.resubscribe()) }
+        Self {
+            signals: self.signals.iter().map(|signal| signal.resubscribe()).collect(),
+            deadline: self.deadline.clone(),
+        }
     }
 }
 
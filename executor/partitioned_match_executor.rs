@@ -0,0 +1,405 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::{
+    any::Any,
+    fmt,
+    num::NonZeroUsize,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        Arc, Mutex, OnceLock,
+        mpsc::{Receiver, Sender, SyncSender, TryRecvError, TrySendError, channel, sync_channel},
+    },
+    thread,
+};
+
+use compiler::executable::{
+    function::ExecutableFunctionRegistry,
+    match_::planner::conjunction_executable::{ConjunctionExecutable, ExecutionStep},
+};
+use resource::{
+    constants::query::{PARALLEL_MATCH_QUEUED_BATCHES_PER_WORKER, PARALLEL_MATCH_WORKERS_MAX},
+    profile::StageProfile,
+};
+use storage::snapshot::ReadableSnapshot;
+
+use crate::{
+    ExecutionInterrupt,
+    batch::FixedBatch,
+    error::ReadExecutionError,
+    pipeline::stage::ExecutionContext,
+    read::{
+        create_pattern_executor_for_conjunction, pattern_executor::PatternExecutor, tabled_functions::TabledFunctions,
+    },
+    row::MaybeOwnedRow,
+};
+
+/// The number of threads the match stages of all parallel queries share.
+pub fn parallel_match_workers() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get).min(PARALLEL_MATCH_WORKERS_MAX)
+}
+
+/// Executes a match stage for its single input row across several threads.
+/// Only constructed for snapshots which may be shared between threads.
+pub trait MatchPartitioner<Snapshot>: fmt::Debug + Send + Sync {
+    fn execute(
+        &self,
+        context: &ExecutionContext<Snapshot>,
+        conjunction_executable: Arc<ConjunctionExecutable>,
+        function_registry: Arc<ExecutableFunctionRegistry>,
+        input: MaybeOwnedRow<'static>,
+        interrupt: &ExecutionInterrupt,
+    ) -> PartitionedRows<Snapshot>;
+}
+
+/// Computes the first step of a conjunction on the thread consuming its rows, and partitions the batches it produces
+/// across jobs on the shared worker threads, which each execute the remaining steps. Rows are merged in the order the
+/// jobs produce them.
+#[derive(Debug)]
+pub(crate) struct ThreadedMatchPartitioner {
+    workers: usize,
+}
+
+impl ThreadedMatchPartitioner {
+    pub(crate) fn new(workers: usize) -> Self {
+        Self { workers }
+    }
+
+    // Function calls are evaluated against answer tables whose executors cannot move between threads, so every job
+    // would have to evaluate the same calls again.
+    pub(crate) fn is_partitionable(conjunction_executable: &ConjunctionExecutable) -> bool {
+        matches!(conjunction_executable.steps(), [ExecutionStep::Intersection(_), _, ..])
+            && !calls_functions(conjunction_executable)
+    }
+}
+
+fn calls_functions(conjunction_executable: &ConjunctionExecutable) -> bool {
+    conjunction_executable.steps().iter().any(|step| match step {
+        ExecutionStep::FunctionCall(_) => true,
+        ExecutionStep::Disjunction(step) => step.branches.iter().any(calls_functions),
+        ExecutionStep::Negation(step) => calls_functions(&step.negation),
+        ExecutionStep::Optional(step) => calls_functions(&step.optional),
+        ExecutionStep::Intersection(_)
+        | ExecutionStep::UnsortedJoin(_)
+        | ExecutionStep::Assignment(_)
+        | ExecutionStep::Check(_) => false,
+    })
+}
+
+impl<Snapshot: ReadableSnapshot + Send + Sync + 'static> MatchPartitioner<Snapshot> for ThreadedMatchPartitioner {
+    fn execute(
+        &self,
+        context: &ExecutionContext<Snapshot>,
+        conjunction_executable: Arc<ConjunctionExecutable>,
+        function_registry: Arc<ExecutableFunctionRegistry>,
+        input: MaybeOwnedRow<'static>,
+        interrupt: &ExecutionInterrupt,
+    ) -> PartitionedRows<Snapshot> {
+        let context = ExecutionContext { partitioner: None, ..context.clone() };
+        let stage_profile =
+            context.profile.profile_stage(|| String::from("Match"), conjunction_executable.executable_id());
+        let queued_batches = self.workers * PARALLEL_MATCH_QUEUED_BATCHES_PER_WORKER;
+        let (partition_sender, partition_receiver) = sync_channel(queued_batches);
+        let (result_sender, result_receiver) = sync_channel(queued_batches);
+        let (finished_sender, finished_receiver) = channel();
+        let partition_receiver = Arc::new(Mutex::new(partition_receiver));
+        let worker = PartitionWorker { context, conjunction_executable, function_registry, stage_profile };
+
+        let first_step = match worker.create_split_executors() {
+            Ok((mut first_step, _)) => {
+                first_step.prepare(FixedBatch::from(input));
+                FirstStep {
+                    executor: first_step,
+                    tabled_functions: TabledFunctions::new(worker.function_registry.clone()),
+                    context: worker.context.clone(),
+                    interrupt: interrupt.clone(),
+                    partitions: partition_sender,
+                    unsent: None,
+                }
+            }
+            Err(err) => return PartitionedRows::failed(err, result_receiver, finished_receiver),
+        };
+        for _ in 0..self.workers {
+            let job = RemainingStepsJob {
+                worker: worker.clone(),
+                interrupt: interrupt.clone(),
+                partitions: partition_receiver.clone(),
+                results: result_sender.clone(),
+                _finished: finished_sender.clone(),
+            };
+            MatchWorkerPool::get().submit(Box::new(move || job.run()));
+        }
+        PartitionedRows::new(first_step, result_receiver, finished_receiver)
+    }
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// A fixed set of threads shared by the partitioned matches of every query, so that concurrent queries cannot start
+/// more threads between them than the machine has cores.
+struct MatchWorkerPool {
+    jobs: Sender<Job>,
+}
+
+impl MatchWorkerPool {
+    fn get() -> &'static Self {
+        static POOL: OnceLock<MatchWorkerPool> = OnceLock::new();
+        POOL.get_or_init(|| Self::start(parallel_match_workers()))
+    }
+
+    fn start(threads: usize) -> Self {
+        let (jobs, job_receiver) = channel::<Job>();
+        let job_receiver = Arc::new(Mutex::new(job_receiver));
+        for index in 0..threads {
+            let job_receiver = job_receiver.clone();
+            thread::Builder::new()
+                .name(format!("match-worker-{index}"))
+                .spawn(move || {
+                    loop {
+                        let job = job_receiver.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).recv();
+                        match job {
+                            Ok(job) => job(),
+                            Err(_) => return,
+                        }
+                    }
+                })
+                .expect("Failed to start a parallel match worker thread");
+        }
+        Self { jobs }
+    }
+
+    fn submit(&self, job: Job) {
+        self.jobs.send(job).expect("The parallel match worker threads never exit")
+    }
+}
+
+enum PartitionOutput {
+    Rows(FixedBatch),
+    // A job finished a partition, so there is room for another
+    PartitionDone,
+    Failed(ReadExecutionError),
+    Panicked(Box<dyn Any + Send>),
+}
+
+struct PartitionWorker<Snapshot> {
+    context: ExecutionContext<Snapshot>,
+    conjunction_executable: Arc<ConjunctionExecutable>,
+    function_registry: Arc<ExecutableFunctionRegistry>,
+    stage_profile: Arc<StageProfile>,
+}
+
+impl<Snapshot: ReadableSnapshot + 'static> PartitionWorker<Snapshot> {
+    fn create_split_executors(&self) -> Result<(PatternExecutor, PatternExecutor), ReadExecutionError> {
+        create_pattern_executor_for_conjunction(
+            &self.context.snapshot,
+            &self.context.thing_manager,
+            &self.function_registry,
+            &self.conjunction_executable,
+            self.stage_profile.clone(),
+        )
+        .map(PatternExecutor::split_first_step)
+        .map_err(|typedb_source| ReadExecutionError::ConceptRead { typedb_source })
+    }
+}
+
+impl<Snapshot> Clone for PartitionWorker<Snapshot> {
+    fn clone(&self) -> Self {
+        Self {
+            context: self.context.clone(),
+            conjunction_executable: self.conjunction_executable.clone(),
+            function_registry: self.function_registry.clone(),
+            stage_profile: self.stage_profile.clone(),
+        }
+    }
+}
+
+// Executes the remaining steps for partitions until none are left. A failed send means the rows were dropped, since
+// the query has finished or failed, so the job stops.
+struct RemainingStepsJob<Snapshot> {
+    worker: PartitionWorker<Snapshot>,
+    interrupt: ExecutionInterrupt,
+    partitions: Arc<Mutex<Receiver<FixedBatch>>>,
+    results: SyncSender<PartitionOutput>,
+    // Disconnects once the job, and the snapshot it holds, is dropped
+    _finished: Sender<()>,
+}
+
+impl<Snapshot: ReadableSnapshot + 'static> RemainingStepsJob<Snapshot> {
+    fn run(self) {
+        let results = self.results.clone();
+        if let Err(panic) = panic::catch_unwind(AssertUnwindSafe(|| self.execute_remaining_steps())) {
+            let _ = results.send(PartitionOutput::Panicked(panic));
+        }
+    }
+
+    fn execute_remaining_steps(mut self) {
+        let mut remaining_steps = match self.worker.create_split_executors() {
+            Ok((_, remaining_steps)) => remaining_steps,
+            Err(err) => {
+                let _ = self.results.send(PartitionOutput::Failed(err));
+                return;
+            }
+        };
+        // Never used, since partitioned conjunctions do not call functions
+        let mut tabled_functions = TabledFunctions::new(self.worker.function_registry.clone());
+        loop {
+            let partition = self.partitions.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).recv();
+            let Ok(partition) = partition else {
+                return;
+            };
+            remaining_steps.prepare(partition);
+            loop {
+                let batch = remaining_steps.compute_next_batch(
+                    &self.worker.context,
+                    &mut self.interrupt,
+                    &mut tabled_functions,
+                );
+                let output = match batch {
+                    Ok(Some(batch)) => PartitionOutput::Rows(batch),
+                    Ok(None) => break,
+                    Err(err) => {
+                        let _ = self.results.send(PartitionOutput::Failed(err));
+                        return;
+                    }
+                };
+                if self.results.send(output).is_err() {
+                    return;
+                }
+            }
+            if self.results.send(PartitionOutput::PartitionDone).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+struct FirstStep<Snapshot> {
+    executor: PatternExecutor,
+    tabled_functions: TabledFunctions,
+    context: ExecutionContext<Snapshot>,
+    interrupt: ExecutionInterrupt,
+    partitions: SyncSender<FixedBatch>,
+    unsent: Option<FixedBatch>,
+}
+
+enum FirstStepProgress {
+    Partitioned,
+    QueueFull,
+    Exhausted,
+}
+
+impl<Snapshot: ReadableSnapshot + 'static> FirstStep<Snapshot> {
+    fn partition_next(&mut self) -> Result<FirstStepProgress, ReadExecutionError> {
+        let batch = match self.unsent.take() {
+            Some(batch) => batch,
+            None => {
+                match self.executor.compute_next_batch(
+                    &self.context,
+                    &mut self.interrupt,
+                    &mut self.tabled_functions,
+                )? {
+                    Some(batch) => batch,
+                    None => return Ok(FirstStepProgress::Exhausted),
+                }
+            }
+        };
+        match self.partitions.try_send(batch) {
+            Ok(()) => Ok(FirstStepProgress::Partitioned),
+            Err(TrySendError::Full(batch)) => {
+                self.unsent = Some(batch);
+                Ok(FirstStepProgress::QueueFull)
+            }
+            // Every job has stopped, and reported why
+            Err(TrySendError::Disconnected(_)) => Ok(FirstStepProgress::Exhausted),
+        }
+    }
+}
+
+/// The rows of a partitioned match, in the order its jobs produce them.
+/// The jobs have always finished, releasing the snapshot, once the rows are exhausted or dropped.
+pub struct PartitionedRows<Snapshot> {
+    first_step: Option<FirstStep<Snapshot>>,
+    first_step_error: Option<ReadExecutionError>,
+    results: Option<Receiver<PartitionOutput>>,
+    finished: Receiver<()>,
+    current_batch: Option<<FixedBatch as IntoIterator>::IntoIter>,
+}
+
+impl<Snapshot> PartitionedRows<Snapshot> {
+    fn new(first_step: FirstStep<Snapshot>, results: Receiver<PartitionOutput>, finished: Receiver<()>) -> Self {
+        Self {
+            first_step: Some(first_step),
+            first_step_error: None,
+            results: Some(results),
+            finished,
+            current_batch: None,
+        }
+    }
+
+    fn failed(error: ReadExecutionError, results: Receiver<PartitionOutput>, finished: Receiver<()>) -> Self {
+        Self { first_step: None, first_step_error: Some(error), results: Some(results), finished, current_batch: None }
+    }
+}
+
+impl<Snapshot: ReadableSnapshot + 'static> PartitionedRows<Snapshot> {
+    // Partitions the first step's batches while the jobs have room for them, and otherwise waits for their output
+    fn next_output(&mut self) -> Option<PartitionOutput> {
+        loop {
+            let results = self.results.as_ref()?;
+            match results.try_recv() {
+                Ok(output) => return Some(output),
+                Err(TryRecvError::Disconnected) => return None,
+                Err(TryRecvError::Empty) => (),
+            }
+            let Some(first_step) = self.first_step.as_mut() else {
+                return results.recv().ok();
+            };
+            match first_step.partition_next() {
+                Ok(FirstStepProgress::Partitioned) => (),
+                Ok(FirstStepProgress::QueueFull) => return results.recv().ok(),
+                Ok(FirstStepProgress::Exhausted) => self.first_step = None,
+                Err(err) => {
+                    self.first_step = None;
+                    return Some(PartitionOutput::Failed(err));
+                }
+            }
+        }
+    }
+}
+
+impl<Snapshot: ReadableSnapshot + 'static> Iterator for PartitionedRows<Snapshot> {
+    type Item = Result<MaybeOwnedRow<'static>, ReadExecutionError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(err) = self.first_step_error.take() {
+            return Some(Err(err));
+        }
+        loop {
+            if let Some(row) = self.current_batch.as_mut().and_then(Iterator::next) {
+                return Some(Ok(row));
+            }
+            match self.next_output() {
+                Some(PartitionOutput::Rows(batch)) => self.current_batch = Some(batch.into_iter()),
+                Some(PartitionOutput::PartitionDone) => (),
+                Some(PartitionOutput::Failed(err)) => return Some(Err(err)),
+                Some(PartitionOutput::Panicked(panic)) => panic::resume_unwind(panic),
+                None => {
+                    self.results = None;
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+impl<Snapshot> Drop for PartitionedRows<Snapshot> {
+    fn drop(&mut self) {
+        // Disconnect first, so that jobs stop at their next send rather than computing their remaining partitions
+        drop(self.first_step.take());
+        drop(self.results.take());
+        let _ = self.finished.recv();
+    }
+}
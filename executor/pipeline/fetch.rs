@@ -7,6 +7,7 @@
 use std::{collections::HashMap, marker::PhantomData, sync::Arc};
 
 use answer::{Concept, Thing, variable::Variable, variable_value::VariableValue};
+use chrono::{DateTime, Utc};
 use compiler::{
     VariablePosition,
     executable::{
@@ -73,7 +74,7 @@ impl<Snapshot: ReadableSnapshot + 'static> FetchStageExecutor<Snapshot> {
         context: ExecutionContext<Snapshot>,
         interrupt: ExecutionInterrupt,
     ) -> (impl Iterator<Item = Result<ConceptDocument, Box<PipelineExecutionError>>>, ExecutionContext<Snapshot>) {
-        let ExecutionContext { snapshot, thing_manager, parameters, profile } = context.clone();
+        let ExecutionContext { snapshot, thing_manager, parameters, profile, query_time, .. } = context.clone();
         let executable = self.executable;
         let functions = self.functions;
         let stage_profile = profile.profile_stage(|| String::from("Fetch"), executable.executable_id);
@@ -88,6 +89,7 @@ impl<Snapshot: ReadableSnapshot + 'static> FetchStageExecutor<Snapshot> {
                     parameters.clone(),
                     functions.clone(),
                     profile.clone(),
+                    query_time,
                     &step,
                     row.as_reference(),
                     interrupt.clone(),
@@ -107,6 +109,7 @@ fn execute_fetch(
     parameters: Arc<ParameterRegistry>,
     functions: Arc<ExecutableFunctionRegistry>,
     query_profile: Arc<QueryProfile>,
+    query_time: DateTime<Utc>,
     step: &StepProfile,
     row: MaybeOwnedRow<'_>,
     interrupt: ExecutionInterrupt,
@@ -119,6 +122,7 @@ fn execute_fetch(
         parameters,
         functions,
         query_profile,
+        query_time,
         row,
         interrupt,
     )?;
@@ -133,6 +137,7 @@ fn execute_fetch_some(
     parameters: Arc<ParameterRegistry>,
     functions_registry: Arc<ExecutableFunctionRegistry>,
     query_profile: Arc<QueryProfile>,
+    query_time: DateTime<Utc>,
     row: MaybeOwnedRow<'_>,
     interrupt: ExecutionInterrupt,
 ) -> Result<DocumentNode, FetchExecutionError> {
@@ -147,6 +152,7 @@ fn execute_fetch_some(
             parameters,
             functions_registry,
             query_profile,
+            query_time,
             row,
             interrupt,
             variable_positions,
@@ -159,6 +165,7 @@ fn execute_fetch_some(
             parameters,
             functions_registry,
             query_profile.clone(),
+            query_time,
             row,
             interrupt,
         ),
@@ -168,6 +175,7 @@ fn execute_fetch_some(
             parameters,
             functions_registry,
             query_profile,
+            query_time,
             row,
             interrupt,
             variable_positions,
@@ -179,6 +187,7 @@ fn execute_fetch_some(
             parameters,
             functions_registry,
             query_profile,
+            query_time,
             row,
             interrupt,
             subfetch,
@@ -221,6 +230,7 @@ fn execute_single_function(
     parameters: Arc<ParameterRegistry>,
     functions_registry: Arc<ExecutableFunctionRegistry>,
     query_profile: Arc<QueryProfile>,
+    query_time: DateTime<Utc>,
     row: MaybeOwnedRow<'_>,
     mut interrupt: ExecutionInterrupt,
     variable_positions: &HashMap<Variable, VariablePosition>,
@@ -232,6 +242,7 @@ fn execute_single_function(
         parameters,
         functions_registry.clone(),
         query_profile,
+        query_time,
         variable_positions,
         row,
         function,
@@ -280,6 +291,7 @@ fn execute_object(
     parameters: Arc<ParameterRegistry>,
     functions: Arc<ExecutableFunctionRegistry>,
     query_profile: Arc<QueryProfile>,
+    query_time: DateTime<Utc>,
     row: MaybeOwnedRow<'_>,
     interrupt: ExecutionInterrupt,
 ) -> Result<DocumentNode, FetchExecutionError> {
@@ -292,6 +304,7 @@ fn execute_object(
                 parameters,
                 functions,
                 query_profile,
+                query_time,
                 row,
                 interrupt,
             )?;
@@ -309,6 +322,7 @@ fn execute_list_function(
     parameters: Arc<ParameterRegistry>,
     functions_registry: Arc<ExecutableFunctionRegistry>,
     query_profile: Arc<QueryProfile>,
+    query_time: DateTime<Utc>,
     row: MaybeOwnedRow<'_>,
     mut interrupt: ExecutionInterrupt,
     variable_positions: &HashMap<Variable, VariablePosition>,
@@ -320,6 +334,7 @@ fn execute_list_function(
         parameters,
         functions_registry.clone(),
         query_profile,
+        query_time,
         variable_positions,
         row,
         function,
@@ -348,6 +363,7 @@ fn execute_list_subfetch(
     parameters: Arc<ParameterRegistry>,
     functions_registry: Arc<ExecutableFunctionRegistry>,
     query_profile: Arc<QueryProfile>,
+    query_time: DateTime<Utc>,
     row: MaybeOwnedRow<'_>,
     interrupt: ExecutionInterrupt,
     executable_subfetch: &ExecutableFetchListSubFetch,
@@ -388,7 +404,8 @@ fn execute_list_subfetch(
             query_profile,
         )
     }
-    .map_err(|typedb_source| FetchExecutionError::Pipeline { typedb_source })?;
+    .map_err(|typedb_source| FetchExecutionError::Pipeline { typedb_source })?
+    .with_query_time(query_time);
 
     let (iterator, _context) = pipeline
         .into_documents_iterator(interrupt)
@@ -554,6 +571,7 @@ fn prepare_single_function_execution<Snapshot: ReadableSnapshot + 'static>(
     parameters: Arc<ParameterRegistry>,
     functions_registry: Arc<ExecutableFunctionRegistry>,
     query_profile: Arc<QueryProfile>,
+    query_time: DateTime<Utc>,
     variable_positions: &HashMap<Variable, VariablePosition>,
     row: MaybeOwnedRow<'_>,
     function: &ExecutableFunction,
@@ -570,7 +588,8 @@ fn prepare_single_function_execution<Snapshot: ReadableSnapshot + 'static>(
             .map_err(|err| FetchExecutionError::ConceptRead { typedb_source: err })?;
     let mut pattern_executor = PatternExecutor::new(next_executable_id(), step_executors);
     pattern_executor.prepare(FixedBatch::from(args));
-    Ok((pattern_executor, Arc::new(ExecutionContext::new(snapshot, thing_manager, parameters))))
+    let context = ExecutionContext::new(snapshot, thing_manager, parameters).with_query_time(query_time);
+    Ok((pattern_executor, Arc::new(context)))
 }
 
 fn execute_object_entries(
@@ -580,6 +599,7 @@ fn execute_object_entries(
     parameters: Arc<ParameterRegistry>,
     functions: Arc<ExecutableFunctionRegistry>,
     query_profile: Arc<QueryProfile>,
+    query_time: DateTime<Utc>,
     row: MaybeOwnedRow<'_>,
     interrupt: ExecutionInterrupt,
 ) -> Result<DocumentMap, FetchExecutionError> {
@@ -594,6 +614,7 @@ fn execute_object_entries(
                 parameters.clone(),
                 functions.clone(),
                 query_profile.clone(),
+                query_time,
                 row.as_reference(),
                 interrupt.clone(),
             )?,
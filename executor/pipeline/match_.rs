@@ -17,6 +17,7 @@ use crate::{
     ExecutionInterrupt,
     error::ReadExecutionError,
     match_executor::{MatchExecutor, PatternIterator},
+    partitioned_match_executor::{MatchPartitioner, PartitionedRows, ThreadedMatchPartitioner},
     pipeline::{
         PipelineExecutionError, StageIterator,
         stage::{ExecutionContext, StageAPI},
@@ -27,12 +28,19 @@ use crate::{
 pub struct MatchStageExecutor<InputIterator> {
     executable: Arc<ConjunctionExecutable>,
     function_registry: Arc<ExecutableFunctionRegistry>,
+    is_partitioned: bool,
     _input_iterator: PhantomData<InputIterator>,
 }
 
 impl<InputIterator> MatchStageExecutor<InputIterator> {
     pub fn new(executable: Arc<ConjunctionExecutable>, function_registry: Arc<ExecutableFunctionRegistry>) -> Self {
-        Self { executable, function_registry, _input_iterator: PhantomData }
+        Self { executable, function_registry, is_partitioned: false, _input_iterator: PhantomData }
+    }
+
+    /// Lets the stage run across threads when its context provides a partitioner.
+    /// Only valid when the order of the stage's output rows is not observable.
+    pub(crate) fn set_partitioned(&mut self) {
+        self.is_partitioned = true;
     }
 }
 
@@ -53,9 +61,20 @@ where
         (Self::OutputIterator, ExecutionContext<Snapshot>),
         (Box<PipelineExecutionError>, ExecutionContext<Snapshot>),
     > {
-        let Self { executable, function_registry, .. } = self;
+        let Self { executable, function_registry, is_partitioned, .. } = self;
+        let partitioner = match is_partitioned && ThreadedMatchPartitioner::is_partitionable(&executable) {
+            true => context.partitioner.clone(),
+            false => None,
+        };
         Ok((
-            MatchStageIterator::new(input_iterator, executable, function_registry, context.clone(), interrupt),
+            MatchStageIterator::new(
+                input_iterator,
+                executable,
+                function_registry,
+                partitioner,
+                context.clone(),
+                interrupt,
+            ),
             context,
         ))
     }
@@ -65,8 +84,9 @@ pub struct MatchStageIterator<Snapshot: ReadableSnapshot + 'static, InputIterato
     context: ExecutionContext<Snapshot>,
     executable: Arc<ConjunctionExecutable>,
     function_registry: Arc<ExecutableFunctionRegistry>,
+    partitioner: Option<Arc<dyn MatchPartitioner<Snapshot>>>,
     source_iterator: InputIterator,
-    current_iterator: Option<Peekable<UniqueRows<AsOwnedRows<PatternIterator<Snapshot>>>>>,
+    current_iterator: Option<Peekable<UniqueRows<MatchRows<Snapshot>>>>,
     interrupt: ExecutionInterrupt,
 }
 
@@ -75,10 +95,19 @@ impl<Snapshot: ReadableSnapshot + 'static, InputIterator> MatchStageIterator<Sna
         iterator: InputIterator,
         executable: Arc<ConjunctionExecutable>,
         function_registry: Arc<ExecutableFunctionRegistry>,
+        partitioner: Option<Arc<dyn MatchPartitioner<Snapshot>>>,
         context: ExecutionContext<Snapshot>,
         interrupt: ExecutionInterrupt,
     ) -> Self {
-        Self { context, executable, function_registry, source_iterator: iterator, current_iterator: None, interrupt }
+        Self {
+            context,
+            executable,
+            function_registry,
+            partitioner,
+            source_iterator: iterator,
+            current_iterator: None,
+            interrupt,
+        }
     }
 }
 
@@ -98,6 +127,18 @@ where
                 Err(err) => return Some(Err(err)),
             };
 
+            if let Some(partitioner) = &self.partitioner {
+                let rows = partitioner.execute(
+                    &self.context,
+                    self.executable.clone(),
+                    self.function_registry.clone(),
+                    input_row.into_owned(),
+                    &self.interrupt,
+                );
+                self.current_iterator = Some(unique_rows(MatchRows::Partitioned(rows)).peekable());
+                continue;
+            }
+
             let executor = MatchExecutor::new(
                 &self.executable,
                 snapshot,
@@ -111,9 +152,9 @@ where
             match executor {
                 Ok(executor) => {
                     self.current_iterator = Some(
-                        unique_rows(as_owned_rows(
+                        unique_rows(MatchRows::Sequential(as_owned_rows(
                             executor.into_iterator(self.context.clone(), self.interrupt.clone()),
-                        ))
+                        )))
                         .peekable(),
                     );
                 }
@@ -133,6 +174,22 @@ where
 {
 }
 
+enum MatchRows<Snapshot: ReadableSnapshot + 'static> {
+    Sequential(AsOwnedRows<PatternIterator<Snapshot>>),
+    Partitioned(PartitionedRows<Snapshot>),
+}
+
+impl<Snapshot: ReadableSnapshot + 'static> Iterator for MatchRows<Snapshot> {
+    type Item = Result<MaybeOwnedRow<'static>, ReadExecutionError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            MatchRows::Sequential(rows) => rows.next(),
+            MatchRows::Partitioned(rows) => rows.next(),
+        }
+    }
+}
+
 type AsOwnedRows<I> = IntoIter<
     Map<
         I,
@@ -14,7 +14,7 @@ use compiler::{
 };
 use ir::pipeline::modifier::SortVariable;
 use lending_iterator::{LendingIterator, Peekable};
-use resource::profile::StorageCounters;
+use resource::{constants::traversal::BATCH_DEFAULT_CAPACITY, profile::StorageCounters};
 use storage::snapshot::ReadableSnapshot;
 
 use crate::{
@@ -58,7 +58,7 @@ where
     > {
         let Self { executable, .. } = self;
         // accumulate once, then we will operate in-place
-        let batch = match input_iterator.collect_owned() {
+        let batch = match collect_within_limit(input_iterator, &context) {
             Ok(batch) => batch,
             Err(err) => return Err((err, context)),
         };
@@ -74,6 +74,27 @@ where
     }
 }
 
+// Collects the input of a sort, failing as soon as it materialises more rows than the query's limit allows
+fn collect_within_limit(
+    mut iterator: impl StageIterator,
+    context: &ExecutionContext<impl ReadableSnapshot>,
+) -> Result<Batch, Box<PipelineExecutionError>> {
+    if context.limits.collected_rows_limit.is_none() {
+        return iterator.collect_owned();
+    }
+    let mut collected: Option<Batch> = None;
+    while let Some(row) = iterator.next() {
+        let row = row?;
+        let batch = collected.get_or_insert_with(|| Batch::new(row.len() as u32, BATCH_DEFAULT_CAPACITY));
+        batch.append_row(row);
+        context
+            .limits
+            .check_collected_rows(batch.len())
+            .map_err(|typedb_source| Box::new(PipelineExecutionError::ReadPatternExecution { typedb_source }))?;
+    }
+    Ok(collected.unwrap_or_else(|| Batch::new(0, 0)))
+}
+
 pub struct SortStageIterator {
     unsorted: Batch,
     sorted_indices: Vec<usize>,
@@ -7,6 +7,7 @@
 use std::{collections::HashMap, sync::Arc};
 
 use answer::variable::Variable;
+use chrono::{DateTime, Utc};
 use compiler::{
     VariablePosition,
     executable::{fetch::executable::ExecutableFetch, function::ExecutableFunctionRegistry, pipeline::ExecutableStage},
@@ -21,6 +22,7 @@ use storage::snapshot::{ReadableSnapshot, WritableSnapshot};
 use crate::{
     ExecutionInterrupt,
     document::ConceptDocument,
+    partitioned_match_executor::ThreadedMatchPartitioner,
     pipeline::{
         PipelineExecutionError,
         delete::DeleteStageExecutor,
@@ -35,7 +37,8 @@ use crate::{
         put::PutStageExecutor,
         reduce::ReduceStageExecutor,
         stage::{
-            ExecutionContext, ReadPipelineStage, ReadStageIterator, StageAPI, WritePipelineStage, WriteStageIterator,
+            ExecutionContext, ExecutionLimits, ReadPipelineStage, ReadStageIterator, StageAPI, WritePipelineStage,
+            WriteStageIterator,
         },
         update::UpdateStageExecutor,
     },
@@ -87,6 +90,20 @@ where
     pub fn pipeline_structure(&self) -> Option<&PipelineStructure> {
         self.pipeline_structure.as_ref()
     }
+
+    pub fn query_profile(&self) -> &Arc<QueryProfile> {
+        &self.context.profile
+    }
+
+    pub fn with_limits(mut self, limits: ExecutionLimits) -> Self {
+        self.context.limits = limits;
+        self
+    }
+
+    pub fn with_query_time(mut self, query_time: DateTime<Utc>) -> Self {
+        self.context.query_time = query_time;
+        self
+    }
 }
 
 impl<Snapshot: ReadableSnapshot + 'static> Pipeline<Snapshot, ReadPipelineStage<Snapshot>> {
@@ -172,6 +189,33 @@ impl<Snapshot: ReadableSnapshot + 'static> Pipeline<Snapshot, ReadPipelineStage<
         ))
     }
 
+    /// Partitions the execution of the first match stage across the given number of jobs on the shared worker threads.
+    /// It is only partitioned when its rows are collected by a later sort or reduce, since the order in which the jobs
+    /// produce rows is not deterministic. Later match stages run once per input row, so they are never partitioned.
+    pub fn with_parallelism(mut self, workers: usize) -> Self
+    where
+        Snapshot: Send + Sync,
+    {
+        if workers <= 1 {
+            return self;
+        }
+        let mut is_collected_later = false;
+        for (index, stage) in self.stages.iter_mut().enumerate().rev() {
+            match stage {
+                ReadPipelineStage::Sort(_) | ReadPipelineStage::Reduce(_) => is_collected_later = true,
+                ReadPipelineStage::Offset(_) | ReadPipelineStage::Limit(_) => is_collected_later = false,
+                ReadPipelineStage::Match(match_stage) => {
+                    if index == 0 && is_collected_later {
+                        match_stage.set_partitioned();
+                    }
+                }
+                ReadPipelineStage::Select(_) | ReadPipelineStage::Distinct(_) | ReadPipelineStage::Require(_) => (),
+            }
+        }
+        self.context.partitioner = Some(Arc::new(ThreadedMatchPartitioner::new(workers)));
+        self
+    }
+
     pub fn into_rows_iterator(
         self,
         execution_interrupt: ExecutionInterrupt,
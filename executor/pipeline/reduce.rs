@@ -77,7 +77,9 @@ fn reduce_iterator<Snapshot: ReadableSnapshot>(
     let mut grouped_reducer = GroupedReducer::new(executable.reduce_rows_executable.clone());
     let mut input_row_count: u64 = 0;
     while let Some(result) = iterator.next() {
-        grouped_reducer.accept(&result?, context, storage_counters)?;
+        grouped_reducer
+            .accept(&result?, context, storage_counters)
+            .map_err(|typedb_source| Box::new(PipelineExecutionError::ReadPatternExecution { typedb_source }))?;
         input_row_count += 1;
     }
     Ok((grouped_reducer.finalise(), input_row_count))
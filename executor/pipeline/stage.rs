@@ -5,6 +5,7 @@
  */
 use std::sync::Arc;
 
+use chrono::{DateTime, Utc};
 use concept::{thing::thing_manager::ThingManager, type_::type_manager::TypeManager};
 use ir::pipeline::ParameterRegistry;
 use lending_iterator::LendingIterator;
@@ -14,6 +15,8 @@ use storage::snapshot::{ReadableSnapshot, WritableSnapshot};
 use crate::{
     ExecutionInterrupt,
     batch::Batch,
+    error::ReadExecutionError,
+    partitioned_match_executor::MatchPartitioner,
     pipeline::{
         PipelineExecutionError, WrittenRowsIterator,
         delete::DeleteStageExecutor,
@@ -38,6 +41,34 @@ pub struct ExecutionContext<Snapshot> {
     pub thing_manager: Arc<ThingManager>,
     pub parameters: Arc<ParameterRegistry>,
     pub profile: Arc<QueryProfile>,
+    pub limits: ExecutionLimits,
+    pub partitioner: Option<Arc<dyn MatchPartitioner<Snapshot>>>,
+    // Captured once, so that every row of a query sees the same `now()`
+    pub query_time: DateTime<Utc>,
+}
+
+/// Bounds on the rows a query may hold in memory while executing. Unset limits are unbounded.
+/// A sort stage collects each of its input rows, while a reduce stage collects one row per group.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct ExecutionLimits {
+    pub collected_rows_limit: Option<usize>,
+    pub tabled_rows_limit: Option<usize>,
+}
+
+impl ExecutionLimits {
+    pub(crate) fn check_collected_rows(&self, rows: usize) -> Result<(), ReadExecutionError> {
+        match self.collected_rows_limit {
+            Some(limit) if rows > limit => Err(ReadExecutionError::CollectedRowsLimitExceeded { limit }),
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn check_tabled_rows(&self, rows: usize) -> Result<(), ReadExecutionError> {
+        match self.tabled_rows_limit {
+            Some(limit) if rows > limit => Err(ReadExecutionError::TabledRowsLimitExceeded { limit }),
+            _ => Ok(()),
+        }
+    }
 }
 
 impl<Snapshot> ExecutionContext<Snapshot> {
@@ -51,7 +82,23 @@ impl<Snapshot> ExecutionContext<Snapshot> {
         parameters: Arc<ParameterRegistry>,
         query_profile: Arc<QueryProfile>,
     ) -> Self {
-        Self { snapshot, thing_manager, parameters, profile: query_profile }
+        Self {
+            snapshot,
+            thing_manager,
+            parameters,
+            profile: query_profile,
+            limits: ExecutionLimits::default(),
+            partitioner: None,
+            query_time: Utc::now(),
+        }
+    }
+
+    pub fn with_limits(self, limits: ExecutionLimits) -> Self {
+        Self { limits, ..self }
+    }
+
+    pub fn with_query_time(self, query_time: DateTime<Utc>) -> Self {
+        Self { query_time, ..self }
     }
 
     pub(crate) fn clone_with_replaced_parameters(&self, parameters: Arc<ParameterRegistry>) -> Self {
@@ -60,6 +107,9 @@ impl<Snapshot> ExecutionContext<Snapshot> {
             thing_manager: self.thing_manager.clone(),
             parameters,
             profile: self.profile.clone(),
+            limits: self.limits,
+            partitioner: self.partitioner.clone(),
+            query_time: self.query_time,
         }
     }
 
@@ -82,12 +132,15 @@ impl<Snapshot> ExecutionContext<Snapshot> {
 
 impl<Snapshot> Clone for ExecutionContext<Snapshot> {
     fn clone(&self) -> Self {
-        let Self { snapshot, thing_manager, parameters, profile } = self;
+        let Self { snapshot, thing_manager, parameters, profile, limits, partitioner, query_time } = self;
         Self {
             snapshot: snapshot.clone(),
             thing_manager: thing_manager.clone(),
             parameters: parameters.clone(),
             profile: profile.clone(),
+            limits: *limits,
+            partitioner: partitioner.clone(),
+            query_time: *query_time,
         }
     }
 }
@@ -117,7 +117,7 @@ impl CollectorEnum {
         context: &ExecutionContext<impl ReadableSnapshot>,
         batch: FixedBatch,
         storage_counters: &StorageCounters,
-    ) {
+    ) -> Result<(), ReadExecutionError> {
         match self {
             CollectorEnum::Reduce(collector) => collector.accept(context, batch, storage_counters),
             CollectorEnum::Sort(collector) => collector.accept(context, batch, storage_counters),
@@ -165,7 +165,7 @@ pub(super) trait CollectorTrait {
         context: &ExecutionContext<impl ReadableSnapshot>,
         batch: FixedBatch,
         storage_counters: &StorageCounters,
-    );
+    ) -> Result<(), ReadExecutionError>;
 
     fn into_iterator(
         self,
@@ -203,10 +203,11 @@ impl CollectorTrait for ReduceCollector {
         context: &ExecutionContext<impl ReadableSnapshot>,
         batch: FixedBatch,
         storage_counters: &StorageCounters,
-    ) {
+    ) -> Result<(), ReadExecutionError> {
         for row in batch {
-            self.active_reducer.accept(&row, context, storage_counters).unwrap(); // TODO: potentially unsafe unwrap
+            self.active_reducer.accept(&row, context, storage_counters)?;
         }
+        Ok(())
     }
 
     fn into_iterator(
@@ -268,13 +269,15 @@ impl SortCollector {
 impl CollectorTrait for SortCollector {
     fn accept(
         &mut self,
-        _context: &ExecutionContext<impl ReadableSnapshot>,
+        context: &ExecutionContext<impl ReadableSnapshot>,
         batch: FixedBatch,
         _storage_counters: &StorageCounters,
-    ) {
+    ) -> Result<(), ReadExecutionError> {
         for row in batch {
             self.collector.append_row(row);
+            context.limits.check_collected_rows(self.collector.len())?;
         }
+        Ok(())
     }
 
     fn into_iterator(
@@ -4,18 +4,21 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use std::{collections::HashMap, hash::Hash, sync::Arc};
+use std::{borrow::Cow, collections::HashMap, hash::Hash, sync::Arc};
 
 use answer::{Thing, variable_value::VariableValue};
+use chrono::{DateTime, Offset, Utc};
 use compiler::annotation::expression::{
     compiled_expression::ExecutableExpression,
     instructions::{
         ExpressionEvaluationError,
         binary::{
-            Binary, BinaryExpression, MathMaxDecimalDecimal, MathMaxDoubleDouble, MathMaxIntegerInteger,
-            MathMinDecimalDecimal, MathMinDoubleDouble, MathMinIntegerInteger, MathRemainderInteger,
+            Binary, BinaryExpression, ConcatString, EndsWithString, MathMaxDecimalDecimal, MathMaxDoubleDouble,
+            MathMaxIntegerInteger, MathMinDecimalDecimal, MathMinDoubleDouble, MathMinIntegerInteger,
+            MathRemainderInteger, StartsWithString,
         },
-        list_operations::{ListConstructor, ListIndex, ListIndexRange},
+        datetime,
+        list_operations::{ListConstructor, ListIndex, ListIndexRange, SplitString},
         load_cast::{
             CastBinaryLeft, CastBinaryRight, CastLeftDecimalToDouble, CastLeftIntegerToDecimal,
             CastLeftIntegerToDouble, CastRightDecimalToDouble, CastRightIntegerToDecimal, CastRightIntegerToDouble,
@@ -24,13 +27,18 @@ use compiler::annotation::expression::{
         },
         op_codes::ExpressionOpCode,
         operators,
+        ternary::{RegexReplaceString, SubstringString, Ternary, TernaryExpression},
         unary::{
-            LenString, MathAbsDecimal, MathAbsDouble, MathAbsInteger, MathCeilDecimal, MathCeilDouble,
-            MathFloorDecimal, MathFloorDouble, MathRoundDecimal, MathRoundDouble, Unary, UnaryExpression,
+            LenString, LowerString, MathAbsDecimal, MathAbsDouble, MathAbsInteger, MathCeilDecimal, MathCeilDouble,
+            MathFloorDecimal, MathFloorDouble, MathRoundDecimal, MathRoundDouble, TrimString, Unary, UnaryExpression,
+            UpperString,
         },
     },
 };
-use encoding::value::value::{NativeValueConvertible, Value};
+use encoding::value::{
+    timezone::TimeZone,
+    value::{NativeValueConvertible, Value},
+};
 use ir::{pattern::ParameterID, pipeline::ParameterRegistry};
 use resource::profile::StorageCounters;
 use storage::snapshot::ReadableSnapshot;
@@ -95,6 +103,7 @@ pub struct ExpressionExecutorState<'this> {
     constants: &'this [ParameterID],
     next_constant_index: usize,
     parameter_registry: &'this ParameterRegistry,
+    query_time: DateTime<Utc>,
 }
 
 impl<'this> ExpressionExecutorState<'this> {
@@ -102,6 +111,7 @@ impl<'this> ExpressionExecutorState<'this> {
         variables: Box<[ExpressionValue]>,
         constants: &'this [ParameterID],
         parameter_registry: &'this ParameterRegistry,
+        query_time: DateTime<Utc>,
     ) -> Self {
         Self {
             stack: Vec::new(),
@@ -110,6 +120,7 @@ impl<'this> ExpressionExecutorState<'this> {
             constants,
             next_constant_index: 0,
             parameter_registry,
+            query_time,
         }
     }
 
@@ -152,13 +163,15 @@ pub fn evaluate_expression<ID: Hash + Eq>(
     compiled: &ExecutableExpression<ID>,
     input: HashMap<ID, ExpressionValue>,
     parameters: &ParameterRegistry,
+    query_time: DateTime<Utc>,
 ) -> Result<ExpressionValue, ExpressionEvaluationError> {
     let mut variables = Vec::new();
     for v in compiled.variables() {
         variables.push(input.get(v).unwrap().clone());
     }
 
-    let mut state = ExpressionExecutorState::new(variables.into_boxed_slice(), compiled.constants(), parameters);
+    let mut state =
+        ExpressionExecutorState::new(variables.into_boxed_slice(), compiled.constants(), parameters, query_time);
     for instr in compiled.instructions() {
         evaluate_instruction(instr, &mut state)?;
     }
@@ -245,6 +258,48 @@ fn evaluate_instruction(
         ExpressionOpCode::MathMaxDecimalDecimal => MathMaxDecimalDecimal::evaluate(state),
 
         ExpressionOpCode::LenString => LenString::evaluate(state),
+
+        ExpressionOpCode::ConcatString => ConcatString::evaluate(state),
+        ExpressionOpCode::SubstringString => SubstringString::evaluate(state),
+        ExpressionOpCode::UpperString => UpperString::evaluate(state),
+        ExpressionOpCode::LowerString => LowerString::evaluate(state),
+        ExpressionOpCode::TrimString => TrimString::evaluate(state),
+        ExpressionOpCode::StartsWithString => StartsWithString::evaluate(state),
+        ExpressionOpCode::EndsWithString => EndsWithString::evaluate(state),
+        ExpressionOpCode::RegexReplaceString => RegexReplaceString::evaluate(state),
+        ExpressionOpCode::SplitString => SplitString::evaluate(state),
+
+        ExpressionOpCode::YearDate => datetime::YearDate::evaluate(state),
+        ExpressionOpCode::YearDateTime => datetime::YearDateTime::evaluate(state),
+        ExpressionOpCode::YearDateTimeTZ => datetime::YearDateTimeTZ::evaluate(state),
+
+        ExpressionOpCode::MonthDate => datetime::MonthDate::evaluate(state),
+        ExpressionOpCode::MonthDateTime => datetime::MonthDateTime::evaluate(state),
+        ExpressionOpCode::MonthDateTimeTZ => datetime::MonthDateTimeTZ::evaluate(state),
+
+        ExpressionOpCode::DayDate => datetime::DayDate::evaluate(state),
+        ExpressionOpCode::DayDateTime => datetime::DayDateTime::evaluate(state),
+        ExpressionOpCode::DayDateTimeTZ => datetime::DayDateTimeTZ::evaluate(state),
+
+        ExpressionOpCode::HourDateTime => datetime::HourDateTime::evaluate(state),
+        ExpressionOpCode::HourDateTimeTZ => datetime::HourDateTimeTZ::evaluate(state),
+
+        ExpressionOpCode::MinuteDateTime => datetime::MinuteDateTime::evaluate(state),
+        ExpressionOpCode::MinuteDateTimeTZ => datetime::MinuteDateTimeTZ::evaluate(state),
+
+        ExpressionOpCode::SecondDateTime => datetime::SecondDateTime::evaluate(state),
+        ExpressionOpCode::SecondDateTimeTZ => datetime::SecondDateTimeTZ::evaluate(state),
+
+        ExpressionOpCode::TruncateDate => datetime::TruncateDate::evaluate(state),
+        ExpressionOpCode::TruncateDateTime => datetime::TruncateDateTime::evaluate(state),
+        ExpressionOpCode::TruncateDateTimeTZ => datetime::TruncateDateTimeTZ::evaluate(state),
+
+        ExpressionOpCode::NowDateTimeTZ => datetime::NowDateTimeTZ::evaluate(state),
+
+        ExpressionOpCode::DurationBetweenDateTime => datetime::DurationBetweenDateTime::evaluate(state),
+        ExpressionOpCode::DurationBetweenDateTimeTZ => datetime::DurationBetweenDateTimeTZ::evaluate(state),
+
+        ExpressionOpCode::InTimezoneDateTimeTZ => datetime::InTimezoneDateTimeTZ::evaluate(state),
     }
 }
 
@@ -267,6 +322,23 @@ where
     }
 }
 
+impl<'a, T1, T2, T3, R, F> ExpressionEvaluation for Ternary<'a, T1, T2, T3, R, F>
+where
+    T1: NativeValueConvertible<'a>,
+    T2: NativeValueConvertible<'a>,
+    T3: NativeValueConvertible<'a>,
+    R: NativeValueConvertible<'a>,
+    F: TernaryExpression<'a, T1, T2, T3, R>,
+{
+    fn evaluate(state: &mut ExpressionExecutorState<'_>) -> Result<(), ExpressionEvaluationError> {
+        let a3: T3 = T3::from_db_value(state.pop_value()).unwrap();
+        let a2: T2 = T2::from_db_value(state.pop_value()).unwrap();
+        let a1: T1 = T1::from_db_value(state.pop_value()).unwrap();
+        state.push_value(F::evaluate(a1, a2, a3)?.to_db_value());
+        Ok(())
+    }
+}
+
 impl ExpressionEvaluation for ListConstructor {
     fn evaluate(state: &mut ExpressionExecutorState<'_>) -> Result<(), ExpressionEvaluationError> {
         let n_elements = state.pop_value().unwrap_integer() as usize;
@@ -311,6 +383,29 @@ impl ExpressionEvaluation for ListIndexRange {
         }
     }
 }
+
+impl ExpressionEvaluation for SplitString {
+    fn evaluate(state: &mut ExpressionExecutorState<'_>) -> Result<(), ExpressionEvaluationError> {
+        let separator = state.pop_value().unwrap_string();
+        let string = state.pop_value().unwrap_string();
+        let parts: Arc<[Value<'static>]> = if separator.is_empty() {
+            // an empty separator splits the string into its characters
+            string.chars().map(|char| Value::String(Cow::Owned(char.to_string()))).collect()
+        } else {
+            string.split(separator.as_ref()).map(|part| Value::String(Cow::Owned(part.to_owned()))).collect()
+        };
+        state.push_list(parts);
+        Ok(())
+    }
+}
+
+impl ExpressionEvaluation for datetime::NowDateTimeTZ {
+    fn evaluate(state: &mut ExpressionExecutorState<'_>) -> Result<(), ExpressionEvaluationError> {
+        state.push_value(Value::DateTimeTZ(state.query_time.with_timezone(&TimeZone::Fixed(Utc.fix()))));
+        Ok(())
+    }
+}
+
 impl ExpressionEvaluation for LoadVariable {
     fn evaluate(state: &mut ExpressionExecutorState<'_>) -> Result<(), ExpressionEvaluationError> {
         match state.next_variable() {
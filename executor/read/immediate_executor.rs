@@ -841,8 +841,9 @@ impl AssignExecutor {
                     Ok((pos, expression_value))
                 })
                 .try_collect()?;
-            let output_value = evaluate_expression(&self.expression, input_variables, &context.parameters)
-                .map_err(|typedb_source| ReadExecutionError::ExpressionEvaluate { typedb_source })?;
+            let output_value =
+                evaluate_expression(&self.expression, input_variables, &context.parameters, context.query_time)
+                    .map_err(|typedb_source| ReadExecutionError::ExpressionEvaluate { typedb_source })?;
             output.append(|mut row| {
                 row.set_multiplicity(input_row.multiplicity());
                 row.set_provenance(input_row.provenance());
@@ -50,6 +50,14 @@ impl PatternExecutor {
         self.control_stack.is_empty()
     }
 
+    /// Splits the executor into one for its first step and one for the steps after it, so the first step's output
+    /// batches can be partitioned across several executors of the remaining steps.
+    pub(crate) fn split_first_step(mut self) -> (PatternExecutor, PatternExecutor) {
+        debug_assert!(self.control_stack.is_empty() && self.executors.len() > 1);
+        let remaining_steps = self.executors.split_off(1);
+        (Self::new(self.executable_id, self.executors), Self::new(self.executable_id, remaining_steps))
+    }
+
     pub(crate) fn compute_next_batch(
         &mut self,
         context: &ExecutionContext<impl ReadableSnapshot + 'static>,
@@ -220,7 +228,7 @@ impl PatternExecutor {
                     let inner = collecting_stage.pattern_mut();
                     let mut batches = 0;
                     while let Some(batch) = inner.compute_next_batch(context, interrupt, tabled_functions)? {
-                        collector.accept(context, batch, &storage_counters);
+                        collector.accept(context, batch, &storage_counters)?;
                         batches += 1;
                     }
                     let iterator = collector.into_iterator(context, step_profile.storage_counters());
@@ -351,12 +359,7 @@ impl PatternExecutor {
                     suspensions: function_suspensions,
                     parameters,
                 } = pattern_state_mutex_guard.deref_mut();
-                let context_with_function_parameters = ExecutionContext::new_with_profile(
-                    context.snapshot.clone(),
-                    context.thing_manager.clone(),
-                    parameters.clone(),
-                    context.profile.clone(),
-                );
+                let context_with_function_parameters = context.clone_with_replaced_parameters(parameters.clone());
                 let batch_opt = pattern_executor.batch_continue(
                     &context_with_function_parameters,
                     interrupt,
@@ -365,6 +368,9 @@ impl PatternExecutor {
                 )?;
                 if let Some(batch) = batch_opt {
                     let deduplicated_batch = executor.add_batch_to_table(&function_state, batch);
+                    if context.limits.tabled_rows_limit.is_some() {
+                        context.limits.check_tabled_rows(tabled_functions.total_table_size())?;
+                    }
                     Some(deduplicated_batch)
                 } else {
                     // Don't use suspend_count_before == suspend_count_after, since we can get away with just one.
@@ -4,7 +4,7 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use std::{borrow::Cow, collections::HashMap, sync::Arc};
+use std::{borrow::Cow, collections::HashMap, iter, sync::Arc};
 
 use answer::{Thing, variable_value::VariableValue};
 use chrono::{DateTime, NaiveDate, NaiveDateTime};
@@ -18,10 +18,7 @@ use resource::profile::StorageCounters;
 use storage::snapshot::ReadableSnapshot;
 
 use crate::{
-    Provenance,
-    batch::Batch,
-    pipeline::{PipelineExecutionError, stage::ExecutionContext},
-    row::MaybeOwnedRow,
+    Provenance, batch::Batch, error::ReadExecutionError, pipeline::stage::ExecutionContext, row::MaybeOwnedRow,
 };
 
 #[derive(Debug)]
@@ -55,13 +52,15 @@ impl GroupedReducer {
         row: &MaybeOwnedRow<'_>,
         context: &ExecutionContext<Snapshot>,
         storage_counters: &StorageCounters,
-    ) -> Result<(), Box<PipelineExecutionError>> {
+    ) -> Result<(), ReadExecutionError> {
         self.reused_group.clear();
         for &pos in &self.rows_executable.input_group_positions {
             self.reused_group.push(row.get(pos).to_owned());
         }
         if !self.grouped_reductions.contains_key(&self.reused_group) {
             self.grouped_reductions.insert(self.reused_group.clone(), self.uninitialised_reducer_executors.clone());
+            // a reduction holds one row per group, however many input rows it reduces
+            context.limits.check_collected_rows(self.grouped_reductions.len())?;
         }
         let reducers = self.grouped_reductions.get_mut(&self.reused_group).unwrap();
         for reducer in reducers {
@@ -179,6 +178,8 @@ reducer_executor! {
     MaxDate, MinDate,
     MaxDateTime, MinDateTime,
     MaxDateTimeTZ, MinDateTimeTZ,
+    ListInteger, ListDouble, ListDecimal, ListBoolean, ListString,
+    ListDate, ListDateTime, ListDateTimeTZ, ListDuration,
 }
 
 #[derive(Debug, Clone)]
@@ -776,3 +777,46 @@ impl ReducerAPI for StdDecimalExecutor {
         }
     }
 }
+
+// Lists hold values of any type, so every list reducer shares one executor
+macro_rules! list_reducer_executors {
+    ($($ty:ident),* $(,)?) => {$(
+        paste! {
+            type [< List $ty Executor >] = ListExecutor;
+        }
+    )*};
+}
+
+list_reducer_executors! {
+    Integer, Double, Decimal, Boolean, String, Date, DateTime, DateTimeTZ, Duration,
+}
+
+#[derive(Debug, Clone)]
+struct ListExecutor {
+    values: Vec<Value<'static>>,
+    target: VariablePosition,
+}
+
+impl ListExecutor {
+    fn new(target: VariablePosition) -> Self {
+        Self { values: Vec::new(), target }
+    }
+}
+
+impl ReducerAPI for ListExecutor {
+    fn accept<Snapshot: ReadableSnapshot>(
+        &mut self,
+        row: &MaybeOwnedRow<'_>,
+        context: &ExecutionContext<Snapshot>,
+        storage_counters: StorageCounters,
+    ) {
+        if let Some(value) = extract_value(row, self.target, context, storage_counters) {
+            let multiplicity = row.multiplicity() as usize;
+            self.values.extend(iter::repeat_n(value, multiplicity));
+        }
+    }
+
+    fn finalise(self) -> Option<VariableValue<'static>> {
+        Some(VariableValue::ValueList(Arc::from(self.values)))
+    }
+}
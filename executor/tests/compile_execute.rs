@@ -146,6 +146,7 @@ fn test_has_planning_traversal() {
         &translation_context.variable_registry,
         &HashMap::new(),
         &statistics,
+        &value_parameters,
         &ExecutableFunctionRegistry::empty(),
     )
     .unwrap();
@@ -245,6 +246,7 @@ fn test_expression_planning_traversal() {
         &translation_context.variable_registry,
         &compiled_expressions,
         &statistics,
+        &value_parameters,
         &ExecutableFunctionRegistry::empty(),
     )
     .unwrap();
@@ -332,6 +334,7 @@ fn test_links_planning_traversal() {
         &translation_context.variable_registry,
         &HashMap::new(),
         &statistics,
+        &value_parameters,
         &ExecutableFunctionRegistry::empty(),
     )
     .unwrap();
@@ -426,6 +429,7 @@ fn test_links_intersection() {
         &translation_context.variable_registry,
         &HashMap::new(),
         &statistics,
+        &value_parameters,
         &ExecutableFunctionRegistry::empty(),
     )
     .unwrap();
@@ -511,6 +515,7 @@ fn test_negation_planning_traversal() {
         &translation_context.variable_registry,
         &HashMap::new(),
         &statistics,
+        &value_parameters,
         &ExecutableFunctionRegistry::empty(),
     )
     .unwrap();
@@ -617,6 +622,7 @@ fn test_forall_planning_traversal() {
         &translation_context.variable_registry,
         &HashMap::new(),
         &statistics,
+        &value_parameters,
         &ExecutableFunctionRegistry::empty(),
     )
     .unwrap();
@@ -710,6 +716,7 @@ fn test_named_var_select() {
         &translation_context.variable_registry,
         &HashMap::new(),
         &statistics,
+        &value_parameters,
         &ExecutableFunctionRegistry::empty(),
     )
     .unwrap();
@@ -802,6 +809,7 @@ fn test_disjunction_planning_traversal() {
         &translation_context.variable_registry,
         &HashMap::new(),
         &statistics,
+        &value_parameters,
         &ExecutableFunctionRegistry::empty(),
     )
     .unwrap();
@@ -898,6 +906,7 @@ fn test_disjunction_planning_nested_negations() {
         &translation_context.variable_registry,
         &HashMap::new(),
         &statistics,
+        &value_parameters,
         &ExecutableFunctionRegistry::empty(),
     )
     .unwrap();
@@ -1059,6 +1068,7 @@ fn compile_query(
         &translation_context.variable_registry,
         &HashMap::new(),
         &statistics,
+        &value_parameters,
         &ExecutableFunctionRegistry::empty(),
     )
     .unwrap()
@@ -7,12 +7,14 @@
 use std::collections::HashMap;
 
 use answer::variable::Variable;
+use chrono::{TimeZone, Utc};
 use compiler::annotation::expression::{
     ExpressionCompileError,
     compiled_expression::{ExecutableExpression, ExpressionValueType},
     expression_compiler::ExpressionCompilationContext,
+    instructions::ExpressionEvaluationError,
 };
-use encoding::value::{value::Value, value_type::ValueTypeCategory};
+use encoding::value::{duration_value::Duration, value::Value, value_type::ValueTypeCategory};
 use executor::read::expression_executor::{ExpressionValue, evaluate_expression};
 use ir::{
     RepresentationError,
@@ -116,13 +118,13 @@ macro_rules! as_list {
 fn test_basic() {
     {
         let (_, expr, params) = compile_expression_via_match("3 - 5", HashMap::new()).unwrap();
-        let result = evaluate_expression(&expr, HashMap::new(), &params).unwrap();
+        let result = evaluate_expression(&expr, HashMap::new(), &params, Utc::now()).unwrap();
         assert_eq!(as_value!(result), Value::Integer(-2));
     }
 
     {
         let (_, expr, params) = compile_expression_via_match("7.0e0 + 9.0e0", HashMap::new()).unwrap();
-        let result = evaluate_expression(&expr, HashMap::new(), &params).unwrap();
+        let result = evaluate_expression(&expr, HashMap::new(), &params, Utc::now()).unwrap();
         assert_eq!(as_value!(result), Value::Double(16.0));
     }
 
@@ -141,7 +143,7 @@ fn test_basic() {
             (a, ExpressionValue::Single(Value::Integer(2))),
             (b, ExpressionValue::Single(Value::Integer(5))),
         ]);
-        let result = evaluate_expression(&expr, inputs, &params).unwrap();
+        let result = evaluate_expression(&expr, inputs, &params, Utc::now()).unwrap();
         assert_eq!(as_value!(result), Value::Integer(7));
     }
 }
@@ -152,36 +154,36 @@ fn test_ops_integer_double() {
     {
         {
             let (_, expr, params) = compile_expression_via_match("12 + 4", HashMap::new()).unwrap();
-            let result = evaluate_expression(&expr, HashMap::new(), &params).unwrap();
+            let result = evaluate_expression(&expr, HashMap::new(), &params, Utc::now()).unwrap();
             assert_eq!(as_value!(result), Value::Integer(16));
         }
         {
             let (_, expr, params) = compile_expression_via_match("12 - 4", HashMap::new()).unwrap();
-            let result = evaluate_expression(&expr, HashMap::new(), &params).unwrap();
+            let result = evaluate_expression(&expr, HashMap::new(), &params, Utc::now()).unwrap();
             assert_eq!(as_value!(result), Value::Integer(8));
         }
 
         {
             let (_, expr, params) = compile_expression_via_match("12 * 4", HashMap::new()).unwrap();
-            let result = evaluate_expression(&expr, HashMap::new(), &params).unwrap();
+            let result = evaluate_expression(&expr, HashMap::new(), &params, Utc::now()).unwrap();
             assert_eq!(as_value!(result), Value::Integer(48));
         }
 
         {
             let (_, expr, params) = compile_expression_via_match("12 / 4", HashMap::new()).unwrap();
-            let result = evaluate_expression(&expr, HashMap::new(), &params).unwrap();
+            let result = evaluate_expression(&expr, HashMap::new(), &params, Utc::now()).unwrap();
             assert_eq!(as_value!(result), Value::Double(3.0));
         }
 
         {
             let (_, expr, params) = compile_expression_via_match("12 % 5", HashMap::new()).unwrap();
-            let result = evaluate_expression(&expr, HashMap::new(), &params).unwrap();
+            let result = evaluate_expression(&expr, HashMap::new(), &params, Utc::now()).unwrap();
             assert_eq!(as_value!(result), Value::Integer(2));
         }
 
         {
             let (_, expr, params) = compile_expression_via_match("12 ^ 4", HashMap::new()).unwrap();
-            let result = evaluate_expression(&expr, HashMap::new(), &params).unwrap();
+            let result = evaluate_expression(&expr, HashMap::new(), &params, Utc::now()).unwrap();
             assert_eq!(as_value!(result), Value::Double(f64::powf(12.0, 4.0)));
         }
     }
@@ -190,37 +192,37 @@ fn test_ops_integer_double() {
     {
         {
             let (_, expr, params) = compile_expression_via_match("12.0e0 + 4.0e0", HashMap::new()).unwrap();
-            let result = evaluate_expression(&expr, HashMap::new(), &params).unwrap();
+            let result = evaluate_expression(&expr, HashMap::new(), &params, Utc::now()).unwrap();
             assert_eq!(as_value!(result), Value::Double(16.0));
         }
 
         {
             let (_, expr, params) = compile_expression_via_match("12.0e0 - 4.0e0", HashMap::new()).unwrap();
-            let result = evaluate_expression(&expr, HashMap::new(), &params).unwrap();
+            let result = evaluate_expression(&expr, HashMap::new(), &params, Utc::now()).unwrap();
             assert_eq!(as_value!(result), Value::Double(8.0));
         }
 
         {
             let (_, expr, params) = compile_expression_via_match("12.0e0 * 4.0e0", HashMap::new()).unwrap();
-            let result = evaluate_expression(&expr, HashMap::new(), &params).unwrap();
+            let result = evaluate_expression(&expr, HashMap::new(), &params, Utc::now()).unwrap();
             assert_eq!(as_value!(result), Value::Double(48.0));
         }
 
         {
             let (_, expr, params) = compile_expression_via_match("12.0e0 / 4.0e0", HashMap::new()).unwrap();
-            let result = evaluate_expression(&expr, HashMap::new(), &params).unwrap();
+            let result = evaluate_expression(&expr, HashMap::new(), &params, Utc::now()).unwrap();
             assert_eq!(as_value!(result), Value::Double(3.0));
         }
 
         {
             let (_, expr, params) = compile_expression_via_match("12.0e0 % 5.0e0", HashMap::new()).unwrap();
-            let result = evaluate_expression(&expr, HashMap::new(), &params).unwrap();
+            let result = evaluate_expression(&expr, HashMap::new(), &params, Utc::now()).unwrap();
             assert_eq!(as_value!(result), Value::Double(2.0));
         }
 
         {
             let (_, expr, params) = compile_expression_via_match("12.0e0 ^ 4.0e0", HashMap::new()).unwrap();
-            let result = evaluate_expression(&expr, HashMap::new(), &params).unwrap();
+            let result = evaluate_expression(&expr, HashMap::new(), &params, Utc::now()).unwrap();
             assert_eq!(as_value!(result), Value::Double(f64::powf(12.0, 4.0)));
         }
     }
@@ -228,13 +230,13 @@ fn test_ops_integer_double() {
     // Integer-double cast ops
     {
         let (_, expr, params) = compile_expression_via_match("12.0e0 + 4", HashMap::new()).unwrap();
-        let result = evaluate_expression(&expr, HashMap::new(), &params).unwrap();
+        let result = evaluate_expression(&expr, HashMap::new(), &params, Utc::now()).unwrap();
         assert_eq!(as_value!(result), Value::Double(16.0));
     }
 
     {
         let (_, expr, params) = compile_expression_via_match("12 + 4.0e0", HashMap::new()).unwrap();
-        let result = evaluate_expression(&expr, HashMap::new(), &params).unwrap();
+        let result = evaluate_expression(&expr, HashMap::new(), &params, Utc::now()).unwrap();
         assert_eq!(as_value!(result), Value::Double(16.0));
     }
 }
@@ -243,25 +245,25 @@ fn test_ops_integer_double() {
 fn test_functions() {
     {
         let (_, expr, params) = compile_expression_via_match("floor(2.5e0)", HashMap::new()).unwrap();
-        let result = evaluate_expression(&expr, HashMap::new(), &params).unwrap();
+        let result = evaluate_expression(&expr, HashMap::new(), &params, Utc::now()).unwrap();
         assert_eq!(as_value!(result), Value::Integer(2));
     }
 
     {
         let (_, expr, params) = compile_expression_via_match("ceil(2.5e0)", HashMap::new()).unwrap();
-        let result = evaluate_expression(&expr, HashMap::new(), &params).unwrap();
+        let result = evaluate_expression(&expr, HashMap::new(), &params, Utc::now()).unwrap();
         assert_eq!(as_value!(result), Value::Integer(3));
     }
 
     {
         let (_, expr, params) = compile_expression_via_match("round(2.5e0)", HashMap::new()).unwrap();
-        let result = evaluate_expression(&expr, HashMap::new(), &params).unwrap();
+        let result = evaluate_expression(&expr, HashMap::new(), &params, Utc::now()).unwrap();
         assert_eq!(as_value!(result), Value::Integer(2));
     }
 
     {
         let (_, expr, params) = compile_expression_via_match("round(3.5e0)", HashMap::new()).unwrap();
-        let result = evaluate_expression(&expr, HashMap::new(), &params).unwrap();
+        let result = evaluate_expression(&expr, HashMap::new(), &params, Utc::now()).unwrap();
         assert_eq!(as_value!(result), Value::Integer(4));
     }
 
@@ -272,11 +274,130 @@ fn test_functions() {
     assert!(matches!(*source, RepresentationError::ExpressionBuiltinArgumentCountMismatch { .. }));
 }
 
+#[test]
+fn string_functions() {
+    let evaluate = |expression: &str| {
+        let (_, expr, params) = compile_expression_via_match(expression, HashMap::new()).unwrap();
+        evaluate_expression(&expr, HashMap::new(), &params, Utc::now())
+    };
+    let string = |string: &str| Value::String(string.to_owned().into());
+
+    assert_eq!(as_value!(evaluate(r#"concat("type", "db")"#).unwrap()), string("typedb"));
+    assert_eq!(as_value!(evaluate(r#"substring("databases", 4, 4)"#).unwrap()), string("base"));
+    assert_eq!(as_value!(evaluate(r#"substring("data", 2, 10)"#).unwrap()), string("ta"));
+    assert_eq!(as_value!(evaluate(r#"upper("Straße")"#).unwrap()), string("STRASSE"));
+    assert_eq!(as_value!(evaluate(r#"lower("TypeDB")"#).unwrap()), string("typedb"));
+    assert_eq!(as_value!(evaluate(r#"trim("  padded ")"#).unwrap()), string("padded"));
+    assert_eq!(as_value!(evaluate(r#"starts_with("typedb", "type")"#).unwrap()), Value::Boolean(true));
+    assert_eq!(as_value!(evaluate(r#"ends_with("typedb", "type")"#).unwrap()), Value::Boolean(false));
+    assert_eq!(as_value!(evaluate(r#"len(upper("abc"))"#).unwrap()), Value::Integer(3));
+    assert_eq!(
+        as_value!(evaluate(r#"regex_replace("2024-01-31", "(\\d+)-(\\d+)-(\\d+)", "$3/$2/$1")"#).unwrap()),
+        string("31/01/2024")
+    );
+    assert_eq!(as_value!(evaluate(r#"regex_replace("a-b-c", "-", "+")"#).unwrap()), string("a+b+c"));
+    assert_eq!(
+        &*as_list!(evaluate(r#"split("a,b,,c", ",")"#).unwrap()),
+        &[string("a"), string("b"), string(""), string("c")]
+    );
+
+    assert!(matches!(
+        evaluate(r#"substring("data", -1, 2)"#).unwrap_err(),
+        ExpressionEvaluationError::SubstringNegative { .. }
+    ));
+    assert!(matches!(
+        evaluate(r#"regex_replace("data", "(", "")"#).unwrap_err(),
+        ExpressionEvaluationError::InvalidRegex { .. }
+    ));
+
+    let err = compile_expression_via_match(r#"upper("a", "b")"#, HashMap::new()).unwrap_err();
+    let PatternDefitionOrExpressionCompileError::PatternDefinition { source } = err else {
+        panic!("wrong error type");
+    };
+    assert!(matches!(*source, RepresentationError::ExpressionBuiltinArgumentCountMismatch { .. }));
+
+    let err = compile_expression_via_match("upper(12)", HashMap::new()).unwrap_err();
+    let PatternDefitionOrExpressionCompileError::ExpressionCompilation { source } = err else {
+        panic!("wrong error type");
+    };
+    assert!(matches!(*source, ExpressionCompileError::UnsupportedArgumentsForBuiltin { .. }));
+}
+
+#[test]
+fn datetime_functions() {
+    let evaluate = |expression: &str| {
+        let (_, expr, params) = compile_expression_via_match(expression, HashMap::new()).unwrap();
+        evaluate_expression(&expr, HashMap::new(), &params, Utc::now())
+    };
+
+    assert_eq!(as_value!(evaluate("year(2024-03-15)").unwrap()), Value::Integer(2024));
+    assert_eq!(as_value!(evaluate("month(2024-03-15T10:42:07)").unwrap()), Value::Integer(3));
+    assert_eq!(as_value!(evaluate("day(2024-03-15T23:42:07-05:00)").unwrap()), Value::Integer(15));
+    assert_eq!(as_value!(evaluate("hour(2024-03-15T10:42:07+05:30)").unwrap()), Value::Integer(10));
+    assert_eq!(as_value!(evaluate("minute(2024-03-15T10:42:07)").unwrap()), Value::Integer(42));
+    assert_eq!(as_value!(evaluate("second(2024-03-15T10:42:07)").unwrap()), Value::Integer(7));
+
+    assert_eq!(
+        as_value!(evaluate(r#"truncate(2024-03-15T10:42:07, "month")"#).unwrap()),
+        as_value!(evaluate(r#"truncate(2024-03-01T00:00:00, "second")"#).unwrap())
+    );
+    assert_eq!(
+        as_value!(evaluate(r#"truncate(2024-03-15, "hour")"#).unwrap()),
+        as_value!(evaluate(r#"truncate(2024-03-15, "day")"#).unwrap())
+    );
+    assert_eq!(
+        as_value!(evaluate(r#"hour(truncate(2024-03-15T10:42:07+05:30, "hour"))"#).unwrap()),
+        Value::Integer(10)
+    );
+
+    assert_eq!(
+        as_value!(evaluate("duration_between(2024-03-15T10:00:00, 2024-03-16T12:30:00)").unwrap()),
+        Value::Duration(Duration::minutes(26 * 60 + 30))
+    );
+    assert!(matches!(
+        evaluate("duration_between(2024-03-16T10:00:00, 2024-03-15T10:00:00)").unwrap_err(),
+        ExpressionEvaluationError::NegativeDatetimeSub { .. }
+    ));
+
+    assert_eq!(
+        as_value!(evaluate(r#"hour(in_timezone(2024-03-15T10:00:00Z, "+05:30"))"#).unwrap()),
+        Value::Integer(15)
+    );
+    assert_eq!(
+        as_value!(evaluate(r#"hour(in_timezone(2024-07-15T10:00:00Z, "Europe/London"))"#).unwrap()),
+        Value::Integer(11)
+    );
+    assert!(matches!(as_value!(evaluate("now()").unwrap()), Value::DateTimeTZ(_)));
+    // `now()` is the time at which the query started, however late a row evaluates it
+    let (_, expr, params) = compile_expression_via_match("now()", HashMap::new()).unwrap();
+    let query_time = Utc.with_ymd_and_hms(2024, 3, 15, 10, 42, 7).unwrap();
+    let Value::DateTimeTZ(now) = as_value!(evaluate_expression(&expr, HashMap::new(), &params, query_time).unwrap())
+    else {
+        panic!("now() should be a datetime-tz");
+    };
+    assert_eq!(now.with_timezone(&Utc), query_time);
+
+    assert!(matches!(
+        evaluate(r#"truncate(2024-03-15, "fortnight")"#).unwrap_err(),
+        ExpressionEvaluationError::InvalidTimeUnit { .. }
+    ));
+    assert!(matches!(
+        evaluate(r#"in_timezone(2024-03-15T10:00:00Z, "Mars/Olympus_Mons")"#).unwrap_err(),
+        ExpressionEvaluationError::InvalidTimezone { .. }
+    ));
+
+    let err = compile_expression_via_match("hour(2024-03-15)", HashMap::new()).unwrap_err();
+    let PatternDefitionOrExpressionCompileError::ExpressionCompilation { source } = err else {
+        panic!("wrong error type");
+    };
+    assert!(matches!(*source, ExpressionCompileError::UnsupportedArgumentsForBuiltin { .. }));
+}
+
 #[test]
 fn list_ops() {
     {
         let (_, expr, params) = compile_expression_via_match("[12,34]", HashMap::new()).unwrap();
-        let result = evaluate_expression(&expr, HashMap::new(), &params).unwrap();
+        let result = evaluate_expression(&expr, HashMap::new(), &params, Utc::now()).unwrap();
         assert_eq!(&*as_list!(result), &[Value::Integer(12), Value::Integer(34)]);
     }
 
@@ -295,7 +416,7 @@ fn list_ops() {
             y,
             ExpressionValue::List([Value::Integer(56), Value::Integer(78), Value::Integer(90)].into()),
         )]);
-        let result = evaluate_expression(&expr, inputs, &params).unwrap();
+        let result = evaluate_expression(&expr, inputs, &params, Utc::now()).unwrap();
         assert_eq!(as_value!(result), Value::Integer(78));
     }
 
@@ -316,7 +437,7 @@ fn list_ops() {
                 [Value::Integer(9), Value::Integer(87), Value::Integer(65), Value::Integer(43)].into(),
             ),
         )]);
-        let result = evaluate_expression(&expr, inputs, &params).unwrap();
+        let result = evaluate_expression(&expr, inputs, &params, Utc::now()).unwrap();
         assert_eq!(&*as_list!(result), &[Value::Integer(87), Value::Integer(65)]);
     }
 }
@@ -25,7 +25,7 @@ use executor::{
         PipelineExecutionError,
         delete::DeleteStageExecutor,
         insert::InsertStageExecutor,
-        stage::{ExecutionContext, StageAPI, StageIterator},
+        stage::{ExecutionContext, ExecutionLimits, StageAPI, StageIterator},
     },
     row::MaybeOwnedRow,
     write::WriteError,
@@ -208,6 +208,8 @@ fn execute_insert<Snapshot: WritableSnapshot + 'static>(
             thing_manager,
             parameters: Arc::new(value_parameters),
             profile: Arc::new(QueryProfile::new(false)),
+            limits: ExecutionLimits::default(),
+            partitioner: None,
         },
     );
     let (input_iter, context) = initial.into_iterator();
@@ -297,6 +299,8 @@ fn execute_delete<Snapshot: WritableSnapshot + 'static>(
             thing_manager,
             parameters: Arc::new(value_parameters),
             profile: Arc::new(QueryProfile::new(false)),
+            limits: ExecutionLimits::default(),
+            partitioner: None,
         },
     );
     let (input_iter, context) = initial.into_iterator();
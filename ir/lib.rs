@@ -11,7 +11,7 @@
 use chrono::{DateTime, NaiveDateTime};
 use encoding::value::timezone::TimeZone;
 use error::typedb_error;
-use typeql::{common::Span, statement::InIterable, token, value::StringLiteral};
+use typeql::{common::Span, statement::InIterable, value::StringLiteral};
 
 use crate::{
     pattern::{expression::ExpressionRepresentationError, variable_category::VariableCategory},
@@ -120,7 +120,7 @@ typedb_error! {
         ExpressionBuiltinArgumentCountMismatch(
             15,
             "Built-in expression function '{builtin}' expects '{expected}' arguments but received '{actual}' arguments.",
-            builtin: token::Function,
+            builtin: String,
             expected: usize,
             actual: usize,
             source_span: Option<Span>,
@@ -302,6 +302,18 @@ typedb_error! {
             "Optionals are not allowed in negations as this can never return a meaningful result.",
             // source_span: Option<Span>,
         ),
+        InvalidQueryParameter(
+            54,
+            "Invalid value '{value}' with value type '{value_type}' bound to the query parameter '${variable}'.",
+            variable: String,
+            value_type: String,
+            value: String,
+        ),
+        UnusedQueryParameter(
+            55,
+            "A value is bound to the query parameter '${variable}', which the query does not use.",
+            variable: String,
+        ),
         InternalNotAValueBuiltin(
             100,
             "Attempted to translate function '{token}' as a builtin value function.",
@@ -290,6 +290,73 @@ pub enum BuiltinValueFunctionID {
     Max,
     Min,
     Len,
+    Concat,
+    Substring,
+    Upper,
+    Lower,
+    Trim,
+    StartsWith,
+    EndsWith,
+    RegexReplace,
+    Split,
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    Truncate,
+    Now,
+    DurationBetween,
+    InTimezone,
+}
+
+impl BuiltinValueFunctionID {
+    /// Resolves the built-in functions that are called by name, rather than by a TypeQL keyword.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            Self::CONCAT => Some(Self::Concat),
+            Self::SUBSTRING => Some(Self::Substring),
+            Self::UPPER => Some(Self::Upper),
+            Self::LOWER => Some(Self::Lower),
+            Self::TRIM => Some(Self::Trim),
+            Self::STARTS_WITH => Some(Self::StartsWith),
+            Self::ENDS_WITH => Some(Self::EndsWith),
+            Self::REGEX_REPLACE => Some(Self::RegexReplace),
+            Self::SPLIT => Some(Self::Split),
+            Self::YEAR => Some(Self::Year),
+            Self::MONTH => Some(Self::Month),
+            Self::DAY => Some(Self::Day),
+            Self::HOUR => Some(Self::Hour),
+            Self::MINUTE => Some(Self::Minute),
+            Self::SECOND => Some(Self::Second),
+            Self::TRUNCATE => Some(Self::Truncate),
+            Self::NOW => Some(Self::Now),
+            Self::DURATION_BETWEEN => Some(Self::DurationBetween),
+            Self::IN_TIMEZONE => Some(Self::InTimezone),
+            _ => None,
+        }
+    }
+
+    const CONCAT: &'static str = "concat";
+    const SUBSTRING: &'static str = "substring";
+    const UPPER: &'static str = "upper";
+    const LOWER: &'static str = "lower";
+    const TRIM: &'static str = "trim";
+    const STARTS_WITH: &'static str = "starts_with";
+    const ENDS_WITH: &'static str = "ends_with";
+    const REGEX_REPLACE: &'static str = "regex_replace";
+    const SPLIT: &'static str = "split";
+    const YEAR: &'static str = "year";
+    const MONTH: &'static str = "month";
+    const DAY: &'static str = "day";
+    const HOUR: &'static str = "hour";
+    const MINUTE: &'static str = "minute";
+    const SECOND: &'static str = "second";
+    const TRUNCATE: &'static str = "truncate";
+    const NOW: &'static str = "now";
+    const DURATION_BETWEEN: &'static str = "duration_between";
+    const IN_TIMEZONE: &'static str = "in_timezone";
 }
 
 impl StructuralEquality for BuiltinValueFunctionID {
@@ -312,6 +379,25 @@ impl fmt::Display for BuiltinValueFunctionID {
             BuiltinValueFunctionID::Max => fmt::Display::fmt(&typeql::token::Function::Max, f),
             BuiltinValueFunctionID::Min => fmt::Display::fmt(&typeql::token::Function::Min, f),
             BuiltinValueFunctionID::Len => fmt::Display::fmt(&typeql::token::Function::Len, f),
+            BuiltinValueFunctionID::Concat => f.write_str(Self::CONCAT),
+            BuiltinValueFunctionID::Substring => f.write_str(Self::SUBSTRING),
+            BuiltinValueFunctionID::Upper => f.write_str(Self::UPPER),
+            BuiltinValueFunctionID::Lower => f.write_str(Self::LOWER),
+            BuiltinValueFunctionID::Trim => f.write_str(Self::TRIM),
+            BuiltinValueFunctionID::StartsWith => f.write_str(Self::STARTS_WITH),
+            BuiltinValueFunctionID::EndsWith => f.write_str(Self::ENDS_WITH),
+            BuiltinValueFunctionID::RegexReplace => f.write_str(Self::REGEX_REPLACE),
+            BuiltinValueFunctionID::Split => f.write_str(Self::SPLIT),
+            BuiltinValueFunctionID::Year => f.write_str(Self::YEAR),
+            BuiltinValueFunctionID::Month => f.write_str(Self::MONTH),
+            BuiltinValueFunctionID::Day => f.write_str(Self::DAY),
+            BuiltinValueFunctionID::Hour => f.write_str(Self::HOUR),
+            BuiltinValueFunctionID::Minute => f.write_str(Self::MINUTE),
+            BuiltinValueFunctionID::Second => f.write_str(Self::SECOND),
+            BuiltinValueFunctionID::Truncate => f.write_str(Self::TRUNCATE),
+            BuiltinValueFunctionID::Now => f.write_str(Self::NOW),
+            BuiltinValueFunctionID::DurationBetween => f.write_str(Self::DURATION_BETWEEN),
+            BuiltinValueFunctionID::InTimezone => f.write_str(Self::IN_TIMEZONE),
         }
     }
 }
@@ -4,7 +4,11 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use std::{collections::HashMap, fmt, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    sync::Arc,
+};
 
 use answer::variable::Variable;
 use bytes::byte_array::ByteArray;
@@ -420,3 +424,30 @@ impl ParameterRegistry {
         self.fetch_key_registry.get(id)
     }
 }
+
+/// Values bound by a client to named variables of a query, rather than written into the query as literals.
+/// Each value is given as the name of its value type and its text, and is parsed when the query is translated.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct QueryParameters {
+    parameters: BTreeMap<String, (String, String)>,
+}
+
+impl QueryParameters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn bind(&mut self, variable: String, value_type: String, value: String) {
+        self.parameters.insert(variable, (value_type, value));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parameters.is_empty()
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, &str, &str)> {
+        self.parameters
+            .iter()
+            .map(|(variable, (value_type, value))| (variable.as_str(), value_type.as_str(), value.as_str()))
+    }
+}
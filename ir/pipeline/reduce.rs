@@ -92,6 +92,7 @@ pub enum Reducer {
     Median(Variable),
     Min(Variable),
     Std(Variable),
+    List(Variable),
     // First, Any etc.
 }
 
@@ -106,6 +107,7 @@ impl Reducer {
             Self::Median(_) => typeql::token::ReduceOperatorStat::Median.as_str(),
             Self::Min(_) => typeql::token::ReduceOperatorStat::Min.as_str(),
             Self::Std(_) => typeql::token::ReduceOperatorStat::Std.as_str(),
+            Self::List(_) => typeql::token::ReduceOperatorCollect::List.as_str(),
         }
     }
 
@@ -118,7 +120,8 @@ impl Reducer {
             | Self::Mean(var)
             | Self::Median(var)
             | Self::Min(var)
-            | Self::Std(var) => Some(*var),
+            | Self::Std(var)
+            | Self::List(var) => Some(*var),
         }
     }
 }
@@ -5,10 +5,10 @@
  */
 
 use ir::{
-    pipeline::function_signature::HashMapFunctionSignatureIndex,
+    pipeline::{QueryParameters, function_signature::HashMapFunctionSignatureIndex},
     translation::{
         function::translate_typeql_function,
-        pipeline::{TranslatedPipeline, translate_pipeline},
+        pipeline::{TranslatedPipeline, translate_pipeline, translate_pipeline_with_parameters},
     },
 };
 use structural_equality::{StructuralEquality, is_structurally_equivalent};
@@ -287,3 +287,27 @@ fn test_anonymous_non_equivalence() {
 
     assert!(!is_structurally_equivalent(&translated_stages, &different_translated_stages));
 }
+
+#[test]
+fn test_bound_parameter_equivalence() {
+    let query = "match $p isa person, has name $p_name, has age $a; $a > $p_min_age;";
+    let translate_with = |name: &str, min_age_type: &str, min_age: &str| {
+        let mut parameters = QueryParameters::new();
+        parameters.bind("p_name".to_owned(), "string".to_owned(), name.to_owned());
+        parameters.bind("p_min_age".to_owned(), min_age_type.to_owned(), min_age.to_owned());
+        let TranslatedPipeline { translated_stages, .. } = translate_pipeline_with_parameters(
+            &HashMapFunctionSignatureIndex::empty(),
+            &typeql::parse_query(query).unwrap().into_structure().into_pipeline(),
+            &parameters,
+        )
+        .unwrap();
+        translated_stages
+    };
+
+    let translated_stages = translate_with("Alice", "integer", "10");
+    let different_values_stages = translate_with("Bob", "integer", "20");
+    assert!(is_structurally_equivalent(&translated_stages, &different_values_stages));
+
+    let different_value_type_stages = translate_with("Alice", "double", "10.5");
+    assert!(!is_structurally_equivalent(&translated_stages, &different_value_type_stages));
+}
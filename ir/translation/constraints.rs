@@ -31,7 +31,10 @@ use crate::{
     },
     pipeline::function_signature::FunctionSignatureIndex,
     translation::{
-        expression::{add_typeql_expression, add_user_defined_function_call, build_expression},
+        expression::{
+            add_typeql_expression, add_user_defined_function_call, build_expression,
+            resolve_named_builtin_value_function,
+        },
         literal::translate_literal,
         tokens::{checked_identifier, translate_value_type},
     },
@@ -67,25 +70,30 @@ pub(super) fn add_statement(
         }
         typeql::Statement::Assignment(Assignment { lhs, rhs, span }) => {
             let assigned = assignment_pattern_to_variables(constraints, lhs)?;
-            if let typeql::Expression::Function(FunctionCall { name: FunctionName::Identifier(id), args, span }) = rhs {
-                add_user_defined_function_call(
-                    function_index,
-                    constraints,
-                    id.as_str_unchecked(),
-                    assigned,
-                    args,
-                    *span,
-                )?;
-            } else {
-                let [assigned] = *assigned else {
-                    return Err(Box::new(RepresentationError::ExpressionAssignmentMustOneVariable {
-                        assigned_count: assigned.len(),
-                        source_span: *span,
-                    }));
-                };
-                let expression = build_expression(function_index, constraints, rhs)?;
-                debug_assert!(assigned.optionality == VariableOptionality::Required);
-                constraints.add_assignment(assigned.variable, expression, *span)?;
+            match rhs {
+                typeql::Expression::Function(FunctionCall { name: FunctionName::Identifier(id), args, span })
+                    if resolve_named_builtin_value_function(function_index, id.as_str_unchecked())?.is_none() =>
+                {
+                    add_user_defined_function_call(
+                        function_index,
+                        constraints,
+                        id.as_str_unchecked(),
+                        assigned,
+                        args,
+                        *span,
+                    )?;
+                }
+                _ => {
+                    let [assigned] = *assigned else {
+                        return Err(Box::new(RepresentationError::ExpressionAssignmentMustOneVariable {
+                            assigned_count: assigned.len(),
+                            source_span: *span,
+                        }));
+                    };
+                    let expression = build_expression(function_index, constraints, rhs)?;
+                    debug_assert!(assigned.optionality == VariableOptionality::Required);
+                    constraints.add_assignment(assigned.variable, expression, *span)?;
+                }
             }
         }
         typeql::Statement::Thing(thing) => add_thing_statement(function_index, constraints, thing)?,
@@ -4,6 +4,8 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+use std::fmt;
+
 use answer::variable::Variable;
 use encoding::value::value::Value;
 use typeql::{
@@ -195,11 +197,30 @@ fn build_function(
             Ok(Expression::Variable(assign))
         }
         FunctionName::Identifier(identifier) => {
+            let function_name = checked_identifier(identifier)?;
+            if let Some(function_id) = resolve_named_builtin_value_function(function_index, function_name)? {
+                let args = function_call
+                    .args
+                    .iter()
+                    .map(|expr| build_recursive(function_index, constraints, expr, tree))
+                    .collect::<Result<Vec<_>, _>>()?;
+                check_builtin_arg_count(
+                    function_id,
+                    args.len(),
+                    named_builtin_value_function_arg_count(function_id),
+                    identifier.span(),
+                )?;
+                return Ok(Expression::BuiltinValueFunctionCall(BuiltinValueFunctionCall::new(
+                    function_id,
+                    args,
+                    identifier.span(),
+                )));
+            }
             let assign = constraints.create_anonymous_variable(identifier.span())?;
             add_user_defined_function_call(
                 function_index,
                 constraints,
-                checked_identifier(identifier)?,
+                function_name,
                 vec![AssignedVariable::new_required(assign)],
                 &function_call.args,
                 function_call.span(),
@@ -221,7 +242,7 @@ fn translate_operator(operator: &ArithmeticOperator) -> Operator {
 }
 
 fn check_builtin_arg_count(
-    builtin: Function,
+    builtin: impl fmt::Display,
     actual: usize,
     expected: usize,
     source_span: Option<Span>,
@@ -230,7 +251,7 @@ fn check_builtin_arg_count(
         Ok(())
     } else {
         Err(Box::new(RepresentationError::ExpressionBuiltinArgumentCountMismatch {
-            builtin,
+            builtin: builtin.to_string(),
             expected,
             actual,
             source_span,
@@ -289,6 +310,51 @@ fn to_builtin_value_function_id(
     }
 }
 
+/// Built-in value functions that are not TypeQL keywords are called by name, so a user-defined function with the same
+/// name takes precedence over them.
+pub(crate) fn resolve_named_builtin_value_function(
+    function_index: &impl FunctionSignatureIndex,
+    function_name: &str,
+) -> Result<Option<BuiltinValueFunctionID>, Box<RepresentationError>> {
+    let Some(function_id) = BuiltinValueFunctionID::from_name(function_name) else {
+        return Ok(None);
+    };
+    let user_defined = function_index
+        .get_function_signature(function_name)
+        .map_err(|typedb_source| RepresentationError::FunctionReadError { typedb_source })?;
+    Ok(user_defined.is_none().then_some(function_id))
+}
+
+fn named_builtin_value_function_arg_count(function_id: BuiltinValueFunctionID) -> usize {
+    match function_id {
+        BuiltinValueFunctionID::Now => 0,
+        BuiltinValueFunctionID::Upper
+        | BuiltinValueFunctionID::Lower
+        | BuiltinValueFunctionID::Trim
+        | BuiltinValueFunctionID::Year
+        | BuiltinValueFunctionID::Month
+        | BuiltinValueFunctionID::Day
+        | BuiltinValueFunctionID::Hour
+        | BuiltinValueFunctionID::Minute
+        | BuiltinValueFunctionID::Second => 1,
+        BuiltinValueFunctionID::Concat
+        | BuiltinValueFunctionID::StartsWith
+        | BuiltinValueFunctionID::EndsWith
+        | BuiltinValueFunctionID::Split
+        | BuiltinValueFunctionID::Truncate
+        | BuiltinValueFunctionID::DurationBetween
+        | BuiltinValueFunctionID::InTimezone => 2,
+        BuiltinValueFunctionID::Substring | BuiltinValueFunctionID::RegexReplace => 3,
+        BuiltinValueFunctionID::Abs
+        | BuiltinValueFunctionID::Ceil
+        | BuiltinValueFunctionID::Floor
+        | BuiltinValueFunctionID::Round
+        | BuiltinValueFunctionID::Max
+        | BuiltinValueFunctionID::Min
+        | BuiltinValueFunctionID::Len => unreachable!("Built-in function '{function_id}' is called by its keyword"),
+    }
+}
+
 fn to_builtin_concept_function_id<T>(
     typeql_id: &BuiltinFunctionName,
     args: &[T],
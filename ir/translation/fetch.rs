@@ -41,7 +41,7 @@ use crate::{
     },
     translation::{
         PipelineTranslationContext,
-        expression::{add_user_defined_function_call, build_expression},
+        expression::{add_user_defined_function_call, build_expression, resolve_named_builtin_value_function},
         fetch::FetchRepresentationError::{
             AnonymousVariableEncountered, InvalidAttributeLabelEncountered, NamedVariableEncountered,
             VariableNotAvailable,
@@ -224,13 +224,19 @@ fn translate_fetch_single(
                 FunctionName::Identifier(name) => {
                     let checked_name = checked_identifier(name)
                         .map_err(|typedb_source| FetchRepresentationError::SubFetchRepresentation { typedb_source })?;
-                    translate_inline_user_function_call_single(
-                        parent_context,
-                        value_parameters,
-                        function_index,
-                        call,
-                        checked_name,
-                    )
+                    let named_builtin = resolve_named_builtin_value_function(function_index, checked_name)
+                        .map_err(|typedb_source| FetchRepresentationError::SubFetchRepresentation { typedb_source })?;
+                    if named_builtin.is_some() {
+                        translate_inline_expression_single(parent_context, value_parameters, function_index, expression)
+                    } else {
+                        translate_inline_user_function_call_single(
+                            parent_context,
+                            value_parameters,
+                            function_index,
+                            call,
+                            checked_name,
+                        )
+                    }
                 }
             },
             Expression::List(_) => {
@@ -16,6 +16,7 @@ use typeql::{
 };
 
 use crate::{
+    RepresentationError,
     pattern::{
         Pattern,
         variable_category::{VariableCategory, VariableOptionality},
@@ -24,6 +25,7 @@ use crate::{
         FunctionRepresentationError, ParameterRegistry,
         function::{Function, FunctionBody, ReturnOperation},
         function_signature::{FunctionID, FunctionSignature, FunctionSignatureIndex},
+        reduce::Reducer,
     },
     translation::{
         PipelineTranslationContext,
@@ -279,6 +281,15 @@ fn build_return_reduce(
             for typeql_reducer in typeql_reducers {
                 let reducer = build_reducer(context, typeql_reducer)
                     .map_err(|typedb_source| FunctionRepresentationError::ReturnReduction { typedb_source })?;
+                if let Reducer::List(_) = reducer {
+                    // Function signatures cannot yet declare list return types
+                    needs_update_when_feature_is_implemented!(Lists);
+                    return Err(FunctionRepresentationError::ReturnReduction {
+                        typedb_source: Box::new(RepresentationError::UnimplementedLanguageFeature {
+                            feature: error::UnimplementedFeature::Lists,
+                        }),
+                    });
+                }
                 reducers.push(reducer);
             }
             Ok(ReturnOperation::ReduceReducer(reducers, reduction.span()))
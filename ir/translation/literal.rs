@@ -31,6 +31,33 @@ pub(crate) fn translate_literal(literal: &Literal) -> Result<Value<'static>, Lit
     Value::from_typeql_literal(&literal.inner, literal.span())
 }
 
+/// Parses the text of a value bound to a query parameter, which is written as the value is displayed in answers,
+/// except that strings are taken verbatim.
+pub(crate) fn parse_parameter_value(value_type: &str, value: &str) -> Option<Value<'static>> {
+    match value_type {
+        "boolean" => value.parse().ok().map(Value::Boolean),
+        "integer" => value.parse().ok().map(Value::Integer),
+        "double" => value.parse().ok().map(Value::Double),
+        "decimal" => value.parse().ok().map(Value::Decimal),
+        "date" => value.parse().ok().map(Value::Date),
+        "datetime" => value.parse().ok().map(Value::DateTime),
+        "datetime-tz" => parse_datetime_tz(value).map(Value::DateTimeTZ),
+        "duration" => value.parse().ok().map(Value::Duration),
+        "string" => Some(Value::String(Cow::Owned(value.to_owned()))),
+        _ => None,
+    }
+}
+
+// A timezone name is separated from the datetime by a space, while a fixed offset directly follows it
+fn parse_datetime_tz(value: &str) -> Option<chrono::DateTime<TimeZone>> {
+    let (date_time, timezone) = match value.split_once(' ') {
+        Some(split) => split,
+        None => value.split_at(value.rfind(['+', '-', 'Z'])?),
+    };
+    let timezone = TimeZone::from_str(timezone).ok()?;
+    NaiveDateTime::from_str(date_time).ok()?.and_local_timezone(timezone).single()
+}
+
 pub trait FromTypeQLLiteral: Sized {
     type TypeQLLiteral;
     fn from_typeql_literal(literal: &Self::TypeQLLiteral, source_span: Option<Span>)
@@ -5,6 +5,7 @@
  */
 
 use std::{
+    collections::HashSet,
     hash::{DefaultHasher, Hasher},
     iter::empty,
     mem,
@@ -20,9 +21,12 @@ use typeql::{
 
 use crate::{
     RepresentationError,
-    pattern::Pattern,
+    pattern::{
+        Pattern,
+        expression::{Expression, ExpressionTree},
+    },
     pipeline::{
-        ParameterRegistry, VariableRegistry,
+        ParameterRegistry, QueryParameters, VariableRegistry,
         block::{Block, BlockBuilder},
         fetch::FetchObject,
         function::Function,
@@ -34,6 +38,7 @@ use crate::{
         PipelineTranslationContext,
         fetch::translate_fetch,
         function::translate_typeql_function,
+        literal::parse_parameter_value,
         match_::translate_match,
         modifiers::{
             translate_distinct, translate_limit, translate_offset, translate_require, translate_select, translate_sort,
@@ -165,6 +170,14 @@ impl StructuralEquality for TranslatedStage {
 pub fn translate_pipeline(
     all_function_signatures: &impl FunctionSignatureIndex,
     query: &typeql::query::Pipeline,
+) -> Result<TranslatedPipeline, Box<RepresentationError>> {
+    translate_pipeline_with_parameters(all_function_signatures, query, &QueryParameters::new())
+}
+
+pub fn translate_pipeline_with_parameters(
+    all_function_signatures: &impl FunctionSignatureIndex,
+    query: &typeql::query::Pipeline,
+    parameters: &QueryParameters,
 ) -> Result<TranslatedPipeline, Box<RepresentationError>> {
     // all_function_signatures contains the preambles already!
     let translated_preamble = query
@@ -176,12 +189,20 @@ pub fn translate_pipeline(
 
     let mut translation_context = PipelineTranslationContext::new();
     let mut value_parameters = ParameterRegistry::new();
-    let (translated_stages, translated_fetch) = translate_pipeline_stages(
+    let parameters_stage = match parameters.is_empty() {
+        true => None,
+        false => Some(translate_parameters(&mut translation_context, &mut value_parameters, parameters)?),
+    };
+    let (mut translated_stages, translated_fetch) = translate_pipeline_stages(
         all_function_signatures,
         &mut translation_context,
         &mut value_parameters,
         &query.stages,
     )?;
+    if let Some((parameters_stage, parameter_variables)) = parameters_stage {
+        reject_unused_parameters(&translated_stages, translated_fetch.as_ref(), parameter_variables)?;
+        translated_stages.insert(0, parameters_stage);
+    }
 
     Ok(TranslatedPipeline::new(
         translation_context,
@@ -215,6 +236,54 @@ pub(crate) fn translate_pipeline_stages(
     Ok((translated_stages, None))
 }
 
+// Bound parameters are assigned in a match stage preceding the query, so that the query uses them as inputs.
+// Their values are registered as value parameters rather than in the IR, so queries only differing in the values
+// bound share a cached plan.
+fn translate_parameters(
+    translation_context: &mut PipelineTranslationContext,
+    value_parameters: &mut ParameterRegistry,
+    parameters: &QueryParameters,
+) -> Result<(TranslatedStage, Vec<(String, Variable)>), Box<RepresentationError>> {
+    let mut variables = Vec::new();
+    let mut builder = Block::builder(translation_context.new_block_builder_context(value_parameters));
+    let mut conjunction = builder.conjunction_mut();
+    let mut constraints = conjunction.constraints_mut();
+    for (variable, value_type, value) in parameters.iter() {
+        let Some(parsed) = parse_parameter_value(value_type, value) else {
+            return Err(Box::new(RepresentationError::InvalidQueryParameter {
+                variable: variable.to_owned(),
+                value_type: value_type.to_owned(),
+                value: value.to_owned(),
+            }));
+        };
+        let assigned = constraints.get_or_declare_variable(variable, None)?;
+        variables.push((variable.to_owned(), assigned));
+        // parameters are not written in the query, so they have no position in its text
+        let parameter = constraints.parameters().register_value(parsed, Span { begin_offset: 0, end_offset: 0 });
+        let mut expression = ExpressionTree::empty();
+        expression.add(Expression::Constant(parameter));
+        constraints.add_assignment(assigned, expression, None)?;
+    }
+    Ok((TranslatedStage::Match { block: builder.finish()?, source_span: None }, variables))
+}
+
+// A parameter the query does not use would only add its value to every answer, so it is most likely misnamed
+fn reject_unused_parameters(
+    translated_stages: &[TranslatedStage],
+    translated_fetch: Option<&FetchObject>,
+    parameter_variables: Vec<(String, Variable)>,
+) -> Result<(), Box<RepresentationError>> {
+    let mut used = HashSet::new();
+    translated_stages.iter().for_each(|stage| used.extend(stage.variables()));
+    if let Some(fetch) = translated_fetch {
+        fetch.record_variables_recursive(&mut used);
+    }
+    match parameter_variables.into_iter().find(|(_, variable)| !used.contains(variable)) {
+        Some((variable, _)) => Err(Box::new(RepresentationError::UnusedQueryParameter { variable })),
+        None => Ok(()),
+    }
+}
+
 fn translate_stage(
     translation_context: &mut PipelineTranslationContext,
     value_parameters: &mut ParameterRegistry,
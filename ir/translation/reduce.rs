@@ -66,6 +66,7 @@ pub(super) fn resolve_category_optionality(reduce: &Reducer) -> (VariableCategor
         Reducer::Median(_) => (VariableCategory::Value, true),
         Reducer::Min(_) => (VariableCategory::Value, true),
         Reducer::Std(_) => (VariableCategory::Value, true),
+        Reducer::List(_) => (VariableCategory::ValueList, false),
     }
 }
 
@@ -92,10 +93,11 @@ pub(crate) fn build_reducer(
                 TypeQLReduceOperatorStat::Std => Ok(Reducer::Std(var)),
             }
         }
-        TypeQLReducer::Collect(collect) => match &collect.reduce_operator {
-            TypeQLReduceOperatorCollect::List => Err(Box::new(RepresentationError::UnimplementedLanguageFeature {
-                feature: error::UnimplementedFeature::Lists,
-            })),
-        },
+        TypeQLReducer::Collect(collect) => {
+            let var = verify_variable_available!(context, collect.variable => ReduceVariableNotAvailable)?;
+            match &collect.reduce_operator {
+                TypeQLReduceOperatorCollect::List => Ok(Reducer::List(var)),
+            }
+        }
     }
 }
@@ -0,0 +1,278 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use answer::variable::Variable;
+use compiler::{
+    ExecutorVariable,
+    executable::{
+        match_::planner::conjunction_executable::{ConjunctionExecutable, ExecutionStep},
+        pipeline::{ExecutablePipeline, ExecutableStage},
+    },
+};
+use ir::pipeline::function_signature::FunctionID;
+use itertools::chain;
+
+/// The plan chosen by the compiler for a query pipeline, built without executing it.
+/// Variables are rendered by their ids; `variable_names` resolves the named variables of the query pipeline itself.
+#[derive(Debug, Clone)]
+pub struct QueryPlan {
+    pub stages: Vec<StagePlan>,
+    pub functions: Vec<FunctionPlan>,
+    pub variable_names: HashMap<Variable, String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FunctionPlan {
+    pub function_id: FunctionID,
+    pub stages: Vec<StagePlan>,
+}
+
+#[derive(Debug, Clone)]
+pub struct StagePlan {
+    pub executable_id: u64,
+    pub kind: StageKind,
+    pub instructions: Vec<String>,
+    pub conjunction: Option<ConjunctionPlan>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum StageKind {
+    Match,
+    Insert,
+    Update,
+    Put,
+    Delete,
+    Select,
+    Sort,
+    Offset,
+    Limit,
+    Require,
+    Distinct,
+    Reduce,
+    Fetch,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConjunctionPlan {
+    pub executable_id: u64,
+    pub estimated_cost: f64,
+    pub estimated_size: f64,
+    pub steps: Vec<StepPlan>,
+}
+
+#[derive(Debug, Clone)]
+pub struct StepPlan {
+    pub kind: StepKind,
+    /// The planner's estimated cost of this step per input row, when the step has its own estimate.
+    pub estimated_cost: Option<f64>,
+    pub estimated_size: Option<f64>,
+    pub sort_variable: Option<Variable>,
+    pub instructions: Vec<String>,
+    pub branches: Vec<ConjunctionPlan>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum StepKind {
+    Intersection,
+    UnsortedJoin,
+    Assignment,
+    Check,
+    Disjunction,
+    Negation,
+    Optional,
+    FunctionCall,
+}
+
+impl QueryPlan {
+    pub(crate) fn build(executable_pipeline: &ExecutablePipeline, variable_names: &HashMap<Variable, String>) -> Self {
+        let mut called_functions = VecDeque::new();
+        let mut stages = explain_stages(&executable_pipeline.executable_stages, &mut called_functions);
+        if let Some(fetch) = &executable_pipeline.executable_fetch {
+            stages.push(StagePlan {
+                executable_id: fetch.executable_id,
+                kind: StageKind::Fetch,
+                instructions: Vec::new(),
+                conjunction: None,
+            });
+        }
+
+        let mut functions = Vec::new();
+        let mut explained_functions = HashSet::new();
+        while let Some(function_id) = called_functions.pop_front() {
+            if !explained_functions.insert(function_id.clone()) {
+                continue;
+            }
+            // builtin functions have no executable and are not explained
+            if let Some(function) = executable_pipeline.executable_functions.get(&function_id) {
+                let stages = explain_stages(&function.executable_stages, &mut called_functions);
+                functions.push(FunctionPlan { function_id, stages });
+            }
+        }
+        Self { stages, functions, variable_names: variable_names.clone() }
+    }
+}
+
+fn explain_stages(stages: &[ExecutableStage], called_functions: &mut VecDeque<FunctionID>) -> Vec<StagePlan> {
+    stages.iter().map(|stage| explain_stage(stage, called_functions)).collect()
+}
+
+fn explain_stage(stage: &ExecutableStage, called_functions: &mut VecDeque<FunctionID>) -> StagePlan {
+    let (executable_id, kind, instructions, conjunction) = match stage {
+        ExecutableStage::Match(executable) => (
+            executable.executable_id(),
+            StageKind::Match,
+            Vec::new(),
+            Some(explain_conjunction(executable, called_functions)),
+        ),
+        ExecutableStage::Insert(executable) => (
+            executable.executable_id,
+            StageKind::Insert,
+            chain(
+                executable.concept_instructions.iter().map(|instruction| instruction.to_string()),
+                executable.connection_instructions.iter().map(|instruction| instruction.to_string()),
+            )
+            .collect(),
+            None,
+        ),
+        ExecutableStage::Update(executable) => (
+            executable.executable_id,
+            StageKind::Update,
+            chain(
+                executable.concept_instructions.iter().map(|instruction| instruction.to_string()),
+                executable.connection_instructions.iter().map(|instruction| instruction.to_string()),
+            )
+            .collect(),
+            None,
+        ),
+        ExecutableStage::Put(executable) => (
+            executable.executable_id,
+            StageKind::Put,
+            chain(
+                executable.insert.concept_instructions.iter().map(|instruction| instruction.to_string()),
+                executable.insert.connection_instructions.iter().map(|instruction| instruction.to_string()),
+            )
+            .collect(),
+            Some(explain_conjunction(&executable.match_, called_functions)),
+        ),
+        ExecutableStage::Delete(executable) => (
+            executable.executable_id,
+            StageKind::Delete,
+            chain(
+                executable.concept_instructions.iter().map(|instruction| instruction.to_string()),
+                executable.connection_instructions.iter().map(|instruction| instruction.to_string()),
+            )
+            .collect(),
+            None,
+        ),
+        ExecutableStage::Select(executable) => (executable.executable_id, StageKind::Select, Vec::new(), None),
+        ExecutableStage::Sort(executable) => (
+            executable.executable_id,
+            StageKind::Sort,
+            executable.sort_on.iter().map(|sort_variable| format!("{sort_variable:?}")).collect(),
+            None,
+        ),
+        ExecutableStage::Offset(executable) => {
+            (executable.executable_id, StageKind::Offset, vec![format!("offset {}", executable.offset)], None)
+        }
+        ExecutableStage::Limit(executable) => {
+            (executable.executable_id, StageKind::Limit, vec![format!("limit {}", executable.limit)], None)
+        }
+        ExecutableStage::Require(executable) => (executable.executable_id, StageKind::Require, Vec::new(), None),
+        ExecutableStage::Distinct(executable) => (executable.executable_id, StageKind::Distinct, Vec::new(), None),
+        ExecutableStage::Reduce(executable) => (
+            executable.executable_id,
+            StageKind::Reduce,
+            executable.reduce_rows_executable.reductions.iter().map(|reduction| format!("{reduction:?}")).collect(),
+            None,
+        ),
+    };
+    StagePlan { executable_id, kind, instructions, conjunction }
+}
+
+fn explain_conjunction(
+    conjunction: &ConjunctionExecutable,
+    called_functions: &mut VecDeque<FunctionID>,
+) -> ConjunctionPlan {
+    let statistics = conjunction.planner_statistics();
+    let variable_map = conjunction.variable_reverse_map();
+    let steps = conjunction
+        .steps()
+        .iter()
+        .enumerate()
+        .map(|(index, step)| {
+            let step_plan = explain_step(step, variable_map, called_functions);
+            StepPlan {
+                estimated_cost: conjunction.step_estimated_cost(index),
+                estimated_size: conjunction.step_estimated_size(index),
+                ..step_plan
+            }
+        })
+        .collect();
+    ConjunctionPlan {
+        executable_id: conjunction.executable_id(),
+        estimated_cost: statistics.estimated_cost(),
+        estimated_size: statistics.estimated_size(),
+        steps,
+    }
+}
+
+fn explain_step(
+    step: &ExecutionStep,
+    variable_map: &HashMap<ExecutorVariable, Variable>,
+    called_functions: &mut VecDeque<FunctionID>,
+) -> StepPlan {
+    let mut sort_variable = None;
+    let mut instructions = Vec::new();
+    let mut branches = Vec::new();
+    let kind = match step {
+        ExecutionStep::Intersection(step) => {
+            sort_variable = variable_map.get(&step.sort_variable).copied();
+            instructions = step
+                .instructions
+                .iter()
+                .map(|(instruction, modes)| {
+                    format!("{} with ({})", instruction.clone().map(variable_map), modes.make_var_mapped(variable_map))
+                })
+                .collect();
+            StepKind::Intersection
+        }
+        ExecutionStep::UnsortedJoin(step) => {
+            instructions = chain([&step.iterate_instruction], &step.check_instructions)
+                .map(|instruction| instruction.clone().map(variable_map).to_string())
+                .collect();
+            StepKind::UnsortedJoin
+        }
+        ExecutionStep::Assignment(step) => {
+            instructions.push(format!("{:?}", step.expression));
+            StepKind::Assignment
+        }
+        ExecutionStep::Check(step) => {
+            instructions =
+                step.check_instructions.iter().map(|check| check.clone().map(variable_map).to_string()).collect();
+            StepKind::Check
+        }
+        ExecutionStep::Disjunction(step) => {
+            branches = step.branches.iter().map(|branch| explain_conjunction(branch, called_functions)).collect();
+            StepKind::Disjunction
+        }
+        ExecutionStep::Negation(step) => {
+            branches.push(explain_conjunction(&step.negation, called_functions));
+            StepKind::Negation
+        }
+        ExecutionStep::Optional(step) => {
+            branches.push(explain_conjunction(&step.optional, called_functions));
+            StepKind::Optional
+        }
+        ExecutionStep::FunctionCall(step) => {
+            instructions.push(step.function_id.to_string());
+            called_functions.push_back(step.function_id.clone());
+            StepKind::FunctionCall
+        }
+    };
+    StepPlan { kind, estimated_cost: None, estimated_size: None, sort_variable, instructions, branches }
+}
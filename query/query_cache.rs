@@ -90,6 +90,12 @@ impl QueryCache {
             .unwrap();
     }
 
+    pub fn cached_plan_count(&self) -> u64 {
+        // the cache only counts its entries once it has processed the pending inserts and invalidations
+        self.cache.run_pending_tasks();
+        self.cache.entry_count()
+    }
+
     pub fn force_reset(&self, statistics: &Statistics) {
         let mut write_lock = self.validity_requirements.write().unwrap();
         (*write_lock).latest_schema_commit = Some(statistics.sequence_number);
@@ -13,19 +13,22 @@ use compiler::{
     transformation::transform::apply_transformations,
 };
 use concept::{thing::thing_manager::ThingManager, type_::type_manager::TypeManager};
-use executor::pipeline::{
-    pipeline::Pipeline,
-    stage::{ReadPipelineStage, WritePipelineStage},
+use executor::{
+    partitioned_match_executor::parallel_match_workers,
+    pipeline::{
+        pipeline::Pipeline,
+        stage::{ExecutionLimits, ReadPipelineStage, WritePipelineStage},
+    },
 };
 use function::function_manager::{FunctionManager, ReadThroughFunctionSignatureIndex, validate_no_cycles};
 use ir::{
     pipeline::{
-        ParameterRegistry, VariableRegistry,
+        ParameterRegistry, QueryParameters, VariableRegistry,
         fetch::FetchObject,
         function::Function,
         function_signature::{FunctionID, HashMapFunctionSignatureIndex},
     },
-    translation::pipeline::{TranslatedPipeline, TranslatedStage},
+    translation::pipeline::{TranslatedPipeline, TranslatedStage, translate_pipeline_with_parameters},
 };
 use resource::{
     constants::query::MAX_PIPELINE_STAGES,
@@ -42,6 +45,7 @@ use crate::{
     },
     define,
     error::QueryError,
+    explain::QueryPlan,
     query_cache::QueryCache,
     redefine, undefine,
 };
@@ -49,11 +53,47 @@ use crate::{
 #[derive(Debug, Clone)]
 pub struct QueryManager {
     cache: Option<Arc<QueryCache>>,
+    is_profiled: bool,
+    is_parallel: bool,
+    limits: ExecutionLimits,
+    parameters: QueryParameters,
 }
 
 impl QueryManager {
     pub fn new(cache: Option<Arc<QueryCache>>) -> Self {
-        Self { cache }
+        Self {
+            cache,
+            is_profiled: false,
+            is_parallel: false,
+            limits: ExecutionLimits::default(),
+            parameters: QueryParameters::new(),
+        }
+    }
+
+    /// A query manager sharing this one's cache, which always profiles the pipelines it prepares.
+    pub fn profiled(&self) -> Self {
+        Self { is_profiled: true, ..self.clone() }
+    }
+
+    /// A query manager sharing this one's cache, whose prepared pipelines fail once they exceed the given limits.
+    pub fn limited(&self, limits: ExecutionLimits) -> Self {
+        Self { limits, ..self.clone() }
+    }
+
+    /// A query manager sharing this one's cache, which partitions the first match stage of the read pipelines it
+    /// prepares across the shared worker threads.
+    pub fn parallel(&self) -> Self {
+        Self { is_parallel: true, ..self.clone() }
+    }
+
+    /// A query manager sharing this one's cache, which binds the given values to the named variables of the pipelines
+    /// it prepares. Pipelines only differing in the values bound reuse the same cached plan.
+    pub fn bound(&self, parameters: QueryParameters) -> Self {
+        Self { parameters, ..self.clone() }
+    }
+
+    fn is_profiling_enabled(&self) -> bool {
+        self.is_profiled || tracing::enabled!(Level::TRACE) || QueryProfile::is_profiling_required()
     }
 
     pub fn execute_schema(
@@ -65,22 +105,38 @@ impl QueryManager {
         query: SchemaQuery,
         source_query: &str,
     ) -> Result<(), Box<QueryError>> {
+        self.execute_schema_profiled(snapshot, type_manager, thing_manager, function_manager, query, source_query).1
+    }
+
+    /// Executes a schema query, also returning the profile of its execution, which measures its time if profiling is
+    /// enabled.
+    pub fn execute_schema_profiled(
+        &self,
+        snapshot: &mut impl WritableSnapshot,
+        type_manager: &TypeManager,
+        thing_manager: &ThingManager,
+        function_manager: &FunctionManager,
+        query: SchemaQuery,
+        source_query: &str,
+    ) -> (QueryProfile, Result<(), Box<QueryError>>) {
         event!(Level::TRACE, "Running schema query:\n{}", query);
-        let query_profile = QueryProfile::new(tracing::enabled!(Level::TRACE));
+        let query_profile = QueryProfile::new(self.is_profiling_enabled());
         let result = match query {
             SchemaQuery::Define(define) => {
                 let profile = query_profile.profile_stage(|| String::from("Define"), 0); // TODO executable id
                 let pattern_profile = profile.create_or_get_pattern(|| String::from("Define pattern"));
                 let step_profile = pattern_profile.extend_or_get_step(0, || String::from("Define execution"));
-                define::execute(
+                let measurement = step_profile.start_measurement();
+                let result = define::execute(
                     snapshot,
                     type_manager,
                     thing_manager,
                     function_manager,
                     define,
                     step_profile.storage_counters(),
-                )
-                .map_err(|err| {
+                );
+                measurement.end(&step_profile, 1, 0);
+                result.map_err(|err| {
                     Box::new(QueryError::Define { source_query: source_query.to_string(), typedb_source: err })
                 })
             }
@@ -88,33 +144,41 @@ impl QueryManager {
                 let profile = query_profile.profile_stage(|| String::from("Redefine"), 0); // TODO executable id
                 let pattern_profile = profile.create_or_get_pattern(|| String::from("Redefine pattern"));
                 let step_profile = pattern_profile.extend_or_get_step(0, || String::from("Redefine execution"));
-                redefine::execute(
+                let measurement = step_profile.start_measurement();
+                let result = redefine::execute(
                     snapshot,
                     type_manager,
                     thing_manager,
                     function_manager,
                     redefine,
                     step_profile.storage_counters(),
-                )
-                .map_err(|err| {
+                );
+                measurement.end(&step_profile, 1, 0);
+                result.map_err(|err| {
                     Box::new(QueryError::Redefine { source_query: source_query.to_string(), typedb_source: err })
                 })
             }
             SchemaQuery::Undefine(undefine) => {
-                undefine::execute(snapshot, type_manager, thing_manager, function_manager, undefine).map_err(|err| {
+                let profile = query_profile.profile_stage(|| String::from("Undefine"), 0); // TODO executable id
+                let pattern_profile = profile.create_or_get_pattern(|| String::from("Undefine pattern"));
+                let step_profile = pattern_profile.extend_or_get_step(0, || String::from("Undefine execution"));
+                let measurement = step_profile.start_measurement();
+                let result = undefine::execute(snapshot, type_manager, thing_manager, function_manager, undefine);
+                measurement.end(&step_profile, 1, 0);
+                result.map_err(|err| {
                     Box::new(QueryError::Undefine { source_query: source_query.to_string(), typedb_source: err })
                 })
             }
         };
 
-        if query_profile.is_enabled() {
+        if query_profile.is_enabled() && tracing::enabled!(Level::TRACE) {
             event!(Level::INFO, "Schema query done.\n{}", query_profile);
         }
 
-        result
+        (query_profile, result)
     }
 
-    pub fn prepare_read_pipeline<Snapshot: ReadableSnapshot + 'static>(
+    pub fn prepare_read_pipeline<Snapshot: ReadableSnapshot + Send + Sync + 'static>(
         &self,
         snapshot: Arc<Snapshot>,
         type_manager: &TypeManager,
@@ -124,7 +188,7 @@ impl QueryManager {
         source_query: &str,
     ) -> Result<Pipeline<Snapshot, ReadPipelineStage<Snapshot>>, Box<QueryError>> {
         event!(Level::TRACE, "Running read query:\n{}", query);
-        let mut query_profile = QueryProfile::new(tracing::enabled!(Level::TRACE));
+        let mut query_profile = QueryProfile::new(self.is_profiling_enabled());
         let compile_profile = query_profile.compilation_profile();
         compile_profile.start();
         // 1: Translate
@@ -134,7 +198,7 @@ impl QueryManager {
             translated_fetch,
             mut variable_registry,
             value_parameters: parameters,
-        } = translate_pipeline(snapshot.as_ref(), function_manager, query, source_query)?;
+        } = translate_pipeline(snapshot.as_ref(), function_manager, &self.parameters, query, source_query)?;
         compile_profile.translation_finished();
         let arced_preamble = Arc::new(translated_preamble);
         let arced_stages = Arc::new(translated_stages);
@@ -189,6 +253,10 @@ impl QueryManager {
             None,
             Arc::new(query_profile),
         )
+        .map(|pipeline| match self.is_parallel {
+            true => pipeline.with_limits(self.limits).with_parallelism(parallel_match_workers()),
+            false => pipeline.with_limits(self.limits),
+        })
         .map_err(|typedb_source| {
             Box::new(QueryError::Pipeline { source_query: source_query.to_string(), typedb_source })
         })
@@ -204,7 +272,7 @@ impl QueryManager {
         source_query: &str,
     ) -> Result<Pipeline<Snapshot, WritePipelineStage<Snapshot>>, (Snapshot, Box<QueryError>)> {
         event!(Level::TRACE, "Running write query:\n{}", query);
-        let mut query_profile = QueryProfile::new(tracing::enabled!(Level::TRACE));
+        let mut query_profile = QueryProfile::new(self.is_profiling_enabled());
         let compile_profile = query_profile.compilation_profile();
         compile_profile.start();
         // 1: Translate
@@ -214,7 +282,7 @@ impl QueryManager {
             translated_fetch,
             mut variable_registry,
             value_parameters,
-        } = match translate_pipeline(&snapshot, function_manager, query, source_query) {
+        } = match translate_pipeline(&snapshot, function_manager, &self.parameters, query, source_query) {
             Ok(translated) => translated,
             Err(err) => return Err((snapshot, err)),
         };
@@ -283,7 +351,56 @@ impl QueryManager {
             executable_fetch,
             arced_parameters.clone(),
             Arc::new(query_profile),
-        ))
+        )
+        .with_limits(self.limits))
+    }
+
+    pub fn explain(
+        &self,
+        snapshot: &impl ReadableSnapshot,
+        type_manager: &TypeManager,
+        thing_manager: Arc<ThingManager>,
+        function_manager: &FunctionManager,
+        query: &typeql::query::Pipeline,
+        source_query: &str,
+    ) -> Result<QueryPlan, Box<QueryError>> {
+        event!(Level::TRACE, "Running explain query:\n{}", query);
+        let mut query_profile = QueryProfile::new(false);
+        let compile_profile = query_profile.compilation_profile();
+        // 1: Translate
+        let TranslatedPipeline {
+            translated_preamble,
+            translated_stages,
+            translated_fetch,
+            mut variable_registry,
+            value_parameters: parameters,
+        } = translate_pipeline(snapshot, function_manager, &self.parameters, query, source_query)?;
+        let arced_preamble = Arc::new(translated_preamble);
+        let arced_stages = Arc::new(translated_stages);
+        let arced_fetch = Arc::new(translated_fetch);
+
+        // 2 & 3: Annotate and compile, reusing a cached plan if there is one
+        let cached_pipeline = self
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.get(arced_preamble.clone(), arced_stages.clone(), arced_fetch.clone()));
+        let executable_pipeline = match cached_pipeline {
+            Some(executable_pipeline) => executable_pipeline,
+            None => annotate_and_compile_query(
+                snapshot,
+                source_query,
+                type_manager,
+                thing_manager,
+                function_manager,
+                compile_profile,
+                &mut variable_registry,
+                Arc::new(parameters),
+                arced_preamble,
+                arced_stages,
+                arced_fetch,
+            )?,
+        };
+        Ok(QueryPlan::build(&executable_pipeline, variable_registry.variable_names()))
     }
 
     pub fn analyse<Snapshot: ReadableSnapshot + 'static>(
@@ -306,7 +423,7 @@ impl QueryManager {
             translated_fetch,
             mut variable_registry,
             value_parameters: parameters,
-        } = translate_pipeline(snapshot.as_ref(), function_manager, query, source_query)?;
+        } = translate_pipeline(snapshot.as_ref(), function_manager, &self.parameters, query, source_query)?;
         compile_profile.translation_finished();
         let arced_preamble = Arc::new(translated_preamble);
         let arced_stages = Arc::new(translated_stages);
@@ -374,6 +491,7 @@ impl QueryManager {
 fn translate_pipeline<Snapshot: ReadableSnapshot>(
     snapshot: &Snapshot,
     function_manager: &FunctionManager,
+    parameters: &QueryParameters,
     query: &typeql::query::Pipeline,
     source_query: &str,
 ) -> Result<TranslatedPipeline, Box<QueryError>> {
@@ -389,7 +507,7 @@ fn translate_pipeline<Snapshot: ReadableSnapshot>(
     );
     let all_function_signatures =
         ReadThroughFunctionSignatureIndex::new(snapshot, function_manager, preamble_signatures);
-    ir::translation::pipeline::translate_pipeline(&all_function_signatures, query).map_err(|err| {
+    translate_pipeline_with_parameters(&all_function_signatures, query, parameters).map_err(|err| {
         Box::new(QueryError::Representation { source_query: source_query.to_string(), typedb_source: err })
     })
 }
@@ -472,6 +590,7 @@ fn annotate_and_compile_query(
     // 3: Compile
     let executable_pipeline = match compile_pipeline_and_functions(
         thing_manager.statistics(),
+        &arced_parameters,
         &variable_registry,
         &annotated_schema_functions,
         annotated_preamble,
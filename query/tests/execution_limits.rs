@@ -0,0 +1,149 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::time::Instant;
+
+use executor::{
+    ExecutionInterrupt, InterruptType,
+    error::ReadExecutionError,
+    pipeline::{PipelineExecutionError, stage::ExecutionLimits},
+};
+use query::query_manager::QueryManager;
+use test_utils::assert_matches;
+use test_utils_query::{QueryTestContext, ReadQueryError, Row, run_read_query, setup_query_context};
+
+const SCHEMA: &str = "define attribute age, value integer; entity person, owns age;";
+const DATA: &str = "insert $p isa person, has age 10; $q isa person, has age 20; $r isa person, has age 30;";
+
+const CHAIN_SCHEMA: &str = r#"define
+    attribute name, value string;
+    entity node, owns name, plays edge:start, plays edge:end_;
+    relation edge, relates start, relates end_;
+"#;
+const CHAIN_DATA: &str = r#"insert
+    $n0 isa node, has name "n0"; $n1 isa node, has name "n1"; $n2 isa node, has name "n2";
+    $n3 isa node, has name "n3"; $n4 isa node, has name "n4"; $n5 isa node, has name "n5";
+    (start: $n0, end_: $n1) isa edge; (start: $n1, end_: $n2) isa edge; (start: $n2, end_: $n3) isa edge;
+    (start: $n3, end_: $n4) isa edge; (start: $n4, end_: $n5) isa edge;
+"#;
+// The recursive function tables every node reachable from the start of the chain
+const REACHABLE_FROM_START: &str = r#"
+    with
+    fun reachable($start: node) -> { node }:
+    match
+        $end isa node;
+        { let $middle in reachable($start); edge (start: $middle, end_: $end); } or
+        { edge (start: $start, end_: $end); };
+    return { $end };
+
+    match
+        $start isa node, has name "n0";
+        let $to in reachable($start);
+"#;
+
+fn collected_rows_limited(context: &QueryTestContext, limit: usize) -> QueryManager {
+    context.query_manager.limited(ExecutionLimits { collected_rows_limit: Some(limit), ..ExecutionLimits::default() })
+}
+
+fn tabled_rows_limited(context: &QueryTestContext, limit: usize) -> QueryManager {
+    context.query_manager.limited(ExecutionLimits { tabled_rows_limit: Some(limit), ..ExecutionLimits::default() })
+}
+
+fn execution_error(result: Result<Vec<Row>, ReadQueryError>) -> PipelineExecutionError {
+    match result {
+        Err(ReadQueryError::Execution(err)) => *err,
+        Err(ReadQueryError::Preparation(err)) => panic!("Expected the query to fail executing, but it failed: {err:?}"),
+        Ok(rows) => panic!("Expected the query to fail executing, but it returned {} rows", rows.len()),
+    }
+}
+
+#[test]
+fn sort_within_collected_rows_limit_succeeds() {
+    let context = setup_query_context(QueryManager::new(None), SCHEMA, DATA);
+    let query_manager = collected_rows_limited(&context, 3);
+    let query = "match $p isa person, has age $a; sort $a;";
+    let rows = run_read_query(&context, &query_manager, query, ExecutionInterrupt::new_uninterruptible()).unwrap();
+    assert_eq!(rows.len(), 3);
+}
+
+#[test]
+fn sort_over_collected_rows_limit_fails() {
+    let context = setup_query_context(QueryManager::new(None), SCHEMA, DATA);
+    let query_manager = collected_rows_limited(&context, 2);
+    let query = "match $p isa person, has age $a; sort $a;";
+    let result = run_read_query(&context, &query_manager, query, ExecutionInterrupt::new_uninterruptible());
+    assert_matches!(
+        execution_error(result),
+        PipelineExecutionError::ReadPatternExecution {
+            typedb_source: ReadExecutionError::CollectedRowsLimitExceeded { limit: 2, .. },
+            ..
+        }
+    );
+}
+
+#[test]
+fn reduce_over_collected_rows_limit_fails() {
+    let context = setup_query_context(QueryManager::new(None), SCHEMA, DATA);
+    let query_manager = collected_rows_limited(&context, 2);
+    let query = "match $p isa person, has age $a; reduce $count = count groupby $a;";
+    let result = run_read_query(&context, &query_manager, query, ExecutionInterrupt::new_uninterruptible());
+    assert_matches!(
+        execution_error(result),
+        PipelineExecutionError::ReadPatternExecution {
+            typedb_source: ReadExecutionError::CollectedRowsLimitExceeded { limit: 2, .. },
+            ..
+        }
+    );
+}
+
+#[test]
+fn reduce_is_limited_by_its_groups_not_its_input_rows() {
+    let context = setup_query_context(QueryManager::new(None), SCHEMA, DATA);
+    let query_manager = collected_rows_limited(&context, 1);
+    let query = "match $p isa person, has age $a; reduce $count = count;";
+    let rows = run_read_query(&context, &query_manager, query, ExecutionInterrupt::new_uninterruptible()).unwrap();
+    assert_eq!(rows.len(), 1);
+}
+
+#[test]
+fn recursive_function_within_tabled_rows_limit_succeeds() {
+    let context = setup_query_context(QueryManager::new(None), CHAIN_SCHEMA, CHAIN_DATA);
+    let query_manager = tabled_rows_limited(&context, 100);
+    let rows =
+        run_read_query(&context, &query_manager, REACHABLE_FROM_START, ExecutionInterrupt::new_uninterruptible())
+            .unwrap();
+    assert_eq!(rows.len(), 5);
+}
+
+#[test]
+fn recursive_function_over_tabled_rows_limit_fails() {
+    let context = setup_query_context(QueryManager::new(None), CHAIN_SCHEMA, CHAIN_DATA);
+    let query_manager = tabled_rows_limited(&context, 2);
+    let result =
+        run_read_query(&context, &query_manager, REACHABLE_FROM_START, ExecutionInterrupt::new_uninterruptible());
+    assert_matches!(
+        execution_error(result),
+        PipelineExecutionError::ReadPatternExecution {
+            typedb_source: ReadExecutionError::TabledRowsLimitExceeded { limit: 2, .. },
+            ..
+        }
+    );
+}
+
+#[test]
+fn query_past_deadline_is_interrupted() {
+    let context = setup_query_context(QueryManager::new(None), SCHEMA, DATA);
+    let interrupt = ExecutionInterrupt::new_uninterruptible().with_deadline(Instant::now());
+    let query = "match $p isa person, has age $a;";
+    let result = run_read_query(&context, &context.query_manager, query, interrupt);
+    assert_matches!(
+        execution_error(result),
+        PipelineExecutionError::ReadPatternExecution {
+            typedb_source: ReadExecutionError::Interrupted { interrupt: InterruptType::QueryTimeout, .. },
+            ..
+        }
+    );
+}
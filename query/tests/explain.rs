@@ -0,0 +1,91 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use executor::ExecutionInterrupt;
+use query::{
+    explain::{QueryPlan, StageKind, StepKind},
+    query_manager::QueryManager,
+};
+use test_utils_concept::load_managers;
+use test_utils_query::{QueryTestContext, run_read_query, setup_query_context};
+
+const SCHEMA: &str = r#"
+    define
+      attribute name value string;
+      attribute age value integer;
+      entity person owns name @card(0..), owns age;
+      fun adults() -> { person }:
+        match $p isa person, has age $a; $a >= 18;
+        return { $p };
+    "#;
+const DATA: &str = r#"insert $p isa person, has name "Alice", has age 30; $q isa person, has name "Bob", has age 10;"#;
+
+fn setup() -> QueryTestContext {
+    setup_query_context(QueryManager::new(None), SCHEMA, DATA)
+}
+
+fn explain(context: &QueryTestContext, query_string: &str) -> QueryPlan {
+    let (type_manager, thing_manager) = load_managers(context.storage.clone(), None);
+    let snapshot = context.storage.clone().open_snapshot_read();
+    let query = typeql::parse_query(query_string).unwrap().into_structure().into_pipeline();
+    context
+        .query_manager
+        .explain(&snapshot, &type_manager, thing_manager, &context.function_manager, &query, query_string)
+        .unwrap()
+}
+
+fn count_people(context: &QueryTestContext) -> usize {
+    let query = "match $p isa person;";
+    run_read_query(context, &context.query_manager, query, ExecutionInterrupt::new_uninterruptible()).unwrap().len()
+}
+
+#[test]
+fn plan_describes_stages_steps_and_their_estimates() {
+    let context = setup();
+    let plan = explain(&context, r#"match $p isa person, has name $n; $n == "Alice"; limit 1;"#);
+
+    let stage_kinds = plan.stages.iter().map(|stage| stage.kind).collect::<Vec<_>>();
+    assert_eq!(stage_kinds, vec![StageKind::Match, StageKind::Limit]);
+    assert_eq!(plan.stages[1].instructions, vec!["limit 1".to_owned()]);
+    assert!(plan.functions.is_empty());
+
+    let conjunction = plan.stages[0].conjunction.as_ref().expect("A match stage should have a conjunction plan");
+    assert_eq!(conjunction.executable_id, plan.stages[0].executable_id);
+    assert!(conjunction.estimated_cost > 0.0);
+    assert!(!conjunction.steps.is_empty());
+    assert!(conjunction.steps.iter().all(|step| !step.instructions.is_empty()));
+    assert!(
+        conjunction.steps[0].estimated_cost.is_some() && conjunction.steps[0].estimated_size.is_some(),
+        "The first step should carry the planner's estimate: {conjunction:?}"
+    );
+
+    let mut variable_names = plan.variable_names.values().cloned().collect::<Vec<_>>();
+    variable_names.sort();
+    assert_eq!(variable_names, vec!["n".to_owned(), "p".to_owned()]);
+}
+
+#[test]
+fn plan_includes_called_functions() {
+    let context = setup();
+    let plan = explain(&context, "match let $p in adults(); $p has name $n;");
+
+    let conjunction = plan.stages[0].conjunction.as_ref().unwrap();
+    assert!(conjunction.steps.iter().any(|step| step.kind == StepKind::FunctionCall));
+    assert_eq!(plan.functions.len(), 1);
+    let function_stage_kinds = plan.functions[0].stages.iter().map(|stage| stage.kind).collect::<Vec<_>>();
+    assert_eq!(function_stage_kinds, vec![StageKind::Match]);
+}
+
+#[test]
+fn explaining_a_write_does_not_execute_it() {
+    let context = setup();
+    let plan = explain(&context, r#"insert $p isa person, has name "Carol";"#);
+
+    assert_eq!(plan.stages.len(), 1);
+    assert_eq!(plan.stages[0].kind, StageKind::Insert);
+    assert!(!plan.stages[0].instructions.is_empty());
+    assert_eq!(count_people(&context), 2);
+}
@@ -0,0 +1,76 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::borrow::Cow;
+
+use answer::variable_value::VariableValue;
+use compiler::annotation::AnnotationError;
+use encoding::value::value::Value;
+use executor::ExecutionInterrupt;
+use query::{error::QueryError, query_manager::QueryManager};
+use test_utils::assert_matches;
+use test_utils_query::{QueryTestContext, ReadQueryError, Row, run_read_query, setup_query_context};
+
+const SCHEMA: &str =
+    "define attribute age, value integer; attribute name, value string; entity person, owns age, owns name @card(0..);";
+const DATA: &str = r#"insert
+    $p isa person, has name "Alice", has name "Bob", has age 10;
+    $q isa person, has name "Alice", has age 20;
+    $r isa person, has name "Carol";
+"#;
+
+fn read_rows(context: &QueryTestContext, query: &str) -> Result<Vec<Row>, ReadQueryError> {
+    run_read_query(context, &context.query_manager, query, ExecutionInterrupt::new_uninterruptible())
+}
+
+fn sorted_list(value: &VariableValue<'static>) -> Vec<Value<'static>> {
+    let VariableValue::ValueList(values) = value else { panic!("Expected a value list, found {value}") };
+    let mut values = values.to_vec();
+    values.sort_by(|lhs, rhs| lhs.partial_cmp(rhs).unwrap());
+    values
+}
+
+#[test]
+fn list_collects_values_per_group() {
+    let context = setup_query_context(QueryManager::new(None), SCHEMA, DATA);
+    let query = "match $p isa person, has name $n, has age $a; let $name = $n; reduce $ages = list($a) groupby $name;";
+    let rows = read_rows(&context, query).unwrap();
+    let ages_of = |name: &str| {
+        let name = VariableValue::Value(Value::String(Cow::Owned(name.to_owned())));
+        rows.iter().find(|row| row["name"] == name).map(|row| sorted_list(&row["ages"])).unwrap()
+    };
+    assert_eq!(rows.len(), 2);
+    assert_eq!(ages_of("Alice"), vec![Value::Integer(10), Value::Integer(20)]);
+    assert_eq!(ages_of("Bob"), vec![Value::Integer(10)]);
+}
+
+#[test]
+fn list_skips_absent_values() {
+    let context = setup_query_context(QueryManager::new(None), SCHEMA, DATA);
+    let query = "match $p isa person; try { $p has age $a; }; reduce $ages = list($a);";
+    let rows = read_rows(&context, query).unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(sorted_list(&rows[0]["ages"]), vec![Value::Integer(10), Value::Integer(20)]);
+}
+
+#[test]
+fn list_of_empty_input_is_empty() {
+    let context = setup_query_context(QueryManager::new(None), SCHEMA, DATA);
+    let query = "match $p isa person, has age $a; $a > 100; reduce $ages = list($a);";
+    let rows = read_rows(&context, query).unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(sorted_list(&rows[0]["ages"]), Vec::new());
+}
+
+#[test]
+fn sort_on_list_fails() {
+    let context = setup_query_context(QueryManager::new(None), SCHEMA, DATA);
+    let query = "match $p isa person, has age $a; reduce $ages = list($a) groupby $p; sort $ages;";
+    let Err(ReadQueryError::Preparation(err)) = read_rows(&context, query) else {
+        panic!("Expected sorting on a list to fail to compile")
+    };
+    assert_matches!(*err, QueryError::Annotation { typedb_source: AnnotationError::SortVariableIsList { .. }, .. });
+}
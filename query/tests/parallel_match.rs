@@ -0,0 +1,99 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::thread;
+
+use answer::variable_value::VariableValue;
+use encoding::value::value::Value;
+use itertools::Itertools;
+use query::query_manager::QueryManager;
+use test_utils_query::{QueryTestContext, read_values, setup_query_context};
+
+const SCHEMA: &str =
+    "define attribute age, value integer; attribute name, value string; entity person, owns age, owns name;";
+// Enough people for the first step of a match to produce several batches
+const PEOPLE: i64 = 300;
+
+fn setup() -> QueryTestContext {
+    let data = format!(
+        "insert $named isa person, has age {PEOPLE}, has name \"Alice\"; {}",
+        (0..PEOPLE).map(|age| format!("$p{age} isa person, has age {age};")).join(" ")
+    );
+    setup_query_context(QueryManager::new(None), SCHEMA, &data)
+}
+
+// The negation makes the first step of the match an intersection followed by further steps, so it is partitioned
+const MATCH_UNNAMED: &str = "match $p isa person, has age $a; not { $p has name \"Alice\"; };";
+
+#[test]
+fn parallel_sort_matches_sequential_sort() {
+    let context = setup();
+    let query = format!("{MATCH_UNNAMED} sort $a;");
+    let sequential = read_values(&context, &QueryManager::new(None), &query, "a");
+    let parallel = read_values(&context, &QueryManager::new(None).parallel(), &query, "a");
+    assert_eq!(sequential.len(), PEOPLE as usize);
+    assert_eq!(parallel, sequential);
+}
+
+#[test]
+fn parallel_reduce_counts_every_partition() {
+    let context = setup();
+    let query = format!("{MATCH_UNNAMED} reduce $count = count;");
+    let count = read_values(&context, &QueryManager::new(None).parallel(), &query, "count");
+    assert_eq!(count, vec![VariableValue::Value(Value::Integer(PEOPLE))]);
+}
+
+#[test]
+fn parallel_match_without_collecting_stage_is_unaffected() {
+    let context = setup();
+    let query = format!("{MATCH_UNNAMED} limit 10;");
+    let parallel = read_values(&context, &QueryManager::new(None).parallel(), &query, "a");
+    let sequential = read_values(&context, &QueryManager::new(None), &query, "a");
+    assert_eq!(parallel, sequential);
+}
+
+#[test]
+fn parallel_match_stages_after_the_first_are_unaffected() {
+    let context = setup();
+    let query = format!("{MATCH_UNNAMED} match $p has age $b; $b > 100; sort $b;");
+    let parallel = read_values(&context, &QueryManager::new(None).parallel(), &query, "b");
+    let sequential = read_values(&context, &QueryManager::new(None), &query, "b");
+    assert_eq!(parallel.len(), PEOPLE as usize - 101);
+    assert_eq!(parallel, sequential);
+}
+
+#[test]
+fn parallel_match_calling_functions_matches_sequential_match() {
+    let context = setup();
+    let query = r#"
+        with
+        fun older_than($age: integer) -> { person }:
+        match $q isa person, has age $b; $b > $age;
+        return { $q };
+
+        match $p isa person, has age $a; $a > 290; let $q in older_than($a);
+        reduce $count = count;
+    "#;
+    let parallel = read_values(&context, &QueryManager::new(None).parallel(), query, "count");
+    let sequential = read_values(&context, &QueryManager::new(None), query, "count");
+    // The person aged 300 - n is younger than n people, for n from 1 to 9
+    assert_eq!(sequential, vec![VariableValue::Value(Value::Integer((1..=9).sum()))]);
+    assert_eq!(parallel, sequential);
+}
+
+#[test]
+fn concurrent_parallel_queries_share_the_worker_threads() {
+    let context = setup();
+    let query = format!("{MATCH_UNNAMED} reduce $count = count;");
+    let query_manager = QueryManager::new(None).parallel();
+    thread::scope(|scope| {
+        let queries =
+            (0..32).map(|_| scope.spawn(|| read_values(&context, &query_manager, &query, "count"))).collect_vec();
+        for query in queries {
+            assert_eq!(query.join().unwrap(), vec![VariableValue::Value(Value::Integer(PEOPLE))]);
+        }
+    });
+}
@@ -0,0 +1,96 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::sync::Arc;
+
+use answer::variable_value::VariableValue;
+use encoding::value::value::Value;
+use executor::ExecutionInterrupt;
+use ir::{RepresentationError, pipeline::QueryParameters};
+use query::{error::QueryError, query_cache::QueryCache, query_manager::QueryManager};
+use test_utils::assert_matches;
+use test_utils_query::{
+    QueryTestContext, ReadQueryError, read_values, run_read_query, run_write_query, setup_query_context,
+};
+
+const SCHEMA: &str =
+    "define attribute age, value integer; attribute name, value string; entity person, owns age, owns name;";
+const DATA: &str = r#"insert
+    $p isa person, has name "Alice", has age 10;
+    $q isa person, has name "Bob", has age 20;
+"#;
+
+const AGE_BY_NAME: &str = "match $p isa person, has name $n, has age $a; $n == $p_name; select $a;";
+
+fn setup() -> QueryTestContext {
+    setup_query_context(QueryManager::new(Some(Arc::new(QueryCache::new()))), SCHEMA, DATA)
+}
+
+fn bound(context: &QueryTestContext, parameters: &[(&str, &str, &str)]) -> QueryManager {
+    let mut query_parameters = QueryParameters::new();
+    for &(variable, value_type, value) in parameters {
+        query_parameters.bind(variable.to_owned(), value_type.to_owned(), value.to_owned());
+    }
+    context.query_manager.bound(query_parameters)
+}
+
+fn integer(value: i64) -> VariableValue<'static> {
+    VariableValue::Value(Value::Integer(value))
+}
+
+#[test]
+fn bound_parameter_is_matched() {
+    let context = setup();
+    let query_manager = bound(&context, &[("p_name", "string", "Alice")]);
+    let ages = read_values(&context, &query_manager, AGE_BY_NAME, "a");
+    assert_eq!(ages, vec![integer(10)]);
+}
+
+#[test]
+fn cached_plan_is_executed_with_different_values() {
+    let cache = Arc::new(QueryCache::new());
+    let context = setup_query_context(QueryManager::new(Some(cache.clone())), SCHEMA, DATA);
+    let initial_plans = cache.cached_plan_count();
+
+    let alice = bound(&context, &[("p_name", "string", "Alice")]);
+    assert_eq!(read_values(&context, &alice, AGE_BY_NAME, "a"), vec![integer(10)]);
+    assert_eq!(cache.cached_plan_count(), initial_plans + 1);
+
+    let bob = bound(&context, &[("p_name", "string", "Bob")]);
+    assert_eq!(read_values(&context, &bob, AGE_BY_NAME, "a"), vec![integer(20)]);
+    assert_eq!(cache.cached_plan_count(), initial_plans + 1, "Expected the plan cached for 'Alice' to be reused");
+}
+
+#[test]
+fn bound_parameters_are_inserted() {
+    let context = setup();
+    let query_manager = bound(&context, &[("p_name", "string", "Carol \"; delete"), ("p_age", "integer", "30")]);
+    run_write_query(&context, &query_manager, "insert $p isa person, has name $p_name, has age $p_age;");
+    let query_manager = bound(&context, &[("p_name", "string", "Carol \"; delete")]);
+    let ages = read_values(&context, &query_manager, AGE_BY_NAME, "a");
+    assert_eq!(ages, vec![integer(30)]);
+}
+
+#[test]
+fn invalid_parameter_value_fails() {
+    let context = setup();
+    let query_manager = bound(&context, &[("p_name", "string", "Alice"), ("p_age", "integer", "ten")]);
+    let query = "match $p isa person, has name $n, has age $a; $n == $p_name; $a == $p_age; select $a;";
+    let result = run_read_query(&context, &query_manager, query, ExecutionInterrupt::new_uninterruptible());
+    let Err(ReadQueryError::Preparation(err)) = result else { panic!("Expected the query to fail to compile") };
+    let QueryError::Representation { typedb_source, .. } = *err else { panic!("Expected a representation error") };
+    assert_matches!(*typedb_source, RepresentationError::InvalidQueryParameter { .. });
+}
+
+#[test]
+fn unused_parameter_fails() {
+    let context = setup();
+    let query_manager = bound(&context, &[("p_name", "string", "Alice"), ("p_nmae", "string", "Bob")]);
+    let result = run_read_query(&context, &query_manager, AGE_BY_NAME, ExecutionInterrupt::new_uninterruptible());
+    let Err(ReadQueryError::Preparation(err)) = result else { panic!("Expected the query to fail to compile") };
+    let QueryError::Representation { typedb_source, .. } = *err else { panic!("Expected a representation error") };
+    assert_matches!(*typedb_source, RepresentationError::UnusedQueryParameter { .. });
+}
@@ -0,0 +1,114 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::{collections::HashMap, sync::Arc};
+
+use answer::variable_value::VariableValue;
+use encoding::graph::definition::definition_key_generator::DefinitionKeyGenerator;
+use executor::{
+    ExecutionInterrupt,
+    pipeline::{PipelineExecutionError, stage::ExecutionContext},
+};
+use function::function_manager::FunctionManager;
+use lending_iterator::LendingIterator;
+use query::{error::QueryError, query_manager::QueryManager};
+use resource::profile::CommitProfile;
+use storage::{MVCCStorage, durability_client::WALClient, snapshot::CommittableSnapshot};
+use test_utils::TempDir;
+use test_utils_concept::{load_managers, setup_concept_storage};
+use test_utils_encoding::create_core_storage;
+
+pub struct QueryTestContext {
+    _tmp_dir: TempDir,
+    pub storage: Arc<MVCCStorage<WALClient>>,
+    pub function_manager: FunctionManager,
+    pub query_manager: QueryManager,
+}
+
+pub type Row = HashMap<String, VariableValue<'static>>;
+
+#[derive(Debug)]
+pub enum ReadQueryError {
+    Preparation(Box<QueryError>),
+    Execution(Box<PipelineExecutionError>),
+}
+
+/// Creates a database with the schema and data committed by the given query manager, which is kept for querying it.
+pub fn setup_query_context(query_manager: QueryManager, schema: &str, data: &str) -> QueryTestContext {
+    let (_tmp_dir, mut storage) = create_core_storage();
+    setup_concept_storage(&mut storage);
+    let (type_manager, thing_manager) = load_managers(storage.clone(), None);
+    let function_manager = FunctionManager::new(Arc::new(DefinitionKeyGenerator::new()), None);
+
+    let mut snapshot = storage.clone().open_snapshot_schema();
+    let schema_query = typeql::parse_query(schema).unwrap().into_structure().into_schema();
+    query_manager
+        .execute_schema(&mut snapshot, &type_manager, &thing_manager, &function_manager, schema_query, schema)
+        .unwrap();
+    snapshot.commit(&mut CommitProfile::DISABLED).unwrap();
+
+    let context = QueryTestContext { _tmp_dir, storage, function_manager, query_manager };
+    run_write_query(&context, &context.query_manager, data);
+    context
+}
+
+pub fn run_write_query(context: &QueryTestContext, query_manager: &QueryManager, query: &str) {
+    let (type_manager, thing_manager) = load_managers(context.storage.clone(), None);
+    let snapshot = context.storage.clone().open_snapshot_write();
+    let pipeline = typeql::parse_query(query).unwrap().into_structure().into_pipeline();
+    let pipeline = query_manager
+        .prepare_write_pipeline(snapshot, &type_manager, thing_manager, &context.function_manager, &pipeline, query)
+        .unwrap();
+    let (mut iterator, ExecutionContext { snapshot, .. }) =
+        pipeline.into_rows_iterator(ExecutionInterrupt::new_uninterruptible()).unwrap();
+    while let Some(row) = iterator.next() {
+        row.unwrap();
+    }
+    drop(iterator);
+    Arc::into_inner(snapshot).unwrap().commit(&mut CommitProfile::DISABLED).unwrap();
+}
+
+/// Runs a read query to completion, returning its rows by variable name.
+pub fn run_read_query(
+    context: &QueryTestContext,
+    query_manager: &QueryManager,
+    query: &str,
+    interrupt: ExecutionInterrupt,
+) -> Result<Vec<Row>, ReadQueryError> {
+    let (type_manager, thing_manager) = load_managers(context.storage.clone(), None);
+    let snapshot = Arc::new(context.storage.clone().open_snapshot_read());
+    let pipeline = typeql::parse_query(query).unwrap().into_structure().into_pipeline();
+    let pipeline = query_manager
+        .prepare_read_pipeline(snapshot, &type_manager, thing_manager, &context.function_manager, &pipeline, query)
+        .map_err(ReadQueryError::Preparation)?;
+    let positions = pipeline.rows_positions().unwrap().clone();
+    let mut iterator = match pipeline.into_rows_iterator(interrupt) {
+        Ok((iterator, _)) => iterator,
+        Err((err, _)) => return Err(ReadQueryError::Execution(err)),
+    };
+    let mut rows = Vec::new();
+    while let Some(row) = iterator.next() {
+        let row = row.map_err(|err| ReadQueryError::Execution(err.clone()))?;
+        rows.push(
+            positions
+                .iter()
+                .map(|(variable, &position)| (variable.clone(), row.get(position).clone().into_owned()))
+                .collect(),
+        );
+    }
+    Ok(rows)
+}
+
+/// Runs a read query that is expected to succeed, returning the values of one variable in answer order.
+pub fn read_values(
+    context: &QueryTestContext,
+    query_manager: &QueryManager,
+    query: &str,
+    variable: &str,
+) -> Vec<VariableValue<'static>> {
+    let rows = run_read_query(context, query_manager, query, ExecutionInterrupt::new_uninterruptible()).unwrap();
+    rows.into_iter().map(|mut row| row.remove(variable).unwrap()).collect()
+}
@@ -64,6 +64,12 @@ pub mod server {
     pub const DEFAULT_ANSWER_COUNT_LIMIT_HTTP: Option<usize> = Some(10_000);
     pub const DEFAULT_INCLUDE_STRUCTURE_HTTP: bool = true; // True for studio backwards compatibility
     pub const DEFAULT_INCLUDE_STRUCTURE_GRPC: bool = false;
+    pub const DEFAULT_QUERY_EXPLAIN: bool = false;
+    pub const DEFAULT_QUERY_PROFILE: bool = false;
+    pub const DEFAULT_QUERY_TIMEOUT_MILLIS: Option<u64> = None;
+    pub const DEFAULT_COLLECTED_ROWS_LIMIT: Option<usize> = None;
+    pub const DEFAULT_TABLED_ROWS_LIMIT: Option<usize> = None;
+    pub const MAX_SUSPENDED_READ_QUERIES_PER_TRANSACTION: usize = 4;
 
     pub const PERF_COUNTERS_ENABLED: bool = true;
 
@@ -87,6 +93,12 @@ pub mod server {
         Duration::from_secs(MAX_AUTHENTICATION_TOKEN_EXPIRATION_SECONDS);
     pub const DEFAULT_AUTHENTICATION_TOKEN_EXPIRATION: Duration =
         Duration::from_secs(DEFAULT_AUTHENTICATION_TOKEN_EXPIRATION_SECONDS);
+    pub const DEFAULT_AUTHENTICATION_LOCKOUT_DURATION: Duration = Duration::from_secs(15 * SECONDS_IN_MINUTE);
+    pub const DEFAULT_AUTHENTICATION_LDAP_TIMEOUT: Duration = Duration::from_secs(5);
+
+    pub const AUDIT_LOG_DEFAULT_MAX_FILES: usize = 30;
+    pub const SLOW_QUERY_LOG_DEFAULT_MAX_FILES: usize = 30;
+    pub const SLOW_QUERY_LOG_DEFAULT_THRESHOLD: Duration = Duration::from_secs(1);
 
     pub const DATABASE_METRICS_UPDATE_INTERVAL: Duration = Duration::from_secs(10 * SECONDS_IN_MINUTE);
 
@@ -110,6 +122,9 @@ pub mod database {
     pub const STATISTICS_DURABLE_WRITE_SEQ_NUMBERS: usize = 1_000;
     pub const STATISTICS_UPDATE_INTERVAL: Duration = Duration::from_millis(50);
     pub const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(60);
+    pub const REPLICATION_BATCH_RECORDS: usize = 10_000;
+    pub const CHANGE_STREAM_BATCH_COMMITS: usize = 1_000;
+    pub const REPLICATION_POLL_INTERVAL: Duration = Duration::from_millis(100);
 
     #[macro_export]
     macro_rules! internal_database_prefix {
@@ -127,6 +142,8 @@ pub mod concept {
 
 pub mod query {
     pub const MAX_PIPELINE_STAGES: usize = 1000;
+    pub const PARALLEL_MATCH_WORKERS_MAX: usize = 8;
+    pub const PARALLEL_MATCH_QUEUED_BATCHES_PER_WORKER: usize = 4;
 }
 
 pub mod traversal {
@@ -142,12 +159,16 @@ pub mod snapshot {
 }
 
 pub mod storage {
+    use std::time::Duration;
+
     use crate::constants::common::MB;
 
     pub const TIMELINE_WINDOW_SIZE: usize = 32;
     pub const WAL_SYNC_INTERVAL_MICROSECONDS: u64 = 1000;
     pub const WATERMARK_WAIT_INTERVAL_MICROSECONDS: u64 = 50;
     pub const COMMIT_WAIT_FOR_FSYNC: bool = true;
+    pub const DEFAULT_HISTORY_RETENTION: Duration = Duration::from_secs(60 * 60);
+    pub const DEFAULT_BACKUP_DIRECTORY: &str = "backups";
 
     pub const ROCKSDB_CACHE_SIZE: u64 = 1024 * MB;
 }
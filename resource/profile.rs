@@ -10,7 +10,7 @@ use std::{
     fmt::{Display, Formatter},
     sync::{
         Arc, OnceLock, RwLock,
-        atomic::{AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicU64, Ordering},
     },
     time::{Duration, Instant},
 };
@@ -432,6 +432,9 @@ impl CommitProfileData {
     }
 }
 
+// Set when every query must be profiled regardless of the tracing level, for example to write the slow query log
+static IS_QUERY_PROFILING_REQUIRED: AtomicBool = AtomicBool::new(false);
+
 #[derive(Debug)]
 pub struct QueryProfile {
     compile_profile: CompileProfile,
@@ -448,6 +451,14 @@ impl QueryProfile {
         self.enabled
     }
 
+    pub fn set_profiling_required(required: bool) {
+        IS_QUERY_PROFILING_REQUIRED.store(required, Ordering::Relaxed);
+    }
+
+    pub fn is_profiling_required() -> bool {
+        IS_QUERY_PROFILING_REQUIRED.load(Ordering::Relaxed)
+    }
+
     pub fn compilation_profile(&mut self) -> &mut CompileProfile {
         &mut self.compile_profile
     }
@@ -470,7 +481,11 @@ impl QueryProfile {
         &self.stage_profiles
     }
 
-    fn total_nanos(&self) -> u64 {
+    pub fn compile_profile(&self) -> &CompileProfile {
+        &self.compile_profile
+    }
+
+    pub fn total_nanos(&self) -> u64 {
         let stage_nanos: u64 = self
             .stage_profiles
             .read()
@@ -562,7 +577,27 @@ impl CompileProfile {
         }
     }
 
-    fn total_nanos(&self) -> u64 {
+    pub fn is_enabled(&self) -> bool {
+        self.data.is_some()
+    }
+
+    pub fn translation(&self) -> Duration {
+        self.data.as_ref().map_or(Duration::ZERO, |data| data.translation)
+    }
+
+    pub fn validation(&self) -> Duration {
+        self.data.as_ref().map_or(Duration::ZERO, |data| data.validation)
+    }
+
+    pub fn annotation(&self) -> Duration {
+        self.data.as_ref().map_or(Duration::ZERO, |data| data.annotation)
+    }
+
+    pub fn compilation(&self) -> Duration {
+        self.data.as_ref().map_or(Duration::ZERO, |data| data.compilation)
+    }
+
+    pub fn total_nanos(&self) -> u64 {
         match &self.data {
             None => 0,
             Some(data) => (data.translation + data.validation + data.annotation + data.compilation).as_nanos() as u64,
@@ -656,6 +691,10 @@ impl StageProfile {
             .clone()
     }
 
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
     /// Returns the pattern profile that has been attached to this stage, or `None` if
     /// the stage's pattern has never been initialised. Inspection-only — does not call
     /// `create_or_get_pattern` and so will not allocate a disabled placeholder.
@@ -737,6 +776,10 @@ impl PatternProfile {
         Self { substeps: RwLock::new(Vec::new()), enabled: false, description: None }
     }
 
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
     pub fn substeps(&self) -> &RwLock<Vec<SubstepProfile>> {
         &self.substeps
     }
@@ -802,7 +845,7 @@ impl PatternProfile {
         step_profile
     }
 
-    fn total_nanos(&self) -> u64 {
+    pub fn total_nanos(&self) -> u64 {
         self.substeps.read().unwrap().iter().map(SubstepProfile::total_nanos).sum()
     }
 }
@@ -868,6 +911,18 @@ impl StepProfile {
     pub fn total_nanos(&self) -> u64 {
         self.data.as_ref().map_or(0, |data| data.nanos.load(Ordering::SeqCst))
     }
+
+    pub fn description(&self) -> Option<&str> {
+        self.data.as_ref().map(|data| data.description.as_str())
+    }
+
+    pub fn batches(&self) -> u64 {
+        self.data.as_ref().map_or(0, |data| data.batches.load(Ordering::SeqCst))
+    }
+
+    pub fn rows(&self) -> u64 {
+        self.data.as_ref().map_or(0, |data| data.rows.load(Ordering::SeqCst))
+    }
 }
 
 impl IndentDisplay for StepProfile {
@@ -966,17 +1021,29 @@ impl StorageCounters {
         }
     }
 
+    pub fn get_advance_mvcc_visible(&self) -> Option<u64> {
+        self.counters.as_ref().map(|counters| counters.advance_mvcc_visible.load(Ordering::SeqCst))
+    }
+
     pub fn increment_advance_mvcc_invisible(&self) {
         if let Some(counters) = self.counters.as_ref() {
             counters.advance_mvcc_invisible.fetch_add(1, Ordering::Relaxed);
         }
     }
 
+    pub fn get_advance_mvcc_invisible(&self) -> Option<u64> {
+        self.counters.as_ref().map(|counters| counters.advance_mvcc_invisible.load(Ordering::SeqCst))
+    }
+
     pub fn increment_advance_mvcc_deleted(&self) {
         if let Some(counters) = self.counters.as_ref() {
             counters.advance_mvcc_deleted.fetch_add(1, Ordering::Relaxed);
         }
     }
+
+    pub fn get_advance_mvcc_deleted(&self) -> Option<u64> {
+        self.counters.as_ref().map(|counters| counters.advance_mvcc_deleted.load(Ordering::SeqCst))
+    }
 }
 
 impl Display for StorageCounters {
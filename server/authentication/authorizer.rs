@@ -0,0 +1,217 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+use std::sync::Arc;
+
+use database::database_manager::DatabaseManager;
+use system::concepts::{Grant, Role};
+use user::{permission_manager::PermissionManager, user_manager::UserManager};
+
+use crate::{error::LocalServerStateError, system_init::SYSTEM_DB, transaction::TransactionType};
+
+// Enforces per-database role grants stored in the system database. When disabled, every
+// authenticated user keeps unrestricted access to user databases.
+#[derive(Debug)]
+pub struct Authorizer {
+    database_manager: Arc<DatabaseManager>,
+    is_enabled: bool,
+}
+
+impl Authorizer {
+    pub fn new(database_manager: Arc<DatabaseManager>, is_enabled: bool) -> Self {
+        Self { database_manager, is_enabled }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.is_enabled
+    }
+
+    pub fn authorize_transaction_open(
+        &self,
+        accessor: &str,
+        database_name: &str,
+        transaction_type: TransactionType,
+    ) -> Result<(), LocalServerStateError> {
+        if !self.is_enabled {
+            return Ok(());
+        }
+        let role = Self::required_role(transaction_type);
+        let grants = self.grants(accessor)?;
+        match PermissionManager::exec_database_access_permitted(accessor, &grants, database_name, role) {
+            true => Ok(()),
+            false => Err(LocalServerStateError::DatabaseAccessNotPermitted {
+                name: database_name.to_string(),
+                role: role.name().to_string(),
+            }),
+        }
+    }
+
+    pub fn authorize_database_create(&self, accessor: &str) -> Result<(), LocalServerStateError> {
+        if !self.is_enabled {
+            return Ok(());
+        }
+        let grants = self.grants(accessor)?;
+        match PermissionManager::exec_database_create_permitted(accessor, &grants) {
+            true => Ok(()),
+            false => Err(LocalServerStateError::OperationNotPermitted {}),
+        }
+    }
+
+    pub fn authorize_database_delete(&self, accessor: &str, database_name: &str) -> Result<(), LocalServerStateError> {
+        if !self.is_enabled {
+            return Ok(());
+        }
+        let grants = self.grants(accessor)?;
+        match PermissionManager::exec_database_delete_permitted(accessor, &grants, database_name) {
+            true => Ok(()),
+            false => Err(LocalServerStateError::DatabaseAccessNotPermitted {
+                name: database_name.to_string(),
+                role: Role::SchemaAdmin.name().to_string(),
+            }),
+        }
+    }
+
+    pub fn authorize_database_backup(&self, accessor: &str, database_name: &str) -> Result<(), LocalServerStateError> {
+        // A backup copies the whole database onto the server's file system, so only its administrators may take one
+        if !self.is_enabled {
+            return Ok(());
+        }
+        let grants = self.grants(accessor)?;
+        match PermissionManager::exec_database_backup_permitted(accessor, &grants, database_name) {
+            true => Ok(()),
+            false => Err(LocalServerStateError::DatabaseAccessNotPermitted {
+                name: database_name.to_string(),
+                role: Role::SchemaAdmin.name().to_string(),
+            }),
+        }
+    }
+
+    pub fn authorize_change_stream(&self, accessor: &str, database_name: &str) -> Result<(), LocalServerStateError> {
+        // Change events carry the committed data, so they require the same access as reading it
+        self.authorize_transaction_open(accessor, database_name, TransactionType::Read)
+    }
+
+    pub fn authorize_replication(&self, accessor: &str, database_name: &str) -> Result<(), LocalServerStateError> {
+        // The write-ahead log carries the committed data, so replicating it requires the same access as reading it
+        self.authorize_transaction_open(accessor, database_name, TransactionType::Read)
+    }
+
+    pub fn authorize_database_statistics(
+        &self,
+        accessor: &str,
+        database_name: &str,
+    ) -> Result<(), LocalServerStateError> {
+        // Statistics reveal the schema and the amount of data of each type, so they require read access
+        self.authorize_transaction_open(accessor, database_name, TransactionType::Read)
+    }
+
+    fn required_role(transaction_type: TransactionType) -> Role {
+        match transaction_type {
+            TransactionType::Read => Role::Reader,
+            TransactionType::Write => Role::Writer,
+            TransactionType::Schema => Role::SchemaAdmin,
+        }
+    }
+
+    fn grants(&self, accessor: &str) -> Result<Vec<Grant>, LocalServerStateError> {
+        let system_db =
+            self.database_manager.database_unrestricted(SYSTEM_DB).ok_or(LocalServerStateError::NotInitialised {})?;
+        UserManager::new(system_db)
+            .grants(accessor)
+            .map_err(|typedb_source| LocalServerStateError::UserGrantsCannotBeRetrieved { typedb_source })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use system::concepts::{Grant, Role, User};
+    use test_utils::{TempDir, create_tmp_storage_dir};
+    use user::{permission_manager::PermissionManager, user_manager::UserManager};
+
+    use super::Authorizer;
+    use crate::{
+        error::LocalServerStateError,
+        system_init::{SYSTEM_DB, tests::system_database_manager},
+        transaction::TransactionType,
+    };
+
+    const USER: &str = "reader-user";
+    const GRANTED_DB: &str = "granted-db";
+    const OTHER_DB: &str = "other-db";
+
+    // An authorizer over a system database in which the user can only read one database
+    fn setup(is_enabled: bool) -> (TempDir, Authorizer) {
+        let data_directory = create_tmp_storage_dir();
+        let database_manager = system_database_manager(data_directory.as_ref());
+        let user_manager = UserManager::new(database_manager.database_unrestricted(SYSTEM_DB).unwrap());
+        user_manager.create(&User::new(USER.to_string()), "reader-password").unwrap();
+        user_manager.grant(USER, &Grant::new(Role::Reader, Some(GRANTED_DB.to_string()))).unwrap();
+        (data_directory, Authorizer::new(database_manager, is_enabled))
+    }
+
+    #[test]
+    fn transactions_beyond_granted_role_are_refused() {
+        let (_data_directory, authorizer) = setup(true);
+        assert!(authorizer.authorize_transaction_open(USER, GRANTED_DB, TransactionType::Read).is_ok());
+        for transaction_type in [TransactionType::Write, TransactionType::Schema] {
+            let result = authorizer.authorize_transaction_open(USER, GRANTED_DB, transaction_type);
+            assert!(matches!(result, Err(LocalServerStateError::DatabaseAccessNotPermitted { .. })));
+        }
+        let result = authorizer.authorize_transaction_open(USER, OTHER_DB, TransactionType::Read);
+        assert!(matches!(result, Err(LocalServerStateError::DatabaseAccessNotPermitted { .. })));
+    }
+
+    #[test]
+    fn database_create_and_delete_are_refused() {
+        let (_data_directory, authorizer) = setup(true);
+        assert!(matches!(
+            authorizer.authorize_database_create(USER),
+            Err(LocalServerStateError::OperationNotPermitted { .. })
+        ));
+        assert!(matches!(
+            authorizer.authorize_database_delete(USER, GRANTED_DB),
+            Err(LocalServerStateError::DatabaseAccessNotPermitted { .. })
+        ));
+    }
+
+    #[test]
+    fn backups_require_schema_admin_role() {
+        let (_data_directory, authorizer) = setup(true);
+        assert!(matches!(
+            authorizer.authorize_database_backup(USER, GRANTED_DB),
+            Err(LocalServerStateError::DatabaseAccessNotPermitted { .. })
+        ));
+    }
+
+    #[test]
+    fn reading_ungranted_database_contents_is_refused() {
+        let (_data_directory, authorizer) = setup(true);
+        let results = [
+            authorizer.authorize_database_backup(USER, OTHER_DB),
+            authorizer.authorize_change_stream(USER, OTHER_DB),
+            authorizer.authorize_replication(USER, OTHER_DB),
+            authorizer.authorize_database_statistics(USER, OTHER_DB),
+        ];
+        for result in results {
+            assert!(matches!(result, Err(LocalServerStateError::DatabaseAccessNotPermitted { .. })));
+        }
+        assert!(authorizer.authorize_change_stream(USER, GRANTED_DB).is_ok());
+    }
+
+    #[test]
+    fn grants_of_other_users_are_refused() {
+        assert!(!PermissionManager::exec_user_grants_get_permitted(USER, "other-user"));
+        assert!(PermissionManager::exec_user_grants_get_permitted(USER, USER));
+        assert!(!PermissionManager::exec_user_grants_update_permitted(USER));
+    }
+
+    #[test]
+    fn disabled_authorizer_permits_everything() {
+        let (_data_directory, authorizer) = setup(false);
+        assert!(authorizer.authorize_transaction_open(USER, OTHER_DB, TransactionType::Schema).is_ok());
+        assert!(authorizer.authorize_database_create(USER).is_ok());
+        assert!(authorizer.authorize_database_delete(USER, OTHER_DB).is_ok());
+    }
+}
@@ -3,29 +3,168 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
-use std::sync::Arc;
+use std::{fmt::Debug, io, sync::Arc};
 
-use system::concepts::Credential;
+use async_trait::async_trait;
+use error::{TypeDBError, typedb_error};
+use system::concepts::{ApiKey, Credential, PasswordState};
+use tracing::{Level, event};
 use user::user_manager::UserManager;
 
-use crate::authentication::AuthenticationError;
+use crate::{
+    authentication::{
+        AuthenticationError, ldap::LdapCredentialVerifier, oidc::OidcCredentialVerifier, token_manager::TokenManager,
+    },
+    parameters::config::{AuthenticationConfig, LockoutConfig},
+};
+
+// Proves the identity of a signing in user. Verifiers of external identity providers are tried after
+// the users' own credentials in the system database, and only ever authenticate users that exist locally.
+#[async_trait]
+pub trait CredentialVerifier: Debug + Send + Sync {
+    // Fails with `InvalidCredential` if the credential does not prove the identity of `username`,
+    // so that the next verifier is tried
+    async fn verify_password(&self, username: &str, password: &str) -> Result<(), AuthenticationError>;
+}
+
+pub(crate) fn external_credential_verifiers(
+    config: &AuthenticationConfig,
+) -> Result<Vec<Arc<dyn CredentialVerifier>>, ExternalAuthenticationError> {
+    let mut verifiers: Vec<Arc<dyn CredentialVerifier>> = Vec::new();
+    if config.oidc.enabled {
+        verifiers.push(Arc::new(OidcCredentialVerifier::new(&config.oidc)?));
+    }
+    if config.ldap.enabled {
+        verifiers.push(Arc::new(LdapCredentialVerifier::new(&config.ldap)?));
+    }
+    Ok(verifiers)
+}
 
 #[derive(Clone, Debug)]
-pub(crate) struct CredentialVerifier {
+pub(crate) struct SystemCredentialVerifier {
     user_manager: Arc<UserManager>,
+    lockout: LockoutConfig,
 }
 
-impl CredentialVerifier {
-    pub(crate) fn new(user_manager: Arc<UserManager>) -> Self {
-        Self { user_manager }
+#[async_trait]
+impl CredentialVerifier for SystemCredentialVerifier {
+    // NOTE: Password verification is an expensive CPU-bound operation!
+    // API keys are accepted in place of the password, since the protocol only carries password credentials
+    async fn verify_password(&self, username: &str, password: &str) -> Result<(), AuthenticationError> {
+        if let Some((id, secret)) = ApiKey::parse(password) {
+            return self.verify_api_key(username, id, secret);
+        }
+        self.verify_user_password(username, password)
     }
+}
 
-    // NOTE: Password verification is an expensive CPU-bound operation!
-    pub(crate) fn verify_password(&self, username: &str, password: &str) -> Result<(), AuthenticationError> {
-        let Ok(Some((_, Credential::PasswordType { password_hash }))) = self.user_manager.get(username) else {
+impl SystemCredentialVerifier {
+    pub(crate) fn new(user_manager: Arc<UserManager>, lockout: LockoutConfig) -> Self {
+        Self { user_manager, lockout }
+    }
+
+    // Fails with `PasswordChangeRequired` only once the password itself has been verified
+    pub(crate) fn verify_user_password(&self, username: &str, password: &str) -> Result<(), AuthenticationError> {
+        let state = self.sign_in_state(username)?;
+        let is_verified = self.matches_password(username, password);
+        self.conclude_sign_in(username, &state, is_verified)
+    }
+
+    // The sign-in state of a user that may sign in at all, whichever verifier proves their identity
+    pub(crate) fn sign_in_state(&self, username: &str) -> Result<PasswordState, AuthenticationError> {
+        let Ok(Some(state)) = self.user_manager.password_state(username) else {
+            return Err(AuthenticationError::InvalidCredential {});
+        };
+        if state.is_locked(TokenManager::now_seconds()) {
+            return Err(AuthenticationError::UserLocked {});
+        }
+        Ok(state)
+    }
+
+    pub(crate) fn matches_password(&self, username: &str, password: &str) -> bool {
+        match self.user_manager.get(username) {
+            Ok(Some((_, Credential::PasswordType { password_hash }))) => password_hash.matches(password),
+            _ => false,
+        }
+    }
+
+    // Applies the lockout and password change rules once every verifier has been consulted
+    pub(crate) fn conclude_sign_in(
+        &self,
+        username: &str,
+        state: &PasswordState,
+        is_verified: bool,
+    ) -> Result<(), AuthenticationError> {
+        if !is_verified {
+            self.record_failed_attempt(username, TokenManager::now_seconds())?;
+            return Err(AuthenticationError::InvalidCredential {});
+        }
+        if state.failed_attempts > 0 {
+            self.user_manager
+                .reset_failed_password_attempts(username)
+                .map_err(|typedb_source| AuthenticationError::SignInAttemptNotRecorded { typedb_source })?;
+        }
+        if state.must_change {
+            return Err(AuthenticationError::PasswordChangeRequired {});
+        }
+        Ok(())
+    }
+
+    // A failed attempt that cannot be recorded fails the sign-in, so that lockouts cannot be evaded
+    fn record_failed_attempt(&self, username: &str, now: u64) -> Result<(), AuthenticationError> {
+        if self.lockout.max_failed_attempts == 0 {
+            return Ok(());
+        }
+        let is_locked_out = self
+            .user_manager
+            .record_failed_password_attempt(
+                username,
+                self.lockout.max_failed_attempts,
+                now + self.lockout.duration.as_secs(),
+            )
+            .map_err(|typedb_source| AuthenticationError::SignInAttemptNotRecorded { typedb_source })?;
+        if is_locked_out {
+            event!(
+                Level::WARN,
+                "User '{username}' is locked out for {} seconds after {} failed sign-in attempts",
+                self.lockout.duration.as_secs(),
+                self.lockout.max_failed_attempts
+            );
+        }
+        Ok(())
+    }
+
+    fn verify_api_key(&self, username: &str, id: &str, secret: &str) -> Result<(), AuthenticationError> {
+        let Ok(api_keys) = self.user_manager.api_keys(username) else {
+            return Err(AuthenticationError::InvalidCredential {});
+        };
+        let now = TokenManager::now_seconds();
+        let Some(api_key) = api_keys.into_iter().find(|api_key| api_key.id == id && !api_key.is_expired(now)) else {
             return Err(AuthenticationError::InvalidCredential {});
         };
+        if !api_key.matches(secret) {
+            return Err(AuthenticationError::InvalidCredential {});
+        }
+
+        if let Err(error) = self.user_manager.update_api_key_last_used(id, now) {
+            event!(
+                Level::WARN,
+                "Could not record the use of API key '{}' of user '{username}': {}",
+                api_key.name,
+                error.format_code_and_description()
+            );
+        }
+        Ok(())
+    }
+}
 
-        password_hash.matches(password).then(|| ()).ok_or(AuthenticationError::InvalidCredential {})
+typedb_error! {
+    pub ExternalAuthenticationError(component = "External authentication", prefix = "EXA") {
+        LdapInvalidAddress(1, "Invalid LDAP address '{address}'. Expected 'ldap://<host>[:<port>]' or 'ldaps://<host>[:<port>]'.", address: String),
+        LdapMissingCertificate(2, "A CA certificate must be configured to connect to the LDAP directory at '{address}'.", address: String),
+        LdapCertificateUnreadable(3, "Could not read the LDAP CA certificate from '{path}'.", path: String, source: Arc<io::Error>),
+        LdapCertificateInvalid(4, "The LDAP CA certificate in '{path}' is invalid.", path: String),
+        OidcJwksUnreadable(5, "Could not read the OIDC JSON Web Key Set from '{path}'.", path: String, source: Arc<io::Error>),
+        OidcJwksInvalid(6, "The OIDC JSON Web Key Set in '{path}' is invalid: {reason}", path: String, reason: String),
     }
 }
@@ -0,0 +1,464 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+use std::{fmt, fs, io, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use tokio::{
+    io::{AsyncRead, AsyncWrite, AsyncWriteExt},
+    net::TcpStream,
+    time::timeout,
+};
+use tokio_rustls::{
+    TlsConnector,
+    rustls::{
+        ClientConfig, RootCertStore,
+        pki_types::{CertificateDer, ServerName, pem::PemObject},
+    },
+};
+use tracing::{Level, event};
+
+use crate::{
+    authentication::{
+        AuthenticationError,
+        credential_verifier::{CredentialVerifier, ExternalAuthenticationError},
+    },
+    parameters::config::LdapConfig,
+};
+
+// Verifies passwords with a simple bind against an LDAP directory, as the entry named by the configured
+// bind DN template. Only the bind and StartTLS operations are needed, so the few messages involved are encoded here.
+// Passwords are only ever sent over TLS: 'ldaps://' addresses are connected to over TLS directly, while connections
+// to 'ldap://' addresses are upgraded with StartTLS before binding.
+#[derive(Clone)]
+pub(crate) struct LdapCredentialVerifier {
+    address: String,
+    host: String,
+    port: u16,
+    is_start_tls: bool,
+    tls_connector: TlsConnector,
+    bind_dn_template: String,
+    timeout: Duration,
+}
+
+impl LdapCredentialVerifier {
+    const LDAP_SCHEME: &'static str = "ldap://";
+    const LDAPS_SCHEME: &'static str = "ldaps://";
+    const LDAP_DEFAULT_PORT: u16 = 389;
+    const LDAPS_DEFAULT_PORT: u16 = 636;
+
+    pub(crate) fn new(config: &LdapConfig) -> Result<Self, ExternalAuthenticationError> {
+        let invalid_address = || ExternalAuthenticationError::LdapInvalidAddress { address: config.address.clone() };
+        let (is_start_tls, authority) = if let Some(authority) = config.address.strip_prefix(Self::LDAPS_SCHEME) {
+            (false, authority)
+        } else if let Some(authority) = config.address.strip_prefix(Self::LDAP_SCHEME) {
+            (true, authority)
+        } else {
+            return Err(invalid_address());
+        };
+        let default_port = if is_start_tls { Self::LDAP_DEFAULT_PORT } else { Self::LDAPS_DEFAULT_PORT };
+        let (host, port) = match authority.trim_end_matches('/').rsplit_once(':') {
+            Some((host, port)) => (host, port.parse::<u16>().map_err(|_| invalid_address())?),
+            None => (authority.trim_end_matches('/'), default_port),
+        };
+        if host.is_empty() {
+            return Err(invalid_address());
+        }
+
+        Ok(Self {
+            address: config.address.clone(),
+            host: host.to_string(),
+            port,
+            is_start_tls,
+            tls_connector: Self::tls_connector(config)?,
+            bind_dn_template: config.bind_dn_template.clone(),
+            timeout: config.timeout,
+        })
+    }
+
+    fn tls_connector(config: &LdapConfig) -> Result<TlsConnector, ExternalAuthenticationError> {
+        let Some(path) = &config.ca_certificate else {
+            return Err(ExternalAuthenticationError::LdapMissingCertificate { address: config.address.clone() });
+        };
+        let invalid_certificate =
+            || ExternalAuthenticationError::LdapCertificateInvalid { path: path.display().to_string() };
+        let pem = fs::read(path).map_err(|source| ExternalAuthenticationError::LdapCertificateUnreadable {
+            path: path.display().to_string(),
+            source: Arc::new(source),
+        })?;
+        let mut roots = RootCertStore::empty();
+        for certificate in CertificateDer::pem_slice_iter(&pem) {
+            roots.add(certificate.map_err(|_| invalid_certificate())?).map_err(|_| invalid_certificate())?;
+        }
+        if roots.is_empty() {
+            return Err(invalid_certificate());
+        }
+        let client_config = ClientConfig::builder().with_root_certificates(roots).with_no_client_auth();
+        Ok(TlsConnector::from(Arc::new(client_config)))
+    }
+
+    fn bind_dn(&self, username: &str) -> String {
+        self.bind_dn_template.replace(LdapConfig::USERNAME_PLACEHOLDER, &escape_dn_value(username))
+    }
+
+    async fn bind(&self, dn: &str, password: &str) -> io::Result<bool> {
+        let mut stream = TcpStream::connect((self.host.as_str(), self.port)).await?;
+        if self.is_start_tls {
+            Self::start_tls(&mut stream).await?;
+        }
+        let server_name = ServerName::try_from(self.host.clone())
+            .map_err(|source| io::Error::new(io::ErrorKind::InvalidInput, source))?;
+        Self::bind_over(self.tls_connector.connect(server_name, stream).await?, dn, password).await
+    }
+
+    // Asks the directory to continue the connection over TLS (RFC 4511, section 4.14)
+    async fn start_tls(stream: &mut (impl AsyncRead + AsyncWrite + Unpin)) -> io::Result<()> {
+        stream.write_all(&ber::start_tls_request()).await?;
+        let response = ber::read_element(stream).await?;
+        match ber::result_code(&response, ber::EXTENDED_RESPONSE)? {
+            ber::RESULT_SUCCESS => Ok(()),
+            result_code => Err(io::Error::new(
+                io::ErrorKind::ConnectionRefused,
+                format!("the directory refused StartTLS with result code {result_code}"),
+            )),
+        }
+    }
+
+    async fn bind_over(mut stream: impl AsyncRead + AsyncWrite + Unpin, dn: &str, password: &str) -> io::Result<bool> {
+        stream.write_all(&ber::bind_request(dn, password)).await?;
+        let response = ber::read_element(&mut stream).await?;
+        let result_code = ber::result_code(&response, ber::BIND_RESPONSE)?;
+        // The connection is closed either way, so a failed unbind is of no consequence
+        let _ = stream.write_all(&ber::unbind_request()).await;
+        Ok(result_code == ber::RESULT_SUCCESS)
+    }
+}
+
+#[async_trait]
+impl CredentialVerifier for LdapCredentialVerifier {
+    async fn verify_password(&self, username: &str, password: &str) -> Result<(), AuthenticationError> {
+        // Directories treat a bind without a password as an anonymous bind, which succeeds for any name
+        if password.is_empty() {
+            return Err(AuthenticationError::InvalidCredential {});
+        }
+        match timeout(self.timeout, self.bind(&self.bind_dn(username), password)).await {
+            Ok(Ok(true)) => Ok(()),
+            Ok(Ok(false)) => Err(AuthenticationError::InvalidCredential {}),
+            Ok(Err(error)) => {
+                event!(
+                    Level::WARN,
+                    "Could not verify the password of user '{username}' at '{}': {error}",
+                    self.address
+                );
+                Err(AuthenticationError::InvalidCredential {})
+            }
+            Err(_) => {
+                event!(Level::WARN, "Timed out verifying the password of user '{username}' at '{}'", self.address);
+                Err(AuthenticationError::InvalidCredential {})
+            }
+        }
+    }
+}
+
+impl fmt::Debug for LdapCredentialVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LdapCredentialVerifier")
+            .field("address", &self.address)
+            .field("bind_dn_template", &self.bind_dn_template)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+// Escapes the characters with a special meaning in distinguished names (RFC 4514)
+fn escape_dn_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    let last = value.chars().count().saturating_sub(1);
+    for (index, char) in value.chars().enumerate() {
+        match char {
+            ',' | '+' | '"' | '\\' | '<' | '>' | ';' | '=' => escaped.push('\\'),
+            '#' | ' ' if index == 0 => escaped.push('\\'),
+            ' ' if index == last => escaped.push('\\'),
+            _ => (),
+        }
+        if char == '\0' {
+            escaped.push_str("\\00");
+        } else {
+            escaped.push(char);
+        }
+    }
+    escaped
+}
+
+// The subset of the BER encoding of LDAP messages (RFC 4511) needed to bind and start TLS
+mod ber {
+    use std::io;
+
+    use tokio::io::{AsyncRead, AsyncReadExt};
+
+    pub(super) const INTEGER: u8 = 0x02;
+    pub(super) const OCTET_STRING: u8 = 0x04;
+    pub(super) const ENUMERATED: u8 = 0x0a;
+    pub(super) const SEQUENCE: u8 = 0x30;
+    pub(super) const BIND_REQUEST: u8 = 0x60;
+    pub(super) const BIND_RESPONSE: u8 = 0x61;
+    pub(super) const UNBIND_REQUEST: u8 = 0x42;
+    pub(super) const EXTENDED_REQUEST: u8 = 0x77;
+    pub(super) const EXTENDED_RESPONSE: u8 = 0x78;
+    pub(super) const SIMPLE_AUTHENTICATION: u8 = 0x80;
+    pub(super) const EXTENDED_REQUEST_NAME: u8 = 0x80;
+    pub(super) const START_TLS_OID: &str = "1.3.6.1.4.1.1466.20037";
+    pub(super) const RESULT_SUCCESS: u32 = 0;
+
+    const LDAP_VERSION: u8 = 3;
+    const START_TLS_MESSAGE_ID: u8 = 1;
+    const BIND_MESSAGE_ID: u8 = 2;
+    const UNBIND_MESSAGE_ID: u8 = 3;
+    const MAX_ELEMENT_LENGTH: usize = 1 << 16;
+
+    pub(super) fn element(tag: u8, content: &[u8]) -> Vec<u8> {
+        let mut encoded = vec![tag];
+        if content.len() < 0x80 {
+            encoded.push(content.len() as u8);
+        } else {
+            let length = content.len().to_be_bytes();
+            let significant = &length[length.iter().take_while(|byte| **byte == 0).count()..];
+            encoded.push(0x80 | significant.len() as u8);
+            encoded.extend_from_slice(significant);
+        }
+        encoded.extend_from_slice(content);
+        encoded
+    }
+
+    fn message(message_id: u8, operation: Vec<u8>) -> Vec<u8> {
+        let mut content = element(INTEGER, &[message_id]);
+        content.extend(operation);
+        element(SEQUENCE, &content)
+    }
+
+    pub(super) fn start_tls_request() -> Vec<u8> {
+        message(
+            START_TLS_MESSAGE_ID,
+            element(EXTENDED_REQUEST, &element(EXTENDED_REQUEST_NAME, START_TLS_OID.as_bytes())),
+        )
+    }
+
+    #[cfg(test)]
+    pub(super) fn start_tls_response(result_code: u8) -> Vec<u8> {
+        let mut response = element(ENUMERATED, &[result_code]);
+        response.extend(element(OCTET_STRING, &[]));
+        response.extend(element(OCTET_STRING, &[]));
+        message(START_TLS_MESSAGE_ID, element(EXTENDED_RESPONSE, &response))
+    }
+
+    pub(super) fn bind_request(dn: &str, password: &str) -> Vec<u8> {
+        let mut bind = element(INTEGER, &[LDAP_VERSION]);
+        bind.extend(element(OCTET_STRING, dn.as_bytes()));
+        bind.extend(element(SIMPLE_AUTHENTICATION, password.as_bytes()));
+        message(BIND_MESSAGE_ID, element(BIND_REQUEST, &bind))
+    }
+
+    #[cfg(test)]
+    pub(super) fn bind_response(result_code: u8) -> Vec<u8> {
+        let mut response = element(ENUMERATED, &[result_code]);
+        response.extend(element(OCTET_STRING, &[]));
+        response.extend(element(OCTET_STRING, &[]));
+        message(BIND_MESSAGE_ID, element(BIND_RESPONSE, &response))
+    }
+
+    pub(super) fn unbind_request() -> Vec<u8> {
+        message(UNBIND_MESSAGE_ID, element(UNBIND_REQUEST, &[]))
+    }
+
+    fn invalid(reason: &str) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, format!("malformed LDAP message: {reason}"))
+    }
+
+    // Reads one complete element, returning its tag and content
+    pub(super) async fn read_element(stream: &mut (impl AsyncRead + Unpin)) -> io::Result<(u8, Vec<u8>)> {
+        let tag = stream.read_u8().await?;
+        let first_length_byte = stream.read_u8().await?;
+        let length = if first_length_byte < 0x80 {
+            first_length_byte as usize
+        } else {
+            let length_bytes = (first_length_byte & 0x7f) as usize;
+            if length_bytes == 0 || length_bytes > size_of::<u32>() {
+                return Err(invalid("unsupported length"));
+            }
+            let mut length = 0usize;
+            for _ in 0..length_bytes {
+                length = (length << 8) | stream.read_u8().await? as usize;
+            }
+            length
+        };
+        if length > MAX_ELEMENT_LENGTH {
+            return Err(invalid("message too long"));
+        }
+        let mut content = vec![0; length];
+        stream.read_exact(&mut content).await?;
+        Ok((tag, content))
+    }
+
+    // Splits the first element off the given bytes, returning its tag, content and the remaining bytes
+    pub(super) fn split_element(bytes: &[u8]) -> io::Result<(u8, &[u8], &[u8])> {
+        let [tag, first_length_byte, rest @ ..] = bytes else {
+            return Err(invalid("truncated element"));
+        };
+        let (length, rest) = if *first_length_byte < 0x80 {
+            (*first_length_byte as usize, rest)
+        } else {
+            let length_bytes = (first_length_byte & 0x7f) as usize;
+            if length_bytes == 0 || length_bytes > size_of::<u32>() || rest.len() < length_bytes {
+                return Err(invalid("unsupported length"));
+            }
+            let length = rest[..length_bytes].iter().fold(0usize, |length, byte| (length << 8) | *byte as usize);
+            (length, &rest[length_bytes..])
+        };
+        if rest.len() < length {
+            return Err(invalid("truncated element"));
+        }
+        Ok((*tag, &rest[..length], &rest[length..]))
+    }
+
+    // The result code of a response with the given tag (RFC 4511, section 4.1.9)
+    pub(super) fn result_code((tag, content): &(u8, Vec<u8>), response_tag: u8) -> io::Result<u32> {
+        if *tag != SEQUENCE {
+            return Err(invalid("expected a message"));
+        }
+        let (INTEGER, _, rest) = split_element(content)? else {
+            return Err(invalid("expected a message id"));
+        };
+        let (tag, response, _) = split_element(rest)?;
+        if tag != response_tag {
+            return Err(invalid("unexpected response"));
+        }
+        let (ENUMERATED, result_code, _) = split_element(response)? else {
+            return Err(invalid("expected a result code"));
+        };
+        if result_code.is_empty() || result_code.len() > size_of::<u32>() {
+            return Err(invalid("unsupported result code"));
+        }
+        Ok(result_code.iter().fold(0, |code, byte| (code << 8) | *byte as u32))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt, DuplexStream, duplex},
+        net::TcpListener,
+    };
+    use tokio_rustls::{
+        TlsConnector,
+        rustls::{ClientConfig, RootCertStore},
+    };
+
+    use super::{LdapCredentialVerifier, ber, escape_dn_value};
+    use crate::{
+        authentication::{AuthenticationError, credential_verifier::CredentialVerifier},
+        parameters::config::LdapConfig,
+    };
+
+    const DN: &str = "uid=alice,ou=people,dc=example,dc=org";
+    const INVALID_CREDENTIALS: u8 = 49;
+    const PROTOCOL_ERROR: u8 = 2;
+
+    // A stand-in directory answering a single bind on the other end of `stream`, which succeeds only for the given
+    // name and password
+    async fn answer_bind(mut stream: DuplexStream, expected_password: &'static str) {
+        let (_, message) = ber::read_element(&mut stream).await.unwrap();
+        let (_, _, rest) = ber::split_element(&message).unwrap();
+        let (_, bind, _) = ber::split_element(rest).unwrap();
+        let (_, _, rest) = ber::split_element(bind).unwrap();
+        let (_, dn, rest) = ber::split_element(rest).unwrap();
+        let (_, password, _) = ber::split_element(rest).unwrap();
+        let is_valid = dn == DN.as_bytes() && password == expected_password.as_bytes();
+        let result_code = if is_valid { ber::RESULT_SUCCESS as u8 } else { INVALID_CREDENTIALS };
+        stream.write_all(&ber::bind_response(result_code)).await.unwrap();
+    }
+
+    // A verifier of the directory at `address` that trusts no certificate, so no TLS handshake can succeed
+    fn verifier(address: &str) -> LdapCredentialVerifier {
+        let client_config =
+            ClientConfig::builder().with_root_certificates(RootCertStore::empty()).with_no_client_auth();
+        let (host, port) = address.trim_start_matches("ldap://").rsplit_once(':').unwrap();
+        LdapCredentialVerifier {
+            address: address.to_string(),
+            host: host.to_string(),
+            port: port.parse().unwrap(),
+            is_start_tls: true,
+            tls_connector: TlsConnector::from(Arc::new(client_config)),
+            bind_dn_template: "uid={username},ou=people,dc=example,dc=org".to_string(),
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    #[tokio::test]
+    async fn bind_with_correct_password_succeeds() {
+        let (client, server) = duplex(1024);
+        tokio::spawn(answer_bind(server, "secret"));
+        assert!(LdapCredentialVerifier::bind_over(client, DN, "secret").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn bind_with_wrong_password_fails() {
+        let (client, server) = duplex(1024);
+        tokio::spawn(answer_bind(server, "secret"));
+        assert!(!LdapCredentialVerifier::bind_over(client, DN, "guess").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn passwords_are_not_sent_if_the_directory_refuses_start_tls() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = format!("ldap://{}", listener.local_addr().unwrap());
+        let directory = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let (_, message) = ber::read_element(&mut stream).await.unwrap();
+            let (_, _, rest) = ber::split_element(&message).unwrap();
+            let (tag, request, _) = ber::split_element(rest).unwrap();
+            assert_eq!(tag, ber::EXTENDED_REQUEST);
+            let (_, request_name, _) = ber::split_element(request).unwrap();
+            assert_eq!(request_name, ber::START_TLS_OID.as_bytes());
+            stream.write_all(&ber::start_tls_response(PROTOCOL_ERROR)).await.unwrap();
+            let mut received = Vec::new();
+            stream.read_to_end(&mut received).await.unwrap();
+            received
+        });
+
+        let result = verifier(&address).verify_password("alice", "secret").await;
+        assert!(matches!(result, Err(AuthenticationError::InvalidCredential { .. })));
+        assert!(directory.await.unwrap().is_empty(), "Nothing may be sent after StartTLS is refused");
+    }
+
+    #[tokio::test]
+    async fn empty_password_is_rejected_without_binding() {
+        // Nothing listens on the address, so any bind attempt would fail differently
+        let result = verifier("ldap://127.0.0.1:1").verify_password("alice", "").await;
+        assert!(matches!(result, Err(AuthenticationError::InvalidCredential { .. })));
+    }
+
+    #[test]
+    fn special_characters_in_usernames_are_escaped() {
+        assert_eq!(escape_dn_value("alice"), "alice");
+        assert_eq!(escape_dn_value("a,ou=admins"), "a\\,ou\\=admins");
+        assert_eq!(escape_dn_value("#alice "), "\\#alice\\ ");
+    }
+
+    #[test]
+    fn addresses_without_ldap_scheme_are_rejected() {
+        let config = LdapConfig { address: "127.0.0.1:389".to_string(), ..LdapConfig::default() };
+        assert!(LdapCredentialVerifier::new(&config).is_err());
+    }
+
+    #[test]
+    fn a_ca_certificate_is_required_with_and_without_start_tls() {
+        for address in ["ldap://127.0.0.1", "ldaps://127.0.0.1"] {
+            let config = LdapConfig { address: address.to_string(), ..LdapConfig::default() };
+            assert!(LdapCredentialVerifier::new(&config).is_err(), "{address} requires a CA certificate");
+        }
+    }
+}
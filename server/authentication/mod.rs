@@ -13,10 +13,14 @@ use axum_extra::{
 use error::typedb_error;
 use http::Extensions;
 use tonic::metadata::MetadataMap;
+use user::errors::UserPasswordStateError;
 
 use crate::state::ServerState;
 
-pub(crate) mod credential_verifier;
+pub(crate) mod authorizer;
+pub mod credential_verifier;
+pub(crate) mod ldap;
+pub(crate) mod oidc;
 pub(crate) mod token_manager;
 
 pub const HTTP_AUTHORIZATION_FIELD: &str = "authorization";
@@ -50,20 +54,47 @@ pub(crate) fn extract_metadata_accessor(metadata: &MetadataMap) -> Option<String
 pub(crate) async fn authenticate<T>(
     server_state: Arc<ServerState>,
     request: http::Request<T>,
+) -> Result<http::Request<T>, AuthenticationError> {
+    authenticate_with(server_state, request, false).await
+}
+
+// Also accepts tokens that only let users change their expired password, marking such requests `PasswordChangeOnly`
+pub(crate) async fn authenticate_allowing_password_change<T>(
+    server_state: Arc<ServerState>,
+    request: http::Request<T>,
+) -> Result<http::Request<T>, AuthenticationError> {
+    authenticate_with(server_state, request, true).await
+}
+
+async fn authenticate_with<T>(
+    server_state: Arc<ServerState>,
+    request: http::Request<T>,
+    allows_password_change: bool,
 ) -> Result<http::Request<T>, AuthenticationError> {
     let (mut parts, body) = request.into_parts();
+    let Some(token) = extract_parts_authorization_token(parts.clone()).await else {
+        return Err(AuthenticationError::MissingToken {});
+    };
 
-    match extract_parts_authorization_token(parts.clone()).await {
-        Some(token) => {
-            let accessor =
-                server_state.users().token_get_owner(&token).await.ok_or(AuthenticationError::InvalidToken {})?;
+    if let Some(accessor) = server_state.users().token_get_owner(&token).await {
+        parts.extensions.insert(Accessor(accessor));
+        return Ok(http::Request::from_parts(parts, body));
+    }
+    match server_state.users().password_change_token_get_owner(&token).await {
+        Some(accessor) if allows_password_change => {
             parts.extensions.insert(Accessor(accessor));
+            parts.extensions.insert(PasswordChangeOnly);
             Ok(http::Request::from_parts(parts, body))
         }
-        None => Err(AuthenticationError::MissingToken {}),
+        Some(_) => Err(AuthenticationError::PasswordChangeRequired {}),
+        None => Err(AuthenticationError::InvalidToken {}),
     }
 }
 
+// Marks requests authenticated by a token that only lets its owner change their expired password
+#[derive(Debug, Clone, Copy)]
+pub struct PasswordChangeOnly;
+
 #[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
 pub struct Accessor(pub String);
 
@@ -88,5 +119,8 @@ typedb_error! {
         MissingToken(2, "Missing token (expected as the authorization bearer)."),
         InvalidToken(3, "Invalid token supplied."),
         CorruptedAccessor(4, "Could not identify the mandatory request's accessor. This might be an authentication bug."),
+        UserLocked(5, "Too many failed sign-in attempts. The user is temporarily locked out."),
+        PasswordChangeRequired(6, "The password has expired and must be changed before signing in."),
+        SignInAttemptNotRecorded(7, "The sign-in attempt could not be recorded.", typedb_source: UserPasswordStateError),
     }
 }
@@ -0,0 +1,214 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+use std::{collections::HashMap, fs, sync::Arc};
+
+use async_trait::async_trait;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode, decode_header, jwk::JwkSet};
+use serde_json::Value;
+
+use crate::{
+    authentication::{
+        AuthenticationError,
+        credential_verifier::{CredentialVerifier, ExternalAuthenticationError},
+    },
+    parameters::config::OidcConfig,
+};
+
+// Accepts ID tokens of an OpenID Connect provider in place of the password. Tokens must be signed with
+// one of the keys of the configured JSON Web Key Set, using the algorithm the key is published for, and sign a user in as the local user their groups
+// are mapped to, or as the user named by the username claim if one is configured.
+#[derive(Debug)]
+pub(crate) struct OidcCredentialVerifier {
+    jwks: JwkSet,
+    issuer: Option<String>,
+    audience: Option<String>,
+    username_claim: Option<String>,
+    groups_claim: String,
+    group_mappings: HashMap<String, String>,
+}
+
+impl OidcCredentialVerifier {
+    pub(crate) fn new(config: &OidcConfig) -> Result<Self, ExternalAuthenticationError> {
+        let path = config.jwks_file.as_deref().unwrap_or_else(|| "".as_ref());
+        let jwks = fs::read_to_string(path).map_err(|source| ExternalAuthenticationError::OidcJwksUnreadable {
+            path: path.display().to_string(),
+            source: Arc::new(source),
+        })?;
+        let jwks: JwkSet =
+            serde_json::from_str(&jwks).map_err(|source| ExternalAuthenticationError::OidcJwksInvalid {
+                path: path.display().to_string(),
+                reason: source.to_string(),
+            })?;
+        if jwks.keys.is_empty() {
+            return Err(ExternalAuthenticationError::OidcJwksInvalid {
+                path: path.display().to_string(),
+                reason: "it contains no keys".to_string(),
+            });
+        }
+        Ok(Self {
+            jwks,
+            issuer: config.issuer.clone(),
+            audience: config.audience.clone(),
+            username_claim: config.username_claim.clone(),
+            groups_claim: config.groups_claim.clone(),
+            group_mappings: config.group_mappings.clone(),
+        })
+    }
+
+    fn decode_claims(&self, token: &str) -> Option<HashMap<String, Value>> {
+        let header = decode_header(token).ok()?;
+        let jwk = match &header.kid {
+            Some(kid) => self.jwks.find(kid)?,
+            None if self.jwks.keys.len() == 1 => &self.jwks.keys[0],
+            None => return None,
+        };
+        // The algorithm is taken from the key rather than the token, so a token cannot choose a weaker one
+        let algorithm = jwk.common.key_algorithm?.to_string().parse::<Algorithm>().ok()?;
+        if header.alg != algorithm {
+            return None;
+        }
+        let key = DecodingKey::from_jwk(jwk).ok()?;
+        let mut validation = Validation::new(algorithm);
+        match &self.issuer {
+            Some(issuer) => validation.set_issuer(&[issuer]),
+            None => (),
+        }
+        match &self.audience {
+            Some(audience) => validation.set_audience(&[audience]),
+            None => validation.validate_aud = false,
+        }
+        decode::<HashMap<String, Value>>(token, &key, &validation).ok().map(|data| data.claims)
+    }
+
+    fn is_identity_of(&self, claims: &HashMap<String, Value>, username: &str) -> bool {
+        let is_named = self
+            .username_claim
+            .as_ref()
+            .and_then(|claim| claims.get(claim))
+            .is_some_and(|name| name.as_str() == Some(username));
+        let is_mapped = claims.get(&self.groups_claim).and_then(Value::as_array).is_some_and(|groups| {
+            groups
+                .iter()
+                .filter_map(Value::as_str)
+                .any(|group| self.group_mappings.get(group).is_some_and(|user| user == username))
+        });
+        is_named || is_mapped
+    }
+}
+
+#[async_trait]
+impl CredentialVerifier for OidcCredentialVerifier {
+    async fn verify_password(&self, username: &str, password: &str) -> Result<(), AuthenticationError> {
+        match self.decode_claims(password) {
+            Some(claims) if self.is_identity_of(&claims, username) => Ok(()),
+            _ => Err(AuthenticationError::InvalidCredential {}),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        fs,
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+    use jsonwebtoken::{Algorithm, EncodingKey, Header, encode};
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use super::OidcCredentialVerifier;
+    use crate::{authentication::credential_verifier::CredentialVerifier, parameters::config::OidcConfig};
+
+    const SECRET: &[u8] = b"a shared secret standing in for the identity provider's key";
+    const ISSUER: &str = "https://idp.example.org";
+
+    // Writes a key set holding a single symmetric key, standing in for the provider's published keys
+    fn verifier(username_claim: Option<&str>) -> OidcCredentialVerifier {
+        verifier_of_key(json!({ "kty": "oct", "kid": "test", "alg": "HS256", "k": URL_SAFE_NO_PAD.encode(SECRET) }))
+            .with_username_claim(username_claim)
+    }
+
+    fn verifier_of_key(jwk: serde_json::Value) -> OidcCredentialVerifier {
+        let jwks = json!({ "keys": [jwk] });
+        let jwks_file = std::env::temp_dir().join(format!("typedb-oidc-test-{}.json", Uuid::new_v4()));
+        fs::write(&jwks_file, jwks.to_string()).unwrap();
+        let config = OidcConfig {
+            enabled: true,
+            jwks_file: Some(jwks_file.clone()),
+            issuer: Some(ISSUER.to_string()),
+            audience: None,
+            username_claim: None,
+            groups_claim: "groups".to_string(),
+            group_mappings: HashMap::from([("db-admins".to_string(), "admin".to_string())]),
+        };
+        let verifier = OidcCredentialVerifier::new(&config).unwrap();
+        fs::remove_file(jwks_file).unwrap();
+        verifier
+    }
+
+    impl OidcCredentialVerifier {
+        fn with_username_claim(self, username_claim: Option<&str>) -> Self {
+            Self { username_claim: username_claim.map(str::to_string), ..self }
+        }
+    }
+
+    fn token(claims: serde_json::Value, secret: &[u8]) -> String {
+        token_with_algorithm(claims, secret, Algorithm::HS256)
+    }
+
+    fn token_with_algorithm(claims: serde_json::Value, secret: &[u8], algorithm: Algorithm) -> String {
+        let header = Header { kid: Some("test".to_string()), ..Header::new(algorithm) };
+        encode(&header, &claims, &EncodingKey::from_secret(secret)).unwrap()
+    }
+
+    fn expiry() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() + 600
+    }
+
+    #[tokio::test]
+    async fn tokens_sign_in_as_the_user_their_groups_are_mapped_to() {
+        let verifier = verifier(None);
+        let token = token(json!({ "iss": ISSUER, "exp": expiry(), "groups": ["analysts", "db-admins"] }), SECRET);
+        assert!(verifier.verify_password("admin", &token).await.is_ok());
+        assert!(verifier.verify_password("analyst", &token).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn username_claim_is_only_used_if_configured() {
+        let token = token(json!({ "iss": ISSUER, "exp": expiry(), "preferred_username": "alice" }), SECRET);
+        assert!(verifier(None).verify_password("alice", &token).await.is_err());
+        assert!(verifier(Some("preferred_username")).verify_password("alice", &token).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn tokens_with_invalid_signature_issuer_or_expiry_are_rejected() {
+        let verifier = verifier(None);
+        let claims = json!({ "iss": ISSUER, "exp": expiry(), "groups": ["db-admins"] });
+        assert!(verifier.verify_password("admin", &token(claims, b"another key")).await.is_err());
+
+        let claims = json!({ "iss": "https://elsewhere.example.org", "exp": expiry(), "groups": ["db-admins"] });
+        assert!(verifier.verify_password("admin", &token(claims, SECRET)).await.is_err());
+
+        let claims = json!({ "iss": ISSUER, "exp": 1, "groups": ["db-admins"] });
+        assert!(verifier.verify_password("admin", &token(claims, SECRET)).await.is_err());
+
+        assert!(verifier.verify_password("admin", "password").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn tokens_must_use_the_algorithm_of_their_key() {
+        let claims = json!({ "iss": ISSUER, "exp": expiry(), "groups": ["db-admins"] });
+        let token = token_with_algorithm(claims.clone(), SECRET, Algorithm::HS384);
+        assert!(verifier(None).verify_password("admin", &token).await.is_err());
+
+        let key_without_algorithm = json!({ "kty": "oct", "kid": "test", "k": URL_SAFE_NO_PAD.encode(SECRET) });
+        let token = token(claims, SECRET);
+        assert!(verifier_of_key(key_without_algorithm).verify_password("admin", &token).await.is_err());
+    }
+}
@@ -5,6 +5,8 @@
  */
 use std::{
     collections::HashMap,
+    fs, io,
+    path::Path,
     sync::Arc,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
@@ -15,85 +17,140 @@ use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode}
 use rand::{self, Rng};
 use resource::constants::server::{MAX_AUTHENTICATION_TOKEN_EXPIRATION, MIN_AUTHENTICATION_TOKEN_EXPIRATION};
 use serde::{Deserialize, Serialize};
+use system::concepts::Session;
 use tokio::sync::RwLock;
+use uuid::Uuid;
 
+// Tokens are JWTs carrying a session id. A token is only accepted while its session is known, so
+// revoking a session invalidates the token before it expires. Sessions are cached here and persisted
+// in the system database by the user operator, which restores them on startup.
 #[derive(Clone, Debug)]
 pub struct TokenManager {
-    token_owners: Arc<RwLock<HashMap<String, String>>>,
+    sessions: Arc<RwLock<HashMap<String, Session>>>,
     tokens_expiration_time: Duration,
     secret_key: String,
 }
 
 impl TokenManager {
     const TOKENS_CLEANUP_INTERVAL_MULTIPLIER: u32 = 2;
+    const RANDOM_SIGNING_KEY_LENGTH: usize = 128;
+    const MIN_SIGNING_KEY_LENGTH: usize = 32;
 
     pub fn new(
         tokens_expiration_time: Duration,
+        signing_key_file: Option<&Path>,
         background_tasks: TokioTaskSpawner,
     ) -> Result<Self, TokenManagerError> {
         Self::validate_tokens_expiration_time(tokens_expiration_time)?;
 
-        let token_owners = Arc::new(RwLock::new(HashMap::new()));
-        let token_owners_clone = token_owners.clone();
+        let sessions = Arc::new(RwLock::new(HashMap::new()));
+        let sessions_clone = sessions.clone();
 
-        // We do not specifically aim to use JWT, as we perform additional manual validation
-        // and use local caches (meaning that every server restart invalidates previously generated tokens).
-        // Therefore, it is acceptable to generate random secret keys without exposing or configuring them.
-        // This approach can be changed in the future if needed.
-        let secret_key = Self::random_key();
-        let secret_key_clone = secret_key.clone();
+        // Without a configured signing key, tokens issued before a restart cannot be verified anymore
+        let secret_key = match signing_key_file {
+            Some(path) => Self::read_key(path)?,
+            None => Self::random_key(),
+        };
 
         let tokens_cleanup_interval = tokens_expiration_time * Self::TOKENS_CLEANUP_INTERVAL_MULTIPLIER;
         background_tasks.spawn_interval(
             move || {
-                let token_owners = token_owners_clone.clone();
-                let secret_key = secret_key_clone.clone();
+                let sessions = sessions_clone.clone();
                 async move {
-                    Self::cleanup_expired_tokens(secret_key.as_ref(), token_owners).await;
+                    Self::cleanup_expired_sessions(sessions).await;
                 }
             },
             IntervalTaskParameters::new_no_delay(tokens_cleanup_interval, false),
         );
-        Ok(Self { token_owners, tokens_expiration_time, secret_key })
+        Ok(Self { sessions, tokens_expiration_time, secret_key })
+    }
+
+    pub async fn new_token(&self, username: String) -> (String, Session) {
+        self.issue_token(username, false).await
     }
 
-    pub async fn new_token(&self, username: String) -> String {
-        // Lock earlier to make sure that `issued_at` and the token are unique
-        let mut write_guard = self.token_owners.write().await;
+    // A token that only lets its owner change their expired password
+    pub async fn new_password_change_token(&self, username: String) -> (String, Session) {
+        self.issue_token(username, true).await
+    }
 
+    async fn issue_token(&self, username: String, password_change_only: bool) -> (String, Session) {
         let issued_at = SystemTime::now();
         let expires_at = issued_at + self.tokens_expiration_time;
+        let session = Session::new(
+            Uuid::new_v4().to_string(),
+            username,
+            Self::system_time_to_seconds(issued_at),
+            Self::system_time_to_seconds(expires_at),
+        );
         let claims = Claims {
-            sub: username.clone(),
-            exp: Self::system_time_to_seconds(expires_at),
-            iat: Self::system_time_to_seconds(issued_at),
+            sub: session.username.clone(),
+            exp: session.expires_at,
+            iat: session.issued_at,
+            jti: session.id.clone(),
+            password_change_only,
         };
 
         let token = Self::encode_token(self.secret_key.as_ref(), claims);
-        write_guard.insert(token.clone(), username);
-        token
+        self.sessions.write().await.insert(session.id.clone(), session.clone());
+        (token, session)
     }
 
     pub async fn get_valid_token_owner(&self, token: &str) -> Option<String> {
-        if let Some(claims) = Self::decode_token(self.secret_key.as_ref(), token) {
-            if !Self::is_expired(claims.exp) {
-                return self.token_owners.read().await.get(token).cloned();
+        self.get_valid_claims(token).await.filter(|claims| !claims.password_change_only).map(|claims| claims.sub)
+    }
+
+    pub async fn get_valid_password_change_token_owner(&self, token: &str) -> Option<String> {
+        self.get_valid_claims(token).await.filter(|claims| claims.password_change_only).map(|claims| claims.sub)
+    }
+
+    async fn get_valid_claims(&self, token: &str) -> Option<Claims> {
+        let claims = Self::decode_token(self.secret_key.as_ref(), token)?;
+        if Self::is_expired(claims.exp) {
+            return None;
+        }
+        match self.sessions.read().await.get(&claims.jti) {
+            Some(session) if session.username == claims.sub => Some(claims),
+            _ => None,
+        }
+    }
+
+    pub async fn sessions(&self, username: &str) -> Vec<Session> {
+        let read_guard = self.sessions.read().await;
+        let mut sessions: Vec<Session> = read_guard
+            .values()
+            .filter(|session| session.username == username && !Self::is_expired(session.expires_at))
+            .cloned()
+            .collect();
+        sessions.sort_by_key(|session| session.issued_at);
+        sessions
+    }
+
+    pub async fn contains_session(&self, username: &str, session_id: &str) -> bool {
+        self.sessions.read().await.get(session_id).is_some_and(|session| session.username == username)
+    }
+
+    pub async fn restore_sessions(&self, sessions: impl IntoIterator<Item = Session>) {
+        let mut write_guard = self.sessions.write().await;
+        for session in sessions {
+            if !Self::is_expired(session.expires_at) {
+                write_guard.insert(session.id.clone(), session);
             }
         }
-        None
+    }
+
+    pub async fn revoke_session(&self, session_id: &str) {
+        self.sessions.write().await.remove(session_id);
     }
 
     pub async fn invalidate_user(&self, username: &str) {
-        let mut write_guard = self.token_owners.write().await;
-        write_guard.retain(|_, token_username| token_username != username);
+        let mut write_guard = self.sessions.write().await;
+        write_guard.retain(|_, session| session.username != username);
     }
 
-    async fn cleanup_expired_tokens(secret_key: &[u8], token_owners: Arc<RwLock<HashMap<String, String>>>) {
-        let mut write_guard = token_owners.write().await;
-        write_guard.retain(|token, _| {
-            let Some(claims) = Self::decode_token(secret_key, token) else { return false };
-            !Self::is_expired(claims.exp)
-        });
+    async fn cleanup_expired_sessions(sessions: Arc<RwLock<HashMap<String, Session>>>) {
+        let mut write_guard = sessions.write().await;
+        write_guard.retain(|_, session| !Self::is_expired(session.expires_at));
     }
 
     fn encode_token(secret_key: &[u8], claims: Claims) -> String {
@@ -112,12 +169,37 @@ impl TokenManager {
         time.duration_since(UNIX_EPOCH).expect("Expected duration since Unix epoch").as_secs()
     }
 
+    pub fn now_seconds() -> u64 {
+        Self::system_time_to_seconds(SystemTime::now())
+    }
+
     fn is_expired(token_exp: u64) -> bool {
-        token_exp <= Self::system_time_to_seconds(SystemTime::now())
+        token_exp <= Self::now_seconds()
     }
 
     fn random_key() -> String {
-        rand::thread_rng().sample_iter(&rand::distributions::Alphanumeric).take(128).map(char::from).collect()
+        rand::thread_rng()
+            .sample_iter(&rand::distributions::Alphanumeric)
+            .take(Self::RANDOM_SIGNING_KEY_LENGTH)
+            .map(char::from)
+            .collect()
+    }
+
+    fn read_key(path: &Path) -> Result<String, TokenManagerError> {
+        let key = fs::read_to_string(path)
+            .map_err(|source| TokenManagerError::SigningKeyUnreadable {
+                path: path.display().to_string(),
+                source: Arc::new(source),
+            })?
+            .trim()
+            .to_string();
+        if key.len() < Self::MIN_SIGNING_KEY_LENGTH {
+            return Err(TokenManagerError::SigningKeyTooShort {
+                path: path.display().to_string(),
+                min: Self::MIN_SIGNING_KEY_LENGTH,
+            });
+        }
+        Ok(key)
     }
 
     fn validate_tokens_expiration_time(tokens_expiration_time: Duration) -> Result<(), TokenManagerError> {
@@ -140,10 +222,15 @@ pub(crate) struct Claims {
     sub: String,
     exp: u64,
     iat: u64,
+    jti: String,
+    #[serde(default)]
+    password_change_only: bool,
 }
 
 typedb_error! {
     pub TokenManagerError(component = "Token manager", prefix = "TKM") {
         InvalidTokensExpirationTime(1, "Invalid tokens expiration time '{value}'. It must be between '{min}' and '{max}' seconds.", value: u64, min: u64, max: u64),
+        SigningKeyUnreadable(2, "Could not read the token signing key from '{path}'.", path: String, source: Arc<io::Error>),
+        SigningKeyTooShort(3, "The token signing key in '{path}' is too short. It must be at least {min} bytes long.", path: String, min: usize),
     }
 }
@@ -8,8 +8,11 @@ use std::{fmt::Debug, io, net::SocketAddr, sync::Arc};
 
 use concept::error::ConceptReadError;
 use database::{
-    DatabaseDeleteError, DatabaseOpenError,
+    DatabaseBackupError, DatabaseDeleteError, DatabaseOpenError,
+    change_stream::ChangeStreamError,
     database::DatabaseCreateError,
+    replication::DatabaseReplicationError,
+    statistics::DatabaseStatisticsError,
     transaction::{DataCommitError, SchemaCommitError, TransactionError},
 };
 use error::{TypeDBError, typedb_error};
@@ -17,10 +20,16 @@ use ir::pipeline::FunctionReadError;
 use tokio_rustls::rustls::{
     pki_types::pem::Error as RustlsCertError, server::VerifierBuilderError as RustlsVerifierError,
 };
-use user::errors::{UserCreateError, UserDeleteError, UserGetError, UserUpdateError};
+use user::errors::{
+    UserApiKeyError, UserCreateError, UserDeleteError, UserGetError, UserGrantError, UserPasswordStateError,
+    UserSessionError, UserUpdateError,
+};
 
 use crate::{
-    authentication::{AuthenticationError, token_manager::TokenManagerError},
+    authentication::{
+        AuthenticationError, credential_verifier::ExternalAuthenticationError, token_manager::TokenManagerError,
+    },
+    replication::ReplicationError,
     service::{export_service::DatabaseExportError, import_service::DatabaseImportServiceError},
 };
 
@@ -78,6 +87,10 @@ typedb_error! {
         AddressResolutionFailed(27, "Could not resolve address '{address}'.", address: String, source: Arc<io::Error>),
         AddressResolutionEmpty(28, "Could not resolve address '{address}' to any IP address.", address: String),
         AdminServe(29, "Could not serve admin on {address}.", address: SocketAddr, source: Arc<tonic::transport::Error>),
+        AuditLogOpen(30, "Could not open the audit log in '{path}'.", path: String, source: Arc<io::Error>),
+        ExternalAuthenticationConfiguration(31, "External authentication configuration error.", typedb_source: ExternalAuthenticationError),
+        SlowQueryLogOpen(32, "Could not open the slow query log in '{path}'.", path: String, source: Arc<io::Error>),
+        ReplicationConfiguration(33, "Replication configuration error.", typedb_source: ReplicationError),
     }
 }
 
@@ -105,6 +118,23 @@ typedb_error! {
         DatabaseCommitRecordExistsFailed(20, "Commit record check failed.", typedb_source: DatabaseOpenError),
         NotSupportedByDistribution(21, "Not supported by this distribution: {description}", description: String),
         TransactionOpenFailed(22, "Failed to open transaction.", typedb_source: TransactionError),
+        UserGrantsCannotBeRetrieved(23, "Unable to retrieve user grants.", typedb_source: UserGrantError),
+        UserGrantsCannotBeUpdated(24, "Unable to update user grants.", typedb_source: UserGrantError),
+        DatabaseAccessNotPermitted(25, "The user requires the '{role}' role to perform this operation on database '{name}'.", name: String, role: String),
+        DatabaseCannotBeBackedUp(26, "Unable to back up database.", typedb_source: DatabaseBackupError),
+        DatabaseChangesUnavailable(27, "Unable to read the changes committed to database.", typedb_source: ChangeStreamError),
+        DatabaseReplicationUnavailable(28, "Unable to read the records of database for replication.", typedb_source: DatabaseReplicationError),
+        ReadOnlyReplica(29, "This server is a read-only replica of the server at '{primary_address}', which accepts the operation instead.", primary_address: String),
+        DatabaseStatisticsUnavailable(30, "Unable to read the statistics of database.", typedb_source: DatabaseStatisticsError),
+        UserSessionsCannotBeRetrieved(31, "Unable to retrieve user sessions.", typedb_source: UserSessionError),
+        UserSessionsCannotBeUpdated(32, "Unable to update user sessions.", typedb_source: UserSessionError),
+        UserApiKeysCannotBeRetrieved(33, "Unable to retrieve user API keys.", typedb_source: UserApiKeyError),
+        UserApiKeysCannotBeUpdated(34, "Unable to update user API keys.", typedb_source: UserApiKeyError),
+        UserPasswordStateCannotBeUpdated(35, "Unable to update the user's password state.", typedb_source: UserPasswordStateError),
+        QueryNotFound(36, "Query '{id}' is not running.", id: String),
+        TransactionNotFound(37, "Transaction '{id}' is not open.", id: String),
+        BackupDirectoryNotPermitted(38, "Backups can only be written inside the backup directory '{root}', which '{directory}' is not.", directory: String, root: String),
+        BackupDirectoryUnavailable(39, "Could not access the backup directory '{root}'.", root: String, source: Arc<io::Error>),
     }
 }
 
@@ -117,13 +147,19 @@ impl ServerStateError for LocalServerStateError {
             Self::Unimplemented { .. } | Self::NotSupportedByDistribution { .. } => NotImplemented,
 
             Self::AuthenticationError { typedb_source } => match typedb_source {
-                AuthenticationError::CorruptedAccessor { .. } => Internal,
+                AuthenticationError::CorruptedAccessor { .. }
+                | AuthenticationError::SignInAttemptNotRecorded { .. } => Internal,
                 _ => Unauthenticated,
             },
 
-            Self::OperationNotPermitted { .. } => Forbidden,
+            Self::OperationNotPermitted { .. }
+            | Self::DatabaseAccessNotPermitted { .. }
+            | Self::BackupDirectoryNotPermitted { .. } => Forbidden,
 
-            Self::DatabaseNotFound { .. } | Self::UserNotFound { .. } => NotFound,
+            Self::DatabaseNotFound { .. }
+            | Self::UserNotFound { .. }
+            | Self::QueryNotFound { .. }
+            | Self::TransactionNotFound { .. } => NotFound,
 
             Self::ConceptReadError { .. } | Self::FunctionReadError { .. } => Internal,
 
@@ -137,8 +173,21 @@ impl ServerStateError for LocalServerStateError {
             | Self::UserCannotBeCreated { .. }
             | Self::UserCannotBeUpdated { .. }
             | Self::UserCannotBeDeleted { .. }
+            | Self::UserGrantsCannotBeRetrieved { .. }
+            | Self::UserGrantsCannotBeUpdated { .. }
+            | Self::UserSessionsCannotBeRetrieved { .. }
+            | Self::UserSessionsCannotBeUpdated { .. }
+            | Self::UserApiKeysCannotBeRetrieved { .. }
+            | Self::UserApiKeysCannotBeUpdated { .. }
+            | Self::UserPasswordStateCannotBeUpdated { .. }
             | Self::DatabaseCannotBeCreated { .. }
             | Self::DatabaseCannotBeDeleted { .. }
+            | Self::DatabaseCannotBeBackedUp { .. }
+            | Self::BackupDirectoryUnavailable { .. }
+            | Self::DatabaseChangesUnavailable { .. }
+            | Self::DatabaseReplicationUnavailable { .. }
+            | Self::ReadOnlyReplica { .. }
+            | Self::DatabaseStatisticsUnavailable { .. }
             | Self::DatabaseExport { .. }
             | Self::DatabaseImport { .. } => InvalidRequest,
         }
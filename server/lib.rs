@@ -41,6 +41,7 @@ use crate::{
 pub mod authentication;
 pub mod error;
 pub mod parameters;
+pub mod replication;
 pub mod service;
 pub mod state;
 pub mod status;
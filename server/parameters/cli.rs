@@ -59,6 +59,81 @@ pub struct CLIArgs {
     #[arg(long = "server.authentication.token-expiration-seconds")]
     pub server_authentication_token_expiration_seconds: Option<u64>,
 
+    /// File containing the secret used to sign authentication tokens (at least 32 bytes). Configure it to keep
+    /// clients signed in across restarts. If left out, a random secret is generated on every start
+    #[arg(long = "server.authentication.signing-key-file", value_name = "FILE")]
+    pub server_authentication_signing_key_file: Option<String>,
+
+    /// Minimum number of characters of the passwords users set
+    #[arg(long = "server.authentication.password-policy.min-length")]
+    pub server_authentication_password_policy_min_length: Option<usize>,
+
+    /// Require the passwords users set to contain a lowercase letter
+    #[arg(long = "server.authentication.password-policy.require-lowercase")]
+    pub server_authentication_password_policy_require_lowercase: Option<bool>,
+
+    /// Require the passwords users set to contain an uppercase letter
+    #[arg(long = "server.authentication.password-policy.require-uppercase")]
+    pub server_authentication_password_policy_require_uppercase: Option<bool>,
+
+    /// Require the passwords users set to contain a digit
+    #[arg(long = "server.authentication.password-policy.require-digit")]
+    pub server_authentication_password_policy_require_digit: Option<bool>,
+
+    /// Require the passwords users set to contain a symbol
+    #[arg(long = "server.authentication.password-policy.require-symbol")]
+    pub server_authentication_password_policy_require_symbol: Option<bool>,
+
+    /// Number of a user's most recent passwords, including the current one, that cannot be set again
+    #[arg(long = "server.authentication.password-policy.history-size")]
+    pub server_authentication_password_policy_history_size: Option<usize>,
+
+    /// Number of consecutive failed sign-in attempts after which a user is temporarily locked out.
+    /// Set to 0 to disable lockouts
+    #[arg(long = "server.authentication.lockout.max-failed-attempts")]
+    pub server_authentication_lockout_max_failed_attempts: Option<u64>,
+
+    /// The amount of seconds a user stays locked out after too many failed sign-in attempts
+    #[arg(long = "server.authentication.lockout.duration-seconds")]
+    pub server_authentication_lockout_duration_seconds: Option<u64>,
+
+    /// Enable/disable signing in with the credentials of an LDAP directory, by binding as the user
+    #[arg(long = "server.authentication.ldap.enabled")]
+    pub server_authentication_ldap_enabled: Option<bool>,
+
+    /// Address of the LDAP directory, as ldaps://host:port or ldap://host:port, which is upgraded with StartTLS
+    #[arg(long = "server.authentication.ldap.address")]
+    pub server_authentication_ldap_address: Option<String>,
+
+    /// Distinguished name to bind as, in which '{username}' is replaced by the name of the user signing in
+    #[arg(long = "server.authentication.ldap.bind-dn-template")]
+    pub server_authentication_ldap_bind_dn_template: Option<String>,
+
+    /// Path to the CA certificate used to verify the LDAP directory
+    #[arg(long = "server.authentication.ldap.ca-certificate")]
+    pub server_authentication_ldap_ca_certificate: Option<String>,
+
+    /// Enable/disable signing in with ID tokens of an OpenID Connect provider in place of the password
+    #[arg(long = "server.authentication.oidc.enabled")]
+    pub server_authentication_oidc_enabled: Option<bool>,
+
+    /// Path to the JSON Web Key Set of the OpenID Connect provider
+    #[arg(long = "server.authentication.oidc.jwks-file")]
+    pub server_authentication_oidc_jwks_file: Option<String>,
+
+    /// Issuer that ID tokens are required to have
+    #[arg(long = "server.authentication.oidc.issuer")]
+    pub server_authentication_oidc_issuer: Option<String>,
+
+    /// Audience that ID tokens are required to have
+    #[arg(long = "server.authentication.oidc.audience")]
+    pub server_authentication_oidc_audience: Option<String>,
+
+    /// Enable/disable role-based access control. When enabled, users other than the default admin
+    /// can only access databases they were granted a role on
+    #[arg(long = "server.authorization.enabled")]
+    pub server_authorization_enabled: Option<bool>,
+
     /// Enable/disable in-flight encryption. Specify to enable, or leave out to disable
     #[arg(long = "server.encryption.enabled", action=clap::ArgAction::Set)]
     pub server_encryption_enabled: Option<bool>,
@@ -75,14 +150,51 @@ pub struct CLIArgs {
     #[arg(long = "server.encryption.ca-certificate", value_name = "FILE")]
     pub server_encryption_ca_certificate: Option<String>,
 
+    /// HTTP address of a primary server (e.g., http://10.0.0.1:8000). When set, this server runs as a read-only
+    /// replica that continuously replicates the user databases of the primary
+    #[arg(long = "server.replication.primary-address")]
+    pub server_replication_primary_address: Option<String>,
+
+    /// Username with which a replica authenticates to its primary. The user requires read access to all databases
+    #[arg(long = "server.replication.username")]
+    pub server_replication_username: Option<String>,
+
+    /// Password with which a replica authenticates to its primary
+    #[arg(long = "server.replication.password")]
+    pub server_replication_password: Option<String>,
+
     /// Path to the data directory
     #[arg(long = "storage.data-directory", value_name = "DIR")]
     pub storage_data_directory: Option<String>,
 
+    /// How long old versions remain readable by point-in-time read transactions, in seconds
+    #[arg(long = "storage.history-retention-seconds", value_name = "SECONDS")]
+    pub storage_history_retention_seconds: Option<u64>,
+
+    /// Path to the directory that database backups must be written inside
+    #[arg(long = "storage.backup-directory", value_name = "DIR")]
+    pub storage_backup_directory: Option<String>,
+
     /// Path to the log directory
     #[arg(long = "logging.directory")]
     pub logging_directory: Option<String>,
 
+    /// Enable the audit log of authenticated actions, written under the log directory
+    #[arg(long = "logging.audit.enabled")]
+    pub logging_audit_enabled: Option<bool>,
+
+    /// Enable the log of queries slower than the threshold, written under the log directory with their profiles
+    #[arg(long = "logging.slow-query.enabled")]
+    pub logging_slow_query_enabled: Option<bool>,
+
+    /// Queries taking at least this long are written to the slow query log, in milliseconds
+    #[arg(long = "logging.slow-query.threshold-millis", value_name = "MILLIS")]
+    pub logging_slow_query_threshold_millis: Option<u64>,
+
+    /// Replace the literals of queries written to the slow query log with placeholders
+    #[arg(long = "logging.slow-query.redact-literals")]
+    pub logging_slow_query_redact_literals: Option<bool>,
+
     /// Enable usage metrics reporting
     #[arg(long = "diagnostics.reporting.metrics")]
     pub diagnostics_reporting_metrics: Option<bool>, // used to be `statistics` in 2.x
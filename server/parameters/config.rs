@@ -5,17 +5,25 @@
  */
 
 use std::{
+    collections::HashMap,
     fs::File,
     io::Read,
     path::{Path, PathBuf},
     time::Duration,
 };
 
-use resource::constants::server::{
-    ADMIN_DEFAULT_PORT, DEFAULT_AUTHENTICATION_TOKEN_EXPIRATION, MONITORING_DEFAULT_PORT,
+use diagnostics::LogRotation;
+use resource::constants::{
+    server::{
+        ADMIN_DEFAULT_PORT, AUDIT_LOG_DEFAULT_MAX_FILES, DEFAULT_AUTHENTICATION_LDAP_TIMEOUT,
+        DEFAULT_AUTHENTICATION_LOCKOUT_DURATION, DEFAULT_AUTHENTICATION_TOKEN_EXPIRATION, MONITORING_DEFAULT_PORT,
+        SLOW_QUERY_LOG_DEFAULT_MAX_FILES, SLOW_QUERY_LOG_DEFAULT_THRESHOLD,
+    },
+    storage::{DEFAULT_BACKUP_DIRECTORY, DEFAULT_HISTORY_RETENTION},
 };
 use serde::Deserialize;
-use serde_with::{DurationSeconds, serde_as};
+use serde_with::{DurationMilliSeconds, DurationSeconds, serde_as};
+use user::password_policy::PasswordPolicy;
 
 use crate::parameters::{ConfigError, cli::CLIArgs};
 
@@ -41,7 +49,11 @@ pub struct ServerConfig {
     #[serde(default)]
     pub admin: AdminConfig,
     pub authentication: AuthenticationConfig,
+    #[serde(default)]
+    pub authorization: AuthorizationConfig,
     pub encryption: EncryptionConfig,
+    #[serde(default)]
+    pub replication: ReplicationConfig,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -60,11 +72,174 @@ pub struct AuthenticationConfig {
     #[serde_as(as = "DurationSeconds")]
     #[serde(rename = "token-expiration-seconds")]
     pub token_expiration: Duration,
+    #[serde(default)]
+    pub signing_key_file: Option<PathBuf>,
+    #[serde(default)]
+    pub password_policy: PasswordPolicyConfig,
+    #[serde(default)]
+    pub lockout: LockoutConfig,
+    #[serde(default)]
+    pub ldap: LdapConfig,
+    #[serde(default)]
+    pub oidc: OidcConfig,
 }
 
 impl Default for AuthenticationConfig {
     fn default() -> Self {
-        Self { token_expiration: DEFAULT_AUTHENTICATION_TOKEN_EXPIRATION }
+        Self {
+            token_expiration: DEFAULT_AUTHENTICATION_TOKEN_EXPIRATION,
+            signing_key_file: None,
+            password_policy: PasswordPolicyConfig::default(),
+            lockout: LockoutConfig::default(),
+            ldap: LdapConfig::default(),
+            oidc: OidcConfig::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PasswordPolicyConfig {
+    pub min_length: usize,
+    pub require_lowercase: bool,
+    pub require_uppercase: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+    pub history_size: usize,
+}
+
+impl PasswordPolicyConfig {
+    pub fn to_policy(&self) -> PasswordPolicy {
+        PasswordPolicy {
+            min_length: self.min_length,
+            require_lowercase: self.require_lowercase,
+            require_uppercase: self.require_uppercase,
+            require_digit: self.require_digit,
+            require_symbol: self.require_symbol,
+            history_size: self.history_size,
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct LockoutConfig {
+    // Zero disables lockouts
+    pub max_failed_attempts: u64,
+    #[serde_as(as = "DurationSeconds")]
+    #[serde(rename = "duration-seconds")]
+    pub duration: Duration,
+}
+
+impl Default for LockoutConfig {
+    fn default() -> Self {
+        Self { max_failed_attempts: 0, duration: DEFAULT_AUTHENTICATION_LOCKOUT_DURATION }
+    }
+}
+
+#[serde_as]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct LdapConfig {
+    pub enabled: bool,
+    pub address: String,
+    // '{username}' is replaced by the escaped name of the user signing in
+    pub bind_dn_template: String,
+    #[serde(default)]
+    pub ca_certificate: Option<PathBuf>,
+    #[serde_as(as = "DurationSeconds")]
+    #[serde(rename = "timeout-seconds", default = "LdapConfig::default_timeout")]
+    pub timeout: Duration,
+}
+
+impl LdapConfig {
+    pub const USERNAME_PLACEHOLDER: &'static str = "{username}";
+
+    fn default_timeout() -> Duration {
+        DEFAULT_AUTHENTICATION_LDAP_TIMEOUT
+    }
+}
+
+impl Default for LdapConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            address: "ldap://127.0.0.1:389".to_string(),
+            bind_dn_template: "uid={username},ou=people,dc=example,dc=org".to_string(),
+            ca_certificate: None,
+            timeout: DEFAULT_AUTHENTICATION_LDAP_TIMEOUT,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct OidcConfig {
+    pub enabled: bool,
+    #[serde(default)]
+    pub jwks_file: Option<PathBuf>,
+    #[serde(default)]
+    pub issuer: Option<String>,
+    #[serde(default)]
+    pub audience: Option<String>,
+    // Unset by default: only tokens whose groups are mapped to a user can sign in
+    #[serde(default)]
+    pub username_claim: Option<String>,
+    #[serde(default = "OidcConfig::default_groups_claim")]
+    pub groups_claim: String,
+    // Maps groups of the identity provider to the local users they sign in as
+    #[serde(default)]
+    pub group_mappings: HashMap<String, String>,
+}
+
+impl OidcConfig {
+    fn default_groups_claim() -> String {
+        "groups".to_string()
+    }
+}
+
+impl Default for OidcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            jwks_file: None,
+            issuer: None,
+            audience: None,
+            username_claim: None,
+            groups_claim: Self::default_groups_claim(),
+            group_mappings: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AuthorizationConfig {
+    pub enabled: bool,
+}
+
+impl Default for AuthorizationConfig {
+    fn default() -> Self {
+        Self { enabled: false }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ReplicationConfig {
+    pub primary_address: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+impl ReplicationConfig {
+    pub const HTTPS_SCHEME: &'static str = "https://";
+}
+
+impl Default for ReplicationConfig {
+    fn default() -> Self {
+        Self { primary_address: None, username: None, password: None }
     }
 }
 
@@ -102,10 +277,27 @@ impl Default for AdminConfig {
     }
 }
 
+#[serde_as]
 #[derive(Clone, Debug, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct StorageConfig {
     pub data_directory: PathBuf,
+    #[serde_as(as = "DurationSeconds")]
+    #[serde(rename = "history-retention-seconds", default = "StorageConfig::default_history_retention")]
+    pub history_retention: Duration,
+    // Database backups can only be written inside this directory
+    #[serde(default = "StorageConfig::default_backup_directory")]
+    pub backup_directory: PathBuf,
+}
+
+impl StorageConfig {
+    fn default_history_retention() -> Duration {
+        DEFAULT_HISTORY_RETENTION
+    }
+
+    fn default_backup_directory() -> PathBuf {
+        PathBuf::from(DEFAULT_BACKUP_DIRECTORY)
+    }
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -147,6 +339,49 @@ pub struct Monitoring {
 #[serde(rename_all = "kebab-case")]
 pub struct LoggingConfig {
     pub directory: PathBuf,
+    #[serde(default)]
+    pub audit: AuditLogConfig,
+    #[serde(default)]
+    pub slow_query: SlowQueryLogConfig,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AuditLogConfig {
+    pub enabled: bool,
+    pub rotation: LogRotation,
+    pub max_files: usize,
+}
+
+impl Default for AuditLogConfig {
+    fn default() -> Self {
+        Self { enabled: false, rotation: LogRotation::Daily, max_files: AUDIT_LOG_DEFAULT_MAX_FILES }
+    }
+}
+
+#[serde_as]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SlowQueryLogConfig {
+    pub enabled: bool,
+    #[serde_as(as = "DurationMilliSeconds")]
+    #[serde(rename = "threshold-millis")]
+    pub threshold: Duration,
+    pub redact_literals: bool,
+    pub rotation: LogRotation,
+    pub max_files: usize,
+}
+
+impl Default for SlowQueryLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold: SLOW_QUERY_LOG_DEFAULT_THRESHOLD,
+            redact_literals: true,
+            rotation: LogRotation::Daily,
+            max_files: SLOW_QUERY_LOG_DEFAULT_MAX_FILES,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -257,12 +492,39 @@ impl ConfigBuilder {
             server_admin_enabled,
             server_admin_port,
             server_authentication_token_expiration_seconds,
+            server_authentication_signing_key_file,
+            server_authentication_password_policy_min_length,
+            server_authentication_password_policy_require_lowercase,
+            server_authentication_password_policy_require_uppercase,
+            server_authentication_password_policy_require_digit,
+            server_authentication_password_policy_require_symbol,
+            server_authentication_password_policy_history_size,
+            server_authentication_lockout_max_failed_attempts,
+            server_authentication_lockout_duration_seconds,
+            server_authentication_ldap_enabled,
+            server_authentication_ldap_address,
+            server_authentication_ldap_bind_dn_template,
+            server_authentication_ldap_ca_certificate,
+            server_authentication_oidc_enabled,
+            server_authentication_oidc_jwks_file,
+            server_authentication_oidc_issuer,
+            server_authentication_oidc_audience,
+            server_authorization_enabled,
             server_encryption_enabled,
             server_encryption_certificate,
             server_encryption_certificate_key,
             server_encryption_ca_certificate,
+            server_replication_primary_address,
+            server_replication_username,
+            server_replication_password,
             storage_data_directory,
+            storage_history_retention_seconds,
+            storage_backup_directory,
             logging_directory,
+            logging_audit_enabled,
+            logging_slow_query_enabled,
+            logging_slow_query_threshold_millis,
+            logging_slow_query_redact_literals,
             diagnostics_reporting_metrics,
             diagnostics_reporting_errors,
             diagnostics_monitoring_enabled,
@@ -277,14 +539,41 @@ impl ConfigBuilder {
             config.server.admin.enabled => server_admin_enabled;
             config.server.admin.port => server_admin_port;
             config.server.authentication.token_expiration => server_authentication_token_expiration_seconds.map(|secs| Duration::new(secs, 0));
+            config.server.authentication.signing_key_file => server_authentication_signing_key_file.map(|file| Some(file.into()));
+            config.server.authentication.password_policy.min_length => server_authentication_password_policy_min_length;
+            config.server.authentication.password_policy.require_lowercase => server_authentication_password_policy_require_lowercase;
+            config.server.authentication.password_policy.require_uppercase => server_authentication_password_policy_require_uppercase;
+            config.server.authentication.password_policy.require_digit => server_authentication_password_policy_require_digit;
+            config.server.authentication.password_policy.require_symbol => server_authentication_password_policy_require_symbol;
+            config.server.authentication.password_policy.history_size => server_authentication_password_policy_history_size;
+            config.server.authentication.lockout.max_failed_attempts => server_authentication_lockout_max_failed_attempts;
+            config.server.authentication.lockout.duration => server_authentication_lockout_duration_seconds.map(|secs| Duration::new(secs, 0));
+            config.server.authentication.ldap.enabled => server_authentication_ldap_enabled;
+            config.server.authentication.ldap.address => server_authentication_ldap_address;
+            config.server.authentication.ldap.bind_dn_template => server_authentication_ldap_bind_dn_template;
+            config.server.authentication.ldap.ca_certificate => server_authentication_ldap_ca_certificate.map(|cert| Some(cert.into()));
+            config.server.authentication.oidc.enabled => server_authentication_oidc_enabled;
+            config.server.authentication.oidc.jwks_file => server_authentication_oidc_jwks_file.map(|file| Some(file.into()));
+            config.server.authentication.oidc.issuer => server_authentication_oidc_issuer.map(Some);
+            config.server.authentication.oidc.audience => server_authentication_oidc_audience.map(Some);
+            config.server.authorization.enabled => server_authorization_enabled;
 
             config.server.encryption.enabled => server_encryption_enabled;
             config.server.encryption.certificate => server_encryption_certificate.map(|cert| Some(cert.into()));
             config.server.encryption.certificate_key => server_encryption_certificate_key.map(|cert| Some(cert.into()));
             config.server.encryption.ca_certificate => server_encryption_ca_certificate.map(|cert| Some(cert.into()));
 
+            config.server.replication.username => server_replication_username.map(Some);
+            config.server.replication.password => server_replication_password.map(Some);
+
             config.storage.data_directory => storage_data_directory.map(|p| CLIArgs::resolve_path_from_pwd(Path::new(&p)));
+            config.storage.history_retention => storage_history_retention_seconds.map(|secs| Duration::new(secs, 0));
+            config.storage.backup_directory => storage_backup_directory.map(|p| CLIArgs::resolve_path_from_pwd(Path::new(&p)));
             config.logging.directory => logging_directory.map(|p| CLIArgs::resolve_path_from_pwd(Path::new(&p)));
+            config.logging.audit.enabled => logging_audit_enabled;
+            config.logging.slow_query.enabled => logging_slow_query_enabled;
+            config.logging.slow_query.threshold => logging_slow_query_threshold_millis.map(Duration::from_millis);
+            config.logging.slow_query.redact_literals => logging_slow_query_redact_literals;
 
             config.diagnostics.reporting.report_metrics => diagnostics_reporting_metrics;
             config.diagnostics.reporting.report_errors => diagnostics_reporting_errors;
@@ -296,6 +585,7 @@ impl ConfigBuilder {
         override_optional_config! {
             config.server.advertise_address => server_advertise_address;
             config.server.http.advertise_address => server_http_advertise_address;
+            config.server.replication.primary_address => server_replication_primary_address;
         }
     }
 
@@ -312,8 +602,39 @@ impl ConfigBuilder {
                 message: "Server encryption was enabled, but certificate key was not configured.",
             });
         }
+        let ldap = &config.server.authentication.ldap;
+        if ldap.enabled && !ldap.bind_dn_template.contains(LdapConfig::USERNAME_PLACEHOLDER) {
+            return Err(ConfigError::ValidationError {
+                message: "LDAP authentication was enabled, but the bind DN template does not contain '{username}'.",
+            });
+        }
+        let oidc = &config.server.authentication.oidc;
+        if oidc.enabled && oidc.jwks_file.is_none() {
+            return Err(ConfigError::ValidationError {
+                message: "OIDC authentication was enabled, but the JWKS file was not configured.",
+            });
+        }
+        let replication = &config.server.replication;
+        if let Some(primary_address) = &replication.primary_address {
+            if !primary_address.starts_with(ReplicationConfig::HTTPS_SCHEME) {
+                return Err(ConfigError::ValidationError {
+                    message: "Replication was enabled, but the primary address does not use https.",
+                });
+            }
+            if replication.username.is_none() || replication.password.is_none() {
+                return Err(ConfigError::ValidationError {
+                    message: "Replication was enabled, but the username or password for the primary server was not configured.",
+                });
+            }
+            if config.server.encryption.ca_certificate.is_none() {
+                return Err(ConfigError::ValidationError {
+                    message: "Replication was enabled, but no CA certificate was configured to verify the primary server.",
+                });
+            }
+        }
         // finalise:
         config.storage.data_directory = Self::resolve_path_from_executable(&config.storage.data_directory);
+        config.storage.backup_directory = Self::resolve_path_from_executable(&config.storage.backup_directory);
         config.logging.directory = Self::resolve_path_from_executable(&config.logging.directory);
         config.development_mode.enabled |= Self::IS_DEVELOPMENT_MODE_FORCED;
         Ok(config)
@@ -369,21 +690,56 @@ impl ConfigBuilder {
         self
     }
 
+    pub fn authorization(mut self, config: AuthorizationConfig) -> Self {
+        self.config.server.authorization = config;
+        self
+    }
+
     pub fn encryption(mut self, config: EncryptionConfig) -> Self {
         self.config.server.encryption = config;
         self
     }
 
+    pub fn replication(mut self, config: ReplicationConfig) -> Self {
+        self.config.server.replication = config;
+        self
+    }
+
     pub fn diagnostics(mut self, config: DiagnosticsConfig) -> Self {
         self.config.diagnostics = config;
         self
     }
 
+    pub fn audit_log(mut self, config: AuditLogConfig) -> Self {
+        self.config.logging.audit = config;
+        self
+    }
+
+    pub fn slow_query_log(mut self, config: SlowQueryLogConfig) -> Self {
+        self.config.logging.slow_query = config;
+        self
+    }
+
+    pub fn log_directory(mut self, path: impl AsRef<Path>) -> Self {
+        self.config.logging.directory = path.as_ref().to_path_buf();
+        self
+    }
+
     pub fn data_directory(mut self, path: impl AsRef<Path>) -> Self {
         self.config.storage.data_directory = path.as_ref().to_path_buf();
         self
     }
 
+    pub fn history_retention(mut self, retention: Duration) -> Self {
+        self.config.storage.history_retention = retention;
+        self
+    }
+
+    pub fn backup_directory(mut self, path: impl AsRef<Path>) -> Self {
+        self.config.storage.backup_directory = path.as_ref().to_path_buf();
+        self
+    }
+
     pub fn development_mode(mut self, is_enabled: bool) -> Self {
         self.config.development_mode.enabled = is_enabled;
         self
@@ -392,7 +748,7 @@ impl ConfigBuilder {
 
 #[cfg(test)]
 pub mod tests {
-    use std::path::PathBuf;
+    use std::{path::PathBuf, time::Duration};
 
     use assert as assert_true;
     use clap::Parser;
@@ -438,6 +794,144 @@ pub mod tests {
         assert_eq!(result.server.listen_address.as_str(), set_to);
     }
 
+    #[test]
+    fn audit_log_is_disabled_by_default_and_can_be_enabled() {
+        let config = load_and_parse(config_path(), vec![]).unwrap();
+        assert_true!(!config.logging.audit.enabled);
+
+        let config = load_and_parse(config_path(), vec!["--logging.audit.enabled", "true"]).unwrap();
+        assert_true!(config.logging.audit.enabled);
+    }
+
+    #[test]
+    fn slow_query_log_threshold_can_be_overridden() {
+        let config = load_and_parse(config_path(), vec![]).unwrap();
+        assert_true!(!config.logging.slow_query.enabled);
+        assert_true!(config.logging.slow_query.redact_literals);
+        assert_eq!(config.logging.slow_query.threshold, Duration::from_secs(1));
+
+        let config = load_and_parse(
+            config_path(),
+            vec!["--logging.slow-query.enabled", "true", "--logging.slow-query.threshold-millis", "250"],
+        )
+        .unwrap();
+        assert_true!(config.logging.slow_query.enabled);
+        assert_eq!(config.logging.slow_query.threshold, Duration::from_millis(250));
+    }
+
+    #[test]
+    fn token_signing_key_is_random_by_default_and_can_be_read_from_a_file() {
+        let config = load_and_parse(config_path(), vec![]).unwrap();
+        assert_true!(config.server.authentication.signing_key_file.is_none());
+
+        let key_file = "/etc/typedb/token-signing.key";
+        let config = load_and_parse(config_path(), vec!["--server.authentication.signing-key-file", key_file]).unwrap();
+        assert_eq!(config.server.authentication.signing_key_file, Some(PathBuf::from(key_file)));
+    }
+
+    #[test]
+    fn password_policy_and_lockout_are_permissive_by_default_and_can_be_configured() {
+        let config = load_and_parse(config_path(), vec![]).unwrap();
+        let policy = config.server.authentication.password_policy.to_policy();
+        assert_true!(policy.validate("password").is_ok());
+        assert_eq!(config.server.authentication.lockout.max_failed_attempts, 0);
+
+        let args = vec![
+            "--server.authentication.password-policy.min-length",
+            "12",
+            "--server.authentication.password-policy.require-digit",
+            "true",
+            "--server.authentication.password-policy.history-size",
+            "3",
+            "--server.authentication.lockout.max-failed-attempts",
+            "5",
+            "--server.authentication.lockout.duration-seconds",
+            "60",
+        ];
+        let config = load_and_parse(config_path(), args).unwrap();
+        let policy = config.server.authentication.password_policy.to_policy();
+        assert_true!(policy.validate("password").is_err());
+        assert_true!(policy.validate("correct-horse-battery-staple").is_err());
+        assert_true!(policy.validate("correct-horse-battery-9").is_ok());
+        assert_eq!(policy.history_size, 3);
+        assert_eq!(config.server.authentication.lockout.max_failed_attempts, 5);
+        assert_eq!(config.server.authentication.lockout.duration, Duration::from_secs(60));
+    }
+
+    #[test]
+    fn external_authentication_is_disabled_by_default_and_can_be_configured() {
+        let config = load_and_parse(config_path(), vec![]).unwrap();
+        assert_true!(!config.server.authentication.ldap.enabled);
+        assert_true!(!config.server.authentication.oidc.enabled);
+        assert_eq!(config.server.authentication.oidc.groups_claim, "groups");
+
+        let args = vec![
+            "--server.authentication.ldap.enabled",
+            "true",
+            "--server.authentication.ldap.address",
+            "ldaps://ldap.example.org",
+            "--server.authentication.ldap.ca-certificate",
+            "/etc/typedb/ldap-ca.pem",
+            "--server.authentication.oidc.enabled",
+            "true",
+            "--server.authentication.oidc.jwks-file",
+            "/etc/typedb/jwks.json",
+            "--server.authentication.oidc.issuer",
+            "https://idp.example.org",
+        ];
+        let config = load_and_parse(config_path(), args).unwrap();
+        assert_eq!(config.server.authentication.ldap.address, "ldaps://ldap.example.org");
+        assert_eq!(config.server.authentication.ldap.ca_certificate, Some(PathBuf::from("/etc/typedb/ldap-ca.pem")));
+        assert_eq!(config.server.authentication.oidc.jwks_file, Some(PathBuf::from("/etc/typedb/jwks.json")));
+        assert_eq!(config.server.authentication.oidc.issuer.as_deref(), Some("https://idp.example.org"));
+
+        let args = vec!["--server.authentication.oidc.enabled", "true"];
+        assert_true!(matches!(load_and_parse(config_path(), args), Err(ConfigError::ValidationError { .. })));
+
+        let args = vec![
+            "--server.authentication.ldap.enabled",
+            "true",
+            "--server.authentication.ldap.bind-dn-template",
+            "uid=admin,dc=example,dc=org",
+        ];
+        assert_true!(matches!(load_and_parse(config_path(), args), Err(ConfigError::ValidationError { .. })));
+    }
+
+    #[test]
+    fn replication_is_disabled_by_default_and_can_follow_a_primary() {
+        let config = load_and_parse(config_path(), vec![]).unwrap();
+        assert_true!(config.server.replication.primary_address.is_none());
+
+        assert_true!(config.server.replication.username.is_none());
+        assert_true!(config.server.replication.password.is_none());
+
+        let primary = "https://10.0.0.1:8000";
+        let credentials =
+            ["--server.replication.username", "replicator", "--server.replication.password", "replicator-password"];
+        let ca_certificate = ["--server.encryption.ca-certificate", "/etc/typedb/ca.pem"];
+        let args =
+            [["--server.replication.primary-address", primary].as_slice(), &credentials, &ca_certificate].concat();
+        let config = load_and_parse(config_path(), args).unwrap();
+        assert_eq!(config.server.replication.primary_address.as_deref(), Some(primary));
+        assert_eq!(config.server.replication.username.as_deref(), Some("replicator"));
+        assert_eq!(config.server.replication.password.as_deref(), Some("replicator-password"));
+
+        // Credentials and database contents must not travel in the clear
+        let args = [
+            ["--server.replication.primary-address", "http://10.0.0.1:8000"].as_slice(),
+            &credentials,
+            &ca_certificate,
+        ]
+        .concat();
+        assert_true!(matches!(load_and_parse(config_path(), args), Err(ConfigError::ValidationError { .. })));
+
+        let args = [["--server.replication.primary-address", primary].as_slice(), &ca_certificate].concat();
+        assert_true!(matches!(load_and_parse(config_path(), args), Err(ConfigError::ValidationError { .. })));
+
+        let args = [["--server.replication.primary-address", primary].as_slice(), &credentials].concat();
+        assert_true!(matches!(load_and_parse(config_path(), args), Err(ConfigError::ValidationError { .. })));
+    }
+
     #[test]
     fn config_file_accepts_old_and_new_address_names() {
         // The current config.yml uses the new names (listen-address). Verify it parses correctly
@@ -0,0 +1,530 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::{
+    fmt,
+    fmt::Debug,
+    future::Future,
+    io,
+    path::Path,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use async_trait::async_trait;
+use concurrency::{IntervalTaskParameters, TokioTaskSpawner};
+use database::{
+    database::DatabaseCreateError, database_manager::DatabaseManager, replication::DatabaseReplicationError,
+};
+use durability::RawRecord;
+use error::{TypeDBError, typedb_error};
+use hyper::{
+    Body, Client, Method, Request, StatusCode, Uri,
+    client::{
+        HttpConnector,
+        connect::{Connected, Connection},
+    },
+    header,
+    service::Service,
+};
+use resource::constants::database::{REPLICATION_BATCH_RECORDS, REPLICATION_POLL_INTERVAL};
+use serde::{Serialize, de::DeserializeOwned};
+use storage::sequence_number::SequenceNumber;
+use tokio::{
+    io::{AsyncRead, AsyncWrite, ReadBuf},
+    net::TcpStream,
+    sync::Mutex,
+};
+use tokio_rustls::{
+    TlsConnector,
+    client::TlsStream,
+    rustls::{
+        ClientConfig, RootCertStore,
+        pki_types::{
+            CertificateDer, PrivateKeyDer, ServerName,
+            pem::{Error as PemError, PemObject},
+        },
+    },
+};
+use tracing::{Level, event};
+
+use crate::{
+    authentication::HTTP_BEARER_PREFIX,
+    parameters::config::{EncryptionConfig, ReplicationConfig},
+    service::http::message::{
+        authentication::{SigninPayload, TokenResponse},
+        database::DatabasesResponse,
+        replication::{ReplicationRecordsResponse, decode_replication_records},
+        version::PROTOCOL_VERSION_LATEST,
+    },
+};
+
+/// The server whose write-ahead logs a replica follows.
+#[async_trait]
+pub trait ReplicationSource: Debug + Send + Sync {
+    async fn database_names(&self) -> Result<Vec<String>, ReplicationError>;
+
+    async fn records_from(
+        &self,
+        database_name: &str,
+        start: SequenceNumber,
+    ) -> Result<Vec<RawRecord<'static>>, ReplicationError>;
+
+    /// The most records returned by one call of `records_from`. A shorter batch holds every record available.
+    fn batch_records(&self) -> usize;
+}
+
+/// Reads from a primary through its HTTP endpoint, signing in again whenever the current token is rejected.
+/// Only https is used, so that neither the credentials nor the database contents are sent in the clear.
+#[derive(Debug)]
+pub struct HttpReplicationSource {
+    client: Client<HttpsConnector>,
+    primary_address: String,
+    username: String,
+    password: String,
+    token: Mutex<Option<String>>,
+}
+
+impl HttpReplicationSource {
+    pub fn new(
+        primary_address: String,
+        username: String,
+        password: String,
+        tls_config: ClientConfig,
+    ) -> Result<Self, ReplicationError> {
+        if !primary_address.starts_with(ReplicationConfig::HTTPS_SCHEME) {
+            return Err(ReplicationError::PrimaryAddressNotSecure { address: primary_address });
+        }
+        let primary_address = primary_address.trim_end_matches('/').to_owned();
+        let client = Client::builder().build(HttpsConnector::new(tls_config));
+        Ok(Self { client, primary_address, username, password, token: Mutex::new(None) })
+    }
+
+    /// Trusts the CA certificate of the server's own encryption configuration, so that the servers of a deployment
+    /// verify each other with the same root. The server's certificate is presented in turn whenever encryption is
+    /// enabled, as a primary with a CA certificate configured requires its clients to authenticate.
+    pub fn tls_config(encryption_config: &EncryptionConfig) -> Result<ClientConfig, ReplicationError> {
+        let ca_path = encryption_config.ca_certificate.as_deref().ok_or(ReplicationError::MissingCaCertificate {})?;
+        let unreadable = |path: &Path, source: PemError| ReplicationError::CertificateUnreadable {
+            path: path.display().to_string(),
+            source: Arc::new(source),
+        };
+        let mut roots = RootCertStore::empty();
+        for certificate in CertificateDer::pem_file_iter(ca_path).map_err(|source| unreadable(ca_path, source))? {
+            roots
+                .add(certificate.map_err(|source| unreadable(ca_path, source))?)
+                .map_err(|source| ReplicationError::TlsConfiguration { source: Arc::new(source) })?;
+        }
+        let builder = ClientConfig::builder().with_root_certificates(roots);
+        let (true, Some(certificate_path), Some(key_path)) = (
+            encryption_config.enabled,
+            encryption_config.certificate.as_deref(),
+            encryption_config.certificate_key.as_deref(),
+        ) else {
+            return Ok(builder.with_no_client_auth());
+        };
+        let certificates = CertificateDer::pem_file_iter(certificate_path)
+            .map_err(|source| unreadable(certificate_path, source))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|source| unreadable(certificate_path, source))?;
+        let key = PrivateKeyDer::from_pem_file(key_path).map_err(|source| unreadable(key_path, source))?;
+        builder
+            .with_client_auth_cert(certificates, key)
+            .map_err(|source| ReplicationError::TlsConfiguration { source: Arc::new(source) })
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, ReplicationError> {
+        let mut token = self.token.lock().await;
+        if token.is_none() {
+            *token = Some(self.signin().await?);
+        }
+        let (status, body) = self.send(Method::GET, path, token.as_deref(), None).await?;
+        let (status, body) = if status == StatusCode::UNAUTHORIZED {
+            // tokens expire, so the first rejection is answered by signing in again
+            *token = Some(self.signin().await?);
+            self.send(Method::GET, path, token.as_deref(), None).await?
+        } else {
+            (status, body)
+        };
+        Self::parse(path, status, body)
+    }
+
+    async fn signin(&self) -> Result<String, ReplicationError> {
+        let path = format!("/{}/signin", PROTOCOL_VERSION_LATEST);
+        let payload = SigninPayload { username: self.username.clone(), password: self.password.clone() };
+        let (status, body) = self.send(Method::POST, &path, None, Some(Self::json(&payload))).await?;
+        Self::parse::<TokenResponse>(&path, status, body).map(|response| response.token)
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        token: Option<&str>,
+        json: Option<String>,
+    ) -> Result<(StatusCode, hyper::body::Bytes), ReplicationError> {
+        let uri = format!("{}{}", self.primary_address, path);
+        let mut request = Request::builder().method(method).uri(&uri);
+        if let Some(token) = token {
+            request = request.header(header::AUTHORIZATION, format!("{HTTP_BEARER_PREFIX}{token}"));
+        }
+        let request = match json {
+            Some(json) => request.header(header::CONTENT_TYPE, "application/json").body(Body::from(json)),
+            None => request.body(Body::empty()),
+        }
+        .map_err(|source| ReplicationError::InvalidRequest { uri: uri.clone(), source: Arc::new(source) })?;
+        let response = self
+            .client
+            .request(request)
+            .await
+            .map_err(|source| ReplicationError::PrimaryUnreachable { uri: uri.clone(), source: Arc::new(source) })?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .map_err(|source| ReplicationError::PrimaryUnreachable { uri, source: Arc::new(source) })?;
+        Ok((status, body))
+    }
+
+    fn parse<T: DeserializeOwned>(
+        path: &str,
+        status: StatusCode,
+        body: hyper::body::Bytes,
+    ) -> Result<T, ReplicationError> {
+        if !status.is_success() {
+            return Err(ReplicationError::PrimaryRejected {
+                path: path.to_owned(),
+                status: status.as_u16(),
+                body: String::from_utf8_lossy(&body).into_owned(),
+            });
+        }
+        serde_json::from_slice(&body)
+            .map_err(|source| ReplicationError::InvalidResponse { path: path.to_owned(), source: Arc::new(source) })
+    }
+
+    fn json(payload: &impl Serialize) -> String {
+        serde_json::to_string(payload).expect("Expected request payloads to serialise")
+    }
+}
+
+#[async_trait]
+impl ReplicationSource for HttpReplicationSource {
+    async fn database_names(&self) -> Result<Vec<String>, ReplicationError> {
+        let path = format!("/{}/databases", PROTOCOL_VERSION_LATEST);
+        let response: DatabasesResponse = self.get(&path).await?;
+        Ok(response.databases.into_iter().map(|database| database.name).collect())
+    }
+
+    async fn records_from(
+        &self,
+        database_name: &str,
+        start: SequenceNumber,
+    ) -> Result<Vec<RawRecord<'static>>, ReplicationError> {
+        let path =
+            format!("/{}/databases/{database_name}/replication?from={}", PROTOCOL_VERSION_LATEST, start.number());
+        let response: ReplicationRecordsResponse = self.get(&path).await?;
+        decode_replication_records(response)
+            .map_err(|source| ReplicationError::InvalidRecord { name: database_name.to_owned(), source })
+    }
+
+    fn batch_records(&self) -> usize {
+        REPLICATION_BATCH_RECORDS
+    }
+}
+
+/// Opens TLS connections for the replication client, refusing any address that is not https.
+#[derive(Clone)]
+struct HttpsConnector {
+    http_connector: HttpConnector,
+    tls_connector: TlsConnector,
+}
+
+impl HttpsConnector {
+    fn new(tls_config: ClientConfig) -> Self {
+        let mut http_connector = HttpConnector::new();
+        http_connector.enforce_http(false);
+        Self { http_connector, tls_connector: TlsConnector::from(Arc::new(tls_config)) }
+    }
+}
+
+impl Debug for HttpsConnector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpsConnector").finish_non_exhaustive()
+    }
+}
+
+impl Service<Uri> for HttpsConnector {
+    type Response = HttpsStream;
+    type Error = io::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.http_connector.poll_ready(cx).map_err(io::Error::other)
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let mut http_connector = self.http_connector.clone();
+        let tls_connector = self.tls_connector.clone();
+        Box::pin(async move {
+            if uri.scheme_str() != Some("https") {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("Refusing non-https address '{uri}'")));
+            }
+            let host = uri.host().unwrap_or_default().trim_start_matches('[').trim_end_matches(']').to_owned();
+            let server_name =
+                ServerName::try_from(host).map_err(|source| io::Error::new(io::ErrorKind::InvalidInput, source))?;
+            let stream = http_connector.call(uri).await.map_err(io::Error::other)?;
+            Ok(HttpsStream(tls_connector.connect(server_name, stream).await?))
+        })
+    }
+}
+
+struct HttpsStream(TlsStream<TcpStream>);
+
+impl Connection for HttpsStream {
+    fn connected(&self) -> Connected {
+        self.0.get_ref().0.connected()
+    }
+}
+
+impl AsyncRead for HttpsStream {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().0).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for HttpsStream {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().0).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().0).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().0).poll_shutdown(cx)
+    }
+}
+
+/// Keeps the user databases of a read-only replica up to date with those of its primary. Databases created on the
+/// primary are created on the replica as they are discovered, while deleted databases are left in place.
+#[derive(Debug)]
+pub struct ReplicationFollower {
+    source: Arc<dyn ReplicationSource>,
+    database_manager: Arc<DatabaseManager>,
+}
+
+impl ReplicationFollower {
+    pub fn new(source: Arc<dyn ReplicationSource>, database_manager: Arc<DatabaseManager>) -> Self {
+        Self { source, database_manager }
+    }
+
+    pub fn from_config(
+        config: &ReplicationConfig,
+        encryption_config: &EncryptionConfig,
+        database_manager: Arc<DatabaseManager>,
+    ) -> Result<Option<Self>, ReplicationError> {
+        let Some(primary_address) = config.primary_address.clone() else {
+            return Ok(None);
+        };
+        let (Some(username), Some(password)) = (config.username.clone(), config.password.clone()) else {
+            return Err(ReplicationError::MissingCredentials {});
+        };
+        let tls_config = HttpReplicationSource::tls_config(encryption_config)?;
+        let source = HttpReplicationSource::new(primary_address, username, password, tls_config)?;
+        Ok(Some(Self::new(Arc::new(source), database_manager)))
+    }
+
+    pub fn start(self, spawner: &TokioTaskSpawner) {
+        let follower = Arc::new(self);
+        spawner.spawn_interval(
+            move || {
+                let follower = follower.clone();
+                async move { follower.replicate_all().await }
+            },
+            IntervalTaskParameters::new_no_delay(REPLICATION_POLL_INTERVAL, false),
+        );
+    }
+
+    pub async fn replicate_all(&self) {
+        let names = match self.source.database_names().await {
+            Ok(names) => names,
+            Err(err) => {
+                event!(
+                    Level::WARN,
+                    "Failed to list the databases of the primary server: {}",
+                    err.format_code_and_description()
+                );
+                return;
+            }
+        };
+        for name in names.iter().filter(|name| DatabaseManager::is_user_database(name)) {
+            if let Err(err) = self.replicate(name).await {
+                event!(Level::WARN, "Failed to replicate database '{name}': {}", err.format_code_and_description());
+            }
+        }
+    }
+
+    /// Applies the records of the primary until the replica has caught up with it. Records of commits whose status
+    /// is not yet part of the fetched records are kept, and the following batches are appended to them, until a
+    /// batch shorter than the source's limit shows that the primary has no further records.
+    pub async fn replicate(&self, name: &str) -> Result<(), ReplicationError> {
+        let database = match self.database_manager.database(name) {
+            Some(database) => database,
+            None => {
+                let database_manager = self.database_manager.clone();
+                let database_name = name.to_owned();
+                tokio::task::spawn_blocking(move || database_manager.put_database(database_name))
+                    .await
+                    .expect("Replica database creation task panicked")
+                    .map_err(|typedb_source| ReplicationError::DatabaseCreate { typedb_source })?;
+                self.database_manager.database(name).expect("Expected the created replica database")
+            }
+        };
+        let mut records: Vec<RawRecord<'static>> = Vec::new();
+        loop {
+            let start = records.last().map_or_else(|| database.replication_position(), |record| record.sequence_number);
+            let batch = self.source.records_from(name, start).await?;
+            let is_last_batch = batch.len() < self.source.batch_records();
+            // the batch starts with every record at `start`, some of which are already held
+            let held_at_start = records.iter().rev().take_while(|record| record.sequence_number == start).count();
+            let held_count = records.len();
+            records.extend(batch.into_iter().skip(held_at_start));
+            if records.len() == held_count {
+                // the primary has no records beyond those already held
+                return Ok(());
+            }
+
+            let replica = database.clone();
+            let batch = records.clone();
+            let position = tokio::task::spawn_blocking(move || replica.replicate(batch))
+                .await
+                .expect("Database replication task panicked")
+                .map_err(|typedb_source| ReplicationError::DatabaseReplication { typedb_source })?;
+            records.retain(|record| record.sequence_number >= position);
+            if is_last_batch {
+                // any records left belong to commits not yet validated on the primary
+                return Ok(());
+            }
+        }
+    }
+}
+
+typedb_error! {
+    pub ReplicationError(component = "Replication", prefix = "SRP") {
+        InvalidRequest(1, "Could not build the request to '{uri}'.", uri: String, source: Arc<hyper::http::Error>),
+        PrimaryUnreachable(2, "Could not reach the primary server at '{uri}'.", uri: String, source: Arc<hyper::Error>),
+        PrimaryRejected(3, "The primary server rejected the request to '{path}' with status {status}: {body}", path: String, status: u16, body: String),
+        InvalidResponse(4, "Could not parse the response of the primary server to '{path}'.", path: String, source: Arc<serde_json::Error>),
+        InvalidRecord(5, "Received an invalid replication record for database '{name}'.", name: String, source: base64::DecodeError),
+        DatabaseCreate(6, "Could not create the replica database.", typedb_source: DatabaseCreateError),
+        DatabaseReplication(7, "Could not apply the records replicated from the primary server.", typedb_source: DatabaseReplicationError),
+        PrimaryAddressNotSecure(8, "The primary server address '{address}' does not use https.", address: String),
+        MissingCredentials(9, "Replication requires the username and password of a user on the primary server."),
+        MissingCaCertificate(10, "Replication requires the server encryption CA certificate, to verify the primary server."),
+        CertificateUnreadable(11, "Could not read '{path}' to connect to the primary server.", path: String, source: Arc<PemError>),
+        TlsConfiguration(12, "Could not configure TLS for connections to the primary server.", source: Arc<tokio_rustls::rustls::Error>),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use database::{
+        Database,
+        database_manager::DatabaseManager,
+        query::{execute_schema_query, execute_write_query_in_write},
+        transaction::{TransactionSchema, TransactionWrite},
+    };
+    use durability::RawRecord;
+    use executor::ExecutionInterrupt;
+    use options::{QueryOptions, TransactionOptions};
+    use storage::{durability_client::WALClient, sequence_number::SequenceNumber};
+    use test_utils::create_tmp_storage_dir;
+
+    use super::{ReplicationError, ReplicationFollower, ReplicationSource};
+
+    const DATABASE_NAME: &str = "people";
+
+    #[derive(Debug)]
+    struct DatabaseReplicationSource {
+        primary: Arc<Database<WALClient>>,
+        batch_records: usize,
+    }
+
+    #[async_trait]
+    impl ReplicationSource for DatabaseReplicationSource {
+        async fn database_names(&self) -> Result<Vec<String>, ReplicationError> {
+            Ok(vec![DATABASE_NAME.to_owned()])
+        }
+
+        async fn records_from(
+            &self,
+            _: &str,
+            start: SequenceNumber,
+        ) -> Result<Vec<RawRecord<'static>>, ReplicationError> {
+            Ok(self.primary.replication_records_from(start, self.batch_records).unwrap())
+        }
+
+        fn batch_records(&self) -> usize {
+            self.batch_records
+        }
+    }
+
+    #[tokio::test]
+    async fn catch_up_continues_past_batches_that_end_exactly_on_the_limit() {
+        let primary_path = create_tmp_storage_dir();
+        let primary_manager = DatabaseManager::new(&primary_path).unwrap();
+        primary_manager.put_database(DATABASE_NAME).unwrap();
+        let primary = primary_manager.database(DATABASE_NAME).unwrap();
+        define_schema(&primary, "define attribute name value string; entity person owns name;");
+        for name in ["alice", "bob", "carol"] {
+            write(&primary, &format!(r#"insert $p isa person, has name "{name}";"#));
+        }
+
+        for batch_records in [2, 3, usize::MAX] {
+            let replica_path = create_tmp_storage_dir();
+            let replica_manager = Arc::new(DatabaseManager::new(&replica_path).unwrap());
+            replica_manager.set_user_databases_read_only(true);
+            replica_manager.put_database(DATABASE_NAME).unwrap();
+            let replica = replica_manager.database(DATABASE_NAME).unwrap();
+            let records = primary.replication_records_from(replica.replication_position(), usize::MAX).unwrap();
+            // the last source returns every record in a single batch that is exactly full
+            let batch_records = batch_records.min(records.len());
+
+            let source = DatabaseReplicationSource { primary: primary.clone(), batch_records };
+            let follower = ReplicationFollower::new(Arc::new(source), replica_manager.clone());
+            follower.replicate(DATABASE_NAME).await.unwrap();
+            assert_eq!(replica.replication_position(), primary.replication_position(), "batch of {batch_records}");
+        }
+    }
+
+    fn define_schema(database: &Arc<Database<WALClient>>, query: &str) {
+        let schema_query = typeql::parse_query(query).unwrap().into_structure().into_schema();
+        let tx = TransactionSchema::open(database.clone(), TransactionOptions::default()).unwrap();
+        let (tx, result) = execute_schema_query(tx, schema_query, query.to_string());
+        result.unwrap();
+        let (mut profile, intent) = tx.finalise();
+        intent.unwrap().commit(profile.commit_profile()).unwrap();
+    }
+
+    fn write(database: &Arc<Database<WALClient>>, query: &str) {
+        let tx = TransactionWrite::open(database.clone(), TransactionOptions::default()).unwrap();
+        let pipeline = typeql::parse_query(query).unwrap().into_structure().into_pipeline();
+        let (tx, result) = execute_write_query_in_write(
+            tx,
+            QueryOptions::default_grpc(),
+            pipeline,
+            query.to_string(),
+            ExecutionInterrupt::new_uninterruptible(),
+        );
+        result.unwrap();
+        let (mut profile, intent) = tx.finalise();
+        intent.unwrap().commit(profile.commit_profile()).unwrap();
+    }
+}
@@ -4,11 +4,20 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use std::sync::Arc;
+use std::{collections::BTreeMap, path::PathBuf, sync::Arc, time::Duration};
 
+use database::statistics::{DatabaseStatistics, LabelledCounts};
+use resource::constants::server::DEFAULT_USER_NAME;
+use system::concepts::{ApiKey, Grant, Role, Session};
 use tonic::{Request, Response, Status};
+use uuid::Uuid;
 
-use crate::{admin_proto, state::ServerState};
+use crate::{
+    admin_proto,
+    authentication::Accessor,
+    service::IntoGrpcStatus,
+    state::{OpenTransactionInfo, RunningQueryInfo, ServerState},
+};
 
 #[derive(Debug, Clone)]
 pub struct AdminService {
@@ -23,6 +32,56 @@ impl AdminService {
     pub fn server_state(&self) -> &Arc<ServerState> {
         &self.server_state
     }
+
+    // The admin endpoint only serves localhost, so its callers act with the default user's privileges
+    fn accessor() -> Accessor {
+        Accessor(DEFAULT_USER_NAME.to_string())
+    }
+
+    fn decode_grant(grant: Option<admin_proto::Grant>) -> Result<Grant, Status> {
+        let grant = grant.ok_or_else(|| Status::invalid_argument("Missing grant"))?;
+        let role = Role::from_name(&grant.role)
+            .ok_or_else(|| Status::invalid_argument(format!("Unknown role '{}'", grant.role)))?;
+        Ok(Grant::new(role, grant.database))
+    }
+
+    fn encode_grant(grant: Grant) -> admin_proto::Grant {
+        admin_proto::Grant { role: grant.role.name().to_string(), database: grant.database }
+    }
+
+    fn encode_session(session: Session) -> admin_proto::Session {
+        admin_proto::Session { id: session.id, issued_at: session.issued_at, expires_at: session.expires_at }
+    }
+
+    fn encode_api_key(api_key: ApiKey) -> admin_proto::ApiKey {
+        admin_proto::ApiKey { name: api_key.name, expires_at: api_key.expires_at, last_used_at: api_key.last_used_at }
+    }
+
+    fn encode_running_query(query: RunningQueryInfo) -> admin_proto::RunningQuery {
+        admin_proto::RunningQuery {
+            id: query.id.to_string(),
+            transaction_id: query.transaction_id.to_string(),
+            database: query.database_name,
+            username: query.owner,
+            query: query.source_query,
+            elapsed_millis: query.elapsed.as_millis() as u64,
+        }
+    }
+
+    fn encode_open_transaction(transaction: OpenTransactionInfo) -> admin_proto::OpenTransaction {
+        admin_proto::OpenTransaction {
+            id: transaction.id.to_string(),
+            database: transaction.database_name,
+            r#type: transaction.transaction_type.to_string(),
+            username: transaction.owner,
+            elapsed_millis: transaction.elapsed.as_millis() as u64,
+            running_queries: transaction.running_queries as u64,
+        }
+    }
+
+    fn decode_id(id: &str) -> Result<Uuid, Status> {
+        Uuid::parse_str(id).map_err(|_| Status::invalid_argument(format!("Invalid id '{id}'")))
+    }
 }
 
 #[tonic::async_trait]
@@ -65,4 +124,216 @@ impl admin_proto::type_db_admin_server::TypeDbAdmin for AdminService {
 
         Ok(Response::new(admin_proto::server_status::Res { grpc: Some(grpc), http, admin_address }))
     }
+
+    async fn user_grants(
+        &self,
+        request: Request<admin_proto::user_grants::Req>,
+    ) -> Result<Response<admin_proto::user_grants::Res>, Status> {
+        let username = request.into_inner().username;
+        let grants =
+            self.server_state.users().grants(Self::accessor(), &username).await.map_err(|err| err.into_status())?;
+        Ok(Response::new(admin_proto::user_grants::Res {
+            grants: grants.into_iter().map(Self::encode_grant).collect(),
+        }))
+    }
+
+    async fn user_grant(
+        &self,
+        request: Request<admin_proto::user_grant::Req>,
+    ) -> Result<Response<admin_proto::user_grant::Res>, Status> {
+        let admin_proto::user_grant::Req { username, grant } = request.into_inner();
+        let grant = Self::decode_grant(grant)?;
+        self.server_state.users().grant(Self::accessor(), &username, grant).await.map_err(|err| err.into_status())?;
+        Ok(Response::new(admin_proto::user_grant::Res {}))
+    }
+
+    async fn user_revoke(
+        &self,
+        request: Request<admin_proto::user_revoke::Req>,
+    ) -> Result<Response<admin_proto::user_revoke::Res>, Status> {
+        let admin_proto::user_revoke::Req { username, grant } = request.into_inner();
+        let grant = Self::decode_grant(grant)?;
+        self.server_state.users().revoke(Self::accessor(), &username, grant).await.map_err(|err| err.into_status())?;
+        Ok(Response::new(admin_proto::user_revoke::Res {}))
+    }
+
+    async fn user_sessions(
+        &self,
+        request: Request<admin_proto::user_sessions::Req>,
+    ) -> Result<Response<admin_proto::user_sessions::Res>, Status> {
+        let username = request.into_inner().username;
+        let sessions =
+            self.server_state.users().sessions(Self::accessor(), &username).await.map_err(|err| err.into_status())?;
+        Ok(Response::new(admin_proto::user_sessions::Res {
+            sessions: sessions.into_iter().map(Self::encode_session).collect(),
+        }))
+    }
+
+    async fn user_sessions_revoke(
+        &self,
+        request: Request<admin_proto::user_sessions_revoke::Req>,
+    ) -> Result<Response<admin_proto::user_sessions_revoke::Res>, Status> {
+        let admin_proto::user_sessions_revoke::Req { username, session_id } = request.into_inner();
+        self.server_state
+            .users()
+            .sessions_revoke(Self::accessor(), &username, session_id.as_deref())
+            .await
+            .map_err(|err| err.into_status())?;
+        Ok(Response::new(admin_proto::user_sessions_revoke::Res {}))
+    }
+
+    async fn user_api_keys(
+        &self,
+        request: Request<admin_proto::user_api_keys::Req>,
+    ) -> Result<Response<admin_proto::user_api_keys::Res>, Status> {
+        let username = request.into_inner().username;
+        let api_keys =
+            self.server_state.users().api_keys(Self::accessor(), &username).await.map_err(|err| err.into_status())?;
+        Ok(Response::new(admin_proto::user_api_keys::Res {
+            api_keys: api_keys.into_iter().map(Self::encode_api_key).collect(),
+        }))
+    }
+
+    async fn user_api_key_create(
+        &self,
+        request: Request<admin_proto::user_api_key_create::Req>,
+    ) -> Result<Response<admin_proto::user_api_key_create::Res>, Status> {
+        let admin_proto::user_api_key_create::Req { username, name, expires_in_seconds } = request.into_inner();
+        let (api_key, key) = self
+            .server_state
+            .users()
+            .api_key_create(Self::accessor(), &username, &name, expires_in_seconds.map(Duration::from_secs))
+            .await
+            .map_err(|err| err.into_status())?;
+        Ok(Response::new(admin_proto::user_api_key_create::Res { api_key: Some(Self::encode_api_key(api_key)), key }))
+    }
+
+    async fn user_api_key_delete(
+        &self,
+        request: Request<admin_proto::user_api_key_delete::Req>,
+    ) -> Result<Response<admin_proto::user_api_key_delete::Res>, Status> {
+        let admin_proto::user_api_key_delete::Req { username, name } = request.into_inner();
+        self.server_state
+            .users()
+            .api_key_delete(Self::accessor(), &username, &name)
+            .await
+            .map_err(|err| err.into_status())?;
+        Ok(Response::new(admin_proto::user_api_key_delete::Res {}))
+    }
+
+    async fn user_password_expire(
+        &self,
+        request: Request<admin_proto::user_password_expire::Req>,
+    ) -> Result<Response<admin_proto::user_password_expire::Res>, Status> {
+        let username = request.into_inner().username;
+        self.server_state
+            .users()
+            .password_expire(Self::accessor(), &username)
+            .await
+            .map_err(|err| err.into_status())?;
+        Ok(Response::new(admin_proto::user_password_expire::Res {}))
+    }
+
+    async fn database_backup(
+        &self,
+        request: Request<admin_proto::database_backup::Req>,
+    ) -> Result<Response<admin_proto::database_backup::Res>, Status> {
+        let admin_proto::database_backup::Req { name, directory, incremental } = request.into_inner();
+        let sequence_number = self
+            .server_state
+            .databases()
+            .backup(Self::accessor(), &name, PathBuf::from(directory), incremental)
+            .await
+            .map_err(|err| err.into_status())?;
+        Ok(Response::new(admin_proto::database_backup::Res { sequence_number: sequence_number.number() }))
+    }
+
+    async fn database_restore(
+        &self,
+        request: Request<admin_proto::database_restore::Req>,
+    ) -> Result<Response<admin_proto::database_restore::Res>, Status> {
+        let admin_proto::database_restore::Req { name, directory } = request.into_inner();
+        self.server_state
+            .databases()
+            .restore(Self::accessor(), &name, PathBuf::from(directory))
+            .await
+            .map_err(|err| err.into_status())?;
+        Ok(Response::new(admin_proto::database_restore::Res {}))
+    }
+
+    async fn database_statistics(
+        &self,
+        request: Request<admin_proto::database_statistics::Req>,
+    ) -> Result<Response<admin_proto::database_statistics::Res>, Status> {
+        let admin_proto::database_statistics::Req { name } = request.into_inner();
+        let statistics =
+            self.server_state.databases().statistics(Self::accessor(), &name).await.map_err(|err| err.into_status())?;
+        Ok(Response::new(encode_database_statistics(statistics)))
+    }
+
+    async fn queries(
+        &self,
+        _request: Request<admin_proto::queries::Req>,
+    ) -> Result<Response<admin_proto::queries::Res>, Status> {
+        let queries = self.server_state.transactions().queries(Self::accessor()).await;
+        Ok(Response::new(admin_proto::queries::Res {
+            queries: queries.into_iter().map(Self::encode_running_query).collect(),
+        }))
+    }
+
+    async fn query_kill(
+        &self,
+        request: Request<admin_proto::query_kill::Req>,
+    ) -> Result<Response<admin_proto::query_kill::Res>, Status> {
+        let admin_proto::query_kill::Req { id } = request.into_inner();
+        let id = Self::decode_id(&id)?;
+        self.server_state.transactions().query_kill(Self::accessor(), id).await.map_err(|err| err.into_status())?;
+        Ok(Response::new(admin_proto::query_kill::Res {}))
+    }
+
+    async fn transactions(
+        &self,
+        _request: Request<admin_proto::transactions::Req>,
+    ) -> Result<Response<admin_proto::transactions::Res>, Status> {
+        let transactions = self.server_state.transactions().transactions(Self::accessor()).await;
+        Ok(Response::new(admin_proto::transactions::Res {
+            transactions: transactions.into_iter().map(Self::encode_open_transaction).collect(),
+        }))
+    }
+
+    async fn transaction_kill(
+        &self,
+        request: Request<admin_proto::transaction_kill::Req>,
+    ) -> Result<Response<admin_proto::transaction_kill::Res>, Status> {
+        let admin_proto::transaction_kill::Req { id } = request.into_inner();
+        let id = Self::decode_id(&id)?;
+        self.server_state.transactions().kill(Self::accessor(), id).await.map_err(|err| err.into_status())?;
+        Ok(Response::new(admin_proto::transaction_kill::Res {}))
+    }
+}
+
+fn encode_database_statistics(statistics: DatabaseStatistics) -> admin_proto::database_statistics::Res {
+    let encode_nested = |counts: BTreeMap<String, LabelledCounts>| {
+        counts
+            .into_iter()
+            .map(|(label, counts)| (label, admin_proto::LabelledCounts { counts: counts.into_iter().collect() }))
+            .collect()
+    };
+    admin_proto::database_statistics::Res {
+        sequence_number: statistics.sequence_number.number(),
+        total_thing_count: statistics.total_thing_count,
+        total_entity_count: statistics.total_entity_count,
+        total_relation_count: statistics.total_relation_count,
+        total_attribute_count: statistics.total_attribute_count,
+        total_role_count: statistics.total_role_count,
+        total_has_count: statistics.total_has_count,
+        entity_counts: statistics.entity_counts.into_iter().collect(),
+        relation_counts: statistics.relation_counts.into_iter().collect(),
+        attribute_counts: statistics.attribute_counts.into_iter().collect(),
+        role_counts: statistics.role_counts.into_iter().collect(),
+        has_counts: encode_nested(statistics.has_counts),
+        role_player_counts: encode_nested(statistics.role_player_counts),
+        relation_role_counts: encode_nested(statistics.relation_role_counts),
+        links_index_counts: encode_nested(statistics.links_index_counts),
+    }
 }
@@ -17,6 +17,7 @@ use tokio::sync::OnceCell;
 
 const GRPC_ADDRESS: &str = "127.0.0.1:11729";
 const ADMIN_PORT: u16 = 11728;
+const BACKUP_DIRECTORY: &str = "backups";
 const DISTRIBUTION_INFO: DistributionInfo =
     DistributionInfo { logo: "logo", distribution: "TypeDB CE TEST", version: "0.0.0-test" };
 
@@ -38,6 +39,7 @@ async fn ensure_server_started() {
                 .admin_port(ADMIN_PORT)
                 .admin_enabled(true)
                 .data_directory(server_dir.as_ref())
+                .backup_directory(server_dir.as_ref().join(BACKUP_DIRECTORY))
                 .development_mode(true)
                 .build()
                 .expect("Failed to build config");
@@ -99,6 +101,181 @@ async fn admin_server_status() {
     assert!(admin_address.contains(&ADMIN_PORT.to_string()), "Admin address should contain the configured port");
 }
 
+#[tokio::test]
+async fn admin_user_grants() {
+    let mut client = connect_admin_client().await;
+    let grant = admin_proto::Grant { role: "reader".to_string(), database: Some("grants-db".to_string()) };
+
+    client
+        .user_grant(admin_proto::user_grant::Req { username: "admin".to_string(), grant: Some(grant.clone()) })
+        .await
+        .expect("RPC failed");
+    let grants = client
+        .user_grants(admin_proto::user_grants::Req { username: "admin".to_string() })
+        .await
+        .expect("RPC failed")
+        .into_inner()
+        .grants;
+    assert_eq!(grants, vec![grant.clone()]);
+
+    client
+        .user_revoke(admin_proto::user_revoke::Req { username: "admin".to_string(), grant: Some(grant) })
+        .await
+        .expect("RPC failed");
+    let grants = client
+        .user_grants(admin_proto::user_grants::Req { username: "admin".to_string() })
+        .await
+        .expect("RPC failed")
+        .into_inner()
+        .grants;
+    assert!(grants.is_empty(), "Revoked grant should no longer be listed");
+
+    let unknown_role = admin_proto::Grant { role: "superuser".to_string(), database: None };
+    let result = client
+        .user_grant(admin_proto::user_grant::Req { username: "admin".to_string(), grant: Some(unknown_role) })
+        .await;
+    assert_eq!(result.unwrap_err().code(), tonic::Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn admin_database_backup_and_restore_reject_invalid_targets() {
+    let mut client = connect_admin_client().await;
+    let backup_dir = create_tmp_storage_dir();
+
+    let result = client
+        .database_backup(admin_proto::database_backup::Req {
+            name: "missing-db".to_string(),
+            directory: "backup".to_string(),
+            incremental: false,
+        })
+        .await;
+    assert_eq!(result.unwrap_err().code(), tonic::Code::NotFound);
+
+    let outside_backup_directory = [
+        backup_dir.as_ref().join("backup").to_string_lossy().to_string(),
+        format!("../{BACKUP_DIRECTORY}-escaped"),
+        String::new(),
+    ];
+    for directory in outside_backup_directory {
+        let result = client
+            .database_backup(admin_proto::database_backup::Req {
+                name: "missing-db".to_string(),
+                directory,
+                incremental: false,
+            })
+            .await;
+        assert_eq!(result.unwrap_err().code(), tonic::Code::PermissionDenied);
+    }
+
+    let result = client
+        .database_restore(admin_proto::database_restore::Req {
+            name: "restored-db".to_string(),
+            directory: backup_dir.as_ref().to_string_lossy().to_string(),
+        })
+        .await;
+    assert_eq!(result.unwrap_err().code(), tonic::Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn admin_database_statistics_of_missing_database_is_not_found() {
+    let mut client = connect_admin_client().await;
+    let result =
+        client.database_statistics(admin_proto::database_statistics::Req { name: "missing-db".to_string() }).await;
+    assert_eq!(result.unwrap_err().code(), tonic::Code::NotFound);
+}
+
+#[tokio::test]
+async fn admin_user_sessions() {
+    let mut client = connect_admin_client().await;
+
+    let unknown_session = admin_proto::user_sessions_revoke::Req {
+        username: "admin".to_string(),
+        session_id: Some("6f1c2a4e-8d3b-4c5a-9e7f-0a1b2c3d4e5f".to_string()),
+    };
+    let result = client.user_sessions_revoke(unknown_session).await;
+    assert_eq!(result.unwrap_err().code(), tonic::Code::InvalidArgument);
+
+    client
+        .user_sessions_revoke(admin_proto::user_sessions_revoke::Req {
+            username: "admin".to_string(),
+            session_id: None,
+        })
+        .await
+        .expect("RPC failed");
+    let sessions = client
+        .user_sessions(admin_proto::user_sessions::Req { username: "admin".to_string() })
+        .await
+        .expect("RPC failed")
+        .into_inner()
+        .sessions;
+    assert!(sessions.is_empty(), "Revoking all sessions should leave none active");
+}
+
+#[tokio::test]
+async fn admin_user_api_keys() {
+    let mut client = connect_admin_client().await;
+    let create = admin_proto::user_api_key_create::Req {
+        username: "admin".to_string(),
+        name: "batch-job".to_string(),
+        expires_in_seconds: Some(3600),
+    };
+
+    let created = client.user_api_key_create(create.clone()).await.expect("RPC failed").into_inner();
+    assert!(created.key.starts_with("tdbk_"), "Created key should carry the API key prefix");
+    let api_keys = client
+        .user_api_keys(admin_proto::user_api_keys::Req { username: "admin".to_string() })
+        .await
+        .expect("RPC failed")
+        .into_inner()
+        .api_keys;
+    assert_eq!(api_keys.len(), 1);
+    assert_eq!(api_keys[0].name, "batch-job");
+    assert!(api_keys[0].expires_at.is_some() && api_keys[0].last_used_at.is_none());
+
+    let duplicate = client.user_api_key_create(create).await;
+    assert_eq!(duplicate.unwrap_err().code(), tonic::Code::InvalidArgument);
+
+    client
+        .user_api_key_delete(admin_proto::user_api_key_delete::Req {
+            username: "admin".to_string(),
+            name: "batch-job".to_string(),
+        })
+        .await
+        .expect("RPC failed");
+    let api_keys = client
+        .user_api_keys(admin_proto::user_api_keys::Req { username: "admin".to_string() })
+        .await
+        .expect("RPC failed")
+        .into_inner()
+        .api_keys;
+    assert!(api_keys.is_empty(), "Deleted API key should no longer be listed");
+}
+
+#[tokio::test]
+async fn admin_user_password_expire_requires_existing_user() {
+    let mut client = connect_admin_client().await;
+    let result = client
+        .user_password_expire(admin_proto::user_password_expire::Req { username: "no-such-user".to_string() })
+        .await;
+    assert_eq!(result.unwrap_err().code(), tonic::Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn admin_kill_unknown_query_or_transaction() {
+    let mut client = connect_admin_client().await;
+    let queries = client.queries(admin_proto::queries::Req {}).await.expect("RPC failed").into_inner().queries;
+    assert!(queries.is_empty());
+
+    let unknown_id = "6f1c2a4e-8d3b-4c5a-9e7f-0a1b2c3d4e5f".to_string();
+    let result = client.query_kill(admin_proto::query_kill::Req { id: unknown_id.clone() }).await;
+    assert_eq!(result.unwrap_err().code(), tonic::Code::NotFound);
+    let result = client.transaction_kill(admin_proto::transaction_kill::Req { id: unknown_id }).await;
+    assert_eq!(result.unwrap_err().code(), tonic::Code::NotFound);
+
+    let result = client.query_kill(admin_proto::query_kill::Req { id: "not-an-id".to_string() }).await;
+    assert_eq!(result.unwrap_err().code(), tonic::Code::InvalidArgument);
+}
+
 mod localhost_guard_tests {
     use std::net::SocketAddr;
 
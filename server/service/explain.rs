@@ -0,0 +1,417 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::{collections::BTreeMap, time::Duration};
+
+use itertools::Itertools;
+use query::explain::{ConjunctionPlan, FunctionPlan, QueryPlan, StageKind, StagePlan, StepKind, StepPlan};
+use resource::profile::{PatternProfile, QueryProfile, StageProfile, StepProfile, StorageCounters, SubstepProfile};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryPlanResponse {
+    pub stages: Vec<StagePlanResponse>,
+    pub functions: Vec<FunctionPlanResponse>,
+    pub variables: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionPlanResponse {
+    pub function_id: String,
+    pub stages: Vec<StagePlanResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StagePlanResponse {
+    pub executable_id: u64,
+    pub kind: StageKindResponse,
+    pub instructions: Vec<String>,
+    pub conjunction: Option<ConjunctionPlanResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StageKindResponse {
+    Match,
+    Insert,
+    Update,
+    Put,
+    Delete,
+    Select,
+    Sort,
+    Offset,
+    Limit,
+    Require,
+    Distinct,
+    Reduce,
+    Fetch,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConjunctionPlanResponse {
+    pub executable_id: u64,
+    pub estimated_cost: f64,
+    pub estimated_size: f64,
+    pub steps: Vec<StepPlanResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepPlanResponse {
+    pub kind: StepKindResponse,
+    pub estimated_cost: Option<f64>,
+    pub estimated_size: Option<f64>,
+    pub sort_variable: Option<String>,
+    pub instructions: Vec<String>,
+    pub branches: Vec<ConjunctionPlanResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StepKindResponse {
+    Intersection,
+    UnsortedJoin,
+    Assignment,
+    Check,
+    Disjunction,
+    Negation,
+    Optional,
+    FunctionCall,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryProfileResponse {
+    pub total_micros: f64,
+    pub compile: Option<CompileProfileResponse>,
+    pub stages: Vec<StageProfileResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompileProfileResponse {
+    pub total_micros: f64,
+    pub translation_micros: f64,
+    pub validation_micros: f64,
+    pub annotation_micros: f64,
+    pub compilation_micros: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StageProfileResponse {
+    pub id: u64,
+    pub description: Option<String>,
+    pub total_micros: f64,
+    pub pattern: Option<PatternProfileResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PatternProfileResponse {
+    pub description: Option<String>,
+    pub total_micros: f64,
+    pub substeps: Vec<SubstepProfileResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum SubstepProfileResponse {
+    Step(StepProfileResponse),
+    Pattern(PatternProfileResponse),
+    #[serde(rename_all = "camelCase")]
+    FunctionCall {
+        description: String,
+        profile: Box<QueryProfileResponse>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepProfileResponse {
+    pub description: Option<String>,
+    pub batches: u64,
+    pub rows: u64,
+    pub micros: f64,
+    pub storage: StorageCountersResponse,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageCountersResponse {
+    pub raw_seeks: Option<u64>,
+    pub raw_advances: Option<u64>,
+    pub advances_mvcc_visible: Option<u64>,
+    pub advances_mvcc_invisible: Option<u64>,
+    pub advances_mvcc_deleted: Option<u64>,
+}
+
+pub fn encode_query_plan(plan: QueryPlan) -> QueryPlanResponse {
+    let QueryPlan { stages, functions, variable_names } = plan;
+    QueryPlanResponse {
+        stages: stages.into_iter().map(encode_stage_plan).collect(),
+        functions: functions.into_iter().map(encode_function_plan).collect(),
+        variables: variable_names.into_iter().map(|(variable, name)| (variable.to_string(), name)).collect(),
+    }
+}
+
+fn encode_function_plan(plan: FunctionPlan) -> FunctionPlanResponse {
+    FunctionPlanResponse {
+        function_id: plan.function_id.to_string(),
+        stages: plan.stages.into_iter().map(encode_stage_plan).collect(),
+    }
+}
+
+fn encode_stage_plan(plan: StagePlan) -> StagePlanResponse {
+    let kind = match plan.kind {
+        StageKind::Match => StageKindResponse::Match,
+        StageKind::Insert => StageKindResponse::Insert,
+        StageKind::Update => StageKindResponse::Update,
+        StageKind::Put => StageKindResponse::Put,
+        StageKind::Delete => StageKindResponse::Delete,
+        StageKind::Select => StageKindResponse::Select,
+        StageKind::Sort => StageKindResponse::Sort,
+        StageKind::Offset => StageKindResponse::Offset,
+        StageKind::Limit => StageKindResponse::Limit,
+        StageKind::Require => StageKindResponse::Require,
+        StageKind::Distinct => StageKindResponse::Distinct,
+        StageKind::Reduce => StageKindResponse::Reduce,
+        StageKind::Fetch => StageKindResponse::Fetch,
+    };
+    StagePlanResponse {
+        executable_id: plan.executable_id,
+        kind,
+        instructions: plan.instructions,
+        conjunction: plan.conjunction.map(encode_conjunction_plan),
+    }
+}
+
+fn encode_conjunction_plan(plan: ConjunctionPlan) -> ConjunctionPlanResponse {
+    ConjunctionPlanResponse {
+        executable_id: plan.executable_id,
+        estimated_cost: plan.estimated_cost,
+        estimated_size: plan.estimated_size,
+        steps: plan.steps.into_iter().map(encode_step_plan).collect(),
+    }
+}
+
+fn encode_step_plan(plan: StepPlan) -> StepPlanResponse {
+    let kind = match plan.kind {
+        StepKind::Intersection => StepKindResponse::Intersection,
+        StepKind::UnsortedJoin => StepKindResponse::UnsortedJoin,
+        StepKind::Assignment => StepKindResponse::Assignment,
+        StepKind::Check => StepKindResponse::Check,
+        StepKind::Disjunction => StepKindResponse::Disjunction,
+        StepKind::Negation => StepKindResponse::Negation,
+        StepKind::Optional => StepKindResponse::Optional,
+        StepKind::FunctionCall => StepKindResponse::FunctionCall,
+    };
+    StepPlanResponse {
+        kind,
+        estimated_cost: plan.estimated_cost,
+        estimated_size: plan.estimated_size,
+        sort_variable: plan.sort_variable.map(|variable| variable.to_string()),
+        instructions: plan.instructions,
+        branches: plan.branches.into_iter().map(encode_conjunction_plan).collect(),
+    }
+}
+
+pub fn encode_query_profile(profile: &QueryProfile) -> QueryProfileResponse {
+    let compile_profile = profile.compile_profile();
+    let compile = compile_profile.is_enabled().then(|| CompileProfileResponse {
+        total_micros: nanos_to_micros(compile_profile.total_nanos()),
+        translation_micros: duration_to_micros(compile_profile.translation()),
+        validation_micros: duration_to_micros(compile_profile.validation()),
+        annotation_micros: duration_to_micros(compile_profile.annotation()),
+        compilation_micros: duration_to_micros(compile_profile.compilation()),
+    });
+    let stages = profile
+        .stage_profiles()
+        .read()
+        .unwrap()
+        .iter()
+        .sorted_by_key(|(id, _)| **id)
+        .map(|(id, stage_profile)| encode_stage_profile(*id, stage_profile))
+        .collect();
+    QueryProfileResponse { total_micros: nanos_to_micros(profile.total_nanos()), compile, stages }
+}
+
+fn encode_stage_profile(id: u64, profile: &StageProfile) -> StageProfileResponse {
+    let pattern = profile.pattern_profile().map(|pattern_profile| encode_pattern_profile(&pattern_profile));
+    StageProfileResponse {
+        id,
+        description: profile.description().map(str::to_owned),
+        total_micros: pattern.as_ref().map_or(0.0, |pattern| pattern.total_micros),
+        pattern,
+    }
+}
+
+fn encode_pattern_profile(profile: &PatternProfile) -> PatternProfileResponse {
+    let substeps = profile
+        .substeps()
+        .read()
+        .unwrap()
+        .iter()
+        .map(|substep| match substep {
+            SubstepProfile::QueryProfile { description, profile } => SubstepProfileResponse::FunctionCall {
+                description: description.clone(),
+                profile: Box::new(encode_query_profile(profile)),
+            },
+            SubstepProfile::PatternProfile(profile) => SubstepProfileResponse::Pattern(encode_pattern_profile(profile)),
+            SubstepProfile::StepProfile(profile) => SubstepProfileResponse::Step(encode_step_profile(profile)),
+        })
+        .collect();
+    PatternProfileResponse {
+        description: profile.description().map(str::to_owned),
+        total_micros: nanos_to_micros(profile.total_nanos()),
+        substeps,
+    }
+}
+
+fn encode_step_profile(profile: &StepProfile) -> StepProfileResponse {
+    StepProfileResponse {
+        description: profile.description().map(str::to_owned),
+        batches: profile.batches(),
+        rows: profile.rows(),
+        micros: nanos_to_micros(profile.total_nanos()),
+        storage: encode_storage_counters(&profile.storage_counters()),
+    }
+}
+
+fn encode_storage_counters(counters: &StorageCounters) -> StorageCountersResponse {
+    StorageCountersResponse {
+        raw_seeks: counters.get_raw_seek(),
+        raw_advances: counters.get_raw_advance(),
+        advances_mvcc_visible: counters.get_advance_mvcc_visible(),
+        advances_mvcc_invisible: counters.get_advance_mvcc_invisible(),
+        advances_mvcc_deleted: counters.get_advance_mvcc_deleted(),
+    }
+}
+
+fn nanos_to_micros(nanos: u64) -> f64 {
+    nanos as f64 / 1000.0
+}
+
+fn duration_to_micros(duration: Duration) -> f64 {
+    nanos_to_micros(duration.as_nanos() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use answer::variable::Variable;
+    use ir::pipeline::function_signature::FunctionID;
+    use query::explain::{ConjunctionPlan, FunctionPlan, QueryPlan, StageKind, StagePlan, StepKind, StepPlan};
+    use serde_json::json;
+
+    use super::encode_query_plan;
+
+    fn step(kind: StepKind, estimated_cost: Option<f64>, branches: Vec<ConjunctionPlan>) -> StepPlan {
+        StepPlan {
+            kind,
+            estimated_cost,
+            estimated_size: estimated_cost.map(|_| 2.0),
+            sort_variable: None,
+            instructions: vec![format!("{kind:?} instruction")],
+            branches,
+        }
+    }
+
+    fn conjunction(executable_id: u64, steps: Vec<StepPlan>) -> ConjunctionPlan {
+        ConjunctionPlan { executable_id, estimated_cost: 1.5, estimated_size: 4.0, steps }
+    }
+
+    #[test]
+    fn query_plan_is_encoded_as_camel_case_json() {
+        let person = Variable::new(0);
+        let negated = conjunction(3, vec![step(StepKind::Check, None, Vec::new())]);
+        let mut intersection = step(StepKind::Intersection, Some(0.5), Vec::new());
+        intersection.sort_variable = Some(person);
+        let plan = QueryPlan {
+            stages: vec![
+                StagePlan {
+                    executable_id: 1,
+                    kind: StageKind::Match,
+                    instructions: Vec::new(),
+                    conjunction: Some(conjunction(
+                        2,
+                        vec![intersection, step(StepKind::Negation, None, vec![negated])],
+                    )),
+                },
+                StagePlan {
+                    executable_id: 4,
+                    kind: StageKind::Limit,
+                    instructions: vec!["limit 1".to_owned()],
+                    conjunction: None,
+                },
+            ],
+            functions: vec![FunctionPlan { function_id: FunctionID::Preamble(0), stages: Vec::new() }],
+            variable_names: HashMap::from([(person, "person".to_owned())]),
+        };
+
+        let encoded = serde_json::to_value(encode_query_plan(plan)).unwrap();
+        assert_eq!(
+            encoded,
+            json!({
+                "stages": [
+                    {
+                        "executableId": 1,
+                        "kind": "match",
+                        "instructions": [],
+                        "conjunction": {
+                            "executableId": 2,
+                            "estimatedCost": 1.5,
+                            "estimatedSize": 4.0,
+                            "steps": [
+                                {
+                                    "kind": "intersection",
+                                    "estimatedCost": 0.5,
+                                    "estimatedSize": 2.0,
+                                    "sortVariable": "$0",
+                                    "instructions": ["Intersection instruction"],
+                                    "branches": [],
+                                },
+                                {
+                                    "kind": "negation",
+                                    "estimatedCost": null,
+                                    "estimatedSize": null,
+                                    "sortVariable": null,
+                                    "instructions": ["Negation instruction"],
+                                    "branches": [{
+                                        "executableId": 3,
+                                        "estimatedCost": 1.5,
+                                        "estimatedSize": 4.0,
+                                        "steps": [{
+                                            "kind": "check",
+                                            "estimatedCost": null,
+                                            "estimatedSize": null,
+                                            "sortVariable": null,
+                                            "instructions": ["Check instruction"],
+                                            "branches": [],
+                                        }],
+                                    }],
+                                },
+                            ],
+                        },
+                    },
+                    {
+                        "executableId": 4,
+                        "kind": "limit",
+                        "instructions": ["limit 1"],
+                        "conjunction": null,
+                    },
+                ],
+                "functions": [{ "functionId": "QueryFunction#0", "stages": [] }],
+                "variables": { "$0": "person" },
+            })
+        );
+    }
+}
@@ -0,0 +1,167 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::{path::PathBuf, time::Duration};
+
+use hyper::{Body, Client, Method, Request, StatusCode, header};
+use resource::{
+    constants::server::{DEFAULT_USER_NAME, DEFAULT_USER_PASSWORD},
+    distribution_info::DistributionInfo,
+};
+use server::{ServerBuilder, parameters::config::ConfigBuilder};
+use test_utils::{TempDir, create_tmp_storage_dir};
+use tokio::sync::OnceCell;
+
+const GRPC_ADDRESS: &str = "127.0.0.1:11761";
+const HTTP_ADDRESS: &str = "127.0.0.1:11760";
+const DISTRIBUTION_INFO: DistributionInfo =
+    DistributionInfo { logo: "logo", distribution: "TypeDB CE TEST", version: "0.0.0-test" };
+
+const DATABASE: &str = "explain";
+
+// The data directory of the server and the token of a signed in user, once the database's schema is defined
+static SERVER: OnceCell<(TempDir, String, tokio::sync::watch::Sender<()>)> = OnceCell::const_new();
+
+fn config_path() -> PathBuf {
+    std::env::current_dir().unwrap().join("server/config.yml")
+}
+
+async fn ensure_server_started() -> &'static str {
+    let (_, token, _) = SERVER
+        .get_or_init(|| async {
+            let (shutdown_sender, shutdown_receiver) = tokio::sync::watch::channel(());
+            let server_dir = create_tmp_storage_dir();
+            let config = ConfigBuilder::from_file(config_path())
+                .expect("Failed to load config file")
+                .server_listen_address(GRPC_ADDRESS)
+                .server_http_enabled(true)
+                .server_http_listen_address(HTTP_ADDRESS)
+                .server_http_advertise_address(format!("http://{HTTP_ADDRESS}"))
+                .admin_enabled(false)
+                .data_directory(server_dir.as_ref())
+                .development_mode(true)
+                .build()
+                .expect("Failed to build config");
+
+            let server = ServerBuilder::new()
+                .distribution_info(DISTRIBUTION_INFO)
+                .shutdown_channel((shutdown_sender.clone(), shutdown_receiver))
+                .build(config)
+                .await
+                .expect("Failed to build server");
+
+            tokio::spawn(async move {
+                server.serve().await.expect("Server failed");
+            });
+
+            let credentials = serde_json::json!({ "username": DEFAULT_USER_NAME, "password": DEFAULT_USER_PASSWORD });
+            let (status, body) = http_post("/v1/signin", None, credentials).await;
+            assert_eq!(status, StatusCode::OK, "Signin failed: {body}");
+            let token = body["token"].as_str().expect("Signin should return a token").to_owned();
+            let (status, body) =
+                http_post(&format!("/v1/databases/{DATABASE}"), Some(&token), serde_json::Value::Null).await;
+            assert_eq!(status, StatusCode::OK, "Database creation failed: {body}");
+            let (status, body) =
+                query(&token, "schema", "define attribute name, value string; entity person, owns name;", None).await;
+            assert_eq!(status, StatusCode::OK, "Schema definition failed: {body}");
+
+            (server_dir, token, shutdown_sender)
+        })
+        .await;
+    token
+}
+
+async fn http_post(path: &str, token: Option<&str>, body: serde_json::Value) -> (StatusCode, serde_json::Value) {
+    let client = Client::new();
+    for _ in 0..50 {
+        let mut request = Request::builder()
+            .method(Method::POST)
+            .uri(format!("http://{HTTP_ADDRESS}{path}"))
+            .header(header::CONTENT_TYPE, "application/json");
+        if let Some(token) = token {
+            request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+        }
+        let Ok(response) = client.request(request.body(Body::from(body.to_string())).unwrap()).await else {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            continue;
+        };
+        let status = response.status();
+        let bytes = hyper::body::to_bytes(response.into_body()).await.expect("Failed to read response body");
+        return (status, serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null));
+    }
+    panic!("Failed to connect to HTTP service")
+}
+
+async fn query(
+    token: &str,
+    transaction_type: &str,
+    query: &str,
+    query_options: Option<serde_json::Value>,
+) -> (StatusCode, serde_json::Value) {
+    let body = serde_json::json!({
+        "databaseName": DATABASE,
+        "transactionType": transaction_type,
+        "query": query,
+        "queryOptions": query_options,
+        "commit": transaction_type != "read",
+    });
+    http_post("/v1/query", Some(token), body).await
+}
+
+#[tokio::test]
+async fn explained_query_returns_its_plan_without_executing() {
+    let token = ensure_server_started().await;
+    let explain = Some(serde_json::json!({ "explain": true }));
+
+    let (status, body) = query(token, "write", r#"insert $p isa person, has name "Explained";"#, explain.clone()).await;
+    assert_eq!(status, StatusCode::OK, "Explaining failed: {body}");
+    assert_eq!(body["answerType"], "ok");
+    assert!(body.get("profile").is_none());
+    let stages = body["plan"]["stages"].as_array().expect("The response should have a plan with stages");
+    assert_eq!(stages.len(), 1);
+    assert_eq!(stages[0]["kind"], "insert");
+    assert!(stages[0]["executableId"].as_u64().is_some());
+
+    let (status, body) = query(token, "read", r#"match $p isa person, has name "Explained";"#, None).await;
+    assert_eq!(status, StatusCode::OK, "Reading failed: {body}");
+    assert_eq!(body["answers"], serde_json::json!([]), "An explained insert should not be executed");
+
+    let (status, body) = query(token, "read", "match $p isa person, has name $n; limit 3;", explain).await;
+    assert_eq!(status, StatusCode::OK, "Explaining failed: {body}");
+    let stages = body["plan"]["stages"].as_array().unwrap();
+    assert_eq!(stages.iter().map(|stage| stage["kind"].clone()).collect::<Vec<_>>(), vec!["match", "limit"]);
+    let conjunction = &stages[0]["conjunction"];
+    assert!(conjunction["estimatedCost"].as_f64().is_some());
+    let steps = conjunction["steps"].as_array().expect("The match stage should have planned steps");
+    assert!(!steps.is_empty());
+    assert!(steps[0]["estimatedCost"].as_f64().is_some(), "The first step should have an estimate: {body}");
+    let variables = body["plan"]["variables"].as_object().unwrap();
+    let mut names = variables.values().map(|name| name.as_str().unwrap()).collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, vec!["n", "p"]);
+}
+
+#[tokio::test]
+async fn profiled_query_returns_its_answers_and_profile() {
+    let token = ensure_server_started().await;
+    let (status, body) = query(token, "write", r#"insert $p isa person, has name "Profiled";"#, None).await;
+    assert_eq!(status, StatusCode::OK, "Inserting failed: {body}");
+
+    let profile = Some(serde_json::json!({ "profile": true }));
+    let (status, body) = query(token, "read", r#"match $p isa person, has name $n; $n == "Profiled";"#, profile).await;
+    assert_eq!(status, StatusCode::OK, "Profiling failed: {body}");
+    assert_eq!(body["answerType"], "conceptRows");
+    assert_eq!(body["answers"].as_array().map(Vec::len), Some(1));
+    assert!(body.get("plan").is_none());
+
+    let profile = &body["profile"];
+    assert!(profile["totalMicros"].as_f64().is_some(), "The response should have a profile: {body}");
+    assert!(profile["compile"]["compilationMicros"].as_f64().is_some());
+    let stages = profile["stages"].as_array().expect("The profile should have stages");
+    assert!(!stages.is_empty());
+    let substeps = stages[0]["pattern"]["substeps"].as_array().expect("The match stage should be profiled");
+    assert!(substeps.iter().any(|substep| substep["kind"] == "step" && substep["rows"].as_u64().is_some()));
+}
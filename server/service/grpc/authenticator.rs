@@ -5,14 +5,14 @@
  */
 use std::sync::Arc;
 
-use diagnostics::metrics::ActionKind;
+use diagnostics::{audit, metrics::ActionKind};
 use futures::future::BoxFuture;
 use http::Request;
 use tonic::{Status, body::BoxBody};
 use tower::{Layer, Service};
 
 use crate::{
-    authentication::authenticate,
+    authentication::{Accessor, authenticate, authenticate_allowing_password_change},
     error::LocalServerStateError,
     service::grpc::{diagnostics::run_with_diagnostics_async, error::IntoGrpcStatus},
     state::ServerState,
@@ -43,6 +43,23 @@ impl Authenticator {
         )
         .await
     }
+
+    pub async fn authenticate_allowing_password_change(
+        &self,
+        request: Request<BoxBody>,
+    ) -> Result<Request<BoxBody>, Status> {
+        run_with_diagnostics_async(
+            self.server_state.diagnostics_manager(),
+            None::<&str>,
+            ActionKind::Authenticate,
+            || async {
+                authenticate_allowing_password_change(self.server_state.clone(), request)
+                    .await
+                    .map_err(|typedb_source| LocalServerStateError::AuthenticationError { typedb_source }.into_status())
+            },
+        )
+        .await
+    }
 }
 
 impl<S: Clone> Layer<S> for Authenticator {
@@ -61,17 +78,16 @@ pub struct AuthenticatedService<S> {
 
 impl<S> AuthenticatedService<S> {
     const AUTHENTICATION_FREE_METHODS: &'static [&'static str] = &["connection_open", "authentication_token_create"];
+    // Users that must change their password can only read and update themselves, so that the protocol's user update
+    // lets them set a new password
+    const PASSWORD_CHANGE_METHODS: &'static [&'static str] = &["users_get", "users_update"];
 
     pub fn new(inner: S, authenticator: Authenticator) -> Self {
         Self { inner, authenticator }
     }
 
-    fn is_authentication_required(request: &Request<BoxBody>) -> bool {
-        if let Some(method) = request.uri().path().split('/').last() {
-            !Self::AUTHENTICATION_FREE_METHODS.contains(&method)
-        } else {
-            true
-        }
+    fn is_any_of(request: &Request<BoxBody>, methods: &[&str]) -> bool {
+        request.uri().path().split('/').last().is_some_and(|method| methods.contains(&method))
     }
 }
 
@@ -95,11 +111,17 @@ where
         let authenticator = self.authenticator.clone();
         let mut inner = self.inner.clone();
         Box::pin(async move {
-            let request = match Self::is_authentication_required(&request) {
-                true => authenticator.authenticate(request).await?,
-                false => request,
+            let request = if Self::is_any_of(&request, Self::AUTHENTICATION_FREE_METHODS) {
+                request
+            } else if Self::is_any_of(&request, Self::PASSWORD_CHANGE_METHODS) {
+                authenticator.authenticate_allowing_password_change(request).await?
+            } else {
+                authenticator.authenticate(request).await?
             };
-            inner.call(request).await
+            match request.extensions().get::<Accessor>().cloned() {
+                Some(Accessor(accessor)) => audit::with_accessor(accessor, inner.call(request)).await,
+                None => inner.call(request).await,
+            }
         })
     }
 }
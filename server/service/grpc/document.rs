@@ -32,6 +32,34 @@ pub(crate) fn encode_document(
     })
 }
 
+// Encodes server-generated JSON, such as query plans and profiles, so it can be returned in a concept document stream
+pub(crate) fn encode_json_document(value: serde_json::Value) -> typedb_protocol::ConceptDocument {
+    typedb_protocol::ConceptDocument { root: Some(encode_json_node(value)) }
+}
+
+fn encode_json_node(value: serde_json::Value) -> typedb_protocol::concept_document::Node {
+    use typedb_protocol::{
+        concept_document::node::{Leaf, List, Map, Node, leaf},
+        value::Value as ValueProto,
+    };
+    let value_leaf =
+        |value: ValueProto| Leaf { leaf: Some(leaf::Leaf::Value(typedb_protocol::Value { value: Some(value) })) };
+    let node = match value {
+        serde_json::Value::Null => Node::Leaf(Leaf { leaf: Some(leaf::Leaf::Empty(leaf::Empty {})) }),
+        serde_json::Value::Bool(boolean) => Node::Leaf(value_leaf(ValueProto::Boolean(boolean))),
+        serde_json::Value::Number(number) => match number.as_i64() {
+            Some(integer) => Node::Leaf(value_leaf(ValueProto::Integer(integer))),
+            None => Node::Leaf(value_leaf(ValueProto::Double(number.as_f64().unwrap_or(f64::NAN)))),
+        },
+        serde_json::Value::String(string) => Node::Leaf(value_leaf(ValueProto::String(string))),
+        serde_json::Value::Array(array) => Node::List(List { list: array.into_iter().map(encode_json_node).collect() }),
+        serde_json::Value::Object(object) => {
+            Node::Map(Map { map: object.into_iter().map(|(key, value)| (key, encode_json_node(value))).collect() })
+        }
+    };
+    typedb_protocol::concept_document::Node { node: Some(node) }
+}
+
 fn encode_node(
     node: DocumentNode,
     snapshot: &impl ReadableSnapshot,
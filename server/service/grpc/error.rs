@@ -158,5 +158,8 @@ fn server_state_error_to_status(
 typedb_error! {
     pub(crate) GrpcServiceError(component = "GRPC Service", prefix = "GSR") {
         UnexpectedMissingField(1, "Invalid request: missing field '{field}'.", field: String),
+        InvalidMetadata(2, "Invalid request: invalid value '{value}' for metadata '{key}'.", key: String, value: String),
+        ConflictingMetadata(3, "Invalid request: metadata '{first}' and '{second}' cannot both be set.", first: String, second: String),
+        RequiredMetadata(4, "Invalid request: metadata '{key}' also requires metadata '{required}' to be set to 'true'.", key: String, required: String),
     }
 }
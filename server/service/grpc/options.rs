@@ -4,13 +4,40 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use options::{QueryOptions, TransactionOptions};
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
+
+use options::{QueryOptions, QueryParameter, ReadPoint, TransactionOptions};
 use resource::constants::server::{
-    DEFAULT_ANSWER_COUNT_LIMIT_GRPC, DEFAULT_INCLUDE_INSTANCE_TYPES, DEFAULT_PREFETCH_SIZE,
-    DEFAULT_SCHEMA_LOCK_ACQUIRE_TIMEOUT_MILLIS, DEFAULT_TRANSACTION_PARALLEL, DEFAULT_TRANSACTION_TIMEOUT_MILLIS,
+    DEFAULT_ANSWER_COUNT_LIMIT_GRPC, DEFAULT_COLLECTED_ROWS_LIMIT, DEFAULT_INCLUDE_INSTANCE_TYPES,
+    DEFAULT_PREFETCH_SIZE, DEFAULT_QUERY_EXPLAIN, DEFAULT_QUERY_PROFILE, DEFAULT_QUERY_TIMEOUT_MILLIS,
+    DEFAULT_SCHEMA_LOCK_ACQUIRE_TIMEOUT_MILLIS, DEFAULT_TABLED_ROWS_LIMIT, DEFAULT_TRANSACTION_PARALLEL,
+    DEFAULT_TRANSACTION_TIMEOUT_MILLIS,
 };
+use tonic::metadata::MetadataMap;
 use typedb_protocol::options::{Query as QueryOptionsProto, Transaction as TransactionOptionsProto};
 
+use crate::service::{grpc::error::GrpcServiceError, parse_read_timestamp};
+
+// The protocol's transaction options have no point-in-time field, so it is requested via the stream's metadata
+pub(crate) const READ_AT_SEQUENCE_NUMBER_METADATA: &str = "typedb-read-at-sequence-number";
+pub(crate) const READ_AT_TIMESTAMP_METADATA: &str = "typedb-read-at-timestamp";
+
+// The protocol's query options have no explain or profile fields, so they are requested via the query request's metadata
+pub(crate) const QUERY_EXPLAIN_METADATA: &str = "typedb-query-explain";
+pub(crate) const QUERY_PROFILE_METADATA: &str = "typedb-query-profile";
+// The profile is sent as a documents part after the answers, which clients of row queries do not otherwise expect, so a
+// profiled query must also declare that its client accepts that part
+pub(crate) const QUERY_PROFILE_PART_METADATA: &str = "typedb-query-profile-part";
+// Likewise for the per-query timeout and resource limits
+pub(crate) const QUERY_TIMEOUT_MILLIS_METADATA: &str = "typedb-query-timeout-millis";
+pub(crate) const QUERY_COLLECTED_ROWS_LIMIT_METADATA: &str = "typedb-query-collected-rows-limit";
+pub(crate) const QUERY_TABLED_ROWS_LIMIT_METADATA: &str = "typedb-query-tabled-rows-limit";
+// Likewise for bound parameters, each given as "<value type>:<value>" under this prefix followed by the variable name
+pub(crate) const QUERY_PARAMETER_METADATA_PREFIX: &str = "typedb-query-parameter-";
+
 pub(crate) fn transaction_options_from_proto(proto: Option<TransactionOptionsProto>) -> TransactionOptions {
     let Some(proto) = proto else {
         return TransactionOptions::default();
@@ -22,18 +49,177 @@ pub(crate) fn transaction_options_from_proto(proto: Option<TransactionOptionsPro
             .schema_lock_acquire_timeout_millis
             .unwrap_or(DEFAULT_SCHEMA_LOCK_ACQUIRE_TIMEOUT_MILLIS),
         transaction_timeout_millis: proto.transaction_timeout_millis.unwrap_or(DEFAULT_TRANSACTION_TIMEOUT_MILLIS),
+        read_point: None,
+    }
+}
+
+pub(crate) fn read_point_from_metadata(metadata: &MetadataMap) -> Result<Option<ReadPoint>, GrpcServiceError> {
+    let sequence_number = metadata_value(metadata, READ_AT_SEQUENCE_NUMBER_METADATA)?;
+    let timestamp = metadata_value(metadata, READ_AT_TIMESTAMP_METADATA)?;
+    match (sequence_number, timestamp) {
+        (None, None) => Ok(None),
+        (Some(sequence_number), None) => match sequence_number.parse::<u64>() {
+            Ok(number) => Ok(Some(ReadPoint::SequenceNumber(number))),
+            Err(_) => Err(GrpcServiceError::InvalidMetadata {
+                key: READ_AT_SEQUENCE_NUMBER_METADATA.to_string(),
+                value: sequence_number,
+            }),
+        },
+        (None, Some(timestamp)) => match parse_read_timestamp(&timestamp) {
+            Some(time) => Ok(Some(ReadPoint::Timestamp(time))),
+            None => {
+                Err(GrpcServiceError::InvalidMetadata { key: READ_AT_TIMESTAMP_METADATA.to_string(), value: timestamp })
+            }
+        },
+        (Some(_), Some(_)) => Err(GrpcServiceError::ConflictingMetadata {
+            first: READ_AT_SEQUENCE_NUMBER_METADATA.to_string(),
+            second: READ_AT_TIMESTAMP_METADATA.to_string(),
+        }),
+    }
+}
+
+fn metadata_value(metadata: &MetadataMap, key: &str) -> Result<Option<String>, GrpcServiceError> {
+    match metadata.get(key) {
+        None => Ok(None),
+        Some(value) => value
+            .to_str()
+            .map(|value| Some(value.to_string()))
+            .map_err(|_| GrpcServiceError::InvalidMetadata { key: key.to_string(), value: format!("{value:?}") }),
     }
 }
 
-pub(crate) fn query_options_from_proto(proto: Option<QueryOptionsProto>) -> QueryOptions {
+pub(crate) fn query_options_from_proto(
+    proto: Option<QueryOptionsProto>,
+    metadata: &HashMap<String, String>,
+) -> Result<QueryOptions, GrpcServiceError> {
+    let explain = request_metadata_flag(metadata, QUERY_EXPLAIN_METADATA)?.unwrap_or(DEFAULT_QUERY_EXPLAIN);
+    let profile = request_metadata_flag(metadata, QUERY_PROFILE_METADATA)?.unwrap_or(DEFAULT_QUERY_PROFILE);
+    if profile && request_metadata_flag(metadata, QUERY_PROFILE_PART_METADATA)? != Some(true) {
+        return Err(GrpcServiceError::RequiredMetadata {
+            key: QUERY_PROFILE_METADATA.to_string(),
+            required: QUERY_PROFILE_PART_METADATA.to_string(),
+        });
+    }
+    let query_timeout_millis =
+        request_metadata_value(metadata, QUERY_TIMEOUT_MILLIS_METADATA)?.or(DEFAULT_QUERY_TIMEOUT_MILLIS);
+    let collected_rows_limit =
+        request_metadata_value(metadata, QUERY_COLLECTED_ROWS_LIMIT_METADATA)?.or(DEFAULT_COLLECTED_ROWS_LIMIT);
+    let tabled_rows_limit =
+        request_metadata_value(metadata, QUERY_TABLED_ROWS_LIMIT_METADATA)?.or(DEFAULT_TABLED_ROWS_LIMIT);
+    let parameters = request_metadata_parameters(metadata)?;
     let Some(proto) = proto else {
-        return QueryOptions::default_grpc();
+        return Ok(QueryOptions {
+            explain,
+            profile,
+            query_timeout_millis,
+            collected_rows_limit,
+            tabled_rows_limit,
+            parameters,
+            ..QueryOptions::default_grpc()
+        });
     };
 
-    QueryOptions {
+    Ok(QueryOptions {
         include_instance_types: proto.include_instance_types.unwrap_or(DEFAULT_INCLUDE_INSTANCE_TYPES),
         answer_count_limit: DEFAULT_ANSWER_COUNT_LIMIT_GRPC,
         prefetch_size: proto.prefetch_size.map(|value| value as usize).unwrap_or(DEFAULT_PREFETCH_SIZE),
         include_query_structure: proto.include_query_structure.unwrap_or(false),
+        explain,
+        profile,
+        query_timeout_millis,
+        collected_rows_limit,
+        tabled_rows_limit,
+        parameters,
+    })
+}
+
+fn request_metadata_flag(metadata: &HashMap<String, String>, key: &str) -> Result<Option<bool>, GrpcServiceError> {
+    request_metadata_value(metadata, key)
+}
+
+fn request_metadata_value<T: FromStr>(
+    metadata: &HashMap<String, String>,
+    key: &str,
+) -> Result<Option<T>, GrpcServiceError> {
+    match metadata.get(key) {
+        None => Ok(None),
+        Some(value) => match value.parse::<T>() {
+            Ok(parsed) => Ok(Some(parsed)),
+            Err(_) => Err(GrpcServiceError::InvalidMetadata { key: key.to_string(), value: value.clone() }),
+        },
+    }
+}
+
+fn request_metadata_parameters(
+    metadata: &HashMap<String, String>,
+) -> Result<BTreeMap<String, QueryParameter>, GrpcServiceError> {
+    let mut parameters = BTreeMap::new();
+    for (key, value) in metadata {
+        let Some(variable) = key.strip_prefix(QUERY_PARAMETER_METADATA_PREFIX) else {
+            continue;
+        };
+        let Some((value_type, parameter_value)) = value.split_once(':') else {
+            return Err(GrpcServiceError::InvalidMetadata { key: key.clone(), value: value.clone() });
+        };
+        let parameter = QueryParameter { value_type: value_type.to_owned(), value: parameter_value.to_owned() };
+        parameters.insert(variable.to_owned(), parameter);
+    }
+    Ok(parameters)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{
+        QUERY_EXPLAIN_METADATA, QUERY_PROFILE_METADATA, QUERY_PROFILE_PART_METADATA, query_options_from_proto,
+    };
+    use crate::service::grpc::error::GrpcServiceError;
+
+    fn metadata(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect()
+    }
+
+    #[test]
+    fn explain_and_profile_are_read_from_metadata() {
+        let options = query_options_from_proto(None, &HashMap::new()).unwrap();
+        assert!(!options.explain && !options.profile);
+
+        let options = query_options_from_proto(None, &metadata(&[(QUERY_EXPLAIN_METADATA, "true")])).unwrap();
+        assert!(options.explain && !options.profile);
+
+        let profiled = metadata(&[(QUERY_PROFILE_METADATA, "true"), (QUERY_PROFILE_PART_METADATA, "true")]);
+        let options = query_options_from_proto(None, &profiled).unwrap();
+        assert!(!options.explain && options.profile);
+
+        let options = query_options_from_proto(None, &metadata(&[(QUERY_PROFILE_METADATA, "false")])).unwrap();
+        assert!(!options.profile);
+    }
+
+    #[test]
+    fn invalid_explain_or_profile_metadata_is_refused() {
+        for key in [QUERY_EXPLAIN_METADATA, QUERY_PROFILE_METADATA, QUERY_PROFILE_PART_METADATA] {
+            let result = query_options_from_proto(None, &metadata(&[(key, "yes")]));
+            assert!(
+                matches!(&result, Err(GrpcServiceError::InvalidMetadata { key: invalid_key, value }) if invalid_key == key && value == "yes"),
+                "'{key}: yes' should be refused: {result:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn profile_requires_accepting_the_profile_part() {
+        for profile_part in [None, Some("false")] {
+            let mut entries = vec![(QUERY_PROFILE_METADATA, "true")];
+            entries.extend(profile_part.map(|value| (QUERY_PROFILE_PART_METADATA, value)));
+            let result = query_options_from_proto(None, &metadata(&entries));
+            assert!(
+                matches!(&result, Err(GrpcServiceError::RequiredMetadata { required, .. }) if required == QUERY_PROFILE_PART_METADATA),
+                "profiling with '{QUERY_PROFILE_PART_METADATA}: {profile_part:?}' should be refused: {result:?}"
+            );
+        }
+
+        let options = query_options_from_proto(None, &metadata(&[(QUERY_PROFILE_PART_METADATA, "true")])).unwrap();
+        assert!(!options.profile);
     }
 }
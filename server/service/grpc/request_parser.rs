@@ -4,19 +4,18 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use system::concepts::{Credential, User};
+use system::concepts::User;
 use tonic::Request;
 use user::errors::{UserCreateError, UserUpdateError};
 
 pub fn users_create_req(
     request: Request<typedb_protocol::user_manager::create::Req>,
-) -> Result<(User, Credential), UserCreateError> {
+) -> Result<(User, String), UserCreateError> {
     let message = request.into_inner();
     match message.user {
         Some(typedb_protocol::User { name: username, password: Some(password) }) => {
             let user = User::new(username);
-            let credential = Credential::new_password(password.as_str());
-            Ok((user, credential))
+            Ok((user, password))
         }
         _ => Err(UserCreateError::IncompleteUserDetail {}),
     }
@@ -24,11 +23,11 @@ pub fn users_create_req(
 
 pub fn users_update_req(
     request: Request<typedb_protocol::user::update::Req>,
-) -> Result<(String, Option<User>, Option<Credential>), UserUpdateError> {
+) -> Result<(String, Option<User>, Option<String>), UserUpdateError> {
     let message = request.into_inner();
     match message.user {
         Some(typedb_protocol::User { name: username, password }) => {
-            Ok((message.name, Some(User::new(username)), password.map(|p| Credential::new_password(p.as_str()))))
+            Ok((message.name, Some(User::new(username)), password))
         }
         None => Err(UserUpdateError::UserDetailNotProvided {}),
     }
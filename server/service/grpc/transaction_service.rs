@@ -6,6 +6,7 @@
 
 use std::{
     collections::{HashMap, VecDeque},
+    mem,
     ops::{
         ControlFlow,
         ControlFlow::{Break, Continue},
@@ -17,10 +18,13 @@ use std::{
 use compiler::query_structure::PipelineStructure;
 use concept::{thing::thing_manager::ThingManager, type_::type_manager::TypeManager};
 use database::query::{
-    StreamQueryOutputDescriptor, WriteQueryAnswer, WriteQueryResult, execute_schema_query,
-    execute_write_query_in_schema, execute_write_query_in_write,
+    StreamQueryOutputDescriptor, WriteQueryAnswer, WriteQueryResult, execute_schema_query_profiled,
+    execute_write_query_in_schema, execute_write_query_in_write, execution_limits, query_parameters,
+};
+use diagnostics::{
+    audit,
+    metrics::{ActionKind, ClientEndpoint, LoadKind, QueryKind},
 };
-use diagnostics::metrics::{ActionKind, ClientEndpoint, LoadKind};
 use executor::{
     ExecutionInterrupt, InterruptType,
     batch::Batch,
@@ -30,7 +34,7 @@ use executor::{
 use ir::pipeline::ParameterRegistry;
 use itertools::{Either, Itertools};
 use lending_iterator::LendingIterator;
-use options::QueryOptions;
+use options::{QueryOptions, ReadPoint};
 use query::error::QueryError;
 use resource::profile::{EncodingProfile, QueryProfile, StorageCounters};
 use storage::snapshot::ReadableSnapshot;
@@ -56,10 +60,11 @@ use uuid::Uuid;
 use crate::{
     service::{
         TransactionType,
+        explain::{encode_query_plan, encode_query_profile},
         grpc::{
             analyze::{encode_analyzed_pipeline_for_query, encode_analyzed_query},
             diagnostics::run_with_diagnostics_async,
-            document::encode_document,
+            document::{encode_document, encode_json_document},
             error::{IntoGrpcStatus, IntoProtocolErrorMessage, ProtocolError},
             options::{query_options_from_proto, transaction_options_from_proto},
             response_builders::transaction::{
@@ -77,10 +82,10 @@ use crate::{
         may_encode_pipeline_structure,
         transaction_service::{
             Transaction, TransactionServiceError, commit_schema_transaction, commit_write_transaction,
-            init_transaction_timeout, is_write_pipeline, with_readable_transaction,
+            init_transaction_timeout, is_write_pipeline, submit_query_diagnostics, with_readable_transaction,
         },
     },
-    state::ServerState,
+    state::{RunningQuery, ServerState},
 };
 
 macro_rules! unwrap_or_execute_and_return {
@@ -120,6 +125,7 @@ macro_rules! send_ok_message_else_return_break {
 pub(crate) struct TransactionService {
     server_state: Arc<ServerState>,
     owner: String,
+    read_point: Option<ReadPoint>,
 
     request_stream: Streaming<typedb_protocol::transaction::Client>,
     response_sender: Sender<Result<ProtocolServer, Status>>,
@@ -143,6 +149,7 @@ impl TransactionService {
     pub(crate) fn new(
         server_state: Arc<ServerState>,
         owner: String,
+        read_point: Option<ReadPoint>,
         request_stream: Streaming<typedb_protocol::transaction::Client>,
         response_sender: Sender<Result<ProtocolServer, Status>>,
     ) -> Self {
@@ -152,6 +159,7 @@ impl TransactionService {
         Self {
             server_state,
             owner,
+            read_point,
 
             request_stream,
             response_sender,
@@ -282,7 +290,6 @@ impl TransactionService {
             Some(Ok(message)) => {
                 for request in message.reqs {
                     let request_id = Uuid::from_slice(&request.req_id).unwrap();
-                    let _metadata = request.metadata;
                     match request.req {
                         None => {
                             return Err(ProtocolError::MissingField {
@@ -291,7 +298,7 @@ impl TransactionService {
                             }
                             .into_status());
                         }
-                        Some(req) => match self.handle_request(request_id, req).await {
+                        Some(req) => match self.handle_request(request_id, req, request.metadata).await {
                             Err(err) => return Err(err),
                             Ok(Break(())) => return Ok(Break(())),
                             Ok(Continue(())) => {}
@@ -307,22 +314,29 @@ impl TransactionService {
         &mut self,
         request_id: Uuid,
         req: typedb_protocol::transaction::req::Req,
+        metadata: HashMap<String, String>,
     ) -> Result<ControlFlow<(), ()>, Status> {
         match (self.is_open, req) {
             (false, typedb_protocol::transaction::req::Req::OpenReq(open_req)) => {
-                run_with_diagnostics_async(
-                    self.server_state.diagnostics_manager().clone(),
-                    Some(open_req.database.clone()),
-                    ActionKind::TransactionOpen,
-                    || async {
-                        let result = self.handle_open(request_id, open_req).await;
-                        match &result {
-                            Ok(Continue(_)) => event!(Level::TRACE, "Transaction opened successfully."),
-                            Ok(Break(_)) => event!(Level::TRACE, "Transaction open aborted."),
-                            Err(status) => event!(Level::TRACE, "Error opening transaction: {}", status),
-                        }
-                        result
-                    },
+                let transaction_type = typedb_protocol::transaction::Type::try_from(open_req.r#type)
+                    .map(|transaction_type| decode_transaction_type(transaction_type).to_string())
+                    .unwrap_or_else(|_| open_req.r#type.to_string());
+                audit::with_transaction_type(
+                    transaction_type,
+                    run_with_diagnostics_async(
+                        self.server_state.diagnostics_manager().clone(),
+                        Some(open_req.database.clone()),
+                        ActionKind::TransactionOpen,
+                        || async {
+                            let result = self.handle_open(request_id, open_req).await;
+                            match &result {
+                                Ok(Continue(_)) => event!(Level::TRACE, "Transaction opened successfully."),
+                                Ok(Break(_)) => event!(Level::TRACE, "Transaction open aborted."),
+                                Err(status) => event!(Level::TRACE, "Error opening transaction: {}", status),
+                            }
+                            result
+                        },
+                    ),
                 )
                 .await
             }
@@ -330,11 +344,15 @@ impl TransactionService {
                 Err(ProtocolError::TransactionAlreadyOpen {}.into_status())
             }
             (true, typedb_protocol::transaction::req::Req::QueryReq(query_req)) => {
-                run_with_diagnostics_async(
-                    self.server_state.diagnostics_manager().clone(),
-                    self.get_database_name().map(|name| name.to_owned()),
-                    ActionKind::TransactionQuery,
-                    || async { self.handle_query(request_id, query_req).await },
+                let query = query_req.query.clone();
+                audit::with_query(
+                    query,
+                    run_with_diagnostics_async(
+                        self.server_state.diagnostics_manager().clone(),
+                        self.get_database_name().map(|name| name.to_owned()),
+                        ActionKind::TransactionQuery,
+                        || async { self.handle_query(request_id, query_req, &metadata).await },
+                    ),
                 )
                 .await
             }
@@ -437,7 +455,8 @@ impl TransactionService {
     ) -> Result<ControlFlow<(), ()>, Status> {
         let receive_time = Instant::now();
         self.network_latency_millis = Some(open_req.network_latency_millis);
-        let transaction_options = transaction_options_from_proto(open_req.options);
+        let mut transaction_options = transaction_options_from_proto(open_req.options);
+        transaction_options.read_point = self.read_point;
         let transaction_timeout_millis = transaction_options.transaction_timeout_millis;
 
         let transaction_type =
@@ -449,7 +468,14 @@ impl TransactionService {
         let transaction = self
             .server_state
             .transactions()
-            .open(&database_name, transaction_type, transaction_options, self.owner.clone(), self.close_sender.clone())
+            .open(
+                &database_name,
+                transaction_type,
+                transaction_options,
+                req_id,
+                self.owner.clone(),
+                self.close_sender.clone(),
+            )
             .await
             .map_err(|err| err.into_status())?;
 
@@ -735,12 +761,15 @@ impl TransactionService {
                 (QueueOptions::Analyze, _) => {
                     self.run_analyse_query(req_id, query_pipeline, source_query).await;
                 }
+                (QueueOptions::Explain(query_options), _) => {
+                    self.run_and_activate_explain_transmitter(req_id, query_options, query_pipeline, source_query);
+                }
                 (QueueOptions::Query(query_options), true) => {
                     self.run_write_query(req_id, query_options, query_pipeline, source_query).await;
                     return;
                 }
                 (QueueOptions::Query(query_options), false) => {
-                    self.run_and_activate_read_transmitter(req_id, query_options, query_pipeline, source_query);
+                    self.run_and_activate_read_transmitter(req_id, query_options, query_pipeline, source_query).await;
                 }
             }
         }
@@ -782,8 +811,15 @@ impl TransactionService {
         &mut self,
         req_id: Uuid,
         query_req: typedb_protocol::query::Req,
+        metadata: &HashMap<String, String>,
     ) -> Result<ControlFlow<(), ()>, Status> {
-        let query_options = query_options_from_proto(query_req.options);
+        let query_options = match query_options_from_proto(query_req.options, metadata) {
+            Ok(query_options) => query_options,
+            Err(err) => {
+                let response = ImmediateQueryResponse::non_fatal_err(err);
+                return Ok(Self::respond_query_response(&self.response_sender, req_id, response).await);
+            }
+        };
         if query_options.prefetch_size < 1 {
             let response = ImmediateQueryResponse::non_fatal_err(TransactionServiceError::InvalidPrefetchSize {
                 value: query_options.prefetch_size,
@@ -801,6 +837,27 @@ impl TransactionService {
             }
         };
         match parsed.into_structure() {
+            typeql::query::QueryStructure::Schema(_) if query_options.explain => {
+                let response =
+                    ImmediateQueryResponse::non_fatal_err(TransactionServiceError::ExplainQueryExpectsPipeline {});
+                Ok(Self::respond_query_response(&self.response_sender, req_id, response).await)
+            }
+            typeql::query::QueryStructure::Schema(_) if !query_options.parameters.is_empty() => {
+                let response =
+                    ImmediateQueryResponse::non_fatal_err(TransactionServiceError::SchemaQueryParametersUnsupported {});
+                Ok(Self::respond_query_response(&self.response_sender, req_id, response).await)
+            }
+            typeql::query::QueryStructure::Pipeline(pipeline) if query_options.explain => {
+                if !self.query_queue.is_empty() || self.running_write_query.is_some() {
+                    self.query_queue.push_back((req_id, QueueOptions::Explain(query_options), pipeline, query));
+                    // queued queries are not handled yet so there will be no query response yet
+                    Ok(Continue(()))
+                } else {
+                    self.run_and_activate_explain_transmitter(req_id, query_options, pipeline, query);
+                    // explained queries have no response on the main loop and will respond asynchronously
+                    Ok(Continue(()))
+                }
+            }
             typeql::query::QueryStructure::Schema(schema_query) => {
                 // schema queries are handled immediately so there is a query response or a fatal Status
                 let response = self.handle_query_schema(schema_query, query).await?;
@@ -823,7 +880,7 @@ impl TransactionService {
                         // queued queries are not handled yet so there will be no query response yet
                         Ok(Continue(()))
                     } else {
-                        self.run_and_activate_read_transmitter(req_id, query_options, pipeline, query);
+                        self.run_and_activate_read_transmitter(req_id, query_options, pipeline, query).await;
                         // running read queries have no response on the main loop and will respond asynchronously
                         Ok(Continue(()))
                     }
@@ -844,10 +901,23 @@ impl TransactionService {
         if let Some(transaction) = self.transaction.take() {
             match transaction {
                 Transaction::Schema(schema_transaction) => {
-                    let (transaction, result) =
-                        spawn_blocking(move || execute_schema_query(schema_transaction, query, source_query))
-                            .await
-                            .expect("Expected schema query execution finishing");
+                    let database_name = schema_transaction.database.name().to_owned();
+                    let start = Instant::now();
+                    let executed_query = source_query.clone();
+                    let (transaction, query_profile, result) = spawn_blocking(move || {
+                        execute_schema_query_profiled(schema_transaction, query, executed_query)
+                    })
+                    .await
+                    .expect("Expected schema query execution finishing");
+                    submit_query_diagnostics(
+                        &self.server_state.diagnostics_manager(),
+                        &database_name,
+                        &self.owner,
+                        QueryKind::Schema,
+                        &source_query,
+                        Some(&query_profile),
+                        start,
+                    );
                     self.transaction = Some(Transaction::Schema(transaction));
                     let message_ok_done =
                         result.map(|_| query_res_ok_done(typedb_protocol::query::Type::Schema)).map_err(|err| {
@@ -906,7 +976,14 @@ impl TransactionService {
     ) {
         debug_assert!(self.running_write_query.is_none());
         self.interrupt_and_close_responders(InterruptType::WriteQueryExecution).await;
-        let handle = match self.spawn_blocking_execute_write_query(query_options, pipeline, source_query) {
+        let (running_query, interrupt) = self.register_query(&query_options, &source_query).await;
+        let handle = match self.spawn_blocking_execute_write_query(
+            query_options,
+            pipeline,
+            source_query,
+            running_query,
+            interrupt,
+        ) {
             Ok(handle) => {
                 // running write queries have no valid response yet (until they finish) and will respond asynchronously
                 handle
@@ -939,7 +1016,7 @@ impl TransactionService {
         self.query_responders.insert(req_id, (answer_reader, stream_transmitter));
     }
 
-    fn run_and_activate_read_transmitter(
+    async fn run_and_activate_read_transmitter(
         &mut self,
         req_id: Uuid,
         query_options: QueryOptions,
@@ -948,7 +1025,9 @@ impl TransactionService {
     ) {
         let prefetch_size = query_options.prefetch_size;
         let (sender, receiver) = channel(prefetch_size);
-        let worker_handle = self.blocking_read_query_worker(sender, query_options, pipeline, source_query);
+        let (running_query, interrupt) = self.register_query(&query_options, &source_query).await;
+        let worker_handle =
+            self.blocking_read_query_worker(sender, query_options, pipeline, source_query, running_query, interrupt);
         let stream_transmitter = QueryStreamTransmitter::start_new(
             self.response_sender.clone(),
             receiver,
@@ -959,24 +1038,99 @@ impl TransactionService {
         self.query_responders.insert(req_id, (worker_handle, stream_transmitter));
     }
 
+    fn run_and_activate_explain_transmitter(
+        &mut self,
+        req_id: Uuid,
+        query_options: QueryOptions,
+        pipeline: typeql::query::Pipeline,
+        source_query: String,
+    ) {
+        let prefetch_size = query_options.prefetch_size;
+        let (sender, receiver) = channel(prefetch_size);
+        let worker_handle = self.blocking_explain_query_worker(sender, query_options, pipeline, source_query);
+        let stream_transmitter = QueryStreamTransmitter::start_new(
+            self.response_sender.clone(),
+            receiver,
+            req_id,
+            prefetch_size,
+            self.network_latency_millis.unwrap() as usize,
+        );
+        self.query_responders.insert(req_id, (worker_handle, stream_transmitter));
+    }
+
+    async fn register_query(
+        &self,
+        query_options: &QueryOptions,
+        source_query: &str,
+    ) -> (RunningQuery, ExecutionInterrupt) {
+        let transaction_id = self.transaction.as_ref().unwrap().id();
+        let interrupt = match query_options.query_timeout_millis {
+            Some(millis) => {
+                let deadline = Instant::now() + Duration::from_millis(millis);
+                self.query_interrupt_receiver.clone().with_deadline(deadline.into_std())
+            }
+            None => self.query_interrupt_receiver.clone(),
+        };
+        self.server_state.transactions().query_register(transaction_id, source_query, interrupt).await
+    }
+
     fn spawn_blocking_execute_write_query(
         &mut self,
         query_options: QueryOptions,
         pipeline: typeql::query::Pipeline,
         source_query: String,
+        running_query: RunningQuery,
+        interrupt: ExecutionInterrupt,
     ) -> Result<JoinHandle<(Transaction, WriteQueryResult)>, TransactionServiceError> {
         debug_assert!(self.running_write_query.is_none());
         debug_assert!(self.transaction.is_some());
-        let interrupt = self.query_interrupt_receiver.clone();
+        let diagnostics_manager = self.server_state.diagnostics_manager();
+        let owner = self.owner.clone();
         match self.transaction.take() {
             Some(Transaction::Schema(schema_transaction)) => Ok(spawn_blocking(move || {
-                let (transaction, result) =
-                    execute_write_query_in_schema(schema_transaction, query_options, pipeline, source_query, interrupt);
+                let database_name = schema_transaction.database.name().to_owned();
+                let start = Instant::now();
+                let (transaction, result) = execute_write_query_in_schema(
+                    schema_transaction,
+                    query_options,
+                    pipeline,
+                    source_query.clone(),
+                    interrupt,
+                );
+                drop(running_query);
+                let profile = result.as_ref().ok().map(|answer| answer.query_profile.as_ref());
+                submit_query_diagnostics(
+                    &diagnostics_manager,
+                    &database_name,
+                    &owner,
+                    QueryKind::Write,
+                    &source_query,
+                    profile,
+                    start,
+                );
                 (Transaction::Schema(transaction), result)
             })),
             Some(Transaction::Write(write_transaction)) => Ok(spawn_blocking(move || {
-                let (transaction, result) =
-                    execute_write_query_in_write(write_transaction, query_options, pipeline, source_query, interrupt);
+                let database_name = write_transaction.database.name().to_owned();
+                let start = Instant::now();
+                let (transaction, result) = execute_write_query_in_write(
+                    write_transaction,
+                    query_options,
+                    pipeline,
+                    source_query.clone(),
+                    interrupt,
+                );
+                drop(running_query);
+                let profile = result.as_ref().ok().map(|answer| answer.query_profile.as_ref());
+                submit_query_diagnostics(
+                    &diagnostics_manager,
+                    &database_name,
+                    &owner,
+                    QueryKind::Write,
+                    &source_query,
+                    profile,
+                    start,
+                );
                 (Transaction::Write(transaction), result)
             })),
             Some(Transaction::Read(transaction)) => {
@@ -1001,6 +1155,7 @@ impl TransactionService {
             let interrupt = self.query_interrupt_receiver.clone();
             tokio::spawn(async move {
                 let encoding_profile = EncodingProfile::new(tracing::enabled!(Level::TRACE));
+                let query_profile = answer.query_options.profile.then_some(answer.query_profile);
                 match answer.answer {
                     Either::Left((output_descriptor, batch, pipeline_structure)) => {
                         Self::submit_write_query_batch_answer(
@@ -1011,6 +1166,7 @@ impl TransactionService {
                             answer.query_options,
                             batch,
                             pipeline_structure.as_ref(),
+                            query_profile,
                             sender,
                             timeout_at,
                             interrupt,
@@ -1025,6 +1181,7 @@ impl TransactionService {
                             thing_manager,
                             parameters,
                             documents,
+                            query_profile,
                             sender,
                             timeout_at,
                             interrupt,
@@ -1045,6 +1202,7 @@ impl TransactionService {
         query_options: QueryOptions,
         batch: Batch,
         pipeline_structure: Option<&PipelineStructure>,
+        query_profile: Option<Arc<QueryProfile>>,
         sender: Sender<StreamQueryResponse>,
         timeout_at: Instant,
         mut interrupt: ExecutionInterrupt,
@@ -1108,6 +1266,9 @@ impl TransactionService {
                 }
             }
         }
+        if let Some(query_profile) = query_profile {
+            Self::submit_response_async(&sender, StreamQueryResponse::profile(&query_profile)).await;
+        }
         Self::submit_response_async(&sender, StreamQueryResponse::done_ok()).await
     }
 
@@ -1117,6 +1278,7 @@ impl TransactionService {
         thing_manager: Arc<ThingManager>,
         parameters: Arc<ParameterRegistry>,
         documents: Vec<ConceptDocument>,
+        query_profile: Option<Arc<QueryProfile>>,
         sender: Sender<StreamQueryResponse>,
         timeout_at: Instant,
         mut interrupt: ExecutionInterrupt,
@@ -1164,6 +1326,9 @@ impl TransactionService {
                 }
             }
         }
+        if let Some(query_profile) = query_profile {
+            Self::submit_response_async(&sender, StreamQueryResponse::profile(&query_profile)).await;
+        }
         Self::submit_response_async(&sender, StreamQueryResponse::done_ok()).await
     }
 
@@ -1173,16 +1338,25 @@ impl TransactionService {
         query_options: QueryOptions,
         pipeline: typeql::query::Pipeline,
         source_query: String,
+        running_query: RunningQuery,
+        interrupt: ExecutionInterrupt,
     ) -> JoinHandle<()> {
         debug_assert!(self.query_queue.is_empty() && self.running_write_query.is_none() && self.transaction.is_some());
         let timeout_at = self.timeout_at;
-        let interrupt = self.query_interrupt_receiver.clone();
+        let diagnostics_manager = self.server_state.diagnostics_manager();
+        let owner = self.owner.clone();
         with_readable_transaction!(self.transaction.as_ref().unwrap(), |transaction| {
+            let database_name = transaction.database.name().to_owned();
             let snapshot = transaction.snapshot.clone();
             let type_manager = transaction.type_manager.clone();
             let thing_manager = transaction.thing_manager.clone();
             let function_manager = transaction.function_manager.clone();
-            let query_manager = transaction.query_manager.clone();
+            let query_manager = match query_options.profile {
+                true => transaction.query_manager.profiled(),
+                false => transaction.query_manager.clone(),
+            };
+            let query_manager =
+                query_manager.limited(execution_limits(&query_options)).bound(query_parameters(&query_options));
             spawn_blocking(move || {
                 let start_time = Instant::now();
                 let pipeline = query_manager.prepare_read_pipeline(
@@ -1196,6 +1370,7 @@ impl TransactionService {
                 let pipeline = unwrap_or_execute_and_return!(pipeline, |err| {
                     Self::submit_response_sync(&sender, StreamQueryResponse::done_err(err));
                 });
+                let query_profile = pipeline.query_profile().clone();
                 Self::respond_read_query_sync(
                     query_options,
                     pipeline,
@@ -1208,6 +1383,21 @@ impl TransactionService {
                     thing_manager,
                     start_time,
                 );
+                drop(running_query);
+                // The last answers may still be queued for the client: the query is timed until the transmitter
+                // has streamed them all and dropped its receiver
+                tokio::spawn(async move {
+                    sender.closed().await;
+                    submit_query_diagnostics(
+                        &diagnostics_manager,
+                        &database_name,
+                        &owner,
+                        QueryKind::Read,
+                        &source_query,
+                        Some(&query_profile),
+                        start_time,
+                    );
+                });
             })
         })
     }
@@ -1358,7 +1548,7 @@ impl TransactionService {
             }
         }
 
-        if query_profile.is_enabled() {
+        if tracing::enabled!(Level::TRACE) {
             let micros = Instant::now().duration_since(start_time).as_micros();
             event!(
                 Level::INFO,
@@ -1368,9 +1558,59 @@ impl TransactionService {
                 encoding_profile
             );
         }
+        if query_options.profile {
+            Self::submit_response_sync(sender, StreamQueryResponse::profile(&query_profile));
+        }
         Self::submit_response_sync(sender, StreamQueryResponse::done_ok())
     }
 
+    fn blocking_explain_query_worker(
+        &self,
+        sender: Sender<StreamQueryResponse>,
+        query_options: QueryOptions,
+        pipeline: typeql::query::Pipeline,
+        source_query: String,
+    ) -> JoinHandle<()> {
+        debug_assert!(self.query_queue.is_empty() && self.running_write_query.is_none() && self.transaction.is_some());
+        with_readable_transaction!(self.transaction.as_ref().unwrap(), |transaction| {
+            let snapshot = transaction.snapshot.clone();
+            let type_manager = transaction.type_manager.clone();
+            let thing_manager = transaction.thing_manager.clone();
+            let function_manager = transaction.function_manager.clone();
+            let query_manager = transaction.query_manager.bound(query_parameters(&query_options));
+            spawn_blocking(move || {
+                let query_type = match is_write_pipeline(&pipeline) {
+                    true => Write,
+                    false => Read,
+                };
+                let plan = query_manager.explain(
+                    snapshot.as_ref(),
+                    &type_manager,
+                    thing_manager,
+                    &function_manager,
+                    &pipeline,
+                    &source_query,
+                );
+                let plan = unwrap_or_execute_and_return!(plan, |typedb_source| {
+                    Self::submit_response_sync(
+                        &sender,
+                        StreamQueryResponse::init_err(TransactionServiceError::ExplainQueryFailed {
+                            typedb_source: *typedb_source,
+                        }),
+                    );
+                });
+                let encoded_plan =
+                    serde_json::to_value(encode_query_plan(plan)).expect("Expected query plan to encode as JSON");
+                Self::submit_response_sync(&sender, StreamQueryResponse::init_ok_documents(query_type));
+                Self::submit_response_sync(
+                    &sender,
+                    StreamQueryResponse::next_document(encode_json_document(encoded_plan)),
+                );
+                Self::submit_response_sync(&sender, StreamQueryResponse::done_ok());
+            })
+        })
+    }
+
     fn submit_response_sync(sender: &Sender<StreamQueryResponse>, response: StreamQueryResponse) {
         if let Err(err) = sender.blocking_send(response) {
             event!(Level::DEBUG, "Failed to send error message: {:?}", err)
@@ -1457,6 +1697,7 @@ enum StreamQueryResponse {
     // stream responses
     StreamNextRow(typedb_protocol::ConceptRow),
     StreamNextDocument(typedb_protocol::ConceptDocument),
+    StreamProfile(typedb_protocol::ConceptDocument),
     StreamDoneOk(),
     StreamDoneErr(typedb_protocol::Error),
 }
@@ -1489,6 +1730,15 @@ impl StreamQueryResponse {
         Self::StreamNextDocument(document)
     }
 
+    // the profile of a profiled query follows all of its answers, in a query part of its own holding a single
+    // document keyed by `profile`, in both row and document streams. Queries are only profiled once their client has
+    // declared that it accepts this part, see `QUERY_PROFILE_PART_METADATA`
+    fn profile(profile: &QueryProfile) -> Self {
+        let encoded_profile =
+            serde_json::to_value(encode_query_profile(profile)).expect("Expected query profile to encode as JSON");
+        Self::StreamProfile(encode_json_document(serde_json::json!({ "profile": encoded_profile })))
+    }
+
     fn done_ok() -> Self {
         Self::StreamDoneOk()
     }
@@ -1678,24 +1928,23 @@ impl QueryStreamTransmitter {
                     }
                     StreamQueryResponse::StreamNextRow(concept_row) => rows.push(concept_row),
                     StreamQueryResponse::StreamNextDocument(concept_document) => documents.push(concept_document),
+                    StreamQueryResponse::StreamProfile(profile) => {
+                        let (rows, documents) = (mem::take(&mut rows), mem::take(&mut documents));
+                        if let Break(()) = Self::send_on_stream_done(response_sender, req_id, rows, documents).await {
+                            return Break(());
+                        }
+                        if let Break(()) = Self::send_documents(response_sender, req_id, vec![profile]).await {
+                            return Break(());
+                        }
+                    }
                 },
             }
             iteration += 1;
         }
 
-        debug_assert!(rows.is_empty() || documents.is_empty());
-        if !rows.is_empty() {
-            match Self::send_rows(response_sender, req_id, rows).await {
-                Continue(_) => Continue(query_response_receiver),
-                Break(_) => Break(()),
-            }
-        } else if !documents.is_empty() {
-            match Self::send_documents(response_sender, req_id, documents).await {
-                Continue(_) => Continue(query_response_receiver),
-                Break(_) => Break(()),
-            }
-        } else {
-            Continue(query_response_receiver)
+        match Self::send_on_stream_done(response_sender, req_id, rows, documents).await {
+            Continue(_) => Continue(query_response_receiver),
+            Break(_) => Break(()),
         }
     }
 
@@ -1746,6 +1995,7 @@ impl QueryStreamTransmitter {
 enum QueueOptions {
     Query(QueryOptions),
     Analyze,
+    Explain(QueryOptions),
 }
 
 impl QueueOptions {
@@ -6,7 +6,7 @@
 
 use std::{pin::Pin, sync::Arc, time::Instant};
 
-use diagnostics::metrics::ActionKind;
+use diagnostics::{audit, metrics::ActionKind};
 use tokio::sync::mpsc::channel;
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status, Streaming};
@@ -20,7 +20,7 @@ use typedb_protocol::{
 use uuid::Uuid;
 
 use crate::{
-    authentication::{Accessor, AuthenticationError},
+    authentication::{Accessor, AuthenticationError, PasswordChangeOnly},
     error::LocalServerStateError,
     service::{
         grpc::{
@@ -31,6 +31,7 @@ use crate::{
                 export_service::{DATABASE_EXPORT_REQUEST_BUFFER_SIZE, DatabaseExportService},
                 import_service::{DatabaseImportService, IMPORT_RESPONSE_BUFFER_SIZE},
             },
+            options::read_point_from_metadata,
             request_parser::{users_create_req, users_update_req},
             response_builders::{
                 authentication::token_create_res,
@@ -61,6 +62,20 @@ impl GRPCTypeDBService {
         Self { server_state }
     }
 
+    // Requests authenticated by a password change token may only concern the token's owner. Returns whether the
+    // request is restricted to changing the password.
+    fn check_password_change_only<T>(
+        request: &Request<T>,
+        accessor: &Accessor,
+        username: &str,
+    ) -> Result<bool, Status> {
+        let is_password_change_only = request.extensions().get::<PasswordChangeOnly>().is_some();
+        if is_password_change_only && accessor.as_str() != username {
+            return Err(password_change_required_status());
+        }
+        Ok(is_password_change_only)
+    }
+
     async fn servers_statuses(&self) -> Result<Vec<typedb_protocol::Server>, Status> {
         let statuses = self.server_state.servers().statuses().await.map_err(|err| err.into_status())?;
         Ok(statuses.into_iter().map(|status| status.to_proto()).collect())
@@ -112,7 +127,7 @@ impl typedb_protocol::type_db_server::TypeDb for GRPCTypeDBService {
                 let token = self
                     .server_state
                     .users()
-                    .token_create(password_credentials.username, password_credentials.password)
+                    .token_create_allowing_password_change(password_credentials.username, password_credentials.password)
                     .await
                     .map_err(|err| err.into_status())?;
 
@@ -139,7 +154,13 @@ impl typedb_protocol::type_db_server::TypeDb for GRPCTypeDBService {
         &self,
         request: Request<typedb_protocol::authentication::token::create::Req>,
     ) -> Result<Response<typedb_protocol::authentication::token::create::Res>, Status> {
-        run_with_diagnostics_async(
+        let username = match &request.get_ref().credentials {
+            Some(typedb_protocol::authentication::token::create::req::Credentials::Password(password_credentials)) => {
+                Some(password_credentials.username.clone())
+            }
+            None => None,
+        };
+        let response = run_with_diagnostics_async(
             self.server_state.diagnostics_manager(),
             None::<&str>,
             ActionKind::SignIn,
@@ -157,13 +178,16 @@ impl typedb_protocol::type_db_server::TypeDb for GRPCTypeDBService {
 
                 self.server_state
                     .users()
-                    .token_create(password_credentials.username, password_credentials.password)
+                    .token_create_allowing_password_change(password_credentials.username, password_credentials.password)
                     .await
                     .map(|result| Response::new(token_create_res(result)))
                     .map_err(|err| err.into_status())
             },
-        )
-        .await
+        );
+        match username {
+            Some(username) => audit::with_accessor(username, response).await,
+            None => response.await,
+        }
     }
 
     async fn servers_all(
@@ -212,7 +236,8 @@ impl typedb_protocol::type_db_server::TypeDb for GRPCTypeDBService {
         &self,
         request: Request<typedb_protocol::user_manager::get::Req>,
     ) -> Result<Response<typedb_protocol::user_manager::get::Res>, Status> {
-        run_with_diagnostics_async(
+        let username = request.get_ref().name.clone();
+        let response = run_with_diagnostics_async(
             self.server_state.diagnostics_manager(),
             None::<&str>,
             ActionKind::UsersGet,
@@ -220,6 +245,7 @@ impl typedb_protocol::type_db_server::TypeDb for GRPCTypeDBService {
                 let accessor = Accessor::from_extensions(&request.extensions()).map_err(|typedb_source| {
                     LocalServerStateError::AuthenticationError { typedb_source }.into_status()
                 })?;
+                Self::check_password_change_only(&request, &accessor, &request.get_ref().name)?;
                 let name = request.into_inner().name;
                 self.server_state
                     .users()
@@ -228,8 +254,8 @@ impl typedb_protocol::type_db_server::TypeDb for GRPCTypeDBService {
                     .map(|user| Ok(Response::new(users_get_res(user))))
                     .map_err(|err| err.into_status())?
             },
-        )
-        .await
+        );
+        audit::with_target_user(username, response).await
     }
 
     async fn users_all(
@@ -259,7 +285,8 @@ impl typedb_protocol::type_db_server::TypeDb for GRPCTypeDBService {
         &self,
         request: Request<typedb_protocol::user_manager::contains::Req>,
     ) -> Result<Response<typedb_protocol::user_manager::contains::Res>, Status> {
-        run_with_diagnostics_async(
+        let username = request.get_ref().name.clone();
+        let response = run_with_diagnostics_async(
             self.server_state.diagnostics_manager(),
             None::<&str>,
             ActionKind::UsersContains,
@@ -275,15 +302,16 @@ impl typedb_protocol::type_db_server::TypeDb for GRPCTypeDBService {
                     .map(|contains| Response::new(users_contains_res(contains)))
                     .map_err(|err| err.into_status())
             },
-        )
-        .await
+        );
+        audit::with_target_user(username, response).await
     }
 
     async fn users_create(
         &self,
         request: Request<typedb_protocol::user_manager::create::Req>,
     ) -> Result<Response<typedb_protocol::user_manager::create::Res>, Status> {
-        run_with_diagnostics_async(
+        let username = request.get_ref().user.as_ref().map(|user| user.name.clone()).unwrap_or_default();
+        let response = run_with_diagnostics_async(
             self.server_state.diagnostics_manager(),
             None::<&str>,
             ActionKind::UsersCreate,
@@ -291,26 +319,27 @@ impl typedb_protocol::type_db_server::TypeDb for GRPCTypeDBService {
                 let accessor = Accessor::from_extensions(&request.extensions()).map_err(|typedb_source| {
                     LocalServerStateError::AuthenticationError { typedb_source }.into_status()
                 })?;
-                let (user, credential) = users_create_req(request).map_err(|typedb_source| {
+                let (user, password) = users_create_req(request).map_err(|typedb_source| {
                     LocalServerStateError::UserCannotBeCreated { typedb_source }.into_status()
                 })?;
 
                 self.server_state
                     .users()
-                    .create(accessor, user, credential)
+                    .create(accessor, user, password)
                     .await
                     .map(|_| Response::new(user_create_res()))
                     .map_err(|err| err.into_status())
             },
-        )
-        .await
+        );
+        audit::with_target_user(username, response).await
     }
 
     async fn users_update(
         &self,
         request: Request<typedb_protocol::user::update::Req>,
     ) -> Result<Response<typedb_protocol::user::update::Res>, Status> {
-        run_with_diagnostics_async(
+        let username = request.get_ref().name.clone();
+        let response = run_with_diagnostics_async(
             self.server_state.diagnostics_manager(),
             None::<&str>,
             ActionKind::UsersUpdate,
@@ -318,27 +347,34 @@ impl typedb_protocol::type_db_server::TypeDb for GRPCTypeDBService {
                 let accessor = Accessor::from_extensions(&request.extensions()).map_err(|typedb_source| {
                     LocalServerStateError::AuthenticationError { typedb_source }.into_status()
                 })?;
-                let (username, user_update, credential_update) =
-                    users_update_req(request).map_err(|typedb_source| {
-                        LocalServerStateError::UserCannotBeUpdated { typedb_source }.into_status()
-                    })?;
+                let is_password_change_only =
+                    Self::check_password_change_only(&request, &accessor, &request.get_ref().name)?;
+                let (username, user_update, password_update) = users_update_req(request).map_err(|typedb_source| {
+                    LocalServerStateError::UserCannotBeUpdated { typedb_source }.into_status()
+                })?;
+                let is_password_change =
+                    password_update.is_some() && user_update.as_ref().map_or(true, |user| user.name == username);
+                if is_password_change_only && !is_password_change {
+                    return Err(password_change_required_status());
+                }
 
                 self.server_state
                     .users()
-                    .update(accessor, &username, user_update, credential_update)
+                    .update(accessor, &username, user_update, password_update)
                     .await
                     .map(|_| Response::new(user_update_res()))
                     .map_err(|err| err.into_status())
             },
-        )
-        .await
+        );
+        audit::with_target_user(username, response).await
     }
 
     async fn users_delete(
         &self,
         request: Request<typedb_protocol::user::delete::Req>,
     ) -> Result<Response<typedb_protocol::user::delete::Res>, Status> {
-        run_with_diagnostics_async(
+        let username = request.get_ref().name.clone();
+        let response = run_with_diagnostics_async(
             self.server_state.diagnostics_manager(),
             None::<&str>,
             ActionKind::UsersDelete,
@@ -355,8 +391,8 @@ impl typedb_protocol::type_db_server::TypeDb for GRPCTypeDBService {
                     .map(|_| Response::new(users_delete_res()))
                     .map_err(|err| err.into_status())
             },
-        )
-        .await
+        );
+        audit::with_target_user(username, response).await
     }
 
     async fn databases_get(
@@ -420,6 +456,8 @@ impl typedb_protocol::type_db_server::TypeDb for GRPCTypeDBService {
         &self,
         request: Request<typedb_protocol::database_manager::create::Req>,
     ) -> Result<Response<typedb_protocol::database_manager::create::Res>, Status> {
+        let accessor = Accessor::from_extensions(&request.extensions())
+            .map_err(|typedb_source| LocalServerStateError::AuthenticationError { typedb_source }.into_status())?;
         let name = request.into_inner().name;
         run_with_diagnostics_async(
             self.server_state.diagnostics_manager(),
@@ -428,7 +466,7 @@ impl typedb_protocol::type_db_server::TypeDb for GRPCTypeDBService {
             || async {
                 self.server_state
                     .databases()
-                    .create(&name)
+                    .create(accessor, &name)
                     .await
                     .map(|_| Response::new(database_create_res(name)))
                     .map_err(|err| err.into_status())
@@ -507,6 +545,8 @@ impl typedb_protocol::type_db_server::TypeDb for GRPCTypeDBService {
         &self,
         request: Request<typedb_protocol::database::delete::Req>,
     ) -> Result<Response<typedb_protocol::database::delete::Res>, Status> {
+        let accessor = Accessor::from_extensions(&request.extensions())
+            .map_err(|typedb_source| LocalServerStateError::AuthenticationError { typedb_source }.into_status())?;
         let name = request.into_inner().name;
         run_with_diagnostics_async(
             self.server_state.diagnostics_manager(),
@@ -515,7 +555,7 @@ impl typedb_protocol::type_db_server::TypeDb for GRPCTypeDBService {
             || async {
                 self.server_state
                     .databases()
-                    .delete(&name)
+                    .delete(accessor, &name)
                     .await
                     .map(|_| Response::new(database_delete_res()))
                     .map_err(|err| err.into_status())
@@ -573,10 +613,18 @@ impl typedb_protocol::type_db_server::TypeDb for GRPCTypeDBService {
     ) -> Result<Response<Self::transactionStream>, Status> {
         let Accessor(accessor) = Accessor::from_extensions(&request.extensions())
             .map_err(|typedb_source| LocalServerStateError::AuthenticationError { typedb_source }.into_status())?;
+        let read_point = read_point_from_metadata(request.metadata())
+            .map_err(|typedb_source| typedb_source.into_proto_error_message().into_status())?;
         let request_stream = request.into_inner();
         let (response_sender, response_receiver) = channel(TRANSACTION_REQUEST_BUFFER_SIZE);
-        let mut service = TransactionService::new(self.server_state.clone(), accessor, request_stream, response_sender);
-        tokio::spawn(async move { service.listen().await });
+        let mut service = TransactionService::new(
+            self.server_state.clone(),
+            accessor.clone(),
+            read_point,
+            request_stream,
+            response_sender,
+        );
+        tokio::spawn(audit::with_accessor(accessor, async move { service.listen().await }));
         let stream: ReceiverStream<Result<TransactionServerProto, Status>> = ReceiverStream::new(response_receiver);
         Ok(Response::new(Box::pin(stream)))
     }
@@ -585,3 +633,8 @@ impl typedb_protocol::type_db_server::TypeDb for GRPCTypeDBService {
 fn generate_connection_id() -> ConnectionID {
     Uuid::new_v4().into_bytes()
 }
+
+fn password_change_required_status() -> Status {
+    LocalServerStateError::AuthenticationError { typedb_source: AuthenticationError::PasswordChangeRequired {} }
+        .into_status()
+}
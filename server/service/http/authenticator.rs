@@ -6,13 +6,13 @@
 use std::{convert, sync::Arc};
 
 use axum::{body::Body, response::IntoResponse};
-use diagnostics::metrics::ActionKind;
+use diagnostics::{audit, metrics::ActionKind};
 use futures::future::BoxFuture;
 use http::{Request, Response};
 use tower::{Layer, Service};
 
 use crate::{
-    authentication::authenticate,
+    authentication::{Accessor, authenticate},
     service::http::{diagnostics::run_with_diagnostics_async, error::HttpServiceError},
     state::ServerState,
 };
@@ -84,7 +84,10 @@ where
         let mut inner = self.inner.clone();
         Box::pin(async move {
             match authenticator.authenticate(request).await {
-                Ok(req) => inner.call(req).await,
+                Ok(req) => match req.extensions().get::<Accessor>().cloned() {
+                    Some(Accessor(accessor)) => audit::with_accessor(accessor, inner.call(req)).await,
+                    None => inner.call(req).await,
+                },
                 Err(err) => Ok(err.into_response()),
             }
         })
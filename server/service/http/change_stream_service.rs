@@ -0,0 +1,101 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::{convert::Infallible, sync::Arc, time::Duration};
+
+use database::change_stream::{ChangeStream, CommittedChanges};
+use serde::Serialize;
+use storage::durability_client::WALClient;
+use tokio::sync::{mpsc::Sender, watch};
+use tracing::{Level, event};
+
+use crate::{
+    error::LocalServerStateError,
+    service::http::{
+        error::HttpServiceError,
+        message::{change::encode_committed_changes, error::encode_error},
+    },
+};
+
+pub(crate) const CHANGE_STREAM_BUFFER_SIZE: usize = 64;
+
+pub(crate) type ChangeLineSender = Sender<Result<String, Infallible>>;
+
+/// Streams the changes committed to a database as JSON lines, one line per commit. If reading the changes fails
+/// after the response has started, the error is sent as the final line.
+///
+/// Changes are only served over HTTP: the gRPC protocol shared with the drivers has no messages for them, and the
+/// admin gRPC service only listens on localhost, so it cannot serve remote consumers either.
+pub(crate) struct ChangeStreamService {
+    stream: ChangeStream<WALClient>,
+    line_sender: ChangeLineSender,
+    shutdown_receiver: watch::Receiver<()>,
+}
+
+impl ChangeStreamService {
+    const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+    pub(crate) fn new(
+        stream: ChangeStream<WALClient>,
+        line_sender: ChangeLineSender,
+        shutdown_receiver: watch::Receiver<()>,
+    ) -> Self {
+        Self { stream, line_sender, shutdown_receiver }
+    }
+
+    pub(crate) async fn listen(mut self) {
+        loop {
+            let (stream, result) = tokio::task::spawn_blocking(move || {
+                let mut stream = self.stream;
+                let result = stream.poll();
+                (stream, result)
+            })
+            .await
+            .expect("Change stream poll task panicked");
+            self.stream = stream;
+
+            match result {
+                Ok(changes) => {
+                    for committed in changes {
+                        if !self.send_changes(committed).await {
+                            return;
+                        }
+                    }
+                }
+                Err(typedb_source) => {
+                    event!(
+                        Level::DEBUG,
+                        "Change stream stopped at {}: {typedb_source:?}",
+                        self.stream.next_sequence_number()
+                    );
+                    let error = HttpServiceError::State {
+                        typedb_source: Arc::new(LocalServerStateError::DatabaseChangesUnavailable { typedb_source }),
+                    };
+                    self.send_line(&encode_error(error)).await;
+                    return;
+                }
+            }
+
+            // Each poll reads a bounded batch of commits, so a stream that is behind polls again straight away
+            let poll_interval = if self.stream.is_caught_up() { Self::POLL_INTERVAL } else { Duration::ZERO };
+            tokio::select! { biased;
+                _ = self.shutdown_receiver.changed() => return,
+                _ = self.line_sender.closed() => return,
+                _ = tokio::time::sleep(poll_interval) => (),
+            }
+        }
+    }
+
+    async fn send_changes(&self, changes: CommittedChanges) -> bool {
+        self.send_line(&encode_committed_changes(changes)).await
+    }
+
+    async fn send_line(&self, response: &impl Serialize) -> bool {
+        let mut line = serde_json::to_string(response).expect("Expected json serialisation of a change response");
+        line.push('\n');
+        self.line_sender.send(Ok(line)).await.is_ok()
+    }
+}
@@ -27,6 +27,8 @@ typedb_error!(
         Transaction(16, "Transaction error.", typedb_source: TransactionServiceError),
         QueryClose(17, "Error while closing single-query transaction.", typedb_source: TransactionServiceError),
         QueryCommit(18, "Error while committing single-query transaction.", typedb_source: TransactionServiceError),
+        InvalidRequestField(19, "Invalid value '{value}' for request field '{field}'.", field: String, value: String),
+        ConflictingRequestFields(20, "Request fields '{first}' and '{second}' cannot both be set.", first: String, second: String),
     }
 );
 
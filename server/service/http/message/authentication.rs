@@ -16,6 +16,14 @@ pub struct SigninPayload {
     pub password: String,
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordChangePayload {
+    pub username: String,
+    pub password: String,
+    pub new_password: String,
+}
+
 #[async_trait]
 impl<S> FromRequestParts<S> for Accessor
 where
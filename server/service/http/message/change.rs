@@ -0,0 +1,126 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use concept::thing::ThingAPI;
+use database::change_stream::{Change, ChangeEvent, ChangeOperation, CommittedChanges};
+use encoding::value::{label::Label, value::Value};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::service::http::message::query::concept::{encode_iid, encode_value_value, encode_value_value_type};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseChangesQuery {
+    pub from: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommittedChangesResponse {
+    pub sequence_number: u64,
+    pub events: Vec<ChangeEventResponse>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeEventResponse {
+    pub operation: ChangeOperationResponse,
+    #[serde(flatten)]
+    pub change: ChangeResponse,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeOperationResponse {
+    Insert,
+    Delete,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum ChangeResponse {
+    Entity(ChangedInstanceResponse),
+    Relation(ChangedInstanceResponse),
+    Attribute(ChangedAttributeResponse),
+    #[serde(rename_all = "camelCase")]
+    Has {
+        owner: ChangedInstanceResponse,
+        attribute: ChangedAttributeResponse,
+    },
+    #[serde(rename_all = "camelCase")]
+    RolePlayer {
+        relation: ChangedInstanceResponse,
+        role: String,
+        player: ChangedInstanceResponse,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangedInstanceResponse {
+    pub iid: String,
+    pub r#type: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangedAttributeResponse {
+    pub iid: String,
+    pub r#type: String,
+    pub value: serde_json::Value,
+    pub value_type: String,
+}
+
+pub(crate) fn encode_committed_changes(changes: CommittedChanges) -> CommittedChangesResponse {
+    CommittedChangesResponse {
+        sequence_number: changes.sequence_number.number(),
+        events: changes.events.into_iter().map(encode_change_event).collect_vec(),
+    }
+}
+
+fn encode_change_event(event: ChangeEvent) -> ChangeEventResponse {
+    let operation = match event.operation {
+        ChangeOperation::Insert => ChangeOperationResponse::Insert,
+        ChangeOperation::Delete => ChangeOperationResponse::Delete,
+    };
+    let change = match event.change {
+        Change::Entity { entity, type_label } => ChangeResponse::Entity(encode_instance(entity, &type_label)),
+        Change::Relation { relation, type_label } => ChangeResponse::Relation(encode_instance(relation, &type_label)),
+        Change::Attribute { attribute, type_label, value } => {
+            ChangeResponse::Attribute(encode_attribute(attribute, &type_label, value))
+        }
+        Change::Has { owner, owner_type_label, attribute, attribute_type_label, value } => ChangeResponse::Has {
+            owner: encode_instance(owner, &owner_type_label),
+            attribute: encode_attribute(attribute, &attribute_type_label, value),
+        },
+        Change::RolePlayer { relation, relation_type_label, player, player_type_label, role_label } => {
+            ChangeResponse::RolePlayer {
+                relation: encode_instance(relation, &relation_type_label),
+                role: encode_label(&role_label),
+                player: encode_instance(player, &player_type_label),
+            }
+        }
+    };
+    ChangeEventResponse { operation, change }
+}
+
+fn encode_instance(instance: impl ThingAPI, type_label: &Label) -> ChangedInstanceResponse {
+    ChangedInstanceResponse { iid: encode_iid(instance.iid()), r#type: encode_label(type_label) }
+}
+
+fn encode_attribute(attribute: impl ThingAPI, type_label: &Label, value: Value<'static>) -> ChangedAttributeResponse {
+    ChangedAttributeResponse {
+        iid: encode_iid(attribute.iid()),
+        r#type: encode_label(type_label),
+        value_type: encode_value_value_type(&value),
+        value: encode_value_value(value),
+    }
+}
+
+fn encode_label(label: &Label) -> String {
+    label.scoped_name().as_str().to_string()
+}
@@ -4,6 +4,9 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+use std::collections::BTreeMap;
+
+use database::statistics::{DatabaseStatistics, LabelledCounts};
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
@@ -35,3 +38,43 @@ pub struct DatabaseResponse {
 pub(crate) fn encode_database(name: String) -> DatabaseResponse {
     DatabaseResponse { name }
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseStatisticsResponse {
+    pub sequence_number: u64,
+    pub total_thing_count: u64,
+    pub total_entity_count: u64,
+    pub total_relation_count: u64,
+    pub total_attribute_count: u64,
+    pub total_role_count: u64,
+    pub total_has_count: u64,
+    pub entity_counts: LabelledCounts,
+    pub relation_counts: LabelledCounts,
+    pub attribute_counts: LabelledCounts,
+    pub role_counts: LabelledCounts,
+    pub has_counts: BTreeMap<String, LabelledCounts>,
+    pub role_player_counts: BTreeMap<String, LabelledCounts>,
+    pub relation_role_counts: BTreeMap<String, LabelledCounts>,
+    pub links_index_counts: BTreeMap<String, LabelledCounts>,
+}
+
+pub(crate) fn encode_database_statistics(statistics: DatabaseStatistics) -> DatabaseStatisticsResponse {
+    DatabaseStatisticsResponse {
+        sequence_number: statistics.sequence_number.number(),
+        total_thing_count: statistics.total_thing_count,
+        total_entity_count: statistics.total_entity_count,
+        total_relation_count: statistics.total_relation_count,
+        total_attribute_count: statistics.total_attribute_count,
+        total_role_count: statistics.total_role_count,
+        total_has_count: statistics.total_has_count,
+        entity_counts: statistics.entity_counts,
+        relation_counts: statistics.relation_counts,
+        attribute_counts: statistics.attribute_counts,
+        role_counts: statistics.role_counts,
+        has_counts: statistics.has_counts,
+        role_player_counts: statistics.role_player_counts,
+        relation_role_counts: statistics.relation_role_counts,
+        links_index_counts: statistics.links_index_counts,
+    }
+}
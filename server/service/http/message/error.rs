@@ -43,6 +43,8 @@ impl IntoResponse for HttpServiceError {
             HttpServiceError::UnknownVersion { .. } => StatusCode::NOT_FOUND,
             HttpServiceError::MissingPathParameter { .. } => StatusCode::NOT_FOUND,
             HttpServiceError::InvalidPathParameter { .. } => StatusCode::BAD_REQUEST,
+            HttpServiceError::InvalidRequestField { .. } => StatusCode::BAD_REQUEST,
+            HttpServiceError::ConflictingRequestFields { .. } => StatusCode::BAD_REQUEST,
             HttpServiceError::State { typedb_source } => match typedb_source.error_response_category() {
                 ErrorResponseCategory::NotFound => StatusCode::NOT_FOUND,
                 ErrorResponseCategory::Unauthenticated => StatusCode::UNAUTHORIZED,
@@ -77,6 +79,9 @@ impl IntoResponse for HttpServiceError {
                 TransactionServiceError::QueryFailed { .. } => StatusCode::BAD_REQUEST,
                 TransactionServiceError::AnalyseQueryFailed { .. } => StatusCode::BAD_REQUEST,
                 TransactionServiceError::AnalyseQueryExpectsPipeline { .. } => StatusCode::BAD_REQUEST,
+                TransactionServiceError::ExplainQueryFailed { .. } => StatusCode::BAD_REQUEST,
+                TransactionServiceError::ExplainQueryExpectsPipeline { .. } => StatusCode::BAD_REQUEST,
+                TransactionServiceError::SchemaQueryParametersUnsupported { .. } => StatusCode::BAD_REQUEST,
                 TransactionServiceError::NoOpenTransaction { .. } => StatusCode::NOT_FOUND,
                 TransactionServiceError::QueryInterrupted { .. } => StatusCode::BAD_REQUEST,
                 TransactionServiceError::QueryStreamNotFound { .. } => StatusCode::NOT_FOUND,
@@ -7,9 +7,12 @@
 pub mod analyze;
 pub mod authentication;
 pub(crate) mod body;
+pub mod change;
 pub mod database;
 pub mod error;
 pub mod query;
+pub mod replication;
+pub mod running_query;
 pub mod server;
 pub mod transaction;
 pub mod user;
@@ -218,7 +218,7 @@ pub fn encode_attribute(
     })
 }
 
-fn encode_iid<const ARRAY_INLINE_SIZE: usize>(iid: Bytes<'_, ARRAY_INLINE_SIZE>) -> String {
+pub(crate) fn encode_iid<const ARRAY_INLINE_SIZE: usize>(iid: Bytes<'_, ARRAY_INLINE_SIZE>) -> String {
     HexBytesFormatter::owned(Vec::from(iid)).format_iid()
 }
 
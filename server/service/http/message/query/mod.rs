@@ -4,16 +4,20 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+use std::collections::BTreeMap;
+
 use axum::response::{IntoResponse, Response};
-use options::QueryOptions;
+use options::{QueryOptions, QueryParameter};
 use resource::constants::server::{
-    DEFAULT_ANSWER_COUNT_LIMIT_HTTP, DEFAULT_INCLUDE_INSTANCE_TYPES, DEFAULT_INCLUDE_STRUCTURE_HTTP,
-    DEFAULT_PREFETCH_SIZE,
+    DEFAULT_ANSWER_COUNT_LIMIT_HTTP, DEFAULT_COLLECTED_ROWS_LIMIT, DEFAULT_INCLUDE_INSTANCE_TYPES,
+    DEFAULT_INCLUDE_STRUCTURE_HTTP, DEFAULT_PREFETCH_SIZE, DEFAULT_QUERY_EXPLAIN, DEFAULT_QUERY_PROFILE,
+    DEFAULT_QUERY_TIMEOUT_MILLIS, DEFAULT_TABLED_ROWS_LIMIT,
 };
 use serde::{Deserialize, Serialize};
 
 use crate::service::{
     AnswerType, QueryType,
+    explain::{QueryPlanResponse, QueryProfileResponse},
     http::{
         message::{analyze::structure::AnalyzedPipelineResponse, body::JsonBody, transaction::TransactionOpenPayload},
         transaction_service::QueryAnswer,
@@ -30,11 +34,25 @@ pub struct QueryOptionsPayload {
     pub include_instance_types: Option<bool>,
     pub answer_count_limit: Option<u64>,
     pub include_query_structure: Option<bool>,
+    pub explain: Option<bool>,
+    pub profile: Option<bool>,
+    pub query_timeout_millis: Option<u64>,
+    pub collected_rows_limit: Option<u64>,
+    pub tabled_rows_limit: Option<u64>,
 }
 
 impl Default for QueryOptionsPayload {
     fn default() -> Self {
-        Self { include_instance_types: None, answer_count_limit: None, include_query_structure: None }
+        Self {
+            include_instance_types: None,
+            answer_count_limit: None,
+            include_query_structure: None,
+            explain: None,
+            profile: None,
+            query_timeout_millis: None,
+            collected_rows_limit: None,
+            tabled_rows_limit: None,
+        }
     }
 }
 
@@ -48,6 +66,15 @@ impl Into<QueryOptions> for QueryOptionsPayload {
                 .unwrap_or(DEFAULT_ANSWER_COUNT_LIMIT_HTTP),
             prefetch_size: DEFAULT_PREFETCH_SIZE as usize,
             include_query_structure: self.include_query_structure.unwrap_or(DEFAULT_INCLUDE_STRUCTURE_HTTP),
+            explain: self.explain.unwrap_or(DEFAULT_QUERY_EXPLAIN),
+            profile: self.profile.unwrap_or(DEFAULT_QUERY_PROFILE),
+            query_timeout_millis: self.query_timeout_millis.or(DEFAULT_QUERY_TIMEOUT_MILLIS),
+            collected_rows_limit: self
+                .collected_rows_limit
+                .map(|limit| limit as usize)
+                .or(DEFAULT_COLLECTED_ROWS_LIMIT),
+            tabled_rows_limit: self.tabled_rows_limit.map(|limit| limit as usize).or(DEFAULT_TABLED_ROWS_LIMIT),
+            parameters: BTreeMap::new(),
         }
     }
 }
@@ -57,6 +84,31 @@ impl Into<QueryOptions> for QueryOptionsPayload {
 pub struct TransactionQueryPayload {
     pub query_options: Option<QueryOptionsPayload>,
     pub query: String,
+    pub parameters: Option<BTreeMap<String, QueryParameterPayload>>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryParameterPayload {
+    pub value_type: String,
+    pub value: serde_json::Value,
+}
+
+impl Into<QueryParameter> for QueryParameterPayload {
+    fn into(self) -> QueryParameter {
+        // non-string values are parsed from their text, as they are written in answers
+        let value = match self.value {
+            serde_json::Value::String(value) => value,
+            value => value.to_string(),
+        };
+        QueryParameter { value_type: self.value_type, value }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryContinuePayload {
+    pub cursor: String,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -64,6 +116,7 @@ pub struct TransactionQueryPayload {
 pub struct QueryPayload {
     pub query_options: Option<QueryOptionsPayload>,
     pub query: String,
+    pub parameters: Option<BTreeMap<String, QueryParameterPayload>>,
     pub commit: Option<bool>,
 
     #[serde(flatten)]
@@ -78,10 +131,25 @@ pub struct QueryAnswerResponse {
     pub answers: Option<Vec<serde_json::Value>>,
     pub query: Option<AnalyzedPipelineResponse>,
     pub warning: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan: Option<QueryPlanResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<QueryProfileResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
 }
 
 pub(crate) fn encode_query_ok_answer(query_type: QueryType) -> QueryAnswerResponse {
-    QueryAnswerResponse { answer_type: AnswerType::Ok, query_type, answers: None, query: None, warning: None }
+    QueryAnswerResponse {
+        answer_type: AnswerType::Ok,
+        query_type,
+        answers: None,
+        query: None,
+        warning: None,
+        plan: None,
+        profile: None,
+        cursor: None,
+    }
 }
 
 pub(crate) fn encode_query_rows_answer(
@@ -96,6 +164,9 @@ pub(crate) fn encode_query_rows_answer(
         answers: Some(rows),
         query: pipeline_structure,
         warning,
+        plan: None,
+        profile: None,
+        cursor: None,
     }
 }
 
@@ -110,28 +181,39 @@ pub(crate) fn encode_query_documents_answer(
         query_type,
         query: None,
         warning,
+        plan: None,
+        profile: None,
+        cursor: None,
+    }
+}
+
+pub(crate) fn encode_query_plan_answer(query_type: QueryType, plan: QueryPlanResponse) -> QueryAnswerResponse {
+    QueryAnswerResponse { plan: Some(plan), ..encode_query_ok_answer(query_type) }
+}
+
+fn encode_query_answer(answer: QueryAnswer) -> QueryAnswerResponse {
+    match answer {
+        QueryAnswer::ResOk(query_type) => encode_query_ok_answer(query_type),
+        QueryAnswer::ResRows((query_type, rows, pipeline_structure, warning)) => {
+            encode_query_rows_answer(query_type, rows, pipeline_structure, warning.map(|warning| warning.to_string()))
+        }
+        QueryAnswer::ResDocuments((query_type, documents, warning)) => {
+            encode_query_documents_answer(query_type, documents, warning.map(|warning| warning.to_string()))
+        }
+        QueryAnswer::ResPlan((query_type, plan)) => encode_query_plan_answer(query_type, plan),
+        QueryAnswer::ResProfiled((answer, profile)) => {
+            QueryAnswerResponse { profile: Some(profile), ..encode_query_answer(*answer) }
+        }
+        QueryAnswer::ResPartial((answer, cursor)) => {
+            QueryAnswerResponse { cursor: Some(cursor.to_string()), ..encode_query_answer(*answer) }
+        }
     }
 }
 
 impl IntoResponse for QueryAnswer {
     fn into_response(self) -> Response {
         let code = self.status_code();
-        let body = match self {
-            QueryAnswer::ResOk(query_type) => JsonBody(encode_query_ok_answer(query_type)),
-            QueryAnswer::ResRows((query_type, rows, pipeline_structure, warning)) => {
-                JsonBody(encode_query_rows_answer(
-                    query_type,
-                    rows,
-                    pipeline_structure,
-                    warning.map(|warning| warning.to_string()),
-                ))
-            }
-            QueryAnswer::ResDocuments((query_type, documents, warning)) => JsonBody(encode_query_documents_answer(
-                query_type,
-                documents,
-                warning.map(|warning| warning.to_string()),
-            )),
-        };
+        let body = JsonBody(encode_query_answer(self));
         (code, body).into_response()
     }
 }
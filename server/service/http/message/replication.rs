@@ -0,0 +1,60 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::borrow::Cow;
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use durability::{DurabilitySequenceNumber, RawRecord};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseReplicationQuery {
+    pub from: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplicationRecordsResponse {
+    pub records: Vec<ReplicationRecordResponse>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplicationRecordResponse {
+    pub sequence_number: u64,
+    pub record_type: u8,
+    pub bytes: String,
+}
+
+pub(crate) fn encode_replication_records(records: Vec<RawRecord<'static>>) -> ReplicationRecordsResponse {
+    ReplicationRecordsResponse { records: records.into_iter().map(encode_replication_record).collect_vec() }
+}
+
+fn encode_replication_record(record: RawRecord<'static>) -> ReplicationRecordResponse {
+    ReplicationRecordResponse {
+        sequence_number: record.sequence_number.number(),
+        record_type: record.record_type,
+        bytes: STANDARD.encode(&record.bytes),
+    }
+}
+
+pub(crate) fn decode_replication_records(
+    response: ReplicationRecordsResponse,
+) -> Result<Vec<RawRecord<'static>>, base64::DecodeError> {
+    response
+        .records
+        .into_iter()
+        .map(|record| {
+            Ok(RawRecord {
+                sequence_number: DurabilitySequenceNumber::new(record.sequence_number),
+                record_type: record.record_type,
+                bytes: Cow::Owned(STANDARD.decode(record.bytes)?),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,92 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    service::http::{error::HttpServiceError, message::from_request_parts_impl},
+    state::{OpenTransactionInfo, RunningQueryInfo},
+};
+
+#[derive(Debug)]
+pub(crate) struct RunningQueryPath {
+    pub(crate) query_id: Uuid,
+}
+
+from_request_parts_impl!(RunningQueryPath { query_id: Uuid });
+
+#[derive(Debug)]
+pub(crate) struct RunningTransactionPath {
+    pub(crate) transaction_id: Uuid,
+}
+
+from_request_parts_impl!(RunningTransactionPath { transaction_id: Uuid });
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunningQueriesResponse {
+    pub queries: Vec<RunningQueryResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunningQueryResponse {
+    pub id: String,
+    pub transaction_id: String,
+    pub database_name: String,
+    pub username: String,
+    pub query: String,
+    pub elapsed_millis: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenTransactionsResponse {
+    pub transactions: Vec<OpenTransactionResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenTransactionResponse {
+    pub id: String,
+    pub database_name: String,
+    pub transaction_type: String,
+    pub username: String,
+    pub elapsed_millis: u64,
+    pub running_queries: usize,
+}
+
+pub(crate) fn encode_running_queries(queries: Vec<RunningQueryInfo>) -> RunningQueriesResponse {
+    RunningQueriesResponse { queries: queries.into_iter().map(encode_running_query).collect_vec() }
+}
+
+fn encode_running_query(query: RunningQueryInfo) -> RunningQueryResponse {
+    RunningQueryResponse {
+        id: query.id.to_string(),
+        transaction_id: query.transaction_id.to_string(),
+        database_name: query.database_name,
+        username: query.owner,
+        query: query.source_query,
+        elapsed_millis: query.elapsed.as_millis() as u64,
+    }
+}
+
+pub(crate) fn encode_open_transactions(transactions: Vec<OpenTransactionInfo>) -> OpenTransactionsResponse {
+    OpenTransactionsResponse { transactions: transactions.into_iter().map(encode_open_transaction).collect_vec() }
+}
+
+fn encode_open_transaction(transaction: OpenTransactionInfo) -> OpenTransactionResponse {
+    OpenTransactionResponse {
+        id: transaction.id.to_string(),
+        database_name: transaction.database_name,
+        transaction_type: transaction.transaction_type.to_string(),
+        username: transaction.owner,
+        elapsed_millis: transaction.elapsed.as_millis() as u64,
+        running_queries: transaction.running_queries,
+    }
+}
@@ -6,7 +6,7 @@
 
 use axum::response::{IntoResponse, Response};
 use http::StatusCode;
-use options::TransactionOptions;
+use options::{ReadPoint, TransactionOptions};
 use resource::constants::server::{
     DEFAULT_SCHEMA_LOCK_ACQUIRE_TIMEOUT_MILLIS, DEFAULT_TRANSACTION_PARALLEL, DEFAULT_TRANSACTION_TIMEOUT_MILLIS,
 };
@@ -18,6 +18,7 @@ use crate::service::{
     http::{
         error::HttpServiceError, message::from_request_parts_impl, transaction_service::TransactionServiceResponse,
     },
+    parse_read_timestamp,
 };
 
 #[derive(Serialize, Deserialize)]
@@ -34,23 +35,54 @@ pub struct TransactionOptionsPayload {
     // pub parallel: Option<bool>, // TODO: Uncomment when introduced
     pub schema_lock_acquire_timeout_millis: Option<u64>,
     pub transaction_timeout_millis: Option<u64>,
+    pub read_at_sequence_number: Option<u64>,
+    pub read_at_timestamp: Option<String>,
 }
 
 impl Default for TransactionOptionsPayload {
     fn default() -> Self {
-        Self { schema_lock_acquire_timeout_millis: None, transaction_timeout_millis: None }
+        Self {
+            schema_lock_acquire_timeout_millis: None,
+            transaction_timeout_millis: None,
+            read_at_sequence_number: None,
+            read_at_timestamp: None,
+        }
     }
 }
 
-impl Into<TransactionOptions> for TransactionOptionsPayload {
-    fn into(self) -> TransactionOptions {
-        TransactionOptions {
+impl TryFrom<TransactionOptionsPayload> for TransactionOptions {
+    type Error = HttpServiceError;
+
+    fn try_from(payload: TransactionOptionsPayload) -> Result<Self, Self::Error> {
+        let read_point = match (payload.read_at_sequence_number, payload.read_at_timestamp) {
+            (None, None) => None,
+            (Some(sequence_number), None) => Some(ReadPoint::SequenceNumber(sequence_number)),
+            (None, Some(timestamp)) => match parse_read_timestamp(&timestamp) {
+                Some(time) => Some(ReadPoint::Timestamp(time)),
+                None => {
+                    return Err(HttpServiceError::InvalidRequestField {
+                        field: "readAtTimestamp".to_string(),
+                        value: timestamp,
+                    });
+                }
+            },
+            (Some(_), Some(_)) => {
+                return Err(HttpServiceError::ConflictingRequestFields {
+                    first: "readAtSequenceNumber".to_string(),
+                    second: "readAtTimestamp".to_string(),
+                });
+            }
+        };
+        Ok(TransactionOptions {
             parallel: DEFAULT_TRANSACTION_PARALLEL,
-            schema_lock_acquire_timeout_millis: self
+            schema_lock_acquire_timeout_millis: payload
                 .schema_lock_acquire_timeout_millis
                 .unwrap_or(DEFAULT_SCHEMA_LOCK_ACQUIRE_TIMEOUT_MILLIS),
-            transaction_timeout_millis: self.transaction_timeout_millis.unwrap_or(DEFAULT_TRANSACTION_TIMEOUT_MILLIS),
-        }
+            transaction_timeout_millis: payload
+                .transaction_timeout_millis
+                .unwrap_or(DEFAULT_TRANSACTION_TIMEOUT_MILLIS),
+            read_point,
+        })
     }
 }
 
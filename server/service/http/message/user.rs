@@ -6,9 +6,10 @@
 
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
-use system::concepts::User;
+use system::concepts::{ApiKey, Grant, Role, Session, User};
+use uuid::Uuid;
 
-use crate::service::http::message::from_request_parts_impl;
+use crate::service::http::{error::HttpServiceError, message::from_request_parts_impl};
 
 #[derive(Debug)]
 pub(crate) struct UserPath {
@@ -17,6 +18,22 @@ pub(crate) struct UserPath {
 
 from_request_parts_impl!(UserPath { username: String });
 
+#[derive(Debug)]
+pub(crate) struct SessionPath {
+    pub(crate) username: String,
+    pub(crate) session_id: Uuid,
+}
+
+from_request_parts_impl!(SessionPath { username: String, session_id: Uuid });
+
+#[derive(Debug)]
+pub(crate) struct ApiKeyPath {
+    pub(crate) username: String,
+    pub(crate) key_name: String,
+}
+
+from_request_parts_impl!(ApiKeyPath { username: String, key_name: String });
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateUserPayload {
@@ -48,3 +65,104 @@ pub struct UserResponse {
 pub(crate) fn encode_user(user: &User) -> UserResponse {
     UserResponse { username: user.name.clone() }
 }
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GrantPayload {
+    pub role: String,
+    pub database: Option<String>,
+}
+
+impl TryFrom<GrantPayload> for Grant {
+    type Error = HttpServiceError;
+
+    fn try_from(payload: GrantPayload) -> Result<Self, Self::Error> {
+        let role = Role::from_name(&payload.role)
+            .ok_or_else(|| HttpServiceError::InvalidRequestField { field: "role".to_string(), value: payload.role })?;
+        Ok(Grant::new(role, payload.database))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GrantsResponse {
+    pub grants: Vec<GrantResponse>,
+}
+
+pub(crate) fn encode_grants(grants: Vec<Grant>) -> GrantsResponse {
+    GrantsResponse { grants: grants.into_iter().map(|grant| encode_grant(&grant)).collect_vec() }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GrantResponse {
+    pub role: String,
+    pub database: Option<String>,
+}
+
+pub(crate) fn encode_grant(grant: &Grant) -> GrantResponse {
+    GrantResponse { role: grant.role.name().to_string(), database: grant.database.clone() }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionsResponse {
+    pub sessions: Vec<SessionResponse>,
+}
+
+pub(crate) fn encode_sessions(sessions: Vec<Session>) -> SessionsResponse {
+    SessionsResponse { sessions: sessions.into_iter().map(|session| encode_session(&session)).collect_vec() }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionResponse {
+    pub id: String,
+    pub issued_at: u64,
+    pub expires_at: u64,
+}
+
+pub(crate) fn encode_session(session: &Session) -> SessionResponse {
+    SessionResponse { id: session.id.clone(), issued_at: session.issued_at, expires_at: session.expires_at }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateApiKeyPayload {
+    pub name: String,
+    pub expires_in_seconds: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeysResponse {
+    pub api_keys: Vec<ApiKeyResponse>,
+}
+
+pub(crate) fn encode_api_keys(api_keys: Vec<ApiKey>) -> ApiKeysResponse {
+    ApiKeysResponse { api_keys: api_keys.into_iter().map(|api_key| encode_api_key(&api_key)).collect_vec() }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyResponse {
+    pub name: String,
+    pub expires_at: Option<u64>,
+    pub last_used_at: Option<u64>,
+}
+
+pub(crate) fn encode_api_key(api_key: &ApiKey) -> ApiKeyResponse {
+    ApiKeyResponse { name: api_key.name.clone(), expires_at: api_key.expires_at, last_used_at: api_key.last_used_at }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedApiKeyResponse {
+    pub name: String,
+    pub key: String,
+    pub expires_at: Option<u64>,
+}
+
+pub(crate) fn encode_created_api_key(api_key: &ApiKey, key: String) -> CreatedApiKeyResponse {
+    CreatedApiKeyResponse { name: api_key.name.clone(), key, expires_at: api_key.expires_at }
+}
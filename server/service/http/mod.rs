@@ -5,6 +5,7 @@
  */
 
 pub(crate) mod authenticator;
+pub(crate) mod change_stream_service;
 mod diagnostics;
 pub(crate) mod encryption;
 mod error;
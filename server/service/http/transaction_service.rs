@@ -12,20 +12,28 @@ use std::{
         ControlFlow::{Break, Continue},
     },
     sync::Arc,
+    time::Duration,
 };
 
 use compiler::query_structure::PipelineStructure;
 use concept::{thing::thing_manager::ThingManager, type_::type_manager::TypeManager};
 use database::query::{
-    StreamQueryOutputDescriptor, WriteQueryAnswer, WriteQueryResult, execute_schema_query,
-    execute_write_query_in_schema, execute_write_query_in_write,
+    StreamQueryOutputDescriptor, WriteQueryAnswer, WriteQueryResult, execute_schema_query_profiled,
+    execute_write_query_in_schema, execute_write_query_in_write, execution_limits, query_parameters,
+};
+use diagnostics::{
+    diagnostics_manager::DiagnosticsManager,
+    metrics::{ClientEndpoint, LoadKind, QueryKind},
 };
-use diagnostics::metrics::{ClientEndpoint, LoadKind};
 use executor::{
     ExecutionInterrupt, InterruptType,
     batch::Batch,
     document::ConceptDocument,
-    pipeline::{PipelineExecutionError, pipeline::Pipeline, stage::ReadPipelineStage},
+    pipeline::{
+        PipelineExecutionError,
+        pipeline::Pipeline,
+        stage::{ReadPipelineStage, ReadStageIterator},
+    },
 };
 use http::StatusCode;
 use ir::pipeline::ParameterRegistry;
@@ -33,13 +41,16 @@ use itertools::{Either, Itertools};
 use lending_iterator::LendingIterator;
 use options::{QueryOptions, TransactionOptions};
 use query::error::QueryError;
-use resource::profile::StorageCounters;
+use resource::{
+    constants::server::MAX_SUSPENDED_READ_QUERIES_PER_TRANSACTION,
+    profile::{QueryProfile, StorageCounters},
+};
 use storage::snapshot::ReadableSnapshot;
 use tokio::{
     spawn,
     sync::{
         broadcast,
-        mpsc::{Receiver, Sender},
+        mpsc::{self, Receiver, Sender},
         oneshot,
     },
     task::{JoinHandle, spawn_blocking},
@@ -47,10 +58,12 @@ use tokio::{
 };
 use tracing::{Level, event};
 use typeql::{parse_query, query::SchemaQuery};
+use uuid::Uuid;
 
 use crate::{
     service::{
-        QueryType, TransactionType,
+        IncludeInvolvedBlocks, QueryType, TransactionType,
+        explain::{QueryPlanResponse, QueryProfileResponse, encode_query_plan, encode_query_profile},
         http::message::{
             analyze::{
                 AnalysedQueryResponse, encode_analyzed_query,
@@ -61,10 +74,10 @@ use crate::{
         may_encode_pipeline_structure,
         transaction_service::{
             Transaction, TransactionServiceError, commit_schema_transaction, commit_write_transaction,
-            init_transaction_timeout, is_write_pipeline, with_readable_transaction,
+            init_transaction_timeout, is_write_pipeline, submit_query_diagnostics, with_readable_transaction,
         },
     },
-    state::ServerState,
+    state::{RunningQuery, ServerState, SuspendedQuery},
 };
 
 macro_rules! respond_error_and_return_break {
@@ -111,6 +124,7 @@ macro_rules! unwrap_or_execute_else_respond_error_and_return_break {
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub(crate) enum TransactionRequest {
     Query(QueryOptions, String),
+    QueryContinue(Uuid),
     AnalyseQuery(String),
     Commit,
     Rollback,
@@ -146,6 +160,7 @@ fn respond_transaction_response(
 #[derive(Debug)]
 pub(crate) struct TransactionService {
     server_state: Arc<ServerState>,
+    owner: String,
 
     request_stream: Receiver<(TransactionRequest, TransactionResponder)>,
     query_interrupt_sender: broadcast::Sender<InterruptType>,
@@ -156,6 +171,8 @@ pub(crate) struct TransactionService {
     transaction: Option<Transaction>,
     query_queue: VecDeque<(TransactionResponder, QueueOptions, typeql::query::Pipeline, String)>,
     running_write_query: Option<(TransactionResponder, JoinHandle<(Transaction, WriteQueryResult)>)>,
+    suspends_read_queries: bool,
+    suspended_read_queries: VecDeque<SuspendedReadQuery>,
 
     close_sender: Sender<()>,
     close_receiver: Receiver<()>,
@@ -174,6 +191,9 @@ pub(crate) enum QueryAnswer {
     ResOk(QueryType),
     ResRows((QueryType, Vec<serde_json::Value>, Option<AnalyzedPipelineResponse>, Option<QueryAnswerWarning>)),
     ResDocuments((QueryType, Vec<serde_json::Value>, Option<QueryAnswerWarning>)),
+    ResPlan((QueryType, QueryPlanResponse)),
+    ResProfiled((Box<QueryAnswer>, QueryProfileResponse)),
+    ResPartial((Box<QueryAnswer>, Uuid)),
 }
 
 impl QueryAnswer {
@@ -182,6 +202,9 @@ impl QueryAnswer {
             QueryAnswer::ResOk(query_type) => *query_type,
             QueryAnswer::ResRows((query_type, _, _, _)) => *query_type,
             QueryAnswer::ResDocuments((query_type, _, _)) => *query_type,
+            QueryAnswer::ResPlan((query_type, _)) => *query_type,
+            QueryAnswer::ResProfiled((answer, _)) => answer.query_type(),
+            QueryAnswer::ResPartial((answer, _)) => answer.query_type(),
         }
     }
 
@@ -196,6 +219,23 @@ impl QueryAnswer {
                 None => StatusCode::OK,
                 Some(warning) => warning.status_code(),
             },
+            QueryAnswer::ResPlan(_) => StatusCode::OK,
+            QueryAnswer::ResProfiled((answer, _)) => answer.status_code(),
+            QueryAnswer::ResPartial((answer, _)) => answer.status_code(),
+        }
+    }
+
+    fn may_attach_cursor(self, cursor: Option<Uuid>) -> Self {
+        match cursor {
+            Some(cursor) => QueryAnswer::ResPartial((Box::new(self), cursor)),
+            None => self,
+        }
+    }
+
+    fn may_attach_profile(self, query_options: &QueryOptions, query_profile: &QueryProfile) -> Self {
+        match query_options.profile {
+            true => QueryAnswer::ResProfiled((Box::new(self), encode_query_profile(query_profile))),
+            false => self,
         }
     }
 }
@@ -229,15 +269,95 @@ impl fmt::Display for QueryAnswerWarning {
     }
 }
 
+// A read query which filled a page of answers, kept with its transaction until a further page is requested.
+// Since its answers hold the transaction snapshot, it is ended before anything may modify or release the snapshot.
+#[derive(Debug)]
+struct SuspendedReadQuery {
+    cursor: Uuid,
+    pages: ReadQueryPages,
+    query: SuspendedQuery,
+}
+
+// A read query answered one page at a time by a blocking worker of its own. The query's answers are not `Send`, so
+// they are created, advanced and dropped by the worker, which waits for page requests in between pages.
+#[derive(Debug)]
+struct ReadQueryPages {
+    cursor: Option<Uuid>,
+    page_requests: Sender<ReadQueryPageRequest>,
+    worker: JoinHandle<()>,
+    interrupt: ExecutionInterrupt,
+}
+
+#[derive(Debug)]
+struct ReadQueryPageRequest {
+    responder: TransactionResponder,
+    page_done: oneshot::Sender<ControlFlow<(), ReadQueryProgress>>,
+}
+
+// Responds with the next page of answers, advancing the query's answer iterator
+type RespondPageFn = dyn FnMut(TransactionResponder, Option<Uuid>) -> ControlFlow<(), ReadQueryProgress>;
+
+struct ReadQueryDiagnostics {
+    diagnostics_manager: Arc<DiagnosticsManager>,
+    database_name: String,
+    owner: String,
+    source_query: String,
+    query_profile: Arc<QueryProfile>,
+    start: Instant,
+}
+
+impl ReadQueryPages {
+    async fn request_page(&self, responder: TransactionResponder) -> ControlFlow<(), ReadQueryProgress> {
+        let (page_done, page_done_receiver) = oneshot::channel();
+        let request = ReadQueryPageRequest { responder, page_done };
+        self.page_requests.send(request).await.expect("Expected read query worker to await page requests");
+        page_done_receiver.await.expect("Expected read query page completion")
+    }
+
+    async fn finish(self) {
+        let ReadQueryPages { page_requests, worker, .. } = self;
+        // without further page requests, the worker drops the query's answers and submits its diagnostics
+        drop(page_requests);
+        worker.await.expect("Expected read query worker completion");
+    }
+}
+
+impl ReadQueryDiagnostics {
+    fn submit(self) {
+        let Self { diagnostics_manager, database_name, owner, source_query, query_profile, start } = self;
+        submit_query_diagnostics(
+            &diagnostics_manager,
+            &database_name,
+            &owner,
+            QueryKind::Read,
+            &source_query,
+            Some(&query_profile),
+            start,
+        );
+        if tracing::enabled!(Level::TRACE) {
+            event!(Level::INFO, "Read query done (including network request time).\n{}", query_profile);
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum ReadQueryProgress {
+    Finished,
+    Suspended,
+}
+
 impl TransactionService {
     pub(crate) fn new(
         server_state: Arc<ServerState>,
+        owner: String,
         request_stream: Receiver<(TransactionRequest, TransactionResponder)>,
+        suspends_read_queries: bool,
     ) -> Self {
         let (query_interrupt_sender, query_interrupt_receiver) = broadcast::channel(1);
         let (close_sender, close_receiver) = tokio::sync::mpsc::channel(1);
         Self {
             server_state,
+            owner,
 
             request_stream,
             query_interrupt_sender,
@@ -248,6 +368,8 @@ impl TransactionService {
             transaction: None,
             query_queue: VecDeque::with_capacity(20),
             running_write_query: None,
+            suspends_read_queries,
+            suspended_read_queries: VecDeque::with_capacity(MAX_SUSPENDED_READ_QUERIES_PER_TRANSACTION),
 
             close_sender,
             close_receiver,
@@ -257,9 +379,9 @@ impl TransactionService {
     pub(crate) async fn open(
         &mut self,
         type_: TransactionType,
-        owner: String,
         database_name: String,
         options: TransactionOptions,
+        transaction_id: Uuid,
     ) -> Result<u64, TransactionServiceError> {
         let receive_time = Instant::now();
         let transaction_timeout_millis = options.transaction_timeout_millis;
@@ -267,7 +389,7 @@ impl TransactionService {
         let transaction = self
             .server_state
             .transactions()
-            .open(&database_name, type_, options, owner, self.close_sender.clone())
+            .open(&database_name, type_, options, transaction_id, self.owner.clone(), self.close_sender.clone())
             .await
             .map_err(|typedb_source| TransactionServiceError::CannotOpen { typedb_source })?;
 
@@ -364,6 +486,7 @@ impl TransactionService {
                 TransactionRequest::Query(query_options, query) => {
                     self.handle_query(query_options, query, response_sender).await
                 }
+                TransactionRequest::QueryContinue(cursor) => self.handle_query_continue(cursor, response_sender).await,
                 TransactionRequest::AnalyseQuery(query) => self.handle_analyse_query(query, response_sender).await,
                 TransactionRequest::Commit => self.handle_commit(response_sender).await,
                 TransactionRequest::Rollback => self.handle_rollback(response_sender).await,
@@ -510,6 +633,9 @@ impl TransactionService {
 
     async fn interrupt(&mut self, interrupt: InterruptType) {
         self.query_interrupt_sender.send(interrupt).expect("Expected query interrupt to be sent");
+        for suspended in self.suspended_read_queries.drain(..).collect::<Vec<_>>() {
+            Self::end_suspended_read_query(suspended).await;
+        }
     }
 
     async fn cancel_queued_read_queries(&mut self, interrupt: InterruptType) -> ControlFlow<(), ()> {
@@ -604,20 +730,18 @@ impl TransactionService {
                         return Break(());
                     }
                 }
+                (QueueOptions::Explain(query_options), _) => {
+                    if let Break(()) =
+                        self.run_explain_query(responder, query_options, query_pipeline, source_query).await
+                    {
+                        return Break(());
+                    }
+                }
                 (QueueOptions::Query(query_options), true) => {
                     return self.run_write_query(responder, query_options, query_pipeline, source_query).await;
                 }
                 (QueueOptions::Query(query_options), false) => {
-                    if let Break(()) = self
-                        .blocking_read_query_worker(
-                            responder,
-                            query_options,
-                            query_pipeline,
-                            source_query,
-                            StorageCounters::DISABLED,
-                        )
-                        .await
-                        .expect("Expected read query completion")
+                    if let Break(()) = self.run_read_query(responder, query_options, query_pipeline, source_query).await
                     {
                         return Break(());
                     }
@@ -644,6 +768,23 @@ impl TransactionService {
             }
         };
         match parsed.into_structure() {
+            typeql::query::QueryStructure::Schema(_) if query_options.explain => {
+                respond_error_and_return_break!(responder, TransactionServiceError::ExplainQueryExpectsPipeline {});
+            }
+            typeql::query::QueryStructure::Schema(_) if !query_options.parameters.is_empty() => {
+                let error = TransactionServiceError::SchemaQueryParametersUnsupported {};
+                respond_else_return_break!(responder, TransactionServiceResponse::Err(error));
+                Continue(())
+            }
+            typeql::query::QueryStructure::Pipeline(pipeline) if query_options.explain => {
+                if !self.query_queue.is_empty() || self.running_write_query.is_some() {
+                    // queued queries are not handled yet so there will be no query response yet
+                    self.query_queue.push_back((responder, QueueOptions::Explain(query_options), pipeline, query));
+                    Continue(())
+                } else {
+                    self.run_explain_query(responder, query_options, pipeline, query).await
+                }
+            }
             typeql::query::QueryStructure::Schema(schema_query) => {
                 // schema queries are handled immediately so there is a query response or a fatal Status
                 match self.handle_query_schema(schema_query, query).await {
@@ -670,21 +811,27 @@ impl TransactionService {
                         // queued queries are not handled yet so there will be no query response yet
                         Continue(())
                     } else {
-                        self.blocking_read_query_worker(
-                            responder,
-                            query_options,
-                            pipeline,
-                            query,
-                            StorageCounters::DISABLED,
-                        )
-                        .await
-                        .expect("Expected read query completion")
+                        self.run_read_query(responder, query_options, pipeline, query).await
                     }
                 }
             }
         }
     }
 
+    async fn handle_query_continue(&mut self, cursor: Uuid, responder: TransactionResponder) -> ControlFlow<(), ()> {
+        // suspended queries are ended by any write query, so a queued or running write leaves nothing to continue
+        let position = self.suspended_read_queries.iter().position(|suspended| suspended.cursor == cursor);
+        let Some(suspended) = position.and_then(|position| self.suspended_read_queries.remove(position)) else {
+            let error = TransactionServiceError::QueryStreamNotFound { query_request_id: cursor };
+            respond_else_return_break!(responder, TransactionServiceResponse::Err(error));
+            return Continue(());
+        };
+        let SuspendedReadQuery { pages, query, .. } = suspended;
+        let running_query = query.resume();
+        pages.interrupt.resume_deadline();
+        self.respond_read_query_page(pages, responder, running_query).await
+    }
+
     async fn handle_query_schema(
         &mut self,
         query: SchemaQuery,
@@ -701,10 +848,23 @@ impl TransactionService {
         if let Some(transaction) = self.transaction.take() {
             match transaction {
                 Transaction::Schema(schema_transaction) => {
-                    let (transaction, result) =
-                        spawn_blocking(move || execute_schema_query(schema_transaction, query, source_query))
-                            .await
-                            .expect("Expected schema query execution finishing");
+                    let database_name = schema_transaction.database.name().to_owned();
+                    let start = Instant::now();
+                    let executed_query = source_query.clone();
+                    let (transaction, query_profile, result) = spawn_blocking(move || {
+                        execute_schema_query_profiled(schema_transaction, query, executed_query)
+                    })
+                    .await
+                    .expect("Expected schema query execution finishing");
+                    submit_query_diagnostics(
+                        &self.server_state.diagnostics_manager(),
+                        &database_name,
+                        &self.owner,
+                        QueryKind::Schema,
+                        &source_query,
+                        Some(&query_profile),
+                        start,
+                    );
                     self.transaction = Some(Transaction::Schema(transaction));
                     match result {
                         Ok(_) => return Ok(TransactionServiceResponse::Query(QueryAnswer::ResOk(QueryType::Schema))),
@@ -722,6 +882,65 @@ impl TransactionService {
         Ok(TransactionServiceResponse::Err(TransactionServiceError::SchemaQueryRequiresSchemaTransaction {}))
     }
 
+    async fn run_read_query(
+        &mut self,
+        responder: TransactionResponder,
+        query_options: QueryOptions,
+        pipeline: typeql::query::Pipeline,
+        source_query: String,
+    ) -> ControlFlow<(), ()> {
+        let (running_query, interrupt) = self.register_query(&query_options, &source_query).await;
+        let cursor = self.suspends_read_queries.then(Uuid::new_v4);
+        let (page_requests, page_request_receiver) = mpsc::channel(1);
+        let worker = self.blocking_read_query_worker(
+            page_request_receiver,
+            cursor,
+            query_options,
+            pipeline,
+            source_query,
+            StorageCounters::DISABLED,
+            interrupt.clone(),
+        );
+        let pages = ReadQueryPages { cursor, page_requests, worker, interrupt };
+        self.respond_read_query_page(pages, responder, running_query).await
+    }
+
+    async fn respond_read_query_page(
+        &mut self,
+        pages: ReadQueryPages,
+        responder: TransactionResponder,
+        running_query: RunningQuery,
+    ) -> ControlFlow<(), ()> {
+        match pages.request_page(responder).await {
+            Continue(ReadQueryProgress::Suspended) => (),
+            progress => {
+                // the query's answers are dropped by its worker before the query stops being listed
+                pages.finish().await;
+                drop(running_query);
+                return match progress {
+                    Continue(_) => Continue(()),
+                    Break(()) => Break(()),
+                };
+            }
+        }
+        if self.suspended_read_queries.len() >= MAX_SUSPENDED_READ_QUERIES_PER_TRANSACTION {
+            let oldest = self.suspended_read_queries.pop_front().unwrap();
+            Self::end_suspended_read_query(oldest).await;
+        }
+        // a suspended query is neither listed as running nor timed out until it is continued
+        pages.interrupt.pause_deadline();
+        let cursor = pages.cursor.expect("Expected only queries with a cursor to be suspended");
+        self.suspended_read_queries.push_back(SuspendedReadQuery { cursor, pages, query: running_query.suspend() });
+        Continue(())
+    }
+
+    async fn end_suspended_read_query(query: SuspendedReadQuery) {
+        let SuspendedReadQuery { pages, query, .. } = query;
+        // dropping the answers may wait for their executors to finish, releasing the snapshot
+        pages.finish().await;
+        drop(query);
+    }
+
     async fn run_write_query(
         &mut self,
         responder: TransactionResponder,
@@ -731,7 +950,8 @@ impl TransactionService {
     ) -> ControlFlow<(), ()> {
         debug_assert!(self.running_write_query.is_none());
         self.interrupt(InterruptType::WriteQueryExecution).await;
-        match self.spawn_blocking_execute_write_query(query_options, pipeline, source_query) {
+        let (running_query, interrupt) = self.register_query(&query_options, &source_query).await;
+        match self.spawn_blocking_execute_write_query(query_options, pipeline, source_query, running_query, interrupt) {
             Ok(handle) => {
                 // running write queries have no valid response yet (until they finish) and will respond asynchronously
                 self.running_write_query = Some((responder, tokio::spawn(async move { handle.await.unwrap() })));
@@ -764,6 +984,7 @@ impl TransactionService {
                             type_manager,
                             thing_manager,
                             answer.query_options,
+                            answer.query_profile,
                             output_descriptor,
                             pipeline_structure,
                             batch,
@@ -780,6 +1001,7 @@ impl TransactionService {
                             type_manager,
                             thing_manager,
                             answer.query_options,
+                            answer.query_profile,
                             parameters,
                             documents,
                             responder,
@@ -796,24 +1018,79 @@ impl TransactionService {
         })
     }
 
+    async fn register_query(
+        &self,
+        query_options: &QueryOptions,
+        source_query: &str,
+    ) -> (RunningQuery, ExecutionInterrupt) {
+        let transaction_id = self.transaction.as_ref().unwrap().id();
+        let interrupt = match query_options.query_timeout_millis {
+            Some(millis) => {
+                let deadline = Instant::now() + Duration::from_millis(millis);
+                self.query_interrupt_receiver.clone().with_deadline(deadline.into_std())
+            }
+            None => self.query_interrupt_receiver.clone(),
+        };
+        self.server_state.transactions().query_register(transaction_id, source_query, interrupt).await
+    }
+
     fn spawn_blocking_execute_write_query(
         &mut self,
         query_options: QueryOptions,
         pipeline: typeql::query::Pipeline,
         source_query: String,
+        running_query: RunningQuery,
+        interrupt: ExecutionInterrupt,
     ) -> Result<JoinHandle<(Transaction, WriteQueryResult)>, TransactionServiceError> {
         debug_assert!(self.running_write_query.is_none());
         debug_assert!(self.transaction.is_some());
-        let interrupt = self.query_interrupt_receiver.clone();
+        let diagnostics_manager = self.server_state.diagnostics_manager();
+        let owner = self.owner.clone();
         match self.transaction.take() {
             Some(Transaction::Schema(schema_transaction)) => Ok(spawn_blocking(move || {
-                let (transaction, result) =
-                    execute_write_query_in_schema(schema_transaction, query_options, pipeline, source_query, interrupt);
+                let database_name = schema_transaction.database.name().to_owned();
+                let start = Instant::now();
+                let (transaction, result) = execute_write_query_in_schema(
+                    schema_transaction,
+                    query_options,
+                    pipeline,
+                    source_query.clone(),
+                    interrupt,
+                );
+                drop(running_query);
+                let profile = result.as_ref().ok().map(|answer| answer.query_profile.as_ref());
+                submit_query_diagnostics(
+                    &diagnostics_manager,
+                    &database_name,
+                    &owner,
+                    QueryKind::Write,
+                    &source_query,
+                    profile,
+                    start,
+                );
                 (Transaction::Schema(transaction), result)
             })),
             Some(Transaction::Write(write_transaction)) => Ok(spawn_blocking(move || {
-                let (transaction, result) =
-                    execute_write_query_in_write(write_transaction, query_options, pipeline, source_query, interrupt);
+                let database_name = write_transaction.database.name().to_owned();
+                let start = Instant::now();
+                let (transaction, result) = execute_write_query_in_write(
+                    write_transaction,
+                    query_options,
+                    pipeline,
+                    source_query.clone(),
+                    interrupt,
+                );
+                drop(running_query);
+                let profile = result.as_ref().ok().map(|answer| answer.query_profile.as_ref());
+                submit_query_diagnostics(
+                    &diagnostics_manager,
+                    &database_name,
+                    &owner,
+                    QueryKind::Write,
+                    &source_query,
+                    profile,
+                    start,
+                );
                 (Transaction::Write(transaction), result)
             })),
             Some(Transaction::Read(transaction)) => {
@@ -829,6 +1106,7 @@ impl TransactionService {
         type_manager: Arc<TypeManager>,
         thing_manager: Arc<ThingManager>,
         query_options: QueryOptions,
+        query_profile: Arc<QueryProfile>,
         output_descriptor: StreamQueryOutputDescriptor,
         pipeline_structure: Option<PipelineStructure>,
         batch: Batch,
@@ -884,10 +1162,8 @@ impl TransactionService {
                 }
             }
         }
-        match respond_query_response(
-            responder,
-            QueryAnswer::ResRows((QueryType::Write, result, encoded_structure, warning)),
-        ) {
+        let answer = QueryAnswer::ResRows((QueryType::Write, result, encoded_structure, warning));
+        match respond_query_response(responder, answer.may_attach_profile(&query_options, &query_profile)) {
             Ok(_) => Continue(()),
             Err(_) => Break(()),
         }
@@ -898,6 +1174,7 @@ impl TransactionService {
         type_manager: Arc<TypeManager>,
         thing_manager: Arc<ThingManager>,
         query_options: QueryOptions,
+        query_profile: Arc<QueryProfile>,
         parameters: Arc<ParameterRegistry>,
         documents: Vec<ConceptDocument>,
         responder: TransactionResponder,
@@ -938,7 +1215,8 @@ impl TransactionService {
                 }
             }
         }
-        match respond_query_response(responder, QueryAnswer::ResDocuments((QueryType::Write, result, warning))) {
+        let answer = QueryAnswer::ResDocuments((QueryType::Write, result, warning));
+        match respond_query_response(responder, answer.may_attach_profile(&query_options, &query_profile)) {
             Ok(_) => Continue(()),
             Err(_) => Break(()),
         }
@@ -946,22 +1224,35 @@ impl TransactionService {
 
     fn blocking_read_query_worker(
         &self,
-        responder: TransactionResponder,
+        mut page_requests: Receiver<ReadQueryPageRequest>,
+        cursor: Option<Uuid>,
         query_options: QueryOptions,
         pipeline: typeql::query::Pipeline,
         source_query: String,
         storage_counters: StorageCounters,
-    ) -> JoinHandle<ControlFlow<(), ()>> {
+        interrupt: ExecutionInterrupt,
+    ) -> JoinHandle<()> {
         debug_assert!(self.query_queue.is_empty() && self.running_write_query.is_none() && self.transaction.is_some());
         let timeout_at = self.timeout_at;
-        let interrupt = self.query_interrupt_receiver.clone();
+        let diagnostics_manager = self.server_state.diagnostics_manager();
+        let owner = self.owner.clone();
         with_readable_transaction!(self.transaction.as_ref().unwrap(), |transaction| {
+            let database_name = transaction.database.name().to_owned();
             let snapshot = transaction.snapshot.clone();
             let type_manager = transaction.type_manager.clone();
             let thing_manager = transaction.thing_manager.clone();
             let function_manager = transaction.function_manager.clone();
-            let query_manager = transaction.query_manager.clone();
+            let query_manager = match query_options.profile {
+                true => transaction.query_manager.profiled(),
+                false => transaction.query_manager.clone(),
+            };
+            let query_manager =
+                query_manager.limited(execution_limits(&query_options)).bound(query_parameters(&query_options));
             spawn_blocking(move || {
+                let Some(ReadQueryPageRequest { mut responder, mut page_done }) = page_requests.blocking_recv() else {
+                    return;
+                };
+                let start = Instant::now();
                 let pipeline_result = query_manager.prepare_read_pipeline(
                     snapshot.clone(),
                     &type_manager,
@@ -973,179 +1264,261 @@ impl TransactionService {
                 let pipeline = match pipeline_result {
                     Ok(pipeline) => pipeline,
                     Err(typedb_source) => {
-                        respond_else_return_break!(
-                            responder,
-                            TransactionServiceResponse::Err(TransactionServiceError::QueryFailed { typedb_source })
-                        );
-                        return Continue(());
+                        let error = TransactionServiceError::QueryFailed { typedb_source };
+                        let progress =
+                            match respond_transaction_response(responder, TransactionServiceResponse::Err(error)) {
+                                Ok(()) => Continue(ReadQueryProgress::Finished),
+                                Err(_) => Break(()),
+                            };
+                        let _ = page_done.send(progress);
+                        return;
                     }
                 };
-                Self::respond_read_query_sync(
+                let query_profile = pipeline.query_profile().clone();
+                let respond_page = Self::read_query_page_responder(
                     query_options,
                     pipeline,
                     &source_query,
                     timeout_at,
                     interrupt,
-                    responder,
                     snapshot,
-                    &type_manager,
+                    type_manager,
                     thing_manager,
                     storage_counters,
-                )
+                );
+                let diagnostics = ReadQueryDiagnostics {
+                    diagnostics_manager,
+                    database_name,
+                    owner,
+                    source_query,
+                    query_profile,
+                    start,
+                };
+                let mut respond_page = match respond_page {
+                    Ok(respond_page) => respond_page,
+                    Err(error) => {
+                        diagnostics.submit();
+                        let _ = respond_transaction_response(responder, TransactionServiceResponse::Err(error));
+                        let _ = page_done.send(Break(()));
+                        return;
+                    }
+                };
+                loop {
+                    let progress = respond_page(responder, cursor);
+                    let suspended = matches!(progress, Continue(ReadQueryProgress::Suspended));
+                    let _ = page_done.send(progress);
+                    // a suspended query waits for its next page to be requested, or for the service to end it
+                    let next_request = if suspended { page_requests.blocking_recv() } else { None };
+                    let Some(request) = next_request else { break };
+                    (responder, page_done) = (request.responder, request.page_done);
+                }
+                // the answers hold the snapshot, so they are dropped before the service sees the worker finish
+                drop(respond_page);
+                diagnostics.submit();
             })
         })
     }
 
-    fn respond_read_query_sync<Snapshot: ReadableSnapshot>(
+    // Creates the iterator over the query's answers, and the responder advancing it by a page at a time
+    fn read_query_page_responder<Snapshot: ReadableSnapshot + 'static>(
         query_options: QueryOptions,
         pipeline: Pipeline<Snapshot, ReadPipelineStage<Snapshot>>,
         source_query: &str,
         timeout_at: Instant,
         mut interrupt: ExecutionInterrupt,
-        responder: TransactionResponder,
         snapshot: Arc<Snapshot>,
-        type_manager: &TypeManager,
+        type_manager: Arc<TypeManager>,
         thing_manager: Arc<ThingManager>,
         storage_counters: StorageCounters,
-    ) -> ControlFlow<(), ()> {
-        let query_profile = if pipeline.has_fetch() {
-            let (iterator, context) = unwrap_or_execute_else_respond_error_and_return_break!(
-                pipeline.into_documents_iterator(interrupt.clone()),
-                responder,
-                |(err, _)| {
-                    TransactionServiceError::QueryFailed {
-                        typedb_source: Box::new(QueryError::ReadPipelineExecution {
-                            source_query: source_query.to_string(),
-                            typedb_source: err,
-                        }),
-                    }
-                }
-            );
-
-            let parameters = context.parameters;
-            let mut result = vec![];
-            let mut warning = None;
-            for next in iterator {
-                if let Some(limit) = query_options.answer_count_limit {
-                    if result.len() >= limit {
-                        warning = Some(QueryAnswerWarning::ReadResultsLimitExceeded { limit });
-                        break;
-                    }
-                }
-
-                check_timeout_else_respond_error_and_return_break!(timeout_at, responder);
-                check_interrupt_else_respond_error_and_return_break!(interrupt, responder);
-
-                let document =
-                    unwrap_or_execute_else_respond_error_and_return_break!(next, responder, |typedb_source| {
-                        TransactionServiceError::PipelineExecution { typedb_source: *typedb_source }
-                    });
-
-                let encoded_document = encode_document(
-                    document,
+    ) -> Result<Box<RespondPageFn>, TransactionServiceError> {
+        let query_failed = |err| TransactionServiceError::QueryFailed {
+            typedb_source: Box::new(QueryError::ReadPipelineExecution {
+                source_query: source_query.to_string(),
+                typedb_source: err,
+            }),
+        };
+        if pipeline.has_fetch() {
+            let (mut iterator, context) =
+                pipeline.into_documents_iterator(interrupt.clone()).map_err(|(err, _)| query_failed(err))?;
+            let mut next_page_first_document = None;
+            Ok(Box::new(move |responder, cursor| {
+                Self::respond_documents_page(
+                    &mut iterator,
+                    &mut next_page_first_document,
+                    &query_options,
+                    &context.profile,
+                    timeout_at,
+                    &mut interrupt,
+                    responder,
+                    cursor,
                     snapshot.as_ref(),
-                    type_manager,
+                    &type_manager,
                     &thing_manager,
-                    &parameters,
-                    storage_counters.clone(),
-                );
-                match encoded_document {
-                    Ok(encoded_document) => result.push(encoded_document),
-                    Err(typedb_source) => {
-                        respond_error_and_return_break!(
-                            responder,
-                            TransactionServiceError::PipelineExecution {
-                                typedb_source: PipelineExecutionError::ConceptRead { typedb_source }
-                            }
-                        );
-                    }
-                }
-            }
-            respond_else_return_break!(
-                responder,
-                TransactionServiceResponse::Query(QueryAnswer::ResDocuments((QueryType::Read, result, warning)))
-            );
-            context.profile
+                    &context.parameters,
+                    &storage_counters,
+                )
+            }))
         } else {
             let named_outputs = pipeline.rows_positions().unwrap();
             let descriptor: StreamQueryOutputDescriptor = named_outputs.clone().into_iter().sorted().collect();
-            let may_encode_result =
+            let (encoded_structure, include_involved_blocks) =
                 may_encode_pipeline_structure(&query_options, pipeline.pipeline_structure(), |structure| {
                     encode_analyzed_pipeline_for_studio(snapshot.as_ref(), &type_manager, structure)
-                });
-            let (encoded_structure, include_involved_blocks) =
-                unwrap_or_execute_else_respond_error_and_return_break!(may_encode_result, responder, |typedb_source| {
+                })
+                .map_err(|typedb_source| TransactionServiceError::PipelineExecution {
+                    typedb_source: PipelineExecutionError::ConceptRead { typedb_source },
+                })?;
+            let (mut iterator, context) =
+                pipeline.into_rows_iterator(interrupt.clone()).map_err(|(err, _)| query_failed(err))?;
+            // the pipeline structure is only sent with the first page
+            let mut encoded_structure = encoded_structure;
+            let mut next_page_first_row = None;
+            Ok(Box::new(move |responder, cursor| {
+                Self::respond_rows_page(
+                    &mut iterator,
+                    &mut next_page_first_row,
+                    encoded_structure.take(),
+                    &descriptor,
+                    &include_involved_blocks,
+                    &query_options,
+                    &context.profile,
+                    timeout_at,
+                    &mut interrupt,
+                    responder,
+                    cursor,
+                    snapshot.as_ref(),
+                    &type_manager,
+                    &thing_manager,
+                    &storage_counters,
+                )
+            }))
+        }
+    }
+
+    fn respond_documents_page<Snapshot: ReadableSnapshot>(
+        iterator: &mut impl Iterator<Item = Result<ConceptDocument, Box<PipelineExecutionError>>>,
+        next_page_first_document: &mut Option<serde_json::Value>,
+        query_options: &QueryOptions,
+        query_profile: &QueryProfile,
+        timeout_at: Instant,
+        interrupt: &mut ExecutionInterrupt,
+        responder: TransactionResponder,
+        cursor: Option<Uuid>,
+        snapshot: &Snapshot,
+        type_manager: &TypeManager,
+        thing_manager: &ThingManager,
+        parameters: &ParameterRegistry,
+        storage_counters: &StorageCounters,
+    ) -> ControlFlow<(), ReadQueryProgress> {
+        let mut result: Vec<_> = next_page_first_document.take().into_iter().collect();
+        let mut warning = None;
+        for next in iterator {
+            check_timeout_else_respond_error_and_return_break!(timeout_at, responder);
+            check_interrupt_else_respond_error_and_return_break!(interrupt, responder);
+
+            let document = unwrap_or_execute_else_respond_error_and_return_break!(next, responder, |typedb_source| {
+                TransactionServiceError::PipelineExecution { typedb_source: *typedb_source }
+            });
+
+            let encoded_document = unwrap_or_execute_else_respond_error_and_return_break!(
+                encode_document(document, snapshot, type_manager, thing_manager, parameters, storage_counters.clone()),
+                responder,
+                |typedb_source| {
                     TransactionServiceError::PipelineExecution {
                         typedb_source: PipelineExecutionError::ConceptRead { typedb_source },
                     }
-                });
-            let (mut iterator, context) = unwrap_or_execute_else_respond_error_and_return_break!(
-                pipeline.into_rows_iterator(interrupt.clone()),
-                responder,
-                |(err, _)| {
-                    TransactionServiceError::QueryFailed {
-                        typedb_source: Box::new(QueryError::ReadPipelineExecution {
-                            source_query: source_query.to_string(),
-                            typedb_source: err,
-                        }),
-                    }
                 }
             );
-
-            let mut result = vec![];
-            let mut warning = None;
-            while let Some(next) = iterator.next() {
-                if let Some(limit) = query_options.answer_count_limit {
-                    if result.len() >= limit {
-                        warning = Some(QueryAnswerWarning::ReadResultsLimitExceeded { limit });
-                        break;
-                    }
+            if let Some(limit) = query_options.answer_count_limit {
+                if result.len() >= limit {
+                    // kept for the next page, so that a continued query does not skip it
+                    *next_page_first_document = Some(encoded_document);
+                    warning = Some(QueryAnswerWarning::ReadResultsLimitExceeded { limit });
+                    break;
                 }
+            }
+            result.push(encoded_document);
+        }
+        let (progress, cursor) = Self::page_progress(warning.is_some(), cursor);
+        let answer = QueryAnswer::ResDocuments((QueryType::Read, result, warning)).may_attach_cursor(cursor);
+        respond_else_return_break!(
+            responder,
+            TransactionServiceResponse::Query(answer.may_attach_profile(query_options, query_profile))
+        );
+        Continue(progress)
+    }
 
-                check_timeout_else_respond_error_and_return_break!(timeout_at, responder);
-                check_interrupt_else_respond_error_and_return_break!(interrupt, responder);
+    fn respond_rows_page<Snapshot: ReadableSnapshot + 'static>(
+        iterator: &mut ReadStageIterator<Snapshot>,
+        next_page_first_row: &mut Option<serde_json::Value>,
+        encoded_structure: Option<AnalyzedPipelineResponse>,
+        descriptor: &StreamQueryOutputDescriptor,
+        include_involved_blocks: &IncludeInvolvedBlocks,
+        query_options: &QueryOptions,
+        query_profile: &QueryProfile,
+        timeout_at: Instant,
+        interrupt: &mut ExecutionInterrupt,
+        responder: TransactionResponder,
+        cursor: Option<Uuid>,
+        snapshot: &Snapshot,
+        type_manager: &TypeManager,
+        thing_manager: &ThingManager,
+        storage_counters: &StorageCounters,
+    ) -> ControlFlow<(), ReadQueryProgress> {
+        let mut result: Vec<_> = next_page_first_row.take().into_iter().collect();
+        let mut warning = None;
+        while let Some(next) = iterator.next() {
+            check_timeout_else_respond_error_and_return_break!(timeout_at, responder);
+            check_interrupt_else_respond_error_and_return_break!(interrupt, responder);
 
-                let row = unwrap_or_execute_else_respond_error_and_return_break!(next, responder, |typedb_source| {
-                    TransactionServiceError::PipelineExecution { typedb_source: *typedb_source }
-                });
+            let row = unwrap_or_execute_else_respond_error_and_return_break!(next, responder, |typedb_source| {
+                TransactionServiceError::PipelineExecution { typedb_source: *typedb_source }
+            });
 
-                let encoded_row = encode_row(
+            let encoded_row = unwrap_or_execute_else_respond_error_and_return_break!(
+                encode_row(
                     row,
-                    &descriptor,
-                    snapshot.as_ref(),
+                    descriptor,
+                    snapshot,
                     type_manager,
-                    &thing_manager,
+                    thing_manager,
                     query_options.include_instance_types,
-                    &include_involved_blocks,
+                    include_involved_blocks,
                     storage_counters.clone(),
-                );
-                match encoded_row {
-                    Ok(encoded_row) => result.push(encoded_row),
-                    Err(typedb_source) => {
-                        respond_error_and_return_break!(
-                            responder,
-                            TransactionServiceError::PipelineExecution {
-                                typedb_source: PipelineExecutionError::ConceptRead { typedb_source }
-                            }
-                        );
+                ),
+                responder,
+                |typedb_source| {
+                    TransactionServiceError::PipelineExecution {
+                        typedb_source: PipelineExecutionError::ConceptRead { typedb_source },
                     }
                 }
-            }
-            respond_else_return_break!(
-                responder,
-                TransactionServiceResponse::Query(QueryAnswer::ResRows((
-                    QueryType::Read,
-                    result,
-                    encoded_structure,
-                    warning
-                )))
             );
-            context.profile
-        };
-        if query_profile.is_enabled() {
-            event!(Level::INFO, "Read query done (including network request time).\n{}", query_profile);
+            if let Some(limit) = query_options.answer_count_limit {
+                if result.len() >= limit {
+                    // kept for the next page, so that a continued query does not skip it
+                    *next_page_first_row = Some(encoded_row);
+                    warning = Some(QueryAnswerWarning::ReadResultsLimitExceeded { limit });
+                    break;
+                }
+            }
+            result.push(encoded_row);
+        }
+        let (progress, cursor) = Self::page_progress(warning.is_some(), cursor);
+        let answer =
+            QueryAnswer::ResRows((QueryType::Read, result, encoded_structure, warning)).may_attach_cursor(cursor);
+        respond_else_return_break!(
+            responder,
+            TransactionServiceResponse::Query(answer.may_attach_profile(query_options, query_profile))
+        );
+        Continue(progress)
+    }
+
+    // A query is only suspended when it has more answers than fit in a page, and may be continued
+    fn page_progress(has_more_answers: bool, cursor: Option<Uuid>) -> (ReadQueryProgress, Option<Uuid>) {
+        match (has_more_answers, cursor) {
+            (true, Some(cursor)) => (ReadQueryProgress::Suspended, Some(cursor)),
+            _ => (ReadQueryProgress::Finished, None),
         }
-        Continue(())
     }
 
     async fn handle_analyse_query(&mut self, query: String, responder: TransactionResponder) -> ControlFlow<(), ()> {
@@ -1214,12 +1587,54 @@ impl TransactionService {
         .await
         .expect("Expected read query completion")
     }
+
+    async fn run_explain_query(
+        &mut self,
+        responder: TransactionResponder,
+        query_options: QueryOptions,
+        pipeline: typeql::query::Pipeline,
+        source_query: String,
+    ) -> ControlFlow<(), ()> {
+        debug_assert!(self.query_queue.is_empty() && self.running_write_query.is_none() && self.transaction.is_some());
+        with_readable_transaction!(self.transaction.as_ref().unwrap(), |transaction| {
+            let snapshot = transaction.snapshot.clone();
+            let type_manager = transaction.type_manager.clone();
+            let thing_manager = transaction.thing_manager.clone();
+            let function_manager = transaction.function_manager.clone();
+            let query_manager = transaction.query_manager.bound(query_parameters(&query_options));
+            spawn_blocking(move || {
+                let query_type = match is_write_pipeline(&pipeline) {
+                    true => QueryType::Write,
+                    false => QueryType::Read,
+                };
+                let explain_result = query_manager.explain(
+                    snapshot.as_ref(),
+                    &type_manager,
+                    thing_manager,
+                    &function_manager,
+                    &pipeline,
+                    &source_query,
+                );
+                let plan = unwrap_or_execute_else_respond_error_and_return_break!(
+                    explain_result,
+                    responder,
+                    |typedb_source| { TransactionServiceError::ExplainQueryFailed { typedb_source: *typedb_source } }
+                );
+                let answer = QueryAnswer::ResPlan((query_type, encode_query_plan(plan)));
+                respond_else_return_break!(responder, TransactionServiceResponse::Query(answer));
+                Continue(())
+            })
+        })
+        .await
+        .expect("Expected read query completion")
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 enum QueueOptions {
     Query(QueryOptions),
     Analyze,
+    Explain(QueryOptions),
 }
 
 impl QueueOptions {
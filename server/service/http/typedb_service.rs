@@ -4,20 +4,26 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::Duration,
+};
 
 use axum::{
     Router,
-    extract::State,
+    body::Body,
+    extract::{Query, State},
     response::{IntoResponse, Redirect},
     routing::{delete, get, post, put},
 };
 use concurrency::{IntervalTaskParameters, TokioTaskSpawner};
-use diagnostics::metrics::ActionKind;
-use http::StatusCode;
+use diagnostics::{audit, metrics::ActionKind};
+use http::{StatusCode, header::CONTENT_TYPE};
 use options::{QueryOptions, TransactionOptions};
 use resource::constants::common::SECONDS_IN_MINUTE;
-use system::concepts::{Credential, User};
+use storage::sequence_number::SequenceNumber;
+use system::concepts::{Grant, User};
 use tokio::{
     sync::{
         RwLock,
@@ -26,6 +32,7 @@ use tokio::{
     },
     time::timeout,
 };
+use tokio_stream::wrappers::ReceiverStream;
 use tower_http::cors::CorsLayer;
 use uuid::Uuid;
 
@@ -34,17 +41,30 @@ use crate::{
     service::{
         QueryType,
         http::{
+            change_stream_service::{CHANGE_STREAM_BUFFER_SIZE, ChangeStreamService},
             diagnostics::run_with_diagnostics_async,
             error::HttpServiceError,
             message::{
                 analyze::{AnalysedQueryResponse, TransactionAnalyzePayload},
-                authentication::{SigninPayload, encode_token},
+                authentication::{PasswordChangePayload, SigninPayload, encode_token},
                 body::{JsonBody, PlainTextBody},
-                database::{DatabasePath, encode_database, encode_databases},
-                query::{QueryOptionsPayload, QueryPayload, TransactionQueryPayload},
+                change::DatabaseChangesQuery,
+                database::{DatabasePath, encode_database, encode_database_statistics, encode_databases},
+                query::{
+                    QueryContinuePayload, QueryOptionsPayload, QueryParameterPayload, QueryPayload,
+                    TransactionQueryPayload,
+                },
+                replication::{DatabaseReplicationQuery, encode_replication_records},
+                running_query::{
+                    RunningQueryPath, RunningTransactionPath, encode_open_transactions, encode_running_queries,
+                },
                 server::encode_servers,
                 transaction::{TransactionOpenPayload, TransactionPath, encode_transaction},
-                user::{CreateUserPayload, UpdateUserPayload, UserPath, encode_user, encode_users},
+                user::{
+                    ApiKeyPath, CreateApiKeyPayload, CreateUserPayload, GrantPayload, SessionPath, UpdateUserPayload,
+                    UserPath, encode_api_keys, encode_created_api_key, encode_grants, encode_sessions, encode_user,
+                    encode_users,
+                },
                 version::{PROTOCOL_VERSION_LATEST, ProtocolVersion, encode_server_version},
             },
             transaction_service::{
@@ -105,22 +125,29 @@ impl HTTPTypeDBService {
         service: &HTTPTypeDBService,
         owner: String,
         payload: TransactionOpenPayload,
-    ) -> Result<(TransactionInfo, u64), HttpServiceError> {
+        suspends_read_queries: bool,
+    ) -> Result<(Uuid, TransactionInfo, u64), HttpServiceError> {
         let (request_sender, request_stream) = channel(TRANSACTION_REQUEST_BUFFER_SIZE);
-        let options =
-            payload.transaction_options.map(|options| options.into()).unwrap_or_else(|| TransactionOptions::default());
+        let options = payload
+            .transaction_options
+            .map(TransactionOptions::try_from)
+            .transpose()?
+            .unwrap_or_else(|| TransactionOptions::default());
         let transaction_timeout_millis = options.transaction_timeout_millis;
-        let mut transaction_service = TransactionService::new(service.server_state.clone(), request_stream);
+        let mut transaction_service =
+            TransactionService::new(service.server_state.clone(), owner.clone(), request_stream, suspends_read_queries);
 
         let database_name = payload.database_name;
 
+        let transaction_id = Uuid::new_v4();
         let processing_time = transaction_service
-            .open(payload.transaction_type, owner.clone(), database_name.clone(), options)
+            .open(payload.transaction_type, database_name.clone(), options, transaction_id)
             .await
             .map_err(|typedb_source| HttpServiceError::Transaction { typedb_source })?;
 
         tokio::spawn(async move { transaction_service.listen().await });
-        Ok((TransactionInfo { owner, database_name, request_sender, transaction_timeout_millis }, processing_time))
+        let transaction_info = TransactionInfo { owner, database_name, request_sender, transaction_timeout_millis };
+        Ok((transaction_id, transaction_info, processing_time))
     }
 
     async fn transaction_request(
@@ -146,9 +173,15 @@ impl HTTPTypeDBService {
         }
     }
 
-    fn build_query_request(query_options_payload: Option<QueryOptionsPayload>, query: String) -> TransactionRequest {
-        let query_options =
+    fn build_query_request(
+        query_options_payload: Option<QueryOptionsPayload>,
+        parameters: Option<BTreeMap<String, QueryParameterPayload>>,
+        query: String,
+    ) -> TransactionRequest {
+        let mut query_options: QueryOptions =
             query_options_payload.map(|options| options.into()).unwrap_or_else(|| QueryOptions::default_http());
+        query_options.parameters =
+            parameters.into_iter().flatten().map(|(variable, parameter)| (variable, parameter.into())).collect();
         TransactionRequest::Query(query_options, query)
     }
 
@@ -185,18 +218,36 @@ impl HTTPTypeDBService {
             .route("/:version/databases/:database-name", delete(Self::databases_delete))
             .route("/:version/databases/:database-name/schema", get(Self::databases_schema))
             .route("/:version/databases/:database-name/type-schema", get(Self::databases_type_schema))
+            .route("/:version/databases/:database-name/changes", get(Self::databases_changes))
+            .route("/:version/databases/:database-name/replication", get(Self::databases_replication))
+            .route("/:version/databases/:database-name/statistics", get(Self::databases_statistics))
             .route("/:version/users", get(Self::users))
             .route("/:version/users/:username", get(Self::users_get))
             .route("/:version/users/:username", post(Self::users_create))
             .route("/:version/users/:username", put(Self::users_update))
             .route("/:version/users/:username", delete(Self::users_delete))
+            .route("/:version/users/:username/grants", get(Self::users_grants))
+            .route("/:version/users/:username/grants", post(Self::users_grant))
+            .route("/:version/users/:username/grants", delete(Self::users_revoke))
+            .route("/:version/users/:username/sessions", get(Self::users_sessions))
+            .route("/:version/users/:username/sessions", delete(Self::users_sessions_revoke))
+            .route("/:version/users/:username/sessions/:session-id", delete(Self::users_session_revoke))
+            .route("/:version/users/:username/api-keys", get(Self::users_api_keys))
+            .route("/:version/users/:username/api-keys", post(Self::users_api_key_create))
+            .route("/:version/users/:username/api-keys/:key-name", delete(Self::users_api_key_delete))
+            .route("/:version/users/:username/password-expire", post(Self::users_password_expire))
             .route("/:version/transactions/open", post(Self::transaction_open))
             .route("/:version/transactions/:transaction-id/commit", post(Self::transactions_commit))
             .route("/:version/transactions/:transaction-id/close", post(Self::transactions_close))
             .route("/:version/transactions/:transaction-id/rollback", post(Self::transactions_rollback))
             .route("/:version/transactions/:transaction-id/analyze", post(Self::transactions_analyse))
             .route("/:version/transactions/:transaction-id/query", post(Self::transactions_query))
+            .route("/:version/transactions/:transaction-id/query/continue", post(Self::transactions_query_continue))
             .route("/:version/query", post(Self::query))
+            .route("/:version/queries", get(Self::queries))
+            .route("/:version/queries/:query-id", delete(Self::queries_kill))
+            .route("/:version/queries/transactions", get(Self::queries_transactions))
+            .route("/:version/queries/transactions/:transaction-id", delete(Self::queries_transaction_kill))
             .with_state(service)
     }
 
@@ -208,6 +259,7 @@ impl HTTPTypeDBService {
             .route("/:version/health", get(Self::health))
             .route("/:version/version", get(Self::version))
             .route("/:version/signin", post(Self::signin))
+            .route("/:version/signin/password-change", post(Self::signin_password_change))
             .with_state(service)
     }
 
@@ -248,7 +300,8 @@ impl HTTPTypeDBService {
         State(service): State<Arc<HTTPTypeDBService>>,
         JsonBody(payload): JsonBody<SigninPayload>,
     ) -> impl IntoResponse {
-        run_with_diagnostics_async(
+        let username = payload.username.clone();
+        let response = run_with_diagnostics_async(
             service.server_state.diagnostics_manager(),
             None::<&str>,
             ActionKind::SignIn,
@@ -261,8 +314,31 @@ impl HTTPTypeDBService {
                     .map(|token| JsonBody(encode_token(token)))
                     .map_err(|typedb_source| HttpServiceError::State { typedb_source })
             },
-        )
-        .await
+        );
+        audit::with_accessor(username, response).await
+    }
+
+    // Unauthenticated, so that users whose password has expired can replace it
+    async fn signin_password_change(
+        _version: ProtocolVersion,
+        State(service): State<Arc<HTTPTypeDBService>>,
+        JsonBody(payload): JsonBody<PasswordChangePayload>,
+    ) -> impl IntoResponse {
+        let username = payload.username.clone();
+        let response = run_with_diagnostics_async(
+            service.server_state.diagnostics_manager(),
+            None::<&str>,
+            ActionKind::UsersPasswordChange,
+            || async {
+                service
+                    .server_state
+                    .users()
+                    .password_change(&payload.username, &payload.password, payload.new_password)
+                    .await
+                    .map_err(|typedb_source| HttpServiceError::State { typedb_source })
+            },
+        );
+        audit::with_accessor(username, response).await
     }
 
     async fn servers(_version: ProtocolVersion, State(service): State<Arc<HTTPTypeDBService>>) -> impl IntoResponse {
@@ -329,6 +405,7 @@ impl HTTPTypeDBService {
     async fn databases_create(
         _version: ProtocolVersion,
         State(service): State<Arc<HTTPTypeDBService>>,
+        accessor: Accessor,
         database_path: DatabasePath,
     ) -> impl IntoResponse {
         run_with_diagnostics_async(
@@ -339,7 +416,7 @@ impl HTTPTypeDBService {
                 service
                     .server_state
                     .databases()
-                    .create(&database_path.database_name)
+                    .create(accessor, &database_path.database_name)
                     .await
                     .map_err(|typedb_source| HttpServiceError::State { typedb_source })
             },
@@ -350,6 +427,7 @@ impl HTTPTypeDBService {
     async fn databases_delete(
         _version: ProtocolVersion,
         State(service): State<Arc<HTTPTypeDBService>>,
+        accessor: Accessor,
         database_path: DatabasePath,
     ) -> impl IntoResponse {
         run_with_diagnostics_async(
@@ -360,7 +438,7 @@ impl HTTPTypeDBService {
                 service
                     .server_state
                     .databases()
-                    .delete(&database_path.database_name)
+                    .delete(accessor, &database_path.database_name)
                     .await
                     .map_err(|typedb_source| HttpServiceError::State { typedb_source })
             },
@@ -412,6 +490,81 @@ impl HTTPTypeDBService {
         .await
     }
 
+    async fn databases_changes(
+        _version: ProtocolVersion,
+        State(service): State<Arc<HTTPTypeDBService>>,
+        accessor: Accessor,
+        database_path: DatabasePath,
+        Query(query): Query<DatabaseChangesQuery>,
+    ) -> impl IntoResponse {
+        run_with_diagnostics_async(
+            service.server_state.diagnostics_manager(),
+            Some(&database_path.database_name),
+            ActionKind::DatabaseChanges,
+            || async {
+                let stream = service
+                    .server_state
+                    .databases()
+                    .change_stream(accessor, &database_path.database_name, query.from.map(SequenceNumber::new))
+                    .await
+                    .map_err(|typedb_source| HttpServiceError::State { typedb_source })?;
+                let (line_sender, line_receiver) = channel(CHANGE_STREAM_BUFFER_SIZE);
+                let change_stream_service =
+                    ChangeStreamService::new(stream, line_sender, service.server_state.shutdown_receiver());
+                tokio::spawn(async move { change_stream_service.listen().await });
+                Ok(([(CONTENT_TYPE, "application/x-ndjson")], Body::from_stream(ReceiverStream::new(line_receiver))))
+            },
+        )
+        .await
+    }
+
+    async fn databases_replication(
+        _version: ProtocolVersion,
+        State(service): State<Arc<HTTPTypeDBService>>,
+        accessor: Accessor,
+        database_path: DatabasePath,
+        Query(query): Query<DatabaseReplicationQuery>,
+    ) -> impl IntoResponse {
+        run_with_diagnostics_async(
+            service.server_state.diagnostics_manager(),
+            Some(&database_path.database_name),
+            ActionKind::DatabaseReplication,
+            || async {
+                service
+                    .server_state
+                    .databases()
+                    .replication_records(accessor, &database_path.database_name, SequenceNumber::new(query.from))
+                    .await
+                    .map(|records| JsonBody(encode_replication_records(records)))
+                    .map_err(|typedb_source| HttpServiceError::State { typedb_source })
+            },
+        )
+        .await
+    }
+
+    async fn databases_statistics(
+        _version: ProtocolVersion,
+        State(service): State<Arc<HTTPTypeDBService>>,
+        accessor: Accessor,
+        database_path: DatabasePath,
+    ) -> impl IntoResponse {
+        run_with_diagnostics_async(
+            service.server_state.diagnostics_manager(),
+            Some(&database_path.database_name),
+            ActionKind::DatabaseStatistics,
+            || async {
+                service
+                    .server_state
+                    .databases()
+                    .statistics(accessor, &database_path.database_name)
+                    .await
+                    .map(|statistics| JsonBody(encode_database_statistics(statistics)))
+                    .map_err(|typedb_source| HttpServiceError::State { typedb_source })
+            },
+        )
+        .await
+    }
+
     async fn users(
         _version: ProtocolVersion,
         State(service): State<Arc<HTTPTypeDBService>>,
@@ -440,7 +593,8 @@ impl HTTPTypeDBService {
         accessor: Accessor,
         user_path: UserPath,
     ) -> impl IntoResponse {
-        run_with_diagnostics_async(
+        let username = user_path.username.clone();
+        let response = run_with_diagnostics_async(
             service.server_state.diagnostics_manager(),
             None::<&str>,
             ActionKind::UsersGet,
@@ -453,8 +607,8 @@ impl HTTPTypeDBService {
                     .map_err(|typedb_source| HttpServiceError::State { typedb_source })
                     .map(|user| JsonBody(encode_user(&user)))
             },
-        )
-        .await
+        );
+        audit::with_target_user(username, response).await
     }
 
     async fn users_create(
@@ -464,23 +618,23 @@ impl HTTPTypeDBService {
         user_path: UserPath,
         JsonBody(payload): JsonBody<CreateUserPayload>,
     ) -> impl IntoResponse {
-        run_with_diagnostics_async(
+        let username = user_path.username.clone();
+        let response = run_with_diagnostics_async(
             service.server_state.diagnostics_manager(),
             None::<&str>,
             ActionKind::UsersCreate,
             || async {
                 let user = User { name: user_path.username };
-                let credential = Credential::new_password(payload.password.as_str());
 
                 service
                     .server_state
                     .users()
-                    .create(accessor, user, credential)
+                    .create(accessor, user, payload.password)
                     .await
                     .map_err(|typedb_source| HttpServiceError::State { typedb_source })
             },
-        )
-        .await
+        );
+        audit::with_target_user(username, response).await
     }
 
     async fn users_update(
@@ -490,24 +644,24 @@ impl HTTPTypeDBService {
         user_path: UserPath,
         JsonBody(payload): JsonBody<UpdateUserPayload>,
     ) -> impl IntoResponse {
-        run_with_diagnostics_async(
+        let username = user_path.username.clone();
+        let response = run_with_diagnostics_async(
             service.server_state.diagnostics_manager(),
             None::<&str>,
             ActionKind::UsersUpdate,
             || async {
                 let user_update = None; // updating username is not supported now
-                let credential_update = Some(Credential::new_password(&payload.password));
                 let username = user_path.username.as_str();
 
                 service
                     .server_state
                     .users()
-                    .update(accessor, username, user_update, credential_update)
+                    .update(accessor, username, user_update, Some(payload.password))
                     .await
                     .map_err(|typedb_source| HttpServiceError::State { typedb_source })
             },
-        )
-        .await
+        );
+        audit::with_target_user(username, response).await
     }
 
     async fn users_delete(
@@ -516,7 +670,8 @@ impl HTTPTypeDBService {
         accessor: Accessor,
         user_path: UserPath,
     ) -> impl IntoResponse {
-        run_with_diagnostics_async(
+        let username = user_path.username.clone();
+        let response = run_with_diagnostics_async(
             service.server_state.diagnostics_manager(),
             None::<&str>,
             ActionKind::UsersDelete,
@@ -530,30 +685,352 @@ impl HTTPTypeDBService {
                     .await
                     .map_err(|typedb_source| HttpServiceError::State { typedb_source })
             },
+        );
+        audit::with_target_user(username, response).await
+    }
+
+    async fn users_grants(
+        _version: ProtocolVersion,
+        State(service): State<Arc<HTTPTypeDBService>>,
+        accessor: Accessor,
+        user_path: UserPath,
+    ) -> impl IntoResponse {
+        let username = user_path.username.clone();
+        let response = run_with_diagnostics_async(
+            service.server_state.diagnostics_manager(),
+            None::<&str>,
+            ActionKind::UsersGrants,
+            || async {
+                service
+                    .server_state
+                    .users()
+                    .grants(accessor, &user_path.username)
+                    .await
+                    .map(|grants| JsonBody(encode_grants(grants)))
+                    .map_err(|typedb_source| HttpServiceError::State { typedb_source })
+            },
+        );
+        audit::with_target_user(username, response).await
+    }
+
+    async fn users_grant(
+        _version: ProtocolVersion,
+        State(service): State<Arc<HTTPTypeDBService>>,
+        accessor: Accessor,
+        user_path: UserPath,
+        JsonBody(payload): JsonBody<GrantPayload>,
+    ) -> impl IntoResponse {
+        let username = user_path.username.clone();
+        let response = run_with_diagnostics_async(
+            service.server_state.diagnostics_manager(),
+            None::<&str>,
+            ActionKind::UsersGrant,
+            || async {
+                let grant = Grant::try_from(payload)?;
+                service
+                    .server_state
+                    .users()
+                    .grant(accessor, &user_path.username, grant)
+                    .await
+                    .map_err(|typedb_source| HttpServiceError::State { typedb_source })
+            },
+        );
+        audit::with_target_user(username, response).await
+    }
+
+    async fn users_revoke(
+        _version: ProtocolVersion,
+        State(service): State<Arc<HTTPTypeDBService>>,
+        accessor: Accessor,
+        user_path: UserPath,
+        JsonBody(payload): JsonBody<GrantPayload>,
+    ) -> impl IntoResponse {
+        let username = user_path.username.clone();
+        let response = run_with_diagnostics_async(
+            service.server_state.diagnostics_manager(),
+            None::<&str>,
+            ActionKind::UsersRevoke,
+            || async {
+                let grant = Grant::try_from(payload)?;
+                service
+                    .server_state
+                    .users()
+                    .revoke(accessor, &user_path.username, grant)
+                    .await
+                    .map_err(|typedb_source| HttpServiceError::State { typedb_source })
+            },
+        );
+        audit::with_target_user(username, response).await
+    }
+
+    async fn users_sessions(
+        _version: ProtocolVersion,
+        State(service): State<Arc<HTTPTypeDBService>>,
+        accessor: Accessor,
+        user_path: UserPath,
+    ) -> impl IntoResponse {
+        let username = user_path.username.clone();
+        let response = run_with_diagnostics_async(
+            service.server_state.diagnostics_manager(),
+            None::<&str>,
+            ActionKind::UsersSessions,
+            || async {
+                service
+                    .server_state
+                    .users()
+                    .sessions(accessor, &user_path.username)
+                    .await
+                    .map(|sessions| JsonBody(encode_sessions(sessions)))
+                    .map_err(|typedb_source| HttpServiceError::State { typedb_source })
+            },
+        );
+        audit::with_target_user(username, response).await
+    }
+
+    async fn users_sessions_revoke(
+        _version: ProtocolVersion,
+        State(service): State<Arc<HTTPTypeDBService>>,
+        accessor: Accessor,
+        user_path: UserPath,
+    ) -> impl IntoResponse {
+        let username = user_path.username.clone();
+        let response = run_with_diagnostics_async(
+            service.server_state.diagnostics_manager(),
+            None::<&str>,
+            ActionKind::UsersSessionsRevoke,
+            || async {
+                service
+                    .server_state
+                    .users()
+                    .sessions_revoke(accessor, &user_path.username, None)
+                    .await
+                    .map_err(|typedb_source| HttpServiceError::State { typedb_source })
+            },
+        );
+        audit::with_target_user(username, response).await
+    }
+
+    async fn users_session_revoke(
+        _version: ProtocolVersion,
+        State(service): State<Arc<HTTPTypeDBService>>,
+        accessor: Accessor,
+        session_path: SessionPath,
+    ) -> impl IntoResponse {
+        let username = session_path.username.clone();
+        let response = run_with_diagnostics_async(
+            service.server_state.diagnostics_manager(),
+            None::<&str>,
+            ActionKind::UsersSessionsRevoke,
+            || async {
+                let session_id = session_path.session_id.to_string();
+                service
+                    .server_state
+                    .users()
+                    .sessions_revoke(accessor, &session_path.username, Some(&session_id))
+                    .await
+                    .map_err(|typedb_source| HttpServiceError::State { typedb_source })
+            },
+        );
+        audit::with_target_user(username, response).await
+    }
+
+    async fn queries(
+        _version: ProtocolVersion,
+        State(service): State<Arc<HTTPTypeDBService>>,
+        accessor: Accessor,
+    ) -> impl IntoResponse {
+        run_with_diagnostics_async(
+            service.server_state.diagnostics_manager(),
+            None::<&str>,
+            ActionKind::QueriesAll,
+            || async {
+                let queries = service.server_state.transactions().queries(accessor).await;
+                Ok::<_, HttpServiceError>(JsonBody(encode_running_queries(queries)))
+            },
         )
         .await
     }
 
-    async fn transaction_open(
+    async fn queries_kill(
         _version: ProtocolVersion,
         State(service): State<Arc<HTTPTypeDBService>>,
-        Accessor(accessor): Accessor,
-        JsonBody(payload): JsonBody<TransactionOpenPayload>,
+        accessor: Accessor,
+        query_path: RunningQueryPath,
+    ) -> impl IntoResponse {
+        run_with_diagnostics_async(
+            service.server_state.diagnostics_manager(),
+            None::<&str>,
+            ActionKind::QueryKill,
+            || async {
+                service
+                    .server_state
+                    .transactions()
+                    .query_kill(accessor, query_path.query_id)
+                    .await
+                    .map_err(|typedb_source| HttpServiceError::State { typedb_source })
+            },
+        )
+        .await
+    }
+
+    async fn queries_transactions(
+        _version: ProtocolVersion,
+        State(service): State<Arc<HTTPTypeDBService>>,
+        accessor: Accessor,
     ) -> impl IntoResponse {
         run_with_diagnostics_async(
             service.server_state.diagnostics_manager(),
-            Some(payload.database_name.clone()),
-            ActionKind::TransactionOpen,
+            None::<&str>,
+            ActionKind::TransactionsAll,
             || async {
-                let (transaction_info, _processing_time) = Self::transaction_new(&service, accessor, payload).await?;
-                let uuid = Uuid::new_v4();
-                service.transaction_services.write().await.insert(uuid, transaction_info);
-                Ok(JsonBody(encode_transaction(uuid)))
+                let transactions = service.server_state.transactions().transactions(accessor).await;
+                Ok::<_, HttpServiceError>(JsonBody(encode_open_transactions(transactions)))
             },
         )
         .await
     }
 
+    async fn queries_transaction_kill(
+        _version: ProtocolVersion,
+        State(service): State<Arc<HTTPTypeDBService>>,
+        accessor: Accessor,
+        transaction_path: RunningTransactionPath,
+    ) -> impl IntoResponse {
+        run_with_diagnostics_async(
+            service.server_state.diagnostics_manager(),
+            None::<&str>,
+            ActionKind::TransactionKill,
+            || async {
+                service
+                    .server_state
+                    .transactions()
+                    .kill(accessor, transaction_path.transaction_id)
+                    .await
+                    .map_err(|typedb_source| HttpServiceError::State { typedb_source })
+            },
+        )
+        .await
+    }
+
+    async fn users_api_keys(
+        _version: ProtocolVersion,
+        State(service): State<Arc<HTTPTypeDBService>>,
+        accessor: Accessor,
+        user_path: UserPath,
+    ) -> impl IntoResponse {
+        let username = user_path.username.clone();
+        let response = run_with_diagnostics_async(
+            service.server_state.diagnostics_manager(),
+            None::<&str>,
+            ActionKind::UsersApiKeys,
+            || async {
+                service
+                    .server_state
+                    .users()
+                    .api_keys(accessor, &user_path.username)
+                    .await
+                    .map(|api_keys| JsonBody(encode_api_keys(api_keys)))
+                    .map_err(|typedb_source| HttpServiceError::State { typedb_source })
+            },
+        );
+        audit::with_target_user(username, response).await
+    }
+
+    async fn users_api_key_create(
+        _version: ProtocolVersion,
+        State(service): State<Arc<HTTPTypeDBService>>,
+        accessor: Accessor,
+        user_path: UserPath,
+        JsonBody(payload): JsonBody<CreateApiKeyPayload>,
+    ) -> impl IntoResponse {
+        let username = user_path.username.clone();
+        let response = run_with_diagnostics_async(
+            service.server_state.diagnostics_manager(),
+            None::<&str>,
+            ActionKind::UsersApiKeyCreate,
+            || async {
+                let validity = payload.expires_in_seconds.map(Duration::from_secs);
+                service
+                    .server_state
+                    .users()
+                    .api_key_create(accessor, &user_path.username, &payload.name, validity)
+                    .await
+                    .map(|(api_key, key)| JsonBody(encode_created_api_key(&api_key, key)))
+                    .map_err(|typedb_source| HttpServiceError::State { typedb_source })
+            },
+        );
+        audit::with_target_user(username, response).await
+    }
+
+    async fn users_api_key_delete(
+        _version: ProtocolVersion,
+        State(service): State<Arc<HTTPTypeDBService>>,
+        accessor: Accessor,
+        api_key_path: ApiKeyPath,
+    ) -> impl IntoResponse {
+        let username = api_key_path.username.clone();
+        let response = run_with_diagnostics_async(
+            service.server_state.diagnostics_manager(),
+            None::<&str>,
+            ActionKind::UsersApiKeyDelete,
+            || async {
+                service
+                    .server_state
+                    .users()
+                    .api_key_delete(accessor, &api_key_path.username, &api_key_path.key_name)
+                    .await
+                    .map_err(|typedb_source| HttpServiceError::State { typedb_source })
+            },
+        );
+        audit::with_target_user(username, response).await
+    }
+
+    async fn users_password_expire(
+        _version: ProtocolVersion,
+        State(service): State<Arc<HTTPTypeDBService>>,
+        accessor: Accessor,
+        user_path: UserPath,
+    ) -> impl IntoResponse {
+        let username = user_path.username.clone();
+        let response = run_with_diagnostics_async(
+            service.server_state.diagnostics_manager(),
+            None::<&str>,
+            ActionKind::UsersPasswordExpire,
+            || async {
+                service
+                    .server_state
+                    .users()
+                    .password_expire(accessor, &user_path.username)
+                    .await
+                    .map_err(|typedb_source| HttpServiceError::State { typedb_source })
+            },
+        );
+        audit::with_target_user(username, response).await
+    }
+
+    async fn transaction_open(
+        _version: ProtocolVersion,
+        State(service): State<Arc<HTTPTypeDBService>>,
+        Accessor(accessor): Accessor,
+        JsonBody(payload): JsonBody<TransactionOpenPayload>,
+    ) -> impl IntoResponse {
+        audit::with_transaction_type(
+            payload.transaction_type.to_string(),
+            run_with_diagnostics_async(
+                service.server_state.diagnostics_manager(),
+                Some(payload.database_name.clone()),
+                ActionKind::TransactionOpen,
+                || async {
+                    let (uuid, transaction_info, _processing_time) =
+                        Self::transaction_new(&service, accessor, payload, true).await?;
+                    service.transaction_services.write().await.insert(uuid, transaction_info);
+                    Ok(JsonBody(encode_transaction(uuid)))
+                },
+            ),
+        )
+        .await
+    }
+
     async fn transactions_commit(
         _version: ProtocolVersion,
         State(service): State<Arc<HTTPTypeDBService>>,
@@ -664,6 +1141,43 @@ impl HTTPTypeDBService {
         let senders = service.transaction_services.read().await;
         let transaction = senders.get(&uuid).ok_or(HttpServiceError::no_open_transaction())?;
 
+        audit::with_query(
+            payload.query.clone(),
+            run_with_diagnostics_async(
+                service.server_state.diagnostics_manager(),
+                Some(transaction.database_name.clone()),
+                ActionKind::TransactionQuery,
+                || async {
+                    if accessor != transaction.owner {
+                        return Err(HttpServiceError::operation_not_permitted());
+                    }
+                    Self::transaction_request(
+                        &transaction,
+                        Self::build_query_request(payload.query_options, payload.parameters, payload.query),
+                        true,
+                    )
+                    .await
+                },
+            ),
+        )
+        .await
+    }
+
+    async fn transactions_query_continue(
+        _version: ProtocolVersion,
+        State(service): State<Arc<HTTPTypeDBService>>,
+        Accessor(accessor): Accessor,
+        path: TransactionPath,
+        JsonBody(payload): JsonBody<QueryContinuePayload>,
+    ) -> impl IntoResponse {
+        let uuid = path.transaction_id;
+        let senders = service.transaction_services.read().await;
+        let transaction = senders.get(&uuid).ok_or(HttpServiceError::no_open_transaction())?;
+        let cursor = Uuid::parse_str(&payload.cursor).map_err(|_| HttpServiceError::InvalidRequestField {
+            field: "cursor".to_string(),
+            value: payload.cursor,
+        })?;
+
         run_with_diagnostics_async(
             service.server_state.diagnostics_manager(),
             Some(transaction.database_name.clone()),
@@ -672,12 +1186,7 @@ impl HTTPTypeDBService {
                 if accessor != transaction.owner {
                     return Err(HttpServiceError::operation_not_permitted());
                 }
-                Self::transaction_request(
-                    &transaction,
-                    Self::build_query_request(payload.query_options, payload.query),
-                    true,
-                )
-                .await
+                Self::transaction_request(&transaction, TransactionRequest::QueryContinue(cursor), true).await
             },
         )
         .await
@@ -689,17 +1198,20 @@ impl HTTPTypeDBService {
         Accessor(accessor): Accessor,
         JsonBody(payload): JsonBody<QueryPayload>,
     ) -> impl IntoResponse {
-        run_with_diagnostics_async(
+        let transaction_type = payload.transaction_open_payload.transaction_type.to_string();
+        let query = payload.query.clone();
+        let response = run_with_diagnostics_async(
             service.server_state.diagnostics_manager(),
             Some(payload.transaction_open_payload.database_name.clone()),
             ActionKind::OneshotQuery,
             || async {
-                let (transaction_info, _processing_time) =
-                    Self::transaction_new(&service, accessor, payload.transaction_open_payload).await?;
+                // the transaction is closed after this query, so it could never be continued
+                let (_, transaction_info, _processing_time) =
+                    Self::transaction_new(&service, accessor, payload.transaction_open_payload, false).await?;
 
                 let transaction_response = Self::transaction_request(
                     &transaction_info,
-                    Self::build_query_request(payload.query_options, payload.query),
+                    Self::build_query_request(payload.query_options, payload.parameters, payload.query),
                     true,
                 )
                 .await?;
@@ -726,7 +1238,7 @@ impl HTTPTypeDBService {
 
                 Ok(TransactionServiceResponse::Query(query_response))
             },
-        )
-        .await
+        );
+        audit::with_transaction_type(transaction_type, audit::with_query(query, response)).await
     }
 }
@@ -4,6 +4,9 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+use std::time::SystemTime;
+
+use chrono::DateTime;
 use compiler::query_structure::{PipelineStructure, QueryStructureConjunctionID};
 use concept::error::ConceptReadError;
 pub use grpc::{IntoGrpcStatus, IntoProtocolErrorMessage, migration::import_service::DatabaseImportService};
@@ -11,6 +14,7 @@ use options::QueryOptions;
 use serde::{Deserialize, Serialize};
 
 pub mod admin;
+pub mod explain;
 pub(crate) mod export_service;
 pub(crate) mod grpc;
 pub mod http;
@@ -55,3 +59,8 @@ pub(crate) fn may_encode_pipeline_structure<T>(
         }
     }
 }
+
+/// Parses the RFC 3339 timestamp of a point-in-time read.
+pub(crate) fn parse_read_timestamp(value: &str) -> Option<SystemTime> {
+    DateTime::parse_from_rfc3339(value).ok().map(SystemTime::from)
+}
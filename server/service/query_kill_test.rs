@@ -0,0 +1,230 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::{path::PathBuf, time::Duration};
+
+use hyper::{Body, Client, Method, Request, StatusCode, header};
+use resource::{
+    constants::server::{DEFAULT_USER_NAME, DEFAULT_USER_PASSWORD},
+    distribution_info::DistributionInfo,
+};
+use server::{
+    ServerBuilder,
+    admin_proto::{self, type_db_admin_client::TypeDbAdminClient},
+    parameters::config::ConfigBuilder,
+};
+use test_utils::{TempDir, create_tmp_storage_dir};
+use tokio::sync::OnceCell;
+
+const GRPC_ADDRESS: &str = "127.0.0.1:11764";
+const HTTP_ADDRESS: &str = "127.0.0.1:11763";
+const ADMIN_PORT: u16 = 11762;
+const DISTRIBUTION_INFO: DistributionInfo =
+    DistributionInfo { logo: "logo", distribution: "TypeDB CE TEST", version: "0.0.0-test" };
+
+const DATABASE: &str = "query-kill";
+const PEOPLE: usize = 300;
+// Counts every combination of three people, which takes long enough to be killed while running
+const LONG_QUERY: &str = "match $a isa person; $b isa person; $c isa person; reduce $count = count;";
+
+// The data directory of the server and the token of a signed in user, once the database has its data
+static SERVER: OnceCell<(TempDir, String, tokio::sync::watch::Sender<()>)> = OnceCell::const_new();
+
+fn config_path() -> PathBuf {
+    std::env::current_dir().unwrap().join("server/config.yml")
+}
+
+async fn ensure_server_started() -> &'static str {
+    let (_, token, _) = SERVER
+        .get_or_init(|| async {
+            let (shutdown_sender, shutdown_receiver) = tokio::sync::watch::channel(());
+            let server_dir = create_tmp_storage_dir();
+            let config = ConfigBuilder::from_file(config_path())
+                .expect("Failed to load config file")
+                .server_listen_address(GRPC_ADDRESS)
+                .server_http_enabled(true)
+                .server_http_listen_address(HTTP_ADDRESS)
+                .server_http_advertise_address(format!("http://{HTTP_ADDRESS}"))
+                .admin_port(ADMIN_PORT)
+                .admin_enabled(true)
+                .data_directory(server_dir.as_ref())
+                .development_mode(true)
+                .build()
+                .expect("Failed to build config");
+
+            let server = ServerBuilder::new()
+                .distribution_info(DISTRIBUTION_INFO)
+                .shutdown_channel((shutdown_sender.clone(), shutdown_receiver))
+                .build(config)
+                .await
+                .expect("Failed to build server");
+
+            tokio::spawn(async move {
+                server.serve().await.expect("Server failed");
+            });
+
+            let credentials = serde_json::json!({ "username": DEFAULT_USER_NAME, "password": DEFAULT_USER_PASSWORD });
+            let (status, body) = http_request(Method::POST, "/v1/signin", None, credentials).await;
+            assert_eq!(status, StatusCode::OK, "Signin failed: {body}");
+            let token = body["token"].as_str().expect("Signin should return a token").to_owned();
+            let (status, body) =
+                http_request(Method::POST, &format!("/v1/databases/{DATABASE}"), Some(&token), serde_json::Value::Null)
+                    .await;
+            assert_eq!(status, StatusCode::OK, "Database creation failed: {body}");
+            oneshot_query(&token, "schema", "define entity person;").await;
+            let people = (0..PEOPLE).map(|i| format!("$p{i} isa person;")).collect::<Vec<_>>().join(" ");
+            oneshot_query(&token, "write", &format!("insert {people}")).await;
+
+            (server_dir, token, shutdown_sender)
+        })
+        .await;
+    token
+}
+
+async fn connect_admin_client() -> TypeDbAdminClient<tonic::transport::Channel> {
+    ensure_server_started().await;
+    for _ in 0..50 {
+        if let Ok(client) = TypeDbAdminClient::connect(format!("http://127.0.0.1:{ADMIN_PORT}")).await {
+            return client;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("Failed to connect to admin service")
+}
+
+async fn http_request(
+    method: Method,
+    path: &str,
+    token: Option<&str>,
+    body: serde_json::Value,
+) -> (StatusCode, serde_json::Value) {
+    let client = Client::new();
+    for _ in 0..50 {
+        let mut request = Request::builder()
+            .method(method.clone())
+            .uri(format!("http://{HTTP_ADDRESS}{path}"))
+            .header(header::CONTENT_TYPE, "application/json");
+        if let Some(token) = token {
+            request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+        }
+        let Ok(response) = client.request(request.body(Body::from(body.to_string())).unwrap()).await else {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            continue;
+        };
+        let status = response.status();
+        let bytes = hyper::body::to_bytes(response.into_body()).await.expect("Failed to read response body");
+        return (status, serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null));
+    }
+    panic!("Failed to connect to HTTP service")
+}
+
+async fn oneshot_query(token: &str, transaction_type: &str, query: &str) {
+    let body = serde_json::json!({
+        "databaseName": DATABASE,
+        "transactionType": transaction_type,
+        "query": query,
+        "commit": true,
+    });
+    let (status, body) = http_request(Method::POST, "/v1/query", Some(token), body).await;
+    assert_eq!(status, StatusCode::OK, "Query '{query}' failed: {body}");
+}
+
+async fn transaction_open(token: &str) -> String {
+    let body = serde_json::json!({ "databaseName": DATABASE, "transactionType": "read" });
+    let (status, body) = http_request(Method::POST, "/v1/transactions/open", Some(token), body).await;
+    assert_eq!(status, StatusCode::OK, "Opening a transaction failed: {body}");
+    body["transactionId"].as_str().expect("The response should have a transaction id").to_owned()
+}
+
+async fn transaction_query(token: &str, transaction_id: &str, query: &str) -> (StatusCode, serde_json::Value) {
+    let body = serde_json::json!({ "query": query });
+    http_request(Method::POST, &format!("/v1/transactions/{transaction_id}/query"), Some(token), body).await
+}
+
+#[tokio::test]
+async fn killing_a_running_query_twice_interrupts_it_once() {
+    let token = ensure_server_started().await;
+    let transaction_id = transaction_open(token).await;
+    let running = tokio::spawn({
+        let transaction_id = transaction_id.clone();
+        async move { transaction_query(token, &transaction_id, LONG_QUERY).await }
+    });
+
+    let mut query_id = None;
+    for _ in 0..100 {
+        let (status, body) = http_request(Method::GET, "/v1/queries", Some(token), serde_json::Value::Null).await;
+        assert_eq!(status, StatusCode::OK, "Listing queries failed: {body}");
+        query_id = body["queries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|query| query["transactionId"].as_str() == Some(&transaction_id))
+            .map(|query| query["id"].as_str().unwrap().to_owned());
+        if query_id.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    let query_id = query_id.expect("The query should be listed under its HTTP transaction id");
+
+    for _ in 0..2 {
+        let path = format!("/v1/queries/{query_id}");
+        let (status, body) = http_request(Method::DELETE, &path, Some(token), serde_json::Value::Null).await;
+        assert!(
+            status == StatusCode::OK || status == StatusCode::NOT_FOUND,
+            "Killing the query should succeed, or find it already finished: {body}"
+        );
+    }
+
+    let (status, body) = running.await.unwrap();
+    assert_eq!(status, StatusCode::BAD_REQUEST, "The killed query should fail: {body}");
+    let message = body["message"].as_str().unwrap_or_default();
+    assert!(message.contains("query kill"), "The query should end with its interrupt: {body}");
+}
+
+#[tokio::test]
+async fn idle_transactions_are_listed_and_killed_by_their_http_id() {
+    let token = ensure_server_started().await;
+    let transaction_id = transaction_open(token).await;
+
+    let (status, body) =
+        http_request(Method::GET, "/v1/queries/transactions", Some(token), serde_json::Value::Null).await;
+    assert_eq!(status, StatusCode::OK, "Listing transactions failed: {body}");
+    let listed = body["transactions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|transaction| transaction["id"].as_str() == Some(&transaction_id))
+        .cloned()
+        .expect("The idle transaction should be listed by its HTTP transaction id");
+    assert_eq!(listed["databaseName"], DATABASE);
+    assert_eq!(listed["transactionType"], "read");
+    assert_eq!(listed["runningQueries"], 0);
+
+    let mut admin_client = connect_admin_client().await;
+    let transactions = admin_client
+        .transactions(admin_proto::transactions::Req {})
+        .await
+        .expect("RPC failed")
+        .into_inner()
+        .transactions;
+    assert!(transactions.iter().any(|transaction| transaction.id == transaction_id));
+
+    let path = format!("/v1/queries/transactions/{transaction_id}");
+    let (status, body) = http_request(Method::DELETE, &path, Some(token), serde_json::Value::Null).await;
+    assert_eq!(status, StatusCode::OK, "Killing the transaction failed: {body}");
+
+    let mut closed = false;
+    for _ in 0..50 {
+        let (status, _) = transaction_query(token, &transaction_id, "match $p isa person; limit 1;").await;
+        if status != StatusCode::OK {
+            closed = true;
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert!(closed, "The killed transaction should be closed");
+}
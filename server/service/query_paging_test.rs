@@ -0,0 +1,262 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::{path::PathBuf, time::Duration};
+
+use hyper::{Body, Client, Method, Request, StatusCode, header};
+use resource::{
+    constants::server::{DEFAULT_USER_NAME, DEFAULT_USER_PASSWORD, MAX_SUSPENDED_READ_QUERIES_PER_TRANSACTION},
+    distribution_info::DistributionInfo,
+};
+use server::{ServerBuilder, parameters::config::ConfigBuilder};
+use test_utils::{TempDir, create_tmp_storage_dir};
+use tokio::sync::OnceCell;
+
+const GRPC_ADDRESS: &str = "127.0.0.1:11767";
+const HTTP_ADDRESS: &str = "127.0.0.1:11766";
+const ADMIN_PORT: u16 = 11765;
+const DISTRIBUTION_INFO: DistributionInfo =
+    DistributionInfo { logo: "logo", distribution: "TypeDB CE TEST", version: "0.0.0-test" };
+
+const DATABASE: &str = "query-paging";
+const PEOPLE: i64 = 10;
+const PAGE_SIZE: usize = 3;
+const PEOPLE_QUERY: &str = "match $p isa person, has id $id; select $id;";
+
+// The data directory of the server and the token of a signed in user, once the database has its data
+static SERVER: OnceCell<(TempDir, String, tokio::sync::watch::Sender<()>)> = OnceCell::const_new();
+
+fn config_path() -> PathBuf {
+    std::env::current_dir().unwrap().join("server/config.yml")
+}
+
+async fn ensure_server_started() -> &'static str {
+    let (_, token, _) = SERVER
+        .get_or_init(|| async {
+            let (shutdown_sender, shutdown_receiver) = tokio::sync::watch::channel(());
+            let server_dir = create_tmp_storage_dir();
+            let config = ConfigBuilder::from_file(config_path())
+                .expect("Failed to load config file")
+                .server_listen_address(GRPC_ADDRESS)
+                .server_http_enabled(true)
+                .server_http_listen_address(HTTP_ADDRESS)
+                .server_http_advertise_address(format!("http://{HTTP_ADDRESS}"))
+                .admin_port(ADMIN_PORT)
+                .admin_enabled(true)
+                .data_directory(server_dir.as_ref())
+                .development_mode(true)
+                .build()
+                .expect("Failed to build config");
+
+            let server = ServerBuilder::new()
+                .distribution_info(DISTRIBUTION_INFO)
+                .shutdown_channel((shutdown_sender.clone(), shutdown_receiver))
+                .build(config)
+                .await
+                .expect("Failed to build server");
+
+            tokio::spawn(async move {
+                server.serve().await.expect("Server failed");
+            });
+
+            let credentials = serde_json::json!({ "username": DEFAULT_USER_NAME, "password": DEFAULT_USER_PASSWORD });
+            let (status, body) = http_request(Method::POST, "/v1/signin", None, credentials).await;
+            assert_eq!(status, StatusCode::OK, "Signin failed: {body}");
+            let token = body["token"].as_str().expect("Signin should return a token").to_owned();
+            let (status, body) =
+                http_request(Method::POST, &format!("/v1/databases/{DATABASE}"), Some(&token), serde_json::Value::Null)
+                    .await;
+            assert_eq!(status, StatusCode::OK, "Database creation failed: {body}");
+            let schema = "define attribute id, value integer; entity person, owns id; entity marker;";
+            oneshot_query(&token, "schema", schema).await;
+            let people = (0..PEOPLE).map(|i| format!("$p{i} isa person, has id {i};")).collect::<Vec<_>>().join(" ");
+            oneshot_query(&token, "write", &format!("insert {people}")).await;
+
+            (server_dir, token, shutdown_sender)
+        })
+        .await;
+    token
+}
+
+async fn http_request(
+    method: Method,
+    path: &str,
+    token: Option<&str>,
+    body: serde_json::Value,
+) -> (StatusCode, serde_json::Value) {
+    let client = Client::new();
+    for _ in 0..50 {
+        let mut request = Request::builder()
+            .method(method.clone())
+            .uri(format!("http://{HTTP_ADDRESS}{path}"))
+            .header(header::CONTENT_TYPE, "application/json");
+        if let Some(token) = token {
+            request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+        }
+        let Ok(response) = client.request(request.body(Body::from(body.to_string())).unwrap()).await else {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            continue;
+        };
+        let status = response.status();
+        let bytes = hyper::body::to_bytes(response.into_body()).await.expect("Failed to read response body");
+        return (status, serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null));
+    }
+    panic!("Failed to connect to HTTP service")
+}
+
+async fn oneshot_query(token: &str, transaction_type: &str, query: &str) {
+    let body = serde_json::json!({
+        "databaseName": DATABASE,
+        "transactionType": transaction_type,
+        "query": query,
+        "commit": true,
+    });
+    let (status, body) = http_request(Method::POST, "/v1/query", Some(token), body).await;
+    assert_eq!(status, StatusCode::OK, "Query '{query}' failed: {body}");
+}
+
+async fn transaction_open(token: &str, transaction_type: &str) -> String {
+    let body = serde_json::json!({ "databaseName": DATABASE, "transactionType": transaction_type });
+    let (status, body) = http_request(Method::POST, "/v1/transactions/open", Some(token), body).await;
+    assert_eq!(status, StatusCode::OK, "Opening a transaction failed: {body}");
+    body["transactionId"].as_str().expect("The response should have a transaction id").to_owned()
+}
+
+async fn transaction_query(
+    token: &str,
+    transaction_id: &str,
+    query: &str,
+    query_options: serde_json::Value,
+) -> (StatusCode, serde_json::Value) {
+    let body = serde_json::json!({ "query": query, "queryOptions": query_options });
+    http_request(Method::POST, &format!("/v1/transactions/{transaction_id}/query"), Some(token), body).await
+}
+
+async fn query_continue(token: &str, transaction_id: &str, cursor: &str) -> (StatusCode, serde_json::Value) {
+    let path = format!("/v1/transactions/{transaction_id}/query/continue");
+    http_request(Method::POST, &path, Some(token), serde_json::json!({ "cursor": cursor })).await
+}
+
+// Runs the paged query of people, returning its first page
+async fn people_first_page(token: &str, transaction_id: &str) -> (StatusCode, serde_json::Value) {
+    let query_options = serde_json::json!({ "answerCountLimit": PAGE_SIZE });
+    transaction_query(token, transaction_id, PEOPLE_QUERY, query_options).await
+}
+
+fn page_ids(page: &serde_json::Value) -> Vec<i64> {
+    let answers = page["answers"].as_array().expect("The page should have answers");
+    answers.iter().map(|answer| answer["data"]["id"]["value"].as_i64().expect("Expected an id")).collect()
+}
+
+fn page_cursor(page: &serde_json::Value) -> &str {
+    page["cursor"].as_str().expect("A partial page should have a cursor")
+}
+
+#[tokio::test]
+async fn paged_read_query_returns_every_answer_once() {
+    let token = ensure_server_started().await;
+    let transaction_id = transaction_open(token, "read").await;
+
+    let (mut status, mut page) = people_first_page(token, &transaction_id).await;
+    let mut ids = Vec::new();
+    let mut cursors = Vec::new();
+    while status == StatusCode::PARTIAL_CONTENT {
+        assert_eq!(page_ids(&page).len(), PAGE_SIZE, "Every partial page should be full: {page}");
+        ids.extend(page_ids(&page));
+        let cursor = page_cursor(&page).to_owned();
+        (status, page) = query_continue(token, &transaction_id, &cursor).await;
+        cursors.push(cursor);
+    }
+    assert_eq!(status, StatusCode::OK, "The last page should complete the query: {page}");
+    assert!(page["cursor"].is_null(), "The last page should have no cursor: {page}");
+    ids.extend(page_ids(&page));
+
+    ids.sort();
+    assert_eq!(ids, (0..PEOPLE).collect::<Vec<_>>(), "Every answer should be returned exactly once");
+    assert!(cursors.windows(2).all(|pair| pair[0] == pair[1]), "A query should keep its cursor across pages");
+
+    let (status, body) = query_continue(token, &transaction_id, &cursors[0]).await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "A finished query should not be continued: {body}");
+}
+
+#[tokio::test]
+async fn suspended_queries_are_not_listed_or_timed_out() {
+    let token = ensure_server_started().await;
+    let transaction_id = transaction_open(token, "read").await;
+
+    let query_options = serde_json::json!({ "answerCountLimit": PAGE_SIZE, "queryTimeoutMillis": 500 });
+    let (status, page) = transaction_query(token, &transaction_id, PEOPLE_QUERY, query_options).await;
+    assert_eq!(status, StatusCode::PARTIAL_CONTENT, "The first page should be partial: {page}");
+
+    let (status, body) = http_request(Method::GET, "/v1/queries", Some(token), serde_json::Value::Null).await;
+    assert_eq!(status, StatusCode::OK, "Listing queries failed: {body}");
+    let queries = body["queries"].as_array().unwrap();
+    assert!(
+        queries.iter().all(|query| query["transactionId"].as_str() != Some(&transaction_id)),
+        "A suspended query should not be listed as running: {body}"
+    );
+
+    tokio::time::sleep(Duration::from_millis(1000)).await;
+    let (status, page) = query_continue(token, &transaction_id, page_cursor(&page)).await;
+    assert_eq!(status, StatusCode::PARTIAL_CONTENT, "The time suspended should not time the query out: {page}");
+}
+
+#[tokio::test]
+async fn suspending_queries_past_the_limit_ends_the_oldest() {
+    let token = ensure_server_started().await;
+    let transaction_id = transaction_open(token, "read").await;
+
+    let mut cursors = Vec::new();
+    for _ in 0..=MAX_SUSPENDED_READ_QUERIES_PER_TRANSACTION {
+        let (status, page) = people_first_page(token, &transaction_id).await;
+        assert_eq!(status, StatusCode::PARTIAL_CONTENT, "The first page should be partial: {page}");
+        cursors.push(page_cursor(&page).to_owned());
+    }
+
+    let (status, body) = query_continue(token, &transaction_id, &cursors[0]).await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "The oldest suspended query should have been ended: {body}");
+    for cursor in &cursors[1..] {
+        let (status, page) = query_continue(token, &transaction_id, cursor).await;
+        assert_eq!(status, StatusCode::PARTIAL_CONTENT, "The newer suspended queries should continue: {page}");
+        assert_eq!(page_ids(&page).len(), PAGE_SIZE);
+    }
+}
+
+#[tokio::test]
+async fn writes_and_rollbacks_end_suspended_queries() {
+    let token = ensure_server_started().await;
+    let transaction_id = transaction_open(token, "write").await;
+
+    let (status, page) = people_first_page(token, &transaction_id).await;
+    assert_eq!(status, StatusCode::PARTIAL_CONTENT, "The first page should be partial: {page}");
+    let (status, body) =
+        transaction_query(token, &transaction_id, "insert $m isa marker;", serde_json::Value::Null).await;
+    assert_eq!(status, StatusCode::OK, "The write query failed: {body}");
+    let (status, body) = query_continue(token, &transaction_id, page_cursor(&page)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "A write should end the suspended query: {body}");
+
+    let (status, page) = people_first_page(token, &transaction_id).await;
+    assert_eq!(status, StatusCode::PARTIAL_CONTENT, "The first page should be partial: {page}");
+    let path = format!("/v1/transactions/{transaction_id}/rollback");
+    let (status, body) = http_request(Method::POST, &path, Some(token), serde_json::Value::Null).await;
+    assert_eq!(status, StatusCode::OK, "The rollback failed: {body}");
+    let (status, body) = query_continue(token, &transaction_id, page_cursor(&page)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "A rollback should end the suspended query: {body}");
+}
+
+#[tokio::test]
+async fn commits_end_suspended_queries() {
+    let token = ensure_server_started().await;
+    let transaction_id = transaction_open(token, "write").await;
+
+    let (status, page) = people_first_page(token, &transaction_id).await;
+    assert_eq!(status, StatusCode::PARTIAL_CONTENT, "The first page should be partial: {page}");
+    let path = format!("/v1/transactions/{transaction_id}/commit");
+    let (status, body) = http_request(Method::POST, &path, Some(token), serde_json::Value::Null).await;
+    assert_eq!(status, StatusCode::OK, "The commit failed: {body}");
+    let (status, body) = query_continue(token, &transaction_id, page_cursor(&page)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "A commit should end the suspended query: {body}");
+}
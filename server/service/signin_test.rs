@@ -0,0 +1,189 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::PathBuf;
+
+use hyper::{Body, Client, Method, Request, StatusCode, header};
+use resource::distribution_info::DistributionInfo;
+use server::{
+    ServerBuilder,
+    admin_proto::{self, type_db_admin_client::TypeDbAdminClient},
+    parameters::config::ConfigBuilder,
+};
+use test_utils::{TempDir, create_tmp_storage_dir};
+use tokio::sync::OnceCell;
+use typedb_protocol::{authentication::token::create, type_db_client::TypeDbClient};
+
+const GRPC_ADDRESS: &str = "127.0.0.1:11739";
+const HTTP_ADDRESS: &str = "127.0.0.1:11738";
+const ADMIN_PORT: u16 = 11737;
+const DISTRIBUTION_INFO: DistributionInfo =
+    DistributionInfo { logo: "logo", distribution: "TypeDB CE TEST", version: "0.0.0-test" };
+
+const USERNAME: &str = "admin";
+
+static SERVER: OnceCell<(TempDir, tokio::sync::watch::Sender<()>)> = OnceCell::const_new();
+
+fn config_path() -> PathBuf {
+    std::env::current_dir().unwrap().join("server/config.yml")
+}
+
+async fn ensure_server_started() {
+    SERVER
+        .get_or_init(|| async {
+            let (shutdown_sender, shutdown_receiver) = tokio::sync::watch::channel(());
+            let server_dir = create_tmp_storage_dir();
+            let config = ConfigBuilder::from_file(config_path())
+                .expect("Failed to load config file")
+                .server_listen_address(GRPC_ADDRESS)
+                .server_http_enabled(true)
+                .server_http_listen_address(HTTP_ADDRESS)
+                .server_http_advertise_address(format!("http://{HTTP_ADDRESS}"))
+                .admin_port(ADMIN_PORT)
+                .admin_enabled(true)
+                .data_directory(server_dir.as_ref())
+                .development_mode(true)
+                .build()
+                .expect("Failed to build config");
+
+            let server = ServerBuilder::new()
+                .distribution_info(DISTRIBUTION_INFO)
+                .shutdown_channel((shutdown_sender.clone(), shutdown_receiver))
+                .build(config)
+                .await
+                .expect("Failed to build server");
+
+            tokio::spawn(async move {
+                server.serve().await.expect("Server failed");
+            });
+
+            (server_dir, shutdown_sender)
+        })
+        .await;
+}
+
+async fn connect_admin_client() -> TypeDbAdminClient<tonic::transport::Channel> {
+    ensure_server_started().await;
+    for _ in 0..50 {
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        if let Ok(client) = TypeDbAdminClient::connect(format!("http://127.0.0.1:{ADMIN_PORT}")).await {
+            return client;
+        }
+    }
+    panic!("Failed to connect to admin service")
+}
+
+async fn connect_client() -> TypeDbClient<tonic::transport::Channel> {
+    ensure_server_started().await;
+    for _ in 0..50 {
+        if let Ok(client) = TypeDbClient::connect(format!("http://{GRPC_ADDRESS}")).await {
+            return client;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    panic!("Failed to connect to gRPC service")
+}
+
+async fn create_api_key(name: &str, expires_in_seconds: Option<u64>) -> String {
+    let mut client = connect_admin_client().await;
+    let request = admin_proto::user_api_key_create::Req {
+        username: USERNAME.to_string(),
+        name: name.to_string(),
+        expires_in_seconds,
+    };
+    client.user_api_key_create(request).await.expect("RPC failed").into_inner().key
+}
+
+async fn delete_api_key(name: &str) {
+    let mut client = connect_admin_client().await;
+    let request = admin_proto::user_api_key_delete::Req { username: USERNAME.to_string(), name: name.to_string() };
+    client.user_api_key_delete(request).await.expect("RPC failed");
+}
+
+async fn grpc_token_create(password: &str) -> Result<String, tonic::Status> {
+    let mut client = connect_client().await;
+    let request = create::Req {
+        credentials: Some(create::req::Credentials::Password(create::req::Password {
+            username: USERNAME.to_string(),
+            password: password.to_string(),
+        })),
+    };
+    client.authentication_token_create(request).await.map(|response| response.into_inner().token)
+}
+
+async fn http_signin(password: &str) -> (StatusCode, serde_json::Value) {
+    ensure_server_started().await;
+    let body = serde_json::json!({ "username": USERNAME, "password": password }).to_string();
+    let client = Client::new();
+    for _ in 0..50 {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri(format!("http://{HTTP_ADDRESS}/v1/signin"))
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.clone()))
+            .unwrap();
+        let Ok(response) = client.request(request).await else {
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            continue;
+        };
+        let status = response.status();
+        let bytes = hyper::body::to_bytes(response.into_body()).await.expect("Failed to read response body");
+        return (status, serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null));
+    }
+    panic!("Failed to connect to HTTP service")
+}
+
+#[tokio::test]
+async fn grpc_token_create_accepts_api_keys() {
+    let key = create_api_key("grpc-job", Some(3600)).await;
+    let token = grpc_token_create(&key).await.expect("Signing in with a valid API key should succeed");
+    assert!(!token.is_empty());
+
+    let (prefix, _) = key.rsplit_once('_').unwrap();
+    let wrong_secret = format!("{prefix}_{}", "x".repeat(48));
+    let error = grpc_token_create(&wrong_secret).await.unwrap_err();
+    assert_eq!(error.code(), tonic::Code::Unauthenticated);
+
+    delete_api_key("grpc-job").await;
+    let error = grpc_token_create(&key).await.unwrap_err();
+    assert_eq!(error.code(), tonic::Code::Unauthenticated, "A deleted API key should be refused");
+}
+
+#[tokio::test]
+async fn grpc_token_create_refuses_malformed_and_expired_api_keys() {
+    for malformed in ["tdbk_", "tdbk__secret", "tdbk_no-secret"] {
+        let error = grpc_token_create(malformed).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::Unauthenticated, "'{malformed}' should be refused");
+    }
+
+    let expired = create_api_key("grpc-expired-job", Some(0)).await;
+    let error = grpc_token_create(&expired).await.unwrap_err();
+    assert_eq!(error.code(), tonic::Code::Unauthenticated, "An expired API key should be refused");
+}
+
+#[tokio::test]
+async fn http_signin_accepts_api_keys() {
+    let key = create_api_key("http-job", None).await;
+    let (status, body) = http_signin(&key).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["token"].as_str().is_some_and(|token| !token.is_empty()), "Signin should return a token: {body}");
+
+    delete_api_key("http-job").await;
+    let (status, _) = http_signin(&key).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "A deleted API key should be refused");
+}
+
+#[tokio::test]
+async fn http_signin_refuses_malformed_and_expired_api_keys() {
+    for malformed in ["tdbk_", "tdbk__secret", "tdbk_no-secret"] {
+        let (status, _) = http_signin(malformed).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED, "'{malformed}' should be refused");
+    }
+
+    let expired = create_api_key("http-expired-job", Some(0)).await;
+    let (status, _) = http_signin(&expired).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "An expired API key should be refused");
+}
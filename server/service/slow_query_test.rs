@@ -0,0 +1,164 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::{fs, path::PathBuf, time::Duration};
+
+use diagnostics::{LogRotation, slow_query::SLOW_QUERY_LOG_FILE_PREFIX};
+use hyper::{Body, Client, Method, Request, StatusCode, header};
+use resource::{
+    constants::server::{DEFAULT_USER_NAME, DEFAULT_USER_PASSWORD},
+    distribution_info::DistributionInfo,
+};
+use server::{
+    ServerBuilder,
+    parameters::config::{ConfigBuilder, SlowQueryLogConfig},
+};
+use test_utils::{TempDir, create_tmp_dir, create_tmp_storage_dir};
+use tokio::sync::OnceCell;
+
+const GRPC_ADDRESS: &str = "127.0.0.1:11759";
+const HTTP_ADDRESS: &str = "127.0.0.1:11758";
+const DISTRIBUTION_INFO: DistributionInfo =
+    DistributionInfo { logo: "logo", distribution: "TypeDB CE TEST", version: "0.0.0-test" };
+
+const DATABASE: &str = "slow-query-log";
+
+// The data and log directories of the server, which logs every query as slow
+static SERVER: OnceCell<(TempDir, TempDir, tokio::sync::watch::Sender<()>)> = OnceCell::const_new();
+
+fn config_path() -> PathBuf {
+    std::env::current_dir().unwrap().join("server/config.yml")
+}
+
+async fn ensure_server_started() -> &'static TempDir {
+    let (_, log_dir, _) = SERVER
+        .get_or_init(|| async {
+            let (shutdown_sender, shutdown_receiver) = tokio::sync::watch::channel(());
+            let server_dir = create_tmp_storage_dir();
+            let log_dir = create_tmp_dir("slow_query_log");
+            let slow_query_log = SlowQueryLogConfig {
+                enabled: true,
+                threshold: Duration::ZERO,
+                redact_literals: false,
+                rotation: LogRotation::Daily,
+                max_files: 1,
+            };
+            let config = ConfigBuilder::from_file(config_path())
+                .expect("Failed to load config file")
+                .server_listen_address(GRPC_ADDRESS)
+                .server_http_enabled(true)
+                .server_http_listen_address(HTTP_ADDRESS)
+                .server_http_advertise_address(format!("http://{HTTP_ADDRESS}"))
+                .admin_enabled(false)
+                .slow_query_log(slow_query_log)
+                .log_directory(log_dir.as_ref())
+                .data_directory(server_dir.as_ref())
+                .development_mode(true)
+                .build()
+                .expect("Failed to build config");
+
+            let server = ServerBuilder::new()
+                .distribution_info(DISTRIBUTION_INFO)
+                .shutdown_channel((shutdown_sender.clone(), shutdown_receiver))
+                .build(config)
+                .await
+                .expect("Failed to build server");
+
+            tokio::spawn(async move {
+                server.serve().await.expect("Server failed");
+            });
+
+            (server_dir, log_dir, shutdown_sender)
+        })
+        .await;
+    log_dir
+}
+
+async fn http_post(path: &str, token: Option<&str>, body: serde_json::Value) -> (StatusCode, serde_json::Value) {
+    let client = Client::new();
+    for _ in 0..50 {
+        let mut request = Request::builder()
+            .method(Method::POST)
+            .uri(format!("http://{HTTP_ADDRESS}{path}"))
+            .header(header::CONTENT_TYPE, "application/json");
+        if let Some(token) = token {
+            request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+        }
+        let Ok(response) = client.request(request.body(Body::from(body.to_string())).unwrap()).await else {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            continue;
+        };
+        let status = response.status();
+        let bytes = hyper::body::to_bytes(response.into_body()).await.expect("Failed to read response body");
+        return (status, serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null));
+    }
+    panic!("Failed to connect to HTTP service")
+}
+
+async fn run_query(token: &str, transaction_type: &str, query: &str) {
+    let body = serde_json::json!({
+        "databaseName": DATABASE,
+        "transactionType": transaction_type,
+        "query": query,
+        "commit": transaction_type != "read",
+    });
+    let (status, body) = http_post("/v1/query", Some(token), body).await;
+    assert_eq!(status, StatusCode::OK, "Query '{query}' failed: {body}");
+}
+
+fn logged_queries(log_dir: &TempDir) -> Vec<serde_json::Value> {
+    let Ok(entries) = fs::read_dir(log_dir.as_ref()) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(SLOW_QUERY_LOG_FILE_PREFIX))
+        .flat_map(|entry| {
+            fs::read_to_string(entry.path()).unwrap_or_default().lines().map(str::to_owned).collect::<Vec<_>>()
+        })
+        .map(|line| serde_json::from_str(&line).expect("Slow query log lines should be JSON"))
+        .collect()
+}
+
+#[tokio::test]
+async fn queries_over_the_threshold_are_written_to_the_slow_query_log() {
+    let log_dir = ensure_server_started().await;
+    let credentials = serde_json::json!({ "username": DEFAULT_USER_NAME, "password": DEFAULT_USER_PASSWORD });
+    let (status, body) = http_post("/v1/signin", None, credentials).await;
+    assert_eq!(status, StatusCode::OK, "Signin failed: {body}");
+    let token = body["token"].as_str().expect("Signin should return a token").to_owned();
+    let (status, body) = http_post(&format!("/v1/databases/{DATABASE}"), Some(&token), serde_json::Value::Null).await;
+    assert_eq!(status, StatusCode::OK, "Database creation failed: {body}");
+
+    let schema_query = "define entity person;";
+    let read_query = "match $p isa person;";
+    run_query(&token, "schema", schema_query).await;
+    run_query(&token, "read", read_query).await;
+
+    let logged_query = |queries: &[serde_json::Value], query: &str| {
+        queries.iter().find(|logged| logged["query"].as_str() == Some(query)).cloned()
+    };
+    let mut queries = Vec::new();
+    for _ in 0..50 {
+        queries = logged_queries(log_dir);
+        if logged_query(&queries, schema_query).is_some() && logged_query(&queries, read_query).is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    let schema = logged_query(&queries, schema_query).expect("The schema query should be logged");
+    assert_eq!(schema["database"], DATABASE);
+    assert_eq!(schema["user"], DEFAULT_USER_NAME);
+    assert_eq!(schema["kind"], "schema");
+    assert!(schema["duration_micros"].as_u64().is_some());
+    let profile = schema["profile"].as_str().expect("Schema queries should be logged with their profile");
+    assert!(profile.contains("Define execution"), "Unexpected profile: {profile}");
+
+    let read = logged_query(&queries, read_query).expect("The read query should be logged");
+    assert_eq!(read["kind"], "read");
+    assert!(read["profile"].as_str().is_some(), "Read queries should be logged with their profile");
+}
@@ -6,10 +6,18 @@
 use std::{sync::Arc, time::Duration};
 
 use database::transaction::{CommitIntent, TransactionError, TransactionSchema, TransactionWrite};
+use diagnostics::{
+    diagnostics_manager::{DiagnosticsManager, is_diagnostics_needed},
+    metrics::QueryKind,
+    slow_query::SlowQuery,
+};
 use error::typedb_error;
 use executor::{InterruptType, pipeline::PipelineExecutionError};
 use query::error::QueryError;
-use resource::{constants::server::DEFAULT_TRANSACTION_TIMEOUT_MILLIS, profile::TransactionProfile};
+use resource::{
+    constants::server::DEFAULT_TRANSACTION_TIMEOUT_MILLIS,
+    profile::{QueryProfile, TransactionProfile},
+};
 use storage::durability_client::WALClient;
 use tokio::time::Instant;
 use tracing::{Level, event};
@@ -34,6 +42,36 @@ pub(crate) fn is_write_pipeline(pipeline: &typeql::query::Pipeline) -> bool {
     false
 }
 
+pub(crate) fn submit_query_diagnostics(
+    diagnostics_manager: &DiagnosticsManager,
+    database_name: &str,
+    username: &str,
+    query_kind: QueryKind,
+    source_query: &str,
+    profile: Option<&QueryProfile>,
+    start: Instant,
+) {
+    if !is_diagnostics_needed(Some(database_name)) {
+        return;
+    }
+    let latency = start.elapsed();
+    diagnostics_manager.submit_query_latency(database_name, query_kind, latency);
+    diagnostics_manager.may_log_slow_query(SlowQuery {
+        database_name,
+        username,
+        query_kind,
+        source_query,
+        latency,
+        profile,
+    });
+}
+
+fn submit_commit_latency(server_state: &ServerState, database_name: &str, start: Instant) {
+    if is_diagnostics_needed(Some(database_name)) {
+        server_state.diagnostics_manager().submit_commit_latency(database_name, start.elapsed());
+    }
+}
+
 pub(crate) fn init_transaction_timeout(transaction_timeout_millis: Option<u64>) -> Instant {
     Instant::now() + Duration::from_millis(transaction_timeout_millis.unwrap_or(DEFAULT_TRANSACTION_TIMEOUT_MILLIS))
 }
@@ -42,15 +80,18 @@ pub(crate) async fn commit_schema_transaction(
     server_state: Arc<ServerState>,
     transaction: TransactionSchema<WALClient>,
 ) -> (TransactionProfile, Result<(), ArcServerStateError>) {
+    let database_name = transaction.database.name().to_owned();
     let (mut profile, result) = match transaction.finalise() {
         (mut profile, Ok(commit_intent)) => {
             if !commit_intent.has_changes() {
                 (profile, Ok(()))
             } else {
+                let start = Instant::now();
                 let commit_profile = profile.take_commit_profile();
                 let (commit_profile, result) =
                     server_state.databases().schema_commit(commit_intent, commit_profile).await;
                 profile.set_commit_profile(commit_profile);
+                submit_commit_latency(&server_state, &database_name, start);
                 (profile, result)
             }
         }
@@ -69,15 +110,18 @@ pub(crate) async fn commit_write_transaction(
     server_state: Arc<ServerState>,
     transaction: TransactionWrite<WALClient>,
 ) -> (TransactionProfile, Result<(), ArcServerStateError>) {
+    let database_name = transaction.database.name().to_owned();
     let (mut profile, result) = match transaction.finalise() {
         (mut profile, Ok(commit_intent)) => {
             if !commit_intent.has_changes() {
                 (profile, Ok(()))
             } else {
+                let start = Instant::now();
                 let commit_profile = profile.take_commit_profile();
                 let (commit_profile, result) =
                     server_state.databases().data_commit(commit_intent, commit_profile).await;
                 profile.set_commit_profile(commit_profile);
+                submit_commit_latency(&server_state, &database_name, start);
                 (profile, result)
             }
         }
@@ -122,5 +166,8 @@ typedb_error! {
         AnalyseQueryExpectsPipeline(19, "Query analyse received a schema query.Only query pipeline can be analysed."),
         AnalyseQueryFailed(20, "Analysing the query failed.", typedb_source: QueryError),
         CannotOpen(21, "Could not open transaction.", typedb_source: ArcServerStateError),
+        ExplainQueryExpectsPipeline(22, "Query explain received a schema query. Only query pipelines can be explained."),
+        ExplainQueryFailed(23, "Explaining the query failed.", typedb_source: QueryError),
+        SchemaQueryParametersUnsupported(24, "Query parameters cannot be bound to schema queries. Only query pipelines take parameters."),
     }
 }
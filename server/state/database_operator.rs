@@ -4,17 +4,24 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use std::{fmt::Debug, sync::Arc};
+use std::{
+    fmt::Debug,
+    fs,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
 
 use async_trait::async_trait;
 use concurrency::TokioTaskSpawner;
 use database::{
     Database,
+    change_stream::ChangeStream,
     database_manager::DatabaseManager,
+    statistics::DatabaseStatistics,
     transaction::{CommitIntent, DataCommitIntent, SchemaCommitIntent, TransactionRead},
 };
-use durability::DurabilitySequenceNumber;
-use resource::profile::CommitProfile;
+use durability::{DurabilitySequenceNumber, RawRecord};
+use resource::{constants::database::REPLICATION_BATCH_RECORDS, profile::CommitProfile};
 use storage::{
     durability_client::{DurabilityClient, WALClient},
     snapshot::snapshot_id::SnapshotId,
@@ -22,6 +29,7 @@ use storage::{
 use tokio::task::JoinHandle;
 
 use crate::{
+    authentication::{Accessor, authorizer::Authorizer},
     error::{ArcServerStateError, LocalServerStateError, arc_server_state_err},
     service::{
         export_service::{get_transaction_schema, get_transaction_type_schema},
@@ -39,7 +47,7 @@ pub trait DatabaseOperator: Debug + Send + Sync {
 
     async fn get_unrestricted(&self, name: &str) -> Result<Option<Arc<Database<WALClient>>>, ArcServerStateError>;
 
-    async fn create(&self, name: &str) -> Result<(), ArcServerStateError>;
+    async fn create(&self, accessor: Accessor, name: &str) -> Result<(), ArcServerStateError>;
 
     async fn create_unrestricted(&self, name: &str) -> Result<(), ArcServerStateError>;
 
@@ -68,7 +76,33 @@ pub trait DatabaseOperator: Debug + Send + Sync {
         snapshot_id: SnapshotId,
     ) -> Result<bool, ArcServerStateError>;
 
-    async fn delete(&self, name: &str) -> Result<(), ArcServerStateError>;
+    async fn delete(&self, accessor: Accessor, name: &str) -> Result<(), ArcServerStateError>;
+
+    async fn backup(
+        &self,
+        accessor: Accessor,
+        name: &str,
+        directory: PathBuf,
+        incremental: bool,
+    ) -> Result<DurabilitySequenceNumber, ArcServerStateError>;
+
+    async fn restore(&self, accessor: Accessor, name: &str, directory: PathBuf) -> Result<(), ArcServerStateError>;
+
+    async fn change_stream(
+        &self,
+        accessor: Accessor,
+        name: &str,
+        start: Option<DurabilitySequenceNumber>,
+    ) -> Result<ChangeStream<WALClient>, ArcServerStateError>;
+
+    async fn replication_records(
+        &self,
+        accessor: Accessor,
+        name: &str,
+        start: DurabilitySequenceNumber,
+    ) -> Result<Vec<RawRecord<'static>>, ArcServerStateError>;
+
+    async fn statistics(&self, accessor: Accessor, name: &str) -> Result<DatabaseStatistics, ArcServerStateError>;
 
     fn manager(&self) -> Arc<DatabaseManager>;
 }
@@ -76,12 +110,53 @@ pub trait DatabaseOperator: Debug + Send + Sync {
 #[derive(Debug)]
 pub struct LocalDatabaseOperator {
     database_manager: Arc<DatabaseManager>,
+    authorizer: Arc<Authorizer>,
+    backup_directory: PathBuf,
     background_task_spawner: TokioTaskSpawner,
 }
 
 impl LocalDatabaseOperator {
-    pub fn new(database_manager: Arc<DatabaseManager>, background_task_spawner: TokioTaskSpawner) -> Self {
-        Self { database_manager, background_task_spawner }
+    pub fn new(
+        database_manager: Arc<DatabaseManager>,
+        authorizer: Arc<Authorizer>,
+        backup_directory: PathBuf,
+        background_task_spawner: TokioTaskSpawner,
+    ) -> Self {
+        Self { database_manager, authorizer, backup_directory, background_task_spawner }
+    }
+
+    // Resolves a requested backup directory against the backup root, refusing any that lies outside of it, whether
+    // through `..` components, an absolute path or a symbolic link.
+    fn confine_backup_directory(&self, directory: &Path) -> Result<PathBuf, LocalServerStateError> {
+        let root = &self.backup_directory;
+        let not_permitted = || LocalServerStateError::BackupDirectoryNotPermitted {
+            directory: directory.display().to_string(),
+            root: root.display().to_string(),
+        };
+        let unavailable = |source| LocalServerStateError::BackupDirectoryUnavailable {
+            root: root.display().to_string(),
+            source: Arc::new(source),
+        };
+        if directory.components().any(|component| component == Component::ParentDir) {
+            return Err(not_permitted());
+        }
+        fs::create_dir_all(root).map_err(unavailable)?;
+        let root = fs::canonicalize(root).map_err(unavailable)?;
+
+        // The backup creates the directory, so only its deepest existing ancestor can be canonicalized
+        let requested = root.join(directory);
+        let mut existing = requested.as_path();
+        let mut missing = Vec::new();
+        while !existing.exists() {
+            missing.push(existing.file_name().ok_or_else(not_permitted)?);
+            existing = existing.parent().ok_or_else(not_permitted)?;
+        }
+        let mut resolved = fs::canonicalize(existing).map_err(|_| not_permitted())?;
+        resolved.extend(missing.into_iter().rev());
+        match resolved.starts_with(&root) && resolved != root {
+            true => Ok(resolved),
+            false => Err(not_permitted()),
+        }
     }
 }
 
@@ -139,7 +214,8 @@ impl DatabaseOperator for LocalDatabaseOperator {
         Ok(self.database_manager.database_unrestricted(name))
     }
 
-    async fn create(&self, name: &str) -> Result<(), ArcServerStateError> {
+    async fn create(&self, accessor: Accessor, name: &str) -> Result<(), ArcServerStateError> {
+        self.authorizer.authorize_database_create(accessor.as_str()).map_err(arc_server_state_err)?;
         self.database_manager
             .put_database(name)
             .map_err(|err| arc_server_state_err(LocalServerStateError::DatabaseCannotBeCreated { typedb_source: err }))
@@ -219,13 +295,218 @@ impl DatabaseOperator for LocalDatabaseOperator {
         })
     }
 
-    async fn delete(&self, name: &str) -> Result<(), ArcServerStateError> {
+    async fn delete(&self, accessor: Accessor, name: &str) -> Result<(), ArcServerStateError> {
+        self.authorizer.authorize_database_delete(accessor.as_str(), name).map_err(arc_server_state_err)?;
         self.database_manager
             .delete_database(name)
             .map_err(|err| arc_server_state_err(LocalServerStateError::DatabaseCannotBeDeleted { typedb_source: err }))
     }
 
+    async fn backup(
+        &self,
+        accessor: Accessor,
+        name: &str,
+        directory: PathBuf,
+        incremental: bool,
+    ) -> Result<DurabilitySequenceNumber, ArcServerStateError> {
+        self.authorizer.authorize_database_backup(accessor.as_str(), name).map_err(arc_server_state_err)?;
+        let directory = self.confine_backup_directory(&directory).map_err(arc_server_state_err)?;
+        let Some(database) = self.database_manager.database(name) else {
+            return Err(Arc::new(LocalServerStateError::DatabaseNotFound { name: name.to_string() }));
+        };
+        tokio::task::spawn_blocking(move || {
+            if incremental { database.backup_incremental(&directory) } else { database.backup(&directory) }
+        })
+        .await
+        .expect("Database backup task panicked")
+        .map_err(|err| arc_server_state_err(LocalServerStateError::DatabaseCannotBeBackedUp { typedb_source: err }))
+    }
+
+    async fn restore(&self, accessor: Accessor, name: &str, directory: PathBuf) -> Result<(), ArcServerStateError> {
+        self.authorizer.authorize_database_create(accessor.as_str()).map_err(arc_server_state_err)?;
+        let database_manager = self.database_manager.clone();
+        let name = name.to_string();
+        tokio::task::spawn_blocking(move || database_manager.restore_database(&name, &directory))
+            .await
+            .expect("Database restore task panicked")
+            .map_err(|err| arc_server_state_err(LocalServerStateError::DatabaseCannotBeCreated { typedb_source: err }))
+    }
+
+    async fn change_stream(
+        &self,
+        accessor: Accessor,
+        name: &str,
+        start: Option<DurabilitySequenceNumber>,
+    ) -> Result<ChangeStream<WALClient>, ArcServerStateError> {
+        self.authorizer.authorize_change_stream(accessor.as_str(), name).map_err(arc_server_state_err)?;
+        let Some(database) = self.database_manager.database(name) else {
+            return Err(Arc::new(LocalServerStateError::DatabaseNotFound { name: name.to_string() }));
+        };
+        Ok(match start {
+            Some(start) => ChangeStream::new(database, start),
+            None => ChangeStream::new_from_latest(database),
+        })
+    }
+
+    async fn replication_records(
+        &self,
+        accessor: Accessor,
+        name: &str,
+        start: DurabilitySequenceNumber,
+    ) -> Result<Vec<RawRecord<'static>>, ArcServerStateError> {
+        self.authorizer.authorize_replication(accessor.as_str(), name).map_err(arc_server_state_err)?;
+        let Some(database) = self.database_manager.database(name) else {
+            return Err(Arc::new(LocalServerStateError::DatabaseNotFound { name: name.to_string() }));
+        };
+        tokio::task::spawn_blocking(move || database.replication_records_from(start, REPLICATION_BATCH_RECORDS))
+            .await
+            .expect("Database replication read task panicked")
+            .map_err(|typedb_source| {
+                arc_server_state_err(LocalServerStateError::DatabaseReplicationUnavailable { typedb_source })
+            })
+    }
+
+    async fn statistics(&self, accessor: Accessor, name: &str) -> Result<DatabaseStatistics, ArcServerStateError> {
+        self.authorizer.authorize_database_statistics(accessor.as_str(), name).map_err(arc_server_state_err)?;
+        let Some(database) = self.database_manager.database(name) else {
+            return Err(Arc::new(LocalServerStateError::DatabaseNotFound { name: name.to_string() }));
+        };
+        tokio::task::spawn_blocking(move || database.statistics())
+            .await
+            .expect("Database statistics task panicked")
+            .map_err(|typedb_source| {
+                arc_server_state_err(LocalServerStateError::DatabaseStatisticsUnavailable { typedb_source })
+            })
+    }
+
     fn manager(&self) -> Arc<DatabaseManager> {
         self.database_manager.clone()
     }
 }
+
+/// Serves the databases of a read-only replica, which are only changed by replicating them from the primary server.
+/// Operations that would change them are rejected, and the rest are delegated to the underlying operator.
+#[derive(Debug)]
+pub struct ReplicaDatabaseOperator {
+    delegate: Arc<dyn DatabaseOperator>,
+    primary_address: String,
+}
+
+impl ReplicaDatabaseOperator {
+    pub fn new(delegate: Arc<dyn DatabaseOperator>, primary_address: String) -> Self {
+        Self { delegate, primary_address }
+    }
+
+    fn read_only_error(&self) -> ArcServerStateError {
+        arc_server_state_err(LocalServerStateError::ReadOnlyReplica { primary_address: self.primary_address.clone() })
+    }
+}
+
+#[async_trait]
+impl DatabaseOperator for ReplicaDatabaseOperator {
+    async fn all(&self) -> Result<Vec<String>, ArcServerStateError> {
+        self.delegate.all().await
+    }
+
+    async fn contains(&self, name: &str) -> Result<bool, ArcServerStateError> {
+        self.delegate.contains(name).await
+    }
+
+    async fn get(&self, name: &str) -> Result<Option<Arc<Database<WALClient>>>, ArcServerStateError> {
+        self.delegate.get(name).await
+    }
+
+    async fn get_unrestricted(&self, name: &str) -> Result<Option<Arc<Database<WALClient>>>, ArcServerStateError> {
+        self.delegate.get_unrestricted(name).await
+    }
+
+    async fn create(&self, _accessor: Accessor, _name: &str) -> Result<(), ArcServerStateError> {
+        Err(self.read_only_error())
+    }
+
+    async fn create_unrestricted(&self, name: &str) -> Result<(), ArcServerStateError> {
+        // Internal databases, such as the system database, belong to this server rather than the primary
+        self.delegate.create_unrestricted(name).await
+    }
+
+    async fn import(&self, _service: DatabaseImportService) -> Result<JoinHandle<()>, ArcServerStateError> {
+        Err(self.read_only_error())
+    }
+
+    async fn schema(&self, name: &str) -> Result<String, ArcServerStateError> {
+        self.delegate.schema(name).await
+    }
+
+    async fn type_schema(&self, name: &str) -> Result<String, ArcServerStateError> {
+        self.delegate.type_schema(name).await
+    }
+
+    async fn schema_commit(
+        &self,
+        commit_intent: SchemaCommitIntent<WALClient>,
+        commit_profile: CommitProfile,
+    ) -> (CommitProfile, Result<(), ArcServerStateError>) {
+        self.delegate.schema_commit(commit_intent, commit_profile).await
+    }
+
+    async fn data_commit(
+        &self,
+        commit_intent: DataCommitIntent<WALClient>,
+        commit_profile: CommitProfile,
+    ) -> (CommitProfile, Result<(), ArcServerStateError>) {
+        self.delegate.data_commit(commit_intent, commit_profile).await
+    }
+
+    async fn commit_record_exists(
+        &self,
+        name: &str,
+        open_sequence_number: DurabilitySequenceNumber,
+        snapshot_id: SnapshotId,
+    ) -> Result<bool, ArcServerStateError> {
+        self.delegate.commit_record_exists(name, open_sequence_number, snapshot_id).await
+    }
+
+    async fn delete(&self, _accessor: Accessor, _name: &str) -> Result<(), ArcServerStateError> {
+        Err(self.read_only_error())
+    }
+
+    async fn backup(
+        &self,
+        accessor: Accessor,
+        name: &str,
+        directory: PathBuf,
+        incremental: bool,
+    ) -> Result<DurabilitySequenceNumber, ArcServerStateError> {
+        self.delegate.backup(accessor, name, directory, incremental).await
+    }
+
+    async fn restore(&self, _accessor: Accessor, _name: &str, _directory: PathBuf) -> Result<(), ArcServerStateError> {
+        Err(self.read_only_error())
+    }
+
+    async fn change_stream(
+        &self,
+        accessor: Accessor,
+        name: &str,
+        start: Option<DurabilitySequenceNumber>,
+    ) -> Result<ChangeStream<WALClient>, ArcServerStateError> {
+        self.delegate.change_stream(accessor, name, start).await
+    }
+
+    async fn replication_records(
+        &self,
+        accessor: Accessor,
+        name: &str,
+        start: DurabilitySequenceNumber,
+    ) -> Result<Vec<RawRecord<'static>>, ArcServerStateError> {
+        self.delegate.replication_records(accessor, name, start).await
+    }
+
+    async fn statistics(&self, accessor: Accessor, name: &str) -> Result<DatabaseStatistics, ArcServerStateError> {
+        self.delegate.statistics(accessor, name).await
+    }
+
+    fn manager(&self) -> Arc<DatabaseManager> {
+        self.delegate.manager()
+    }
+}
@@ -13,22 +13,33 @@ use std::{collections::HashSet, net::SocketAddr, path::PathBuf, sync::Arc};
 
 use concurrency::{IntervalRunner, TokioTaskSpawner};
 use database::database_manager::DatabaseManager;
-use diagnostics::{Diagnostics, diagnostics_manager::DiagnosticsManager};
-use resource::{constants::server::DATABASE_METRICS_UPDATE_INTERVAL, distribution_info::DistributionInfo};
+use diagnostics::{Diagnostics, audit::AuditLog, diagnostics_manager::DiagnosticsManager, slow_query::SlowQueryLog};
+use resource::{
+    constants::server::DATABASE_METRICS_UPDATE_INTERVAL, distribution_info::DistributionInfo, profile::QueryProfile,
+};
 use tokio::{net::lookup_host, sync::watch::Receiver};
 
 pub use self::{
     database_operator::{
-        DatabaseOperator, LocalDatabaseOperator, get_database_schema, get_functions_syntax, get_types_syntax,
+        DatabaseOperator, LocalDatabaseOperator, ReplicaDatabaseOperator, get_database_schema, get_functions_syntax,
+        get_types_syntax,
     },
     server_operator::{LocalServerOperator, ServerOperator},
-    transaction_operator::{LocalTransactionOperator, TransactionOperator},
+    transaction_operator::{
+        LocalTransactionOperator, OpenTransactionInfo, RunningQuery, RunningQueryInfo, SuspendedQuery,
+        TransactionOperator,
+    },
     user_operator::{LocalUserOperator, UserOperator},
 };
 use crate::{
-    authentication::token_manager::TokenManager,
+    authentication::{
+        authorizer::Authorizer,
+        credential_verifier::{CredentialVerifier, external_credential_verifiers},
+        token_manager::TokenManager,
+    },
     error::{ArcServerStateError, ServerOpenError},
-    parameters::config::{Config, DiagnosticsConfig},
+    parameters::config::{AuthenticationConfig, Config, DiagnosticsConfig, LoggingConfig, ReplicationConfig},
+    replication::ReplicationFollower,
     status::{LocalServerStatus, PrivateEndpointAddress, PublicEndpointAddress, ServerStatus},
 };
 
@@ -61,10 +72,28 @@ impl ServerState {
     ) -> Result<ServerStateBuilder, ServerOpenError> {
         let database_manager = DatabaseManager::new(&config.storage.data_directory)
             .map_err(|typedb_source| ServerOpenError::DatabaseOpen { typedb_source })?;
+        database_manager.set_history_retention(config.storage.history_retention);
+        database_manager.set_user_databases_read_only(config.server.replication.primary_address.is_some());
         let token_manager = Arc::new(
-            TokenManager::new(config.server.authentication.token_expiration, background_task_spawner.clone())
-                .map_err(|typedb_source| ServerOpenError::TokenConfiguration { typedb_source })?,
+            TokenManager::new(
+                config.server.authentication.token_expiration,
+                config.server.authentication.signing_key_file.as_deref(),
+                background_task_spawner.clone(),
+            )
+            .map_err(|typedb_source| ServerOpenError::TokenConfiguration { typedb_source })?,
         );
+        let authorizer = Arc::new(Authorizer::new(database_manager.clone(), config.server.authorization.enabled));
+        let credential_verifiers = external_credential_verifiers(&config.server.authentication)
+            .map_err(|typedb_source| ServerOpenError::ExternalAuthenticationConfiguration { typedb_source })?;
+        let replication_follower = ReplicationFollower::from_config(
+            &config.server.replication,
+            &config.server.encryption,
+            database_manager.clone(),
+        )
+        .map_err(|typedb_source| ServerOpenError::ReplicationConfiguration { typedb_source })?;
+
+        let audit_log = Self::initialise_audit_log(&config.logging)?;
+        let slow_query_log = Self::initialise_slow_query_log(&config.logging)?;
 
         let deployment_id = deployment_id.unwrap_or(server_id.clone());
         let diagnostics_manager = Arc::new(
@@ -75,6 +104,8 @@ impl ServerState {
                 &config.diagnostics,
                 config.storage.data_directory.clone(),
                 config.development_mode.enabled,
+                audit_log,
+                slow_query_log,
                 background_task_spawner.clone(),
             )
             .await,
@@ -97,10 +128,16 @@ impl ServerState {
             server_status,
             database_manager,
             token_manager,
+            authorizer,
             diagnostics_manager,
             database_diagnostics_updater,
             shutdown_receiver,
             background_task_spawner,
+            authentication: config.server.authentication,
+            credential_verifiers,
+            replication: config.server.replication,
+            replication_follower,
+            backup_directory: config.storage.backup_directory,
             server_operator_override: None,
             database_operator_override: None,
             transaction_operator_override: None,
@@ -154,16 +191,20 @@ impl ServerState {
     }
 
     pub async fn initialise(&self) -> Result<(), ArcServerStateError> {
-        if self.is_initialised() {
-            return Ok(());
-        }
+        let is_initialised = self.is_initialised();
 
         // Initialize self for user_operator
-        crate::system_init::initialise_system_database(self).await?;
+        if !is_initialised {
+            crate::system_init::initialise_system_database(self).await?;
+        }
+        // Also brings system databases created by older versions up to date with the current schema
         crate::system_init::initialise_system_database_schema(self).await?;
-        crate::system_init::initialise_default_user(self).await?;
+        if !is_initialised {
+            crate::system_init::initialise_default_user(self).await?;
+        }
 
-        Ok(())
+        // Tokens issued before a restart stay valid until they expire or their sessions are revoked
+        self.user_operator.sessions_restore().await
     }
 
     async fn initialise_diagnostics(
@@ -173,6 +214,8 @@ impl ServerState {
         config: &DiagnosticsConfig,
         storage_directory: PathBuf,
         is_development_mode: bool,
+        audit_log: Option<AuditLog>,
+        slow_query_log: Option<SlowQueryLog>,
         background_tasks: TokioTaskSpawner,
     ) -> DiagnosticsManager {
         let diagnostics = Diagnostics::new(
@@ -188,6 +231,8 @@ impl ServerState {
             config.monitoring.port,
             config.monitoring.enabled,
             is_development_mode,
+            audit_log,
+            slow_query_log,
             background_tasks,
         );
         diagnostics_manager.may_start_monitoring().await;
@@ -195,6 +240,36 @@ impl ServerState {
         diagnostics_manager
     }
 
+    fn initialise_audit_log(config: &LoggingConfig) -> Result<Option<AuditLog>, ServerOpenError> {
+        if !config.audit.enabled {
+            return Ok(None);
+        }
+        AuditLog::new(&config.directory, config.audit.rotation, config.audit.max_files).map(Some).map_err(|source| {
+            ServerOpenError::AuditLogOpen { path: config.directory.display().to_string(), source: Arc::new(source) }
+        })
+    }
+
+    fn initialise_slow_query_log(config: &LoggingConfig) -> Result<Option<SlowQueryLog>, ServerOpenError> {
+        let config_slow_query = &config.slow_query;
+        if !config_slow_query.enabled {
+            return Ok(None);
+        }
+        let slow_query_log = SlowQueryLog::new(
+            &config.directory,
+            config_slow_query.threshold,
+            config_slow_query.redact_literals,
+            config_slow_query.rotation,
+            config_slow_query.max_files,
+        )
+        .map_err(|source| ServerOpenError::SlowQueryLogOpen {
+            path: config.directory.display().to_string(),
+            source: Arc::new(source),
+        })?;
+        // Slow queries can only be explained if every query is profiled, as they are not known to be slow in advance
+        QueryProfile::set_profiling_required(true);
+        Ok(Some(slow_query_log))
+    }
+
     fn synchronize_database_metrics(
         diagnostics_manager: Arc<DiagnosticsManager>,
         database_manager: Arc<DatabaseManager>,
@@ -268,10 +343,16 @@ pub struct ServerStateBuilder {
     server_status: LocalServerStatus,
     database_manager: Arc<DatabaseManager>,
     token_manager: Arc<TokenManager>,
+    authorizer: Arc<Authorizer>,
     diagnostics_manager: Arc<DiagnosticsManager>,
     database_diagnostics_updater: IntervalRunner,
     shutdown_receiver: Receiver<()>,
     background_task_spawner: TokioTaskSpawner,
+    authentication: AuthenticationConfig,
+    credential_verifiers: Vec<Arc<dyn CredentialVerifier>>,
+    replication: ReplicationConfig,
+    replication_follower: Option<ReplicationFollower>,
+    backup_directory: PathBuf,
 
     server_operator_override: Option<Arc<dyn ServerOperator>>,
     database_operator_override: Option<Arc<dyn DatabaseOperator>>,
@@ -288,6 +369,10 @@ impl ServerStateBuilder {
         self.token_manager.clone()
     }
 
+    pub fn authorizer(&self) -> Arc<Authorizer> {
+        self.authorizer.clone()
+    }
+
     pub fn background_task_spawner(&self) -> TokioTaskSpawner {
         self.background_task_spawner.clone()
     }
@@ -316,16 +401,41 @@ impl ServerStateBuilder {
         self
     }
 
+    // Adds a verifier of an external identity provider, tried after the configured ones
+    pub fn credential_verifier(mut self, verifier: Arc<dyn CredentialVerifier>) -> Self {
+        self.credential_verifiers.push(verifier);
+        self
+    }
+
     pub fn build(self) -> ServerState {
         let server_operator =
             self.server_operator_override.unwrap_or_else(|| Arc::new(LocalServerOperator::new(self.server_status)));
 
         let database_operator = self.database_operator_override.unwrap_or_else(|| {
-            Arc::new(LocalDatabaseOperator::new(self.database_manager.clone(), self.background_task_spawner.clone()))
+            let local_operator: Arc<dyn DatabaseOperator> = Arc::new(LocalDatabaseOperator::new(
+                self.database_manager.clone(),
+                self.authorizer.clone(),
+                self.backup_directory.clone(),
+                self.background_task_spawner.clone(),
+            ));
+            match &self.replication.primary_address {
+                Some(primary_address) => {
+                    Arc::new(ReplicaDatabaseOperator::new(local_operator, primary_address.clone()))
+                }
+                None => local_operator,
+            }
         });
 
+        if let Some(follower) = self.replication_follower {
+            follower.start(&self.background_task_spawner);
+        }
+
         let transaction_operator = self.transaction_operator_override.unwrap_or_else(|| {
-            Arc::new(LocalTransactionOperator::new(self.database_manager.clone(), self.background_task_spawner.clone()))
+            Arc::new(LocalTransactionOperator::new(
+                self.database_manager.clone(),
+                self.authorizer.clone(),
+                self.background_task_spawner.clone(),
+            ))
         });
 
         let user_operator = self.user_operator_override.unwrap_or_else(|| {
@@ -333,6 +443,10 @@ impl ServerStateBuilder {
                 self.database_manager.clone(),
                 self.token_manager.clone(),
                 transaction_operator.clone(),
+                self.authentication.password_policy.to_policy(),
+                self.authentication.lockout.clone(),
+                self.credential_verifiers,
+                self.background_task_spawner.clone(),
             ))
         });
 
@@ -7,18 +7,22 @@
 use std::{
     collections::{HashMap, HashSet},
     fmt::Debug,
-    sync::Arc,
-    time::Duration,
+    sync::{Arc, RwLock as StdRwLock},
+    time::{Duration, Instant},
 };
 
 use async_trait::async_trait;
 use concurrency::{IntervalTaskParameters, TokioTaskSpawner};
 use database::{database_manager::DatabaseManager, transaction::TransactionId};
+use executor::{ExecutionInterrupt, InterruptType};
 use options::TransactionOptions;
 use resource::constants::common::SECONDS_IN_MINUTE;
-use tokio::sync::{RwLock, mpsc::Sender};
+use tokio::sync::{RwLock, broadcast, mpsc::Sender};
+use user::permission_manager::PermissionManager;
+use uuid::Uuid;
 
 use crate::{
+    authentication::{Accessor, authorizer::Authorizer},
     error::{ArcServerStateError, LocalServerStateError, arc_server_state_err},
     service::TransactionType,
     transaction::{Transaction, open_transaction_blocking},
@@ -26,18 +30,93 @@ use crate::{
 
 #[derive(Debug)]
 pub(crate) struct TransactionInfo {
+    id: Uuid,
+    database_name: String,
     transaction_type: TransactionType,
     owner: String,
+    start: Instant,
     close_sender: Sender<()>,
 }
 
+#[derive(Debug)]
+struct QueryInfo {
+    transaction_id: Uuid,
+    database_name: String,
+    owner: String,
+    source_query: String,
+    start: Instant,
+    interrupt_sender: broadcast::Sender<InterruptType>,
+    is_killed: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct OpenTransactionInfo {
+    pub id: Uuid,
+    pub database_name: String,
+    pub transaction_type: TransactionType,
+    pub owner: String,
+    pub elapsed: Duration,
+    pub running_queries: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct RunningQueryInfo {
+    pub id: Uuid,
+    pub transaction_id: Uuid,
+    pub database_name: String,
+    pub owner: String,
+    pub source_query: String,
+    pub elapsed: Duration,
+}
+
+// Keeps a query listed as running, and able to be killed, until it is dropped
+#[derive(Debug)]
+pub struct RunningQuery {
+    id: Uuid,
+    queries: Arc<StdRwLock<HashMap<Uuid, QueryInfo>>>,
+}
+
+impl Drop for RunningQuery {
+    fn drop(&mut self) {
+        self.queries.write().unwrap_or_else(|poisoned| poisoned.into_inner()).remove(&self.id);
+    }
+}
+
+impl RunningQuery {
+    // Stops listing the query while it is paused between pages of answers, keeping its interrupt signal open
+    pub fn suspend(self) -> SuspendedQuery {
+        let info = self.queries.write().unwrap_or_else(|poisoned| poisoned.into_inner()).remove(&self.id);
+        SuspendedQuery { id: self.id, info, queries: self.queries.clone() }
+    }
+}
+
+// A query paused between pages of answers, which is not listed as running, and cannot be killed, until resumed
+#[derive(Debug)]
+pub struct SuspendedQuery {
+    id: Uuid,
+    info: Option<QueryInfo>,
+    queries: Arc<StdRwLock<HashMap<Uuid, QueryInfo>>>,
+}
+
+impl SuspendedQuery {
+    pub fn resume(self) -> RunningQuery {
+        let SuspendedQuery { id, info, queries } = self;
+        if let Some(info) = info {
+            queries.write().unwrap_or_else(|poisoned| poisoned.into_inner()).insert(id, info);
+        }
+        RunningQuery { id, queries }
+    }
+}
+
 #[async_trait]
 pub trait TransactionOperator: Debug + Send + Sync {
+    // The transaction is listed, and can be killed, by the id its client knows it by
     async fn open(
         &self,
         database_name: &str,
         transaction_type: TransactionType,
         options: TransactionOptions,
+        id: Uuid,
         owner: String,
         close_sender: Sender<()>,
     ) -> Result<Transaction, ArcServerStateError>;
@@ -45,18 +124,42 @@ pub trait TransactionOperator: Debug + Send + Sync {
     async fn close_by_types(&self, types: &HashSet<TransactionType>);
 
     async fn close_by_owner(&self, username: &str);
+
+    // Lists the open transactions, including those not running any query
+    async fn transactions(&self, accessor: Accessor) -> Vec<OpenTransactionInfo>;
+
+    async fn kill(&self, accessor: Accessor, transaction_id: Uuid) -> Result<(), ArcServerStateError>;
+
+    // Registers a query of an open transaction. The returned interrupt extends the transaction's one, so
+    // that killing the query only interrupts its own execution.
+    async fn query_register(
+        &self,
+        transaction_id: TransactionId,
+        source_query: &str,
+        interrupt: ExecutionInterrupt,
+    ) -> (RunningQuery, ExecutionInterrupt);
+
+    async fn queries(&self, accessor: Accessor) -> Vec<RunningQueryInfo>;
+
+    async fn query_kill(&self, accessor: Accessor, query_id: Uuid) -> Result<(), ArcServerStateError>;
 }
 
 #[derive(Debug)]
 pub struct LocalTransactionOperator {
     database_manager: Arc<DatabaseManager>,
+    authorizer: Arc<Authorizer>,
     transactions: Arc<RwLock<HashMap<TransactionId, TransactionInfo>>>,
+    queries: Arc<StdRwLock<HashMap<Uuid, QueryInfo>>>,
 }
 
 impl LocalTransactionOperator {
     const CLEANUP_INTERVAL: Duration = Duration::from_secs(5 * SECONDS_IN_MINUTE);
 
-    pub fn new(database_manager: Arc<DatabaseManager>, background_task_spawner: TokioTaskSpawner) -> Self {
+    pub fn new(
+        database_manager: Arc<DatabaseManager>,
+        authorizer: Arc<Authorizer>,
+        background_task_spawner: TokioTaskSpawner,
+    ) -> Self {
         let transactions: Arc<RwLock<HashMap<TransactionId, TransactionInfo>>> = Arc::new(RwLock::new(HashMap::new()));
         let cleanup_transactions = transactions.clone();
         background_task_spawner.spawn_interval(
@@ -69,18 +172,21 @@ impl LocalTransactionOperator {
             },
             IntervalTaskParameters::new_with_delay(Self::CLEANUP_INTERVAL, Self::CLEANUP_INTERVAL, false),
         );
-        Self { database_manager, transactions }
+        Self { database_manager, authorizer, transactions, queries: Arc::new(StdRwLock::new(HashMap::new())) }
     }
 
     pub async fn record(
         &self,
         transaction_id: TransactionId,
+        id: Uuid,
+        database_name: String,
         transaction_type: TransactionType,
         owner: String,
         close_sender: Sender<()>,
     ) {
         let mut transactions = self.transactions.write().await;
-        transactions.insert(transaction_id, TransactionInfo { transaction_type, owner, close_sender });
+        let info = TransactionInfo { id, database_name, transaction_type, owner, start: Instant::now(), close_sender };
+        transactions.insert(transaction_id, info);
     }
 }
 
@@ -91,9 +197,14 @@ impl TransactionOperator for LocalTransactionOperator {
         database_name: &str,
         transaction_type: TransactionType,
         options: TransactionOptions,
+        id: Uuid,
         owner: String,
         close_sender: Sender<()>,
     ) -> Result<Transaction, ArcServerStateError> {
+        // Authorize before the lookup, so that users cannot tell which ungranted databases exist
+        self.authorizer
+            .authorize_transaction_open(&owner, database_name, transaction_type)
+            .map_err(arc_server_state_err)?;
         let database = self.database_manager.database(database_name).ok_or_else(|| {
             arc_server_state_err(LocalServerStateError::DatabaseNotFound { name: database_name.to_string() })
         })?;
@@ -101,7 +212,7 @@ impl TransactionOperator for LocalTransactionOperator {
             open_transaction_blocking(database, transaction_type, options).await.map_err(|typedb_source| {
                 arc_server_state_err(LocalServerStateError::TransactionOpenFailed { typedb_source })
             })?;
-        self.record(transaction.id(), transaction_type, owner, close_sender).await;
+        self.record(transaction.id(), id, database_name.to_owned(), transaction_type, owner, close_sender).await;
         Ok(transaction)
     }
 
@@ -126,4 +237,104 @@ impl TransactionOperator for LocalTransactionOperator {
             }
         }
     }
+
+    async fn transactions(&self, accessor: Accessor) -> Vec<OpenTransactionInfo> {
+        let transactions = self.transactions.read().await;
+        let queries = self.queries.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+        transactions
+            .values()
+            .filter(|info| !info.close_sender.is_closed())
+            .filter(|info| PermissionManager::exec_transaction_kill_permitted(accessor.as_str(), &info.owner))
+            .map(|info| OpenTransactionInfo {
+                id: info.id,
+                database_name: info.database_name.clone(),
+                transaction_type: info.transaction_type,
+                owner: info.owner.clone(),
+                elapsed: info.start.elapsed(),
+                running_queries: queries.values().filter(|query| query.transaction_id == info.id).count(),
+            })
+            .collect()
+    }
+
+    async fn kill(&self, accessor: Accessor, transaction_id: Uuid) -> Result<(), ArcServerStateError> {
+        let mut transactions = self.transactions.write().await;
+        let Some(id) = transactions.iter().find(|(_, info)| info.id == transaction_id).map(|(id, _)| *id) else {
+            return Err(arc_server_state_err(LocalServerStateError::TransactionNotFound {
+                id: transaction_id.to_string(),
+            }));
+        };
+        if !PermissionManager::exec_transaction_kill_permitted(accessor.as_str(), &transactions[&id].owner) {
+            return Err(arc_server_state_err(LocalServerStateError::OperationNotPermitted {}));
+        }
+        if let Some(info) = transactions.remove(&id) {
+            let _ = info.close_sender.send(()).await;
+        }
+        Ok(())
+    }
+
+    async fn query_register(
+        &self,
+        transaction_id: TransactionId,
+        source_query: &str,
+        interrupt: ExecutionInterrupt,
+    ) -> (RunningQuery, ExecutionInterrupt) {
+        let id = Uuid::new_v4();
+        let running_query = RunningQuery { id, queries: self.queries.clone() };
+        // queries of a transaction that is already being closed are not listed, and cannot be killed
+        let Some(transaction) = self
+            .transactions
+            .read()
+            .await
+            .get(&transaction_id)
+            .map(|transaction| (transaction.id, transaction.database_name.clone(), transaction.owner.clone()))
+        else {
+            return (running_query, interrupt);
+        };
+        let (transaction_id, database_name, owner) = transaction;
+        let (interrupt_sender, interrupt_receiver) = broadcast::channel(1);
+        let info = QueryInfo {
+            transaction_id,
+            database_name,
+            owner,
+            source_query: source_query.to_owned(),
+            start: Instant::now(),
+            interrupt_sender,
+            is_killed: false,
+        };
+        self.queries.write().unwrap_or_else(|poisoned| poisoned.into_inner()).insert(id, info);
+        (running_query, interrupt.with_signal(interrupt_receiver))
+    }
+
+    async fn queries(&self, accessor: Accessor) -> Vec<RunningQueryInfo> {
+        let queries = self.queries.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+        queries
+            .iter()
+            .filter(|(_, info)| PermissionManager::exec_transaction_kill_permitted(accessor.as_str(), &info.owner))
+            .map(|(id, info)| RunningQueryInfo {
+                id: *id,
+                transaction_id: info.transaction_id,
+                database_name: info.database_name.clone(),
+                owner: info.owner.clone(),
+                source_query: info.source_query.clone(),
+                elapsed: info.start.elapsed(),
+            })
+            .collect()
+    }
+
+    async fn query_kill(&self, accessor: Accessor, query_id: Uuid) -> Result<(), ArcServerStateError> {
+        let mut queries = self.queries.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let Some(info) = queries.get_mut(&query_id) else {
+            return Err(arc_server_state_err(LocalServerStateError::QueryNotFound { id: query_id.to_string() }));
+        };
+        if !PermissionManager::exec_transaction_kill_permitted(accessor.as_str(), &info.owner) {
+            return Err(arc_server_state_err(LocalServerStateError::OperationNotPermitted {}));
+        }
+        // killing a query that is already being killed has no further effect
+        if !info.is_killed {
+            info.is_killed = true;
+            // the query may have finished, and dropped its receivers, in the meantime
+            let _ = info.interrupt_sender.send(InterruptType::QueryKilled);
+        }
+        Ok(())
+    }
 }
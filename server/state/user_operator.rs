@@ -7,18 +7,30 @@
 use std::{
     fmt::Debug,
     sync::{Arc, RwLock as StdRwLock},
+    time::Duration,
 };
 
 use async_trait::async_trait;
+use concurrency::{IntervalTaskParameters, TokioTaskSpawner};
 use database::database_manager::DatabaseManager;
-use resource::constants::server::DEFAULT_USER_NAME;
-use system::concepts::{Credential, User};
-use user::{permission_manager::PermissionManager, user_manager::UserManager};
+use error::TypeDBError;
+use resource::constants::{common::SECONDS_IN_MINUTE, server::DEFAULT_USER_NAME};
+use system::concepts::{ApiKey, Grant, Session, User};
+use tracing::{Level, event};
+use user::{
+    errors::UserSessionError, password_policy::PasswordPolicy, permission_manager::PermissionManager,
+    user_manager::UserManager,
+};
 
 use super::TransactionOperator;
 use crate::{
-    authentication::{Accessor, credential_verifier::CredentialVerifier, token_manager::TokenManager},
+    authentication::{
+        Accessor, AuthenticationError,
+        credential_verifier::{CredentialVerifier, SystemCredentialVerifier},
+        token_manager::TokenManager,
+    },
     error::{ArcServerStateError, LocalServerStateError, arc_server_state_err},
+    parameters::config::LockoutConfig,
     system_init::SYSTEM_DB,
 };
 
@@ -30,24 +42,71 @@ pub trait UserOperator: Debug + Send + Sync {
 
     async fn get(&self, accessor: Accessor, name: &str) -> Result<User, ArcServerStateError>;
 
-    async fn create(&self, accessor: Accessor, user: User, credential: Credential) -> Result<(), ArcServerStateError>;
+    async fn create(&self, accessor: Accessor, user: User, password: String) -> Result<(), ArcServerStateError>;
 
     async fn update(
         &self,
         accessor: Accessor,
         username: &str,
         user_update: Option<User>,
-        credential_update: Option<Credential>,
+        password_update: Option<String>,
     ) -> Result<(), ArcServerStateError>;
 
     async fn delete(&self, accessor: Accessor, username: &str) -> Result<(), ArcServerStateError>;
 
+    async fn grants(&self, accessor: Accessor, username: &str) -> Result<Vec<Grant>, ArcServerStateError>;
+
+    async fn grant(&self, accessor: Accessor, username: &str, grant: Grant) -> Result<(), ArcServerStateError>;
+
+    async fn revoke(&self, accessor: Accessor, username: &str, grant: Grant) -> Result<(), ArcServerStateError>;
+
+    async fn sessions(&self, accessor: Accessor, username: &str) -> Result<Vec<Session>, ArcServerStateError>;
+
+    async fn sessions_revoke(
+        &self,
+        accessor: Accessor,
+        username: &str,
+        session_id: Option<&str>,
+    ) -> Result<(), ArcServerStateError>;
+
+    async fn sessions_restore(&self) -> Result<(), ArcServerStateError>;
+
+    async fn api_keys(&self, accessor: Accessor, username: &str) -> Result<Vec<ApiKey>, ArcServerStateError>;
+
+    async fn api_key_create(
+        &self,
+        accessor: Accessor,
+        username: &str,
+        name: &str,
+        validity: Option<Duration>,
+    ) -> Result<(ApiKey, String), ArcServerStateError>;
+
+    async fn api_key_delete(&self, accessor: Accessor, username: &str, name: &str) -> Result<(), ArcServerStateError>;
+
+    async fn password_change(
+        &self,
+        username: &str,
+        password: &str,
+        new_password: String,
+    ) -> Result<(), ArcServerStateError>;
+
+    async fn password_expire(&self, accessor: Accessor, username: &str) -> Result<(), ArcServerStateError>;
+
     async fn verify_password(&self, username: &str, password: &str) -> Result<(), ArcServerStateError>;
 
     async fn token_create(&self, username: String, password: String) -> Result<String, ArcServerStateError>;
 
+    // Like `token_create`, but users that must change their password get a token that only lets them change it
+    async fn token_create_allowing_password_change(
+        &self,
+        username: String,
+        password: String,
+    ) -> Result<String, ArcServerStateError>;
+
     async fn token_get_owner(&self, token: &str) -> Option<String>;
 
+    async fn password_change_token_get_owner(&self, token: &str) -> Option<String>;
+
     fn manager(&self) -> Result<Arc<UserManager>, ArcServerStateError>;
 
     fn is_initialised(&self) -> bool;
@@ -58,34 +117,74 @@ pub struct LocalUserOperator {
     database_manager: Arc<DatabaseManager>,
     token_manager: Arc<TokenManager>,
     user_manager: StdRwLock<Option<Arc<UserManager>>>,
-    credential_verifier: StdRwLock<Option<Arc<CredentialVerifier>>>,
+    credential_verifier: StdRwLock<Option<Arc<SystemCredentialVerifier>>>,
+    external_credential_verifiers: Vec<Arc<dyn CredentialVerifier>>,
     transaction_operator: Arc<dyn TransactionOperator>,
+    password_policy: PasswordPolicy,
+    lockout: LockoutConfig,
 }
 
 impl LocalUserOperator {
+    const SESSIONS_CLEANUP_INTERVAL: Duration = Duration::from_secs(10 * SECONDS_IN_MINUTE);
+
     pub fn new(
         database_manager: Arc<DatabaseManager>,
         token_manager: Arc<TokenManager>,
         transaction_operator: Arc<dyn TransactionOperator>,
+        password_policy: PasswordPolicy,
+        lockout: LockoutConfig,
+        external_credential_verifiers: Vec<Arc<dyn CredentialVerifier>>,
+        background_task_spawner: TokioTaskSpawner,
     ) -> Self {
+        // The token manager forgets expired sessions by itself, but they stay in the system database until removed
+        let cleanup_database_manager = database_manager.clone();
+        background_task_spawner.spawn_interval(
+            move || {
+                let database_manager = cleanup_database_manager.clone();
+                async move {
+                    if let Err(err) = Self::delete_expired_sessions(&database_manager) {
+                        event!(Level::WARN, "Failed to delete expired sessions: {}", err.format_code_and_description());
+                    }
+                }
+            },
+            IntervalTaskParameters::new_with_delay(
+                Self::SESSIONS_CLEANUP_INTERVAL,
+                Self::SESSIONS_CLEANUP_INTERVAL,
+                false,
+            ),
+        );
         Self {
             database_manager,
             token_manager,
             user_manager: StdRwLock::new(None),
             credential_verifier: StdRwLock::new(None),
+            external_credential_verifiers,
             transaction_operator,
+            password_policy,
+            lockout,
         }
     }
 
     fn try_load_system_managers(&self) {
         if let Some(system_db) = self.database_manager.database_unrestricted(SYSTEM_DB) {
-            let user_manager = Arc::new(UserManager::new(system_db));
-            let credential_verifier = Arc::new(CredentialVerifier::new(user_manager.clone()));
+            let user_manager = Arc::new(UserManager::new(system_db).with_password_policy(self.password_policy.clone()));
+            let credential_verifier =
+                Arc::new(SystemCredentialVerifier::new(user_manager.clone(), self.lockout.clone()));
             *self.user_manager.write().unwrap() = Some(user_manager);
             *self.credential_verifier.write().unwrap() = Some(credential_verifier);
         }
     }
 
+    fn delete_expired_sessions(database_manager: &DatabaseManager) -> Result<(), LocalServerStateError> {
+        // before initialisation there are no sessions to delete
+        let Some(system_db) = database_manager.database_unrestricted(SYSTEM_DB) else {
+            return Ok(());
+        };
+        UserManager::new(system_db)
+            .delete_expired_sessions(TokenManager::now_seconds())
+            .map_err(|typedb_source| LocalServerStateError::UserSessionsCannotBeUpdated { typedb_source })
+    }
+
     fn get_user_manager(&self) -> Result<Arc<UserManager>, LocalServerStateError> {
         if let Some(um) = self.user_manager.read().unwrap().clone() {
             return Ok(um);
@@ -94,13 +193,77 @@ impl LocalUserOperator {
         self.user_manager.read().unwrap().clone().ok_or(LocalServerStateError::NotInitialised {})
     }
 
-    fn get_credential_verifier(&self) -> Result<Arc<CredentialVerifier>, LocalServerStateError> {
+    // Ends every session of the user after their credentials changed
+    async fn sign_out(&self, user_manager: &UserManager, username: &str) -> Result<(), ArcServerStateError> {
+        user_manager.delete_sessions(username, None).map_err(|typedb_source| {
+            arc_server_state_err(LocalServerStateError::UserSessionsCannotBeUpdated { typedb_source })
+        })?;
+        self.token_manager.invalidate_user(username).await;
+        self.transaction_operator.close_by_owner(username).await;
+        Ok(())
+    }
+
+    fn get_credential_verifier(&self) -> Result<Arc<SystemCredentialVerifier>, LocalServerStateError> {
         if let Some(cv) = self.credential_verifier.read().unwrap().clone() {
             return Ok(cv);
         }
         self.try_load_system_managers();
         self.credential_verifier.read().unwrap().clone().ok_or(LocalServerStateError::NotInitialised {})
     }
+
+    async fn verify_credential(&self, username: &str, password: &str) -> Result<(), LocalServerStateError> {
+        if !self.is_initialised() {
+            return Err(LocalServerStateError::NotInitialised {});
+        }
+        let credential_verifier = self.get_credential_verifier()?;
+        let result = match ApiKey::parse(password) {
+            Some(_) => credential_verifier.verify_password(username, password).await,
+            None => self.verify_user_password(&credential_verifier, username, password).await,
+        };
+        result.map_err(|typedb_source| LocalServerStateError::AuthenticationError { typedb_source })
+    }
+
+    // The users' own passwords are checked before any external identity provider is consulted. Lockouts and
+    // required password changes apply whichever of them proves the identity.
+    async fn verify_user_password(
+        &self,
+        credential_verifier: &SystemCredentialVerifier,
+        username: &str,
+        password: &str,
+    ) -> Result<(), AuthenticationError> {
+        let state = credential_verifier.sign_in_state(username)?;
+        let is_verified = credential_verifier.matches_password(username, password)
+            || self.verify_external_credential(username, password).await?;
+        credential_verifier.conclude_sign_in(username, &state, is_verified)
+    }
+
+    async fn issue_token(&self, username: String, password_change_only: bool) -> Result<String, ArcServerStateError> {
+        let user_manager = self.get_user_manager().map_err(arc_server_state_err)?;
+        let (token, session) = match password_change_only {
+            true => self.token_manager.new_password_change_token(username).await,
+            false => self.token_manager.new_token(username).await,
+        };
+        // Each sign-in commits its session before the token is handed out, as a token whose session is lost in a
+        // restart stops being accepted. Sign-ins are rare next to the requests their tokens authenticate, and each
+        // write holds a single small relation, so the writes are not batched.
+        if let Err(typedb_source) = user_manager.create_session(&session) {
+            self.token_manager.revoke_session(&session.id).await;
+            return Err(Arc::new(LocalServerStateError::UserSessionsCannotBeUpdated { typedb_source }));
+        }
+        Ok(token)
+    }
+
+    // External identity providers only vouch for users that exist locally, which `sign_in_state` establishes
+    async fn verify_external_credential(&self, username: &str, password: &str) -> Result<bool, AuthenticationError> {
+        for verifier in &self.external_credential_verifiers {
+            match verifier.verify_password(username, password).await {
+                Ok(()) => return Ok(true),
+                Err(AuthenticationError::InvalidCredential { .. }) => (),
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(false)
+    }
 }
 
 #[async_trait]
@@ -147,14 +310,14 @@ impl UserOperator for LocalUserOperator {
         }
     }
 
-    async fn create(&self, accessor: Accessor, user: User, credential: Credential) -> Result<(), ArcServerStateError> {
+    async fn create(&self, accessor: Accessor, user: User, password: String) -> Result<(), ArcServerStateError> {
         if !PermissionManager::exec_user_create_permitted(accessor.as_str()) {
             return Err(Arc::new(LocalServerStateError::OperationNotPermitted {}));
         }
 
         let user_manager = self.get_user_manager().map_err(arc_server_state_err)?;
         user_manager
-            .create(&user, &credential)
+            .create(&user, &password)
             .map_err(|typedb_source| arc_server_state_err(LocalServerStateError::UserCannotBeCreated { typedb_source }))
     }
 
@@ -163,20 +326,17 @@ impl UserOperator for LocalUserOperator {
         accessor: Accessor,
         username: &str,
         user_update: Option<User>,
-        credential_update: Option<Credential>,
+        password_update: Option<String>,
     ) -> Result<(), ArcServerStateError> {
         if !PermissionManager::exec_user_update_permitted(accessor.as_str(), username) {
             return Err(Arc::new(LocalServerStateError::OperationNotPermitted {}));
         }
 
         let user_manager = self.get_user_manager().map_err(arc_server_state_err)?;
-        user_manager.update(username, &user_update, &credential_update).map_err(|typedb_source| {
+        user_manager.update(username, &user_update, password_update.as_deref()).map_err(|typedb_source| {
             arc_server_state_err(LocalServerStateError::UserCannotBeUpdated { typedb_source })
         })?;
-
-        self.token_manager.invalidate_user(username).await;
-        self.transaction_operator.close_by_owner(username).await;
-        Ok(())
+        self.sign_out(&user_manager, username).await
     }
 
     async fn delete(&self, accessor: Accessor, username: &str) -> Result<(), ArcServerStateError> {
@@ -185,6 +345,9 @@ impl UserOperator for LocalUserOperator {
         }
 
         let user_manager = self.get_user_manager().map_err(arc_server_state_err)?;
+        user_manager.delete_sessions(username, None).map_err(|typedb_source| {
+            arc_server_state_err(LocalServerStateError::UserSessionsCannotBeUpdated { typedb_source })
+        })?;
         user_manager.delete(username).map_err(|typedb_source| {
             arc_server_state_err(LocalServerStateError::UserCannotBeDeleted { typedb_source })
         })?;
@@ -194,28 +357,198 @@ impl UserOperator for LocalUserOperator {
         Ok(())
     }
 
-    async fn verify_password(&self, username: &str, password: &str) -> Result<(), ArcServerStateError> {
+    async fn grants(&self, accessor: Accessor, username: &str) -> Result<Vec<Grant>, ArcServerStateError> {
+        if !PermissionManager::exec_user_grants_get_permitted(accessor.as_str(), username) {
+            return Err(Arc::new(LocalServerStateError::OperationNotPermitted {}));
+        }
+
+        let user_manager = self.get_user_manager().map_err(arc_server_state_err)?;
+        user_manager.grants(username).map_err(|typedb_source| {
+            arc_server_state_err(LocalServerStateError::UserGrantsCannotBeRetrieved { typedb_source })
+        })
+    }
+
+    async fn grant(&self, accessor: Accessor, username: &str, grant: Grant) -> Result<(), ArcServerStateError> {
+        if !PermissionManager::exec_user_grants_update_permitted(accessor.as_str()) {
+            return Err(Arc::new(LocalServerStateError::OperationNotPermitted {}));
+        }
+
+        let user_manager = self.get_user_manager().map_err(arc_server_state_err)?;
+        user_manager.grant(username, &grant).map_err(|typedb_source| {
+            arc_server_state_err(LocalServerStateError::UserGrantsCannotBeUpdated { typedb_source })
+        })
+    }
+
+    async fn revoke(&self, accessor: Accessor, username: &str, grant: Grant) -> Result<(), ArcServerStateError> {
+        if !PermissionManager::exec_user_grants_update_permitted(accessor.as_str()) {
+            return Err(Arc::new(LocalServerStateError::OperationNotPermitted {}));
+        }
+
+        let user_manager = self.get_user_manager().map_err(arc_server_state_err)?;
+        user_manager.revoke(username, &grant).map_err(|typedb_source| {
+            arc_server_state_err(LocalServerStateError::UserGrantsCannotBeUpdated { typedb_source })
+        })?;
+
+        // Open transactions were authorised with the revoked grant
+        self.transaction_operator.close_by_owner(username).await;
+        Ok(())
+    }
+
+    async fn sessions(&self, accessor: Accessor, username: &str) -> Result<Vec<Session>, ArcServerStateError> {
+        if !PermissionManager::exec_user_sessions_permitted(accessor.as_str(), username) {
+            return Err(Arc::new(LocalServerStateError::OperationNotPermitted {}));
+        }
+
+        Ok(self.token_manager.sessions(username).await)
+    }
+
+    async fn sessions_revoke(
+        &self,
+        accessor: Accessor,
+        username: &str,
+        session_id: Option<&str>,
+    ) -> Result<(), ArcServerStateError> {
+        if !PermissionManager::exec_user_sessions_permitted(accessor.as_str(), username) {
+            return Err(Arc::new(LocalServerStateError::OperationNotPermitted {}));
+        }
+        if let Some(session_id) = session_id {
+            if !self.token_manager.contains_session(username, session_id).await {
+                return Err(Arc::new(LocalServerStateError::UserSessionsCannotBeUpdated {
+                    typedb_source: UserSessionError::SessionNotFound {},
+                }));
+            }
+        }
+
+        let user_manager = self.get_user_manager().map_err(arc_server_state_err)?;
+        user_manager.delete_sessions(username, session_id).map_err(|typedb_source| {
+            arc_server_state_err(LocalServerStateError::UserSessionsCannotBeUpdated { typedb_source })
+        })?;
+
+        match session_id {
+            Some(session_id) => self.token_manager.revoke_session(session_id).await,
+            None => {
+                self.token_manager.invalidate_user(username).await;
+                self.transaction_operator.close_by_owner(username).await;
+            }
+        }
+        Ok(())
+    }
+
+    async fn sessions_restore(&self) -> Result<(), ArcServerStateError> {
+        Self::delete_expired_sessions(&self.database_manager).map_err(arc_server_state_err)?;
+        let user_manager = self.get_user_manager().map_err(arc_server_state_err)?;
+        let sessions = user_manager.sessions().map_err(|typedb_source| {
+            arc_server_state_err(LocalServerStateError::UserSessionsCannotBeRetrieved { typedb_source })
+        })?;
+        self.token_manager.restore_sessions(sessions).await;
+        Ok(())
+    }
+
+    async fn api_keys(&self, accessor: Accessor, username: &str) -> Result<Vec<ApiKey>, ArcServerStateError> {
+        if !PermissionManager::exec_user_api_keys_permitted(accessor.as_str(), username) {
+            return Err(Arc::new(LocalServerStateError::OperationNotPermitted {}));
+        }
+
+        let user_manager = self.get_user_manager().map_err(arc_server_state_err)?;
+        user_manager.api_keys(username).map_err(|typedb_source| {
+            arc_server_state_err(LocalServerStateError::UserApiKeysCannotBeRetrieved { typedb_source })
+        })
+    }
+
+    async fn api_key_create(
+        &self,
+        accessor: Accessor,
+        username: &str,
+        name: &str,
+        validity: Option<Duration>,
+    ) -> Result<(ApiKey, String), ArcServerStateError> {
+        if !PermissionManager::exec_user_api_keys_permitted(accessor.as_str(), username) {
+            return Err(Arc::new(LocalServerStateError::OperationNotPermitted {}));
+        }
+
+        let expires_at = validity.map(|validity| TokenManager::now_seconds() + validity.as_secs());
+        let user_manager = self.get_user_manager().map_err(arc_server_state_err)?;
+        user_manager.create_api_key(username, name, expires_at).map_err(|typedb_source| {
+            arc_server_state_err(LocalServerStateError::UserApiKeysCannotBeUpdated { typedb_source })
+        })
+    }
+
+    async fn api_key_delete(&self, accessor: Accessor, username: &str, name: &str) -> Result<(), ArcServerStateError> {
+        if !PermissionManager::exec_user_api_keys_permitted(accessor.as_str(), username) {
+            return Err(Arc::new(LocalServerStateError::OperationNotPermitted {}));
+        }
+
+        let user_manager = self.get_user_manager().map_err(arc_server_state_err)?;
+        user_manager.delete_api_key(username, name).map_err(|typedb_source| {
+            arc_server_state_err(LocalServerStateError::UserApiKeysCannotBeUpdated { typedb_source })
+        })
+    }
+
+    // Lets users whose password has expired set a new one without signing in
+    async fn password_change(
+        &self,
+        username: &str,
+        password: &str,
+        new_password: String,
+    ) -> Result<(), ArcServerStateError> {
         if !self.is_initialised() {
             return Err(Arc::new(LocalServerStateError::NotInitialised {}));
         }
-        match self.get_credential_verifier() {
-            Ok(credential_verifier) => match credential_verifier.verify_password(username, password) {
-                Ok(()) => Ok(()),
-                Err(typedb_source) => Err(Arc::new(LocalServerStateError::AuthenticationError { typedb_source })),
-            },
-            Err(err) => Err(Arc::new(err)),
+        let credential_verifier = self.get_credential_verifier().map_err(arc_server_state_err)?;
+        match credential_verifier.verify_user_password(username, password) {
+            Ok(()) | Err(AuthenticationError::PasswordChangeRequired { .. }) => (),
+            Err(typedb_source) => return Err(Arc::new(LocalServerStateError::AuthenticationError { typedb_source })),
         }
+
+        let user_manager = self.get_user_manager().map_err(arc_server_state_err)?;
+        user_manager.update(username, &None, Some(&new_password)).map_err(|typedb_source| {
+            arc_server_state_err(LocalServerStateError::UserCannotBeUpdated { typedb_source })
+        })?;
+        self.sign_out(&user_manager, username).await
+    }
+
+    async fn password_expire(&self, accessor: Accessor, username: &str) -> Result<(), ArcServerStateError> {
+        if !PermissionManager::exec_user_password_expire_permitted(accessor.as_str()) {
+            return Err(Arc::new(LocalServerStateError::OperationNotPermitted {}));
+        }
+
+        let user_manager = self.get_user_manager().map_err(arc_server_state_err)?;
+        user_manager.expire_password(username).map_err(|typedb_source| {
+            arc_server_state_err(LocalServerStateError::UserPasswordStateCannotBeUpdated { typedb_source })
+        })
+    }
+
+    async fn verify_password(&self, username: &str, password: &str) -> Result<(), ArcServerStateError> {
+        self.verify_credential(username, password).await.map_err(arc_server_state_err)
     }
 
     async fn token_create(&self, username: String, password: String) -> Result<String, ArcServerStateError> {
         self.verify_password(&username, &password).await?;
-        Ok(self.token_manager.new_token(username).await)
+        self.issue_token(username, false).await
+    }
+
+    async fn token_create_allowing_password_change(
+        &self,
+        username: String,
+        password: String,
+    ) -> Result<String, ArcServerStateError> {
+        match self.verify_credential(&username, &password).await {
+            Ok(()) => self.issue_token(username, false).await,
+            Err(LocalServerStateError::AuthenticationError {
+                typedb_source: AuthenticationError::PasswordChangeRequired { .. },
+            }) => self.issue_token(username, true).await,
+            Err(err) => Err(Arc::new(err)),
+        }
     }
 
     async fn token_get_owner(&self, token: &str) -> Option<String> {
         self.token_manager.get_valid_token_owner(token).await
     }
 
+    async fn password_change_token_get_owner(&self, token: &str) -> Option<String> {
+        self.token_manager.get_valid_password_change_token_owner(token).await
+    }
+
     fn manager(&self) -> Result<Arc<UserManager>, ArcServerStateError> {
         self.get_user_manager().map_err(arc_server_state_err)
     }
@@ -228,3 +561,332 @@ impl UserOperator for LocalUserOperator {
         user_manager.contains(DEFAULT_USER_NAME).unwrap_or(false)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::Path,
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::Duration,
+    };
+
+    use async_trait::async_trait;
+    use concurrency::{TokioTaskSpawner, TokioTaskTracker};
+    use resource::constants::server::{DEFAULT_USER_NAME, DEFAULT_USER_PASSWORD};
+    use system::concepts::{Session, User};
+    use test_utils::{create_tmp_dir, create_tmp_storage_dir};
+    use tokio::sync::watch;
+    use user::password_policy::PasswordPolicy;
+
+    use super::{LocalUserOperator, UserOperator};
+    use crate::{
+        authentication::{
+            Accessor, AuthenticationError, authorizer::Authorizer, credential_verifier::CredentialVerifier,
+            token_manager::TokenManager,
+        },
+        parameters::config::LockoutConfig,
+        state::transaction_operator::LocalTransactionOperator,
+        system_init::tests::system_database_manager,
+    };
+
+    const USER: &str = "session-user";
+    const PASSWORD: &str = "session-password";
+
+    // A user operator as a server starting from `data_directory` builds it, signing tokens with a key kept in
+    // `key_directory` so that tokens stay verifiable across restarts
+    fn user_operator(data_directory: &Path, key_directory: &Path, spawner: TokioTaskSpawner) -> LocalUserOperator {
+        user_operator_with(
+            data_directory,
+            key_directory,
+            spawner,
+            PasswordPolicy::default(),
+            LockoutConfig::default(),
+            Vec::new(),
+        )
+    }
+
+    fn user_operator_with(
+        data_directory: &Path,
+        key_directory: &Path,
+        spawner: TokioTaskSpawner,
+        password_policy: PasswordPolicy,
+        lockout: LockoutConfig,
+        external_credential_verifiers: Vec<Arc<dyn CredentialVerifier>>,
+    ) -> LocalUserOperator {
+        let database_manager = system_database_manager(data_directory);
+        let signing_key_file = key_directory.join("signing-key");
+        if !signing_key_file.exists() {
+            fs::write(&signing_key_file, "k".repeat(64)).unwrap();
+        }
+        let token_manager =
+            TokenManager::new(Duration::from_secs(3600), Some(&signing_key_file), spawner.clone()).unwrap();
+        let authorizer = Arc::new(Authorizer::new(database_manager.clone(), false));
+        let transaction_operator =
+            Arc::new(LocalTransactionOperator::new(database_manager.clone(), authorizer, spawner.clone()));
+        LocalUserOperator::new(
+            database_manager,
+            Arc::new(token_manager),
+            transaction_operator,
+            password_policy,
+            lockout,
+            external_credential_verifiers,
+            spawner,
+        )
+    }
+
+    // An identity provider that accepts a single password for every user, counting how often it is consulted
+    #[derive(Debug, Default)]
+    struct ExternalVerifier {
+        consulted: AtomicUsize,
+    }
+
+    impl ExternalVerifier {
+        const PASSWORD: &'static str = "external-password";
+    }
+
+    #[async_trait]
+    impl CredentialVerifier for ExternalVerifier {
+        async fn verify_password(&self, _: &str, password: &str) -> Result<(), AuthenticationError> {
+            self.consulted.fetch_add(1, Ordering::SeqCst);
+            match password == Self::PASSWORD {
+                true => Ok(()),
+                false => Err(AuthenticationError::InvalidCredential {}),
+            }
+        }
+    }
+
+    fn create_users(user_operator: &LocalUserOperator) {
+        let user_manager = user_operator.manager().unwrap();
+        user_manager.create(&User::new(DEFAULT_USER_NAME.to_string()), DEFAULT_USER_PASSWORD).unwrap();
+        user_manager.create(&User::new(USER.to_string()), PASSWORD).unwrap();
+    }
+
+    // The name of the error a sign-in of the user fails with
+    async fn sign_in_error(user_operator: &LocalUserOperator, password: &str) -> &'static str {
+        let error = user_operator.token_create(USER.to_string(), password.to_string()).await.unwrap_err();
+        error.source_typedb_error().map_or(error.variant_name(), |source| source.variant_name())
+    }
+
+    #[tokio::test]
+    async fn revoked_sessions_no_longer_accept_their_tokens() {
+        let (_shutdown_sender, shutdown_receiver) = watch::channel(());
+        let tracker = TokioTaskTracker::new(shutdown_receiver);
+        let (data_directory, key_directory) = (create_tmp_storage_dir(), create_tmp_dir("signing_key"));
+        let user_operator = user_operator(data_directory.as_ref(), key_directory.as_ref(), tracker.get_spawner());
+        create_users(&user_operator);
+
+        let tokens = [
+            user_operator.token_create(USER.to_string(), PASSWORD.to_string()).await.unwrap(),
+            user_operator.token_create(USER.to_string(), PASSWORD.to_string()).await.unwrap(),
+        ];
+        let sessions = user_operator.sessions(Accessor(USER.to_string()), USER).await.unwrap();
+        assert_eq!(sessions.len(), 2);
+
+        user_operator.sessions_revoke(Accessor(USER.to_string()), USER, Some(&sessions[0].id)).await.unwrap();
+        let mut owners = Vec::new();
+        for token in &tokens {
+            owners.push(user_operator.token_get_owner(token).await);
+        }
+        owners.sort();
+        assert_eq!(owners, vec![None, Some(USER.to_string())]);
+
+        user_operator.sessions_revoke(Accessor(USER.to_string()), USER, None).await.unwrap();
+        for token in &tokens {
+            assert_eq!(user_operator.token_get_owner(token).await, None);
+        }
+        // revoked sessions are not restored either
+        user_operator.sessions_restore().await.unwrap();
+        for token in &tokens {
+            assert_eq!(user_operator.token_get_owner(token).await, None);
+        }
+    }
+
+    #[tokio::test]
+    async fn sessions_survive_a_restart() {
+        let (data_directory, key_directory) = (create_tmp_storage_dir(), create_tmp_dir("signing_key"));
+        let token = {
+            let (shutdown_sender, shutdown_receiver) = watch::channel(());
+            let tracker = TokioTaskTracker::new(shutdown_receiver);
+            let user_operator = user_operator(data_directory.as_ref(), key_directory.as_ref(), tracker.get_spawner());
+            create_users(&user_operator);
+            let token = user_operator.token_create(USER.to_string(), PASSWORD.to_string()).await.unwrap();
+            drop(user_operator);
+            shutdown_sender.send(()).unwrap();
+            tracker.join().await;
+            token
+        };
+
+        let (_shutdown_sender, shutdown_receiver) = watch::channel(());
+        let tracker = TokioTaskTracker::new(shutdown_receiver);
+        let user_operator = user_operator(data_directory.as_ref(), key_directory.as_ref(), tracker.get_spawner());
+        assert_eq!(user_operator.token_get_owner(&token).await, None);
+        user_operator.sessions_restore().await.unwrap();
+        assert_eq!(user_operator.token_get_owner(&token).await.as_deref(), Some(USER));
+    }
+
+    #[tokio::test]
+    async fn expired_sessions_are_deleted_from_the_system_database() {
+        let (_shutdown_sender, shutdown_receiver) = watch::channel(());
+        let tracker = TokioTaskTracker::new(shutdown_receiver);
+        let (data_directory, key_directory) = (create_tmp_storage_dir(), create_tmp_dir("signing_key"));
+        let user_operator = user_operator(data_directory.as_ref(), key_directory.as_ref(), tracker.get_spawner());
+        create_users(&user_operator);
+
+        let user_manager = user_operator.manager().unwrap();
+        let now = TokenManager::now_seconds();
+        user_manager
+            .create_session(&Session::new("expired".to_string(), USER.to_string(), now - 20, now - 10))
+            .unwrap();
+        user_manager.create_session(&Session::new("active".to_string(), USER.to_string(), now, now + 3600)).unwrap();
+
+        LocalUserOperator::delete_expired_sessions(&user_operator.database_manager).unwrap();
+        let session_ids = user_manager.sessions().unwrap().into_iter().map(|session| session.id).collect::<Vec<_>>();
+        assert_eq!(session_ids, vec!["active".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn repeated_failed_sign_ins_lock_the_user_out() {
+        let (_shutdown_sender, shutdown_receiver) = watch::channel(());
+        let tracker = TokioTaskTracker::new(shutdown_receiver);
+        let (data_directory, key_directory) = (create_tmp_storage_dir(), create_tmp_dir("signing_key"));
+        let lockout = LockoutConfig { max_failed_attempts: 3, duration: Duration::from_secs(3600) };
+        let user_operator = user_operator_with(
+            data_directory.as_ref(),
+            key_directory.as_ref(),
+            tracker.get_spawner(),
+            PasswordPolicy::default(),
+            lockout,
+            Vec::new(),
+        );
+        create_users(&user_operator);
+
+        for _ in 0..2 {
+            assert_eq!(sign_in_error(&user_operator, "wrong-password").await, "InvalidCredential");
+        }
+        // a successful sign-in forgets earlier failed attempts
+        user_operator.token_create(USER.to_string(), PASSWORD.to_string()).await.unwrap();
+        for _ in 0..3 {
+            assert_eq!(sign_in_error(&user_operator, "wrong-password").await, "InvalidCredential");
+        }
+        assert_eq!(sign_in_error(&user_operator, PASSWORD).await, "UserLocked");
+
+        let state = user_operator.manager().unwrap().password_state(USER).unwrap().unwrap();
+        assert!(state.is_locked(TokenManager::now_seconds()));
+        assert_eq!(state.failed_attempts, 0);
+    }
+
+    #[tokio::test]
+    async fn concurrent_failed_sign_ins_are_all_counted() {
+        let (_shutdown_sender, shutdown_receiver) = watch::channel(());
+        let tracker = TokioTaskTracker::new(shutdown_receiver);
+        let (data_directory, key_directory) = (create_tmp_storage_dir(), create_tmp_dir("signing_key"));
+        let user_operator = user_operator(data_directory.as_ref(), key_directory.as_ref(), tracker.get_spawner());
+        create_users(&user_operator);
+
+        let user_manager = user_operator.manager().unwrap();
+        let attempts = 4;
+        std::thread::scope(|scope| {
+            for _ in 0..attempts {
+                scope.spawn(|| assert!(!user_manager.record_failed_password_attempt(USER, 100, 0).unwrap()));
+            }
+        });
+        let state = user_manager.password_state(USER).unwrap().unwrap();
+        assert_eq!(state.failed_attempts, attempts);
+    }
+
+    #[tokio::test]
+    async fn recently_used_passwords_cannot_be_set_again() {
+        let (_shutdown_sender, shutdown_receiver) = watch::channel(());
+        let tracker = TokioTaskTracker::new(shutdown_receiver);
+        let (data_directory, key_directory) = (create_tmp_storage_dir(), create_tmp_dir("signing_key"));
+        let password_policy = PasswordPolicy { history_size: 2, ..PasswordPolicy::default() };
+        let user_operator = user_operator_with(
+            data_directory.as_ref(),
+            key_directory.as_ref(),
+            tracker.get_spawner(),
+            password_policy,
+            LockoutConfig::default(),
+            Vec::new(),
+        );
+        create_users(&user_operator);
+
+        let set_password = |password: &'static str| {
+            user_operator.update(Accessor(USER.to_string()), USER, None, Some(password.to_string()))
+        };
+        assert!(set_password(PASSWORD).await.is_err(), "The current password cannot be set again");
+        set_password("second-password").await.unwrap();
+        assert!(set_password(PASSWORD).await.is_err(), "The previous password is within the history");
+        set_password("third-password").await.unwrap();
+        set_password(PASSWORD).await.unwrap();
+        user_operator.token_create(USER.to_string(), PASSWORD.to_string()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn users_that_must_change_their_password_only_get_a_password_change_token() {
+        let (_shutdown_sender, shutdown_receiver) = watch::channel(());
+        let tracker = TokioTaskTracker::new(shutdown_receiver);
+        let (data_directory, key_directory) = (create_tmp_storage_dir(), create_tmp_dir("signing_key"));
+        let user_operator = user_operator(data_directory.as_ref(), key_directory.as_ref(), tracker.get_spawner());
+        create_users(&user_operator);
+        user_operator.password_expire(Accessor(DEFAULT_USER_NAME.to_string()), USER).await.unwrap();
+
+        assert_eq!(sign_in_error(&user_operator, PASSWORD).await, "PasswordChangeRequired");
+        assert!(
+            user_operator
+                .token_create_allowing_password_change(USER.to_string(), "wrong-password".to_string())
+                .await
+                .is_err()
+        );
+        let token =
+            user_operator.token_create_allowing_password_change(USER.to_string(), PASSWORD.to_string()).await.unwrap();
+        assert_eq!(user_operator.token_get_owner(&token).await, None);
+        assert_eq!(user_operator.password_change_token_get_owner(&token).await.as_deref(), Some(USER));
+
+        let new_password = "new-session-password";
+        user_operator.update(Accessor(USER.to_string()), USER, None, Some(new_password.to_string())).await.unwrap();
+        assert_eq!(user_operator.password_change_token_get_owner(&token).await, None);
+        assert_eq!(sign_in_error(&user_operator, PASSWORD).await, "InvalidCredential");
+        let token = user_operator
+            .token_create_allowing_password_change(USER.to_string(), new_password.to_string())
+            .await
+            .unwrap();
+        assert_eq!(user_operator.token_get_owner(&token).await.as_deref(), Some(USER));
+    }
+
+    #[tokio::test]
+    async fn external_sign_ins_follow_the_local_sign_in_rules() {
+        let (_shutdown_sender, shutdown_receiver) = watch::channel(());
+        let tracker = TokioTaskTracker::new(shutdown_receiver);
+        let (data_directory, key_directory) = (create_tmp_storage_dir(), create_tmp_dir("signing_key"));
+        let external_verifier = Arc::new(ExternalVerifier::default());
+        let user_operator = user_operator_with(
+            data_directory.as_ref(),
+            key_directory.as_ref(),
+            tracker.get_spawner(),
+            PasswordPolicy::default(),
+            LockoutConfig { max_failed_attempts: 2, duration: Duration::from_secs(3600) },
+            vec![external_verifier.clone()],
+        );
+        create_users(&user_operator);
+
+        user_operator.token_create(USER.to_string(), PASSWORD.to_string()).await.unwrap();
+        assert_eq!(external_verifier.consulted.load(Ordering::SeqCst), 0, "Local passwords are checked first");
+        user_operator.token_create(USER.to_string(), ExternalVerifier::PASSWORD.to_string()).await.unwrap();
+        assert_eq!(external_verifier.consulted.load(Ordering::SeqCst), 1);
+        assert_eq!(sign_in_error(&user_operator, "unknown-token").await, "InvalidCredential");
+        assert_eq!(sign_in_error(&user_operator, "wrong-token").await, "InvalidCredential");
+        assert_eq!(sign_in_error(&user_operator, ExternalVerifier::PASSWORD).await, "UserLocked");
+
+        user_operator.password_expire(Accessor(DEFAULT_USER_NAME.to_string()), DEFAULT_USER_NAME).await.unwrap();
+        let error = user_operator
+            .token_create(DEFAULT_USER_NAME.to_string(), ExternalVerifier::PASSWORD.to_string())
+            .await
+            .unwrap_err();
+        let error = error.source_typedb_error().map_or(error.variant_name(), |source| source.variant_name());
+        assert_eq!(error, "PasswordChangeRequired");
+    }
+}
@@ -60,14 +60,20 @@ pub async fn initialise_default_user(server_state: &ServerState) -> Result<(), A
     let accessor = Accessor(DEFAULT_USER_NAME.to_string());
     let exists = server_state.users().contains(accessor.clone(), DEFAULT_USER_NAME).await?;
     if !exists {
-        server_state
-            .users()
-            .create(
-                accessor,
-                User::new(DEFAULT_USER_NAME.to_string()),
-                Credential::PasswordType { password_hash: PasswordHash::from_password(DEFAULT_USER_PASSWORD) },
+        // The well-known default password bypasses the password policy, but has to be replaced at the first
+        // sign-in whenever the policy would reject it
+        let user_manager = server_state.users().manager()?;
+        user_manager
+            .create_unrestricted(
+                &User::new(DEFAULT_USER_NAME.to_string()),
+                &Credential::PasswordType { password_hash: PasswordHash::from_password(DEFAULT_USER_PASSWORD) },
             )
-            .await?;
+            .map_err(|typedb_source| LocalServerStateError::UserCannotBeCreated { typedb_source })?;
+        if user_manager.password_policy().validate(DEFAULT_USER_PASSWORD).is_err() {
+            user_manager
+                .expire_password(DEFAULT_USER_NAME)
+                .map_err(|typedb_source| LocalServerStateError::UserPasswordStateCannotBeUpdated { typedb_source })?;
+        }
     }
     Ok(())
 }
@@ -93,3 +99,23 @@ pub fn get_system_database_schema_commit_intent(
     let commit_intent_opt = if commit_intent.has_changes() { Some(commit_intent) } else { None };
     Ok((transaction_profile, commit_intent_opt))
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{path::Path, sync::Arc};
+
+    use database::{database_manager::DatabaseManager, transaction::CommitIntent};
+    use resource::profile::CommitProfile;
+
+    use super::{SYSTEM_DB, get_system_database_schema_commit_intent};
+
+    // A database manager holding an initialised system database, for testing components without a running server
+    pub(crate) fn system_database_manager(data_directory: &Path) -> Arc<DatabaseManager> {
+        let database_manager = DatabaseManager::new(data_directory).unwrap();
+        database_manager.put_database_unrestricted(SYSTEM_DB).unwrap();
+        let system_db = database_manager.database_unrestricted(SYSTEM_DB).unwrap();
+        let (_, commit_intent) = get_system_database_schema_commit_intent(system_db).unwrap();
+        commit_intent.unwrap().commit(&mut CommitProfile::DISABLED).unwrap();
+        database_manager
+    }
+}
@@ -4,7 +4,7 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use std::sync::Arc;
+use std::{fmt, sync::Arc};
 
 use database::{
     Database,
@@ -24,6 +24,16 @@ pub enum TransactionType {
     Schema,
 }
 
+impl fmt::Display for TransactionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransactionType::Read => write!(f, "read"),
+            TransactionType::Write => write!(f, "write"),
+            TransactionType::Schema => write!(f, "schema"),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum Transaction {
     Read(TransactionRead<WALClient>),
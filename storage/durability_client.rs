@@ -6,12 +6,14 @@
 
 use std::{
     io::{self, Read, Write},
+    path::Path,
     sync::{Arc, mpsc},
 };
 
 use durability::{DurabilityRecordType, DurabilityService, DurabilityServiceError, RawRecord, wal::WAL};
 use error::typedb_error;
 use itertools::Itertools;
+use primitive::histogram::LatencySnapshot;
 use resource::constants::storage::COMMIT_WAIT_FOR_FSYNC;
 
 use crate::sequence_number::SequenceNumber;
@@ -97,6 +99,15 @@ pub trait DurabilityClient {
 
     fn truncate_from(&self, sequence_number: SequenceNumber) -> Result<(), DurabilityClientError>;
 
+    /// Appends a record read from another durability service, keeping its sequence number.
+    fn append_replicated(&self, record: RawRecord<'_>) -> Result<(), DurabilityClientError>;
+
+    fn copy_from(
+        &self,
+        sequence_number: SequenceNumber,
+        directory: &Path,
+    ) -> Result<SequenceNumber, DurabilityClientError>;
+
     fn delete_durability(self) -> Result<(), DurabilityClientError>;
 
     fn reset(&mut self) -> Result<(), DurabilityClientError>;
@@ -112,6 +123,10 @@ impl WALClient {
         Self { wal }
     }
 
+    pub fn fsync_latency(&self) -> LatencySnapshot {
+        self.wal.fsync_latency()
+    }
+
     fn serialise_record(record: &impl DurabilityRecord) -> Result<Vec<u8>, DurabilityClientError> {
         let mut buf = Vec::new();
         record.serialise_into(&mut buf)?;
@@ -207,6 +222,20 @@ impl DurabilityClient for WALClient {
         self.wal.truncate_from(sequence_number).map_err(|err| DurabilityClientError::ServiceError { source: err })
     }
 
+    fn append_replicated(&self, record: RawRecord<'_>) -> Result<(), DurabilityClientError> {
+        self.wal.append_copied(record).map_err(|err| DurabilityClientError::ServiceError { source: err })
+    }
+
+    fn copy_from(
+        &self,
+        sequence_number: SequenceNumber,
+        directory: &Path,
+    ) -> Result<SequenceNumber, DurabilityClientError> {
+        self.wal
+            .copy_from(sequence_number, directory)
+            .map_err(|err| DurabilityClientError::ServiceError { source: err })
+    }
+
     fn delete_durability(self) -> Result<(), DurabilityClientError> {
         self.wal.delete_durability().map_err(|err| DurabilityClientError::ServiceError { source: err })
     }
@@ -0,0 +1,136 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::{
+    collections::VecDeque,
+    sync::Mutex,
+    time::{Duration, SystemTime},
+};
+
+use chrono::{DateTime, Utc};
+use error::typedb_error;
+
+use crate::sequence_number::SequenceNumber;
+
+/// Records the watermark as of each commit, so that wall-clock times can be resolved to the
+/// sequence number a reader would have seen at that time. Only the retention window is kept, and
+/// reads older than the window are refused.
+///
+/// The retention window only bounds which reads are accepted: storage never removes old versions,
+/// so nothing outside this type depends on the window.
+///
+/// Commit times are only known from when the storage was opened. Times before that cannot be
+/// resolved, but sequence numbers from before that are still accepted.
+#[derive(Debug)]
+pub struct CommitHistory {
+    retention: Mutex<Duration>,
+    timeline: Mutex<VecDeque<(SystemTime, SequenceNumber)>>,
+}
+
+impl CommitHistory {
+    pub(crate) fn new(watermark: SequenceNumber, retention: Duration) -> Self {
+        Self {
+            retention: Mutex::new(retention),
+            timeline: Mutex::new(VecDeque::from([(SystemTime::now(), watermark)])),
+        }
+    }
+
+    pub fn retention(&self) -> Duration {
+        *self.retention.lock().unwrap()
+    }
+
+    pub fn set_retention(&self, retention: Duration) {
+        *self.retention.lock().unwrap() = retention;
+    }
+
+    pub(crate) fn record(&self, watermark: SequenceNumber) {
+        let now = SystemTime::now();
+        let cutoff = self.cutoff(now);
+        let mut timeline = self.timeline.lock().unwrap();
+        if timeline.back().is_some_and(|(_, latest)| *latest >= watermark) {
+            return;
+        }
+        timeline.push_back((now, watermark));
+        // Keep the newest entry at or before the cutoff: it describes the state at the start of the window
+        while timeline.len() > 1 && timeline[1].0 <= cutoff {
+            timeline.pop_front();
+        }
+    }
+
+    pub(crate) fn reset(&self, watermark: SequenceNumber) {
+        let mut timeline = self.timeline.lock().unwrap();
+        timeline.clear();
+        timeline.push_back((SystemTime::now(), watermark));
+    }
+
+    pub fn sequence_number_at(&self, time: SystemTime) -> Result<SequenceNumber, HistoricalReadError> {
+        let now = SystemTime::now();
+        if time > now {
+            return Err(HistoricalReadError::TimestampInFuture { timestamp: Self::display_time(time) });
+        }
+        if time < self.cutoff(now) {
+            return Err(HistoricalReadError::TimestampOutsideRetention {
+                timestamp: Self::display_time(time),
+                retention_seconds: self.retention().as_secs(),
+            });
+        }
+        let timeline = self.timeline.lock().unwrap();
+        let index = timeline.partition_point(|(recorded, _)| *recorded <= time);
+        match index {
+            0 => Err(HistoricalReadError::TimestampNotRecorded {
+                timestamp: Self::display_time(time),
+                earliest: Self::display_time(timeline.front().unwrap().0),
+            }),
+            _ => Ok(timeline[index - 1].1),
+        }
+    }
+
+    pub fn validate_sequence_number(
+        &self,
+        sequence_number: SequenceNumber,
+        watermark: SequenceNumber,
+    ) -> Result<(), HistoricalReadError> {
+        if sequence_number > watermark {
+            return Err(HistoricalReadError::SequenceNumberNotCommitted {
+                sequence_number: sequence_number.number(),
+                watermark: watermark.number(),
+            });
+        }
+        let cutoff = self.cutoff(SystemTime::now());
+        let timeline = self.timeline.lock().unwrap();
+        let index = timeline.partition_point(|(recorded, _)| *recorded <= cutoff);
+        let oldest_retained = match index {
+            0 => SequenceNumber::MIN,
+            _ => timeline[index - 1].1,
+        };
+        if sequence_number < oldest_retained {
+            return Err(HistoricalReadError::SequenceNumberOutsideRetention {
+                sequence_number: sequence_number.number(),
+                oldest_retained: oldest_retained.number(),
+                retention_seconds: self.retention().as_secs(),
+            });
+        }
+        Ok(())
+    }
+
+    fn cutoff(&self, now: SystemTime) -> SystemTime {
+        now.checked_sub(self.retention()).unwrap_or(SystemTime::UNIX_EPOCH)
+    }
+
+    fn display_time(time: SystemTime) -> String {
+        DateTime::<Utc>::from(time).to_rfc3339()
+    }
+}
+
+typedb_error! {
+    pub HistoricalReadError(component = "Historical read", prefix = "HRD") {
+        TimestampInFuture(1, "Cannot read at '{timestamp}', which is in the future.", timestamp: String),
+        TimestampOutsideRetention(2, "Cannot read at '{timestamp}', which is older than the history retention window of {retention_seconds} seconds.", timestamp: String, retention_seconds: u64),
+        TimestampNotRecorded(3, "Cannot read at '{timestamp}': commit times are only known since '{earliest}'. Use a sequence number instead.", timestamp: String, earliest: String),
+        SequenceNumberNotCommitted(4, "Cannot read at sequence number {sequence_number}, which is ahead of the latest committed sequence number {watermark}.", sequence_number: u64, watermark: u64),
+        SequenceNumberOutsideRetention(5, "Cannot read at sequence number {sequence_number}, which is older than the oldest sequence number {oldest_retained} in the history retention window of {retention_seconds} seconds.", sequence_number: u64, oldest_retained: u64, retention_seconds: u64),
+    }
+}
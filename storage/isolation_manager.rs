@@ -33,6 +33,7 @@ use crate::{
 pub(crate) struct IsolationManager {
     initial_sequence_number: SequenceNumber,
     timeline: Timeline,
+    conflict_counts: [AtomicU64; IsolationConflict::ALL.len()],
 }
 
 impl fmt::Display for IsolationManager {
@@ -46,9 +47,20 @@ impl IsolationManager {
         IsolationManager {
             initial_sequence_number: next_sequence_number,
             timeline: Timeline::new(next_sequence_number),
+            conflict_counts: Default::default(),
         }
     }
 
+    pub(crate) fn conflict_counts(&self) -> Vec<(IsolationConflict, u64)> {
+        IsolationConflict::ALL
+            .into_iter()
+            .map(|conflict| {
+                let count = self.conflict_counts[conflict.index()].load(Ordering::Relaxed);
+                (conflict, count)
+            })
+            .collect()
+    }
+
     pub(crate) fn opened_for_read(&self, sequence_number: SequenceNumber) -> ReaderDropGuard {
         debug_assert!(
             sequence_number <= self.watermark(),
@@ -100,7 +112,10 @@ impl IsolationManager {
             self.timeline.may_increment_watermark(sequence_number);
         }
         match isolation_conflict {
-            Some(conflict) => Ok(ValidatedCommit::Conflict(conflict)),
+            Some(conflict) => {
+                self.conflict_counts[conflict.index()].fetch_add(1, Ordering::Relaxed);
+                Ok(ValidatedCommit::Conflict(conflict))
+            }
             None => {
                 let commit_record = match window.get_status(sequence_number) {
                     CommitStatus::Validated(commit_record) | CommitStatus::Applied(commit_record) => commit_record,
@@ -309,6 +324,30 @@ pub enum IsolationConflict {
     ExclusiveLock,
 }
 
+impl IsolationConflict {
+    pub const ALL: [IsolationConflict; 3] = [
+        IsolationConflict::DeletingRequiredKey,
+        IsolationConflict::RequireDeletedKey,
+        IsolationConflict::ExclusiveLock,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            IsolationConflict::DeletingRequiredKey => "deletingRequiredKey",
+            IsolationConflict::RequireDeletedKey => "requireDeletedKey",
+            IsolationConflict::ExclusiveLock => "exclusiveLock",
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            IsolationConflict::DeletingRequiredKey => 0,
+            IsolationConflict::RequireDeletedKey => 1,
+            IsolationConflict::ExclusiveLock => 2,
+        }
+    }
+}
+
 impl fmt::Display for IsolationConflict {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -9,4 +9,6 @@
 pub(crate) mod rocksdb {
     pub(crate) const PROPERTY_ESTIMATE_LIVE_DATA_SIZE: &str = "rocksdb.estimate-live-data-size";
     pub(crate) const PROPERTY_ESTIMATE_NUM_KEYS: &str = "rocksdb.estimate-num-keys";
+    pub(crate) const PROPERTY_TOTAL_SST_FILES_SIZE: &str = "rocksdb.total-sst-files-size";
+    pub(crate) const PROPERTY_CUR_SIZE_ALL_MEM_TABLES: &str = "rocksdb.cur-size-all-mem-tables";
 }
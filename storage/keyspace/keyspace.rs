@@ -165,6 +165,18 @@ impl Keyspaces {
             Ok(total + count)
         })
     }
+
+    pub fn estimate_sizes(&self) -> Result<Vec<KeyspaceSizeEstimate>, KeyspaceError> {
+        self.keyspaces.iter().map(Keyspace::estimate_sizes).collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyspaceSizeEstimate {
+    pub name: &'static str,
+    pub live_data_in_bytes: u64,
+    pub sst_files_in_bytes: u64,
+    pub memtables_in_bytes: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -334,6 +346,21 @@ impl Keyspace {
             .map_err(|source| KeyspaceError::Property { name: property_name, source })
             .map(|result_opt| result_opt.unwrap_or(0))
     }
+
+    pub fn estimate_sizes(&self) -> Result<KeyspaceSizeEstimate, KeyspaceError> {
+        let property = |property_name| {
+            self.kv_storage
+                .property_int_value(property_name)
+                .map_err(|source| KeyspaceError::Property { name: property_name, source })
+                .map(|result_opt| result_opt.unwrap_or(0))
+        };
+        Ok(KeyspaceSizeEstimate {
+            name: self.name,
+            live_data_in_bytes: property(constants::rocksdb::PROPERTY_ESTIMATE_LIVE_DATA_SIZE)?,
+            sst_files_in_bytes: property(constants::rocksdb::PROPERTY_TOTAL_SST_FILES_SIZE)?,
+            memtables_in_bytes: property(constants::rocksdb::PROPERTY_CUR_SIZE_ALL_MEM_TABLES)?,
+        })
+    }
 }
 
 impl fmt::Debug for Keyspace {
@@ -5,7 +5,9 @@
  */
 
 pub(crate) use keyspace::{KEYSPACE_MAXIMUM_COUNT, Keyspace, KeyspaceCheckpointError, KeyspaceError, Keyspaces};
-pub use keyspace::{KeyspaceDeleteError, KeyspaceId, KeyspaceOpenError, KeyspaceSet, KeyspaceValidationError};
+pub use keyspace::{
+    KeyspaceDeleteError, KeyspaceId, KeyspaceOpenError, KeyspaceSet, KeyspaceSizeEstimate, KeyspaceValidationError,
+};
 use rocksdb::{DB, DBRawIterator};
 
 use crate::snapshot::pool::{PoolRecycleGuard, Poolable, SinglePool};
@@ -132,6 +132,98 @@ pub fn load_commit_data_from_with_context(
     Ok(recovered_commits)
 }
 
+/// Load commit data from the start onwards, until the statuses of the first `limit` commits are known or the end
+/// of the log is reached. Commits after those may be included, whether their status is known or not.
+pub fn load_resolved_commit_data_from(
+    start: SequenceNumber,
+    limit: usize,
+    durability_client: &impl DurabilityClient,
+) -> Result<BTreeMap<SequenceNumber, RecoveryCommitStatus>, StorageRecoveryError> {
+    use StorageRecoveryError::{DurabilityClientRead, DurabilityRecordDeserialize};
+
+    let records = durability_client.iter_from(start).map_err(|error| DurabilityClientRead { typedb_source: error })?;
+    let mut recovered_commits = BTreeMap::new();
+    let mut resolved_prefix = 0;
+    for record in records {
+        let RawRecord { sequence_number, record_type, bytes } =
+            record.map_err(|error| DurabilityClientRead { typedb_source: error })?;
+        match record_type {
+            LegacyCommitRecordV1::RECORD_TYPE => {
+                let legacy = LegacyCommitRecordV1::deserialise_from(&mut &*bytes)
+                    .map_err(|error| DurabilityRecordDeserialize { source: Arc::new(error) })?;
+                recovered_commits.insert(sequence_number, RecoveryCommitStatus::Pending(CommitRecord::from(legacy)));
+            }
+            CommitRecord::RECORD_TYPE => {
+                let commit_record = CommitRecord::deserialise_from(&mut &*bytes)
+                    .map_err(|error| DurabilityRecordDeserialize { source: Arc::new(error) })?;
+                recovered_commits.insert(sequence_number, RecoveryCommitStatus::Pending(commit_record));
+            }
+            StatusRecord::RECORD_TYPE => {
+                let StatusRecord { commit_record_sequence_number, was_committed } =
+                    StatusRecord::deserialise_from(&mut &*bytes)
+                        .map_err(|error| DurabilityRecordDeserialize { source: Arc::new(error) })?;
+                let Some(status) = recovered_commits.get_mut(&commit_record_sequence_number) else {
+                    continue;
+                };
+                *status = match std::mem::replace(status, RecoveryCommitStatus::Rejected) {
+                    RecoveryCommitStatus::Pending(record) if was_committed => RecoveryCommitStatus::Validated(record),
+                    RecoveryCommitStatus::Pending(_) => RecoveryCommitStatus::Rejected,
+                    _ => unreachable!("found second commit status for a record"),
+                };
+                resolved_prefix += recovered_commits
+                    .values()
+                    .skip(resolved_prefix)
+                    .take_while(|status| !matches!(status, RecoveryCommitStatus::Pending(_)))
+                    .count();
+                if resolved_prefix >= limit {
+                    break;
+                }
+            }
+            _not_storage_record => (),
+        }
+    }
+    Ok(recovered_commits)
+}
+
+/// Finds where a run of records read from a write-ahead log can be cut so that every commit before the cut also has
+/// its status before the cut. Returns the sequence number of the first record after the cut, which is the end of
+/// the records if all of their commits are resolved.
+pub fn resolved_records_end(records: &[RawRecord<'_>]) -> Result<SequenceNumber, StorageRecoveryError> {
+    use StorageRecoveryError::DurabilityRecordDeserialize;
+
+    let Some(last) = records.last() else {
+        return Ok(SequenceNumber::MIN);
+    };
+    let mut status_sequence_numbers = BTreeMap::new();
+    for RawRecord { sequence_number, record_type, bytes } in records {
+        match *record_type {
+            LegacyCommitRecordV1::RECORD_TYPE | CommitRecord::RECORD_TYPE => {
+                status_sequence_numbers.insert(*sequence_number, None);
+            }
+            StatusRecord::RECORD_TYPE => {
+                let status = StatusRecord::deserialise_from(&mut &**bytes)
+                    .map_err(|error| DurabilityRecordDeserialize { source: Arc::new(error) })?;
+                if let Some(status_sequence_number) =
+                    status_sequence_numbers.get_mut(&status.commit_record_sequence_number)
+                {
+                    *status_sequence_number = Some(*sequence_number);
+                }
+            }
+            _not_storage_record => (),
+        }
+    }
+
+    // Cutting before an unresolved commit may strand the status of an earlier commit after the cut, so repeat
+    let mut end = last.sequence_number.next();
+    while let Some((&commit_sequence_number, _)) = status_sequence_numbers
+        .range(..end)
+        .find(|(_, status_sequence_number)| status_sequence_number.is_none_or(|status| status >= end))
+    {
+        end = commit_sequence_number;
+    }
+    Ok(end)
+}
+
 fn format_size(bytes: usize) -> String {
     const K: usize = 1024;
     const M: usize = 1024 * K;
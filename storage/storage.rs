@@ -33,7 +33,10 @@ use keyspace::KeyspaceDeleteError;
 use lending_iterator::LendingIterator;
 use logger::{error, result::ResultExt};
 use resource::{
-    constants::{snapshot::BUFFER_VALUE_INLINE, storage::WATERMARK_WAIT_INTERVAL_MICROSECONDS},
+    constants::{
+        snapshot::BUFFER_VALUE_INLINE,
+        storage::{DEFAULT_HISTORY_RETENTION, WATERMARK_WAIT_INTERVAL_MICROSECONDS},
+    },
     profile::{CommitProfile, StorageCounters},
 };
 use tracing::trace;
@@ -41,13 +44,14 @@ use tracing::trace;
 use crate::{
     durability_client::{DurabilityClient, DurabilityClientError},
     error::{MVCCStorageError, MVCCStorageErrorKind},
+    history::CommitHistory,
     isolation_manager::{IsolationManager, ValidatedCommit},
     iterator::MVCCRangeIterator,
     key_range::KeyRange,
     key_value::{StorageKey, StorageKeyReference},
     keyspace::{
-        IteratorPool, Keyspace, KeyspaceError, KeyspaceId, KeyspaceOpenError, KeyspaceSet, Keyspaces,
-        iterator::KeyspaceRangeIterator,
+        IteratorPool, Keyspace, KeyspaceError, KeyspaceId, KeyspaceOpenError, KeyspaceSet, KeyspaceSizeEstimate,
+        Keyspaces, iterator::KeyspaceRangeIterator,
     },
     record::{CommitRecord, LegacyCommitRecordV1, StatusRecord},
     recovery::{
@@ -58,10 +62,12 @@ use crate::{
     snapshot::{
         CommittableSnapshot, ReadSnapshot, SchemaSnapshot, WriteSnapshot, snapshot_id::SnapshotId, write::Write,
     },
+    write_batches::WriteBatches,
 };
 
 pub mod durability_client;
 pub mod error;
+pub mod history;
 pub mod isolation_manager;
 pub mod iterator;
 pub mod key_range;
@@ -81,6 +87,7 @@ pub struct MVCCStorage<Durability> {
     durability_client: Durability,
     isolation_manager: IsolationManager,
     highest_committed_snapshot: AtomicU64,
+    commit_history: CommitHistory,
 }
 
 impl<Durability> MVCCStorage<Durability> {
@@ -116,6 +123,10 @@ impl<Durability> MVCCStorage<Durability> {
             keyspaces,
             isolation_manager,
             highest_committed_snapshot: AtomicU64::new(next_sequence_number.number() - 1),
+            commit_history: CommitHistory::new(
+                SequenceNumber::new(next_sequence_number.number() - 1),
+                DEFAULT_HISTORY_RETENTION,
+            ),
         })
     }
 
@@ -177,6 +188,10 @@ impl<Durability> MVCCStorage<Durability> {
             keyspaces,
             isolation_manager,
             highest_committed_snapshot: AtomicU64::new(next_sequence_number.number() - 1),
+            commit_history: CommitHistory::new(
+                SequenceNumber::new(next_sequence_number.number() - 1),
+                DEFAULT_HISTORY_RETENTION,
+            ),
         })
     }
 
@@ -304,6 +319,7 @@ impl<Durability> MVCCStorage<Durability> {
                     .applied(commit_sequence_number)
                     .map_err(|error| Internal { name: self.name.clone(), source: Arc::new(error) })?;
                 commit_profile.snapshot_isolation_manager_notified();
+                self.commit_history.record(self.snapshot_watermark());
 
                 Self::persist_commit_status(true, commit_sequence_number, &self.durability_client)
                     .map_err(|error| Durability { name: self.name.clone(), typedb_source: error })?;
@@ -332,6 +348,34 @@ impl<Durability> MVCCStorage<Durability> {
         result
     }
 
+    /// Applies a commit that was validated by another storage, such as the primary followed by a read replica.
+    /// Replicated commits must be applied or skipped in sequence number order, directly after the watermark.
+    pub fn apply_replicated_commit(
+        &self,
+        commit_sequence_number: SequenceNumber,
+        commit_record: CommitRecord,
+    ) -> Result<(), StorageCommitError> {
+        use StorageCommitError::{Internal, Keyspace};
+
+        let write_batches = WriteBatches::from_operations(commit_sequence_number, commit_record.operations());
+        self.isolation_manager.load_validated(commit_sequence_number, commit_record);
+        self.keyspaces
+            .write(write_batches)
+            .map_err(|error| Keyspace { name: self.name.clone(), source: Arc::new(error) })?;
+        self.isolation_manager
+            .applied(commit_sequence_number)
+            .map_err(|error| Internal { name: self.name.clone(), source: Arc::new(error) })?;
+        self.commit_history.record(self.snapshot_watermark());
+        self.update_highest_committed_snapshot(commit_sequence_number);
+        Ok(())
+    }
+
+    /// Passes over a commit that was rejected by the storage it is replicated from.
+    pub fn skip_replicated_commit(&self, commit_sequence_number: SequenceNumber) {
+        self.isolation_manager.load_aborted(commit_sequence_number);
+        self.update_highest_committed_snapshot(commit_sequence_number);
+    }
+
     fn set_initial_put_status(
         &self,
         snapshot: &impl CommittableSnapshot<Durability>,
@@ -506,6 +550,10 @@ impl<Durability> MVCCStorage<Durability> {
         self.isolation_manager.watermark()
     }
 
+    pub fn commit_history(&self) -> &CommitHistory {
+        &self.commit_history
+    }
+
     // --- direct access to storage, bypassing MVCC and returning raw key/value pairs ---
 
     #[cfg(debug_assertions)] // put_raw is only used in tests, this will make typedb fail to compile in release if it's used anywhere in the binary
@@ -568,6 +616,7 @@ impl<Durability> MVCCStorage<Durability> {
         Durability: DurabilityClient,
     {
         self.isolation_manager.reset();
+        self.commit_history.reset(self.snapshot_watermark());
         self.keyspaces
             .reset()
             .map_err(|err| StorageResetError::KeyspaceError { name: self.name.clone(), source: err })?;
@@ -584,6 +633,14 @@ impl<Durability> MVCCStorage<Durability> {
     pub fn estimate_key_count(&self) -> Result<u64, StorageOpenError> {
         self.keyspaces.estimate_key_count().map_err(|source| StorageOpenError::Keyspace { source })
     }
+
+    pub fn estimate_keyspace_sizes(&self) -> Result<Vec<KeyspaceSizeEstimate>, StorageOpenError> {
+        self.keyspaces.estimate_sizes().map_err(|source| StorageOpenError::Keyspace { source })
+    }
+
+    pub fn isolation_conflict_counts(&self) -> Vec<(IsolationConflict, u64)> {
+        self.isolation_manager.conflict_counts()
+    }
 }
 
 typedb_error! {
@@ -5,6 +5,8 @@
  */
 
 use pwhash::bcrypt;
+use rand::{Rng, distributions::Alphanumeric};
+use uuid::Uuid;
 
 #[derive(Clone, Debug)]
 pub struct User {
@@ -20,6 +22,7 @@ impl User {
 #[derive(Clone, Debug)]
 pub enum Credential {
     PasswordType { password_hash: PasswordHash },
+    ApiKeyType { api_key: ApiKey },
 }
 
 impl Credential {
@@ -46,3 +49,196 @@ impl PasswordHash {
         bcrypt::verify(password, self.value.as_str())
     }
 }
+
+// Sign-in bookkeeping of a user's password. Timestamps are seconds since the Unix epoch.
+#[derive(Clone, Debug, Default)]
+pub struct PasswordState {
+    // Hashes of the passwords replaced by the current one, most recent first
+    pub previous_hashes: Vec<PasswordHash>,
+    pub failed_attempts: u64,
+    // Zero unless the user is locked out after too many failed attempts
+    pub locked_until: u64,
+    pub must_change: bool,
+}
+
+impl PasswordState {
+    pub fn is_locked(&self, now: u64) -> bool {
+        self.locked_until > now
+    }
+
+    // Counts a failed sign-in attempt, locking the user until `locked_until` once `max_failed_attempts` are reached.
+    // Returns whether this attempt locked the user out.
+    pub fn record_failed_attempt(&mut self, max_failed_attempts: u64, locked_until: u64) -> bool {
+        self.failed_attempts += 1;
+        if self.failed_attempts < max_failed_attempts {
+            return false;
+        }
+        self.failed_attempts = 0;
+        self.locked_until = locked_until;
+        true
+    }
+}
+
+// A named secret for non-interactive clients, accepted in place of the user's password. The full key
+// is only known when it is generated: `tdbk_<id>_<secret>`, of which only the secret's hash is stored.
+// Timestamps are seconds since the Unix epoch.
+#[derive(Clone, Debug)]
+pub struct ApiKey {
+    pub id: String,
+    pub name: String,
+    pub secret_hash: PasswordHash,
+    pub expires_at: Option<u64>,
+    pub last_used_at: Option<u64>,
+}
+
+impl ApiKey {
+    const PREFIX: &'static str = "tdbk";
+    const SECRET_LENGTH: usize = 48;
+
+    pub fn new(
+        id: String,
+        name: String,
+        secret_hash: PasswordHash,
+        expires_at: Option<u64>,
+        last_used_at: Option<u64>,
+    ) -> Self {
+        Self { id, name, secret_hash, expires_at, last_used_at }
+    }
+
+    // Returns the new key together with the full key string to hand out to its owner
+    pub fn generate(name: String, expires_at: Option<u64>) -> (Self, String) {
+        let id = Uuid::new_v4().simple().to_string();
+        let secret: String =
+            rand::thread_rng().sample_iter(&Alphanumeric).take(Self::SECRET_LENGTH).map(char::from).collect();
+        let key = format!("{}_{id}_{secret}", Self::PREFIX);
+        (Self::new(id, name, PasswordHash::from_password(&secret), expires_at, None), key)
+    }
+
+    // Splits a full key string into its id and secret
+    pub fn parse(key: &str) -> Option<(&str, &str)> {
+        let (prefix, rest) = key.split_once('_')?;
+        if prefix != Self::PREFIX {
+            return None;
+        }
+        let (id, secret) = rest.split_once('_')?;
+        (!id.is_empty() && !secret.is_empty()).then_some((id, secret))
+    }
+
+    pub fn matches(&self, secret: &str) -> bool {
+        self.secret_hash.matches(secret)
+    }
+
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Role {
+    Reader,
+    Writer,
+    SchemaAdmin,
+}
+
+impl Role {
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Role::Reader => "reader",
+            Role::Writer => "writer",
+            Role::SchemaAdmin => "schema-admin",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "reader" => Some(Role::Reader),
+            "writer" => Some(Role::Writer),
+            "schema-admin" => Some(Role::SchemaAdmin),
+            _ => None,
+        }
+    }
+
+    // Roles are cumulative: a writer can read and a schema admin can write
+    pub fn includes(&self, other: Role) -> bool {
+        *self >= other
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Grant {
+    pub role: Role,
+    // `None` grants the role on every database, including databases created later
+    pub database: Option<String>,
+}
+
+impl Grant {
+    pub fn new(role: Role, database: Option<String>) -> Self {
+        Self { role, database }
+    }
+
+    pub fn applies_to(&self, database_name: &str) -> bool {
+        self.database.as_ref().map_or(true, |database| database == database_name)
+    }
+}
+
+// An issued authentication token. Timestamps are seconds since the Unix epoch, as in the token claims
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Session {
+    pub id: String,
+    pub username: String,
+    pub issued_at: u64,
+    pub expires_at: u64,
+}
+
+impl Session {
+    pub fn new(id: String, username: String, issued_at: u64, expires_at: u64) -> Self {
+        Self { id, username, issued_at, expires_at }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ApiKey, PasswordState};
+
+    #[test]
+    fn generated_keys_parse_into_their_id_and_secret() {
+        let (api_key, key) = ApiKey::generate("batch-job".to_owned(), None);
+        let (id, secret) = ApiKey::parse(&key).expect("A generated key should parse");
+        assert_eq!(id, api_key.id);
+        assert_eq!(secret.len(), ApiKey::SECRET_LENGTH);
+        assert!(api_key.matches(secret));
+        assert!(!api_key.matches(&secret[1..]));
+    }
+
+    #[test]
+    fn malformed_keys_do_not_parse() {
+        for key in ["", "tdbk", "tdbk_", "tdbk_id", "tdbk__secret", "tdbk_id_", "key_id_secret", "TDBK_id_secret"] {
+            assert_eq!(ApiKey::parse(key), None, "'{key}' should not parse as an API key");
+        }
+        assert_eq!(ApiKey::parse("tdbk_id_se_cret"), Some(("id", "se_cret")));
+    }
+
+    #[test]
+    fn failed_attempts_lock_the_user_out_once_the_limit_is_reached() {
+        let mut state = PasswordState::default();
+        assert!(!state.record_failed_attempt(3, 1000));
+        assert!(!state.record_failed_attempt(3, 1000));
+        assert_eq!((state.failed_attempts, state.locked_until), (2, 0));
+
+        assert!(state.record_failed_attempt(3, 1000));
+        assert_eq!((state.failed_attempts, state.locked_until), (0, 1000));
+        assert!(state.is_locked(999));
+        assert!(!state.is_locked(1000));
+    }
+
+    #[test]
+    fn keys_expire_at_their_expiry_time() {
+        let (never_expires, _) = ApiKey::generate("never".to_owned(), None);
+        assert!(!never_expires.is_expired(u64::MAX));
+
+        let (api_key, _) = ApiKey::generate("expiring".to_owned(), Some(100));
+        assert!(!api_key.is_expired(99));
+        assert!(api_key.is_expired(100));
+        assert!(api_key.is_expired(101));
+    }
+}
@@ -21,16 +21,19 @@ pub mod user_repository {
     use uuid::Uuid;
 
     use crate::{
-        concepts::{Credential, PasswordHash, User},
+        concepts::{Credential, PasswordHash, PasswordState, User},
         util::{
-            answer_util::get_string,
+            answer_util::{
+                get_optional_boolean, get_optional_integer, get_optional_integer_in_snapshot, get_optional_string,
+                get_string,
+            },
             query_util::{execute_read_pipeline, execute_write_pipeline},
         },
     };
 
     pub fn list(tx: TransactionRead<WALClient>) -> Vec<User> {
         let unexpected_error_msg = "An unexpected error occurred when acquiring the list of users";
-        let query_str = "match (user: $u, credentials: $c) isa user-credentials; $u has name $n;";
+        let query_str = "match (user: $u, credentials: $c) isa user-credentials; $c isa password; $u has name $n;";
         let query = parse_query(query_str).expect(unexpected_error_msg);
         let (tx, result) = execute_read_pipeline(tx, &query.into_structure().into_pipeline(), query_str);
         let rows = result.expect(unexpected_error_msg);
@@ -47,7 +50,7 @@ pub mod user_repository {
             "match
                 (user: $u, credentials: $p) isa user-credentials;
                 $u has name '{username}';
-                $p has hash $h;"
+                $p isa password, has hash $h;"
         );
         let query = parse_query(&query_str).expect(unexpected_error_msg);
         let (tx, result) = execute_read_pipeline(tx, &query.into_structure().into_pipeline(), &query_str);
@@ -95,6 +98,9 @@ pub mod user_repository {
                 );
                 (parse_query(query_string.as_str()).expect(unexpected_error_msg), query_string)
             }
+            Credential::ApiKeyType { .. } => {
+                return (Err(SystemDBError::UnsupportedCredential {}), Arc::new(snapshot));
+            }
         };
         let (_, snapshot) = execute_write_pipeline(
             snapshot,
@@ -123,7 +129,7 @@ pub mod user_repository {
         match credential {
             Some(Credential::PasswordType { password_hash: PasswordHash { value: hash } }) => {
                 let query_string = format!(
-                    "match (user: $u, credentials: $p) isa user-credentials; $u has name '{username}'; $p has hash $h; delete has $h of $p; insert $p has hash '{password_hash}';",
+                    "match (user: $u, credentials: $p) isa user-credentials; $u has name '{username}'; $p isa password, has hash $h; delete has $h of $p; insert $p has hash '{password_hash}';",
                     username = username,
                     password_hash = hash
                 );
@@ -139,6 +145,7 @@ pub mod user_repository {
                 );
                 (Ok(()), snapshot)
             }
+            Some(Credential::ApiKeyType { .. }) => (Err(SystemDBError::UnsupportedCredential {}), Arc::new(snapshot)),
             None => (Err(SystemDBError::EmptyUpdate {}), Arc::new(snapshot)),
         }
     }
@@ -174,6 +181,206 @@ pub mod user_repository {
         (Ok(()), snapshot)
     }
 
+    // Previous hashes are stored in a single attribute, most recent first. Hashes never contain spaces.
+    const PREVIOUS_HASHES_SEPARATOR: char = ' ';
+
+    pub fn get_password_state(
+        tx: TransactionRead<WALClient>,
+        username: &str,
+    ) -> Result<Option<PasswordState>, SystemDBError> {
+        if !is_valid_typeql_value(username) {
+            return Err(SystemDBError::IllegalQueryInput {});
+        }
+        let unexpected_error_msg = "An unexpected error occurred when attempting to retrieve a user's password state";
+        let query_str = format!(
+            "match
+                (user: $u, credentials: $p) isa user-credentials;
+                $u has name '{username}';
+                $p isa password;
+                try {{ $p has previous-hashes $h; }};
+                try {{ $p has failed-attempts $f; }};
+                try {{ $p has locked-until $l; }};
+                try {{ $p has must-change-password $m; }};"
+        );
+        let query = parse_query(&query_str).expect(unexpected_error_msg);
+        let (tx, result) = execute_read_pipeline(tx, &query.into_structure().into_pipeline(), &query_str);
+        let mut rows: Vec<HashMap<String, VariableValue>> = match result {
+            Ok(rows) => rows,
+            Err(_) => return Err(SystemDBError::QueryFailed {}),
+        };
+        let Some(row) = rows.pop() else {
+            return Ok(None);
+        };
+        let previous_hashes = get_optional_string(&tx, &row, "h")
+            .map(|hashes| {
+                hashes
+                    .split(PREVIOUS_HASHES_SEPARATOR)
+                    .filter(|hash| !hash.is_empty())
+                    .map(|hash| PasswordHash::new(hash.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(Some(PasswordState {
+            previous_hashes,
+            failed_attempts: get_optional_integer(&tx, &row, "f").unwrap_or(0) as u64,
+            locked_until: get_optional_integer(&tx, &row, "l").unwrap_or(0) as u64,
+            must_change: get_optional_boolean(&tx, &row, "m").unwrap_or(false),
+        }))
+    }
+
+    pub fn update_password_state(
+        snapshot: WriteSnapshot<WALClient>,
+        type_manager: &TypeManager,
+        thing_manager: Arc<ThingManager>,
+        function_manager: &FunctionManager,
+        query_manager: &QueryManager,
+        username: &str,
+        state: &PasswordState,
+    ) -> (Result<(), SystemDBError>, Arc<WriteSnapshot<WALClient>>) {
+        if !is_valid_typeql_value(username) {
+            return (Err(SystemDBError::IllegalQueryInput {}), Arc::new(snapshot));
+        }
+        let unexpected_error_msg = "An unexpected error occurred when attempting to update a user's password state";
+        let previous_hashes = state
+            .previous_hashes
+            .iter()
+            .map(|hash| hash.value.as_str())
+            .collect::<Vec<_>>()
+            .join(&PREVIOUS_HASHES_SEPARATOR.to_string());
+        let query_string = format!(
+            "match
+                (user: $u, credentials: $p) isa user-credentials;
+                $u has name '{username}';
+                $p isa password;
+            update
+                $p has previous-hashes '{previous_hashes}';
+                $p has failed-attempts {failed_attempts};
+                $p has locked-until {locked_until};
+                $p has must-change-password {must_change};",
+            failed_attempts = state.failed_attempts,
+            locked_until = state.locked_until,
+            must_change = state.must_change,
+        );
+        let query = parse_query(&query_string).expect(unexpected_error_msg);
+        let (result, snapshot) = execute_write_pipeline(
+            snapshot,
+            type_manager,
+            thing_manager,
+            function_manager,
+            query_manager,
+            &query.into_structure().into_pipeline(),
+            &query_string,
+        );
+        (result.map(|_| ()).map_err(|_| SystemDBError::QueryFailed {}), snapshot)
+    }
+
+    // Reads and updates the failed attempts in the same write snapshot, so that concurrent failed attempts conflict
+    // on commit instead of overwriting each other's count. Returns whether this attempt locked the user out.
+    pub fn record_failed_attempt(
+        snapshot: WriteSnapshot<WALClient>,
+        type_manager: &TypeManager,
+        thing_manager: Arc<ThingManager>,
+        function_manager: &FunctionManager,
+        query_manager: &QueryManager,
+        username: &str,
+        max_failed_attempts: u64,
+        locked_until: u64,
+    ) -> (Result<bool, SystemDBError>, Arc<WriteSnapshot<WALClient>>) {
+        if !is_valid_typeql_value(username) {
+            return (Err(SystemDBError::IllegalQueryInput {}), Arc::new(snapshot));
+        }
+        let unexpected_error_msg = "An unexpected error occurred when attempting to record a failed sign-in attempt";
+        let query_string = format!(
+            "match
+                (user: $u, credentials: $p) isa user-credentials;
+                $u has name '{username}';
+                $p isa password;
+                try {{ $p has failed-attempts $f; }};
+                try {{ $p has locked-until $l; }};"
+        );
+        let query = parse_query(&query_string).expect(unexpected_error_msg);
+        let (result, snapshot) = execute_write_pipeline(
+            snapshot,
+            type_manager,
+            thing_manager.clone(),
+            function_manager,
+            query_manager,
+            &query.into_structure().into_pipeline(),
+            &query_string,
+        );
+        let mut rows = match result {
+            Ok(rows) => rows,
+            Err(_) => return (Err(SystemDBError::QueryFailed {}), snapshot),
+        };
+        let Some(row) = rows.pop() else {
+            return (Ok(false), snapshot);
+        };
+        let mut state = PasswordState {
+            failed_attempts: get_optional_integer_in_snapshot(&*snapshot, &thing_manager, &row, "f").unwrap_or(0)
+                as u64,
+            locked_until: get_optional_integer_in_snapshot(&*snapshot, &thing_manager, &row, "l").unwrap_or(0) as u64,
+            ..PasswordState::default()
+        };
+        let is_locked_out = state.record_failed_attempt(max_failed_attempts, locked_until);
+
+        let snapshot = Arc::try_unwrap(snapshot).unwrap_or_else(|_| panic!("Expected unique ownership of snapshot"));
+        let query_string = format!(
+            "match
+                (user: $u, credentials: $p) isa user-credentials;
+                $u has name '{username}';
+                $p isa password;
+            update
+                $p has failed-attempts {failed_attempts};
+                $p has locked-until {locked_until};",
+            failed_attempts = state.failed_attempts,
+            locked_until = state.locked_until,
+        );
+        let query = parse_query(&query_string).expect(unexpected_error_msg);
+        let (result, snapshot) = execute_write_pipeline(
+            snapshot,
+            type_manager,
+            thing_manager,
+            function_manager,
+            query_manager,
+            &query.into_structure().into_pipeline(),
+            &query_string,
+        );
+        (result.map(|_| is_locked_out).map_err(|_| SystemDBError::QueryFailed {}), snapshot)
+    }
+
+    pub fn reset_failed_attempts(
+        snapshot: WriteSnapshot<WALClient>,
+        type_manager: &TypeManager,
+        thing_manager: Arc<ThingManager>,
+        function_manager: &FunctionManager,
+        query_manager: &QueryManager,
+        username: &str,
+    ) -> (Result<(), SystemDBError>, Arc<WriteSnapshot<WALClient>>) {
+        if !is_valid_typeql_value(username) {
+            return (Err(SystemDBError::IllegalQueryInput {}), Arc::new(snapshot));
+        }
+        let unexpected_error_msg = "An unexpected error occurred when attempting to reset failed sign-in attempts";
+        let query_string = format!(
+            "match
+                (user: $u, credentials: $p) isa user-credentials;
+                $u has name '{username}';
+                $p isa password;
+            update
+                $p has failed-attempts 0;"
+        );
+        let query = parse_query(&query_string).expect(unexpected_error_msg);
+        let (result, snapshot) = execute_write_pipeline(
+            snapshot,
+            type_manager,
+            thing_manager,
+            function_manager,
+            query_manager,
+            &query.into_structure().into_pipeline(),
+            &query_string,
+        );
+        (result.map(|_| ()).map_err(|_| SystemDBError::QueryFailed {}), snapshot)
+    }
+
     pub fn is_valid_typeql_value(value: &str) -> bool {
         is_valid_label(value)
     }
@@ -183,6 +390,445 @@ pub mod user_repository {
             EmptyUpdate(1, "There is nothing to update"),
             IllegalQueryInput(2, "The specified input contains one or more illegal character(s)"),
             QueryFailed(3, "System database query could not be executed. The system database may not be fully initialised yet."),
+            UnsupportedCredential(4, "The credential type is not supported by this operation."),
         }
     }
 }
+
+pub mod grant_repository {
+    use std::{collections::HashMap, sync::Arc};
+
+    use answer::variable_value::VariableValue;
+    use concept::{thing::thing_manager::ThingManager, type_::type_manager::TypeManager};
+    use database::transaction::TransactionRead;
+    use function::function_manager::FunctionManager;
+    use query::query_manager::QueryManager;
+    use storage::{durability_client::WALClient, snapshot::WriteSnapshot};
+    use typeql::parse_query;
+
+    use crate::{
+        concepts::{Grant, Role},
+        repositories::user_repository::{SystemDBError, is_valid_typeql_value},
+        util::{
+            answer_util::{get_optional_string, get_string},
+            query_util::{execute_read_pipeline, execute_write_pipeline},
+        },
+    };
+
+    pub fn list(tx: TransactionRead<WALClient>, username: &str) -> Result<Vec<Grant>, SystemDBError> {
+        if !is_valid_typeql_value(username) {
+            return Err(SystemDBError::IllegalQueryInput {});
+        }
+        let unexpected_error_msg = "An unexpected error occurred when attempting to retrieve user grants";
+        let query_str = format!(
+            "match
+                $g isa user-grant, links (user: $u), has role-name $r;
+                $u has name '{username}';
+                try {{ $g has database-name $d; }};"
+        );
+        let query = parse_query(&query_str).expect(unexpected_error_msg);
+        let (tx, result) = execute_read_pipeline(tx, &query.into_structure().into_pipeline(), &query_str);
+        let rows: Vec<HashMap<String, VariableValue>> = match result {
+            Ok(rows) => rows,
+            Err(_) => return Err(SystemDBError::QueryFailed {}),
+        };
+        let grants = rows
+            .iter()
+            .filter_map(|row| {
+                let role = Role::from_name(&get_string(&tx, row, "r"))?;
+                Some(Grant::new(role, get_optional_string(&tx, row, "d")))
+            })
+            .collect();
+        Ok(grants)
+    }
+
+    pub fn create(
+        snapshot: WriteSnapshot<WALClient>,
+        type_manager: &TypeManager,
+        thing_manager: Arc<ThingManager>,
+        function_manager: &FunctionManager,
+        query_manager: &QueryManager,
+        username: &str,
+        grant: &Grant,
+    ) -> (Result<(), SystemDBError>, Arc<WriteSnapshot<WALClient>>) {
+        if !is_valid_typeql_value(username) || !grant.database.as_deref().map_or(true, is_valid_typeql_value) {
+            return (Err(SystemDBError::IllegalQueryInput {}), Arc::new(snapshot));
+        }
+        let unexpected_error_msg = "An unexpected error occurred when attempting to create a user grant";
+        let database_constraint = match &grant.database {
+            Some(database) => format!(", has database-name '{database}'"),
+            None => String::new(),
+        };
+        let query_string = format!(
+            "match $u isa user, has name '{username}';
+            insert (user: $u) isa user-grant, has role-name '{role}'{database_constraint};",
+            role = grant.role.name(),
+        );
+        let query = parse_query(&query_string).expect(unexpected_error_msg);
+        let (result, snapshot) = execute_write_pipeline(
+            snapshot,
+            type_manager,
+            thing_manager,
+            function_manager,
+            query_manager,
+            &query.into_structure().into_pipeline(),
+            &query_string,
+        );
+        (result.map(|_| ()).map_err(|_| SystemDBError::QueryFailed {}), snapshot)
+    }
+
+    pub fn delete(
+        snapshot: WriteSnapshot<WALClient>,
+        type_manager: &TypeManager,
+        thing_manager: Arc<ThingManager>,
+        function_manager: &FunctionManager,
+        query_manager: &QueryManager,
+        username: &str,
+        grant: &Grant,
+    ) -> (Result<(), SystemDBError>, Arc<WriteSnapshot<WALClient>>) {
+        if !is_valid_typeql_value(username) || !grant.database.as_deref().map_or(true, is_valid_typeql_value) {
+            return (Err(SystemDBError::IllegalQueryInput {}), Arc::new(snapshot));
+        }
+        let unexpected_error_msg = "An unexpected error occurred when attempting to delete a user grant";
+        let database_constraint = match &grant.database {
+            Some(database) => format!("$g has database-name '{database}';"),
+            None => "not { $g has database-name $d; };".to_string(),
+        };
+        let query_string = format!(
+            "match
+                $g isa user-grant, links (user: $u), has role-name '{role}';
+                $u has name '{username}';
+                {database_constraint}
+            delete $g;",
+            role = grant.role.name(),
+        );
+        let query = parse_query(&query_string).expect(unexpected_error_msg);
+        let (result, snapshot) = execute_write_pipeline(
+            snapshot,
+            type_manager,
+            thing_manager,
+            function_manager,
+            query_manager,
+            &query.into_structure().into_pipeline(),
+            &query_string,
+        );
+        (result.map(|_| ()).map_err(|_| SystemDBError::QueryFailed {}), snapshot)
+    }
+}
+
+pub mod session_repository {
+    use std::{collections::HashMap, sync::Arc};
+
+    use answer::variable_value::VariableValue;
+    use concept::{thing::thing_manager::ThingManager, type_::type_manager::TypeManager};
+    use database::transaction::TransactionRead;
+    use function::function_manager::FunctionManager;
+    use query::query_manager::QueryManager;
+    use storage::{durability_client::WALClient, snapshot::WriteSnapshot};
+    use typeql::parse_query;
+    use uuid::Uuid;
+
+    use crate::{
+        concepts::Session,
+        repositories::user_repository::{SystemDBError, is_valid_typeql_value},
+        util::{
+            answer_util::{get_integer, get_string},
+            query_util::{execute_read_pipeline, execute_write_pipeline},
+        },
+    };
+
+    pub fn list(tx: TransactionRead<WALClient>) -> Result<Vec<Session>, SystemDBError> {
+        let unexpected_error_msg = "An unexpected error occurred when attempting to retrieve user sessions";
+        let query_str = "match
+                $s isa user-session, links (user: $u), has session-id $i, has issued-at $ia, has expires-at $ea;
+                $u has name $n;";
+        let query = parse_query(query_str).expect(unexpected_error_msg);
+        let (tx, result) = execute_read_pipeline(tx, &query.into_structure().into_pipeline(), query_str);
+        let rows: Vec<HashMap<String, VariableValue>> = match result {
+            Ok(rows) => rows,
+            Err(_) => return Err(SystemDBError::QueryFailed {}),
+        };
+        let sessions = rows
+            .iter()
+            .map(|row| {
+                Session::new(
+                    get_string(&tx, row, "i"),
+                    get_string(&tx, row, "n"),
+                    get_integer(&tx, row, "ia") as u64,
+                    get_integer(&tx, row, "ea") as u64,
+                )
+            })
+            .collect();
+        Ok(sessions)
+    }
+
+    pub fn create(
+        snapshot: WriteSnapshot<WALClient>,
+        type_manager: &TypeManager,
+        thing_manager: Arc<ThingManager>,
+        function_manager: &FunctionManager,
+        query_manager: &QueryManager,
+        session: &Session,
+    ) -> (Result<(), SystemDBError>, Arc<WriteSnapshot<WALClient>>) {
+        if !is_valid_typeql_value(&session.username) || !is_valid_session_id(&session.id) {
+            return (Err(SystemDBError::IllegalQueryInput {}), Arc::new(snapshot));
+        }
+        let unexpected_error_msg = "An unexpected error occurred when attempting to create a user session";
+        let query_string = format!(
+            "match $u isa user, has name '{username}';
+            insert (user: $u) isa user-session,
+                has session-id '{id}', has issued-at {issued_at}, has expires-at {expires_at};",
+            username = session.username,
+            id = session.id,
+            issued_at = session.issued_at,
+            expires_at = session.expires_at,
+        );
+        let query = parse_query(&query_string).expect(unexpected_error_msg);
+        let (result, snapshot) = execute_write_pipeline(
+            snapshot,
+            type_manager,
+            thing_manager,
+            function_manager,
+            query_manager,
+            &query.into_structure().into_pipeline(),
+            &query_string,
+        );
+        (result.map(|_| ()).map_err(|_| SystemDBError::QueryFailed {}), snapshot)
+    }
+
+    // Deletes a single session of the user, or all of their sessions if `session_id` is `None`
+    pub fn delete(
+        snapshot: WriteSnapshot<WALClient>,
+        type_manager: &TypeManager,
+        thing_manager: Arc<ThingManager>,
+        function_manager: &FunctionManager,
+        query_manager: &QueryManager,
+        username: &str,
+        session_id: Option<&str>,
+    ) -> (Result<(), SystemDBError>, Arc<WriteSnapshot<WALClient>>) {
+        if !is_valid_typeql_value(username) || !session_id.map_or(true, is_valid_session_id) {
+            return (Err(SystemDBError::IllegalQueryInput {}), Arc::new(snapshot));
+        }
+        let unexpected_error_msg = "An unexpected error occurred when attempting to delete user sessions";
+        let session_constraint = match session_id {
+            Some(session_id) => format!("$s has session-id '{session_id}';"),
+            None => String::new(),
+        };
+        let query_string = format!(
+            "match
+                $s isa user-session, links (user: $u);
+                $u has name '{username}';
+                {session_constraint}
+            delete $s;"
+        );
+        let query = parse_query(&query_string).expect(unexpected_error_msg);
+        let (result, snapshot) = execute_write_pipeline(
+            snapshot,
+            type_manager,
+            thing_manager,
+            function_manager,
+            query_manager,
+            &query.into_structure().into_pipeline(),
+            &query_string,
+        );
+        (result.map(|_| ()).map_err(|_| SystemDBError::QueryFailed {}), snapshot)
+    }
+
+    pub fn delete_expired(
+        snapshot: WriteSnapshot<WALClient>,
+        type_manager: &TypeManager,
+        thing_manager: Arc<ThingManager>,
+        function_manager: &FunctionManager,
+        query_manager: &QueryManager,
+        now: u64,
+    ) -> (Result<(), SystemDBError>, Arc<WriteSnapshot<WALClient>>) {
+        let unexpected_error_msg = "An unexpected error occurred when attempting to delete expired user sessions";
+        let query_string = format!(
+            "match
+                $s isa user-session, has expires-at $e;
+                $e <= {now};
+            delete $s;"
+        );
+        let query = parse_query(&query_string).expect(unexpected_error_msg);
+        let (result, snapshot) = execute_write_pipeline(
+            snapshot,
+            type_manager,
+            thing_manager,
+            function_manager,
+            query_manager,
+            &query.into_structure().into_pipeline(),
+            &query_string,
+        );
+        (result.map(|_| ()).map_err(|_| SystemDBError::QueryFailed {}), snapshot)
+    }
+
+    pub fn is_valid_session_id(session_id: &str) -> bool {
+        Uuid::parse_str(session_id).is_ok()
+    }
+}
+
+pub mod api_key_repository {
+    use std::{collections::HashMap, sync::Arc};
+
+    use answer::variable_value::VariableValue;
+    use concept::{thing::thing_manager::ThingManager, type_::type_manager::TypeManager};
+    use database::transaction::TransactionRead;
+    use function::function_manager::FunctionManager;
+    use query::query_manager::QueryManager;
+    use storage::{durability_client::WALClient, snapshot::WriteSnapshot};
+    use typeql::parse_query;
+
+    use crate::{
+        concepts::{ApiKey, PasswordHash},
+        repositories::user_repository::{SystemDBError, is_valid_typeql_value},
+        util::{
+            answer_util::{get_optional_integer, get_string},
+            query_util::{execute_read_pipeline, execute_write_pipeline},
+        },
+    };
+
+    pub fn list(tx: TransactionRead<WALClient>, username: &str) -> Result<Vec<ApiKey>, SystemDBError> {
+        if !is_valid_typeql_value(username) {
+            return Err(SystemDBError::IllegalQueryInput {});
+        }
+        let unexpected_error_msg = "An unexpected error occurred when attempting to retrieve user API keys";
+        let query_str = format!(
+            "match
+                (user: $u, credentials: $k) isa user-credentials;
+                $u has name '{username}';
+                $k isa api-key, has uuid $i, has name $n, has hash $h;
+                try {{ $k has expires-at $e; }};
+                try {{ $k has last-used-at $l; }};"
+        );
+        let query = parse_query(&query_str).expect(unexpected_error_msg);
+        let (tx, result) = execute_read_pipeline(tx, &query.into_structure().into_pipeline(), &query_str);
+        let rows: Vec<HashMap<String, VariableValue>> = match result {
+            Ok(rows) => rows,
+            Err(_) => return Err(SystemDBError::QueryFailed {}),
+        };
+        let api_keys = rows
+            .iter()
+            .map(|row| {
+                ApiKey::new(
+                    get_string(&tx, row, "i"),
+                    get_string(&tx, row, "n"),
+                    PasswordHash::new(get_string(&tx, row, "h")),
+                    get_optional_integer(&tx, row, "e").map(|expires_at| expires_at as u64),
+                    get_optional_integer(&tx, row, "l").map(|last_used_at| last_used_at as u64),
+                )
+            })
+            .collect();
+        Ok(api_keys)
+    }
+
+    pub fn create(
+        snapshot: WriteSnapshot<WALClient>,
+        type_manager: &TypeManager,
+        thing_manager: Arc<ThingManager>,
+        function_manager: &FunctionManager,
+        query_manager: &QueryManager,
+        username: &str,
+        api_key: &ApiKey,
+    ) -> (Result<(), SystemDBError>, Arc<WriteSnapshot<WALClient>>) {
+        if !is_valid_typeql_value(username) || !is_valid_typeql_value(&api_key.name) {
+            return (Err(SystemDBError::IllegalQueryInput {}), Arc::new(snapshot));
+        }
+        let unexpected_error_msg = "An unexpected error occurred when attempting to create a user API key";
+        let expiry_constraint = match api_key.expires_at {
+            Some(expires_at) => format!(", has expires-at {expires_at}"),
+            None => String::new(),
+        };
+        let query_string = format!(
+            "match $u isa user, has name '{username}';
+            insert $k isa api-key, has uuid '{id}', has name '{name}', has hash '{hash}'{expiry_constraint};
+                (user: $u, credentials: $k) isa user-credentials;",
+            id = api_key.id,
+            name = api_key.name,
+            hash = api_key.secret_hash.value,
+        );
+        let query = parse_query(&query_string).expect(unexpected_error_msg);
+        let (result, snapshot) = execute_write_pipeline(
+            snapshot,
+            type_manager,
+            thing_manager,
+            function_manager,
+            query_manager,
+            &query.into_structure().into_pipeline(),
+            &query_string,
+        );
+        (result.map(|_| ()).map_err(|_| SystemDBError::QueryFailed {}), snapshot)
+    }
+
+    // Deletes the API key with the given name, or all API keys of the user if `name` is `None`
+    pub fn delete(
+        snapshot: WriteSnapshot<WALClient>,
+        type_manager: &TypeManager,
+        thing_manager: Arc<ThingManager>,
+        function_manager: &FunctionManager,
+        query_manager: &QueryManager,
+        username: &str,
+        name: Option<&str>,
+    ) -> (Result<(), SystemDBError>, Arc<WriteSnapshot<WALClient>>) {
+        if !is_valid_typeql_value(username) || !name.map_or(true, is_valid_typeql_value) {
+            return (Err(SystemDBError::IllegalQueryInput {}), Arc::new(snapshot));
+        }
+        let unexpected_error_msg = "An unexpected error occurred when attempting to delete user API keys";
+        let name_constraint = match name {
+            Some(name) => format!("$k has name '{name}';"),
+            None => String::new(),
+        };
+        let query_string = format!(
+            "match
+                $uc isa user-credentials, links (user: $u, credentials: $k);
+                $u has name '{username}';
+                $k isa api-key;
+                {name_constraint}
+            delete $k; $uc;"
+        );
+        let query = parse_query(&query_string).expect(unexpected_error_msg);
+        let (result, snapshot) = execute_write_pipeline(
+            snapshot,
+            type_manager,
+            thing_manager,
+            function_manager,
+            query_manager,
+            &query.into_structure().into_pipeline(),
+            &query_string,
+        );
+        (result.map(|_| ()).map_err(|_| SystemDBError::QueryFailed {}), snapshot)
+    }
+
+    pub fn update_last_used(
+        snapshot: WriteSnapshot<WALClient>,
+        type_manager: &TypeManager,
+        thing_manager: Arc<ThingManager>,
+        function_manager: &FunctionManager,
+        query_manager: &QueryManager,
+        id: &str,
+        last_used_at: u64,
+    ) -> (Result<(), SystemDBError>, Arc<WriteSnapshot<WALClient>>) {
+        if !is_valid_api_key_id(id) {
+            return (Err(SystemDBError::IllegalQueryInput {}), Arc::new(snapshot));
+        }
+        let unexpected_error_msg = "An unexpected error occurred when attempting to update a user API key";
+        let query_string = format!(
+            "match $k isa api-key, has uuid '{id}';
+            update $k has last-used-at {last_used_at};"
+        );
+        let query = parse_query(&query_string).expect(unexpected_error_msg);
+        let (result, snapshot) = execute_write_pipeline(
+            snapshot,
+            type_manager,
+            thing_manager,
+            function_manager,
+            query_manager,
+            &query.into_structure().into_pipeline(),
+            &query_string,
+        );
+        (result.map(|_| ()).map_err(|_| SystemDBError::QueryFailed {}), snapshot)
+    }
+
+    pub fn is_valid_api_key_id(id: &str) -> bool {
+        !id.is_empty() && id.chars().all(|c| c.is_ascii_hexdigit())
+    }
+}
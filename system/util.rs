@@ -237,11 +237,12 @@ pub mod answer_util {
 
     use answer::variable_value::VariableValue;
     use compiler::VariablePosition;
+    use concept::thing::thing_manager::ThingManager;
     use database::transaction::TransactionRead;
     use executor::batch::Batch;
     use lending_iterator::LendingIterator;
     use resource::profile::StorageCounters;
-    use storage::durability_client::WALClient;
+    use storage::{durability_client::WALClient, snapshot::ReadableSnapshot};
 
     pub fn collect_answer(
         batch: Batch,
@@ -270,4 +271,67 @@ pub mod answer_util {
             .to_string();
         val
     }
+
+    pub fn get_integer(tx: &TransactionRead<WALClient>, row: &HashMap<String, VariableValue>, var: &str) -> i64 {
+        let attr = row.get(var).unwrap().as_thing().as_attribute();
+        attr.get_value(&*tx.snapshot, &tx.thing_manager, StorageCounters::DISABLED).unwrap().unwrap_integer()
+    }
+
+    pub fn get_boolean(tx: &TransactionRead<WALClient>, row: &HashMap<String, VariableValue>, var: &str) -> bool {
+        let attr = row.get(var).unwrap().as_thing().as_attribute();
+        attr.get_value(&*tx.snapshot, &tx.thing_manager, StorageCounters::DISABLED).unwrap().unwrap_boolean()
+    }
+
+    pub fn get_optional_integer(
+        tx: &TransactionRead<WALClient>,
+        row: &HashMap<String, VariableValue>,
+        var: &str,
+    ) -> Option<i64> {
+        match row.get(var) {
+            None | Some(VariableValue::None) => None,
+            Some(_) => Some(get_integer(tx, row, var)),
+        }
+    }
+
+    // Reads an optional integer of a row returned by a write pipeline, through the write snapshot it was read from
+    pub fn get_optional_integer_in_snapshot(
+        snapshot: &impl ReadableSnapshot,
+        thing_manager: &ThingManager,
+        row: &HashMap<String, VariableValue>,
+        var: &str,
+    ) -> Option<i64> {
+        match row.get(var) {
+            None | Some(VariableValue::None) => None,
+            Some(value) => Some(
+                value
+                    .as_thing()
+                    .as_attribute()
+                    .get_value(snapshot, thing_manager, StorageCounters::DISABLED)
+                    .unwrap()
+                    .unwrap_integer(),
+            ),
+        }
+    }
+
+    pub fn get_optional_boolean(
+        tx: &TransactionRead<WALClient>,
+        row: &HashMap<String, VariableValue>,
+        var: &str,
+    ) -> Option<bool> {
+        match row.get(var) {
+            None | Some(VariableValue::None) => None,
+            Some(_) => Some(get_boolean(tx, row, var)),
+        }
+    }
+
+    pub fn get_optional_string(
+        tx: &TransactionRead<WALClient>,
+        row: &HashMap<String, VariableValue>,
+        var: &str,
+    ) -> Option<String> {
+        match row.get(var) {
+            None | Some(VariableValue::None) => None,
+            Some(_) => Some(get_string(tx, row, var)),
+        }
+    }
 }
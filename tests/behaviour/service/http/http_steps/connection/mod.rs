@@ -48,7 +48,10 @@ pub(crate) async fn start_typedb()
             .admin_enabled(false)
             .data_directory(server_dir.as_ref())
             .development_mode(true)
-            .authentication(AuthenticationConfig { token_expiration: TEST_TOKEN_EXPIRATION })
+            .authentication(AuthenticationConfig {
+                token_expiration: TEST_TOKEN_EXPIRATION,
+                ..AuthenticationConfig::default()
+            })
             .build()
             .unwrap();
 
@@ -19,6 +19,7 @@ typedb_error! {
         UserAlreadyExist(2, "User already exists."),
         IncompleteUserDetail(3, "Incomplete user detail."),
         Unexpected(4, "An unexpected error has occurred in the process of creating a new user."),
+        PasswordRejected(5, "The password does not satisfy the password policy.", typedb_source: PasswordPolicyError),
     }
 }
 
@@ -27,6 +28,7 @@ typedb_error! {
         UserDetailNotProvided(1, "User detail not provided."),
         IllegalUsername(2, "Invalid credential supplied,"),
         Unexpected(3, "An unexpected error has occurred in the process of updating a user."),
+        PasswordRejected(4, "The password does not satisfy the password policy.", typedb_source: PasswordPolicyError),
     }
 }
 
@@ -38,3 +40,49 @@ typedb_error! {
         Unexpected(4, "An unexpected error has occurred in the process of deleting a user."),
     }
 }
+
+typedb_error! {
+    pub UserGrantError(component = "User grant", prefix = "USR") {
+        IllegalInput(1, "Invalid username or database name supplied."),
+        UserNotFound(2, "User not found."),
+        GrantNotFound(3, "The user does not hold the specified grant."),
+        Unexpected(4, "An unexpected error has occurred in the process of managing user grants."),
+    }
+}
+
+typedb_error! {
+    pub UserSessionError(component = "User session", prefix = "USS") {
+        IllegalInput(1, "Invalid username or session id supplied."),
+        SessionNotFound(2, "The user has no active session with the specified id."),
+        Unexpected(3, "An unexpected error has occurred in the process of managing user sessions."),
+    }
+}
+
+typedb_error! {
+    pub UserApiKeyError(component = "User API key", prefix = "USK") {
+        IllegalInput(1, "Invalid username or API key name supplied."),
+        UserNotFound(2, "User not found."),
+        ApiKeyAlreadyExists(3, "The user already has an API key named '{name}'.", name: String),
+        ApiKeyNotFound(4, "The user has no API key named '{name}'.", name: String),
+        Unexpected(5, "An unexpected error has occurred in the process of managing user API keys."),
+    }
+}
+
+typedb_error! {
+    pub PasswordPolicyError(component = "Password policy", prefix = "USP") {
+        TooShort(1, "The password must be at least {min_length} characters long.", min_length: usize),
+        MissingLowercase(2, "The password must contain a lowercase letter."),
+        MissingUppercase(3, "The password must contain an uppercase letter."),
+        MissingDigit(4, "The password must contain a digit."),
+        MissingSymbol(5, "The password must contain a symbol."),
+        RecentlyUsed(6, "The password must differ from the user's last {history_size} passwords.", history_size: usize),
+    }
+}
+
+typedb_error! {
+    pub UserPasswordStateError(component = "User password state", prefix = "USW") {
+        IllegalInput(1, "Invalid username supplied."),
+        UserNotFound(2, "User not found."),
+        Unexpected(3, "An unexpected error has occurred in the process of updating a user's password state."),
+    }
+}
@@ -5,5 +5,6 @@
  */
 
 pub mod errors;
+pub mod password_policy;
 pub mod permission_manager;
 pub mod user_manager;
@@ -0,0 +1,114 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use system::concepts::PasswordHash;
+
+use crate::errors::PasswordPolicyError;
+
+// Requirements on the passwords users set. The default policy accepts any password.
+#[derive(Clone, Debug, Default)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub require_lowercase: bool,
+    pub require_uppercase: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+    // Number of the user's most recent passwords, including the current one, that cannot be set again
+    pub history_size: usize,
+}
+
+impl PasswordPolicy {
+    pub fn validate(&self, password: &str) -> Result<(), PasswordPolicyError> {
+        if password.chars().count() < self.min_length {
+            return Err(PasswordPolicyError::TooShort { min_length: self.min_length });
+        }
+        if self.require_lowercase && !password.chars().any(char::is_lowercase) {
+            return Err(PasswordPolicyError::MissingLowercase {});
+        }
+        if self.require_uppercase && !password.chars().any(char::is_uppercase) {
+            return Err(PasswordPolicyError::MissingUppercase {});
+        }
+        if self.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            return Err(PasswordPolicyError::MissingDigit {});
+        }
+        if self.require_symbol && !password.chars().any(|c| !c.is_alphanumeric() && !c.is_whitespace()) {
+            return Err(PasswordPolicyError::MissingSymbol {});
+        }
+        Ok(())
+    }
+
+    // `recent_hashes` are the hashes of the user's passwords, most recent first
+    pub fn validate_reuse<'a>(
+        &self,
+        password: &str,
+        recent_hashes: impl IntoIterator<Item = &'a PasswordHash>,
+    ) -> Result<(), PasswordPolicyError> {
+        if recent_hashes.into_iter().take(self.history_size).any(|hash| hash.matches(password)) {
+            return Err(PasswordPolicyError::RecentlyUsed { history_size: self.history_size });
+        }
+        Ok(())
+    }
+
+    // Number of replaced hashes to keep so that reuse can be checked
+    pub fn retained_history(&self) -> usize {
+        self.history_size.saturating_sub(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use system::concepts::PasswordHash;
+
+    use super::PasswordPolicy;
+    use crate::errors::PasswordPolicyError;
+
+    #[test]
+    fn default_policy_accepts_any_password() {
+        let policy = PasswordPolicy::default();
+        assert!(policy.validate("").is_ok());
+        assert!(policy.validate_reuse("password", &[PasswordHash::from_password("password")]).is_ok());
+        assert_eq!(policy.retained_history(), 0);
+    }
+
+    #[test]
+    fn passwords_must_satisfy_every_requirement() {
+        let policy = PasswordPolicy {
+            min_length: 8,
+            require_lowercase: true,
+            require_uppercase: true,
+            require_digit: true,
+            require_symbol: true,
+            history_size: 0,
+        };
+        assert!(matches!(policy.validate("aB1!"), Err(PasswordPolicyError::TooShort { min_length: 8 })));
+        assert!(matches!(policy.validate("ABCDEF1!"), Err(PasswordPolicyError::MissingLowercase { .. })));
+        assert!(matches!(policy.validate("abcdef1!"), Err(PasswordPolicyError::MissingUppercase { .. })));
+        assert!(matches!(policy.validate("abcdEFG!"), Err(PasswordPolicyError::MissingDigit { .. })));
+        assert!(matches!(policy.validate("abcdEF12"), Err(PasswordPolicyError::MissingSymbol { .. })));
+        assert!(matches!(policy.validate("abc EF12"), Err(PasswordPolicyError::MissingSymbol { .. })));
+        assert!(policy.validate("abcdEF1!").is_ok());
+    }
+
+    #[test]
+    fn length_is_counted_in_characters() {
+        let policy = PasswordPolicy { min_length: 4, ..PasswordPolicy::default() };
+        assert!(policy.validate("ééé").is_err());
+        assert!(policy.validate("éééé").is_ok());
+    }
+
+    #[test]
+    fn only_the_most_recent_passwords_cannot_be_reused() {
+        let policy = PasswordPolicy { history_size: 2, ..PasswordPolicy::default() };
+        let recent_hashes = ["current", "previous", "oldest"].map(PasswordHash::from_password);
+        assert!(matches!(
+            policy.validate_reuse("current", &recent_hashes),
+            Err(PasswordPolicyError::RecentlyUsed { history_size: 2 })
+        ));
+        assert!(policy.validate_reuse("previous", &recent_hashes).is_err());
+        assert!(policy.validate_reuse("oldest", &recent_hashes).is_ok());
+        assert_eq!(policy.retained_history(), 1);
+    }
+}
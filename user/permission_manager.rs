@@ -5,6 +5,7 @@
  */
 
 use resource::constants::server::DEFAULT_USER_NAME;
+use system::concepts::{Grant, Role};
 
 pub struct PermissionManager {}
 
@@ -28,4 +29,46 @@ impl PermissionManager {
     pub fn exec_user_delete_allowed(accessor: &str, subject: &str) -> bool {
         accessor == DEFAULT_USER_NAME || accessor == subject
     }
+
+    pub fn exec_user_grants_get_permitted(accessor: &str, subject: &str) -> bool {
+        accessor == DEFAULT_USER_NAME || accessor == subject
+    }
+
+    pub fn exec_user_grants_update_permitted(accessor: &str) -> bool {
+        accessor == DEFAULT_USER_NAME
+    }
+
+    pub fn exec_user_sessions_permitted(accessor: &str, subject: &str) -> bool {
+        accessor == DEFAULT_USER_NAME || accessor == subject
+    }
+
+    pub fn exec_user_api_keys_permitted(accessor: &str, subject: &str) -> bool {
+        accessor == DEFAULT_USER_NAME || accessor == subject
+    }
+
+    pub fn exec_user_password_expire_permitted(accessor: &str) -> bool {
+        accessor == DEFAULT_USER_NAME
+    }
+
+    pub fn exec_transaction_kill_permitted(accessor: &str, owner: &str) -> bool {
+        accessor == DEFAULT_USER_NAME || accessor == owner
+    }
+
+    pub fn exec_database_access_permitted(accessor: &str, grants: &[Grant], database_name: &str, role: Role) -> bool {
+        accessor == DEFAULT_USER_NAME
+            || grants.iter().any(|grant| grant.applies_to(database_name) && grant.role.includes(role))
+    }
+
+    pub fn exec_database_create_permitted(accessor: &str, grants: &[Grant]) -> bool {
+        accessor == DEFAULT_USER_NAME
+            || grants.iter().any(|grant| grant.database.is_none() && grant.role.includes(Role::SchemaAdmin))
+    }
+
+    pub fn exec_database_delete_permitted(accessor: &str, grants: &[Grant], database_name: &str) -> bool {
+        Self::exec_database_access_permitted(accessor, grants, database_name, Role::SchemaAdmin)
+    }
+
+    pub fn exec_database_backup_permitted(accessor: &str, grants: &[Grant], database_name: &str) -> bool {
+        Self::exec_database_access_permitted(accessor, grants, database_name, Role::SchemaAdmin)
+    }
 }
@@ -6,25 +6,48 @@
 
 use std::sync::Arc;
 
-use database::Database;
+use database::{Database, transaction::DataCommitError};
 use resource::constants::server::DEFAULT_USER_NAME;
 use storage::durability_client::WALClient;
 use system::{
-    concepts::{Credential, User},
-    repositories::{user_repository, user_repository::SystemDBError},
+    concepts::{ApiKey, Credential, Grant, PasswordHash, PasswordState, Session, User},
+    repositories::{
+        api_key_repository, grant_repository, session_repository, user_repository, user_repository::SystemDBError,
+    },
     util::transaction_util::TransactionUtil,
 };
 
-use crate::errors::{UserCreateError, UserDeleteError, UserGetError, UserUpdateError};
+use crate::{
+    errors::{
+        UserApiKeyError, UserCreateError, UserDeleteError, UserGetError, UserGrantError, UserPasswordStateError,
+        UserSessionError, UserUpdateError,
+    },
+    password_policy::PasswordPolicy,
+};
 
 #[derive(Debug)]
 pub struct UserManager {
     transaction_util: TransactionUtil,
+    password_policy: PasswordPolicy,
 }
 
 impl UserManager {
+    const FAILED_ATTEMPT_COMMIT_ATTEMPTS: usize = 10;
+
     pub fn new(system_db: Arc<Database<WALClient>>) -> Self {
-        UserManager { transaction_util: TransactionUtil::new(system_db.clone()) }
+        UserManager {
+            transaction_util: TransactionUtil::new(system_db.clone()),
+            password_policy: PasswordPolicy::default(),
+        }
+    }
+
+    pub fn with_password_policy(mut self, password_policy: PasswordPolicy) -> Self {
+        self.password_policy = password_policy;
+        self
+    }
+
+    pub fn password_policy(&self) -> &PasswordPolicy {
+        &self.password_policy
     }
 
     pub fn all(&self) -> Vec<User> {
@@ -35,7 +58,9 @@ impl UserManager {
         self.transaction_util.read_transaction(|tx| {
             user_repository::get(tx, username).map_err(|query_error| match query_error {
                 SystemDBError::IllegalQueryInput { .. } => UserGetError::IllegalUsername {},
-                SystemDBError::EmptyUpdate { .. } | SystemDBError::QueryFailed { .. } => UserGetError::Unexpected {},
+                SystemDBError::EmptyUpdate { .. }
+                | SystemDBError::QueryFailed { .. }
+                | SystemDBError::UnsupportedCredential { .. } => UserGetError::Unexpected {},
             })
         })
     }
@@ -44,7 +69,15 @@ impl UserManager {
         self.get(username).map(|opt| opt.is_some())
     }
 
-    pub fn create(&self, user: &User, credential: &Credential) -> Result<(), UserCreateError> {
+    pub fn create(&self, user: &User, password: &str) -> Result<(), UserCreateError> {
+        self.password_policy
+            .validate(password)
+            .map_err(|typedb_source| UserCreateError::PasswordRejected { typedb_source })?;
+        self.create_unrestricted(user, &Credential::new_password(password))
+    }
+
+    // Creates the user without checking the credential against the password policy
+    pub fn create_unrestricted(&self, user: &User, credential: &Credential) -> Result<(), UserCreateError> {
         match self.contains(&user.name) {
             Ok(contains) => {
                 if contains {
@@ -71,7 +104,38 @@ impl UserManager {
         }
     }
 
-    pub fn update(
+    // Setting a new password also clears any lockout and the requirement to change the password
+    pub fn update(&self, username: &str, user: &Option<User>, password: Option<&str>) -> Result<(), UserUpdateError> {
+        let Some(password) = password else {
+            return self.update_credential(username, user, &None);
+        };
+        self.password_policy
+            .validate(password)
+            .map_err(|typedb_source| UserUpdateError::PasswordRejected { typedb_source })?;
+        let current = match self.get(username) {
+            Ok(Some((_, Credential::PasswordType { password_hash }))) => Some(password_hash),
+            Ok(_) => None,
+            Err(UserGetError::IllegalUsername { .. }) => return Err(UserUpdateError::IllegalUsername {}),
+            Err(UserGetError::Unexpected { .. }) => return Err(UserUpdateError::Unexpected {}),
+        };
+        let Some(current) = current else {
+            return self.update_credential(username, user, &Some(Credential::new_password(password)));
+        };
+        let state = self.password_state(username).map_err(|_| UserUpdateError::Unexpected {})?.unwrap_or_default();
+        self.password_policy
+            .validate_reuse(password, std::iter::once(&current).chain(&state.previous_hashes))
+            .map_err(|typedb_source| UserUpdateError::PasswordRejected { typedb_source })?;
+
+        self.update_credential(username, user, &Some(Credential::new_password(password)))?;
+        let previous_hashes: Vec<PasswordHash> = std::iter::once(current)
+            .chain(state.previous_hashes)
+            .take(self.password_policy.retained_history())
+            .collect();
+        let state = PasswordState { previous_hashes, ..PasswordState::default() };
+        self.update_password_state(username, &state).map_err(|_| UserUpdateError::Unexpected {})
+    }
+
+    fn update_credential(
         &self,
         username: &str,
         user: &Option<User>,
@@ -117,6 +181,9 @@ impl UserManager {
             }
         }
 
+        if self.delete_api_keys(username, None).is_err() {
+            return Err(UserDeleteError::Unexpected {});
+        }
         let delete_result = self
             .transaction_util
             .write_transaction(|snapshot, type_mgr, thing_mgr, fn_mgr, query_mgr, _db, _tx_opts| {
@@ -129,4 +196,299 @@ impl UserManager {
             Err(_) => Err(UserDeleteError::Unexpected {}),
         }
     }
+
+    pub fn grants(&self, username: &str) -> Result<Vec<Grant>, UserGrantError> {
+        self.transaction_util.read_transaction(|tx| {
+            grant_repository::list(tx, username).map_err(|query_error| match query_error {
+                SystemDBError::IllegalQueryInput { .. } => UserGrantError::IllegalInput {},
+                SystemDBError::EmptyUpdate { .. }
+                | SystemDBError::QueryFailed { .. }
+                | SystemDBError::UnsupportedCredential { .. } => UserGrantError::Unexpected {},
+            })
+        })
+    }
+
+    pub fn grant(&self, username: &str, grant: &Grant) -> Result<(), UserGrantError> {
+        self.require_user(username)?;
+        if self.grants(username)?.contains(grant) {
+            return Ok(());
+        }
+        let grant_result = self
+            .transaction_util
+            .write_transaction(|snapshot, type_mgr, thing_mgr, fn_mgr, query_mgr, _db, _tx_opts| {
+                grant_repository::create(snapshot, &type_mgr, thing_mgr.clone(), &fn_mgr, &query_mgr, username, grant)
+            })
+            .1;
+        match grant_result {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(SystemDBError::IllegalQueryInput { .. })) => Err(UserGrantError::IllegalInput {}),
+            Ok(Err(_)) | Err(_) => Err(UserGrantError::Unexpected {}),
+        }
+    }
+
+    pub fn revoke(&self, username: &str, grant: &Grant) -> Result<(), UserGrantError> {
+        self.require_user(username)?;
+        if !self.grants(username)?.contains(grant) {
+            return Err(UserGrantError::GrantNotFound {});
+        }
+        let revoke_result = self
+            .transaction_util
+            .write_transaction(|snapshot, type_mgr, thing_mgr, fn_mgr, query_mgr, _db, _tx_opts| {
+                grant_repository::delete(snapshot, &type_mgr, thing_mgr.clone(), &fn_mgr, &query_mgr, username, grant)
+            })
+            .1;
+        match revoke_result {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(SystemDBError::IllegalQueryInput { .. })) => Err(UserGrantError::IllegalInput {}),
+            Ok(Err(_)) | Err(_) => Err(UserGrantError::Unexpected {}),
+        }
+    }
+
+    pub fn sessions(&self) -> Result<Vec<Session>, UserSessionError> {
+        self.transaction_util.read_transaction(session_repository::list).map_err(|_| UserSessionError::Unexpected {})
+    }
+
+    pub fn create_session(&self, session: &Session) -> Result<(), UserSessionError> {
+        let create_result = self
+            .transaction_util
+            .write_transaction(|snapshot, type_mgr, thing_mgr, fn_mgr, query_mgr, _db, _tx_opts| {
+                session_repository::create(snapshot, &type_mgr, thing_mgr.clone(), &fn_mgr, &query_mgr, session)
+            })
+            .1;
+        Self::session_write_result(create_result)
+    }
+
+    pub fn delete_sessions(&self, username: &str, session_id: Option<&str>) -> Result<(), UserSessionError> {
+        let delete_result = self
+            .transaction_util
+            .write_transaction(|snapshot, type_mgr, thing_mgr, fn_mgr, query_mgr, _db, _tx_opts| {
+                session_repository::delete(
+                    snapshot,
+                    &type_mgr,
+                    thing_mgr.clone(),
+                    &fn_mgr,
+                    &query_mgr,
+                    username,
+                    session_id,
+                )
+            })
+            .1;
+        Self::session_write_result(delete_result)
+    }
+
+    pub fn delete_expired_sessions(&self, now: u64) -> Result<(), UserSessionError> {
+        let delete_result = self
+            .transaction_util
+            .write_transaction(|snapshot, type_mgr, thing_mgr, fn_mgr, query_mgr, _db, _tx_opts| {
+                session_repository::delete_expired(snapshot, &type_mgr, thing_mgr.clone(), &fn_mgr, &query_mgr, now)
+            })
+            .1;
+        Self::session_write_result(delete_result)
+    }
+
+    fn session_write_result(
+        result: Result<Result<(), SystemDBError>, DataCommitError>,
+    ) -> Result<(), UserSessionError> {
+        match result {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(SystemDBError::IllegalQueryInput { .. })) => Err(UserSessionError::IllegalInput {}),
+            Ok(Err(_)) | Err(_) => Err(UserSessionError::Unexpected {}),
+        }
+    }
+
+    pub fn api_keys(&self, username: &str) -> Result<Vec<ApiKey>, UserApiKeyError> {
+        self.transaction_util.read_transaction(|tx| {
+            api_key_repository::list(tx, username).map_err(|query_error| match query_error {
+                SystemDBError::IllegalQueryInput { .. } => UserApiKeyError::IllegalInput {},
+                SystemDBError::EmptyUpdate { .. }
+                | SystemDBError::QueryFailed { .. }
+                | SystemDBError::UnsupportedCredential { .. } => UserApiKeyError::Unexpected {},
+            })
+        })
+    }
+
+    // Returns the created key together with the full key string, which is not stored and cannot be retrieved later
+    pub fn create_api_key(
+        &self,
+        username: &str,
+        name: &str,
+        expires_at: Option<u64>,
+    ) -> Result<(ApiKey, String), UserApiKeyError> {
+        match self.contains(username) {
+            Ok(true) => (),
+            Ok(false) => return Err(UserApiKeyError::UserNotFound {}),
+            Err(UserGetError::IllegalUsername { .. }) => return Err(UserApiKeyError::IllegalInput {}),
+            Err(UserGetError::Unexpected { .. }) => return Err(UserApiKeyError::Unexpected {}),
+        }
+        if self.api_keys(username)?.iter().any(|api_key| api_key.name == name) {
+            return Err(UserApiKeyError::ApiKeyAlreadyExists { name: name.to_string() });
+        }
+        let (api_key, key) = ApiKey::generate(name.to_string(), expires_at);
+        let create_result = self
+            .transaction_util
+            .write_transaction(|snapshot, type_mgr, thing_mgr, fn_mgr, query_mgr, _db, _tx_opts| {
+                api_key_repository::create(
+                    snapshot,
+                    &type_mgr,
+                    thing_mgr.clone(),
+                    &fn_mgr,
+                    &query_mgr,
+                    username,
+                    &api_key,
+                )
+            })
+            .1;
+        Self::api_key_write_result(create_result).map(|()| (api_key, key))
+    }
+
+    pub fn delete_api_key(&self, username: &str, name: &str) -> Result<(), UserApiKeyError> {
+        if !self.api_keys(username)?.iter().any(|api_key| api_key.name == name) {
+            return Err(UserApiKeyError::ApiKeyNotFound { name: name.to_string() });
+        }
+        self.delete_api_keys(username, Some(name))
+    }
+
+    pub fn update_api_key_last_used(&self, id: &str, last_used_at: u64) -> Result<(), UserApiKeyError> {
+        let update_result = self
+            .transaction_util
+            .write_transaction(|snapshot, type_mgr, thing_mgr, fn_mgr, query_mgr, _db, _tx_opts| {
+                api_key_repository::update_last_used(
+                    snapshot,
+                    &type_mgr,
+                    thing_mgr.clone(),
+                    &fn_mgr,
+                    &query_mgr,
+                    id,
+                    last_used_at,
+                )
+            })
+            .1;
+        Self::api_key_write_result(update_result)
+    }
+
+    fn delete_api_keys(&self, username: &str, name: Option<&str>) -> Result<(), UserApiKeyError> {
+        let delete_result = self
+            .transaction_util
+            .write_transaction(|snapshot, type_mgr, thing_mgr, fn_mgr, query_mgr, _db, _tx_opts| {
+                api_key_repository::delete(snapshot, &type_mgr, thing_mgr.clone(), &fn_mgr, &query_mgr, username, name)
+            })
+            .1;
+        Self::api_key_write_result(delete_result)
+    }
+
+    fn api_key_write_result(result: Result<Result<(), SystemDBError>, DataCommitError>) -> Result<(), UserApiKeyError> {
+        match result {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(SystemDBError::IllegalQueryInput { .. })) => Err(UserApiKeyError::IllegalInput {}),
+            Ok(Err(_)) | Err(_) => Err(UserApiKeyError::Unexpected {}),
+        }
+    }
+
+    pub fn password_state(&self, username: &str) -> Result<Option<PasswordState>, UserPasswordStateError> {
+        self.transaction_util.read_transaction(|tx| {
+            user_repository::get_password_state(tx, username).map_err(|query_error| match query_error {
+                SystemDBError::IllegalQueryInput { .. } => UserPasswordStateError::IllegalInput {},
+                SystemDBError::EmptyUpdate { .. }
+                | SystemDBError::QueryFailed { .. }
+                | SystemDBError::UnsupportedCredential { .. } => UserPasswordStateError::Unexpected {},
+            })
+        })
+    }
+
+    pub fn update_password_state(&self, username: &str, state: &PasswordState) -> Result<(), UserPasswordStateError> {
+        let update_result = self
+            .transaction_util
+            .write_transaction(|snapshot, type_mgr, thing_mgr, fn_mgr, query_mgr, _db, _tx_opts| {
+                user_repository::update_password_state(
+                    snapshot,
+                    &type_mgr,
+                    thing_mgr.clone(),
+                    &fn_mgr,
+                    &query_mgr,
+                    username,
+                    state,
+                )
+            })
+            .1;
+        match update_result {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(SystemDBError::IllegalQueryInput { .. })) => Err(UserPasswordStateError::IllegalInput {}),
+            Ok(Err(_)) | Err(_) => Err(UserPasswordStateError::Unexpected {}),
+        }
+    }
+
+    // Returns whether this attempt locked the user out. Attempts that conflict with concurrent ones are retried, so
+    // that every failed attempt is counted.
+    pub fn record_failed_password_attempt(
+        &self,
+        username: &str,
+        max_failed_attempts: u64,
+        locked_until: u64,
+    ) -> Result<bool, UserPasswordStateError> {
+        for _ in 0..Self::FAILED_ATTEMPT_COMMIT_ATTEMPTS {
+            let record_result = self
+                .transaction_util
+                .write_transaction(|snapshot, type_mgr, thing_mgr, fn_mgr, query_mgr, _db, _tx_opts| {
+                    user_repository::record_failed_attempt(
+                        snapshot,
+                        &type_mgr,
+                        thing_mgr.clone(),
+                        &fn_mgr,
+                        &query_mgr,
+                        username,
+                        max_failed_attempts,
+                        locked_until,
+                    )
+                })
+                .1;
+            match record_result {
+                Ok(Ok(is_locked_out)) => return Ok(is_locked_out),
+                Ok(Err(SystemDBError::IllegalQueryInput { .. })) => {
+                    return Err(UserPasswordStateError::IllegalInput {});
+                }
+                Ok(Err(_)) => return Err(UserPasswordStateError::Unexpected {}),
+                Err(DataCommitError::SnapshotError { .. }) => continue,
+                Err(_) => return Err(UserPasswordStateError::Unexpected {}),
+            }
+        }
+        Err(UserPasswordStateError::Unexpected {})
+    }
+
+    pub fn reset_failed_password_attempts(&self, username: &str) -> Result<(), UserPasswordStateError> {
+        let reset_result = self
+            .transaction_util
+            .write_transaction(|snapshot, type_mgr, thing_mgr, fn_mgr, query_mgr, _db, _tx_opts| {
+                user_repository::reset_failed_attempts(
+                    snapshot,
+                    &type_mgr,
+                    thing_mgr.clone(),
+                    &fn_mgr,
+                    &query_mgr,
+                    username,
+                )
+            })
+            .1;
+        match reset_result {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(SystemDBError::IllegalQueryInput { .. })) => Err(UserPasswordStateError::IllegalInput {}),
+            Ok(Err(_)) | Err(_) => Err(UserPasswordStateError::Unexpected {}),
+        }
+    }
+
+    // Requires the user to set a new password before signing in with a password again
+    pub fn expire_password(&self, username: &str) -> Result<(), UserPasswordStateError> {
+        let Some(state) = self.password_state(username)? else {
+            return Err(UserPasswordStateError::UserNotFound {});
+        };
+        self.update_password_state(username, &PasswordState { must_change: true, ..state })
+    }
+
+    fn require_user(&self, username: &str) -> Result<(), UserGrantError> {
+        match self.contains(username) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(UserGrantError::UserNotFound {}),
+            Err(UserGetError::IllegalUsername { .. }) => Err(UserGrantError::IllegalInput {}),
+            Err(UserGetError::Unexpected { .. }) => Err(UserGrantError::Unexpected {}),
+        }
+    }
 }